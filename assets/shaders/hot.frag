@@ -0,0 +1,8 @@
+#version 450
+
+layout(location = 0) out vec4 o_Target;
+
+void main() {
+    // change this color, or make a typo, while the hot_shader_reloading example runs
+    o_Target = vec4(0.9, 0.3, 0.1, 1.0);
+}
@@ -0,0 +1,145 @@
+use crate::{Curve, Interpolate};
+use bevy_math::{Quat, Vec3};
+use bevy_transform::prelude::Transform;
+use bevy_type_registry::TypeUuid;
+
+/// The keyframed translation, rotation and scale of one joint. Parts without a curve are left as they are.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JointCurves {
+    pub translation: Option<Curve<Vec3>>,
+    pub rotation: Option<Curve<Quat>>,
+    pub scale: Option<Curve<Vec3>>,
+}
+
+impl JointCurves {
+    pub fn with_translation(mut self, translation: Curve<Vec3>) -> Self {
+        self.translation = Some(translation);
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Curve<Quat>) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Curve<Vec3>) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// The time of the last keyframe of any of the curves
+    pub fn duration(&self) -> f32 {
+        let translation = self
+            .translation
+            .as_ref()
+            .map_or(0.0, |curve| curve.duration());
+        let rotation = self.rotation.as_ref().map_or(0.0, |curve| curve.duration());
+        let scale = self.scale.as_ref().map_or(0.0, |curve| curve.duration());
+        translation.max(rotation).max(scale)
+    }
+}
+
+/// The pose of a joint blended from the [JointCurves] of several clips
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct JointPose {
+    translation: Option<(Vec3, f32)>,
+    rotation: Option<(Quat, f32)>,
+    scale: Option<(Vec3, f32)>,
+}
+
+impl JointPose {
+    /// Blends in the curves sampled at the given time, weighted by `weight` relative to the curves added before
+    pub fn add(&mut self, curves: &JointCurves, time: f32, weight: f32) {
+        if weight <= 0.0 {
+            return;
+        }
+        if let Some(translation) = curves.translation.as_ref().and_then(|c| c.sample(time)) {
+            blend(&mut self.translation, translation, weight);
+        }
+        if let Some(rotation) = curves.rotation.as_ref().and_then(|c| c.sample(time)) {
+            blend(&mut self.rotation, rotation, weight);
+        }
+        if let Some(scale) = curves.scale.as_ref().and_then(|c| c.sample(time)) {
+            blend(&mut self.scale, scale, weight);
+        }
+    }
+
+    /// Whether no curves were added
+    pub fn is_empty(&self) -> bool {
+        self.translation.is_none() && self.rotation.is_none() && self.scale.is_none()
+    }
+
+    /// Writes the blended pose into the transform. Parts that no clip animates are left as they are.
+    pub fn apply(&self, transform: &mut Transform) {
+        if let Some((translation, _)) = self.translation {
+            transform.translation = translation;
+        }
+        if let Some((rotation, _)) = self.rotation {
+            transform.rotation = rotation;
+        }
+        if let Some((scale, _)) = self.scale {
+            transform.scale = scale;
+        }
+    }
+}
+
+/// Adds a value to a weighted average, stored with the sum of the weights so far
+fn blend<T: Interpolate>(average: &mut Option<(T, f32)>, value: T, weight: f32) {
+    match average {
+        Some((average, total_weight)) => {
+            *total_weight += weight;
+            *average = average.interpolate(value, weight / *total_weight);
+        }
+        None => *average = Some((value, weight)),
+    }
+}
+
+/// An animation of a skeleton. Joint `i` of the clip animates the `i`th entity in an
+/// [AnimationPlayer](crate::AnimationPlayer)'s joints.
+#[derive(Debug, Clone, Default, PartialEq, TypeUuid)]
+#[uuid = "381db373-59b7-40e6-800d-a0b54a9d07df"]
+pub struct AnimationClip {
+    pub joints: Vec<JointCurves>,
+}
+
+impl AnimationClip {
+    pub fn new(joints: Vec<JointCurves>) -> Self {
+        AnimationClip { joints }
+    }
+
+    /// The time of the last keyframe of any joint, in seconds
+    pub fn duration(&self) -> f32 {
+        self.joints
+            .iter()
+            .map(|joint| joint.duration())
+            .fold(0.0, f32::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JointCurves, JointPose};
+    use crate::Curve;
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::Transform;
+
+    #[test]
+    fn joint_pose_blending() {
+        let curves = |x: f32| {
+            JointCurves::default().with_translation(Curve::new(vec![
+                (0.0, Vec3::zero()),
+                (1.0, Vec3::new(x, 0.0, 0.0)),
+            ]))
+        };
+        let mut pose = JointPose::default();
+        assert!(pose.is_empty());
+        pose.add(&curves(4.0), 0.5, 1.0);
+        pose.add(&curves(8.0), 1.0, 3.0);
+        pose.add(&curves(100.0), 1.0, 0.0);
+
+        let mut transform = Transform::from_scale(Vec3::splat(2.0));
+        pose.apply(&mut transform);
+        assert_eq!(transform.translation, Vec3::new(6.5, 0.0, 0.0));
+        assert_eq!(transform.scale, Vec3::splat(2.0));
+    }
+}
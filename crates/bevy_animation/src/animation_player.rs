@@ -0,0 +1,296 @@
+use crate::{AnimationClip, JointPose};
+use bevy_asset::{Assets, Handle};
+use bevy_core::{EntityNames, Time};
+use bevy_ecs::{Entity, Query, Res};
+use bevy_transform::prelude::Transform;
+
+/// A clip played by an [AnimationPlayer], and how much it contributes to the blended pose
+#[derive(Debug, Clone)]
+pub struct AnimationLayer {
+    pub clip: Handle<AnimationClip>,
+    pub weight: f32,
+    /// The weight the layer fades to during a cross-fade
+    target_weight: f32,
+    elapsed: f32,
+}
+
+impl AnimationLayer {
+    fn new(clip: Handle<AnimationClip>, weight: f32) -> Self {
+        AnimationLayer {
+            clip,
+            weight,
+            target_weight: weight,
+            elapsed: 0.0,
+        }
+    }
+
+    /// The time of the clip that is being played, in seconds
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+}
+
+/// Plays [AnimationClip]s by writing the sampled pose into the [Transform]s of the joint entities each frame.
+/// `joints[i]` is the entity animated by joint `i` of the clips. Joints without an entity, or whose entity has no
+/// [Transform], are skipped.
+///
+/// Several clips can play at once, each on its own [AnimationLayer]. Their poses are blended by weight, and
+/// [AnimationPlayer::cross_fade] fades from the current clips to another one so transitions don't snap.
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    pub joints: Vec<Entity>,
+    /// Multiplies the speed the clips are played at. Negative speeds play them backwards.
+    pub speed: f32,
+    /// Whether clips start over when they end, instead of holding their last pose
+    pub looping: bool,
+    layers: Vec<AnimationLayer>,
+    fade_duration: f32,
+    paused: bool,
+}
+
+impl AnimationPlayer {
+    pub fn new(joints: Vec<Entity>) -> Self {
+        AnimationPlayer {
+            joints,
+            speed: 1.0,
+            looping: true,
+            layers: Vec::new(),
+            fade_duration: 0.0,
+            paused: false,
+        }
+    }
+
+    /// Creates a player for the entities with the given names, `joint_names[i]` being the name of joint `i`. Returns
+    /// None if any of the names isn't found.
+    pub fn from_joint_names<'a>(
+        joint_names: impl IntoIterator<Item = &'a str>,
+        entity_names: &EntityNames,
+    ) -> Option<Self> {
+        let joints = joint_names
+            .into_iter()
+            .map(|name| entity_names.find_by_name(name))
+            .collect::<Option<Vec<_>>>()?;
+        Some(AnimationPlayer::new(joints))
+    }
+
+    /// Plays only the given clip, from its start unless it's already playing
+    pub fn play(&mut self, clip: Handle<AnimationClip>) {
+        self.layers.retain(|layer| layer.clip == clip);
+        match self.layers.first_mut() {
+            Some(layer) => {
+                layer.weight = 1.0;
+                layer.target_weight = 1.0;
+            }
+            None => self.layers.push(AnimationLayer::new(clip, 1.0)),
+        }
+        self.paused = false;
+    }
+
+    /// Plays the clip with the given weight alongside the other clips, or changes its weight if it's already playing.
+    /// A weight of 0 removes the clip.
+    pub fn blend(&mut self, clip: Handle<AnimationClip>, weight: f32) {
+        if weight <= 0.0 {
+            self.layers.retain(|layer| layer.clip != clip);
+            return;
+        }
+        match self.layers.iter_mut().find(|layer| layer.clip == clip) {
+            Some(layer) => {
+                layer.weight = weight;
+                layer.target_weight = weight;
+            }
+            None => self.layers.push(AnimationLayer::new(clip, weight)),
+        }
+        self.paused = false;
+    }
+
+    /// Fades the given clip in and all other clips out over `duration` seconds. The clip starts from its start unless
+    /// it's already playing.
+    pub fn cross_fade(&mut self, clip: Handle<AnimationClip>, duration: f32) {
+        if duration <= 0.0 {
+            self.play(clip);
+            return;
+        }
+        if !self.layers.iter().any(|layer| layer.clip == clip) {
+            self.layers.push(AnimationLayer::new(clip.clone(), 0.0));
+        }
+        for layer in self.layers.iter_mut() {
+            layer.target_weight = if layer.clip == clip { 1.0 } else { 0.0 };
+        }
+        self.fade_duration = duration;
+        self.paused = false;
+    }
+
+    pub fn layers(&self) -> &[AnimationLayer] {
+        &self.layers
+    }
+
+    /// The clip with the largest weight, which is the one being faded in during a cross-fade
+    pub fn clip(&self) -> Option<&Handle<AnimationClip>> {
+        self.strongest_layer().map(|layer| &layer.clip)
+    }
+
+    pub fn stop(&mut self) {
+        self.layers.clear();
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Jumps to the given time of all clips, in seconds
+    pub fn seek(&mut self, time: f32) {
+        for layer in self.layers.iter_mut() {
+            layer.elapsed = time;
+        }
+    }
+
+    /// The time of the clip with the largest weight, in seconds
+    pub fn elapsed(&self) -> f32 {
+        self.strongest_layer().map_or(0.0, |layer| layer.elapsed)
+    }
+
+    fn strongest_layer(&self) -> Option<&AnimationLayer> {
+        self.layers.iter().max_by(|a, b| {
+            (a.target_weight, a.weight)
+                .partial_cmp(&(b.target_weight, b.weight))
+                .unwrap()
+        })
+    }
+
+    /// Advances the playback time of each layer by `delta` seconds and moves the weights towards the end of the
+    /// cross-fade. Layers that faded out are removed. `duration` returns the duration of a loaded clip.
+    fn tick(&mut self, delta: f32, duration: impl Fn(&Handle<AnimationClip>) -> Option<f32>) {
+        if self.paused {
+            return;
+        }
+
+        let fade_step = if self.fade_duration > 0.0 {
+            delta / self.fade_duration
+        } else {
+            1.0
+        };
+        for layer in self.layers.iter_mut() {
+            if layer.weight < layer.target_weight {
+                layer.weight = (layer.weight + fade_step).min(layer.target_weight);
+            } else {
+                layer.weight = (layer.weight - fade_step).max(layer.target_weight);
+            }
+
+            let duration = if let Some(duration) = duration(&layer.clip) {
+                duration
+            } else {
+                continue;
+            };
+            layer.elapsed += delta * self.speed;
+            layer.elapsed = if duration <= 0.0 {
+                0.0
+            } else if self.looping {
+                layer.elapsed.rem_euclid(duration)
+            } else {
+                layer.elapsed.max(0.0).min(duration)
+            };
+        }
+        self.layers
+            .retain(|layer| layer.weight > 0.0 || layer.target_weight > 0.0);
+    }
+}
+
+pub fn animation_player_system(
+    time: Res<Time>,
+    clips: Res<Assets<AnimationClip>>,
+    mut player_query: Query<&mut AnimationPlayer>,
+    mut transform_query: Query<&mut Transform>,
+) {
+    for mut player in player_query.iter_mut() {
+        player.tick(time.delta_seconds, |clip| {
+            clips.get(clip).map(|clip| clip.duration())
+        });
+
+        for (joint_index, joint) in player.joints.iter().enumerate() {
+            let mut pose = JointPose::default();
+            for layer in player.layers.iter() {
+                if let Some(joint_curves) = clips
+                    .get(&layer.clip)
+                    .and_then(|clip| clip.joints.get(joint_index))
+                {
+                    pose.add(joint_curves, layer.elapsed, layer.weight);
+                }
+            }
+            if pose.is_empty() {
+                continue;
+            }
+            if let Ok(mut transform) = transform_query.get_mut(*joint) {
+                pose.apply(&mut transform);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AnimationPlayer;
+    use crate::AnimationClip;
+    use bevy_asset::{Handle, HandleId};
+    use bevy_type_registry::TypeUuid;
+
+    fn clip(id: u64) -> Handle<AnimationClip> {
+        Handle::weak(HandleId::new(AnimationClip::TYPE_UUID, id))
+    }
+
+    #[test]
+    fn animation_player_time() {
+        let mut player = AnimationPlayer::new(Vec::new());
+        player.play(clip(0));
+        player.tick(0.5, |_| Some(2.0));
+        assert_eq!(player.elapsed(), 0.5);
+        player.tick(2.0, |_| Some(2.0));
+        assert_eq!(player.elapsed(), 0.5);
+
+        player.pause();
+        player.tick(1.0, |_| Some(2.0));
+        assert_eq!(player.elapsed(), 0.5);
+        player.resume();
+
+        player.looping = false;
+        player.speed = 2.0;
+        player.tick(1.0, |_| Some(2.0));
+        assert_eq!(player.elapsed(), 2.0);
+        player.seek(1.0);
+        player.speed = -1.0;
+        player.tick(0.25, |_| Some(2.0));
+        assert_eq!(player.elapsed(), 0.75);
+    }
+
+    #[test]
+    fn cross_fade() {
+        let mut player = AnimationPlayer::new(Vec::new());
+        player.play(clip(0));
+        player.blend(clip(1), 0.5);
+        assert_eq!(player.layers().len(), 2);
+
+        player.cross_fade(clip(2), 1.0);
+        assert_eq!(player.clip(), Some(&clip(2)));
+        player.tick(0.25, |_| Some(2.0));
+        let weights = player
+            .layers()
+            .iter()
+            .map(|layer| layer.weight)
+            .collect::<Vec<_>>();
+        assert_eq!(weights, vec![0.75, 0.25, 0.25]);
+
+        player.tick(0.5, |_| Some(2.0));
+        assert_eq!(player.layers().len(), 2);
+        player.tick(0.5, |_| Some(2.0));
+        assert_eq!(player.layers().len(), 1);
+        assert_eq!(player.layers()[0].weight, 1.0);
+        assert_eq!(player.layers()[0].elapsed(), 1.25);
+    }
+}
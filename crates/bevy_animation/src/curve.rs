@@ -0,0 +1,103 @@
+use bevy_math::{Quat, Vec3};
+
+/// A value that can be interpolated between two keyframes
+pub trait Interpolate: Copy {
+    fn interpolate(self, other: Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Vec3 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl Interpolate for Quat {
+    /// Normalized linear interpolation, which takes the shortest path between the rotations
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+/// A keyframe of a [Curve]: a value at a time, in seconds from the start of the clip
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+}
+
+/// Values that are linearly interpolated between keyframes. Before the first and after the last keyframe, the curve
+/// holds the value of that keyframe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve<T> {
+    keyframes: Vec<Keyframe<T>>,
+}
+
+impl<T: Interpolate> Curve<T> {
+    /// Creates a curve from `(time, value)` pairs, which don't need to be sorted
+    pub fn new(keyframes: impl IntoIterator<Item = (f32, T)>) -> Self {
+        let mut keyframes = keyframes
+            .into_iter()
+            .map(|(time, value)| Keyframe { time, value })
+            .collect::<Vec<_>>();
+        keyframes.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+        Curve { keyframes }
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+
+    /// The time of the last keyframe
+    pub fn duration(&self) -> f32 {
+        self.keyframes
+            .last()
+            .map(|keyframe| keyframe.time)
+            .unwrap_or(0.0)
+    }
+
+    /// Returns the value at the given time, or None if the curve has no keyframes
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let next = self
+            .keyframes
+            .iter()
+            .position(|keyframe| keyframe.time > time);
+        match next {
+            None => self.keyframes.last().map(|keyframe| keyframe.value),
+            Some(0) => Some(self.keyframes[0].value),
+            Some(next) => {
+                let a = &self.keyframes[next - 1];
+                let b = &self.keyframes[next];
+                let t = (time - a.time) / (b.time - a.time);
+                Some(a.value.interpolate(b.value, t))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Curve;
+    use bevy_math::Vec3;
+
+    #[test]
+    fn curve_sampling() {
+        let curve = Curve::new(vec![
+            (1.0, Vec3::new(2.0, 0.0, 0.0)),
+            (0.0, Vec3::zero()),
+            (2.0, Vec3::new(2.0, 4.0, 0.0)),
+        ]);
+        assert_eq!(curve.duration(), 2.0);
+        assert_eq!(curve.sample(-1.0), Some(Vec3::zero()));
+        assert_eq!(curve.sample(0.5), Some(Vec3::new(1.0, 0.0, 0.0)));
+        assert_eq!(curve.sample(1.0), Some(Vec3::new(2.0, 0.0, 0.0)));
+        assert_eq!(curve.sample(1.25), Some(Vec3::new(2.0, 1.0, 0.0)));
+        assert_eq!(curve.sample(3.0), Some(Vec3::new(2.0, 4.0, 0.0)));
+        assert_eq!(Curve::<f32>::new(vec![]).sample(0.0), None);
+    }
+}
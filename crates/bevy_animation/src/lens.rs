@@ -0,0 +1,151 @@
+use crate::{Interpolate, Lens};
+use bevy_math::{Quat, Vec3};
+use bevy_transform::prelude::Transform;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformPositionLens {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl Lens<Transform> for TransformPositionLens {
+    fn lerp(&mut self, target: &mut Transform, ratio: f32) {
+        target.translation = self.start.interpolate(self.end, ratio);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformRotationLens {
+    pub start: Quat,
+    pub end: Quat,
+}
+
+impl Lens<Transform> for TransformRotationLens {
+    fn lerp(&mut self, target: &mut Transform, ratio: f32) {
+        target.rotation = self.start.interpolate(self.end, ratio);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformScaleLens {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl Lens<Transform> for TransformScaleLens {
+    fn lerp(&mut self, target: &mut Transform, ratio: f32) {
+        target.scale = self.start.interpolate(self.end, ratio);
+    }
+}
+
+#[cfg(feature = "render")]
+mod render {
+    use crate::{Interpolate, Lens};
+    use bevy_math::{Rect, Size};
+    use bevy_pbr::prelude::StandardMaterial;
+    use bevy_render::color::Color;
+    use bevy_sprite::ColorMaterial;
+    use bevy_ui::{Style, Val};
+
+    impl Interpolate for Color {
+        /// Colors are mixed in linear space
+        fn interpolate(self, other: Self, t: f32) -> Self {
+            self.lerp(other, t)
+        }
+    }
+
+    impl Interpolate for Val {
+        /// Values with different units can't be interpolated, so they switch to `other` at the end
+        fn interpolate(self, other: Self, t: f32) -> Self {
+            match (self, other) {
+                (Val::Px(a), Val::Px(b)) => Val::Px(a.interpolate(b, t)),
+                (Val::Percent(a), Val::Percent(b)) => Val::Percent(a.interpolate(b, t)),
+                _ if t >= 1.0 => other,
+                _ => self,
+            }
+        }
+    }
+
+    /// Animates the color of a sprite or UI node material. Use it with a material that isn't shared, unless all
+    /// users of the material should change color.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct ColorMaterialColorLens {
+        pub start: Color,
+        pub end: Color,
+    }
+
+    impl Lens<ColorMaterial> for ColorMaterialColorLens {
+        fn lerp(&mut self, target: &mut ColorMaterial, ratio: f32) {
+            target.color = self.start.interpolate(self.end, ratio);
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct StandardMaterialAlbedoLens {
+        pub start: Color,
+        pub end: Color,
+    }
+
+    impl Lens<StandardMaterial> for StandardMaterialAlbedoLens {
+        fn lerp(&mut self, target: &mut StandardMaterial, ratio: f32) {
+            target.albedo = self.start.interpolate(self.end, ratio);
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct UiPositionLens {
+        pub start: Rect<Val>,
+        pub end: Rect<Val>,
+    }
+
+    impl Lens<Style> for UiPositionLens {
+        fn lerp(&mut self, target: &mut Style, ratio: f32) {
+            target.position = Rect {
+                left: self.start.left.interpolate(self.end.left, ratio),
+                right: self.start.right.interpolate(self.end.right, ratio),
+                top: self.start.top.interpolate(self.end.top, ratio),
+                bottom: self.start.bottom.interpolate(self.end.bottom, ratio),
+            };
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct UiSizeLens {
+        pub start: Size<Val>,
+        pub end: Size<Val>,
+    }
+
+    impl Lens<Style> for UiSizeLens {
+        fn lerp(&mut self, target: &mut Style, ratio: f32) {
+            target.size = Size::new(
+                self.start.width.interpolate(self.end.width, ratio),
+                self.start.height.interpolate(self.end.height, ratio),
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::Interpolate;
+        use bevy_render::color::Color;
+        use bevy_ui::Val;
+
+        #[test]
+        fn color_and_val_interpolation() {
+            let color =
+                Color::rgba(0.0, 0.2, 1.0, 0.0).interpolate(Color::rgba(1.0, 0.2, 0.0, 1.0), 0.5);
+            for (value, expected) in [color.r_linear(), color.g(), color.b_linear(), color.a()]
+                .iter()
+                .zip([0.5, 0.2, 0.5, 0.5].iter())
+            {
+                assert!((value - expected).abs() < 1e-5);
+            }
+            assert_eq!(Val::Px(10.0).interpolate(Val::Px(20.0), 0.5), Val::Px(15.0));
+            assert_eq!(Val::Auto.interpolate(Val::Px(20.0), 0.5), Val::Auto);
+            assert_eq!(Val::Auto.interpolate(Val::Px(20.0), 1.0), Val::Px(20.0));
+        }
+    }
+}
+
+#[cfg(feature = "render")]
+pub use render::*;
@@ -0,0 +1,53 @@
+mod animation_clip;
+mod animation_player;
+mod curve;
+mod lens;
+mod tween;
+
+pub use animation_clip::*;
+pub use animation_player::*;
+pub use curve::*;
+pub use lens::*;
+pub use tween::*;
+
+pub mod prelude {
+    pub use crate::{
+        AnimationClip, AnimationPlayer, Curve, EaseFunction, JointCurves, Tween, TweenCompleted,
+        TweenRepeat,
+    };
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::AddAsset;
+use bevy_ecs::IntoQuerySystem;
+use bevy_transform::prelude::Transform;
+
+/// Adds support for keyframed and blended joint animations, and tweens of transforms, to an App. With the `render`
+/// feature, material colors and UI styles can be tweened as well.
+#[derive(Default)]
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        // the tweens run in UPDATE, before the UI is laid out, and the animation player before the transforms are
+        // propagated
+        app.add_asset::<AnimationClip>()
+            .add_event::<TweenCompleted>()
+            .add_system_to_stage_front(stage::POST_UPDATE, animation_player_system.system())
+            .add_system_to_stage(stage::UPDATE, component_tween_system::<Transform>.system());
+
+        #[cfg(feature = "render")]
+        app.add_system_to_stage(
+            stage::UPDATE,
+            asset_tween_system::<bevy_sprite::ColorMaterial>.system(),
+        )
+        .add_system_to_stage(
+            stage::UPDATE,
+            asset_tween_system::<bevy_pbr::prelude::StandardMaterial>.system(),
+        )
+        .add_system_to_stage(
+            stage::UPDATE,
+            component_tween_system::<bevy_ui::Style>.system(),
+        );
+    }
+}
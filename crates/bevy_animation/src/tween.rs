@@ -0,0 +1,424 @@
+use bevy_app::Events;
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_core::Time;
+use bevy_ecs::{Component, Entity, Query, Res, ResMut};
+use std::f32::consts::PI;
+
+/// Maps the linear progress of a [Tween] to the ratio that is passed to its [Lens]. See <https://easings.net> for
+/// what the curves look like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EaseFunction {
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExponentialIn,
+    ExponentialOut,
+    /// Overshoots the end slightly before settling
+    BackOut,
+    /// Springs around the end before settling
+    ElasticOut,
+    BounceOut,
+}
+
+impl Default for EaseFunction {
+    fn default() -> Self {
+        EaseFunction::Linear
+    }
+}
+
+impl EaseFunction {
+    /// Returns the eased ratio for a progress between 0 and 1. The ratio is 0 at the start and 1 at the end, but may
+    /// leave that range in between.
+    pub fn sample(self, t: f32) -> f32 {
+        match self {
+            EaseFunction::Linear => t,
+            EaseFunction::QuadraticIn => t * t,
+            EaseFunction::QuadraticOut => t * (2.0 - t),
+            EaseFunction::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(2) / 2.0
+                }
+            }
+            EaseFunction::CubicIn => t * t * t,
+            EaseFunction::CubicOut => 1.0 - (1.0 - t).powi(3),
+            EaseFunction::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+            EaseFunction::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            EaseFunction::SineOut => (t * PI / 2.0).sin(),
+            EaseFunction::SineInOut => (1.0 - (t * PI).cos()) / 2.0,
+            EaseFunction::ExponentialIn => {
+                if t <= 0.0 {
+                    0.0
+                } else {
+                    2.0f32.powf(10.0 * t - 10.0)
+                }
+            }
+            EaseFunction::ExponentialOut => {
+                if t >= 1.0 {
+                    1.0
+                } else {
+                    1.0 - 2.0f32.powf(-10.0 * t)
+                }
+            }
+            EaseFunction::BackOut => {
+                const OVERSHOOT: f32 = 1.70158;
+                let t = t - 1.0;
+                1.0 + (OVERSHOOT + 1.0) * t * t * t + OVERSHOOT * t * t
+            }
+            EaseFunction::ElasticOut => {
+                if t <= 0.0 {
+                    0.0
+                } else if t >= 1.0 {
+                    1.0
+                } else {
+                    2.0f32.powf(-10.0 * t) * ((10.0 * t - 0.75) * 2.0 * PI / 3.0).sin() + 1.0
+                }
+            }
+            EaseFunction::BounceOut => {
+                const SCALE: f32 = 7.5625;
+                const WIDTH: f32 = 2.75;
+                if t < 1.0 / WIDTH {
+                    SCALE * t * t
+                } else if t < 2.0 / WIDTH {
+                    let t = t - 1.5 / WIDTH;
+                    SCALE * t * t + 0.75
+                } else if t < 2.5 / WIDTH {
+                    let t = t - 2.25 / WIDTH;
+                    SCALE * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / WIDTH;
+                    SCALE * t * t + 0.984375
+                }
+            }
+        }
+    }
+}
+
+/// How many times a [Tween] plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TweenRepeat {
+    Once,
+    /// Plays the given number of times in total
+    Times(u32),
+    Forever,
+}
+
+impl Default for TweenRepeat {
+    fn default() -> Self {
+        TweenRepeat::Once
+    }
+}
+
+impl TweenRepeat {
+    fn count(self) -> Option<u32> {
+        match self {
+            TweenRepeat::Once => Some(1),
+            TweenRepeat::Times(count) => Some(count.max(1)),
+            TweenRepeat::Forever => None,
+        }
+    }
+}
+
+/// Animates part of a value, like the translation of a [Transform](bevy_transform::prelude::Transform) or the
+/// color of a material, between a start and an end
+pub trait Lens<T>: Send + Sync + 'static {
+    /// Sets the animated part of `target` to its value at `ratio`, which is 0 at the start and 1 at the end
+    fn lerp(&mut self, target: &mut T, ratio: f32);
+}
+
+/// Sent when a [Tween] has played all of its repetitions. Tweens that repeat forever never complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TweenCompleted {
+    pub entity: Entity,
+    pub user_data: u64,
+}
+
+/// Animates a component `T` of its entity, or the asset `T` of its entity's `Handle<T>`, along an easing curve.
+/// The tween systems of the [AnimationPlugin](crate::AnimationPlugin) drive transforms and, with the `render`
+/// feature, material colors and UI styles. Other types need a [component_tween_system] or [asset_tween_system].
+///
+/// An entity can have one tween per animated type. The tween stays on the entity when it completes, and can be
+/// restarted with [Tween::reset].
+pub struct Tween<T> {
+    pub easing: EaseFunction,
+    /// The duration of one repetition, in seconds
+    pub duration: f32,
+    /// The time before the first repetition starts, in seconds
+    pub delay: f32,
+    pub repeat: TweenRepeat,
+    /// Whether every other repetition plays backwards, so a repeating tween goes back and forth
+    pub mirrored: bool,
+    /// Passed on in the [TweenCompleted] event, to tell apart the tweens of an entity
+    pub user_data: u64,
+    lens: Box<dyn Lens<T>>,
+    elapsed: f32,
+    completed: bool,
+}
+
+impl<T> Tween<T> {
+    pub fn new(easing: EaseFunction, duration: f32, lens: impl Lens<T>) -> Self {
+        Tween {
+            easing,
+            duration,
+            delay: 0.0,
+            repeat: TweenRepeat::Once,
+            mirrored: false,
+            user_data: 0,
+            lens: Box::new(lens),
+            elapsed: 0.0,
+            completed: false,
+        }
+    }
+
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn with_repeat(mut self, repeat: TweenRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn with_mirrored(mut self, mirrored: bool) -> Self {
+        self.mirrored = mirrored;
+        self
+    }
+
+    pub fn with_user_data(mut self, user_data: u64) -> Self {
+        self.user_data = user_data;
+        self
+    }
+
+    /// The time since the tween started, including the delay, in seconds
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completed
+    }
+
+    /// Plays the tween again from the start, delay included
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.completed = false;
+    }
+
+    /// Advances the tween by `delta` seconds and returns the eased ratio to apply, or None while it waits for its
+    /// delay or after it completed
+    fn tick(&mut self, delta: f32) -> Option<f32> {
+        if self.completed {
+            return None;
+        }
+        self.elapsed += delta;
+        let time = self.elapsed - self.delay;
+        if time < 0.0 {
+            return None;
+        }
+
+        let repetitions = if self.duration > 0.0 {
+            time / self.duration
+        } else {
+            f32::INFINITY
+        };
+        let (repetition, progress) = match self.repeat.count() {
+            Some(count) if repetitions >= count as f32 => {
+                self.completed = true;
+                (count - 1, 1.0)
+            }
+            None if self.duration <= 0.0 => {
+                self.completed = true;
+                (0, 1.0)
+            }
+            _ => (repetitions as u32, repetitions.fract()),
+        };
+        let progress = if self.mirrored && repetition % 2 == 1 {
+            1.0 - progress
+        } else {
+            progress
+        };
+        Some(self.easing.sample(progress))
+    }
+}
+
+/// Drives the [Tween]s of the component `T`
+pub fn component_tween_system<T: Component>(
+    time: Res<Time>,
+    mut completed_events: ResMut<Events<TweenCompleted>>,
+    mut query: Query<(Entity, &mut Tween<T>, &mut T)>,
+) {
+    for (entity, mut tween, mut target) in query.iter_mut() {
+        if let Some(ratio) = tween.tick(time.delta_seconds) {
+            tween.lens.lerp(&mut target, ratio);
+            if tween.completed {
+                completed_events.send(TweenCompleted {
+                    entity,
+                    user_data: tween.user_data,
+                });
+            }
+        }
+    }
+}
+
+/// Drives the [Tween]s of the asset `T`. Entities that share a handle animate the same asset.
+pub fn asset_tween_system<T: Asset>(
+    time: Res<Time>,
+    mut completed_events: ResMut<Events<TweenCompleted>>,
+    mut assets: ResMut<Assets<T>>,
+    mut query: Query<(Entity, &mut Tween<T>, &Handle<T>)>,
+) {
+    for (entity, mut tween, handle) in query.iter_mut() {
+        // the tween waits for its asset to load. get_mut marks the asset as modified, so it's only called when the
+        // tween has a new value to apply
+        if !assets.contains(handle) {
+            continue;
+        }
+        if let Some(ratio) = tween.tick(time.delta_seconds) {
+            tween.lens.lerp(assets.get_mut(handle).unwrap(), ratio);
+            if tween.completed {
+                completed_events.send(TweenCompleted {
+                    entity,
+                    user_data: tween.user_data,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{asset_tween_system, EaseFunction, Lens, Tween, TweenCompleted, TweenRepeat};
+    use bevy_app::{prelude::*, Events};
+    use bevy_asset::{AddAsset, AssetEvent, AssetPlugin, Assets};
+    use bevy_core::{DefaultTaskPoolOptions, Time};
+    use bevy_ecs::IntoQuerySystem;
+    use bevy_type_registry::{TypeRegistry, TypeUuid};
+
+    struct ValueLens;
+
+    impl Lens<f32> for ValueLens {
+        fn lerp(&mut self, target: &mut f32, ratio: f32) {
+            *target = ratio;
+        }
+    }
+
+    #[derive(TypeUuid)]
+    #[uuid = "0e3b7c56-84c2-4ab1-9d5f-3f7a0f2c6e91"]
+    struct Opacity(f32);
+
+    struct OpacityLens;
+
+    impl Lens<Opacity> for OpacityLens {
+        fn lerp(&mut self, target: &mut Opacity, ratio: f32) {
+            target.0 = ratio;
+        }
+    }
+
+    #[test]
+    fn ease_functions() {
+        let functions = [
+            EaseFunction::Linear,
+            EaseFunction::QuadraticIn,
+            EaseFunction::QuadraticOut,
+            EaseFunction::QuadraticInOut,
+            EaseFunction::CubicIn,
+            EaseFunction::CubicOut,
+            EaseFunction::CubicInOut,
+            EaseFunction::SineIn,
+            EaseFunction::SineOut,
+            EaseFunction::SineInOut,
+            EaseFunction::ExponentialIn,
+            EaseFunction::ExponentialOut,
+            EaseFunction::BackOut,
+            EaseFunction::ElasticOut,
+            EaseFunction::BounceOut,
+        ];
+        for function in functions.iter() {
+            assert!(function.sample(0.0).abs() < 1e-3, "{:?}", function);
+            assert!((function.sample(1.0) - 1.0).abs() < 1e-3, "{:?}", function);
+        }
+        assert_eq!(EaseFunction::QuadraticIn.sample(0.5), 0.25);
+        assert_eq!(EaseFunction::CubicInOut.sample(0.5), 0.5);
+        assert!(EaseFunction::BackOut.sample(0.8) > 1.0);
+    }
+
+    #[test]
+    fn tween_timing() {
+        let mut tween = Tween::new(EaseFunction::Linear, 2.0, ValueLens)
+            .with_delay(1.0)
+            .with_repeat(TweenRepeat::Times(2))
+            .with_mirrored(true);
+        assert_eq!(tween.tick(0.5), None);
+        assert_eq!(tween.tick(1.0), Some(0.25));
+        assert_eq!(tween.tick(2.0), Some(0.75));
+        assert!(!tween.is_completed());
+        assert_eq!(tween.tick(2.0), Some(0.0));
+        assert!(tween.is_completed());
+        assert_eq!(tween.tick(1.0), None);
+
+        tween.reset();
+        tween.delay = 0.0;
+        tween.repeat = TweenRepeat::Forever;
+        assert_eq!(tween.tick(5.0), Some(0.5));
+        assert!(!tween.is_completed());
+
+        let mut tween = Tween::new(EaseFunction::Linear, 0.0, ValueLens);
+        assert_eq!(tween.tick(0.0), Some(1.0));
+        assert!(tween.is_completed());
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    #[test]
+    fn asset_tweens_only_modify_their_asset_when_they_apply_a_value() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .init_resource::<Time>()
+            .add_plugin(AssetPlugin)
+            .add_event::<TweenCompleted>()
+            .add_asset::<Opacity>()
+            .add_system(asset_tween_system::<Opacity>.system());
+        let handle = app
+            .resources_mut()
+            .get_mut::<Assets<Opacity>>()
+            .unwrap()
+            .add(Opacity(0.0));
+        app.app.world.spawn((
+            handle.clone(),
+            Tween::new(EaseFunction::Linear, 2.0, OpacityLens).with_delay(1.0),
+        ));
+
+        let modified = |app: &mut AppBuilder, delta_seconds: f32| {
+            app.resources_mut().get_mut::<Time>().unwrap().delta_seconds = delta_seconds;
+            app.app.update();
+            let events = app
+                .resources()
+                .get::<Events<AssetEvent<Opacity>>>()
+                .unwrap();
+            let modified = events
+                .iter_current_update_events()
+                .any(|event| matches!(event, AssetEvent::Modified { .. }));
+            modified
+        };
+        // waiting for the delay
+        assert!(!modified(&mut app, 0.5));
+        assert!(modified(&mut app, 1.5));
+        let assets = app.resources().get::<Assets<Opacity>>().unwrap();
+        assert_eq!(assets.get(&handle).unwrap().0, 0.5);
+    }
+}
@@ -75,6 +75,16 @@ impl App {
         );
     }
 
+    /// Drops the app's schedules, world and resources. Backends that own OS or GPU resources (ex: windows, the
+    /// renderer and audio output) release them when dropped, so runners call this when the app exits instead of
+    /// relying on the process ending.
+    pub fn teardown(&mut self) {
+        self.schedule = Schedule::default();
+        self.startup_schedule = Schedule::default();
+        self.world = World::default();
+        self.resources = Resources::default();
+    }
+
     pub fn run(mut self) {
         self.executor.initialize(&mut self.resources);
         let runner = std::mem::replace(&mut self.runner, Box::new(run_once));
@@ -82,6 +92,7 @@ impl App {
     }
 }
 
-/// An event that indicates the app should exit. This will fully exit the app process.
+/// An event that indicates the app should exit. The runner stops updating the app and [tears it down](App::teardown)
+/// before the process exits.
 #[derive(Debug, Clone)]
 pub struct AppExit;
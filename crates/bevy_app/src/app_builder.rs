@@ -129,6 +129,17 @@ impl AppBuilder {
         self
     }
 
+    pub fn add_startup_system_to_stage_front(
+        &mut self,
+        stage_name: &'static str,
+        system: Box<dyn System>,
+    ) -> &mut Self {
+        self.app
+            .startup_schedule
+            .add_system_to_stage_front(stage_name, system);
+        self
+    }
+
     pub fn add_startup_systems_to_stage(
         &mut self,
         stage_name: &'static str,
@@ -258,6 +269,14 @@ impl AppBuilder {
         self
     }
 
+    /// Runs the systems of each stage one at a time in the order they were added, so updates are deterministic. See
+    /// [ParallelExecutor::set_sequential](bevy_ecs::ParallelExecutor::set_sequential).
+    pub fn set_sequential_execution(&mut self, sequential: bool) -> &mut Self {
+        self.app.executor.set_sequential(sequential);
+        self.app.startup_executor.set_sequential(sequential);
+        self
+    }
+
     pub fn set_runner(&mut self, run_fn: impl Fn(App) + 'static) -> &mut Self {
         self.app.runner = Box::new(run_fn);
         self
@@ -291,3 +310,61 @@ impl AppBuilder {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{startup_stage, App};
+    use bevy_ecs::prelude::*;
+
+    struct Settings(u32);
+
+    #[test]
+    fn startup_stages_run_in_order() {
+        fn load_settings(mut commands: Commands) {
+            commands.insert_resource(Settings(1));
+        }
+
+        fn use_settings(mut settings: ResMut<Settings>) {
+            settings.0 += 1;
+        }
+
+        fn check_settings(settings: Res<Settings>, mut ran: ResMut<bool>) {
+            assert_eq!(settings.0, 2);
+            *ran = true;
+        }
+
+        // systems are registered in reverse order to ensure stage order (not registration order) is respected
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(false)
+            .add_startup_system_to_stage(startup_stage::POST_STARTUP, check_settings.system())
+            .add_startup_system(use_settings.system())
+            .add_startup_system_to_stage(startup_stage::PRE_STARTUP, load_settings.system());
+
+        app_builder.app.initialize();
+        assert!(*app_builder.resources().get::<bool>().unwrap());
+    }
+
+    #[test]
+    fn startup_system_added_to_stage_front_runs_first() {
+        fn added_first(mut order: ResMut<Vec<&'static str>>) {
+            order.push("added_first");
+        }
+
+        fn added_to_front(mut order: ResMut<Vec<&'static str>>) {
+            order.push("added_to_front");
+        }
+
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(Vec::<&'static str>::new())
+            .add_startup_system(added_first.system())
+            .add_startup_system_to_stage_front(startup_stage::STARTUP, added_to_front.system());
+
+        app_builder.app.initialize();
+        assert_eq!(
+            *app_builder.resources().get::<Vec<&'static str>>().unwrap(),
+            vec!["added_to_front", "added_first"]
+        );
+    }
+}
@@ -72,6 +72,17 @@ impl PluginGroupBuilder {
         self
     }
 
+    /// Replaces a plugin in the group with the given instance, keeping its position in the group. Useful for
+    /// configuring a plugin that is part of a group (ex: `group.set(WindowPlugin { exit_on_close: false, .. })`).
+    pub fn set<T: Plugin>(&mut self, plugin: T) -> &mut Self {
+        let plugin_entry = self
+            .plugins
+            .get_mut(&TypeId::of::<T>())
+            .expect("Cannot set a plugin that does not exist");
+        plugin_entry.plugin = Box::new(plugin);
+        self
+    }
+
     pub fn enable<T: Plugin>(&mut self) -> &mut Self {
         let mut plugin_entry = self
             .plugins
@@ -107,6 +107,7 @@ impl Plugin for ScheduleRunnerPlugin {
                                 thread::sleep(delay);
                             }
                         }
+                        app.teardown();
                     }
 
                     #[cfg(target_arch = "wasm32")]
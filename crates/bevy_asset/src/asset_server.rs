@@ -1,8 +1,8 @@
 use crate::{
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetReader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext,
+    LoadState, RefChange, RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::Res;
@@ -129,11 +129,25 @@ impl AssetServer {
         &self,
         path: P,
     ) -> Result<Arc<Box<dyn AssetLoader>>, AssetServerError> {
-        path.as_ref()
-            .extension()
-            .and_then(|e| e.to_str())
-            .ok_or(AssetServerError::MissingAssetLoader(None))
-            .and_then(|extension| self.get_asset_loader(extension))
+        let file_name = path
+            .as_ref()
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or(AssetServerError::MissingAssetLoader(None))?;
+        // try the longest extension first, so loaders can claim compound extensions like "atlas.json"
+        let mut result = Err(AssetServerError::MissingAssetLoader(None));
+        for (index, _) in file_name.match_indices('.') {
+            result = self.get_asset_loader(&file_name[index + 1..]);
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Opens an asset to be read while it's used instead of loading it, see [AssetIo::open_path]
+    pub fn open_path<P: AsRef<Path>>(&self, path: P) -> Result<Box<dyn AssetReader>, AssetIoError> {
+        self.server.asset_io.open_path(path.as_ref())
     }
 
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
@@ -437,3 +451,57 @@ impl AssetServer {
 pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
     asset_server.free_unused_assets();
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::AssetServer;
+    use crate::{AssetLoader, FileAssetIo, LoadContext};
+    use bevy_tasks::TaskPool;
+    use bevy_utils::BoxedFuture;
+
+    struct TestLoader(&'static [&'static str]);
+
+    impl AssetLoader for TestLoader {
+        fn load<'a>(
+            &'a self,
+            _bytes: &'a [u8],
+            _load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, anyhow::Result<()>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            self.0
+        }
+    }
+
+    #[test]
+    fn compound_extensions_take_precedence() {
+        let asset_server = AssetServer::new(FileAssetIo::new("."), TaskPool::new());
+        asset_server.add_loader(TestLoader(&["ron"]));
+        asset_server.add_loader(TestLoader(&["scn.ron"]));
+
+        let extensions = |path: &str| {
+            asset_server.get_path_asset_loader(path).ok().map(|loader| {
+                loader
+                    .extensions()
+                    .iter()
+                    .map(|extension| extension.to_string())
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            extensions("scenes/level.scn.ron"),
+            Some(vec!["scn.ron".to_string()])
+        );
+        assert_eq!(extensions("settings.ron"), Some(vec!["ron".to_string()]));
+        // only the file name is matched, not dots in directory names
+        assert_eq!(
+            extensions("my.scn/settings.ron"),
+            Some(vec!["ron".to_string()])
+        );
+        assert_eq!(extensions("archive.tar.ron"), Some(vec!["ron".to_string()]));
+        assert_eq!(extensions("texture.png"), None);
+        assert_eq!(extensions("README"), None);
+    }
+}
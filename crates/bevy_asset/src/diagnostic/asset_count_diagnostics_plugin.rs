@@ -0,0 +1,78 @@
+use crate::{Asset, Assets};
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::{IntoQuerySystem, Res, ResMut};
+use std::{any::type_name, marker::PhantomData};
+
+/// Adds "asset count" and "asset memory" diagnostics for the assets of type `T` to an App. Counts that keep growing
+/// point to assets that are never unloaded.
+pub struct AssetCountDiagnosticsPlugin<T: Asset> {
+    memory_estimate: fn(&T) -> usize,
+    marker: PhantomData<T>,
+}
+
+/// Estimates the memory used by an asset, see [AssetCountDiagnosticsPlugin::with_memory_estimate]
+struct AssetMemoryEstimate<T: Asset>(fn(&T) -> usize);
+
+impl<T: Asset> Default for AssetCountDiagnosticsPlugin<T> {
+    fn default() -> Self {
+        AssetCountDiagnosticsPlugin {
+            memory_estimate: |_| std::mem::size_of::<T>(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset> Plugin for AssetCountDiagnosticsPlugin<T> {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_resource(AssetMemoryEstimate(self.memory_estimate))
+            .add_startup_system(Self::setup_system.system())
+            .add_system(Self::diagnostic_system.system());
+    }
+}
+
+impl<T: Asset> AssetCountDiagnosticsPlugin<T> {
+    /// Estimates the memory used by each asset with a function, instead of the size of `T`. Assets that own their data,
+    /// like textures and meshes, should count the data they own.
+    pub fn with_memory_estimate(memory_estimate: fn(&T) -> usize) -> Self {
+        AssetCountDiagnosticsPlugin {
+            memory_estimate,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn diagnostic_id() -> DiagnosticId {
+        DiagnosticId(T::TYPE_UUID)
+    }
+
+    pub fn memory_diagnostic_id() -> DiagnosticId {
+        // derived from the asset's uuid so each asset type gets its own id
+        DiagnosticId::from_u128(T::TYPE_UUID.as_u128() ^ 0x4451_e1f9_6bd0_4a3c_9d26_7a8c_2f10_b5e7)
+    }
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::diagnostic_id(),
+            &format!("asset_count/{}", type_name::<T>()),
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::memory_diagnostic_id(),
+            &format!("asset_memory/{}", type_name::<T>()),
+            20,
+        ));
+    }
+
+    fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        memory_estimate: Res<AssetMemoryEstimate<T>>,
+        assets: Res<Assets<T>>,
+    ) {
+        diagnostics.add_measurement(Self::diagnostic_id(), assets.len() as f64);
+        let memory = assets
+            .iter()
+            .map(|(_, asset)| (memory_estimate.0)(asset))
+            .sum::<usize>();
+        diagnostics.add_measurement(Self::memory_diagnostic_id(), memory as f64);
+    }
+}
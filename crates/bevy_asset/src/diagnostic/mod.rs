@@ -0,0 +1,2 @@
+mod asset_count_diagnostics_plugin;
+pub use asset_count_diagnostics_plugin::AssetCountDiagnosticsPlugin;
@@ -1,4 +1,6 @@
-use crate::{filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetServer};
+use crate::{
+    filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetReader, AssetServer,
+};
 use anyhow::Result;
 use bevy_ecs::{bevy_utils::BoxedFuture, Res};
 use bevy_utils::HashSet;
@@ -7,7 +9,8 @@ use fs::File;
 use io::Read;
 use parking_lot::RwLock;
 use std::{
-    env, fs, io,
+    env, fs,
+    io::{self, BufReader},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -61,6 +64,16 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    fn open_path(&self, path: &Path) -> Result<Box<dyn AssetReader>, AssetIoError> {
+        match File::open(self.root_path.join(path)) {
+            Ok(file) => Ok(Box::new(BufReader::new(file))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(AssetIoError::NotFound(path.to_owned()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -30,11 +30,24 @@ pub enum AssetIoError {
     Io(#[from] io::Error),
     #[error("Failed to watch path")]
     PathWatchError(PathBuf),
+    #[error("Assets can't be streamed on this platform")]
+    StreamingNotSupported(PathBuf),
 }
 
+/// A reader of an asset that is read while it's used, see [AssetIo::open_path]
+pub trait AssetReader: io::Read + io::Seek + Send + Sync {}
+
+impl<T> AssetReader for T where T: io::Read + io::Seek + Send + Sync {}
+
 /// Handles load requests from an AssetServer
 pub trait AssetIo: Downcast + Send + Sync + 'static {
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
+    /// Opens a path to be read while it's used instead of being loaded at once, for large assets like music. By
+    /// default the whole file is loaded first, blocking until it is.
+    fn open_path(&self, path: &Path) -> Result<Box<dyn AssetReader>, AssetIoError> {
+        let bytes = futures_lite::future::block_on(self.load_path(path))?;
+        Ok(Box::new(io::Cursor::new(bytes)))
+    }
     fn read_directory(
         &self,
         path: &Path,
@@ -1,4 +1,4 @@
-use crate::{AssetIo, AssetIoError};
+use crate::{AssetIo, AssetIoError, AssetReader};
 use anyhow::Result;
 use bevy_ecs::bevy_utils::BoxedFuture;
 use js_sys::Uint8Array;
@@ -34,6 +34,11 @@ impl AssetIo for WasmAssetIo {
         })
     }
 
+    fn open_path(&self, path: &Path) -> Result<Box<dyn AssetReader>, AssetIoError> {
+        // fetch can't be waited on without yielding to the browser
+        Err(AssetIoError::StreamingNotSupported(path.to_owned()))
+    }
+
     fn read_directory(
         &self,
         _path: &Path,
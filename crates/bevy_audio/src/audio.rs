@@ -1,14 +1,21 @@
-use crate::{AudioSource, Decodable};
+use crate::{AudioChannel, AudioSource, Decodable};
 use bevy_asset::Handle;
 use parking_lot::RwLock;
-use std::{collections::VecDeque, fmt};
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 /// The external struct used to play audio
 pub struct Audio<P = AudioSource>
 where
     P: Decodable,
 {
-    pub queue: RwLock<VecDeque<Handle<P>>>,
+    pub(crate) queue: RwLock<VecDeque<(Handle<P>, PlaybackSettings, AudioInstance)>>,
 }
 
 impl<P> fmt::Debug for Audio<P>
@@ -37,7 +44,169 @@ where
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    pub fn play(&self, audio_source: Handle<P>) {
-        self.queue.write().push_front(audio_source);
+    /// Plays the audio source once at full volume. The returned [AudioInstance] controls the playback.
+    pub fn play(&self, audio_source: Handle<P>) -> AudioInstance {
+        self.play_with_settings(audio_source, PlaybackSettings::ONCE)
+    }
+
+    /// Plays the audio source with the given settings. The returned [AudioInstance] controls the playback.
+    pub fn play_with_settings(
+        &self,
+        audio_source: Handle<P>,
+        settings: PlaybackSettings,
+    ) -> AudioInstance {
+        let instance = AudioInstance::new(&settings);
+        self.queue
+            .write()
+            .push_front((audio_source, settings, instance.clone()));
+        instance
+    }
+}
+
+/// How an audio source is played, see [Audio::play_with_settings]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackSettings {
+    /// Whether the audio starts paused, to be started with [AudioInstance::resume]
+    pub paused: bool,
+    pub volume: f32,
+    /// How fast the audio is played. 2.0 plays it twice as fast and an octave higher.
+    pub speed: f32,
+    /// Whether the audio starts over when it ends, until it is stopped
+    pub looping: bool,
+    /// Whether the audio is mixed down to mono and panned by a [SpatialAudioEmitter](crate::SpatialAudioEmitter)
+    pub spatial: bool,
+    /// The channel whose settings in [AudioChannels](crate::AudioChannels) apply to the audio
+    pub channel: AudioChannel,
+}
+
+impl PlaybackSettings {
+    /// Plays the audio once
+    pub const ONCE: PlaybackSettings = PlaybackSettings {
+        paused: false,
+        volume: 1.0,
+        speed: 1.0,
+        looping: false,
+        spatial: false,
+        channel: AudioChannel::DEFAULT,
+    };
+
+    /// Plays the audio over and over until it is stopped
+    pub const LOOP: PlaybackSettings = PlaybackSettings {
+        looping: true,
+        ..PlaybackSettings::ONCE
+    };
+
+    pub fn paused(mut self) -> Self {
+        self.paused = true;
+        self
+    }
+
+    pub fn spatial(mut self) -> Self {
+        self.spatial = true;
+        self
+    }
+
+    pub fn in_channel(mut self, channel: AudioChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+impl Default for PlaybackSettings {
+    fn default() -> Self {
+        PlaybackSettings::ONCE
+    }
+}
+
+/// Controls a playing audio source. Clones control the same playback. Changes apply when [AudioOutput](crate::AudioOutput)
+/// updates in the `POST_UPDATE` stage, and the audio keeps playing when the instance is dropped.
+#[derive(Debug, Clone)]
+pub struct AudioInstance {
+    channel: AudioChannel,
+    state: Arc<AudioInstanceState>,
+}
+
+#[derive(Debug)]
+struct AudioInstanceState {
+    paused: AtomicBool,
+    stopped: AtomicBool,
+    finished: AtomicBool,
+    volume: RwLock<f32>,
+    channel_volumes: RwLock<[f32; 2]>,
+}
+
+impl AudioInstance {
+    pub(crate) fn new(settings: &PlaybackSettings) -> Self {
+        AudioInstance {
+            channel: settings.channel,
+            state: Arc::new(AudioInstanceState {
+                paused: AtomicBool::new(settings.paused),
+                stopped: AtomicBool::new(false),
+                finished: AtomicBool::new(false),
+                volume: RwLock::new(settings.volume),
+                channel_volumes: RwLock::new([1.0, 1.0]),
+            }),
+        }
+    }
+
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::Relaxed)
+    }
+
+    /// Stops the audio for good. Stopped audio can't be resumed, play the source again instead.
+    pub fn stop(&self) {
+        self.state.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the audio was stopped or played to its end
+    pub fn is_finished(&self) -> bool {
+        self.is_stopped() || self.state.finished.load(Ordering::Relaxed)
+    }
+
+    pub fn volume(&self) -> f32 {
+        *self.state.volume.read()
+    }
+
+    pub fn set_volume(&self, volume: f32) {
+        *self.state.volume.write() = volume;
+    }
+
+    pub fn channel(&self) -> AudioChannel {
+        self.channel
+    }
+
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.state.stopped.load(Ordering::Relaxed)
+    }
+
+    /// The volumes of the left and right channels of spatial audio
+    pub(crate) fn channel_volumes(&self) -> [f32; 2] {
+        *self.state.channel_volumes.read()
+    }
+
+    pub(crate) fn set_channel_volumes(&self, channel_volumes: [f32; 2]) {
+        *self.state.channel_volumes.write() = channel_volumes;
+    }
+
+    pub(crate) fn finish(&self) {
+        self.state.finished.store(true, Ordering::Relaxed);
     }
 }
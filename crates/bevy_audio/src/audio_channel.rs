@@ -0,0 +1,199 @@
+use bevy_utils::HashMap;
+use rodio::Source;
+use std::{fmt, sync::Arc};
+
+/// A named group of audio instances that share a volume, like music, sound effects or voices. Audio is played in a
+/// channel with [PlaybackSettings::in_channel](crate::PlaybackSettings::in_channel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioChannel(pub &'static str);
+
+impl AudioChannel {
+    /// The channel audio is played in unless another one is chosen
+    pub const DEFAULT: AudioChannel = AudioChannel("default");
+    pub const MUSIC: AudioChannel = AudioChannel("music");
+    pub const SFX: AudioChannel = AudioChannel("sfx");
+    pub const VOICE: AudioChannel = AudioChannel("voice");
+}
+
+impl Default for AudioChannel {
+    fn default() -> Self {
+        AudioChannel::DEFAULT
+    }
+}
+
+/// The samples an [AudioEffect] adapts
+pub type EffectSource = Box<dyn Source<Item = f32> + Send>;
+
+/// Adapts the samples of every instance played in an [AudioChannel], for example to filter them or add reverb with
+/// rodio's [Source] adapters. It is applied when an instance starts playing, and again each time looping audio starts
+/// over, so changing it doesn't affect audio that is already playing.
+#[derive(Clone)]
+pub struct AudioEffect(Arc<dyn Fn(EffectSource) -> EffectSource + Send + Sync>);
+
+impl AudioEffect {
+    pub fn new(effect: impl Fn(EffectSource) -> EffectSource + Send + Sync + 'static) -> Self {
+        AudioEffect(Arc::new(effect))
+    }
+
+    pub fn apply(&self, source: EffectSource) -> EffectSource {
+        (self.0)(source)
+    }
+}
+
+impl fmt::Debug for AudioEffect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AudioEffect").finish()
+    }
+}
+
+/// The settings of an [AudioChannel], applied on top of the settings of each instance in the channel
+#[derive(Debug, Clone)]
+pub struct AudioChannelSettings {
+    pub volume: f32,
+    pub muted: bool,
+    pub effect: Option<AudioEffect>,
+}
+
+impl Default for AudioChannelSettings {
+    fn default() -> Self {
+        AudioChannelSettings {
+            volume: 1.0,
+            muted: false,
+            effect: None,
+        }
+    }
+}
+
+/// The settings of every [AudioChannel]. Channels that weren't configured play at full volume.
+#[derive(Debug)]
+pub struct AudioChannels {
+    channels: HashMap<AudioChannel, AudioChannelSettings>,
+    /// Multiplies the volume of every channel
+    pub master_volume: f32,
+}
+
+impl Default for AudioChannels {
+    fn default() -> Self {
+        AudioChannels {
+            channels: Default::default(),
+            master_volume: 1.0,
+        }
+    }
+}
+
+impl AudioChannels {
+    pub fn get(&self, channel: AudioChannel) -> Option<&AudioChannelSettings> {
+        self.channels.get(&channel)
+    }
+
+    pub fn get_mut(&mut self, channel: AudioChannel) -> &mut AudioChannelSettings {
+        self.channels.entry(channel).or_default()
+    }
+
+    pub fn set_volume(&mut self, channel: AudioChannel, volume: f32) {
+        self.get_mut(channel).volume = volume;
+    }
+
+    pub fn set_muted(&mut self, channel: AudioChannel, muted: bool) {
+        self.get_mut(channel).muted = muted;
+    }
+
+    pub fn set_effect(&mut self, channel: AudioChannel, effect: Option<AudioEffect>) {
+        self.get_mut(channel).effect = effect;
+    }
+
+    /// The effect of the channel, if it has one
+    pub fn effect(&self, channel: AudioChannel) -> Option<&AudioEffect> {
+        self.channels
+            .get(&channel)
+            .and_then(|settings| settings.effect.as_ref())
+    }
+
+    /// The factor the volume of instances in the channel is multiplied by
+    pub fn gain(&self, channel: AudioChannel) -> f32 {
+        match self.channels.get(&channel) {
+            Some(settings) if settings.muted => 0.0,
+            Some(settings) => settings.volume * self.master_volume,
+            None => self.master_volume,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioChannel, AudioChannels, AudioEffect, EffectSource};
+    use rodio::Source;
+    use std::time::Duration;
+
+    struct Samples(Vec<f32>);
+
+    impl Iterator for Samples {
+        type Item = f32;
+
+        fn next(&mut self) -> Option<f32> {
+            if self.0.is_empty() {
+                None
+            } else {
+                Some(self.0.remove(0))
+            }
+        }
+    }
+
+    impl Source for Samples {
+        fn current_frame_len(&self) -> Option<usize> {
+            Some(self.0.len())
+        }
+
+        fn channels(&self) -> u16 {
+            1
+        }
+
+        fn sample_rate(&self) -> u32 {
+            44100
+        }
+
+        fn total_duration(&self) -> Option<Duration> {
+            None
+        }
+    }
+
+    #[test]
+    fn channel_gain() {
+        let mut channels = AudioChannels::default();
+        assert_eq!(channels.gain(AudioChannel::MUSIC), 1.0);
+
+        channels.set_volume(AudioChannel::MUSIC, 0.5);
+        channels.master_volume = 0.5;
+        assert_eq!(channels.gain(AudioChannel::MUSIC), 0.25);
+        assert_eq!(channels.gain(AudioChannel::SFX), 0.5);
+
+        channels.set_muted(AudioChannel::MUSIC, true);
+        assert_eq!(channels.gain(AudioChannel::MUSIC), 0.0);
+        assert_eq!(channels.get(AudioChannel::MUSIC).unwrap().volume, 0.5);
+    }
+
+    #[test]
+    fn channel_effects() {
+        let mut channels = AudioChannels::default();
+        assert!(channels.effect(AudioChannel::SFX).is_none());
+
+        // an effect that reverses the samples it gets
+        channels.set_effect(
+            AudioChannel::SFX,
+            Some(AudioEffect::new(|source: EffectSource| {
+                let mut samples = source.collect::<Vec<_>>();
+                samples.reverse();
+                Box::new(Samples(samples))
+            })),
+        );
+        let source = channels
+            .effect(AudioChannel::SFX)
+            .unwrap()
+            .apply(Box::new(Samples(vec![0.25, 0.5])));
+        assert_eq!(source.collect::<Vec<_>>(), vec![0.5, 0.25]);
+        assert!(channels.effect(AudioChannel::MUSIC).is_none());
+
+        channels.set_effect(AudioChannel::SFX, None);
+        assert!(channels.effect(AudioChannel::SFX).is_none());
+    }
+}
@@ -1,16 +1,48 @@
-use crate::{Audio, AudioSource, Decodable};
+use crate::{Audio, AudioChannels, AudioInstance, AudioSource, Decodable, PlaybackSettings};
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::{Resources, World};
-use rodio::{OutputStream, OutputStreamHandle, Sink};
-use std::marker::PhantomData;
+use rodio::{source::ChannelVolume, OutputStream, OutputStreamHandle, Sample, Sink, Source};
+use std::{marker::PhantomData, time::Duration};
 
-/// Used internally to play audio on the current "audio device"
+/// The audio device that every [AudioOutput] plays on. It is opened once, when the [AudioPlugin](crate::AudioPlugin) is
+/// added. If there is no device, or it can't be opened, the error is logged and audio finishes without playing.
+pub struct AudioDevice {
+    _stream: Option<OutputStream>,
+    stream_handle: Option<OutputStreamHandle>,
+}
+
+impl Default for AudioDevice {
+    fn default() -> Self {
+        match OutputStream::try_default() {
+            Ok((stream, stream_handle)) => AudioDevice {
+                _stream: Some(stream),
+                stream_handle: Some(stream_handle),
+            },
+            Err(err) => {
+                log::error!("Failed to open the audio device, audio won't play: {}", err);
+                AudioDevice {
+                    _stream: None,
+                    stream_handle: None,
+                }
+            }
+        }
+    }
+}
+
+struct PlayingSink<P> {
+    instance: AudioInstance,
+    sink: Sink,
+    settings: PlaybackSettings,
+    /// Decoded again before the sink runs dry, so looping audio keeps streaming instead of being buffered in memory
+    looping_source: Option<P>,
+}
+
+/// Used internally to play audio on the [AudioDevice]
 pub struct AudioOutput<P = AudioSource>
 where
     P: Decodable,
 {
-    _stream: OutputStream,
-    stream_handle: OutputStreamHandle,
+    sinks: Vec<PlayingSink<P>>,
     phantom: PhantomData<P>,
 }
 
@@ -19,11 +51,8 @@ where
     P: Decodable,
 {
     fn default() -> Self {
-        let (stream, stream_handle) = OutputStream::try_default().unwrap();
-
         Self {
-            _stream: stream,
-            stream_handle,
+            sinks: Vec::new(),
             phantom: PhantomData,
         }
     }
@@ -31,44 +60,210 @@ where
 
 impl<P> AudioOutput<P>
 where
-    P: Asset + Decodable,
+    P: Asset + Decodable + Clone,
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    fn play_source(&self, audio_source: &P) {
-        let sink = Sink::try_new(&self.stream_handle).unwrap();
-        sink.append(audio_source.decoder());
-        sink.detach();
+    fn play_source(
+        &mut self,
+        device: &AudioDevice,
+        audio_source: &P,
+        settings: PlaybackSettings,
+        instance: AudioInstance,
+        channels: &AudioChannels,
+    ) {
+        if instance.is_stopped() {
+            return;
+        }
+        let sink = match device.stream_handle.as_ref().map(Sink::try_new) {
+            Some(Ok(sink)) => sink,
+            Some(Err(err)) => {
+                log::warn!("Failed to play audio: {}", err);
+                instance.finish();
+                return;
+            }
+            None => {
+                instance.finish();
+                return;
+            }
+        };
+        if !append_decoder(&sink, audio_source, &settings, &instance, channels) {
+            instance.finish();
+            return;
+        }
+        if instance.is_paused() {
+            sink.pause();
+        }
+        sink.set_volume(instance.volume() * channels.gain(instance.channel()));
+        self.sinks.push(PlayingSink {
+            instance,
+            sink,
+            settings,
+            looping_source: if settings.looping {
+                Some(audio_source.clone())
+            } else {
+                None
+            },
+        });
     }
 
-    fn try_play_queued(&self, audio_sources: &Assets<P>, audio: &mut Audio<P>) {
+    fn try_play_queued(
+        &mut self,
+        device: &AudioDevice,
+        audio_sources: &Assets<P>,
+        audio: &mut Audio<P>,
+        channels: &AudioChannels,
+    ) {
         let mut queue = audio.queue.write();
         let len = queue.len();
         let mut i = 0;
         while i < len {
-            let audio_source_handle = queue.pop_back().unwrap();
+            let (audio_source_handle, settings, instance) = queue.pop_back().unwrap();
             if let Some(audio_source) = audio_sources.get(&audio_source_handle) {
-                self.play_source(audio_source);
+                self.play_source(device, audio_source, settings, instance, channels);
             } else {
                 // audio source hasn't loaded yet. add it back to the queue
-                queue.push_front(audio_source_handle);
+                queue.push_front((audio_source_handle, settings, instance));
             }
             i += 1;
         }
     }
+
+    /// Applies the changes made through [AudioInstance]s to their sinks, and drops the sinks that are done playing
+    fn update_sinks(&mut self, channels: &AudioChannels) {
+        self.sinks.retain(|playing| {
+            let PlayingSink {
+                instance,
+                sink,
+                settings,
+                looping_source,
+            } = playing;
+            if instance.is_stopped() {
+                sink.stop();
+                return false;
+            }
+            // keep the next loop queued behind the one that is playing, so there is no gap between them
+            if let Some(audio_source) = looping_source {
+                if sink.len() < 2
+                    && !append_decoder(sink, audio_source, settings, instance, channels)
+                {
+                    instance.finish();
+                    return false;
+                }
+            }
+            if sink.empty() {
+                instance.finish();
+                return false;
+            }
+            if instance.is_paused() != sink.is_paused() {
+                if instance.is_paused() {
+                    sink.pause();
+                } else {
+                    sink.play();
+                }
+            }
+            let volume = instance.volume() * channels.gain(instance.channel());
+            if volume != sink.volume() {
+                sink.set_volume(volume);
+            }
+            true
+        });
+    }
+}
+
+/// Opens a new decoder of `audio_source` and queues it on the sink. Returns false if it couldn't be decoded.
+fn append_decoder<P>(
+    sink: &Sink,
+    audio_source: &P,
+    settings: &PlaybackSettings,
+    instance: &AudioInstance,
+    channels: &AudioChannels,
+) -> bool
+where
+    P: Decodable,
+    <P as Decodable>::Decoder: rodio::Source + Send + Sync,
+    <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
+{
+    match audio_source.decoder() {
+        Ok(decoder) => {
+            let source = decoder.speed(settings.speed);
+            if let Some(effect) = channels.effect(instance.channel()) {
+                let source = effect.apply(Box::new(source.convert_samples::<f32>()));
+                append_source(sink, source, settings, instance);
+            } else {
+                append_source(sink, source, settings, instance);
+            }
+            true
+        }
+        Err(err) => {
+            log::warn!("Failed to decode audio: {}", err);
+            false
+        }
+    }
+}
+
+fn append_source<S>(sink: &Sink, source: S, settings: &PlaybackSettings, instance: &AudioInstance)
+where
+    S: Source + Send + 'static,
+    S::Item: Sample + Send,
+{
+    if settings.spatial {
+        let instance = instance.clone();
+        let source = ChannelVolume::new(source, instance.channel_volumes().to_vec())
+            .periodic_access(Duration::from_millis(5), move |source| {
+                let [left, right] = instance.channel_volumes();
+                source.set_volume(0, left);
+                source.set_volume(1, right);
+            });
+        sink.append(source);
+    } else {
+        sink.append(source);
+    }
 }
 
 /// Plays audio currently queued in the [Audio] resource through the [AudioOutput] resource
 pub fn play_queued_audio_system<P: Asset>(_world: &mut World, resources: &mut Resources)
 where
-    P: Decodable,
+    P: Decodable + Clone,
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    let audio_output = resources.get_thread_local::<AudioOutput<P>>().unwrap();
+    let device = resources.get_thread_local::<AudioDevice>().unwrap();
+    let mut audio_output = resources.get_thread_local_mut::<AudioOutput<P>>().unwrap();
     let mut audio = resources.get_mut::<Audio<P>>().unwrap();
+    let channels = resources.get::<AudioChannels>().unwrap();
 
     if let Some(audio_sources) = resources.get::<Assets<P>>() {
-        audio_output.try_play_queued(&*audio_sources, &mut *audio);
+        audio_output.try_play_queued(&device, &*audio_sources, &mut *audio, &channels);
+    }
+    audio_output.update_sinks(&channels);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioDevice, AudioOutput};
+    use crate::{AudioChannels, AudioInstance, AudioSource, PlaybackSettings};
+    use std::sync::Arc;
+
+    #[test]
+    fn audio_finishes_without_a_device() {
+        let device = AudioDevice {
+            _stream: None,
+            stream_handle: None,
+        };
+        let mut audio_output = AudioOutput::<AudioSource>::default();
+        let audio_source = AudioSource {
+            bytes: Arc::new([]),
+        };
+        let instance = AudioInstance::new(&PlaybackSettings::LOOP);
+        audio_output.play_source(
+            &device,
+            &audio_source,
+            PlaybackSettings::LOOP,
+            instance.clone(),
+            &AudioChannels::default(),
+        );
+        assert!(instance.is_finished());
+        assert!(audio_output.sinks.is_empty());
     }
 }
@@ -3,6 +3,7 @@ use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_type_registry::TypeUuid;
 use bevy_utils::BoxedFuture;
 use std::{io::Cursor, sync::Arc};
+use thiserror::Error;
 
 /// A source of audio data
 #[derive(Debug, Clone, TypeUuid)]
@@ -17,20 +18,103 @@ impl AsRef<[u8]> for AudioSource {
     }
 }
 
-/// Loads mp3 files as [AudioSource] [Assets](bevy_asset::Assets)
+/// The audio file formats that can be decoded. Each one has to be enabled with the cargo feature of the same name:
+/// `wav`, `flac`, `vorbis` or `mp3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Flac,
+    Vorbis,
+    Mp3,
+}
+
+impl AudioFormat {
+    pub fn from_extension(extension: &str) -> Option<AudioFormat> {
+        match extension.to_ascii_lowercase().as_str() {
+            "wav" => Some(AudioFormat::Wav),
+            "flac" => Some(AudioFormat::Flac),
+            "ogg" | "oga" => Some(AudioFormat::Vorbis),
+            "mp3" => Some(AudioFormat::Mp3),
+            _ => None,
+        }
+    }
+
+    /// Recognizes the format from the first bytes of a file
+    pub fn from_magic_bytes(bytes: &[u8]) -> Option<AudioFormat> {
+        if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
+            Some(AudioFormat::Wav)
+        } else if bytes.starts_with(b"fLaC") {
+            Some(AudioFormat::Flac)
+        } else if bytes.starts_with(b"OggS") {
+            Some(AudioFormat::Vorbis)
+        } else if bytes.starts_with(b"ID3")
+            || (bytes.len() >= 2 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0)
+        {
+            Some(AudioFormat::Mp3)
+        } else {
+            None
+        }
+    }
+
+    /// The cargo feature that enables decoding this format
+    pub fn feature(self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Vorbis => "vorbis",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+
+    pub fn is_enabled(self) -> bool {
+        match self {
+            AudioFormat::Wav => cfg!(feature = "wav"),
+            AudioFormat::Flac => cfg!(feature = "flac"),
+            AudioFormat::Vorbis => cfg!(feature = "vorbis"),
+            AudioFormat::Mp3 => cfg!(feature = "mp3"),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum AudioLoaderError {
+    #[error("Unknown audio format")]
+    UnknownFormat,
+    #[error("Decoding {0:?} audio requires the `{}` feature", .0.feature())]
+    FormatNotEnabled(AudioFormat),
+}
+
+/// Loads wav, flac, ogg vorbis and mp3 files as [AudioSource] [Assets](bevy_asset::Assets). The format is recognized
+/// from the first bytes of the file, or from its extension if they don't match a known format.
 #[derive(Default)]
-pub struct Mp3Loader;
+pub struct AudioLoader;
+
+impl AssetLoader for AudioLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let format = AudioFormat::from_magic_bytes(bytes)
+                .or_else(|| {
+                    let extension = load_context.path().extension()?.to_str()?;
+                    AudioFormat::from_extension(extension)
+                })
+                .ok_or(AudioLoaderError::UnknownFormat)?;
+            if !format.is_enabled() {
+                return Err(AudioLoaderError::FormatNotEnabled(format).into());
+            }
 
-impl AssetLoader for Mp3Loader {
-    fn load(&self, bytes: &[u8], load_context: &mut LoadContext) -> BoxedFuture<Result<()>> {
-        load_context.set_default_asset(LoadedAsset::new(AudioSource {
-            bytes: bytes.into(),
-        }));
-        Box::pin(async move { Ok(()) })
+            load_context.set_default_asset(LoadedAsset::new(AudioSource {
+                bytes: bytes.into(),
+            }));
+            Ok(())
+        })
     }
 
     fn extensions(&self) -> &[&str] {
-        static EXTENSIONS: &[&str] = &["mp3", "flac", "wav", "ogg"];
+        static EXTENSIONS: &[&str] = &["wav", "flac", "ogg", "oga", "mp3"];
         EXTENSIONS
     }
 }
@@ -38,13 +122,48 @@ impl AssetLoader for Mp3Loader {
 pub trait Decodable: Send + Sync + 'static {
     type Decoder;
 
-    fn decoder(&self) -> Self::Decoder;
+    fn decoder(&self) -> Result<Self::Decoder>;
 }
 
 impl Decodable for AudioSource {
     type Decoder = rodio::Decoder<Cursor<AudioSource>>;
 
-    fn decoder(&self) -> Self::Decoder {
-        rodio::Decoder::new(Cursor::new(self.clone())).unwrap()
+    fn decoder(&self) -> Result<Self::Decoder> {
+        Ok(rodio::Decoder::new(Cursor::new(self.clone()))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AudioFormat;
+
+    #[test]
+    fn audio_format_detection() {
+        assert_eq!(
+            AudioFormat::from_magic_bytes(b"RIFF\x24\x08\x00\x00WAVEfmt "),
+            Some(AudioFormat::Wav)
+        );
+        assert_eq!(
+            AudioFormat::from_magic_bytes(b"fLaC\x00\x00\x00\x22"),
+            Some(AudioFormat::Flac)
+        );
+        assert_eq!(
+            AudioFormat::from_magic_bytes(b"OggS\x00\x02"),
+            Some(AudioFormat::Vorbis)
+        );
+        assert_eq!(
+            AudioFormat::from_magic_bytes(b"ID3\x03\x00"),
+            Some(AudioFormat::Mp3)
+        );
+        assert_eq!(
+            AudioFormat::from_magic_bytes(&[0xFF, 0xFB, 0x90, 0x64]),
+            Some(AudioFormat::Mp3)
+        );
+        assert_eq!(AudioFormat::from_magic_bytes(b"RIFF"), None);
+        assert_eq!(
+            AudioFormat::from_extension("OGG"),
+            Some(AudioFormat::Vorbis)
+        );
+        assert_eq!(AudioFormat::from_extension("aiff"), None);
     }
 }
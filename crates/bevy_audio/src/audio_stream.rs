@@ -0,0 +1,50 @@
+use crate::Decodable;
+use anyhow::Result;
+use bevy_asset::{AssetReader, AssetServer};
+use bevy_type_registry::TypeUuid;
+use std::path::PathBuf;
+
+/// Audio that is read and decoded from the asset folder while it plays, instead of being loaded into memory like an
+/// [AudioSource](crate::AudioSource). Use it for long tracks like background music, and play it with
+/// `Audio<AudioStream>`. Platforms that can't stream assets load the whole file when it starts playing.
+#[derive(Clone, TypeUuid)]
+#[uuid = "412b6b32-0c1e-4d92-8539-4cfd8b9f4c7c"]
+pub struct AudioStream {
+    pub path: PathBuf,
+    asset_server: AssetServer,
+}
+
+impl AudioStream {
+    /// Streams a file through the [AssetServer], with a path like the ones passed to
+    /// [AssetServer::load](bevy_asset::AssetServer::load)
+    pub fn new<P: Into<PathBuf>>(asset_server: &AssetServer, path: P) -> Self {
+        AudioStream {
+            path: path.into(),
+            asset_server: asset_server.clone(),
+        }
+    }
+}
+
+impl Decodable for AudioStream {
+    type Decoder = rodio::Decoder<Box<dyn AssetReader>>;
+
+    fn decoder(&self) -> Result<Self::Decoder> {
+        let reader = self.asset_server.open_path(&self.path)?;
+        Ok(rodio::Decoder::new(reader)?)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), not(target_os = "android")))]
+mod tests {
+    use super::AudioStream;
+    use crate::Decodable;
+    use bevy_asset::{AssetServer, FileAssetIo};
+
+    #[test]
+    fn missing_file_fails_to_decode() {
+        let asset_server =
+            AssetServer::new(FileAssetIo::new(std::env::temp_dir()), Default::default());
+        let stream = AudioStream::new(&asset_server, "bevy_audio_stream_test_missing.ogg");
+        assert!(stream.decoder().is_err());
+    }
+}
@@ -1,18 +1,27 @@
 mod audio;
+mod audio_channel;
 mod audio_output;
 mod audio_source;
+mod audio_stream;
+mod spatial;
 
 pub use audio::*;
+pub use audio_channel::*;
 pub use audio_output::*;
 pub use audio_source::*;
+pub use audio_stream::*;
+pub use spatial::*;
 
 pub mod prelude {
-    pub use crate::{Audio, AudioOutput, AudioSource, Decodable};
+    pub use crate::{
+        Audio, AudioChannel, AudioChannels, AudioEffect, AudioInstance, AudioListener, AudioOutput,
+        AudioSource, AudioStream, Decodable, PlaybackSettings, SpatialAudioEmitter,
+    };
 }
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
-use bevy_ecs::IntoThreadLocalSystem;
+use bevy_ecs::{IntoQuerySystem, IntoThreadLocalSystem};
 
 /// Adds support for audio playback to an App
 #[derive(Default)]
@@ -20,13 +29,23 @@ pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.init_thread_local_resource::<AudioOutput<AudioSource>>()
+        app.init_thread_local_resource::<AudioDevice>()
+            .init_thread_local_resource::<AudioOutput<AudioSource>>()
+            .init_thread_local_resource::<AudioOutput<AudioStream>>()
             .add_asset::<AudioSource>()
-            .init_asset_loader::<Mp3Loader>()
+            .add_asset::<AudioStream>()
+            .init_asset_loader::<AudioLoader>()
             .init_resource::<Audio<AudioSource>>()
+            .init_resource::<Audio<AudioStream>>()
+            .init_resource::<AudioChannels>()
+            .add_system_to_stage(stage::POST_UPDATE, spatial_audio_system.system())
             .add_system_to_stage(
                 stage::POST_UPDATE,
                 play_queued_audio_system::<AudioSource>.thread_local_system(),
+            )
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                play_queued_audio_system::<AudioStream>.thread_local_system(),
             );
     }
 }
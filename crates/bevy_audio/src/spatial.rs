@@ -0,0 +1,100 @@
+use crate::AudioInstance;
+use bevy_ecs::Query;
+use bevy_math::Vec3;
+use bevy_transform::components::GlobalTransform;
+
+/// Hears [SpatialAudioEmitter]s from the entity's position, with its right ear towards its local x axis. Only the
+/// first listener is used.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AudioListener;
+
+/// Plays audio from the entity's position. The volume and stereo panning of the instance follow the distance and
+/// direction to the [AudioListener] every frame.
+///
+/// The instance has to be played with [PlaybackSettings::spatial](crate::PlaybackSettings::spatial), which mixes it
+/// down to mono before it is panned.
+#[derive(Debug, Clone)]
+pub struct SpatialAudioEmitter {
+    pub instance: AudioInstance,
+    /// The distance at which the audio fades out completely
+    pub range: f32,
+}
+
+impl SpatialAudioEmitter {
+    pub fn new(instance: AudioInstance, range: f32) -> Self {
+        SpatialAudioEmitter { instance, range }
+    }
+}
+
+/// The volumes of the left and right channels of audio played at `emitter_position`
+pub fn spatial_channel_volumes(
+    listener: &GlobalTransform,
+    emitter_position: Vec3,
+    range: f32,
+) -> [f32; 2] {
+    let offset = emitter_position - listener.translation;
+    let distance = offset.length();
+    let attenuation = (1.0 - distance / range).max(0.0);
+    let attenuation = attenuation * attenuation;
+    if distance <= std::f32::EPSILON {
+        return [attenuation, attenuation];
+    }
+
+    // -1.0 when the emitter is to the left of the listener, 1.0 when it is to the right
+    let right = listener.rotation * Vec3::unit_x();
+    let pan = offset.dot(right) / distance;
+    [
+        attenuation * (1.0 - pan).min(1.0),
+        attenuation * (1.0 + pan).min(1.0),
+    ]
+}
+
+pub fn spatial_audio_system(
+    listener_query: Query<(&AudioListener, &GlobalTransform)>,
+    emitter_query: Query<(&SpatialAudioEmitter, &GlobalTransform)>,
+) {
+    let listener = if let Some((_, listener)) = listener_query.iter().next() {
+        listener
+    } else {
+        return;
+    };
+
+    for (emitter, global_transform) in emitter_query.iter() {
+        emitter
+            .instance
+            .set_channel_volumes(spatial_channel_volumes(
+                listener,
+                global_transform.translation,
+                emitter.range,
+            ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::spatial_channel_volumes;
+    use bevy_math::{Quat, Vec3};
+    use bevy_transform::components::GlobalTransform;
+
+    #[test]
+    fn spatial_volumes() {
+        let listener = GlobalTransform::from_translation(Vec3::new(10.0, 0.0, 0.0));
+        assert_eq!(
+            spatial_channel_volumes(&listener, Vec3::new(10.0, 0.0, -50.0), 100.0),
+            [0.25, 0.25]
+        );
+        assert_eq!(
+            spatial_channel_volumes(&listener, Vec3::new(60.0, 0.0, 0.0), 100.0),
+            [0.0, 0.25]
+        );
+        assert_eq!(
+            spatial_channel_volumes(&listener, Vec3::new(-90.0, 0.0, 0.0), 100.0),
+            [0.0, 0.0]
+        );
+
+        // turned around, the listener hears the emitter with its left ear
+        let listener = GlobalTransform::from_rotation(Quat::from_rotation_y(std::f32::consts::PI));
+        let volumes = spatial_channel_volumes(&listener, Vec3::new(50.0, 0.0, 0.0), 100.0);
+        assert!((volumes[0] - 0.25).abs() < 1e-6 && volumes[1].abs() < 1e-6);
+    }
+}
@@ -105,22 +105,24 @@ unsafe impl Byteable for Vec2 {}
 // uniform buffer objects pad Vec3s to be 16 bytes.
 unsafe impl Byteable for Vec3 {}
 unsafe impl Byteable for Vec4 {}
+// NOTE: Mat4 is made of 4 column Vec4s, which matches the layout of a mat4 in shaders. This lets Vec<Mat4> fill
+// storage buffers, like the joint matrices of skinned meshes.
+unsafe impl Byteable for Mat4 {}
 
-impl Bytes for Mat4 {
+/// Bools are written as 32 bit integers, which is how shaders represent them
+impl Bytes for bool {
     fn write_bytes(&self, buffer: &mut [u8]) {
-        let array = self.to_cols_array();
-        array.write_bytes(buffer);
+        (*self as u32).write_bytes(buffer);
     }
 
     fn byte_len(&self) -> usize {
-        std::mem::size_of::<Self>()
+        std::mem::size_of::<u32>()
     }
 }
 
-impl FromBytes for Mat4 {
+impl FromBytes for bool {
     fn from_bytes(bytes: &[u8]) -> Self {
-        let array = <[f32; 16]>::from_bytes(bytes);
-        Mat4::from_cols_array(&array)
+        u32::from_bytes(bytes) != 0
     }
 }
 
@@ -198,6 +200,12 @@ mod tests {
         test_round_trip(123u32);
     }
 
+    #[test]
+    fn test_bool_bytes_round_trip() {
+        test_round_trip(true);
+        test_round_trip(false);
+    }
+
     #[test]
     fn test_f64_bytes_round_trip() {
         test_round_trip(123f64);
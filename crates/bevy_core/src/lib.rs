@@ -1,17 +1,24 @@
 mod bytes;
 mod float_ord;
 mod label;
+mod name;
+mod random;
 mod task_pool_options;
 mod time;
 
 pub use bytes::*;
 pub use float_ord::*;
 pub use label::*;
+pub use name::*;
+pub use random::*;
 pub use task_pool_options::DefaultTaskPoolOptions;
 pub use time::*;
 
 pub mod prelude {
-    pub use crate::{DefaultTaskPoolOptions, EntityLabels, Labels, Time, Timer};
+    pub use crate::{
+        DefaultTaskPoolOptions, EntityLabels, EntityNames, FixedTimestep, Labels, Name, Random,
+        Stopwatch, Time, Timer,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -31,9 +38,19 @@ impl Plugin for CorePlugin {
             .unwrap_or_else(DefaultTaskPoolOptions::default)
             .create_default_pools(app.resources_mut());
 
+        // keep a generator that was inserted with a seed before this plugin
+        if app.resources().get::<Random>().is_none() {
+            app.init_resource::<Random>();
+        }
+
         app.init_resource::<Time>()
+            .init_resource::<FixedTimestepState>()
+            .init_resource::<FixedStepSystems>()
             .init_resource::<EntityLabels>()
+            .init_resource::<EntityNames>()
+            .register_component::<Name>()
             .register_component::<Timer>()
+            .register_component::<Stopwatch>()
             .register_property::<Vec2>()
             .register_property::<Vec3>()
             .register_property::<Mat3>()
@@ -42,6 +59,8 @@ impl Plugin for CorePlugin {
             .register_property::<Option<String>>()
             .add_system_to_stage(stage::FIRST, time_system.system())
             .add_system_to_stage(stage::FIRST, timer_system.system())
-            .add_system_to_stage(stage::PRE_UPDATE, entity_labels_system.system());
+            .add_system_to_stage(stage::FIRST, stopwatch_system.system())
+            .add_system_to_stage(stage::PRE_UPDATE, entity_labels_system.system())
+            .add_system_to_stage(stage::PRE_UPDATE, entity_names_system.system());
     }
 }
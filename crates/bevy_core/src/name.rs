@@ -0,0 +1,169 @@
+use bevy_ecs::prelude::*;
+use bevy_property::Properties;
+use bevy_utils::HashMap;
+use std::{fmt, ops::Deref};
+
+/// The name of an entity, for finding it with [EntityNames] and telling entities apart in debugging output. Names
+/// don't have to be unique.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Properties)]
+pub struct Name {
+    name: String,
+}
+
+impl Name {
+    pub fn new(name: impl Into<String>) -> Self {
+        Name { name: name.into() }
+    }
+
+    pub fn set(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.name
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl From<&str> for Name {
+    fn from(name: &str) -> Self {
+        Name::new(name)
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Self {
+        Name::new(name)
+    }
+}
+
+/// Maintains a mapping from [Name]s to the entities that have them. The mapping is updated in `PRE_UPDATE`, so
+/// entities named after that are found in the next update.
+#[derive(Debug, Default)]
+pub struct EntityNames {
+    name_entities: HashMap<String, Vec<Entity>>,
+    entity_names: HashMap<Entity, String>,
+}
+
+impl EntityNames {
+    /// An entity with the given name. If several entities have the name, the one that was named first.
+    pub fn find_by_name(&self, name: &str) -> Option<Entity> {
+        self.find_all_by_name(name).first().copied()
+    }
+
+    /// All entities with the given name, in the order they were named
+    pub fn find_all_by_name(&self, name: &str) -> &[Entity] {
+        self.name_entities
+            .get(name)
+            .map_or(&[], |entities| entities.as_slice())
+    }
+
+    /// The name of an entity
+    pub fn get(&self, entity: Entity) -> Option<&str> {
+        self.entity_names.get(&entity).map(|name| name.as_str())
+    }
+
+    /// The name of an entity followed by its id, or just its id if it has no name. Useful in logs.
+    pub fn describe(&self, entity: Entity) -> String {
+        match self.get(entity) {
+            Some(name) => format!("{} ({})", name, entity.id()),
+            None => format!("{}", entity.id()),
+        }
+    }
+
+    fn insert(&mut self, entity: Entity, name: &str) {
+        if self.get(entity) == Some(name) {
+            return;
+        }
+        self.remove(entity);
+        self.name_entities
+            .entry(name.to_string())
+            .or_insert_with(Vec::new)
+            .push(entity);
+        self.entity_names.insert(entity, name.to_string());
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(name) = self.entity_names.remove(&entity) {
+            if let Some(entities) = self.name_entities.get_mut(&name) {
+                entities.retain(|e| *e != entity);
+                if entities.is_empty() {
+                    self.name_entities.remove(&name);
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn entity_names_system(
+    mut entity_names: ResMut<EntityNames>,
+    changed_query: Query<(Entity, Changed<Name>)>,
+    query: Query<&Name>,
+) {
+    // forget entities that were despawned or lost their name
+    let unnamed = entity_names
+        .entity_names
+        .keys()
+        .filter(|entity| query.get(**entity).is_err())
+        .copied()
+        .collect::<Vec<_>>();
+    for entity in unnamed {
+        entity_names.remove(entity);
+    }
+
+    for (entity, name) in changed_query.iter() {
+        entity_names.insert(entity, name.as_str());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entity_names_system, EntityNames, Name};
+    use bevy_ecs::{prelude::*, Schedule};
+
+    #[test]
+    fn find_entities_by_name() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(EntityNames::default());
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", entity_names_system.system());
+
+        let player = world.spawn((Name::new("Player"),));
+        let first_enemy = world.spawn((Name::new("Enemy"),));
+        let second_enemy = world.spawn((Name::new("Enemy"),));
+        schedule.run(&mut world, &mut resources);
+        {
+            let entity_names = resources.get::<EntityNames>().unwrap();
+            assert_eq!(entity_names.find_by_name("Player"), Some(player));
+            assert_eq!(entity_names.find_by_name("Enemy"), Some(first_enemy));
+            assert_eq!(
+                entity_names.find_all_by_name("Enemy"),
+                &[first_enemy, second_enemy]
+            );
+            assert_eq!(entity_names.get(player), Some("Player"));
+        }
+
+        world.get_mut::<Name>(player).unwrap().set("Hero");
+        world.despawn(first_enemy).unwrap();
+        schedule.run(&mut world, &mut resources);
+        let entity_names = resources.get::<EntityNames>().unwrap();
+        assert_eq!(entity_names.find_by_name("Player"), None);
+        assert_eq!(entity_names.find_by_name("Hero"), Some(player));
+        assert_eq!(entity_names.find_all_by_name("Enemy"), &[second_enemy]);
+    }
+}
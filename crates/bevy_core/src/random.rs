@@ -0,0 +1,156 @@
+use std::ops::Range;
+
+/// A seedable pseudo random number generator (xoshiro256**). The same seed always produces the same numbers on every
+/// platform, so games that only use this resource for randomness can be replayed, or kept in lockstep over the
+/// network, by sharing the seed. Combine it with
+/// [AppBuilder::set_sequential_execution](bevy_app::AppBuilder::set_sequential_execution) so systems also draw
+/// numbers in the same order every update.
+///
+/// The default seed is fixed, so insert a `Random` with a seed of your choosing (for example the current time) to get
+/// different numbers on each run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Random {
+    seed: u64,
+    state: [u64; 4],
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Random::with_seed(0)
+    }
+}
+
+impl Random {
+    pub fn with_seed(seed: u64) -> Self {
+        // expand the seed with splitmix64, so similar seeds still give unrelated states
+        let mut splitmix = seed;
+        let mut next = || {
+            splitmix = splitmix.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = splitmix;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+        Random {
+            seed,
+            state: [next(), next(), next(), next()],
+        }
+    }
+
+    /// The seed this generator started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts over from the given seed
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Random::with_seed(seed);
+    }
+
+    /// A new generator seeded from this one. Giving each system or entity its own fork keeps their numbers
+    /// independent of how many numbers the others draw.
+    pub fn fork(&mut self) -> Random {
+        Random::with_seed(self.next_u64())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s1.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = *s1 << 17;
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= t;
+        *s3 = s3.rotate_left(45);
+        result
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// A number from 0.0 up to but not including 1.0
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// A number from 0.0 up to but not including 1.0
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// True with the given probability
+    pub fn next_bool(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// A number in the range. Panics if the range is empty.
+    pub fn range_u32(&mut self, range: Range<u32>) -> u32 {
+        assert!(range.start < range.end, "The range must not be empty");
+        let span = (range.end - range.start) as u64;
+        // multiply instead of taking the remainder, which avoids most of the bias towards small numbers
+        range.start + ((self.next_u32() as u64 * span) >> 32) as u32
+    }
+
+    /// A number in the range. Panics if the range is empty.
+    pub fn range_f32(&mut self, range: Range<f32>) -> f32 {
+        assert!(range.start < range.end, "The range must not be empty");
+        let value = range.start + (range.end - range.start) * self.next_f32();
+        // rounding can land on the end of the range
+        value.min(range.end - (range.end - range.start) * f32::EPSILON)
+    }
+
+    /// A random element of the slice, or None if it is empty
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.range_u32(0..items.len() as u32) as usize)
+        }
+    }
+
+    /// Shuffles the slice in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range_u32(0..i as u32 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Random;
+
+    #[test]
+    fn seeded_sequences() {
+        let mut a = Random::with_seed(42);
+        let mut b = Random::with_seed(42);
+        let mut c = Random::with_seed(43);
+        let a_values = (0..16).map(|_| a.next_u64()).collect::<Vec<_>>();
+        let b_values = (0..16).map(|_| b.next_u64()).collect::<Vec<_>>();
+        let c_values = (0..16).map(|_| c.next_u64()).collect::<Vec<_>>();
+        assert_eq!(a_values, b_values);
+        assert_ne!(a_values, c_values);
+
+        a.reseed(42);
+        assert_eq!(a.next_u64(), a_values[0]);
+        assert_eq!(a.seed(), 42);
+
+        for _ in 0..1000 {
+            let value = a.range_u32(3..7);
+            assert!((3..7).contains(&value));
+            let value = a.range_f32(-1.0..1.0);
+            assert!((-1.0..1.0).contains(&value));
+            let value = a.next_f64();
+            assert!((0.0..1.0).contains(&value));
+        }
+
+        let mut items = [1, 2, 3, 4, 5, 6];
+        a.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(a.choose::<u32>(&[]), None);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::Time;
+use bevy_ecs::{Resources, Schedule, System, ThreadLocalSystemFn, World};
+
+/// The progress of the app's [FixedTimestep]. Systems can read this to interpolate between the last two
+/// simulation steps when rendering.
+#[derive(Debug, Clone)]
+pub struct FixedTimestepState {
+    /// The duration of a single step, in seconds
+    pub step: f64,
+    /// Time that has passed but has not been consumed by a step yet, in seconds
+    pub accumulator: f64,
+    /// Whether a [FixedTimestep] is running. Without one there is nothing to interpolate between.
+    pub running: bool,
+}
+
+impl Default for FixedTimestepState {
+    fn default() -> Self {
+        FixedTimestepState {
+            step: 1.0 / 60.0,
+            accumulator: 0.0,
+            running: false,
+        }
+    }
+}
+
+impl FixedTimestepState {
+    /// How far the app is into the next step, from 0.0 (just stepped) to 1.0 (about to step)
+    pub fn overstep_percentage(&self) -> f64 {
+        self.accumulator / self.step
+    }
+}
+
+/// Systems that every [FixedTimestep] runs at the start of each step, before its own schedule. Plugins add systems
+/// here that record per-step state, like the previous transforms used for interpolation.
+#[derive(Default)]
+pub struct FixedStepSystems {
+    systems: Vec<fn() -> Box<dyn System>>,
+}
+
+impl FixedStepSystems {
+    /// Adds a system. It is created with `system` once for each [FixedTimestep].
+    pub fn add(&mut self, system: fn() -> Box<dyn System>) {
+        self.systems.push(system);
+    }
+}
+
+/// A thread local system that runs a [Schedule] as many times as needed to advance it in steps of a fixed duration,
+/// independent of the frame rate. Its progress is stored in the [FixedTimestepState] resource. Add it to an app with
+/// `app.add_system(FixedTimestep::new(1.0 / 60.0, schedule).thread_local_system())`.
+///
+/// At most `max_steps_per_frame` steps run per frame. Time beyond that is dropped, so a long frame (ex: a hitch or
+/// a breakpoint) slows the simulation down instead of making every following frame longer.
+pub struct FixedTimestep {
+    step: f64,
+    max_steps_per_frame: u32,
+    schedule: Schedule,
+    step_start: Schedule,
+}
+
+impl FixedTimestep {
+    pub const DEFAULT_MAX_STEPS_PER_FRAME: u32 = 5;
+
+    pub fn new(step: f64, schedule: Schedule) -> Self {
+        FixedTimestep {
+            step,
+            max_steps_per_frame: Self::DEFAULT_MAX_STEPS_PER_FRAME,
+            schedule,
+            step_start: Schedule::default(),
+        }
+    }
+
+    pub fn steps_per_second(rate: f64, schedule: Schedule) -> Self {
+        Self::new(1.0 / rate, schedule)
+    }
+
+    pub fn with_max_steps_per_frame(mut self, max_steps_per_frame: u32) -> Self {
+        self.max_steps_per_frame = max_steps_per_frame;
+        self
+    }
+}
+
+impl ThreadLocalSystemFn for FixedTimestep {
+    fn initialize(&mut self, world: &mut World, resources: &mut Resources) {
+        // keep the progress of a state that is already there
+        let mut state = resources
+            .get_cloned::<FixedTimestepState>()
+            .unwrap_or_default();
+        state.step = self.step;
+        state.running = true;
+        resources.insert(state);
+
+        if let Some(step_systems) = resources.get::<FixedStepSystems>() {
+            self.step_start.add_stage("step_start");
+            for system in step_systems.systems.iter() {
+                self.step_start.add_system_to_stage("step_start", system());
+            }
+        }
+        self.step_start.initialize(world, resources);
+        self.schedule.initialize(world, resources);
+    }
+
+    fn run(&mut self, world: &mut World, resources: &mut Resources) {
+        let delta = resources.get::<Time>().unwrap().delta_seconds_f64;
+        let mut state = resources.get_cloned::<FixedTimestepState>().unwrap();
+        state.accumulator += delta;
+        let mut steps = 0;
+        while state.accumulator >= state.step {
+            if steps == self.max_steps_per_frame {
+                // drop the time we can't catch up on, but keep the progress into the next step
+                state.accumulator %= state.step;
+                break;
+            }
+            state.accumulator -= state.step;
+            self.step_start.run(world, resources);
+            self.schedule.run(world, resources);
+            steps += 1;
+        }
+        resources.insert(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{IntoQuerySystem, Res, ResMut};
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct Steps(u32);
+
+    fn count_steps(mut steps: ResMut<Steps>) {
+        steps.0 += 1;
+    }
+
+    fn setup(fixed_timestep: FixedTimestep) -> (World, Resources, FixedTimestep) {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(Time::default());
+        resources.insert(Steps::default());
+        let mut fixed_timestep = fixed_timestep;
+        fixed_timestep.initialize(&mut world, &mut resources);
+        (world, resources, fixed_timestep)
+    }
+
+    fn count_steps_schedule() -> Schedule {
+        let mut fixed_schedule = Schedule::default();
+        fixed_schedule.add_stage("update");
+        fixed_schedule.add_system_to_stage("update", count_steps.system());
+        fixed_schedule
+    }
+
+    fn advance(
+        delta: f64,
+        fixed_timestep: &mut FixedTimestep,
+        world: &mut World,
+        resources: &mut Resources,
+    ) {
+        {
+            let mut time = resources.get_mut::<Time>().unwrap();
+            time.delta = Duration::from_secs_f64(delta);
+            time.delta_seconds_f64 = delta;
+        }
+        fixed_timestep.run(world, resources);
+    }
+
+    #[test]
+    fn runs_fixed_steps() {
+        let (mut world, mut resources, mut fixed_timestep) =
+            setup(FixedTimestep::new(0.1, count_steps_schedule()));
+
+        advance(0.25, &mut fixed_timestep, &mut world, &mut resources);
+        assert_eq!(resources.get::<Steps>().unwrap().0, 2);
+        let overstep = resources
+            .get::<FixedTimestepState>()
+            .unwrap()
+            .overstep_percentage();
+        assert!((overstep - 0.5).abs() < 1e-6);
+
+        advance(0.06, &mut fixed_timestep, &mut world, &mut resources);
+        assert_eq!(resources.get::<Steps>().unwrap().0, 3);
+    }
+
+    #[test]
+    fn runs_step_systems_before_each_step() {
+        #[derive(Default)]
+        struct StepStarts(Vec<u32>);
+
+        fn record_step_start(steps: Res<Steps>, mut step_starts: ResMut<StepStarts>) {
+            step_starts.0.push(steps.0);
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(Time::default());
+        resources.insert(Steps::default());
+        resources.insert(StepStarts::default());
+        let mut step_systems = FixedStepSystems::default();
+        step_systems.add(|| record_step_start.system());
+        resources.insert(step_systems);
+        let mut fixed_timestep = FixedTimestep::new(0.1, count_steps_schedule());
+        fixed_timestep.initialize(&mut world, &mut resources);
+
+        advance(0.25, &mut fixed_timestep, &mut world, &mut resources);
+        assert_eq!(resources.get::<StepStarts>().unwrap().0, vec![0, 1]);
+    }
+
+    #[test]
+    fn keeps_the_progress_of_an_existing_state() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(FixedTimestepState {
+            step: 0.5,
+            accumulator: 0.05,
+            running: false,
+        });
+        let mut fixed_timestep = FixedTimestep::new(0.1, count_steps_schedule());
+        fixed_timestep.initialize(&mut world, &mut resources);
+
+        let state = resources.get::<FixedTimestepState>().unwrap();
+        assert_eq!(state.step, 0.1);
+        assert_eq!(state.accumulator, 0.05);
+        assert!(state.running);
+    }
+
+    #[test]
+    fn caps_steps_per_frame() {
+        let (mut world, mut resources, mut fixed_timestep) =
+            setup(FixedTimestep::new(0.1, count_steps_schedule()).with_max_steps_per_frame(3));
+
+        advance(1.05, &mut fixed_timestep, &mut world, &mut resources);
+        assert_eq!(resources.get::<Steps>().unwrap().0, 3);
+        let overstep = resources
+            .get::<FixedTimestepState>()
+            .unwrap()
+            .overstep_percentage();
+        assert!((overstep - 0.5).abs() < 1e-6);
+
+        // the dropped time isn't caught up on later
+        advance(0.1, &mut fixed_timestep, &mut world, &mut resources);
+        assert_eq!(resources.get::<Steps>().unwrap().0, 4);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::time::Time;
+use bevy_ecs::prelude::*;
+use bevy_property::Properties;
+
+/// Tracks the time elapsed since it was started or reset, without a duration to reach. Useful for measuring how long
+/// something has been going on, like how long a button has been held down.
+///
+/// Stopwatch components are ticked by the [CorePlugin](crate::CorePlugin) at the start of each frame. Stopwatches
+/// stored elsewhere need to be ticked with [Time::delta_seconds].
+#[derive(Clone, Debug, Default, Properties)]
+pub struct Stopwatch {
+    pub elapsed: f32,
+    /// Paused stopwatches ignore ticks
+    pub paused: bool,
+}
+
+impl Stopwatch {
+    pub fn new() -> Self {
+        Stopwatch::default()
+    }
+
+    /// Advances the stopwatch by `delta` seconds.
+    pub fn tick(&mut self, delta: f32) -> &Self {
+        if !self.paused {
+            self.elapsed += delta;
+        }
+        self
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+}
+
+pub(crate) fn stopwatch_system(time: Res<Time>, mut query: Query<&mut Stopwatch>) {
+    for mut stopwatch in query.iter_mut() {
+        stopwatch.tick(time.delta_seconds);
+    }
+}
@@ -7,6 +7,9 @@ use std::time::Duration;
 ///
 /// Non repeating timers will stop tracking and stay in the finished state until reset.
 /// Repeating timers will only be in the finished state on each tick `duration` is reached or exceeded, and can still be reset at any given point.
+///
+/// Timer components are ticked by the [CorePlugin](crate::CorePlugin) at the start of each frame. Timers stored
+/// elsewhere need to be ticked with [Time::delta_seconds].
 #[derive(Clone, Debug, Default, Properties)]
 pub struct Timer {
     pub elapsed: f32,
@@ -14,7 +17,12 @@ pub struct Timer {
     pub finished: bool,
     /// Will only be true on the tick `duration` is reached or exceeded.
     pub just_finished: bool,
+    /// How many times the timer finished during the last tick. A repeating timer finishes more than once when the
+    /// tick is longer than `duration`.
+    pub times_finished: u32,
     pub repeating: bool,
+    /// Paused timers ignore ticks
+    pub paused: bool,
 }
 
 impl Timer {
@@ -35,25 +43,69 @@ impl Timer {
     }
 
     /// Advances the timer by `delta` seconds.
-    pub fn tick(&mut self, delta: f32) {
-        let prev_finished = self.elapsed >= self.duration;
-        if !prev_finished {
-            self.elapsed += delta;
+    pub fn tick(&mut self, delta: f32) -> &Self {
+        self.just_finished = false;
+        self.times_finished = 0;
+        if self.paused {
+            if self.repeating {
+                self.finished = false;
+            }
+            return self;
         }
 
-        self.finished = self.elapsed >= self.duration;
-        self.just_finished = !prev_finished && self.finished;
-
-        if self.repeating && self.finished {
-            self.elapsed %= self.duration;
+        if self.repeating {
+            self.elapsed += delta;
+            if self.duration <= 0.0 {
+                self.times_finished = 1;
+                self.elapsed = 0.0;
+            } else if self.elapsed >= self.duration {
+                self.times_finished = (self.elapsed / self.duration) as u32;
+                self.elapsed %= self.duration;
+            }
+            self.finished = self.times_finished > 0;
+            self.just_finished = self.finished;
+        } else {
+            let prev_finished = self.elapsed >= self.duration;
+            self.elapsed = (self.elapsed + delta).min(self.duration);
+            self.finished = self.elapsed >= self.duration;
+            self.just_finished = !prev_finished && self.finished;
+            self.times_finished = self.just_finished as u32;
         }
+        self
     }
 
     pub fn reset(&mut self) {
         self.finished = false;
         self.just_finished = false;
+        self.times_finished = 0;
         self.elapsed = 0.0;
     }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    /// The fraction of `duration` that has elapsed, between 0 and 1
+    pub fn percent(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        }
+    }
+
+    pub fn percent_left(&self) -> f32 {
+        1.0 - self.percent()
+    }
+
+    /// The time left until the timer finishes, in seconds
+    pub fn remaining(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
 }
 
 pub(crate) fn timer_system(time: Res<Time>, mut query: Query<&mut Timer>) {
@@ -61,3 +113,50 @@ pub(crate) fn timer_system(time: Res<Time>, mut query: Query<&mut Timer>) {
         timer.tick(time.delta_seconds);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Timer;
+
+    #[test]
+    fn non_repeating_timer() {
+        let mut timer = Timer::from_seconds(1.0, false);
+        timer.tick(0.25);
+        assert_eq!(timer.percent(), 0.25);
+        assert_eq!(timer.remaining(), 0.75);
+        assert!(!timer.finished);
+
+        timer.pause();
+        timer.tick(0.5);
+        assert_eq!(timer.elapsed, 0.25);
+        timer.unpause();
+
+        assert!(timer.tick(1.0).just_finished);
+        assert_eq!(timer.elapsed, 1.0);
+        assert_eq!(timer.times_finished, 1);
+        timer.tick(1.0);
+        assert!(timer.finished);
+        assert!(!timer.just_finished);
+        assert_eq!(timer.times_finished, 0);
+
+        timer.reset();
+        assert!(!timer.finished);
+        assert_eq!(timer.elapsed, 0.0);
+    }
+
+    #[test]
+    fn repeating_timer() {
+        let mut timer = Timer::from_seconds(1.0, true);
+        timer.tick(0.75);
+        assert!(!timer.finished);
+        timer.tick(0.5);
+        assert!(timer.finished && timer.just_finished);
+        assert_eq!(timer.times_finished, 1);
+        assert_eq!(timer.elapsed, 0.25);
+        timer.tick(2.0);
+        assert_eq!(timer.times_finished, 2);
+        assert_eq!(timer.elapsed, 0.25);
+        timer.tick(0.25);
+        assert!(!timer.finished);
+    }
+}
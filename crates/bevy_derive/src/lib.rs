@@ -7,6 +7,7 @@ mod render_resource;
 mod render_resources;
 mod resource;
 mod shader_defs;
+mod std140;
 mod type_uuid;
 
 use proc_macro::TokenStream;
@@ -25,18 +26,27 @@ pub fn derive_bytes(input: TokenStream) -> TokenStream {
 }
 
 /// Derives the RenderResources trait. Each field must implement RenderResource or this will fail.
-/// You can ignore fields using `#[render_resources(ignore)]`.
+/// You can ignore fields using `#[render_resources(ignore)]`, and bind fields as storage buffers instead of uniform
+/// buffers using `#[render_resources(buffer)]`. Storage buffers can hold large arrays, like `Vec<Mat4>`.
 #[proc_macro_derive(RenderResources, attributes(render_resources, as_crate))]
 pub fn derive_render_resources(input: TokenStream) -> TokenStream {
     render_resources::derive_render_resources(input)
 }
 
 /// Derives the RenderResource trait. The type must also implement `Bytes` or this will fail.
-#[proc_macro_derive(RenderResource, attributes(as_crate))]
+/// Use `#[render_resource(std140)]` to write the type with its `Std140` layout instead.
+#[proc_macro_derive(RenderResource, attributes(render_resource, as_crate))]
 pub fn derive_render_resource(input: TokenStream) -> TokenStream {
     render_resource::derive_render_resource(input)
 }
 
+/// Derives the Std140 trait, which lays out the fields like a GLSL struct in a std140 uniform block. Each field must
+/// also implement Std140 or this will fail.
+#[proc_macro_derive(Std140, attributes(as_crate))]
+pub fn derive_std140(input: TokenStream) -> TokenStream {
+    std140::derive_std140(input)
+}
+
 /// Derives the ShaderDefs trait. Each field must implement ShaderDef or this will fail.
 /// You can ignore fields using `#[shader_defs(ignore)]`.
 #[proc_macro_derive(ShaderDefs, attributes(shader_def, as_crate))]
@@ -1,7 +1,14 @@
 use crate::modules::{get_modules, get_path};
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Path};
+use syn::{parse::ParseStream, parse_macro_input, DeriveInput, Path};
+
+#[derive(Default)]
+struct RenderResourceAttributes {
+    pub std140: bool,
+}
+
+static RENDER_RESOURCE_ATTRIBUTE_NAME: &str = "render_resource";
 
 pub fn derive_render_resource(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -10,20 +17,57 @@ pub fn derive_render_resource(input: TokenStream) -> TokenStream {
     let bevy_render_path: Path = get_path(&modules.bevy_render);
     let bevy_asset_path: Path = get_path(&modules.bevy_asset);
     let bevy_core_path: Path = get_path(&modules.bevy_core);
+    let attributes = ast
+        .attrs
+        .iter()
+        .find(|a| *a.path.get_ident().as_ref().unwrap() == RENDER_RESOURCE_ATTRIBUTE_NAME)
+        .map_or_else(RenderResourceAttributes::default, |a| {
+            syn::custom_keyword!(std140);
+            let mut attributes = RenderResourceAttributes::default();
+            a.parse_args_with(|input: ParseStream| {
+                if input.parse::<Option<std140>>()?.is_some() {
+                    attributes.std140 = true;
+                }
+                Ok(())
+            })
+            .expect("invalid 'render_resource' attribute format");
+
+            attributes
+        });
     let struct_name = &ast.ident;
 
+    let (write_buffer_bytes, buffer_byte_len) = if attributes.std140 {
+        (
+            quote! {
+                #bevy_render_path::renderer::Std140::write_std140(self, buffer);
+            },
+            quote! {
+                Some(#bevy_render_path::renderer::std140_block_size::<Self>())
+            },
+        )
+    } else {
+        (
+            quote! {
+                use #bevy_core_path::Bytes;
+                self.write_bytes(buffer);
+            },
+            quote! {
+                use #bevy_core_path::Bytes;
+                Some(self.byte_len())
+            },
+        )
+    };
+
     TokenStream::from(quote! {
         impl #bevy_render_path::renderer::RenderResource for #struct_name {
             fn resource_type(&self) -> Option<#bevy_render_path::renderer::RenderResourceType> {
                 Some(#bevy_render_path::renderer::RenderResourceType::Buffer)
             }
             fn write_buffer_bytes(&self, buffer: &mut [u8]) {
-                use #bevy_core_path::Bytes;
-                self.write_bytes(buffer);
+                #write_buffer_bytes
             }
             fn buffer_byte_len(&self) -> Option<usize> {
-                use #bevy_core_path::Bytes;
-                Some(self.byte_len())
+                #buffer_byte_len
             }
             fn texture(&self) -> Option<&#bevy_asset_path::Handle<#bevy_render_path::texture::Texture>> {
                 None
@@ -0,0 +1,54 @@
+use crate::modules::{get_modules, get_path};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Fields, Path};
+
+pub fn derive_std140(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("expected a struct with named fields"),
+    };
+
+    let modules = get_modules(&ast.attrs);
+    let bevy_render_path: Path = get_path(&modules.bevy_render);
+    let std140 = quote! { #bevy_render_path::renderer::Std140 };
+    let align = quote! { #bevy_render_path::renderer::std140_align };
+    let max = quote! { #bevy_render_path::renderer::std140_max };
+
+    let field_names = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let field_types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    // structs are aligned like their most aligned member, rounded up to the alignment of a vec4
+    let alignment = field_types.iter().fold(quote! { 16 }, |alignment, ty| {
+        quote! { #max(<#ty as #std140>::ALIGNMENT, #alignment) }
+    });
+    let end_offset = field_types.iter().fold(quote! { 0 }, |offset, ty| {
+        quote! { #align(#offset, <#ty as #std140>::ALIGNMENT) + <#ty as #std140>::SIZE }
+    });
+
+    let generics = ast.generics;
+    let (impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
+
+    let struct_name = &ast.ident;
+
+    TokenStream::from(quote! {
+        impl #impl_generics #std140 for #struct_name#ty_generics {
+            const ALIGNMENT: usize = #alignment;
+            const SIZE: usize = #align(#end_offset, Self::ALIGNMENT);
+
+            fn write_std140(&self, buffer: &mut [u8]) {
+                let mut offset: usize = 0;
+                #(offset = #align(offset, <#field_types as #std140>::ALIGNMENT);
+                #std140::write_std140(&self.#field_names, &mut buffer[offset..(offset + <#field_types as #std140>::SIZE)]);
+                offset += <#field_types as #std140>::SIZE;)*
+            }
+        }
+    })
+}
@@ -63,8 +63,9 @@ impl Diagnostic {
         }
     }
 
+    /// The most recent measurement
     pub fn value(&self) -> Option<f64> {
-        self.history.back().map(|measurement| measurement.value)
+        self.history.front().map(|measurement| measurement.value)
     }
 
     pub fn sum(&self) -> f64 {
@@ -79,6 +80,11 @@ impl Diagnostic {
         }
     }
 
+    /// The measurements in the history, from the oldest to the most recent
+    pub fn measurements(&self) -> impl Iterator<Item = &DiagnosticMeasurement> {
+        self.history.iter().rev()
+    }
+
     pub fn history_len(&self) -> usize {
         self.history.len()
     }
@@ -137,3 +143,28 @@ impl Diagnostics {
         self.diagnostics.values()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, DiagnosticId};
+
+    #[test]
+    fn diagnostic_history() {
+        let mut diagnostic = Diagnostic::new(DiagnosticId::default(), "test", 3);
+        assert_eq!(diagnostic.value(), None);
+        assert_eq!(diagnostic.average(), None);
+
+        for value in 1..=4 {
+            diagnostic.add_measurement(value as f64);
+        }
+        assert_eq!(diagnostic.value(), Some(4.0));
+        assert_eq!(diagnostic.history_len(), 3);
+        assert_eq!(diagnostic.sum(), 9.0);
+        assert_eq!(diagnostic.average(), Some(3.0));
+        let values = diagnostic
+            .measurements()
+            .map(|measurement| measurement.value)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![2.0, 3.0, 4.0]);
+    }
+}
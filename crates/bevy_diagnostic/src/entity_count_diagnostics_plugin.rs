@@ -0,0 +1,73 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_app::prelude::*;
+use bevy_ecs::{IntoQuerySystem, IntoThreadLocalSystem, ResMut, Resources, World};
+use bevy_utils::HashMap;
+use std::any::TypeId;
+
+/// Adds an "entity count" diagnostic to an App, and optionally a diagnostic with the number of entities that have each
+/// component type. Counts that keep growing point to entities that are never despawned.
+#[derive(Default)]
+pub struct EntityCountDiagnosticsPlugin {
+    pub component_counts: bool,
+}
+
+/// State used by the [EntityCountDiagnosticsPlugin]
+#[derive(Default)]
+pub struct EntityCountDiagnosticsState {
+    component_counts: bool,
+    component_diagnostics: HashMap<TypeId, DiagnosticId>,
+}
+
+impl Plugin for EntityCountDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_resource(EntityCountDiagnosticsState {
+            component_counts: self.component_counts,
+            ..Default::default()
+        })
+        .add_startup_system(Self::setup_system.system())
+        .add_system_to_stage(stage::LAST, Self::diagnostic_system.thread_local_system());
+    }
+}
+
+impl EntityCountDiagnosticsPlugin {
+    pub const ENTITY_COUNT: DiagnosticId =
+        DiagnosticId::from_u128(187513512115068938494459732780662867798);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(Self::ENTITY_COUNT, "entity_count", 20));
+    }
+
+    pub fn diagnostic_system(world: &mut World, resources: &mut Resources) {
+        let mut diagnostics = resources.get_mut::<Diagnostics>().unwrap();
+        let mut state = resources.get_mut::<EntityCountDiagnosticsState>().unwrap();
+
+        let entity_count = world
+            .archetypes()
+            .map(|archetype| archetype.len())
+            .sum::<usize>();
+        diagnostics.add_measurement(Self::ENTITY_COUNT, entity_count as f64);
+
+        if !state.component_counts {
+            return;
+        }
+        let mut component_counts = HashMap::<TypeId, usize>::default();
+        for archetype in world.archetypes() {
+            for type_info in archetype.types() {
+                *component_counts.entry(type_info.id()).or_insert(0) += archetype.len();
+                if !state.component_diagnostics.contains_key(&type_info.id()) {
+                    let diagnostic_id = DiagnosticId::default();
+                    let name = format!("component_count/{}", type_info.type_name());
+                    diagnostics.add(Diagnostic::new(diagnostic_id, &name, 20));
+                    state
+                        .component_diagnostics
+                        .insert(type_info.id(), diagnostic_id);
+                }
+            }
+        }
+        // components without entities left still report a count of 0
+        for (type_id, diagnostic_id) in state.component_diagnostics.iter() {
+            let count = component_counts.get(type_id).cloned().unwrap_or(0);
+            diagnostics.add_measurement(*diagnostic_id, count as f64);
+        }
+    }
+}
@@ -1,9 +1,13 @@
 mod diagnostic;
+mod entity_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod print_diagnostics_plugin;
 #[cfg(feature = "profiler")]
 mod system_profiler;
 pub use diagnostic::*;
+pub use entity_count_diagnostics_plugin::{
+    EntityCountDiagnosticsPlugin, EntityCountDiagnosticsState,
+};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use print_diagnostics_plugin::PrintDiagnosticsPlugin;
 
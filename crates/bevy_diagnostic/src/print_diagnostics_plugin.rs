@@ -74,7 +74,8 @@ impl PrintDiagnosticsPlugin {
             println!("Diagnostics:");
             println!("{}", "-".repeat(93));
             if let Some(ref filter) = state.filter {
-                for diagnostic in filter.iter().map(|id| diagnostics.get(*id).unwrap()) {
+                // diagnostics in the filter may not have been added yet
+                for diagnostic in filter.iter().filter_map(|id| diagnostics.get(*id)) {
                     Self::print_diagnostic(diagnostic);
                 }
             } else {
@@ -95,7 +96,8 @@ impl PrintDiagnosticsPlugin {
             println!("Diagnostics (Debug):");
             println!("{}", "-".repeat(93));
             if let Some(ref filter) = state.filter {
-                for diagnostic in filter.iter().map(|id| diagnostics.get(*id).unwrap()) {
+                // diagnostics in the filter may not have been added yet
+                for diagnostic in filter.iter().filter_map(|id| diagnostics.get(*id)) {
                     println!("{:#?}\n", diagnostic);
                 }
             } else {
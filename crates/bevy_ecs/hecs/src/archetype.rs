@@ -488,6 +488,7 @@ pub struct TypeInfo {
     id: TypeId,
     layout: Layout,
     drop: unsafe fn(*mut u8),
+    type_name: &'static str,
 }
 
 impl TypeInfo {
@@ -501,6 +502,7 @@ impl TypeInfo {
             id: TypeId::of::<T>(),
             layout: Layout::new::<T>(),
             drop: drop_ptr::<T>,
+            type_name: type_name::<T>(),
         }
     }
 
@@ -516,6 +518,12 @@ impl TypeInfo {
         self.layout
     }
 
+    /// The name of the component type, for debugging
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
     pub(crate) unsafe fn drop(&self, data: *mut u8) {
         (self.drop)(data)
     }
@@ -322,18 +322,14 @@ impl<'a, T: Resource + FromResources> FetchResource<'a> for FetchResourceLocalMu
         }
     }
 
-    fn borrow(resources: &Resources) {
-        resources.borrow_mut::<T>();
-    }
+    // Local<T> resources are only ever accessed by the system that owns them, so they don't need to take
+    // a borrow on (or register access to) the global resource of the same type
+    fn borrow(_resources: &Resources) {}
 
-    fn release(resources: &Resources) {
-        resources.release_mut::<T>();
-    }
+    fn release(_resources: &Resources) {}
 
     fn access() -> TypeAccess<TypeId> {
-        let mut access = TypeAccess::default();
-        access.add_write(TypeId::of::<T>());
-        access
+        TypeAccess::default()
     }
 }
 
@@ -15,12 +15,15 @@ use std::ops::Range;
 /// * in a given stage, systems the read [archetype+component] X cannot run before systems registered before them that write [archetype+component] X
 /// * in a given stage, systems that mutate resource Y cannot run before systems registered before them that read/write resource Y
 /// * in a given stage, systems the read resource Y cannot run before systems registered before them that write resource Y
+///
+/// With [ParallelExecutor::set_sequential], every system runs after the system registered before it instead.
 
 #[derive(Debug)]
 pub struct ParallelExecutor {
     stages: Vec<ExecutorStage>,
     last_schedule_generation: usize,
     clear_trackers: bool,
+    sequential: bool,
 }
 
 impl Default for ParallelExecutor {
@@ -29,6 +32,7 @@ impl Default for ParallelExecutor {
             stages: Default::default(),
             last_schedule_generation: usize::MAX, // MAX forces prepare to run the first time
             clear_trackers: true,
+            sequential: false,
         }
     }
 }
@@ -41,6 +45,21 @@ impl ParallelExecutor {
         }
     }
 
+    /// Runs the systems of each stage one at a time, in the order they were registered. Systems that run in parallel
+    /// can finish in any order, which makes the order of their commands' entity ids, log output and other side
+    /// effects vary between runs. Running them in order makes each update deterministic, at the cost of parallelism.
+    pub fn set_sequential(&mut self, sequential: bool) {
+        if self.sequential != sequential {
+            self.sequential = sequential;
+            // rebuild the system dependencies
+            self.last_schedule_generation = usize::MAX;
+        }
+    }
+
+    pub fn is_sequential(&self) -> bool {
+        self.sequential
+    }
+
     pub fn initialize(&mut self, resources: &mut Resources) {
         if resources.get::<ComputeTaskPool>().is_none() {
             resources.insert(ComputeTaskPool(TaskPool::default()));
@@ -58,7 +77,12 @@ impl ParallelExecutor {
         for (stage_name, executor_stage) in schedule.stage_order.iter().zip(self.stages.iter_mut())
         {
             log::trace!("run stage {:?}", stage_name);
+            #[cfg(feature = "trace")]
+            let stage_span = tracing::info_span!("stage", name = &**stage_name);
+            #[cfg(feature = "trace")]
+            let _stage_guard = stage_span.enter();
             if let Some(stage_systems) = schedule.stages.get_mut(stage_name) {
+                executor_stage.sequential = self.sequential;
                 executor_stage.run(world, resources, stage_systems, schedule_changed);
             }
         }
@@ -111,6 +135,8 @@ pub struct ExecutorStage {
     /// When archetypes change a counter is bumped - we cache the state of that counter when it was
     /// last read here so that we can detect when archetypes are changed
     last_archetypes_generation: ArchetypesGeneration,
+    /// whether each system depends on the system before it
+    sequential: bool,
 }
 
 impl Default for ExecutorStage {
@@ -123,6 +149,7 @@ impl Default for ExecutorStage {
             system_dependencies: Default::default(),
             thread_local_system_indices: Default::default(),
             last_archetypes_generation: ArchetypesGeneration(u64::MAX), // MAX forces prepare to run the first time
+            sequential: false,
         }
     }
 }
@@ -218,6 +245,15 @@ impl ExecutorStage {
                             }
                         }
 
+                        // in sequential mode, also wait for the previous system
+                        if self.sequential
+                            && system_index > prepare_system_index_range.start
+                            && !self.system_dependencies[system_index].contains(system_index - 1)
+                        {
+                            self.system_dependents[system_index - 1].push(system_index);
+                            self.system_dependencies[system_index].insert(system_index - 1);
+                        }
+
                         current_archetype_access.union(archetype_access);
                         current_resource_access.union(resource_access);
 
@@ -393,6 +429,10 @@ impl ExecutorStage {
                     // triggering dependents
                     {
                         log::trace!("run {}", system.name());
+                        #[cfg(feature = "trace")]
+                        let system_span = tracing::info_span!("system", name = &*system.name());
+                        #[cfg(feature = "trace")]
+                        let _system_guard = system_span.enter();
                         #[cfg(feature = "profiler")]
                         crate::profiler_start(resources, system.name().clone());
                         system.run(world_ref, resources_ref);
@@ -484,6 +524,10 @@ impl ExecutorStage {
                 // if a thread local system is ready to run, run it exclusively on the main thread
                 let system = systems[thread_local_system_index].as_mut();
                 log::trace!("running thread local system {}", system.name());
+                #[cfg(feature = "trace")]
+                let system_span = tracing::info_span!("system", name = &*system.name());
+                #[cfg(feature = "trace")]
+                let _system_guard = system_span.enter();
                 system.run(world, resources);
                 system.run_thread_local(world, resources);
             }
@@ -513,7 +557,14 @@ impl ExecutorStage {
         // "flush"
         for system in systems.iter_mut() {
             match system.thread_local_execution() {
-                ThreadLocalExecution::NextFlush => system.run_thread_local(world, resources),
+                ThreadLocalExecution::NextFlush => {
+                    #[cfg(feature = "trace")]
+                    let system_span =
+                        tracing::info_span!("system_commands", name = &*system.name());
+                    #[cfg(feature = "trace")]
+                    let _system_guard = system_span.enter();
+                    system.run_thread_local(world, resources)
+                }
                 ThreadLocalExecution::Immediate => { /* already ran */ }
             }
         }
@@ -843,4 +894,38 @@ mod tests {
             run_executor_and_validate(&mut executor, &mut schedule, &mut world, &mut resources);
         }
     }
+
+    #[test]
+    fn sequential_schedule() {
+        let mut world = World::new();
+        let mut resources = Resources::default();
+        resources.insert(ComputeTaskPool(TaskPool::default()));
+        resources.insert(Mutex::new(Vec::<usize>::new()));
+
+        // these systems only read resources, so they would otherwise run in parallel
+        fn first(order: Res<Mutex<Vec<usize>>>) {
+            order.lock().push(0);
+        }
+        fn second(order: Res<Mutex<Vec<usize>>>) {
+            order.lock().push(1);
+        }
+        fn third(order: Res<Mutex<Vec<usize>>>) {
+            order.lock().push(2);
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", first.system());
+        schedule.add_system_to_stage("update", second.system());
+        schedule.add_system_to_stage("update", third.system());
+
+        let mut executor = ParallelExecutor::default();
+        executor.set_sequential(true);
+        for _ in 0..100 {
+            executor.run(&mut schedule, &mut world, &mut resources);
+            let order = resources.get::<Mutex<Vec<usize>>>().unwrap();
+            assert_eq!(*order.lock(), vec![0, 1, 2]);
+            order.lock().clear();
+        }
+    }
 }
@@ -145,9 +145,19 @@ impl Schedule {
     }
 
     pub fn run(&mut self, world: &mut World, resources: &mut Resources) {
+        // systems (and their Local<T> resources) must be initialized before they can run
+        self.initialize(world, resources);
         for stage_name in self.stage_order.iter() {
+            #[cfg(feature = "trace")]
+            let stage_span = tracing::info_span!("stage", name = &**stage_name);
+            #[cfg(feature = "trace")]
+            let _stage_guard = stage_span.enter();
             if let Some(stage_systems) = self.stages.get_mut(stage_name) {
                 for system in stage_systems.iter_mut() {
+                    #[cfg(feature = "trace")]
+                    let system_span = tracing::info_span!("system", name = &*system.name());
+                    #[cfg(feature = "trace")]
+                    let _system_guard = system_span.enter();
                     #[cfg(feature = "profiler")]
                     crate::profiler_start(resources, system.name().clone());
                     system.update(world);
@@ -168,6 +178,11 @@ impl Schedule {
                 for system in stage_systems.iter_mut() {
                     match system.thread_local_execution() {
                         ThreadLocalExecution::NextFlush => {
+                            #[cfg(feature = "trace")]
+                            let system_span =
+                                tracing::info_span!("system_commands", name = &*system.name());
+                            #[cfg(feature = "trace")]
+                            let _system_guard = system_span.enter();
                             system.run_thread_local(world, resources)
                         }
                         ThreadLocalExecution::Immediate => { /* already ran immediate */ }
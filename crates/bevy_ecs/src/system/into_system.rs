@@ -369,7 +369,9 @@ impl_into_systems!(Ra,Rb,Rc,Rd,Re,Rf,Rg,Rh,Ri);
 #[rustfmt::skip]
 impl_into_systems!(Ra,Rb,Rc,Rd,Re,Rf,Rg,Rh,Ri,Rj);
 
-/// Converts `Self` into a thread local system
+/// Converts `Self` into a thread local system. Thread local systems have exclusive access to the [World] and [Resources]
+/// and run on the main thread at the exact position they were added to their stage, after all prior systems in the stage
+/// have finished.
 pub trait IntoThreadLocalSystem {
     fn thread_local_system(self) -> Box<dyn System>;
 }
@@ -378,14 +380,20 @@ impl<F> IntoThreadLocalSystem for F
 where
     F: ThreadLocalSystemFn,
 {
-    fn thread_local_system(mut self) -> Box<dyn System> {
+    fn thread_local_system(self) -> Box<dyn System> {
         Box::new(SystemFn {
-            state: (),
-            thread_local_func: move |world, resources, _| {
-                self.run(world, resources);
+            state: (self, false),
+            thread_local_func: |world, resources, (state, _)| {
+                state.run(world, resources);
             },
             func: |_, _, _| {},
-            init_func: |_, _, _| {},
+            // the schedule initializes its systems again whenever systems are added, so only the first call is passed on
+            init_func: |world, resources, (state, initialized)| {
+                if !*initialized {
+                    state.initialize(world, resources);
+                    *initialized = true;
+                }
+            },
             update_func: |_, _, _| {},
             thread_local_execution: ThreadLocalExecution::Immediate,
             name: core::any::type_name::<F>().into(),
@@ -399,6 +407,10 @@ where
 /// A thread local system function
 pub trait ThreadLocalSystemFn: Send + Sync + 'static {
     fn run(&mut self, world: &mut World, resource: &mut Resources);
+
+    /// Called once before the first `run`, even if systems are added to the schedule later. Useful for inserting the
+    /// resources this system depends on.
+    fn initialize(&mut self, _world: &mut World, _resources: &mut Resources) {}
 }
 
 impl<F> ThreadLocalSystemFn for F
@@ -412,13 +424,16 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{IntoForEachSystem, IntoQuerySystem, Query};
+    use super::{
+        IntoForEachSystem, IntoQuerySystem, IntoThreadLocalSystem, Query, ThreadLocalSystemFn,
+    };
     use crate::{
-        resource::{ResMut, Resources},
+        resource::{Local, Res, ResMut, Resources},
         schedule::Schedule,
-        ChangedRes, Mut, QuerySet,
+        ChangedRes, Mut, QuerySet, TypeAccess,
     };
     use bevy_hecs::{Entity, With, World};
+    use std::any::TypeId;
 
     #[derive(Debug, Eq, PartialEq)]
     struct A;
@@ -552,6 +567,118 @@ mod tests {
         assert_eq!(*(world.get::<i32>(ent).unwrap()), 2);
     }
 
+    #[test]
+    fn local_system_state() {
+        fn count(mut local: Local<u32>, mut total: ResMut<u32>) {
+            *local += 1;
+            *total += 1;
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(0u32);
+
+        let count_a = count.system();
+        let count_b = count.system();
+        let (id_a, id_b) = (count_a.id(), count_b.id());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", count_a);
+        schedule.add_system_to_stage("update", count_b);
+
+        schedule.run(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+
+        assert_eq!(*resources.get::<u32>().unwrap(), 4);
+        assert_eq!(*resources.get_local::<u32>(id_a).unwrap(), 2);
+        assert_eq!(*resources.get_local::<u32>(id_b).unwrap(), 2);
+    }
+
+    #[test]
+    fn local_resource_access() {
+        fn sys(_local: Local<u32>, _value: Res<f32>) {}
+
+        let system = sys.system();
+        let mut global_u32_write = TypeAccess::default();
+        global_u32_write.add_write(TypeId::of::<u32>());
+        assert!(
+            system.resource_access().is_compatible(&global_u32_write),
+            "Local<u32> should not conflict with the global u32 resource"
+        );
+
+        let mut global_f32_write = TypeAccess::default();
+        global_f32_write.add_write(TypeId::of::<f32>());
+        assert!(!system.resource_access().is_compatible(&global_f32_write));
+    }
+
+    #[test]
+    fn thread_local_system() {
+        struct SpawnA {
+            spawned: usize,
+        }
+
+        impl ThreadLocalSystemFn for SpawnA {
+            fn initialize(&mut self, _world: &mut World, resources: &mut Resources) {
+                resources.insert(0usize);
+            }
+
+            fn run(&mut self, world: &mut World, resources: &mut Resources) {
+                world.spawn((A,));
+                self.spawned += 1;
+                *resources.get_mut::<usize>().unwrap() = self.spawned;
+            }
+        }
+
+        fn count_a(mut count: ResMut<usize>, query: Query<&A>) {
+            assert_eq!(query.iter().count(), *count);
+            *count += 1;
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", SpawnA { spawned: 0 }.thread_local_system());
+        schedule.add_system_to_stage("update", count_a.system());
+
+        schedule.run(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+
+        assert_eq!(world.query::<&A>().count(), 2);
+        assert_eq!(*resources.get::<usize>().unwrap(), 3);
+    }
+
+    #[test]
+    fn thread_local_systems_are_initialized_once() {
+        struct CountInitializations;
+
+        impl ThreadLocalSystemFn for CountInitializations {
+            fn initialize(&mut self, _world: &mut World, resources: &mut Resources) {
+                *resources.get_mut::<usize>().unwrap() += 1;
+            }
+
+            fn run(&mut self, _world: &mut World, _resources: &mut Resources) {}
+        }
+
+        fn empty() {}
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(0usize);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", CountInitializations.thread_local_system());
+        schedule.run(&mut world, &mut resources);
+
+        // adding a system initializes the schedule's systems again
+        schedule.add_system_to_stage("update", empty.system());
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 1);
+    }
+
     #[test]
     #[should_panic]
     fn conflicting_query_mut_system() {
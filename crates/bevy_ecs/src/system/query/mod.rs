@@ -59,6 +59,9 @@ impl<'a, Q: HecsQuery> Query<'a, Q> {
         self.world.query_unchecked()
     }
 
+    /// Returns a [ParallelIterator] over the query results, split into batches of `batch_size` entities.
+    /// Each batch can be processed on a different thread of a [TaskPool](bevy_tasks::TaskPool).
+    /// This can only be called for read-only queries
     #[inline]
     pub fn par_iter(&self, batch_size: usize) -> ParIter<'_, Q>
     where
@@ -68,6 +71,8 @@ impl<'a, Q: HecsQuery> Query<'a, Q> {
         unsafe { ParIter::new(self.world.query_batched_unchecked(batch_size)) }
     }
 
+    /// Returns a [ParallelIterator] over the query results, split into batches of `batch_size` entities.
+    /// Each batch can be processed on a different thread of a [TaskPool](bevy_tasks::TaskPool).
     #[inline]
     pub fn par_iter_mut(&mut self, batch_size: usize) -> ParIter<'_, Q> {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
@@ -204,3 +209,66 @@ impl<'w, Q: HecsQuery> ParallelIterator<Batch<'w, Q>> for ParIter<'w, Q> {
         self.batched_iter.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        resource::{Res, ResMut, Resources},
+        schedule::Schedule,
+        system::{IntoQuerySystem, Query},
+        Changed, TypeAccess,
+    };
+    use bevy_hecs::World;
+    use bevy_tasks::{ComputeTaskPool, ParallelIterator, TaskPool};
+
+    #[test]
+    fn par_iter() {
+        fn increment(pool: Res<ComputeTaskPool>, mut query: Query<&mut u32>) {
+            query
+                .par_iter_mut(4)
+                .for_each(&pool, |mut value| *value += 1);
+        }
+
+        fn sum(pool: Res<ComputeTaskPool>, mut total: ResMut<u64>, query: Query<&u32>) {
+            *total = query
+                .par_iter(4)
+                .map(|value| *value as u64)
+                .collect::<Vec<u64>>(&pool)
+                .iter()
+                .sum();
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(ComputeTaskPool(TaskPool::new()));
+        resources.insert(0u64);
+        world.spawn_batch((0..100u32).map(|i| (i,)));
+        world.spawn_batch((0..10u32).map(|i| (i, 0.0f32)));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", increment.system());
+        schedule.add_system_to_stage("update", sum.system());
+        schedule.run(&mut world, &mut resources);
+
+        let expected = (1..=100u64).sum::<u64>() + (1..=10u64).sum::<u64>();
+        assert_eq!(*resources.get::<u64>().unwrap(), expected);
+    }
+
+    #[test]
+    fn par_iter_changed() {
+        let mut world = World::default();
+        let entities = world
+            .spawn_batch((0..20u32).map(|i| (i,)))
+            .collect::<Vec<_>>();
+        world.clear_trackers();
+        for entity in entities.iter().step_by(3) {
+            *world.get_mut::<u32>(*entity).unwrap() += 1;
+        }
+
+        let pool = TaskPool::new();
+        let component_access = TypeAccess::default();
+        let query = Query::<Changed<u32>>::new(&world, &component_access);
+        assert_eq!(query.par_iter(4).count(&pool), 7);
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use bevy_asset::{AssetIoError, AssetLoader, AssetPath, LoadContext, LoadedAsset};
+use bevy_core::Name;
 use bevy_ecs::{bevy_utils::BoxedFuture, World, WorldBuilderSource};
 use bevy_math::Mat4;
 use bevy_pbr::prelude::{PbrComponents, StandardMaterial};
@@ -107,6 +108,13 @@ async fn load_gltf<'a, 'b>(
                     mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vertex_attribute);
                 }
 
+                if let Some(vertex_attribute) = reader
+                    .read_tex_coords(1)
+                    .map(|v| VertexAttributeValues::Float2(v.into_f32().collect()))
+                {
+                    mesh.set_attribute(Mesh::ATTRIBUTE_UV_1, vertex_attribute);
+                }
+
                 if let Some(indices) = reader.read_indices() {
                     mesh.set_indices(Some(Indices::U32(indices.into_u32().collect())));
                 };
@@ -140,6 +148,7 @@ async fn load_gltf<'a, 'b>(
                     size: bevy_math::f32::vec2(size.0 as f32, size.1 as f32),
                     format: TextureFormat::Rgba8Unorm,
                     sampler: texture_sampler(&texture)?,
+                    cubemap: false,
                 }),
             );
         }
@@ -211,42 +220,44 @@ fn load_node(
 ) -> Result<(), GltfError> {
     let transform = node.transform();
     let mut gltf_error = None;
-    world_builder
-        .spawn((
-            Transform::from_matrix(Mat4::from_cols_array_2d(&transform.matrix())),
-            GlobalTransform::default(),
-        ))
-        .with_children(|parent| {
-            if let Some(mesh) = node.mesh() {
-                for primitive in mesh.primitives() {
-                    let primitive_label = primitive_label(&mesh, &primitive);
-                    let mesh_asset_path =
-                        AssetPath::new_ref(load_context.path(), Some(&primitive_label));
-                    let material = primitive.material();
-                    let material_label = material_label(&material);
-                    let material_asset_path =
-                        AssetPath::new_ref(load_context.path(), Some(&material_label));
-                    parent.spawn(PbrComponents {
-                        mesh: load_context.get_handle(mesh_asset_path),
-                        material: load_context.get_handle(material_asset_path),
-                        ..Default::default()
-                    });
-                }
+    world_builder.spawn((
+        Transform::from_matrix(Mat4::from_cols_array_2d(&transform.matrix())),
+        GlobalTransform::default(),
+    ));
+    if let Some(name) = node.name() {
+        world_builder.with(Name::new(name));
+    }
+    world_builder.with_children(|parent| {
+        if let Some(mesh) = node.mesh() {
+            for primitive in mesh.primitives() {
+                let primitive_label = primitive_label(&mesh, &primitive);
+                let mesh_asset_path =
+                    AssetPath::new_ref(load_context.path(), Some(&primitive_label));
+                let material = primitive.material();
+                let material_label = material_label(&material);
+                let material_asset_path =
+                    AssetPath::new_ref(load_context.path(), Some(&material_label));
+                parent.spawn(PbrComponents {
+                    mesh: load_context.get_handle(mesh_asset_path),
+                    material: load_context.get_handle(material_asset_path),
+                    ..Default::default()
+                });
             }
+        }
 
-            if parent.current_entity().is_none() {
-                return;
-            }
+        if parent.current_entity().is_none() {
+            return;
+        }
 
-            parent.with_children(|parent| {
-                for child in node.children() {
-                    if let Err(err) = load_node(&child, parent, load_context, buffer_data) {
-                        gltf_error = Some(err);
-                        return;
-                    }
+        parent.with_children(|parent| {
+            for child in node.children() {
+                if let Err(err) = load_node(&child, parent, load_context, buffer_data) {
+                    gltf_error = Some(err);
+                    return;
                 }
-            });
+            }
         });
+    });
     if let Some(err) = gltf_error {
         Err(err)
     } else {
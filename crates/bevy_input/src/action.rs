@@ -0,0 +1,220 @@
+use crate::{
+    gamepad::{GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, Input,
+};
+use bevy_ecs::{Res, ResMut};
+use bevy_utils::{HashMap, HashSet};
+
+/// A button-like input that can be bound to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActionBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    GamepadButton(GamepadButton),
+}
+
+/// An input that can be bound to an axis. Axis values are in the range -1.0 to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisBinding {
+    /// Two keys, where `negative` maps to -1.0 and `positive` maps to 1.0
+    Keys {
+        negative: KeyCode,
+        positive: KeyCode,
+    },
+    GamepadAxis(GamepadAxis),
+}
+
+/// Binds named actions (ex: "Jump") and axes (ex: "MoveX") to inputs. Bindings can be changed at runtime, and the
+/// map can be (de)serialized with the `serialize` feature to load and save user configuration.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap {
+    pub actions: HashMap<String, Vec<ActionBinding>>,
+    pub axes: HashMap<String, Vec<AxisBinding>>,
+}
+
+impl InputMap {
+    pub fn bind_action(&mut self, action: impl Into<String>, binding: ActionBinding) -> &mut Self {
+        self.actions
+            .entry(action.into())
+            .or_insert_with(Vec::new)
+            .push(binding);
+        self
+    }
+
+    pub fn bind_axis(&mut self, axis: impl Into<String>, binding: AxisBinding) -> &mut Self {
+        self.axes
+            .entry(axis.into())
+            .or_insert_with(Vec::new)
+            .push(binding);
+        self
+    }
+
+    /// Removes all bindings of the given action
+    pub fn clear_action(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+
+    /// Removes all bindings of the given axis
+    pub fn clear_axis(&mut self, axis: &str) {
+        self.axes.remove(axis);
+    }
+}
+
+/// The state of the actions and axes in the [InputMap], updated every frame by [action_system]
+#[derive(Debug, Default)]
+pub struct Actions {
+    pressed: HashSet<String>,
+    just_pressed: HashSet<String>,
+    just_released: HashSet<String>,
+    axes: HashMap<String, f32>,
+}
+
+impl Actions {
+    pub fn pressed(&self, action: &str) -> bool {
+        self.pressed.contains(action)
+    }
+
+    pub fn just_pressed(&self, action: &str) -> bool {
+        self.just_pressed.contains(action)
+    }
+
+    pub fn just_released(&self, action: &str) -> bool {
+        self.just_released.contains(action)
+    }
+
+    /// Returns the value of the given axis, or 0.0 if none of its bindings are active
+    pub fn axis(&self, axis: &str) -> f32 {
+        self.axes.get(axis).copied().unwrap_or(0.0)
+    }
+}
+
+/// Updates the [Actions] resource from the current input state and the [InputMap]
+pub fn action_system(
+    input_map: Res<InputMap>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    gamepad_button_input: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut actions: ResMut<Actions>,
+) {
+    let actions = &mut *actions;
+    actions.just_pressed.clear();
+    actions.just_released.clear();
+
+    for (action, bindings) in input_map.actions.iter() {
+        let pressed = bindings.iter().any(|binding| match binding {
+            ActionBinding::Key(key_code) => keyboard_input.pressed(*key_code),
+            ActionBinding::Mouse(mouse_button) => mouse_button_input.pressed(*mouse_button),
+            ActionBinding::GamepadButton(button) => gamepad_button_input.pressed(*button),
+        });
+        let was_pressed = actions.pressed.contains(action);
+        if pressed && !was_pressed {
+            actions.pressed.insert(action.clone());
+            actions.just_pressed.insert(action.clone());
+        } else if !pressed && was_pressed {
+            actions.pressed.remove(action);
+            actions.just_released.insert(action.clone());
+        }
+    }
+    // actions that were unbound while pressed are released
+    let released = actions
+        .pressed
+        .iter()
+        .filter(|action| !input_map.actions.contains_key(*action))
+        .cloned()
+        .collect::<Vec<_>>();
+    for action in released {
+        actions.pressed.remove(&action);
+        actions.just_released.insert(action);
+    }
+
+    actions.axes.clear();
+    for (axis, bindings) in input_map.axes.iter() {
+        let value = bindings
+            .iter()
+            .map(|binding| match binding {
+                AxisBinding::Keys { negative, positive } => {
+                    let mut value = 0.0;
+                    if keyboard_input.pressed(*negative) {
+                        value -= 1.0;
+                    }
+                    if keyboard_input.pressed(*positive) {
+                        value += 1.0;
+                    }
+                    value
+                }
+                AxisBinding::GamepadAxis(gamepad_axis) => {
+                    gamepad_axes.get(*gamepad_axis).unwrap_or(0.0)
+                }
+            })
+            .sum::<f32>();
+        actions.axes.insert(axis.clone(), value.max(-1.0).min(1.0));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_ecs::{IntoQuerySystem, Resources, Schedule, World};
+
+    #[test]
+    fn action_state() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut input_map = InputMap::default();
+        input_map
+            .bind_action("Jump", ActionBinding::Key(KeyCode::Space))
+            .bind_action("Jump", ActionBinding::Mouse(MouseButton::Left))
+            .bind_axis(
+                "MoveX",
+                AxisBinding::Keys {
+                    negative: KeyCode::A,
+                    positive: KeyCode::D,
+                },
+            );
+        resources.insert(input_map);
+        resources.insert(Input::<KeyCode>::default());
+        resources.insert(Input::<MouseButton>::default());
+        resources.insert(Input::<GamepadButton>::default());
+        resources.insert(Axis::<GamepadAxis>::default());
+        resources.insert(Actions::default());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", action_system.system());
+
+        resources
+            .get_mut::<Input<MouseButton>>()
+            .unwrap()
+            .press(MouseButton::Left);
+        resources
+            .get_mut::<Input<KeyCode>>()
+            .unwrap()
+            .press(KeyCode::D);
+        schedule.run(&mut world, &mut resources);
+        {
+            let actions = resources.get::<Actions>().unwrap();
+            assert!(actions.pressed("Jump"));
+            assert!(actions.just_pressed("Jump"));
+            assert_eq!(actions.axis("MoveX"), 1.0);
+            assert_eq!(actions.axis("MoveY"), 0.0);
+        }
+
+        schedule.run(&mut world, &mut resources);
+        assert!(!resources.get::<Actions>().unwrap().just_pressed("Jump"));
+
+        resources
+            .get_mut::<InputMap>()
+            .unwrap()
+            .clear_action("Jump");
+        schedule.run(&mut world, &mut resources);
+        let actions = resources.get::<Actions>().unwrap();
+        assert!(!actions.pressed("Jump"));
+        assert!(actions.just_released("Jump"));
+    }
+}
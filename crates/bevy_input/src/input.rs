@@ -1,7 +1,9 @@
 use bevy_utils::HashSet;
 use std::hash::Hash;
 
-/// A "press-able" input of type `T`
+/// A "press-able" input of type `T`. The [InputPlugin](crate::InputPlugin) keeps `Input<KeyCode>`,
+/// `Input<MouseButton>` and `Input<GamepadButton>` resources up to date, so systems can query their state directly
+/// instead of reading the raw input events.
 #[derive(Debug)]
 pub struct Input<T> {
     pressed: HashSet<T>,
@@ -35,15 +37,31 @@ where
         self.pressed.contains(&input)
     }
 
+    /// Returns true if any of the given inputs are pressed
+    pub fn any_pressed(&self, inputs: impl IntoIterator<Item = T>) -> bool {
+        inputs.into_iter().any(|input| self.pressed(input))
+    }
+
     pub fn release(&mut self, input: T) {
-        self.pressed.remove(&input);
-        self.just_released.insert(input);
+        if self.pressed.remove(&input) {
+            self.just_released.insert(input);
+        }
+    }
+
+    /// Releases every pressed input, ex: when the window loses focus and release events will not be received
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
     }
 
     pub fn just_pressed(&self, input: T) -> bool {
         self.just_pressed.contains(&input)
     }
 
+    /// Returns true if any of the given inputs were pressed since the last update
+    pub fn any_just_pressed(&self, inputs: impl IntoIterator<Item = T>) -> bool {
+        inputs.into_iter().any(|input| self.just_pressed(input))
+    }
+
     pub fn just_released(&self, input: T) -> bool {
         self.just_released.contains(&input)
     }
@@ -71,3 +89,42 @@ where
         self.just_released.iter()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Input;
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    enum Button {
+        A,
+        B,
+    }
+
+    #[test]
+    fn press_and_release() {
+        let mut input = Input::<Button>::default();
+        input.press(Button::A);
+        assert!(input.pressed(Button::A));
+        assert!(input.just_pressed(Button::A));
+        assert!(input.any_just_pressed(vec![Button::B, Button::A]));
+
+        input.update();
+        assert!(input.pressed(Button::A));
+        assert!(!input.just_pressed(Button::A));
+
+        // releasing an input that isn't pressed does nothing
+        input.release(Button::B);
+        assert!(!input.just_released(Button::B));
+
+        input.release(Button::A);
+        assert!(!input.pressed(Button::A));
+        assert!(input.just_released(Button::A));
+
+        input.update();
+        input.press(Button::A);
+        input.press(Button::B);
+        input.release_all();
+        assert!(!input.any_pressed(vec![Button::A, Button::B]));
+        assert_eq!(input.get_just_released().len(), 2);
+    }
+}
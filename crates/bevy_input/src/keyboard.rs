@@ -2,12 +2,20 @@ use crate::{ElementState, Input};
 use bevy_app::prelude::*;
 use bevy_ecs::{Local, Res, ResMut};
 
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
 /// A key input event from a keyboard device
 #[derive(Debug, Clone)]
 pub struct KeyboardInput {
     pub scan_code: u32,
     pub key_code: Option<KeyCode>,
     pub state: ElementState,
+    /// When the app received the event from the OS. See [InputPlugin](crate::InputPlugin) for how this relates to
+    /// update timing.
+    pub timestamp: Instant,
 }
 
 /// State used by the keyboard input system
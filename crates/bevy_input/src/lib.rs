@@ -1,11 +1,15 @@
+mod action;
 mod axis;
 pub mod gamepad;
 mod input;
 pub mod keyboard;
 pub mod mouse;
+#[cfg(feature = "serialize")]
+pub mod recording;
 pub mod system;
 pub mod touch;
 
+pub use action::*;
 pub use axis::*;
 pub use input::*;
 
@@ -18,7 +22,7 @@ pub mod prelude {
         keyboard::KeyCode,
         mouse::MouseButton,
         touch::{TouchInput, Touches},
-        Axis, Input,
+        ActionBinding, Actions, Axis, AxisBinding, Input, InputMap,
     };
 }
 
@@ -35,6 +39,12 @@ use gamepad::{
 };
 
 /// Adds keyboard and mouse input to an App
+///
+/// Keyboard, mouse, touch and window events carry a `timestamp` of when winit delivered them to the app, which is
+/// usually earlier than the update that handles them. It isn't when the OS generated the event, which winit doesn't
+/// report, so events that wait in the OS queue while an update runs get a later timestamp. Compare it with `Time::instant`, the start of the current update, to
+/// find out how long ago an input happened. Events of each type are delivered in the order they were received and
+/// their timestamps never decrease, so events of different types can be merged back into one sequence by timestamp.
 #[derive(Default)]
 pub struct InputPlugin;
 
@@ -58,12 +68,16 @@ impl Plugin for InputPlugin {
             .add_startup_system_to_stage(STARTUP, gamepad_event_system.system())
             .add_event::<TouchInput>()
             .init_resource::<Touches>()
-            .add_system_to_stage(bevy_app::stage::EVENT, touch_screen_input_system.system());
+            .add_system_to_stage(bevy_app::stage::EVENT, touch_screen_input_system.system())
+            .init_resource::<InputMap>()
+            .init_resource::<Actions>()
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, action_system.system());
     }
 }
 
 /// The current "press" state of an element
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum ElementState {
     Pressed,
     Released,
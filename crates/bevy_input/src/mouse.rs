@@ -3,11 +3,19 @@ use bevy_app::prelude::{EventReader, Events};
 use bevy_ecs::{Local, Res, ResMut};
 use bevy_math::Vec2;
 
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
 /// A mouse button input event
 #[derive(Debug, Clone)]
 pub struct MouseButtonInput {
     pub button: MouseButton,
     pub state: ElementState,
+    /// When the app received the event from the OS. See [InputPlugin](crate::InputPlugin) for how this relates to
+    /// update timing.
+    pub timestamp: Instant,
 }
 
 /// A button on a mouse device
@@ -24,10 +32,15 @@ pub enum MouseButton {
 #[derive(Debug, Clone)]
 pub struct MouseMotion {
     pub delta: Vec2,
+    /// When the app received the event from the OS. See [InputPlugin](crate::InputPlugin) for how this relates to
+    /// update timing.
+    pub timestamp: Instant,
 }
 
-/// Unit of scroll
-#[derive(Debug, Clone, Copy)]
+/// Unit of scroll. Mouse wheels usually scroll in lines, while trackpads and other high precision devices scroll in
+/// pixels.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseScrollUnit {
     Line,
     Pixel,
@@ -39,6 +52,32 @@ pub struct MouseWheel {
     pub unit: MouseScrollUnit,
     pub x: f32,
     pub y: f32,
+    /// When the app received the event from the OS. See [InputPlugin](crate::InputPlugin) for how this relates to
+    /// update timing.
+    pub timestamp: Instant,
+}
+
+impl MouseWheel {
+    /// A reasonable default for the number of pixels scrolled by one line
+    pub const DEFAULT_PIXELS_PER_LINE: f32 = 20.0;
+
+    /// Returns the scroll delta in lines, converting pixel deltas using the given number of pixels per line. Use this
+    /// for stepped behavior like zooming, so mouse wheels and trackpads move at a similar speed.
+    pub fn lines(&self, pixels_per_line: f32) -> Vec2 {
+        match self.unit {
+            MouseScrollUnit::Line => Vec2::new(self.x, self.y),
+            MouseScrollUnit::Pixel => Vec2::new(self.x, self.y) / pixels_per_line,
+        }
+    }
+
+    /// Returns the scroll delta in pixels, converting line deltas using the given number of pixels per line. Use this
+    /// for smooth behavior like scrolling a list.
+    pub fn pixels(&self, pixels_per_line: f32) -> Vec2 {
+        match self.unit {
+            MouseScrollUnit::Line => Vec2::new(self.x, self.y) * pixels_per_line,
+            MouseScrollUnit::Pixel => Vec2::new(self.x, self.y),
+        }
+    }
 }
 
 /// State used by the mouse button input system
@@ -0,0 +1,615 @@
+use crate::{
+    gamepad::GamepadEventRaw,
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    touch::{ForceTouch, TouchInput, TouchPhase},
+    ElementState,
+};
+use bevy_app::{prelude::*, AppExit};
+use bevy_ecs::{IntoThreadLocalSystem, Resource, Resources, World};
+use bevy_math::Vec2;
+use bevy_window::{
+    CursorMoved, FileDragAndDrop, ReceivedCharacter, WindowCloseRequested, WindowId, WindowResized,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// Records the input and window events of each frame to a file, or replays a recording by sending its events through
+/// the same [Events] the OS input goes through, so gameplay and UI flows can be tested without anyone at the keyboard.
+/// The recording is only available with the `serialize` feature.
+///
+/// Events are replayed in the frame (app update) they were recorded in, counted from the first update. Window events
+/// are only recorded for the primary window, and replayed events don't change the window itself: a replayed
+/// [WindowResized] doesn't resize it. Live input still reaches the app during playback. For the replay to match the
+/// recording, the app has to behave the same for the same input, for example by using a fixed time step.
+#[derive(Debug, Clone)]
+pub struct InputRecordingPlugin {
+    pub mode: InputRecordingMode,
+    /// The recording file
+    pub path: PathBuf,
+    /// Whether playback sends [AppExit] after the last recorded frame
+    pub exit_when_finished: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputRecordingMode {
+    Record,
+    Playback,
+}
+
+impl InputRecordingPlugin {
+    /// Records to `path`, replacing an existing recording
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        InputRecordingPlugin {
+            mode: InputRecordingMode::Record,
+            path: path.into(),
+            exit_when_finished: false,
+        }
+    }
+
+    /// Replays the recording at `path`, and exits the app once it is done
+    pub fn playback(path: impl Into<PathBuf>) -> Self {
+        InputRecordingPlugin {
+            mode: InputRecordingMode::Playback,
+            path: path.into(),
+            exit_when_finished: true,
+        }
+    }
+}
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        match self.mode {
+            InputRecordingMode::Record => {
+                let recorder = InputRecorder::create(&self.path).unwrap_or_else(|err| {
+                    panic!(
+                        "Failed to create the input recording {:?}: {}",
+                        self.path, err
+                    )
+                });
+                // after the events of this update were sent, including gamepad events sent in PRE_EVENT
+                app.add_resource(recorder).add_system_to_stage(
+                    stage::PRE_UPDATE,
+                    input_recorder_system.thread_local_system(),
+                );
+            }
+            InputRecordingMode::Playback => {
+                let mut playback = InputPlayback::load(&self.path).unwrap_or_else(|err| {
+                    panic!(
+                        "Failed to load the input recording {:?}: {}",
+                        self.path, err
+                    )
+                });
+                playback.exit_when_finished = self.exit_when_finished;
+                // before the input systems in the EVENT stage handle the events
+                app.add_resource(playback).add_system_to_stage(
+                    stage::PRE_EVENT,
+                    input_playback_system.thread_local_system(),
+                );
+            }
+        }
+    }
+}
+
+/// An input or window event in a recording, without its timestamp and window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedEvent {
+    Keyboard {
+        scan_code: u32,
+        key_code: Option<KeyCode>,
+        state: ElementState,
+    },
+    MouseButton {
+        button: MouseButton,
+        state: ElementState,
+    },
+    MouseMotion {
+        delta: (f32, f32),
+    },
+    MouseWheel {
+        unit: MouseScrollUnit,
+        x: f32,
+        y: f32,
+    },
+    Touch {
+        phase: TouchPhase,
+        position: (f32, f32),
+        force: Option<ForceTouch>,
+        id: u64,
+    },
+    Gamepad(GamepadEventRaw),
+    CursorMoved {
+        position: (f32, f32),
+    },
+    ReceivedCharacter(char),
+    WindowResized {
+        width: f32,
+        height: f32,
+    },
+    DroppedFile(PathBuf),
+    HoveredFile(PathBuf),
+    HoveredFileCancelled,
+    WindowCloseRequested,
+}
+
+/// The events of one frame of a recording. Recordings store one frame per line, and skip frames without events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    pub frame: u64,
+    pub events: Vec<RecordedEvent>,
+}
+
+#[derive(Debug)]
+pub enum InputRecordingError {
+    Io(io::Error),
+    Parse { line: usize, error: ron::Error },
+}
+
+impl fmt::Display for InputRecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputRecordingError::Io(err) => write!(f, "{}", err),
+            InputRecordingError::Parse { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for InputRecordingError {}
+
+impl From<io::Error> for InputRecordingError {
+    fn from(err: io::Error) -> Self {
+        InputRecordingError::Io(err)
+    }
+}
+
+#[derive(Default)]
+struct RecorderEventReaders {
+    keyboard: EventReader<KeyboardInput>,
+    mouse_button: EventReader<MouseButtonInput>,
+    mouse_motion: EventReader<MouseMotion>,
+    mouse_wheel: EventReader<MouseWheel>,
+    touch: EventReader<TouchInput>,
+    gamepad: EventReader<GamepadEventRaw>,
+    cursor_moved: EventReader<CursorMoved>,
+    received_character: EventReader<ReceivedCharacter>,
+    window_resized: EventReader<WindowResized>,
+    file_drag_and_drop: EventReader<FileDragAndDrop>,
+    window_close_requested: EventReader<WindowCloseRequested>,
+}
+
+/// Writes the events of each frame to the recording file. When it is dropped, it writes the last frame, so playback
+/// lasts as long as the recorded session.
+pub struct InputRecorder {
+    frame: u64,
+    writer: BufWriter<File>,
+    readers: RecorderEventReaders,
+}
+
+impl InputRecorder {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(InputRecorder {
+            frame: 0,
+            writer: BufWriter::new(File::create(path)?),
+            readers: Default::default(),
+        })
+    }
+
+    fn write_frame(&mut self, events: Vec<RecordedEvent>) -> io::Result<()> {
+        let frame = RecordedFrame {
+            frame: self.frame,
+            events,
+        };
+        let line = ron::ser::to_string(&frame)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        writeln!(self.writer, "{}", line)?;
+        // flushed every frame, so a crash doesn't lose the input that led to it
+        self.writer.flush()
+    }
+}
+
+impl Drop for InputRecorder {
+    fn drop(&mut self) {
+        let _ = self.write_frame(Vec::new());
+    }
+}
+
+/// Replays a recording frame by frame
+pub struct InputPlayback {
+    frame: u64,
+    frames: VecDeque<RecordedFrame>,
+    exit_when_finished: bool,
+}
+
+impl InputPlayback {
+    pub fn load(path: &Path) -> Result<Self, InputRecordingError> {
+        let mut frames = VecDeque::new();
+        for (index, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame = ron::de::from_str(&line).map_err(|error| InputRecordingError::Parse {
+                line: index + 1,
+                error,
+            })?;
+            frames.push_back(frame);
+        }
+        Ok(InputPlayback {
+            frame: 0,
+            frames,
+            exit_when_finished: false,
+        })
+    }
+
+    /// Whether every recorded frame was replayed
+    pub fn is_finished(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+fn record<T: Resource>(
+    reader: &mut EventReader<T>,
+    resources: &Resources,
+    recorded_events: &mut Vec<RecordedEvent>,
+    to_recorded_event: impl Fn(&T) -> Option<RecordedEvent>,
+) {
+    if let Some(events) = resources.get::<Events<T>>() {
+        recorded_events.extend(reader.iter(&events).filter_map(to_recorded_event));
+    }
+}
+
+fn send<T: Resource>(resources: &Resources, event: T) {
+    if let Some(mut events) = resources.get_mut::<Events<T>>() {
+        events.send(event);
+    }
+}
+
+fn primary_window(id: &WindowId) -> Option<()> {
+    if id.is_primary() {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// Records the events of the current frame
+pub fn input_recorder_system(_world: &mut World, resources: &mut Resources) {
+    let mut recorder = resources.get_mut::<InputRecorder>().unwrap();
+    let readers = &mut recorder.readers;
+    let mut events = Vec::new();
+    record(&mut readers.keyboard, resources, &mut events, |event| {
+        Some(RecordedEvent::Keyboard {
+            scan_code: event.scan_code,
+            key_code: event.key_code,
+            state: event.state.clone(),
+        })
+    });
+    record(&mut readers.mouse_button, resources, &mut events, |event| {
+        Some(RecordedEvent::MouseButton {
+            button: event.button,
+            state: event.state.clone(),
+        })
+    });
+    record(&mut readers.mouse_motion, resources, &mut events, |event| {
+        Some(RecordedEvent::MouseMotion {
+            delta: (event.delta.x(), event.delta.y()),
+        })
+    });
+    record(&mut readers.mouse_wheel, resources, &mut events, |event| {
+        Some(RecordedEvent::MouseWheel {
+            unit: event.unit,
+            x: event.x,
+            y: event.y,
+        })
+    });
+    record(&mut readers.touch, resources, &mut events, |event| {
+        Some(RecordedEvent::Touch {
+            phase: event.phase,
+            position: (event.position.x(), event.position.y()),
+            force: event.force,
+            id: event.id,
+        })
+    });
+    record(&mut readers.gamepad, resources, &mut events, |event| {
+        Some(RecordedEvent::Gamepad(event.clone()))
+    });
+    record(&mut readers.cursor_moved, resources, &mut events, |event| {
+        primary_window(&event.id).map(|_| RecordedEvent::CursorMoved {
+            position: (event.position.x(), event.position.y()),
+        })
+    });
+    record(
+        &mut readers.received_character,
+        resources,
+        &mut events,
+        |event| primary_window(&event.id).map(|_| RecordedEvent::ReceivedCharacter(event.char)),
+    );
+    record(
+        &mut readers.window_resized,
+        resources,
+        &mut events,
+        |event| {
+            primary_window(&event.id).map(|_| RecordedEvent::WindowResized {
+                width: event.width,
+                height: event.height,
+            })
+        },
+    );
+    record(
+        &mut readers.file_drag_and_drop,
+        resources,
+        &mut events,
+        |event| match event {
+            FileDragAndDrop::DroppedFile { id, path_buf, .. } => {
+                primary_window(id).map(|_| RecordedEvent::DroppedFile(path_buf.clone()))
+            }
+            FileDragAndDrop::HoveredFile { id, path_buf, .. } => {
+                primary_window(id).map(|_| RecordedEvent::HoveredFile(path_buf.clone()))
+            }
+            FileDragAndDrop::HoveredFileCancelled { id, .. } => {
+                primary_window(id).map(|_| RecordedEvent::HoveredFileCancelled)
+            }
+        },
+    );
+    record(
+        &mut readers.window_close_requested,
+        resources,
+        &mut events,
+        |event| primary_window(&event.id).map(|_| RecordedEvent::WindowCloseRequested),
+    );
+
+    if !events.is_empty() {
+        recorder
+            .write_frame(events)
+            .expect("Failed to write the input recording");
+    }
+    recorder.frame += 1;
+}
+
+/// Sends the recorded events of the current frame
+pub fn input_playback_system(_world: &mut World, resources: &mut Resources) {
+    let mut playback = resources.get_mut::<InputPlayback>().unwrap();
+    if playback.is_finished() {
+        if playback.exit_when_finished {
+            send(resources, AppExit);
+        }
+        return;
+    }
+
+    let timestamp = Instant::now();
+    let window = WindowId::primary();
+    while playback
+        .frames
+        .front()
+        .map_or(false, |frame| frame.frame <= playback.frame)
+    {
+        let frame = playback.frames.pop_front().unwrap();
+        for event in frame.events {
+            match event {
+                RecordedEvent::Keyboard {
+                    scan_code,
+                    key_code,
+                    state,
+                } => send(
+                    resources,
+                    KeyboardInput {
+                        scan_code,
+                        key_code,
+                        state,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::MouseButton { button, state } => send(
+                    resources,
+                    MouseButtonInput {
+                        button,
+                        state,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::MouseMotion { delta } => send(
+                    resources,
+                    MouseMotion {
+                        delta: Vec2::new(delta.0, delta.1),
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::MouseWheel { unit, x, y } => send(
+                    resources,
+                    MouseWheel {
+                        unit,
+                        x,
+                        y,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::Touch {
+                    phase,
+                    position,
+                    force,
+                    id,
+                } => send(
+                    resources,
+                    TouchInput {
+                        phase,
+                        position: Vec2::new(position.0, position.1),
+                        force,
+                        id,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::Gamepad(event) => send(resources, event),
+                RecordedEvent::CursorMoved { position } => send(
+                    resources,
+                    CursorMoved {
+                        id: window,
+                        position: Vec2::new(position.0, position.1),
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::ReceivedCharacter(char) => send(
+                    resources,
+                    ReceivedCharacter {
+                        id: window,
+                        char,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::WindowResized { width, height } => send(
+                    resources,
+                    WindowResized {
+                        id: window,
+                        width,
+                        height,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::DroppedFile(path_buf) => send(
+                    resources,
+                    FileDragAndDrop::DroppedFile {
+                        id: window,
+                        path_buf,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::HoveredFile(path_buf) => send(
+                    resources,
+                    FileDragAndDrop::HoveredFile {
+                        id: window,
+                        path_buf,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::HoveredFileCancelled => send(
+                    resources,
+                    FileDragAndDrop::HoveredFileCancelled {
+                        id: window,
+                        timestamp,
+                    },
+                ),
+                RecordedEvent::WindowCloseRequested => send(
+                    resources,
+                    WindowCloseRequested {
+                        id: window,
+                        timestamp,
+                    },
+                ),
+            }
+        }
+    }
+    playback.frame += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_playback_system, input_recorder_system, InputPlayback, InputRecorder};
+    use crate::{keyboard::KeyCode, keyboard::KeyboardInput, ElementState};
+    use bevy_app::{EventReader, Events};
+    use bevy_ecs::{Resources, World};
+    use bevy_math::Vec2;
+    use bevy_window::{CursorMoved, WindowId};
+    use std::time::Instant;
+
+    fn event_resources() -> Resources {
+        let mut resources = Resources::default();
+        resources.insert(Events::<KeyboardInput>::default());
+        resources.insert(Events::<CursorMoved>::default());
+        resources
+    }
+
+    #[test]
+    fn playback_sends_events_in_their_recorded_frame() {
+        // unique per run, so tests running at the same time don't share the file
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "bevy_input_recording_test-{}-{}.ron",
+            std::process::id(),
+            nanos
+        ));
+        let mut world = World::default();
+
+        let mut resources = event_resources();
+        resources.insert(InputRecorder::create(&path).unwrap());
+        let frames: [&dyn Fn(&Resources); 3] = [
+            &|resources| {
+                resources
+                    .get_mut::<Events<KeyboardInput>>()
+                    .unwrap()
+                    .send(KeyboardInput {
+                        scan_code: 30,
+                        key_code: Some(KeyCode::A),
+                        state: ElementState::Pressed,
+                        timestamp: Instant::now(),
+                    })
+            },
+            &|_| {},
+            &|resources| {
+                resources
+                    .get_mut::<Events<CursorMoved>>()
+                    .unwrap()
+                    .send(CursorMoved {
+                        id: WindowId::primary(),
+                        position: Vec2::new(10.0, 20.0),
+                        timestamp: Instant::now(),
+                    })
+            },
+        ];
+        for send_input in frames.iter() {
+            send_input(&resources);
+            input_recorder_system(&mut world, &mut resources);
+            resources
+                .get_mut::<Events<KeyboardInput>>()
+                .unwrap()
+                .update();
+            resources.get_mut::<Events<CursorMoved>>().unwrap().update();
+        }
+        // dropping the recorder writes the frame it was dropped in
+        drop(resources);
+
+        let mut resources = event_resources();
+        resources.insert(InputPlayback::load(&path).unwrap());
+        let mut keyboard_reader = EventReader::<KeyboardInput>::default();
+        let mut cursor_reader = EventReader::<CursorMoved>::default();
+        let mut replayed = Vec::new();
+        while !resources.get::<InputPlayback>().unwrap().is_finished() {
+            input_playback_system(&mut world, &mut resources);
+            let keyboard_events = resources.get::<Events<KeyboardInput>>().unwrap();
+            let cursor_events = resources.get::<Events<CursorMoved>>().unwrap();
+            replayed.push((
+                keyboard_reader
+                    .iter(&keyboard_events)
+                    .map(|event| event.key_code)
+                    .collect::<Vec<_>>(),
+                cursor_reader
+                    .iter(&cursor_events)
+                    .map(|event| event.position)
+                    .collect::<Vec<_>>(),
+            ));
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            replayed,
+            vec![
+                (vec![Some(KeyCode::A)], vec![]),
+                (vec![], vec![]),
+                (vec![], vec![Vec2::new(10.0, 20.0)]),
+                (vec![], vec![]),
+            ]
+        );
+    }
+}
@@ -4,6 +4,11 @@ use bevy_math::Vec2;
 use bevy_utils::HashMap;
 use core::ops::DerefMut;
 
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
 /// Represents a touch event
 ///
 /// Every time the user touches the screen, a new `Start` event with an unique
@@ -33,10 +38,14 @@ pub struct TouchInput {
     pub force: Option<ForceTouch>,
     /// Unique identifier of a finger.
     pub id: u64,
+    /// When the app received the event from the OS. See [InputPlugin](crate::InputPlugin) for how this relates to
+    /// update timing.
+    pub timestamp: Instant,
 }
 
 /// Describes the force of a touch event
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum ForceTouch {
     /// On iOS, the force is calibrated so that the same number corresponds to
     /// roughly the same amount of pressure on the screen regardless of the
@@ -102,6 +111,13 @@ impl Touch {
     pub fn distance(&self) -> Vec2 {
         self.position - self.start_position
     }
+
+    fn update(&mut self, event: &TouchInput) {
+        self.previous_position = self.position;
+        self.previous_force = self.force;
+        self.position = event.position;
+        self.force = event.force;
+    }
 }
 
 impl From<&TouchInput> for Touch {
@@ -140,9 +156,11 @@ impl Touches {
     }
 
     pub fn iter_just_pressed(&self) -> impl Iterator<Item = &Touch> + '_ {
+        // prefer the pressed touch, which is up to date if it moved this frame. it won't exist if the touch was
+        // released in the same frame it was pressed
         self.just_pressed
             .iter()
-            .map(move |(id, _)| self.pressed.get(id).unwrap())
+            .map(move |(id, touch)| self.pressed.get(id).unwrap_or(touch))
     }
 
     pub fn get_released(&self, id: u64) -> Option<&Touch> {
@@ -154,19 +172,30 @@ impl Touches {
     }
 
     pub fn iter_just_released(&self) -> impl Iterator<Item = &Touch> + '_ {
-        self.just_released
-            .iter()
-            .map(move |(id, _)| self.pressed.get(id).unwrap())
+        self.just_released.values()
     }
 
     pub fn just_cancelled(&self, id: u64) -> bool {
         self.just_cancelled.contains_key(&id)
     }
 
+    pub fn get_cancelled(&self, id: u64) -> Option<&Touch> {
+        self.just_cancelled.get(&id)
+    }
+
     pub fn iter_just_cancelled(&self) -> impl Iterator<Item = &Touch> + '_ {
-        self.just_cancelled
-            .iter()
-            .map(move |(id, _)| self.pressed.get(id).unwrap())
+        self.just_cancelled.values()
+    }
+
+    /// Removes the touch with the given id and returns it, updated with the final event
+    fn finish_touch(&mut self, event: &TouchInput) -> Touch {
+        match self.pressed.remove(&event.id) {
+            Some(mut touch) => {
+                touch.update(event);
+                touch
+            }
+            None => event.into(),
+        }
     }
 }
 
@@ -179,6 +208,7 @@ pub fn touch_screen_input_system(
     let touch_state = touch_state.deref_mut();
     touch_state.just_pressed.clear();
     touch_state.just_released.clear();
+    touch_state.just_cancelled.clear();
     for event in state.touch_event_reader.iter(&touch_input_events) {
         match event.phase {
             TouchPhase::Started => {
@@ -186,20 +216,20 @@ pub fn touch_screen_input_system(
                 touch_state.just_pressed.insert(event.id, event.into());
             }
             TouchPhase::Moved => {
-                let mut new_touch = touch_state.pressed.get(&event.id).cloned().unwrap();
-                new_touch.previous_position = new_touch.position;
-                new_touch.previous_force = new_touch.force;
-                new_touch.position = event.position;
-                new_touch.force = event.force;
-                touch_state.pressed.insert(event.id, new_touch);
+                // touches that started before the app received input are tracked from their first move
+                touch_state
+                    .pressed
+                    .entry(event.id)
+                    .or_insert_with(|| event.into())
+                    .update(event);
             }
             TouchPhase::Ended => {
-                touch_state.just_released.insert(event.id, event.into());
-                touch_state.pressed.remove_entry(&event.id);
+                let touch = touch_state.finish_touch(event);
+                touch_state.just_released.insert(event.id, touch);
             }
             TouchPhase::Cancelled => {
-                touch_state.just_cancelled.insert(event.id, event.into());
-                touch_state.pressed.remove_entry(&event.id);
+                let touch = touch_state.finish_touch(event);
+                touch_state.just_cancelled.insert(event.id, touch);
             }
         };
     }
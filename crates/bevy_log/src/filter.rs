@@ -0,0 +1,104 @@
+use log::{LevelFilter, Metadata};
+use std::str::FromStr;
+
+/// Decides which log records are written, with a default level and levels for modules. Filters are written like
+/// `RUST_LOG` for `env_logger`: `"warn,bevy_render=debug,wgpu=error"` logs warnings and errors, but also debug
+/// messages from `bevy_render` and its modules, and only errors from `wgpu`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogFilter {
+    pub default_level: LevelFilter,
+    /// Levels of module paths. The most specific one that matches the target of a record applies.
+    pub module_levels: Vec<(String, LevelFilter)>,
+}
+
+impl LogFilter {
+    pub fn new(default_level: LevelFilter) -> Self {
+        LogFilter {
+            default_level,
+            module_levels: Vec::new(),
+        }
+    }
+
+    pub fn with_module_level<S: Into<String>>(mut self, module: S, level: LevelFilter) -> Self {
+        self.module_levels.push((module.into(), level));
+        self
+    }
+
+    /// Parses a filter from comma separated directives, which are either a level or `module=level`. Without a level
+    /// directive, only errors are logged. Invalid directives are ignored.
+    pub fn parse(filter: &str) -> Self {
+        let mut log_filter = LogFilter::new(LevelFilter::Error);
+        for directive in filter.split(',').map(str::trim) {
+            let mut parts = directive.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(level), None) => {
+                    if let Ok(level) = LevelFilter::from_str(level) {
+                        log_filter.default_level = level;
+                    }
+                }
+                (Some(module), Some(level)) => {
+                    if let Ok(level) = LevelFilter::from_str(level.trim()) {
+                        log_filter
+                            .module_levels
+                            .push((module.trim().to_string(), level));
+                    }
+                }
+                _ => {}
+            }
+        }
+        log_filter
+    }
+
+    /// The level that applies to records with the given target, which is their module path unless it was overridden
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.module_levels
+            .iter()
+            .filter(|(module, _)| is_in_module(target, module))
+            .max_by_key(|(module, _)| module.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default_level)
+    }
+
+    pub fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    /// The most verbose level of the filter, to skip formatting records no module wants
+    pub fn max_level(&self) -> LevelFilter {
+        self.module_levels
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default_level, std::cmp::max)
+    }
+}
+
+fn is_in_module(target: &str, module: &str) -> bool {
+    target == module || (target.starts_with(module) && target[module.len()..].starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogFilter;
+    use log::LevelFilter;
+
+    #[test]
+    fn log_filter() {
+        let filter =
+            LogFilter::parse("warn, bevy_render=debug,bevy_render::pipeline=trace,wgpu=off,x=loud");
+        assert_eq!(filter.default_level, LevelFilter::Warn);
+        assert_eq!(filter.module_levels.len(), 3);
+
+        assert_eq!(filter.level_for("bevy_app"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("bevy_render"), LevelFilter::Debug);
+        assert_eq!(filter.level_for("bevy_render::texture"), LevelFilter::Debug);
+        assert_eq!(
+            filter.level_for("bevy_render::pipeline::pipeline_compiler"),
+            LevelFilter::Trace
+        );
+        // a module with the same prefix isn't a submodule
+        assert_eq!(filter.level_for("bevy_renderer"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("wgpu_core"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("wgpu"), LevelFilter::Off);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+    }
+}
@@ -0,0 +1,111 @@
+mod filter;
+
+pub use filter::*;
+pub use log::{debug, error, info, trace, warn, Level, LevelFilter};
+
+pub mod prelude {
+    pub use crate::{debug, error, info, trace, warn};
+}
+
+use bevy_app::prelude::*;
+#[cfg(feature = "trace_chrome")]
+use bevy_ecs::IntoThreadLocalSystem;
+use log::{Log, Metadata, Record};
+
+/// Adds a logger that writes the records of the `log` crate to stderr, or to the browser console on wasm. Which records
+/// are written is decided by the `filter`, or by the `RUST_LOG` environment variable on native platforms when it is
+/// set, see [LogFilter::parse].
+///
+/// Only one logger can be installed, so the plugin does nothing if the app or the platform (like Android) installed
+/// one already.
+///
+/// With the `trace_chrome` feature, the plugin also writes the tracing spans recorded by the `trace` features of the
+/// other crates to a `trace-{timestamp}.json` file in the working directory. The file can be opened in
+/// `chrome://tracing` or imported into Tracy with its `import-chrome` tool.
+#[derive(Debug, Clone)]
+pub struct LogPlugin {
+    pub filter: LogFilter,
+}
+
+impl Default for LogPlugin {
+    fn default() -> Self {
+        LogPlugin {
+            filter: LogFilter::new(LevelFilter::Info).with_module_level("wgpu", LevelFilter::Error),
+        }
+    }
+}
+
+impl Plugin for LogPlugin {
+    #[cfg_attr(not(feature = "trace_chrome"), allow(unused_variables))]
+    fn build(&self, app: &mut AppBuilder) {
+        #[cfg(not(target_arch = "wasm32"))]
+        let filter = std::env::var("RUST_LOG")
+            .map(|filter| LogFilter::parse(&filter))
+            .unwrap_or_else(|_| self.filter.clone());
+        #[cfg(target_arch = "wasm32")]
+        let filter = self.filter.clone();
+
+        let max_level = filter.max_level();
+        if log::set_boxed_logger(Box::new(BevyLogger { filter })).is_ok() {
+            log::set_max_level(max_level);
+        }
+
+        #[cfg(feature = "trace_chrome")]
+        {
+            use tracing_subscriber::layer::SubscriberExt;
+
+            let (chrome_layer, guard) = tracing_chrome::ChromeLayerBuilder::new().build();
+            let subscriber = tracing_subscriber::registry().with(chrome_layer);
+            if tracing::subscriber::set_global_default(subscriber).is_ok() {
+                app.add_thread_local_resource(guard)
+                    .add_system_to_stage(stage::LAST, flush_trace_system.thread_local_system());
+            }
+        }
+    }
+}
+
+/// Writes the spans recorded so far to the trace file. Apps that exit without dropping their resources would otherwise
+/// lose the spans that are still buffered.
+#[cfg(feature = "trace_chrome")]
+fn flush_trace_system(_world: &mut bevy_ecs::World, resources: &mut bevy_ecs::Resources) {
+    if let Some(guard) = resources.get_thread_local::<tracing_chrome::FlushGuard>() {
+        guard.flush();
+    }
+}
+
+struct BevyLogger {
+    filter: LogFilter,
+}
+
+impl Log for BevyLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        eprintln!(
+            "{:<5} {}: {}",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let message = format!("{}: {}", record.target(), record.args()).into();
+            match record.level() {
+                Level::Error => web_sys::console::error_1(&message),
+                Level::Warn => web_sys::console::warn_1(&message),
+                Level::Info => web_sys::console::info_1(&message),
+                Level::Debug | Level::Trace => web_sys::console::debug_1(&message),
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
@@ -0,0 +1,213 @@
+use glam::{Mat4, Vec3};
+
+/// An axis-aligned bounding box, defined by its minimum and maximum corners
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Aabb { min, max }
+    }
+
+    /// The smallest box that contains all of the points, or None if there are no points
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Aabb::new(first, first), |aabb, point| Aabb {
+            min: aabb.min.min(point),
+            max: aabb.max.max(point),
+        }))
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// The smallest box that contains both boxes
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// The box that contains this box after it is transformed by `matrix`
+    pub fn transformed(&self, matrix: &Mat4) -> Aabb {
+        let center = matrix.transform_point3(self.center());
+        let half_extents = self.half_extents();
+        let columns = matrix.to_cols_array_2d();
+        let mut extents = [0.0; 3];
+        for (axis, extent) in extents.iter_mut().enumerate() {
+            *extent = columns[0][axis].abs() * half_extents.x()
+                + columns[1][axis].abs() * half_extents.y()
+                + columns[2][axis].abs() * half_extents.z();
+        }
+        let extents = Vec3::from(extents);
+        Aabb::new(center - extents, center + extents)
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.min.cmple(point).all() && point.cmple(self.max).all()
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+}
+
+/// A half line that starts at `origin` and goes in `direction`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Creates a ray. `direction` is normalized, so distances along the ray are in world units.
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Ray {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// The point at `distance` along the ray
+    pub fn at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// The distance along the ray to where it enters the box, 0 if it starts inside, or None if it misses the box
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        let inverse_direction = Vec3::one() / self.direction;
+        let t1 = (aabb.min - self.origin) * inverse_direction;
+        let t2 = (aabb.max - self.origin) * inverse_direction;
+        let near = t1.min(t2);
+        let far = t1.max(t2);
+        let enter = near.x().max(near.y()).max(near.z()).max(0.0);
+        let exit = far.x().min(far.y()).min(far.z());
+        if enter <= exit {
+            Some(enter)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub fn new(center: Vec3, radius: f32) -> Self {
+        Sphere { center, radius }
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let closest = self.center.max(aabb.min).min(aabb.max);
+        (closest - self.center).length_squared() <= self.radius * self.radius
+    }
+}
+
+/// The points `p` with `normal.dot(p) + d == 0`. Points with a positive distance are in front of the plane.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub d: f32,
+}
+
+impl Plane {
+    /// Creates a plane from the coefficients of `a * x + b * y + c * z + d = 0`, normalized so distances are in world
+    /// units
+    pub fn from_coefficients(a: f32, b: f32, c: f32, d: f32) -> Self {
+        let normal = Vec3::new(a, b, c);
+        let length = normal.length();
+        Plane {
+            normal: normal / length,
+            d: d / length,
+        }
+    }
+
+    /// The signed distance from the plane to `point`
+    pub fn distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) + self.d
+    }
+}
+
+/// The volume a camera sees, as six planes that face inwards
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Frustum {
+    /// The left, right, bottom, top, near and far planes
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// The frustum of a view projection matrix that maps depth to 0..1, like the projections of Bevy cameras
+    pub fn from_view_projection(view_projection: &Mat4) -> Self {
+        let rows = view_projection.transpose().to_cols_array_2d();
+        let plane = |row: [f32; 4]| Plane::from_coefficients(row[0], row[1], row[2], row[3]);
+        let combine = |sign: f32, row: [f32; 4]| {
+            plane([
+                rows[3][0] + sign * row[0],
+                rows[3][1] + sign * row[1],
+                rows[3][2] + sign * row[2],
+                rows[3][3] + sign * row[3],
+            ])
+        };
+        Frustum {
+            planes: [
+                combine(1.0, rows[0]),
+                combine(-1.0, rows[0]),
+                combine(1.0, rows[1]),
+                combine(-1.0, rows[1]),
+                plane(rows[2]),
+                combine(-1.0, rows[2]),
+            ],
+        }
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.planes.iter().all(|plane| plane.distance(point) >= 0.0)
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.distance(sphere.center) >= -sphere.radius)
+    }
+
+    /// Whether the box is at least partly inside. Boxes near the frustum's corners may be reported as intersecting
+    /// even though they are outside.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            // the corner of the box that is furthest in front of the plane
+            let normal = plane.normal;
+            let corner = Vec3::new(
+                if normal.x() >= 0.0 {
+                    aabb.max.x()
+                } else {
+                    aabb.min.x()
+                },
+                if normal.y() >= 0.0 {
+                    aabb.max.y()
+                } else {
+                    aabb.min.y()
+                },
+                if normal.z() >= 0.0 {
+                    aabb.max.z()
+                } else {
+                    aabb.min.z()
+                },
+            );
+            plane.distance(corner) >= 0.0
+        })
+    }
+}
@@ -1,7 +1,9 @@
+mod bounding;
 mod clamp;
 mod face_toward;
 mod geometry;
 
+pub use bounding::*;
 pub use clamp::*;
 pub use face_toward::*;
 pub use geometry::*;
@@ -0,0 +1,31 @@
+mod network;
+mod packet;
+mod transport;
+
+pub use network::*;
+pub use packet::{MAX_MESSAGE_SIZE, MAX_PACKET_SIZE};
+pub use transport::*;
+
+pub mod prelude {
+    pub use crate::{Channel, ConnectionId, Network, NetworkEvent, NetworkMessage, NetworkPlugin};
+}
+
+use bevy_app::prelude::*;
+use bevy_ecs::IntoQuerySystem;
+
+/// Adds the [Network] resource, which connects apps for multiplayer games, and sends its connection changes and
+/// received messages as [NetworkEvent] and [NetworkMessage] events. Insert a [Network] before adding the plugin to
+/// change its [NetworkConfig].
+#[derive(Default)]
+pub struct NetworkPlugin;
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        if app.resources().get::<Network>().is_none() {
+            app.init_resource::<Network>();
+        }
+        app.add_event::<NetworkEvent>()
+            .add_event::<NetworkMessage>()
+            .add_system_to_stage(stage::PRE_EVENT, network_system.system());
+    }
+}
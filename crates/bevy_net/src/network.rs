@@ -0,0 +1,796 @@
+use crate::{
+    packet::{Packet, MAX_MESSAGE_SIZE, MAX_PACKET_SIZE},
+    transport::{Transport, UdpTransport},
+};
+use bevy_app::Events;
+use bevy_ecs::ResMut;
+use bevy_utils::HashMap;
+use std::{
+    collections::{hash_map::RandomState, BTreeMap},
+    hash::{BuildHasher, Hasher},
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// How far ahead of the next expected reliable message a message may be. Later ones are dropped without an
+/// acknowledgement, so the sender sends them again.
+const RECEIVE_WINDOW: u32 = 4096;
+
+/// Identifies a connection of a [Network]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ConnectionId(u32);
+
+/// How a message is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    /// Messages can be lost or arrive out of order, but are never held back by resends. Suits state that is sent
+    /// again every update, like positions.
+    Unreliable,
+    /// Messages arrive exactly once and in the order they were sent. Lost messages are sent again until the other
+    /// side acknowledges them.
+    Reliable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// [Network::disconnect] was called on either side
+    Closed,
+    /// Nothing was received for [NetworkConfig::timeout]. Connection attempts that are never answered time out too.
+    TimedOut,
+    /// The other side connected again from the same address, for example after it restarted. A new connection
+    /// replaces this one.
+    Reconnected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkEvent {
+    Connected(ConnectionId),
+    Disconnected(ConnectionId, DisconnectReason),
+}
+
+/// A message received on a connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkMessage {
+    pub connection: ConnectionId,
+    pub channel: Channel,
+    pub data: Vec<u8>,
+}
+
+#[derive(Error, Debug)]
+pub enum NetworkError {
+    #[error("Network IO error")]
+    Io(#[from] io::Error),
+    #[error("Network has no transport. Call listen or connect first.")]
+    NoTransport,
+    #[error("Connection {0:?} is not connected")]
+    NotConnected(ConnectionId),
+    #[error(
+        "Message of {0} bytes is larger than the maximum of {} bytes",
+        MAX_MESSAGE_SIZE
+    )]
+    MessageTooLarge(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct NetworkConfig {
+    /// How long a connection stays open without receiving anything
+    pub timeout: Duration,
+    /// How often an otherwise idle connection sends a packet, so the other side doesn't time out
+    pub heartbeat_interval: Duration,
+    /// How long to wait for an acknowledgement before sending a reliable message or connection request again
+    pub resend_interval: Duration,
+    /// The most connections that are accepted
+    pub max_connections: usize,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        NetworkConfig {
+            timeout: Duration::from_secs(10),
+            heartbeat_interval: Duration::from_secs(1),
+            resend_interval: Duration::from_millis(200),
+            max_connections: 64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectionState {
+    Connecting,
+    Connected,
+}
+
+#[derive(Debug)]
+struct Connection {
+    address: SocketAddr,
+    state: ConnectionState,
+    /// The session of the other side's connection request. It changes when the other side connects again.
+    remote_session: Option<u32>,
+    last_received: Instant,
+    last_sent: Instant,
+    next_send_sequence: u32,
+    /// Reliable packets that weren't acknowledged yet, and when they were last sent
+    unacknowledged: BTreeMap<u32, (Vec<u8>, Instant)>,
+    next_receive_sequence: u32,
+    /// Reliable messages that arrived before the messages sent ahead of them
+    early_messages: BTreeMap<u32, Vec<u8>>,
+}
+
+impl Connection {
+    fn new(
+        address: SocketAddr,
+        state: ConnectionState,
+        remote_session: Option<u32>,
+        now: Instant,
+    ) -> Self {
+        Connection {
+            address,
+            state,
+            remote_session,
+            last_received: now,
+            last_sent: now,
+            next_send_sequence: 0,
+            unacknowledged: BTreeMap::new(),
+            next_receive_sequence: 0,
+            early_messages: BTreeMap::new(),
+        }
+    }
+
+    fn send(&mut self, transport: &mut dyn Transport, packet: &[u8], now: Instant) {
+        send_packet(transport, self.address, packet);
+        self.last_sent = now;
+    }
+
+    fn send_message(
+        &mut self,
+        transport: &mut dyn Transport,
+        channel: Channel,
+        message: &[u8],
+        now: Instant,
+    ) {
+        match channel {
+            Channel::Unreliable => self.send(transport, &Packet::Unreliable(message).encode(), now),
+            Channel::Reliable => {
+                let sequence = self.next_send_sequence;
+                self.next_send_sequence = sequence.wrapping_add(1);
+                let packet = Packet::Reliable(sequence, message).encode();
+                self.send(transport, &packet, now);
+                self.unacknowledged.insert(sequence, (packet, now));
+            }
+        }
+    }
+}
+
+fn send_packet(transport: &mut dyn Transport, address: SocketAddr, packet: &[u8]) {
+    if let Err(err) = transport.send(address, packet) {
+        log::warn!("Failed to send packet to {}. {}", address, err);
+    }
+}
+
+/// Connections to other apps, and the messages sent over them. One side calls [Network::listen] and the others call
+/// [Network::connect] with its address. Connection changes are sent as [NetworkEvent]s and received messages as
+/// [NetworkMessage] events.
+///
+/// Connections run over a [Transport], which is a UDP socket unless [Network::set_transport] is called.
+pub struct Network {
+    config: NetworkConfig,
+    transport: Option<Box<dyn Transport>>,
+    accept_connections: bool,
+    connections: BTreeMap<ConnectionId, Connection>,
+    addresses: HashMap<SocketAddr, ConnectionId>,
+    next_connection_id: u32,
+    /// Derives the session of each connection request, see [connection_session]
+    session_state: RandomState,
+    now: Instant,
+    events: Vec<NetworkEvent>,
+    messages: Vec<NetworkMessage>,
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Network::new(NetworkConfig::default())
+    }
+}
+
+impl Network {
+    pub fn new(config: NetworkConfig) -> Self {
+        Network {
+            config,
+            transport: None,
+            accept_connections: false,
+            connections: BTreeMap::new(),
+            addresses: HashMap::default(),
+            next_connection_id: 0,
+            session_state: RandomState::new(),
+            now: Instant::now(),
+            events: Vec::new(),
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &NetworkConfig {
+        &self.config
+    }
+
+    /// Sends and receives packets with the given transport instead of the current one. Existing connections move to
+    /// the new transport.
+    pub fn set_transport(&mut self, transport: impl Transport) {
+        self.transport = Some(Box::new(transport));
+    }
+
+    /// Binds a UDP socket to the address and accepts connections on it. Returns the bound address, which tells the
+    /// port that was picked when binding to port 0.
+    pub fn listen(&mut self, address: impl ToSocketAddrs) -> Result<SocketAddr, NetworkError> {
+        let transport = UdpTransport::bind(address)?;
+        let local_address = transport.local_address()?;
+        self.set_transport(transport);
+        self.accept_connections = true;
+        Ok(local_address)
+    }
+
+    /// Whether connection requests from other apps are accepted. [Network::listen] turns this on.
+    pub fn set_accept_connections(&mut self, accept_connections: bool) {
+        self.accept_connections = accept_connections;
+    }
+
+    /// Starts connecting to an app that listens at the address, binding a UDP socket to any free port first if there
+    /// is no transport yet. A [NetworkEvent::Connected] is sent once the other side accepts.
+    pub fn connect(&mut self, address: impl ToSocketAddrs) -> Result<ConnectionId, NetworkError> {
+        let address = address.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "No address to connect to")
+        })?;
+        if let Some(connection) = self.addresses.get(&address) {
+            return Ok(*connection);
+        }
+        if self.transport.is_none() {
+            let local_address: SocketAddr = if address.is_ipv4() {
+                (Ipv4Addr::UNSPECIFIED, 0).into()
+            } else {
+                (Ipv6Addr::UNSPECIFIED, 0).into()
+            };
+            self.set_transport(UdpTransport::bind(local_address)?);
+        }
+
+        let connection = self.add_connection(address, ConnectionState::Connecting, None);
+        let now = self.now;
+        let packet = Packet::Connect(connection_session(&self.session_state, connection)).encode();
+        if let (Some(transport), Some(connection)) = (
+            self.transport.as_deref_mut(),
+            self.connections.get_mut(&connection),
+        ) {
+            connection.send(transport, &packet, now);
+        }
+        Ok(connection)
+    }
+
+    /// Closes the connection. The other side is told, and times out if that packet is lost.
+    pub fn disconnect(&mut self, connection: ConnectionId) {
+        if let Some(mut removed) = self.remove_connection(connection) {
+            if let Some(transport) = self.transport.as_deref_mut() {
+                removed.send(transport, &Packet::Disconnect.encode(), self.now);
+            }
+            self.events.push(NetworkEvent::Disconnected(
+                connection,
+                DisconnectReason::Closed,
+            ));
+        }
+    }
+
+    /// Sends a message on a connection. Messages can be at most [MAX_MESSAGE_SIZE] bytes.
+    pub fn send(
+        &mut self,
+        connection: ConnectionId,
+        channel: Channel,
+        message: &[u8],
+    ) -> Result<(), NetworkError> {
+        if message.len() > MAX_MESSAGE_SIZE {
+            return Err(NetworkError::MessageTooLarge(message.len()));
+        }
+        let transport = self
+            .transport
+            .as_deref_mut()
+            .ok_or(NetworkError::NoTransport)?;
+        let connection_state = self
+            .connections
+            .get_mut(&connection)
+            .filter(|connection| connection.state == ConnectionState::Connected)
+            .ok_or(NetworkError::NotConnected(connection))?;
+        connection_state.send_message(transport, channel, message, self.now);
+        Ok(())
+    }
+
+    /// Sends a message on every connection. Messages can be at most [MAX_MESSAGE_SIZE] bytes.
+    pub fn broadcast(&mut self, channel: Channel, message: &[u8]) -> Result<(), NetworkError> {
+        if message.len() > MAX_MESSAGE_SIZE {
+            return Err(NetworkError::MessageTooLarge(message.len()));
+        }
+        let transport = self
+            .transport
+            .as_deref_mut()
+            .ok_or(NetworkError::NoTransport)?;
+        for connection in self
+            .connections
+            .values_mut()
+            .filter(|connection| connection.state == ConnectionState::Connected)
+        {
+            connection.send_message(transport, channel, message, self.now);
+        }
+        Ok(())
+    }
+
+    /// The established connections
+    pub fn connections(&self) -> impl Iterator<Item = ConnectionId> + '_ {
+        self.connections
+            .iter()
+            .filter(|(_, connection)| connection.state == ConnectionState::Connected)
+            .map(|(id, _)| *id)
+    }
+
+    pub fn is_connected(&self, connection: ConnectionId) -> bool {
+        self.connections
+            .get(&connection)
+            .map_or(false, |connection| {
+                connection.state == ConnectionState::Connected
+            })
+    }
+
+    /// The address of the other side of a connection
+    pub fn address(&self, connection: ConnectionId) -> Option<SocketAddr> {
+        self.connections
+            .get(&connection)
+            .map(|connection| connection.address)
+    }
+
+    pub fn local_address(&self) -> Option<SocketAddr> {
+        self.transport
+            .as_ref()
+            .and_then(|transport| transport.local_address().ok())
+    }
+
+    /// Receives packets, sends lost packets again and closes connections that timed out. [network_system] calls this
+    /// every update.
+    pub fn update(&mut self, now: Instant) {
+        self.now = now;
+        let mut transport = match self.transport.take() {
+            Some(transport) => transport,
+            None => return,
+        };
+
+        let mut buffer = [0; MAX_PACKET_SIZE];
+        loop {
+            match transport.receive(&mut buffer) {
+                Ok(Some((address, length))) => {
+                    self.receive(&mut *transport, address, &buffer[..length])
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    log::warn!("Failed to receive packet. {}", err);
+                    break;
+                }
+            }
+        }
+        self.update_connections(&mut *transport);
+
+        self.transport = Some(transport);
+    }
+
+    /// The connection events since the last call. [network_system] sends them as [NetworkEvent] events.
+    pub fn drain_events(&mut self) -> impl Iterator<Item = NetworkEvent> + '_ {
+        self.events.drain(..)
+    }
+
+    /// The messages received since the last call. [network_system] sends them as [NetworkMessage] events.
+    pub fn drain_messages(&mut self) -> impl Iterator<Item = NetworkMessage> + '_ {
+        self.messages.drain(..)
+    }
+
+    fn receive(&mut self, transport: &mut dyn Transport, address: SocketAddr, bytes: &[u8]) {
+        let packet = match Packet::decode(bytes) {
+            Some(packet) => packet,
+            None => return,
+        };
+        let mut id = self.addresses.get(&address).copied();
+        if let (Some(old_id), Packet::Connect(session)) = (id, packet) {
+            let connection = self.connections.get_mut(&old_id).unwrap();
+            match connection.remote_session {
+                // the other side started over, so its sequences and messages in flight belong to the old connection
+                Some(remote_session) if remote_session != session => {
+                    self.remove_connection(old_id);
+                    self.events.push(NetworkEvent::Disconnected(
+                        old_id,
+                        DisconnectReason::Reconnected,
+                    ));
+                    id = None;
+                }
+                // both sides connected to each other at the same time
+                None => connection.remote_session = Some(session),
+                _ => {}
+            }
+        }
+        let id = match (id, packet) {
+            (Some(id), _) => id,
+            (None, Packet::Connect(session))
+                if self.accept_connections
+                    && self.connections.len() < self.config.max_connections =>
+            {
+                let id = self.add_connection(address, ConnectionState::Connected, Some(session));
+                self.events.push(NetworkEvent::Connected(id));
+                id
+            }
+            (None, _) => return,
+        };
+
+        let now = self.now;
+        let connection = self.connections.get_mut(&id).unwrap();
+        connection.last_received = now;
+        if connection.state == ConnectionState::Connecting
+            && !matches!(packet, Packet::Connect(_) | Packet::Disconnect)
+        {
+            // anything else means the other side accepted, even if its Accept packet was lost
+            connection.state = ConnectionState::Connected;
+            self.events.push(NetworkEvent::Connected(id));
+        }
+
+        match packet {
+            // answer repeated requests too, in case the first Accept was lost
+            Packet::Connect(_) => connection.send(transport, &Packet::Accept.encode(), now),
+            Packet::Accept | Packet::Heartbeat => {}
+            Packet::Disconnect => {
+                self.remove_connection(id);
+                self.events
+                    .push(NetworkEvent::Disconnected(id, DisconnectReason::Closed));
+            }
+            Packet::Unreliable(message) => self.messages.push(NetworkMessage {
+                connection: id,
+                channel: Channel::Unreliable,
+                data: message.to_vec(),
+            }),
+            Packet::Reliable(sequence, message) => {
+                let ahead = sequence.wrapping_sub(connection.next_receive_sequence);
+                if ahead >= RECEIVE_WINDOW && ahead <= u32::MAX - RECEIVE_WINDOW {
+                    return;
+                }
+                // acknowledge messages that were already received too, in case the first acknowledgement was lost
+                connection.send(transport, &Packet::Ack(sequence).encode(), now);
+                if ahead < RECEIVE_WINDOW {
+                    connection
+                        .early_messages
+                        .entry(sequence)
+                        .or_insert_with(|| message.to_vec());
+                }
+                while let Some(message) = connection
+                    .early_messages
+                    .remove(&connection.next_receive_sequence)
+                {
+                    self.messages.push(NetworkMessage {
+                        connection: id,
+                        channel: Channel::Reliable,
+                        data: message,
+                    });
+                    connection.next_receive_sequence =
+                        connection.next_receive_sequence.wrapping_add(1);
+                }
+            }
+            Packet::Ack(sequence) => {
+                connection.unacknowledged.remove(&sequence);
+            }
+        }
+    }
+
+    fn update_connections(&mut self, transport: &mut dyn Transport) {
+        let now = self.now;
+        let config = &self.config;
+        let session_state = &self.session_state;
+        let mut timed_out = Vec::new();
+        for (id, connection) in self.connections.iter_mut() {
+            if now.saturating_duration_since(connection.last_received) > config.timeout {
+                timed_out.push(*id);
+                continue;
+            }
+
+            match connection.state {
+                ConnectionState::Connecting => {
+                    if now.saturating_duration_since(connection.last_sent) >= config.resend_interval
+                    {
+                        let session = connection_session(session_state, *id);
+                        connection.send(transport, &Packet::Connect(session).encode(), now);
+                    }
+                }
+                ConnectionState::Connected => {
+                    for (packet, sent) in connection.unacknowledged.values_mut() {
+                        if now.saturating_duration_since(*sent) >= config.resend_interval {
+                            send_packet(transport, connection.address, packet);
+                            *sent = now;
+                            connection.last_sent = now;
+                        }
+                    }
+                    if now.saturating_duration_since(connection.last_sent)
+                        >= config.heartbeat_interval
+                    {
+                        connection.send(transport, &Packet::Heartbeat.encode(), now);
+                    }
+                }
+            }
+        }
+
+        for id in timed_out {
+            self.remove_connection(id);
+            self.events
+                .push(NetworkEvent::Disconnected(id, DisconnectReason::TimedOut));
+        }
+    }
+
+    fn add_connection(
+        &mut self,
+        address: SocketAddr,
+        state: ConnectionState,
+        remote_session: Option<u32>,
+    ) -> ConnectionId {
+        let id = ConnectionId(self.next_connection_id);
+        self.next_connection_id = self.next_connection_id.wrapping_add(1);
+        self.connections.insert(
+            id,
+            Connection::new(address, state, remote_session, self.now),
+        );
+        self.addresses.insert(address, id);
+        id
+    }
+
+    fn remove_connection(&mut self, id: ConnectionId) -> Option<Connection> {
+        let connection = self.connections.remove(&id)?;
+        self.addresses.remove(&connection.address);
+        Some(connection)
+    }
+}
+
+/// The session sent with the connection requests of an outgoing connection. It differs between connections and
+/// between runs of the app, so the other side notices when an address connects again.
+fn connection_session(session_state: &RandomState, id: ConnectionId) -> u32 {
+    let mut hasher = session_state.build_hasher();
+    hasher.write_u32(id.0);
+    hasher.finish() as u32
+}
+
+/// Updates the [Network] and sends its connection events and received messages as events
+pub fn network_system(
+    mut network: ResMut<Network>,
+    mut network_events: ResMut<Events<NetworkEvent>>,
+    mut network_messages: ResMut<Events<NetworkMessage>>,
+) {
+    network.update(Instant::now());
+    network_events.extend(network.drain_events());
+    network_messages.extend(network.drain_messages());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Channel, DisconnectReason, Network, NetworkConfig, NetworkEvent};
+    use crate::Transport;
+    use std::{
+        collections::{HashMap, VecDeque},
+        io,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    type Inboxes = Arc<Mutex<HashMap<SocketAddr, VecDeque<(SocketAddr, Vec<u8>)>>>>;
+
+    /// Delivers packets in memory, dropping every `drop_every`th packet it sends
+    struct MemoryTransport {
+        address: SocketAddr,
+        inboxes: Inboxes,
+        drop_every: usize,
+        sent: usize,
+    }
+
+    impl Transport for MemoryTransport {
+        fn send(&mut self, address: SocketAddr, packet: &[u8]) -> io::Result<()> {
+            self.sent += 1;
+            if self.drop_every == 0 || self.sent % self.drop_every != 0 {
+                let mut inboxes = self.inboxes.lock().unwrap();
+                let inbox = inboxes.entry(address).or_insert_with(VecDeque::new);
+                inbox.push_back((self.address, packet.to_vec()));
+            }
+            Ok(())
+        }
+
+        fn receive(&mut self, buffer: &mut [u8]) -> io::Result<Option<(SocketAddr, usize)>> {
+            let mut inboxes = self.inboxes.lock().unwrap();
+            Ok(inboxes
+                .get_mut(&self.address)
+                .and_then(|inbox| inbox.pop_front())
+                .map(|(address, packet)| {
+                    buffer[..packet.len()].copy_from_slice(&packet);
+                    (address, packet.len())
+                }))
+        }
+
+        fn local_address(&self) -> io::Result<SocketAddr> {
+            Ok(self.address)
+        }
+    }
+
+    #[test]
+    fn connect_and_send_over_lossy_transport() {
+        let inboxes = Inboxes::default();
+        let server_address: SocketAddr = ([10, 0, 0, 1], 5000).into();
+        let client_address: SocketAddr = ([10, 0, 0, 2], 6000).into();
+        let mut server = Network::default();
+        server.set_transport(MemoryTransport {
+            address: server_address,
+            inboxes: inboxes.clone(),
+            drop_every: 0,
+            sent: 0,
+        });
+        server.set_accept_connections(true);
+        let mut client = Network::default();
+        client.set_transport(MemoryTransport {
+            address: client_address,
+            inboxes: inboxes.clone(),
+            drop_every: 3,
+            sent: 0,
+        });
+        let server_connection = client.connect(server_address).unwrap();
+        assert!(!client.is_connected(server_connection));
+
+        let start = Instant::now();
+        let mut received = Vec::new();
+        let mut client_connection = None;
+        for tick in 0..100 {
+            let now = start + Duration::from_millis(50 * tick);
+            server.update(now);
+            client.update(now);
+            for event in server.drain_events() {
+                if let NetworkEvent::Connected(connection) = event {
+                    client_connection = Some(connection);
+                }
+            }
+            received.extend(
+                server
+                    .drain_messages()
+                    .filter(|message| message.channel == Channel::Reliable)
+                    .map(|message| message.data[0]),
+            );
+            if tick < 20 && client.is_connected(server_connection) {
+                client
+                    .send(server_connection, Channel::Reliable, &[tick as u8])
+                    .unwrap();
+                client
+                    .send(server_connection, Channel::Unreliable, &[0; 16])
+                    .unwrap();
+            }
+        }
+
+        assert_eq!(
+            client.drain_events().collect::<Vec<_>>(),
+            vec![NetworkEvent::Connected(server_connection)]
+        );
+        let sent = (0..20).filter(|tick| received.contains(tick)).count();
+        assert!(sent > 0);
+        // every reliable message arrived exactly once and in order, even though a third of the packets were lost
+        assert_eq!(received, (20 - sent as u8..20).collect::<Vec<_>>());
+
+        let client_connection = client_connection.unwrap();
+        assert_eq!(server.address(client_connection), Some(client_address));
+        server.disconnect(client_connection);
+        client.update(start + Duration::from_secs(10));
+        assert_eq!(
+            client.drain_events().collect::<Vec<_>>(),
+            vec![NetworkEvent::Disconnected(
+                server_connection,
+                DisconnectReason::Closed
+            )]
+        );
+        assert_eq!(server.connections().count(), 0);
+    }
+
+    #[test]
+    fn unanswered_connection_times_out() {
+        let mut client = Network::new(NetworkConfig {
+            timeout: Duration::from_secs(1),
+            ..Default::default()
+        });
+        client.set_transport(MemoryTransport {
+            address: ([10, 0, 0, 2], 6000).into(),
+            inboxes: Default::default(),
+            drop_every: 0,
+            sent: 0,
+        });
+        let connection = client
+            .connect(SocketAddr::from(([10, 0, 0, 1], 5000)))
+            .unwrap();
+        let start = Instant::now();
+        client.update(start);
+        assert_eq!(client.drain_events().count(), 0);
+        client.update(start + Duration::from_secs(2));
+        assert_eq!(
+            client.drain_events().collect::<Vec<_>>(),
+            vec![NetworkEvent::Disconnected(
+                connection,
+                DisconnectReason::TimedOut
+            )]
+        );
+    }
+
+    #[test]
+    fn connecting_again_from_the_same_address_replaces_the_connection() {
+        let inboxes = Inboxes::default();
+        let server_address: SocketAddr = ([10, 0, 0, 1], 5000).into();
+        let client_address: SocketAddr = ([10, 0, 0, 2], 6000).into();
+        let transport = |address| MemoryTransport {
+            address,
+            inboxes: inboxes.clone(),
+            drop_every: 0,
+            sent: 0,
+        };
+        let mut server = Network::default();
+        server.set_transport(transport(server_address));
+        server.set_accept_connections(true);
+
+        let start = Instant::now();
+        let mut tick = 0;
+        let mut connect_and_send = |server: &mut Network, messages: &[u8]| {
+            // a new app on the same address, like a client that restarted
+            let mut client = Network::default();
+            client.set_transport(transport(client_address));
+            let server_connection = client.connect(server_address).unwrap();
+            let mut events = Vec::new();
+            let mut received = Vec::new();
+            let mut sent = false;
+            for _ in 0..10 {
+                let now = start + Duration::from_millis(50 * tick);
+                tick += 1;
+                client.update(now);
+                server.update(now);
+                if !sent && client.is_connected(server_connection) {
+                    for message in messages {
+                        client
+                            .send(server_connection, Channel::Reliable, &[*message])
+                            .unwrap();
+                    }
+                    sent = true;
+                }
+                events.extend(server.drain_events());
+                received.extend(
+                    server
+                        .drain_messages()
+                        .map(|message| (message.connection, message.data[0])),
+                );
+            }
+            (events, received)
+        };
+
+        let (events, received) = connect_and_send(&mut server, &[1, 2, 3]);
+        let first_connection = match events[..] {
+            [NetworkEvent::Connected(connection)] => connection,
+            _ => panic!("unexpected events {:?}", events),
+        };
+        assert_eq!(
+            received,
+            vec![
+                (first_connection, 1),
+                (first_connection, 2),
+                (first_connection, 3)
+            ]
+        );
+
+        // the new client's reliable messages start at the first sequence again
+        let (events, received) = connect_and_send(&mut server, &[4]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0],
+            NetworkEvent::Disconnected(first_connection, DisconnectReason::Reconnected)
+        );
+        let second_connection = match events[1] {
+            NetworkEvent::Connected(connection) => connection,
+            event => panic!("unexpected event {:?}", event),
+        };
+        assert_ne!(second_connection, first_connection);
+        assert_eq!(received, vec![(second_connection, 4)]);
+        assert_eq!(server.address(second_connection), Some(client_address));
+        assert_eq!(server.connections().count(), 1);
+    }
+}
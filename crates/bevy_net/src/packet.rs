@@ -0,0 +1,76 @@
+use std::convert::TryInto;
+
+/// Starts every packet, so packets from other programs that happen to reach the socket are ignored
+const PROTOCOL_ID: u32 = 0x6265_7679;
+const HEADER_SIZE: usize = 5;
+const SEQUENCE_SIZE: usize = 4;
+
+/// The largest packet that is sent. Packets up to this size fit in a single datagram on almost any network path.
+pub const MAX_PACKET_SIZE: usize = 1200;
+/// The largest message that can be sent on a [Channel](crate::Channel)
+pub const MAX_MESSAGE_SIZE: usize = MAX_PACKET_SIZE - HEADER_SIZE - SEQUENCE_SIZE;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Packet<'a> {
+    /// Carries the session of the connection attempt, which tells a resent request from a new one
+    Connect(u32),
+    Accept,
+    Disconnect,
+    Heartbeat,
+    Unreliable(&'a [u8]),
+    Reliable(u32, &'a [u8]),
+    Ack(u32),
+}
+
+impl<'a> Packet<'a> {
+    fn kind(&self) -> u8 {
+        match self {
+            Packet::Connect(_) => 0,
+            Packet::Accept => 1,
+            Packet::Disconnect => 2,
+            Packet::Heartbeat => 3,
+            Packet::Unreliable(_) => 4,
+            Packet::Reliable(_, _) => 5,
+            Packet::Ack(_) => 6,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + SEQUENCE_SIZE);
+        bytes.extend_from_slice(&PROTOCOL_ID.to_le_bytes());
+        bytes.push(self.kind());
+        match self {
+            Packet::Connect(session) => bytes.extend_from_slice(&session.to_le_bytes()),
+            Packet::Unreliable(message) => bytes.extend_from_slice(message),
+            Packet::Reliable(sequence, message) => {
+                bytes.extend_from_slice(&sequence.to_le_bytes());
+                bytes.extend_from_slice(message);
+            }
+            Packet::Ack(sequence) => bytes.extend_from_slice(&sequence.to_le_bytes()),
+            _ => {}
+        }
+        bytes
+    }
+
+    /// Reads a packet, or returns None if the bytes aren't a packet of this protocol
+    pub fn decode(bytes: &'a [u8]) -> Option<Packet<'a>> {
+        if bytes.len() < HEADER_SIZE || bytes[..4] != PROTOCOL_ID.to_le_bytes() {
+            return None;
+        }
+        let body = &bytes[HEADER_SIZE..];
+        let sequence = || {
+            body.get(..SEQUENCE_SIZE)
+                .map(|sequence| u32::from_le_bytes(sequence.try_into().unwrap()))
+        };
+        match bytes[4] {
+            0 => sequence().map(Packet::Connect),
+            1 => Some(Packet::Accept),
+            2 => Some(Packet::Disconnect),
+            3 => Some(Packet::Heartbeat),
+            4 => Some(Packet::Unreliable(body)),
+            5 => sequence().map(|sequence| Packet::Reliable(sequence, &body[SEQUENCE_SIZE..])),
+            6 => sequence().map(Packet::Ack),
+            _ => None,
+        }
+    }
+}
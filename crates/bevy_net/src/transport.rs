@@ -0,0 +1,55 @@
+use std::{
+    io,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+};
+
+/// Sends and receives unreliable packets. [Network](crate::Network) builds connections and reliable channels on top
+/// of a transport, so any datagram protocol can carry them. [UdpTransport] is used by default, and is the only
+/// transport this crate provides. There is no QUIC transport: to run over QUIC, implement this trait with the
+/// datagrams of a QUIC library.
+pub trait Transport: Send + Sync + 'static {
+    fn send(&mut self, address: SocketAddr, packet: &[u8]) -> io::Result<()>;
+
+    /// Copies the next packet that arrived into `buffer` and returns who sent it and its length. Must not block:
+    /// returns `Ok(None)` when no packet is waiting.
+    fn receive(&mut self, buffer: &mut [u8]) -> io::Result<Option<(SocketAddr, usize)>>;
+
+    fn local_address(&self) -> io::Result<SocketAddr>;
+}
+
+/// A [Transport] over a non-blocking UDP socket
+#[derive(Debug)]
+pub struct UdpTransport {
+    socket: UdpSocket,
+}
+
+impl UdpTransport {
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(UdpTransport { socket })
+    }
+}
+
+impl Transport for UdpTransport {
+    fn send(&mut self, address: SocketAddr, packet: &[u8]) -> io::Result<()> {
+        match self.socket.send_to(packet, address) {
+            Ok(_) => Ok(()),
+            // the packet is dropped, which the reliable channel recovers from like any other lost packet
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn receive(&mut self, buffer: &mut [u8]) -> io::Result<Option<(SocketAddr, usize)>> {
+        match self.socket.recv_from(buffer) {
+            Ok((length, address)) => Ok(Some((address, length))),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn local_address(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+}
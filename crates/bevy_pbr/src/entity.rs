@@ -1,11 +1,15 @@
-use crate::{light::Light, material::StandardMaterial, render_graph::FORWARD_PIPELINE_HANDLE};
+use crate::{
+    light::Light,
+    material::StandardMaterial,
+    render_graph::{FORWARD_PIPELINE_HANDLE, FORWARD_TRANSPARENT_PIPELINE_HANDLE},
+};
 use bevy_asset::Handle;
 use bevy_ecs::Bundle;
 use bevy_render::{
-    draw::Draw,
+    draw::{Draw, Visible},
     mesh::Mesh,
     pipeline::{DynamicBinding, PipelineSpecialization, RenderPipeline, RenderPipelines},
-    render_graph::base::MainPass,
+    render_graph::base::{MainPass, TransparentPass},
 };
 use bevy_transform::prelude::{GlobalTransform, Transform};
 
@@ -16,29 +20,45 @@ pub struct PbrComponents {
     pub material: Handle<StandardMaterial>,
     pub main_pass: MainPass,
     pub draw: Draw,
+    pub visible: Visible,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
 
+/// The uniforms of the forward pipeline that are written to dynamic buffers
+fn forward_dynamic_bindings() -> Vec<DynamicBinding> {
+    vec![
+        // Transform
+        DynamicBinding {
+            bind_group: 2,
+            binding: 0,
+        },
+        // StandardMaterial_albedo
+        DynamicBinding {
+            bind_group: 3,
+            binding: 0,
+        },
+        // StandardMaterial_lightmap_exposure
+        DynamicBinding {
+            bind_group: 3,
+            binding: 5,
+        },
+        // StandardMaterial_depth_fade_distance
+        DynamicBinding {
+            bind_group: 3,
+            binding: 6,
+        },
+    ]
+}
+
 impl Default for PbrComponents {
     fn default() -> Self {
         Self {
             render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::specialized(
                 FORWARD_PIPELINE_HANDLE,
                 PipelineSpecialization {
-                    dynamic_bindings: vec![
-                        // Transform
-                        DynamicBinding {
-                            bind_group: 2,
-                            binding: 0,
-                        },
-                        // StandardMaterial_albedo
-                        DynamicBinding {
-                            bind_group: 3,
-                            binding: 0,
-                        },
-                    ],
+                    dynamic_bindings: forward_dynamic_bindings(),
                     ..Default::default()
                 },
             )]),
@@ -46,6 +66,46 @@ impl Default for PbrComponents {
             material: Default::default(),
             main_pass: Default::default(),
             draw: Default::default(),
+            visible: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+        }
+    }
+}
+
+/// A component bundle for "pbr mesh" entities that are see-through, like particles and water. They are drawn in the
+/// transparent pass after all [PbrComponents], from back to front, and can use
+/// [StandardMaterial::depth_fade].
+#[derive(Bundle)]
+pub struct TransparentPbrComponents {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+    pub transparent_pass: TransparentPass,
+    pub draw: Draw,
+    pub visible: Visible,
+    pub render_pipelines: RenderPipelines,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+impl Default for TransparentPbrComponents {
+    fn default() -> Self {
+        Self {
+            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::specialized(
+                FORWARD_TRANSPARENT_PIPELINE_HANDLE,
+                PipelineSpecialization {
+                    dynamic_bindings: forward_dynamic_bindings(),
+                    ..Default::default()
+                },
+            )]),
+            visible: Visible {
+                is_transparent: true,
+                ..Default::default()
+            },
+            mesh: Default::default(),
+            material: Default::default(),
+            transparent_pass: Default::default(),
+            draw: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -59,3 +119,122 @@ pub struct LightComponents {
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::{PbrComponents, TransparentPbrComponents};
+    use crate::render_graph::{build_forward_pipeline, build_forward_transparent_pipeline};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_render::{
+        pipeline::{
+            BindGroupDescriptor, BindType, PipelineCompiler, PipelineDescriptor,
+            PipelineSpecialization,
+        },
+        renderer::HeadlessRenderResourceContext,
+        shader::Shader,
+    };
+    use bevy_type_registry::TypeRegistry;
+
+    /// Compiles the pipeline with the given shader defs, and returns the compiled descriptor
+    fn compile_pipeline(
+        build_pipeline: fn(&mut Assets<Shader>) -> PipelineDescriptor,
+        specialization: &PipelineSpecialization,
+        shader_defs: &[&str],
+    ) -> PipelineDescriptor {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Shader>()
+            .add_asset::<PipelineDescriptor>();
+        let mut shaders = app.resources().get_mut::<Assets<Shader>>().unwrap();
+        let mut pipelines = app
+            .resources()
+            .get_mut::<Assets<PipelineDescriptor>>()
+            .unwrap();
+        let pipeline = pipelines.add(build_pipeline(&mut shaders));
+
+        let mut specialization = specialization.clone();
+        specialization.shader_specialization.shader_defs = shader_defs
+            .iter()
+            .map(|shader_def| shader_def.to_string())
+            .collect();
+        let compiled_pipeline = PipelineCompiler::default()
+            .compile_pipeline(
+                &HeadlessRenderResourceContext::default(),
+                &mut pipelines,
+                &mut shaders,
+                &pipeline,
+                &specialization,
+            )
+            .unwrap();
+        let compiled_pipeline = pipelines.get(&compiled_pipeline).unwrap().clone();
+        compiled_pipeline
+    }
+
+    // the material's uniforms are written to dynamic buffers, so the layout has to expect them there
+    fn assert_uniforms_are_dynamic(material_bind_group: &BindGroupDescriptor) {
+        for binding in material_bind_group.bindings.iter() {
+            if let BindType::Uniform { dynamic, .. } = binding.bind_type {
+                assert!(dynamic, "{} isn't dynamic", binding.name);
+            }
+        }
+    }
+
+    #[test]
+    fn lightmapped_materials_only_have_dynamic_uniforms() {
+        let pipeline = compile_pipeline(
+            build_forward_pipeline,
+            &PbrComponents::default().render_pipelines.pipelines[0].specialization,
+            &["STANDARDMATERIAL_SHADED", "STANDARDMATERIAL_LIGHTMAP"],
+        );
+
+        let layout = pipeline.get_layout().unwrap();
+        let material_bind_group = layout.get_bind_group(3).unwrap();
+        assert!(material_bind_group
+            .bindings
+            .iter()
+            .any(|binding| binding.name == "StandardMaterial_lightmap_exposure"));
+        assert_uniforms_are_dynamic(material_bind_group);
+    }
+
+    #[test]
+    fn depth_faded_materials_sample_the_scene_depth() {
+        let pipeline = compile_pipeline(
+            build_forward_transparent_pipeline,
+            &TransparentPbrComponents::default()
+                .render_pipelines
+                .pipelines[0]
+                .specialization,
+            &[
+                "STANDARDMATERIAL_SHADED",
+                "STANDARDMATERIAL_LIGHTMAP",
+                "STANDARDMATERIAL_DEPTH_FADE",
+            ],
+        );
+
+        assert!(
+            !pipeline
+                .depth_stencil_state
+                .as_ref()
+                .unwrap()
+                .depth_write_enabled
+        );
+        let layout = pipeline.get_layout().unwrap();
+        let lights_bind_group = layout.get_bind_group(1).unwrap();
+        for name in ["SceneDepth", "SceneDepth_sampler"].iter() {
+            assert!(lights_bind_group
+                .bindings
+                .iter()
+                .any(|binding| binding.name == *name));
+        }
+        let material_bind_group = layout.get_bind_group(3).unwrap();
+        assert!(material_bind_group
+            .bindings
+            .iter()
+            .any(|binding| binding.name == "StandardMaterial_depth_fade_distance"));
+        assert_uniforms_are_dynamic(material_bind_group);
+    }
+}
@@ -3,13 +3,23 @@ pub mod render_graph;
 mod entity;
 mod light;
 mod material;
+mod shadow_cascades;
+mod sky;
 
 pub use entity::*;
 pub use light::*;
 pub use material::*;
+pub use shadow_cascades::*;
+pub use sky::*;
 
 pub mod prelude {
-    pub use crate::{entity::*, light::Light, material::StandardMaterial};
+    pub use crate::{
+        entity::*,
+        light::{EnvironmentLight, Light},
+        material::StandardMaterial,
+        shadow_cascades::CascadeShadowConfig,
+        sky::{Sky, SkyPlugin},
+    };
 }
 
 use bevy_app::prelude::*;
@@ -17,7 +27,7 @@ use bevy_asset::{AddAsset, Assets, Handle};
 use bevy_ecs::IntoQuerySystem;
 use bevy_render::{prelude::Color, render_graph::RenderGraph, shader};
 use bevy_type_registry::RegisterType;
-use light::Light;
+use light::{EnvironmentLight, Light};
 use material::StandardMaterial;
 use render_graph::add_pbr_graph;
 
@@ -33,6 +43,12 @@ impl Plugin for PbrPlugin {
                 stage::POST_UPDATE,
                 shader::asset_shader_defs_system::<StandardMaterial>.system(),
             );
+        if app.resources().get::<EnvironmentLight>().is_none() {
+            app.init_resource::<EnvironmentLight>();
+        }
+        if app.resources().get::<CascadeShadowConfig>().is_none() {
+            app.init_resource::<CascadeShadowConfig>();
+        }
         let resources = app.resources();
         let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
         add_pbr_graph(&mut render_graph, resources);
@@ -48,6 +64,10 @@ impl Plugin for PbrPlugin {
                 albedo: Color::PINK,
                 shaded: false,
                 albedo_texture: None,
+                lightmap: None,
+                lightmap_exposure: 1.0,
+                depth_fade: false,
+                depth_fade_distance: 1.0,
             },
         );
     }
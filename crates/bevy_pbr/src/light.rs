@@ -1,4 +1,5 @@
 use bevy_core::Byteable;
+use bevy_math::Vec3;
 use bevy_property::Properties;
 use bevy_render::{
     camera::{CameraProjection, PerspectiveProjection},
@@ -11,16 +12,47 @@ use std::ops::Range;
 #[derive(Debug, Properties)]
 pub struct Light {
     pub color: Color,
+    /// Multiplies the color. Together with a camera [Exposure](bevy_render::camera::Exposure), this can be a physical
+    /// light intensity.
+    pub intensity: f32,
     pub fov: f32,
     pub depth: Range<f32>,
+    /// The distance the light reaches. Light fades out smoothly towards the range, and fragments further away don't
+    /// evaluate the light at all.
+    pub range: f32,
 }
 
 impl Default for Light {
     fn default() -> Self {
         Light {
             color: Color::rgb(1.0, 1.0, 1.0),
+            intensity: 1.0,
             depth: 0.1..50.0,
             fov: f32::to_radians(60.0),
+            range: 50.0,
+        }
+    }
+}
+
+/// The light of shaded materials that doesn't come from [Light]s: a constant ambient light and a directional sun.
+/// Colors are linear RGB, in the same units as light intensities. [SkyPlugin](crate::SkyPlugin) sets this from the
+/// [Sky](crate::Sky).
+#[derive(Debug, Clone)]
+pub struct EnvironmentLight {
+    /// Light that reaches surfaces from every direction
+    pub ambient: Vec3,
+    /// Points from the scene towards the sun
+    pub sun_direction: Vec3,
+    /// The light of the sun on a white surface that faces it. Zero when there is no sun.
+    pub sun_color: Vec3,
+}
+
+impl Default for EnvironmentLight {
+    fn default() -> Self {
+        EnvironmentLight {
+            ambient: Vec3::new(0.05, 0.05, 0.05),
+            sun_direction: Vec3::unit_y(),
+            sun_color: Vec3::zero(),
         }
     }
 }
@@ -46,10 +78,13 @@ impl LightRaw {
 
         let proj = perspective.get_projection_matrix() * global_transform.compute_matrix();
         let (x, y, z) = global_transform.translation.into();
+        // scale the linear color, multiplying a Color scales its sRGB components
+        let [r, g, b, a]: [f32; 4] = light.color.into();
+        let intensity = light.intensity;
         LightRaw {
             proj: proj.to_cols_array_2d(),
-            pos: [x, y, z, 1.0],
-            color: light.color.into(),
+            pos: [x, y, z, light.range],
+            color: [r * intensity, g * intensity, b * intensity, a],
         }
     }
 }
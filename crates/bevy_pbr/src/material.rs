@@ -12,6 +12,20 @@ pub struct StandardMaterial {
     #[render_resources(ignore)]
     #[shader_def]
     pub shaded: bool,
+    /// Baked lighting, sampled with the mesh's [Mesh::ATTRIBUTE_UV_1](bevy_render::mesh::Mesh::ATTRIBUTE_UV_1)
+    /// coordinates. It replaces the ambient light of shaded materials.
+    #[shader_def]
+    pub lightmap: Option<Handle<Texture>>,
+    /// Multiplies the lightmap, to bring baked lighting into the same units as the scene's lights
+    pub lightmap_exposure: f32,
+    /// Fades the material out where it gets closer than `depth_fade_distance` to the geometry behind it, to soften the
+    /// edges of particles and water. It samples the scene depth, so only entities in the
+    /// [TransparentPass](bevy_render::render_graph::base::TransparentPass) are drawn with it, like the ones spawned
+    /// with [TransparentPbrComponents](crate::entity::TransparentPbrComponents).
+    #[render_resources(ignore)]
+    #[shader_def]
+    pub depth_fade: bool,
+    pub depth_fade_distance: f32,
 }
 
 impl Default for StandardMaterial {
@@ -20,6 +34,10 @@ impl Default for StandardMaterial {
             albedo: Color::rgb(1.0, 1.0, 1.0),
             albedo_texture: None,
             shaded: true,
+            lightmap: None,
+            lightmap_exposure: 1.0,
+            depth_fade: false,
+            depth_fade_distance: 1.0,
         }
     }
 }
@@ -1,6 +1,6 @@
 #version 450
 
-const int MAX_LIGHTS = 10;
+const int MAX_LIGHTS = 128;
 
 struct Light {
     mat4 proj;
@@ -11,6 +11,12 @@ struct Light {
 layout(location = 0) in vec3 v_Position;
 layout(location = 1) in vec3 v_Normal;
 layout(location = 2) in vec2 v_Uv;
+# ifdef STANDARDMATERIAL_LIGHTMAP
+layout(location = 3) in vec2 v_Uv2;
+# endif
+# ifdef STANDARDMATERIAL_DEPTH_FADE
+layout(location = 4) flat in mat4 v_InverseViewProj;
+# endif
 
 layout(location = 0) out vec4 o_Target;
 
@@ -20,9 +26,74 @@ layout(set = 0, binding = 0) uniform Camera {
 
 layout(set = 1, binding = 0) uniform Lights {
     uvec4 NumLights;
+    float Exposure;
+    vec4 Ambient;
+    vec4 SunDirection;
+    vec4 SunColor;
     Light SceneLights[MAX_LIGHTS];
 };
 
+// the light indices of each cluster, see LightClusters::write_uniform
+layout(set = 1, binding = 1) uniform LightClusters {
+    uvec4 ClusterDims;
+    vec4 ClusterDepth;
+    uvec4 ClusterLights[288];
+    uvec4 ClusterLightIndices[512];
+};
+
+# ifdef STANDARDMATERIAL_DEPTH_FADE
+// the depth of the main pass, see SceneDepthNode
+layout(set = 1, binding = 2) uniform texture2D SceneDepth;
+layout(set = 1, binding = 3) uniform sampler SceneDepth_sampler;
+# endif
+
+# ifdef STANDARDMATERIAL_SHADED
+const int MAX_CASCADES = 4;
+
+// the cascades of the sun's shadow maps, which are side by side in ShadowMap, see ShadowNode
+layout(set = 1, binding = 4) uniform ShadowCascades {
+    mat4 CascadeViewProj[MAX_CASCADES];
+    vec4 CascadeFar;
+    vec4 CascadeTexelSize;
+    // the number of cascades in x, the width and height of each cascade's shadow map in y
+    uvec4 CascadeCount;
+};
+layout(set = 1, binding = 5) uniform texture2D ShadowMap;
+layout(set = 1, binding = 6) uniform sampler ShadowMap_sampler;
+
+// how much of the sun's light reaches the surface, from 0 in full shadow to 1
+float sun_visibility(vec3 position, vec3 normal, float view_depth) {
+    uint cascade_count = min(CascadeCount.x, uint(MAX_CASCADES));
+    uint cascade = 0u;
+    while (cascade < cascade_count && view_depth > CascadeFar[cascade]) {
+        ++cascade;
+    }
+    if (cascade >= cascade_count) {
+        return 1.0;
+    }
+    // look up the shadow map a little off the surface, so the surface doesn't shadow itself
+    vec3 offset_position = position + normal * CascadeTexelSize[cascade] * 1.5;
+    vec4 light_clip = CascadeViewProj[cascade] * vec4(offset_position, 1.0);
+    vec3 light_ndc = light_clip.xyz / light_clip.w;
+    int resolution = int(CascadeCount.y);
+    ivec2 texel = ivec2(floor((light_ndc.xy * vec2(0.5, -0.5) + 0.5) * float(resolution)));
+    ivec2 tile_offset = ivec2(int(cascade) * resolution, 0);
+    // average the nine nearest texels, staying inside this cascade's shadow map
+    float lit = 0.0;
+    for (int y = -1; y <= 1; ++y) {
+        for (int x = -1; x <= 1; ++x) {
+            ivec2 sample_texel = clamp(texel + ivec2(x, y), ivec2(0), ivec2(resolution - 1));
+            float caster_depth = texelFetch(
+                sampler2D(ShadowMap, ShadowMap_sampler),
+                sample_texel + tile_offset,
+                0).r;
+            lit += light_ndc.z <= caster_depth ? 1.0 : 0.0;
+        }
+    }
+    return lit / 9.0;
+}
+# endif
+
 layout(set = 3, binding = 0) uniform StandardMaterial_albedo {
     vec4 Albedo;
 };
@@ -32,6 +103,20 @@ layout(set = 3, binding = 1) uniform texture2D StandardMaterial_albedo_texture;
 layout(set = 3, binding = 2) uniform sampler StandardMaterial_albedo_texture_sampler;
 # endif
 
+# ifdef STANDARDMATERIAL_LIGHTMAP
+layout(set = 3, binding = 3) uniform texture2D StandardMaterial_lightmap;
+layout(set = 3, binding = 4) uniform sampler StandardMaterial_lightmap_sampler;
+layout(set = 3, binding = 5) uniform StandardMaterial_lightmap_exposure {
+    float LightmapExposure;
+};
+# endif
+
+# ifdef STANDARDMATERIAL_DEPTH_FADE
+layout(set = 3, binding = 6) uniform StandardMaterial_depth_fade_distance {
+    float DepthFadeDistance;
+};
+# endif
+
 void main() {
     vec4 output_color = Albedo;
 # ifdef STANDARDMATERIAL_ALBEDO_TEXTURE
@@ -42,18 +127,54 @@ void main() {
 
 # ifdef STANDARDMATERIAL_SHADED
     vec3 normal = normalize(v_Normal);
-    vec3 ambient = vec3(0.05, 0.05, 0.05);
-    // accumulate color
-    vec3 color = ambient;
-    for (int i=0; i<int(NumLights.x) && i<MAX_LIGHTS; ++i) {
-        Light light = SceneLights[i];
+# ifdef STANDARDMATERIAL_LIGHTMAP
+    // baked lighting replaces the constant ambient light
+    vec3 ambient = texture(
+        sampler2D(StandardMaterial_lightmap, StandardMaterial_lightmap_sampler),
+        v_Uv2).rgb * LightmapExposure;
+# else
+    vec3 ambient = Ambient.rgb;
+# endif
+    vec4 clip = ViewProj * vec4(v_Position, 1.0);
+    // accumulate color, starting with the sun
+    float sun = max(0.0, dot(normal, SunDirection.xyz)) * sun_visibility(v_Position, normal, clip.w);
+    vec3 color = ambient + sun * SunColor.rgb;
+    // find the cluster this fragment is in
+    vec2 ndc = clip.xy / clip.w;
+    uvec2 tile = uvec2(clamp(floor((ndc * 0.5 + 0.5) * vec2(ClusterDims.xy)), vec2(0.0), vec2(ClusterDims.xy - 1u)));
+    float slice = floor(log(max(clip.w, ClusterDepth.x) / ClusterDepth.x) * ClusterDepth.y);
+    uint z = uint(clamp(slice, 0.0, float(ClusterDims.z - 1u)));
+    uint cluster = (z * ClusterDims.y + tile.y) * ClusterDims.x + tile.x;
+    uint cluster_lights = ClusterLights[cluster / 4u][cluster % 4u];
+    uint offset = cluster_lights >> 8;
+    uint count = cluster_lights & 0xFFu;
+    for (uint i=0u; i<count; ++i) {
+        uint index_slot = offset + i;
+        uint index = (ClusterLightIndices[index_slot / 16u][(index_slot % 16u) / 4u] >> ((index_slot % 4u) * 8u)) & 0xFFu;
+        if (index >= NumLights.x) {
+            continue;
+        }
+        Light light = SceneLights[index];
         // compute Lambertian diffuse term
-        vec3 light_dir = normalize(light.pos.xyz - v_Position);
+        vec3 light_vector = light.pos.xyz - v_Position;
+        vec3 light_dir = normalize(light_vector);
         float diffuse = max(0.0, dot(normal, light_dir));
+        // fade out towards the light's range
+        float range_ratio = length(light_vector) / light.pos.w;
+        float falloff = clamp(1.0 - range_ratio * range_ratio * range_ratio * range_ratio, 0.0, 1.0);
         // add light contribution
-        color += diffuse * light.color.xyz;
+        color += diffuse * falloff * falloff * light.color.xyz;
     }
-    output_color.xyz *= color;
+    output_color.xyz *= color * Exposure;
+# endif
+
+# ifdef STANDARDMATERIAL_DEPTH_FADE
+    // fade out by the distance along the view ray to the scene behind this fragment
+    vec4 fade_clip = ViewProj * vec4(v_Position, 1.0);
+    float scene_depth = texelFetch(sampler2D(SceneDepth, SceneDepth_sampler), ivec2(gl_FragCoord.xy), 0).r;
+    vec4 scene_position = v_InverseViewProj * vec4(fade_clip.xy / fade_clip.w, scene_depth, 1.0);
+    float scene_distance = distance(scene_position.xyz / scene_position.w, v_Position);
+    output_color.a *= clamp(scene_distance / DepthFadeDistance, 0.0, 1.0);
 # endif
 
     // multiply the light by material color
@@ -3,10 +3,19 @@
 layout(location = 0) in vec3 Vertex_Position;
 layout(location = 1) in vec3 Vertex_Normal;
 layout(location = 2) in vec2 Vertex_Uv;
+# ifdef STANDARDMATERIAL_LIGHTMAP
+layout(location = 3) in vec2 Vertex_Uv2;
+# endif
 
 layout(location = 0) out vec3 v_Position;
 layout(location = 1) out vec3 v_Normal;
 layout(location = 2) out vec2 v_Uv;
+# ifdef STANDARDMATERIAL_LIGHTMAP
+layout(location = 3) out vec2 v_Uv2;
+# endif
+# ifdef STANDARDMATERIAL_DEPTH_FADE
+layout(location = 4) flat out mat4 v_InverseViewProj;
+# endif
 
 layout(set = 0, binding = 0) uniform Camera {
     mat4 ViewProj;
@@ -14,12 +23,18 @@ layout(set = 0, binding = 0) uniform Camera {
 
 layout(set = 2, binding = 0) uniform Transform {
     mat4 Model;
+    mat3 NormalMatrix;
 };
 
 void main() {
-    v_Normal = (Model * vec4(Vertex_Normal, 1.0)).xyz;
-    v_Normal = mat3(Model) * Vertex_Normal;
+    v_Normal = NormalMatrix * Vertex_Normal;
     v_Position = (Model * vec4(Vertex_Position, 1.0)).xyz;
     v_Uv = Vertex_Uv;
+# ifdef STANDARDMATERIAL_LIGHTMAP
+    v_Uv2 = Vertex_Uv2;
+# endif
+# ifdef STANDARDMATERIAL_DEPTH_FADE
+    v_InverseViewProj = inverse(ViewProj);
+# endif
     gl_Position = ViewProj * vec4(v_Position, 1.0);
 }
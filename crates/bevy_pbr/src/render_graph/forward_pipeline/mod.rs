@@ -13,6 +13,9 @@ use bevy_type_registry::TypeUuid;
 pub const FORWARD_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
     Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 13148362314012771389);
 
+pub const FORWARD_TRANSPARENT_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 4729374518102651083);
+
 pub(crate) fn build_forward_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
     PipelineDescriptor {
         rasterization_state: Some(RasterizationStateDescriptor {
@@ -60,3 +63,14 @@ pub(crate) fn build_forward_pipeline(shaders: &mut Assets<Shader>) -> PipelineDe
         })
     }
 }
+
+/// The forward pipeline without depth writes, so transparent entities don't hide the ones drawn after them
+pub(crate) fn build_forward_transparent_pipeline(
+    shaders: &mut Assets<Shader>,
+) -> PipelineDescriptor {
+    let mut pipeline = build_forward_pipeline(shaders);
+    if let Some(depth_stencil_state) = pipeline.depth_stencil_state.as_mut() {
+        depth_stencil_state.depth_write_enabled = false;
+    }
+    pipeline
+}
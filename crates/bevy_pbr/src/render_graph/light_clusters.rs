@@ -0,0 +1,220 @@
+use bevy_math::{Mat4, Vec3};
+use bevy_render::camera::{CameraProjection, PerspectiveProjection};
+
+/// The most lights the forward pipeline can shade. Must match MAX_LIGHTS in forward.frag.
+pub const MAX_LIGHTS: usize = 128;
+/// The number of clusters the view is split into horizontally, vertically and by depth
+pub const CLUSTER_DIMENSIONS: [u32; 3] = [12, 8, 12];
+pub const MAX_CLUSTERS: usize = 12 * 8 * 12;
+/// The most light indices all clusters can hold together. Lights that don't fit are left out of the clusters.
+pub const MAX_CLUSTER_LIGHT_INDICES: usize = 8192;
+
+/// The view frustum of the 3D camera, split into a grid of clusters, and the lights that reach each cluster. The grid
+/// is uniform on screen and exponential in depth, so clusters are roughly cube shaped. Shaded materials only
+/// evaluate the lights of the cluster a fragment is in.
+///
+/// Lights are indexed with a byte, so only the first 256 lights can be assigned to clusters.
+#[derive(Debug, Clone)]
+pub struct LightClusters {
+    dimensions: [u32; 3],
+    near: f32,
+    far: f32,
+    /// The lights of each cluster
+    cluster_lights: Vec<Vec<u8>>,
+}
+
+/// The fields of the LightClusters uniform before the cluster arrays
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct LightClustersHeader {
+    dimensions: [u32; 4],
+    /// The near distance and the number of depth slices per logarithmic unit of distance
+    depth: [f32; 4],
+}
+
+unsafe impl bevy_core::Byteable for LightClustersHeader {}
+
+impl LightClusters {
+    /// The size of the LightClusters uniform in bytes
+    pub const UNIFORM_SIZE: usize =
+        std::mem::size_of::<LightClustersHeader>() + MAX_CLUSTERS * 4 + MAX_CLUSTER_LIGHT_INDICES;
+
+    /// Assigns lights to the clusters of a perspective camera. `lights` are world space positions and ranges.
+    pub fn new(
+        projection: &PerspectiveProjection,
+        camera_transform: &Mat4,
+        lights: impl Iterator<Item = (Vec3, f32)>,
+    ) -> Self {
+        let [width, height, depth] = CLUSTER_DIMENSIONS;
+        let mut clusters = LightClusters {
+            dimensions: CLUSTER_DIMENSIONS,
+            near: projection.near,
+            far: projection.far,
+            cluster_lights: vec![Vec::new(); (width * height * depth) as usize],
+        };
+
+        let view = camera_transform.inverse();
+        let projection_matrix = projection.get_projection_matrix();
+        for (index, (position, range)) in lights.take(256).enumerate() {
+            let center = view.transform_point3(position);
+            let distance = -center.z();
+            if distance + range < clusters.near || distance - range > clusters.far {
+                continue;
+            }
+
+            let slices = clusters.slice(distance - range)..=clusters.slice(distance + range);
+            let (min_tile, max_tile) = if distance - range <= clusters.near {
+                // lights that reach the camera can light the whole screen
+                ([0, 0], [width - 1, height - 1])
+            } else {
+                // project the light's bounding box onto the screen
+                let mut min = [f32::MAX; 2];
+                let mut max = [f32::MIN; 2];
+                for corner in 0..8 {
+                    let offset = Vec3::new(
+                        if corner & 1 == 0 { -range } else { range },
+                        if corner & 2 == 0 { -range } else { range },
+                        if corner & 4 == 0 { -range } else { range },
+                    );
+                    let clip = projection_matrix * (center + offset).extend(1.0);
+                    let ndc = clip.truncate() / clip.w();
+                    for axis in 0..2 {
+                        let value = if axis == 0 { ndc.x() } else { ndc.y() };
+                        min[axis] = min[axis].min(value);
+                        max[axis] = max[axis].max(value);
+                    }
+                }
+                (
+                    [tile(min[0], width), tile(min[1], height)],
+                    [tile(max[0], width), tile(max[1], height)],
+                )
+            };
+
+            for slice in slices {
+                for y in min_tile[1]..=max_tile[1] {
+                    for x in min_tile[0]..=max_tile[0] {
+                        let cluster = (slice * height + y) * width + x;
+                        clusters.cluster_lights[cluster as usize].push(index as u8);
+                    }
+                }
+            }
+        }
+
+        clusters
+    }
+
+    /// A single cluster that holds every light, for cameras that aren't perspective cameras
+    pub fn single(light_count: usize) -> Self {
+        LightClusters {
+            dimensions: [1, 1, 1],
+            near: 1.0,
+            far: 1.0,
+            cluster_lights: vec![(0..light_count.min(256)).map(|i| i as u8).collect()],
+        }
+    }
+
+    /// The depth slice that contains the given distance from the camera
+    fn slice(&self, distance: f32) -> u32 {
+        let slices = self.dimensions[2];
+        if distance <= self.near || slices <= 1 {
+            return 0;
+        }
+        let slice = (distance / self.near).ln() * self.slices_per_log_distance();
+        (slice as u32).min(slices - 1)
+    }
+
+    fn slices_per_log_distance(&self) -> f32 {
+        if self.far > self.near {
+            self.dimensions[2] as f32 / (self.far / self.near).ln()
+        } else {
+            0.0
+        }
+    }
+
+    /// The lights that reach the cluster at the given grid position
+    pub fn lights(&self, x: u32, y: u32, slice: u32) -> &[u8] {
+        let [width, height, _] = self.dimensions;
+        &self.cluster_lights[((slice * height + y) * width + x) as usize]
+    }
+
+    /// Writes the LightClusters uniform. Each cluster is a u32 with the offset of its first light index in the upper
+    /// 24 bits and its light count in the lower 8 bits. Light indices are bytes.
+    pub fn write_uniform(&self, buffer: &mut [u8]) {
+        use bevy_core::Bytes;
+        let header_size = std::mem::size_of::<LightClustersHeader>();
+        let [width, height, depth] = self.dimensions;
+        LightClustersHeader {
+            dimensions: [width, height, depth, 0],
+            depth: [self.near, self.slices_per_log_distance(), 0.0, 0.0],
+        }
+        .write_bytes(&mut buffer[..header_size]);
+
+        let (cluster_buffer, index_buffer) = buffer[header_size..].split_at_mut(MAX_CLUSTERS * 4);
+        let mut offset = 0;
+        for (lights, cluster) in self
+            .cluster_lights
+            .iter()
+            .zip(cluster_buffer.chunks_exact_mut(4))
+        {
+            let count = lights
+                .len()
+                .min(255)
+                .min(MAX_CLUSTER_LIGHT_INDICES - offset);
+            index_buffer[offset..offset + count].copy_from_slice(&lights[..count]);
+            cluster.copy_from_slice(&(((offset as u32) << 8) | count as u32).to_ne_bytes());
+            offset += count;
+        }
+    }
+}
+
+/// The tile that contains a normalized device coordinate, in a row or column of `tiles` tiles
+fn tile(ndc: f32, tiles: u32) -> u32 {
+    (((ndc * 0.5 + 0.5) * tiles as f32).floor().max(0.0) as u32).min(tiles - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LightClusters, CLUSTER_DIMENSIONS, MAX_CLUSTERS};
+    use bevy_math::{Mat4, Vec3};
+    use bevy_render::camera::PerspectiveProjection;
+
+    #[test]
+    fn lights_are_assigned_to_clusters() {
+        let projection = PerspectiveProjection {
+            near: 1.0,
+            far: 100.0,
+            ..Default::default()
+        };
+        // the camera looks down -z from the origin
+        let lights = vec![
+            // a small light straight ahead
+            (Vec3::new(0.0, 0.0, -10.0), 1.0),
+            // a light that reaches the camera
+            (Vec3::new(0.0, 1.0, 0.0), 2.0),
+            // a light behind the camera
+            (Vec3::new(0.0, 0.0, 50.0), 5.0),
+        ];
+        let clusters = LightClusters::new(&projection, &Mat4::identity(), lights.into_iter());
+
+        let [width, height, depth] = CLUSTER_DIMENSIONS;
+        let center_slice = clusters.slice(10.0);
+        assert_eq!(clusters.lights(width / 2, height / 2, center_slice), &[0]);
+        assert_eq!(clusters.lights(0, 0, center_slice), &[] as &[u8]);
+        assert_eq!(clusters.lights(0, 0, 0), &[1]);
+        assert_eq!(
+            clusters.lights(width - 1, height - 1, depth - 1),
+            &[] as &[u8]
+        );
+        assert_eq!(clusters.slice(0.5), 0);
+        assert_eq!(clusters.slice(1000.0), depth - 1);
+
+        let mut buffer = vec![0; LightClusters::UNIFORM_SIZE];
+        clusters.write_uniform(&mut buffer);
+        let cluster = ((center_slice * height + height / 2) * width + width / 2) as usize;
+        let mut word = [0; 4];
+        word.copy_from_slice(&buffer[32 + cluster * 4..32 + cluster * 4 + 4]);
+        let word = u32::from_ne_bytes(word);
+        assert_eq!(word & 0xFF, 1);
+        assert_eq!(buffer[32 + MAX_CLUSTERS * 4 + (word >> 8) as usize], 0);
+    }
+}
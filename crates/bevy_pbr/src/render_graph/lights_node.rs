@@ -1,11 +1,13 @@
+use super::LightClusters;
 use crate::{
-    light::{Light, LightRaw},
+    light::{EnvironmentLight, Light, LightRaw},
     render_graph::uniform,
 };
 use bevy_core::{AsBytes, Byteable};
 use bevy_ecs::{Commands, IntoQuerySystem, Local, Query, Res, ResMut, Resources, System, World};
 use bevy_render::{
-    render_graph::{CommandQueue, Node, ResourceSlots, SystemNode},
+    camera::{ActiveCameras, Camera, Exposure, PerspectiveProjection},
+    render_graph::{base, CommandQueue, Node, ResourceSlots, SystemNode},
     renderer::{
         BufferId, BufferInfo, BufferUsage, RenderContext, RenderResourceBinding,
         RenderResourceBindings, RenderResourceContext,
@@ -42,13 +44,20 @@ impl Node for LightsNode {
     }
 }
 
+/// The fields of the Lights uniform before the light array
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
-struct LightCount {
+struct LightsHeader {
     pub num_lights: [u32; 4],
+    /// The [Exposure::scale] of the 3D camera in `x`
+    pub exposure: [f32; 4],
+    /// The [EnvironmentLight]
+    pub ambient: [f32; 4],
+    pub sun_direction: [f32; 4],
+    pub sun_color: [f32; 4],
 }
 
-unsafe impl Byteable for LightCount {}
+unsafe impl Byteable for LightsHeader {}
 
 impl SystemNode for LightsNode {
     fn get_system(&self, commands: &mut Commands) -> Box<dyn System> {
@@ -59,6 +68,7 @@ impl SystemNode for LightsNode {
                 command_queue: self.command_queue.clone(),
                 max_lights: self.max_lights,
                 light_buffer: None,
+                cluster_buffer: None,
                 staging_buffer: None,
             },
         );
@@ -70,6 +80,7 @@ impl SystemNode for LightsNode {
 #[derive(Debug, Default)]
 pub struct LightsNodeSystemState {
     light_buffer: Option<BufferId>,
+    cluster_buffer: Option<BufferId>,
     staging_buffer: Option<BufferId>,
     command_queue: CommandQueue,
     max_lights: usize,
@@ -80,24 +91,42 @@ pub fn lights_node_system(
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
     // TODO: this write on RenderResourceBindings will prevent this system from running in parallel with other systems that do the same
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
+    active_cameras: Res<ActiveCameras>,
+    environment_light: Res<EnvironmentLight>,
+    camera_query: Query<(&Camera, &GlobalTransform, Option<&PerspectiveProjection>)>,
     query: Query<(&Light, &GlobalTransform)>,
 ) {
     let state = &mut state;
     let render_resource_context = &**render_resource_context;
 
-    let light_count = query.iter().count();
+    let light_count = query.iter().count().min(state.max_lights);
     let size = std::mem::size_of::<LightRaw>();
-    let light_count_size = std::mem::size_of::<LightCount>();
+    let light_count_size = std::mem::size_of::<LightsHeader>();
     let light_array_size = size * light_count;
     let light_array_max_size = size * state.max_lights;
     let current_light_uniform_size = light_count_size + light_array_size;
     let max_light_uniform_size = light_count_size + light_array_max_size;
+    let staging_size = max_light_uniform_size + LightClusters::UNIFORM_SIZE;
 
-    if let Some(staging_buffer) = state.staging_buffer {
-        if light_count == 0 {
-            return;
-        }
+    let camera = active_cameras
+        .get(base::camera::CAMERA3D)
+        .and_then(|entity| camera_query.get(entity).ok());
+    let exposure = camera
+        .and_then(|(camera, _, _)| camera.exposure)
+        .map_or(1.0, |exposure: Exposure| exposure.scale());
+    let clusters = match camera {
+        Some((_, camera_transform, Some(projection))) => LightClusters::new(
+            projection,
+            &camera_transform.compute_matrix(),
+            query
+                .iter()
+                .take(light_count)
+                .map(|(light, transform)| (transform.translation, light.range)),
+        ),
+        _ => LightClusters::single(light_count),
+    };
 
+    if let Some(staging_buffer) = state.staging_buffer {
         render_resource_context.map_buffer(staging_buffer);
     } else {
         let buffer = render_resource_context.create_buffer(BufferInfo {
@@ -115,8 +144,23 @@ pub fn lights_node_system(
         );
         state.light_buffer = Some(buffer);
 
+        let cluster_buffer = render_resource_context.create_buffer(BufferInfo {
+            size: LightClusters::UNIFORM_SIZE,
+            buffer_usage: BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+            ..Default::default()
+        });
+        render_resource_bindings.set(
+            uniform::LIGHT_CLUSTERS,
+            RenderResourceBinding::Buffer {
+                buffer: cluster_buffer,
+                range: 0..LightClusters::UNIFORM_SIZE as u64,
+                dynamic_index: None,
+            },
+        );
+        state.cluster_buffer = Some(cluster_buffer);
+
         let staging_buffer = render_resource_context.create_buffer(BufferInfo {
-            size: max_light_uniform_size,
+            size: staging_size,
             buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
             mapped_at_creation: true,
         });
@@ -126,10 +170,21 @@ pub fn lights_node_system(
     let staging_buffer = state.staging_buffer.unwrap();
     render_resource_context.write_mapped_buffer(
         staging_buffer,
-        0..current_light_uniform_size as u64,
+        0..staging_size as u64,
         &mut |data, _renderer| {
-            // light count
-            data[0..light_count_size].copy_from_slice([light_count as u32, 0, 0, 0].as_bytes());
+            // light count, exposure and environment light
+            let header = LightsHeader {
+                num_lights: [light_count as u32, 0, 0, 0],
+                exposure: [exposure, 0.0, 0.0, 0.0],
+                ambient: environment_light.ambient.extend(0.0).into(),
+                sun_direction: environment_light
+                    .sun_direction
+                    .normalize()
+                    .extend(0.0)
+                    .into(),
+                sun_color: environment_light.sun_color.extend(0.0).into(),
+            };
+            data[0..light_count_size].copy_from_slice(header.as_bytes());
 
             // light array
             for ((light, global_transform), slot) in query
@@ -138,6 +193,9 @@ pub fn lights_node_system(
             {
                 slot.copy_from_slice(LightRaw::from(&light, &global_transform).as_bytes());
             }
+
+            // the lights of each cluster, after the space for the maximum number of lights
+            clusters.write_uniform(&mut data[max_light_uniform_size..]);
         },
     );
     render_resource_context.unmap_buffer(staging_buffer);
@@ -149,4 +207,12 @@ pub fn lights_node_system(
         0,
         max_light_uniform_size as u64,
     );
+    let cluster_buffer = state.cluster_buffer.unwrap();
+    state.command_queue.copy_buffer_to_buffer(
+        staging_buffer,
+        max_light_uniform_size as u64,
+        cluster_buffer,
+        0,
+        LightClusters::UNIFORM_SIZE as u64,
+    );
 }
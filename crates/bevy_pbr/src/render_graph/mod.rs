@@ -1,19 +1,31 @@
 mod forward_pipeline;
+mod light_clusters;
 mod lights_node;
+mod shadow_node;
+mod sky_node;
 
 pub use forward_pipeline::*;
+pub use light_clusters::*;
 pub use lights_node::*;
+pub use shadow_node::*;
+pub use sky_node::*;
 
 /// the names of pbr graph nodes
 pub mod node {
     pub const TRANSFORM: &str = "transform";
     pub const STANDARD_MATERIAL: &str = "standard_material";
     pub const LIGHTS: &str = "lights";
+    pub const SHADOWS: &str = "shadows";
+    pub const SKY: &str = "sky";
 }
 
 /// the names of pbr uniforms
 pub mod uniform {
     pub const LIGHTS: &str = "Lights";
+    pub const LIGHT_CLUSTERS: &str = "LightClusters";
+    pub const SHADOW_CASCADES: &str = "ShadowCascades";
+    pub const SHADOW_MAP: &str = "ShadowMap";
+    pub const SHADOW_MAP_SAMPLER: &str = "ShadowMap_sampler";
 }
 
 use crate::prelude::StandardMaterial;
@@ -35,13 +47,19 @@ pub(crate) fn add_pbr_graph(graph: &mut RenderGraph, resources: &Resources) {
         node::STANDARD_MATERIAL,
         AssetRenderResourcesNode::<StandardMaterial>::new(true),
     );
-    graph.add_system_node(node::LIGHTS, LightsNode::new(10));
+    graph.add_system_node(node::LIGHTS, LightsNode::new(MAX_LIGHTS));
+    graph.add_system_node(node::SHADOWS, ShadowNode::default());
     let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
     let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
     pipelines.set_untracked(
         FORWARD_PIPELINE_HANDLE,
         build_forward_pipeline(&mut shaders),
     );
+    pipelines.set_untracked(
+        FORWARD_TRANSPARENT_PIPELINE_HANDLE,
+        build_forward_transparent_pipeline(&mut shaders),
+    );
+    pipelines.set_untracked(SHADOW_PIPELINE_HANDLE, build_shadow_pipeline(&mut shaders));
 
     // TODO: replace these with "autowire" groups
     graph
@@ -53,4 +71,9 @@ pub(crate) fn add_pbr_graph(graph: &mut RenderGraph, resources: &Resources) {
     graph
         .add_node_edge(node::LIGHTS, base::node::MAIN_PASS)
         .unwrap();
+    // the shadow casters' transforms are written by the transform node
+    graph.add_node_edge(node::TRANSFORM, node::SHADOWS).unwrap();
+    graph
+        .add_node_edge(node::SHADOWS, base::node::MAIN_PASS)
+        .unwrap();
 }
@@ -0,0 +1,17 @@
+#version 450
+
+layout(location = 0) in vec3 Vertex_Position;
+
+// the view projection of the cascade being drawn, see ShadowNode
+layout(set = 0, binding = 0) uniform ShadowCascade {
+    mat4 CascadeViewProj;
+};
+
+layout(set = 1, binding = 0) uniform Transform {
+    mat4 Model;
+    mat3 NormalMatrix;
+};
+
+void main() {
+    gl_Position = CascadeViewProj * Model * vec4(Vertex_Position, 1.0);
+}
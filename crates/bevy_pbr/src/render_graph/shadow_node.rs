@@ -0,0 +1,493 @@
+use super::uniform;
+use crate::{
+    light::EnvironmentLight,
+    material::StandardMaterial,
+    shadow_cascades::{Cascade, CascadeShadowConfig, MAX_CASCADES},
+};
+use bevy_asset::{Assets, Handle};
+use bevy_core::{AsBytes, Byteable};
+use bevy_ecs::{
+    Commands, IntoQuerySystem, Local, Query, Res, ResMut, Resources, System, With, World,
+};
+use bevy_math::Vec3;
+use bevy_render::{
+    camera::{ActiveCameras, PerspectiveProjection},
+    draw::Visible,
+    mesh::{Indices, Mesh},
+    pass::{
+        LoadOp, Operations, PassDescriptor, RenderPassDepthStencilAttachmentDescriptor,
+        TextureAttachment,
+    },
+    pipeline::{
+        BindGroupDescriptorId, CullMode, DynamicBinding, FrontFace, PipelineCompiler,
+        PipelineDescriptor, PipelineSpecialization, RasterizationStateDescriptor, RenderPipelines,
+    },
+    render_graph::{base, CommandQueue, Node, ResourceSlots, SystemNode},
+    renderer::{
+        BindGroup, BufferId, BufferInfo, BufferUsage, RenderContext, RenderResourceBinding,
+        RenderResourceBindings, RenderResourceContext, TextureId,
+    },
+    shader::{Shader, ShaderStage, ShaderStages},
+    texture::{
+        Extent3d, FilterMode, SamplerDescriptor, TextureDescriptor, TextureFormat, TextureUsage,
+    },
+};
+use bevy_transform::prelude::GlobalTransform;
+use bevy_type_registry::TypeUuid;
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+pub const SHADOW_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 9185403926513578021);
+
+/// Where the view projection of each cascade starts in the shadow uniform buffer, after the ShadowCascades uniform.
+/// Bindings into a uniform buffer have to start at multiples of 256 bytes.
+const CASCADE_OFFSET: u64 = 512;
+const CASCADE_STRIDE: u64 = 256;
+const UNIFORM_BUFFER_SIZE: u64 = CASCADE_OFFSET + CASCADE_STRIDE * MAX_CASCADES as u64;
+
+/// The ShadowCascades uniform of forward.frag
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct ShadowCascadesUniform {
+    view_projections: [[[f32; 4]; 4]; MAX_CASCADES],
+    /// The distance from the camera where each cascade ends
+    far: [f32; MAX_CASCADES],
+    /// The size of a shadow map texel of each cascade, in world units
+    texel_size: [f32; MAX_CASCADES],
+    /// The number of cascades in `x`, and the width and height of each cascade's shadow map in `y`
+    count: [u32; 4],
+}
+
+unsafe impl Byteable for ShadowCascadesUniform {}
+
+impl ShadowCascadesUniform {
+    fn new(cascades: &[Cascade], resolution: u32) -> Self {
+        let mut uniform = ShadowCascadesUniform {
+            view_projections: [[[0.0; 4]; 4]; MAX_CASCADES],
+            far: [0.0; MAX_CASCADES],
+            texel_size: [0.0; MAX_CASCADES],
+            count: [cascades.len().min(MAX_CASCADES) as u32, resolution, 0, 0],
+        };
+        for (index, cascade) in cascades.iter().take(MAX_CASCADES).enumerate() {
+            uniform.view_projections[index] = cascade.view_projection.to_cols_array_2d();
+            uniform.far[index] = cascade.far;
+            uniform.texel_size[index] = cascade.texel_size;
+        }
+        uniform
+    }
+}
+
+/// The shadow map of the current frame, shared by the [ShadowNode] and its system
+#[derive(Debug, Default)]
+struct ShadowMap {
+    texture: Option<TextureId>,
+    uniform_buffer: Option<BufferId>,
+    /// The width and height of each cascade's part of the texture, in texels
+    resolution: u32,
+    /// How many cascades fit in the texture
+    capacity: usize,
+    /// How many cascades are drawn this frame
+    cascade_count: usize,
+}
+
+/// Renders the depth of shadow casters, as seen from the sun of the [EnvironmentLight], into one shadow map for each
+/// cascade of the [CascadeShadowConfig]. The shadow maps are side by side in the "ShadowMap" texture, which shaded
+/// [StandardMaterial]s sample in the main pass. Every opaque entity with a [StandardMaterial] casts shadows in every
+/// cascade.
+#[derive(Debug, Default)]
+pub struct ShadowNode {
+    command_queue: CommandQueue,
+    shadow_map: Arc<Mutex<ShadowMap>>,
+}
+
+impl Node for ShadowNode {
+    fn update(
+        &mut self,
+        world: &World,
+        resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        self.command_queue.execute(render_context);
+
+        let shadow_map = self.shadow_map.lock().unwrap();
+        let (texture, uniform_buffer) = match (shadow_map.texture, shadow_map.uniform_buffer) {
+            (Some(texture), Some(uniform_buffer)) => (texture, uniform_buffer),
+            _ => return,
+        };
+        let resolution = shadow_map.resolution;
+
+        let render_resource_context = render_context.resources();
+        let mut casters = Vec::new();
+        if shadow_map.cascade_count > 0 {
+            let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+            let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+            let mut pipeline_compiler = resources.get_mut::<PipelineCompiler>().unwrap();
+            let meshes = resources.get::<Assets<Mesh>>().unwrap();
+            for (visible, mesh, render_pipelines) in
+                world.query::<With<
+                    Handle<StandardMaterial>,
+                    (Option<&Visible>, &Handle<Mesh>, &RenderPipelines),
+                >>()
+            {
+                if !visible.map_or(true, |visible| {
+                    visible.is_drawn() && !visible.is_transparent
+                }) {
+                    continue;
+                }
+                if let Some(caster) = meshes.get(mesh).and_then(|mesh| {
+                    ShadowCaster::new(
+                        render_resource_context,
+                        &mut pipelines,
+                        &mut shaders,
+                        &mut pipeline_compiler,
+                        mesh,
+                        render_pipelines,
+                    )
+                }) {
+                    casters.push(caster);
+                }
+            }
+        }
+
+        // every shadow pipeline has the same cascade bind group, so it doesn't matter which caster it comes from
+        let cascade_bind_groups = casters.first().map_or_else(Vec::new, |caster| {
+            (0..shadow_map.cascade_count as u64)
+                .map(|index| {
+                    let start = CASCADE_OFFSET + index * CASCADE_STRIDE;
+                    let bind_group = BindGroup::build()
+                        .add_buffer(0, uniform_buffer, start..start + 64)
+                        .finish();
+                    render_resource_context
+                        .create_bind_group(caster.cascade_bind_group_descriptor, &bind_group);
+                    bind_group
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let descriptor = PassDescriptor {
+            color_attachments: Vec::new(),
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                attachment: TextureAttachment::Id(texture),
+                depth_ops: Some(Operations {
+                    load: LoadOp::Clear(1.0),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+            sample_count: 1,
+        };
+        let render_resource_bindings = resources.get::<RenderResourceBindings>().unwrap();
+        render_context.begin_pass(&descriptor, &render_resource_bindings, &mut |render_pass| {
+            for (index, cascade_bind_group) in cascade_bind_groups.iter().enumerate() {
+                // each cascade draws into its own part of the texture
+                let x = index as u32 * resolution;
+                render_pass.set_viewport(
+                    x as f32,
+                    0.0,
+                    resolution as f32,
+                    resolution as f32,
+                    0.0,
+                    1.0,
+                );
+                render_pass.set_scissor_rect(x, 0, resolution, resolution);
+                for caster in casters.iter() {
+                    render_pass.set_pipeline(&caster.pipeline);
+                    render_pass.set_bind_group(
+                        0,
+                        caster.cascade_bind_group_descriptor,
+                        cascade_bind_group.id,
+                        None,
+                    );
+                    render_pass.set_bind_group(
+                        1,
+                        caster.transform_bind_group_descriptor,
+                        caster.transform_bind_group.id,
+                        caster
+                            .transform_bind_group
+                            .dynamic_uniform_indices
+                            .as_deref(),
+                    );
+                    render_pass.set_vertex_buffer(0, caster.vertex_buffer, 0);
+                    render_pass.set_index_buffer(caster.index_buffer, 0);
+                    render_pass.draw_indexed(caster.indices.clone(), 0, 0..1);
+                }
+            }
+        });
+    }
+}
+
+/// An entity drawn into the shadow maps, with the shadow pipeline compiled for its mesh
+struct ShadowCaster {
+    pipeline: Handle<PipelineDescriptor>,
+    cascade_bind_group_descriptor: BindGroupDescriptorId,
+    transform_bind_group_descriptor: BindGroupDescriptorId,
+    transform_bind_group: BindGroup,
+    vertex_buffer: BufferId,
+    index_buffer: BufferId,
+    indices: Range<u32>,
+}
+
+impl ShadowCaster {
+    /// Returns `None` until the entity's mesh and transform are on the GPU, or if the shadow pipeline fails to compile
+    fn new(
+        render_resource_context: &dyn RenderResourceContext,
+        pipelines: &mut Assets<PipelineDescriptor>,
+        shaders: &mut Assets<Shader>,
+        pipeline_compiler: &mut PipelineCompiler,
+        mesh: &Mesh,
+        render_pipelines: &RenderPipelines,
+    ) -> Option<Self> {
+        let indices = match mesh.indices()? {
+            Indices::U32(indices) => 0..indices.len() as u32,
+            Indices::U16(indices) => 0..indices.len() as u32,
+        };
+        let transform = render_pipelines.bindings.get("Transform")?.clone();
+        // the mesh's layout is in the specialization of the entity's own pipelines
+        let mesh_specialization = &render_pipelines.pipelines.first()?.specialization;
+        let dynamic_bindings = match transform {
+            RenderResourceBinding::Buffer {
+                dynamic_index: Some(_),
+                ..
+            } => vec![DynamicBinding {
+                bind_group: 1,
+                binding: 0,
+            }],
+            _ => Vec::new(),
+        };
+        let specialization = PipelineSpecialization {
+            primitive_topology: mesh_specialization.primitive_topology,
+            index_format: mesh_specialization.index_format,
+            vertex_buffer_descriptor: mesh_specialization.vertex_buffer_descriptor.clone(),
+            dynamic_bindings,
+            ..Default::default()
+        };
+        let pipeline = pipeline_compiler
+            .compile_pipeline(
+                render_resource_context,
+                pipelines,
+                shaders,
+                &SHADOW_PIPELINE_HANDLE,
+                &specialization,
+            )
+            .ok()?;
+        let layout = pipelines.get(&pipeline)?.get_layout()?;
+        let cascade_bind_group_descriptor = layout.get_bind_group(0)?.id;
+        let transform_bind_group_descriptor = layout.get_bind_group(1)?.id;
+        let transform_bind_group = BindGroup::build().add_binding(0, transform).finish();
+        render_resource_context
+            .create_bind_group(transform_bind_group_descriptor, &transform_bind_group);
+
+        Some(ShadowCaster {
+            pipeline,
+            cascade_bind_group_descriptor,
+            transform_bind_group_descriptor,
+            transform_bind_group,
+            vertex_buffer: render_pipelines.bindings.vertex_attribute_buffer?,
+            index_buffer: render_pipelines.bindings.index_buffer?,
+            indices,
+        })
+    }
+}
+
+impl SystemNode for ShadowNode {
+    fn get_system(&self, commands: &mut Commands) -> Box<dyn System> {
+        let system = shadow_node_system.system();
+        commands.insert_local_resource(
+            system.id(),
+            ShadowNodeSystemState {
+                command_queue: self.command_queue.clone(),
+                shadow_map: self.shadow_map.clone(),
+                staging_buffer: None,
+            },
+        );
+        system
+    }
+}
+
+/// Local "shadow node system" state
+#[derive(Debug, Default)]
+pub struct ShadowNodeSystemState {
+    command_queue: CommandQueue,
+    shadow_map: Arc<Mutex<ShadowMap>>,
+    staging_buffer: Option<BufferId>,
+}
+
+/// Fits the cascades to the 3D camera's view and writes them to the ShadowCascades uniform. Sets the global
+/// "ShadowCascades", "ShadowMap" and "ShadowMap_sampler" bindings, which exist even when nothing casts shadows.
+pub fn shadow_node_system(
+    mut state: Local<ShadowNodeSystemState>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+    mut render_resource_bindings: ResMut<RenderResourceBindings>,
+    config: Res<CascadeShadowConfig>,
+    environment_light: Res<EnvironmentLight>,
+    active_cameras: Res<ActiveCameras>,
+    camera_query: Query<(&GlobalTransform, &PerspectiveProjection)>,
+) {
+    let state = &mut *state;
+    let render_resource_context = &**render_resource_context;
+    let mut shadow_map = state.shadow_map.lock().unwrap();
+
+    let resolution = config.resolution.max(1);
+    // keep room for a cascade when shadows are off, so there is always a texture to bind
+    let capacity = config.cascade_count.clamp(1, MAX_CASCADES);
+    if shadow_map.texture.is_none()
+        || shadow_map.resolution != resolution
+        || shadow_map.capacity != capacity
+    {
+        if let Some(texture) = shadow_map.texture {
+            render_resource_context.remove_texture(texture);
+        }
+        let texture = render_resource_context.create_texture(TextureDescriptor {
+            size: Extent3d {
+                width: resolution * capacity as u32,
+                height: resolution,
+                depth: 1,
+            },
+            format: TextureFormat::Depth32Float,
+            usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
+            ..Default::default()
+        });
+        render_resource_bindings.set(uniform::SHADOW_MAP, RenderResourceBinding::Texture(texture));
+        shadow_map.texture = Some(texture);
+        shadow_map.resolution = resolution;
+        shadow_map.capacity = capacity;
+    }
+
+    if state.staging_buffer.is_none() {
+        // the shadow map is read with texelFetch, the sampler is only there to bind it
+        let sampler = render_resource_context.create_sampler(&SamplerDescriptor {
+            min_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+        render_resource_bindings.set(
+            uniform::SHADOW_MAP_SAMPLER,
+            RenderResourceBinding::Sampler(sampler),
+        );
+        let buffer = render_resource_context.create_buffer(BufferInfo {
+            size: UNIFORM_BUFFER_SIZE as usize,
+            buffer_usage: BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+            ..Default::default()
+        });
+        render_resource_bindings.set(
+            uniform::SHADOW_CASCADES,
+            RenderResourceBinding::Buffer {
+                buffer,
+                range: 0..std::mem::size_of::<ShadowCascadesUniform>() as u64,
+                dynamic_index: None,
+            },
+        );
+        shadow_map.uniform_buffer = Some(buffer);
+        state.staging_buffer = Some(render_resource_context.create_buffer(BufferInfo {
+            size: UNIFORM_BUFFER_SIZE as usize,
+            buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
+            ..Default::default()
+        }));
+    }
+
+    // the sun shines the opposite way of the direction towards it
+    let cascades = match active_cameras
+        .get(base::camera::CAMERA3D)
+        .and_then(|entity| camera_query.get(entity).ok())
+    {
+        Some((camera_transform, projection)) if environment_light.sun_color != Vec3::zero() => {
+            config.compute_cascades(
+                projection,
+                camera_transform,
+                -environment_light.sun_direction,
+            )
+        }
+        _ => Vec::new(),
+    };
+    shadow_map.cascade_count = cascades.len();
+    let uniform = ShadowCascadesUniform::new(&cascades, resolution);
+
+    let staging_buffer = state.staging_buffer.unwrap();
+    render_resource_context.map_buffer(staging_buffer);
+    render_resource_context.write_mapped_buffer(
+        staging_buffer,
+        0..UNIFORM_BUFFER_SIZE,
+        &mut |data, _renderer| {
+            let uniform = uniform.as_bytes();
+            data[0..uniform.len()].copy_from_slice(uniform);
+            for (index, cascade) in cascades.iter().enumerate() {
+                let start = (CASCADE_OFFSET + index as u64 * CASCADE_STRIDE) as usize;
+                data[start..start + 64]
+                    .copy_from_slice(cascade.view_projection.to_cols_array().as_bytes());
+            }
+        },
+    );
+    render_resource_context.unmap_buffer(staging_buffer);
+    state.command_queue.copy_buffer_to_buffer(
+        staging_buffer,
+        0,
+        shadow_map.uniform_buffer.unwrap(),
+        0,
+        UNIFORM_BUFFER_SIZE,
+    );
+}
+
+pub(crate) fn build_shadow_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    PipelineDescriptor {
+        rasterization_state: Some(RasterizationStateDescriptor {
+            front_face: FrontFace::Ccw,
+            cull_mode: CullMode::Back,
+            // pushes the depth away from the light, so lit surfaces don't shadow themselves
+            depth_bias: 2,
+            depth_bias_slope_scale: 2.0,
+            depth_bias_clamp: 0.0,
+            clamp_depth: false,
+        }),
+        // depth only
+        color_states: Vec::new(),
+        ..PipelineDescriptor::default_config(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                include_str!("shadow.vert"),
+            )),
+            fragment: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShadowCascadesUniform, CASCADE_OFFSET};
+    use crate::shadow_cascades::{CascadeShadowConfig, MAX_CASCADES};
+    use bevy_math::Vec3;
+    use bevy_render::camera::PerspectiveProjection;
+    use bevy_transform::components::GlobalTransform;
+
+    #[test]
+    fn shadow_uniform_holds_every_cascade() {
+        let config = CascadeShadowConfig {
+            cascade_count: 3,
+            resolution: 1024,
+            ..Default::default()
+        };
+        let camera_transform = GlobalTransform::from_translation(Vec3::new(0.0, 2.0, 10.0));
+        let cascades = config.compute_cascades(
+            &PerspectiveProjection::default(),
+            &camera_transform,
+            Vec3::new(0.0, -1.0, -1.0),
+        );
+        let uniform = ShadowCascadesUniform::new(&cascades, config.resolution);
+
+        assert_eq!(uniform.count, [3, 1024, 0, 0]);
+        for (index, cascade) in cascades.iter().enumerate() {
+            assert_eq!(uniform.far[index], cascade.far);
+            assert_eq!(uniform.texel_size[index], cascade.texel_size);
+            assert_eq!(
+                uniform.view_projections[index],
+                cascade.view_projection.to_cols_array_2d()
+            );
+        }
+        assert_eq!(uniform.far[MAX_CASCADES - 1], 0.0);
+        // the cascade matrices come after the uniform in the same buffer
+        assert!(std::mem::size_of::<ShadowCascadesUniform>() as u64 <= CASCADE_OFFSET);
+    }
+}
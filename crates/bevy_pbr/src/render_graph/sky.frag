@@ -0,0 +1,45 @@
+#version 450
+
+layout(location = 0) in vec2 v_Position;
+
+layout(location = 0) out vec4 o_Target;
+
+// see SkyUniform
+layout(set = 0, binding = 0) uniform Sky {
+    mat4 InverseViewProj;
+    vec4 SunDirection;
+    vec4 SunDisc;
+    vec4 Zenith;
+    vec4 Perez[5];
+};
+
+// the Perez distribution of the luminance and chromaticity, see SkyModel::distribution
+vec3 distribution(float cos_theta, float gamma) {
+    float cos_gamma = cos(gamma);
+    return (1.0 + Perez[0].xyz * exp(Perez[1].xyz / cos_theta))
+        * (1.0 + Perez[2].xyz * exp(Perez[3].xyz * gamma) + Perez[4].xyz * cos_gamma * cos_gamma);
+}
+
+vec3 xyy_to_rgb(vec3 xyy) {
+    float x = xyy.y / xyy.z * xyy.x;
+    float z = (1.0 - xyy.y - xyy.z) / xyy.z * xyy.x;
+    return max(vec3(
+        3.2406 * x - 1.5372 * xyy.x - 0.4986 * z,
+        -0.9689 * x + 1.8758 * xyy.x + 0.0415 * z,
+        0.0557 * x - 0.2040 * xyy.x + 1.0570 * z), vec3(0.0));
+}
+
+void main() {
+    // the direction of the far plane behind this fragment
+    vec4 far = InverseViewProj * vec4(v_Position, 1.0, 1.0);
+    vec3 direction = normalize(far.xyz / far.w);
+
+    float cos_sun = dot(direction, SunDirection.xyz);
+    float gamma = acos(clamp(cos_sun, -1.0, 1.0));
+    vec3 xyy = Zenith.xyz * distribution(max(direction.y, 0.01), gamma);
+    vec3 color = xyy_to_rgb(xyy) * Zenith.w;
+    if (cos_sun > SunDirection.w && direction.y > 0.0) {
+        color += SunDisc.rgb;
+    }
+    o_Target = vec4(color, 1.0);
+}
@@ -0,0 +1,10 @@
+#version 450
+
+layout(location = 0) in vec2 Vertex_Position;
+
+layout(location = 0) out vec2 v_Position;
+
+void main() {
+    v_Position = Vertex_Position;
+    gl_Position = vec4(Vertex_Position, 0.0, 1.0);
+}
@@ -0,0 +1,433 @@
+use super::node;
+use crate::sky::{Sky, SUN_ANGULAR_RADIUS};
+use bevy_asset::{Assets, Handle};
+use bevy_core::{AsBytes, Byteable};
+use bevy_ecs::{Resources, World};
+use bevy_math::{Mat4, Vec3};
+use bevy_render::{
+    camera::{ActiveCameras, Camera, Viewport},
+    pass::{ClearColor, LoadOp, Operations, PassDescriptor, TextureAttachment},
+    pipeline::{
+        BlendDescriptor, ColorStateDescriptor, ColorWrite, CullMode, FrontFace, InputStepMode,
+        PipelineCompiler, PipelineDescriptor, PipelineSpecialization, RasterizationStateDescriptor,
+        VertexAttributeDescriptor, VertexBufferDescriptor, VertexFormat,
+    },
+    render_graph::{
+        base::{self, MainPass, Msaa},
+        Edge, Node, PassNode, RenderGraph, ResourceSlotInfo, ResourceSlots,
+    },
+    renderer::{
+        BindGroup, BufferId, BufferInfo, BufferUsage, RenderContext, RenderResourceBindings,
+        RenderResourceType,
+    },
+    shader::{Shader, ShaderStage, ShaderStages},
+    texture::TextureFormat,
+};
+use bevy_transform::prelude::GlobalTransform;
+use bevy_type_registry::TypeUuid;
+use bevy_window::Windows;
+use std::borrow::Cow;
+
+pub const SKY_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 4813960253227581146);
+
+/// The Sky uniform of sky.frag
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct SkyUniform {
+    /// Turns clip space positions into view directions, without the camera's translation
+    inverse_view_proj: [[f32; 4]; 4],
+    /// The cosine of the sun's angular radius in `w`
+    sun_direction: [f32; 4],
+    /// The luminance of the sun's disc
+    sun_disc: [f32; 4],
+    /// [SkyModel::zenith](crate::sky::SkyModel), and the scale of the luminance in `w`
+    zenith: [f32; 4],
+    perez: [[f32; 4]; 5],
+}
+
+unsafe impl Byteable for SkyUniform {}
+
+/// Draws the [Sky] over the whole color attachment, as seen by the 3D camera. It runs before the main pass, which
+/// draws on top of the sky instead of clearing it. Without a 3D camera, the color attachment is cleared to the
+/// [ClearColor].
+#[derive(Debug)]
+pub struct SkyNode {
+    descriptor: PassDescriptor,
+    inputs: Vec<ResourceSlotInfo>,
+    pipeline_specialization: PipelineSpecialization,
+    vertex_buffer: Option<BufferId>,
+    uniform_buffer: Option<BufferId>,
+    staging_buffer: Option<BufferId>,
+}
+
+impl SkyNode {
+    pub const IN_COLOR_ATTACHMENT: &'static str = "color_attachment";
+    pub const IN_COLOR_RESOLVE_TARGET: &'static str = "color_resolve_target";
+
+    pub fn new(msaa: &Msaa) -> Self {
+        let mut inputs = vec![ResourceSlotInfo::new(
+            SkyNode::IN_COLOR_ATTACHMENT,
+            RenderResourceType::Texture,
+        )];
+        if msaa.samples > 1 {
+            inputs.push(ResourceSlotInfo::new(
+                SkyNode::IN_COLOR_RESOLVE_TARGET,
+                RenderResourceType::Texture,
+            ));
+        }
+
+        SkyNode {
+            descriptor: PassDescriptor {
+                color_attachments: vec![msaa.color_attachment_descriptor(
+                    TextureAttachment::Input(SkyNode::IN_COLOR_ATTACHMENT.to_string()),
+                    TextureAttachment::Input(SkyNode::IN_COLOR_RESOLVE_TARGET.to_string()),
+                    Operations {
+                        load: LoadOp::Clear(Default::default()),
+                        store: true,
+                    },
+                )],
+                depth_stencil_attachment: None,
+                sample_count: msaa.samples,
+            },
+            inputs,
+            pipeline_specialization: PipelineSpecialization {
+                sample_count: msaa.samples,
+                vertex_buffer_descriptor: VertexBufferDescriptor {
+                    name: Cow::Borrowed("Vertex_Position"),
+                    stride: VertexFormat::Float2.get_size(),
+                    step_mode: InputStepMode::Vertex,
+                    attributes: vec![VertexAttributeDescriptor {
+                        name: Cow::Borrowed("Vertex_Position"),
+                        offset: 0,
+                        format: VertexFormat::Float2,
+                        shader_location: 0,
+                    }],
+                },
+                ..Default::default()
+            },
+            vertex_buffer: None,
+            uniform_buffer: None,
+            staging_buffer: None,
+        }
+    }
+}
+
+impl Node for SkyNode {
+    fn input(&self) -> &[ResourceSlotInfo] {
+        &self.inputs
+    }
+
+    fn update(
+        &mut self,
+        world: &World,
+        resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        const COLOR_ATTACHMENT: usize = 0;
+        const COLOR_RESOLVE_TARGET: usize = 1;
+        let color_attachment = &mut self.descriptor.color_attachments[0];
+        color_attachment.attachment =
+            TextureAttachment::Id(input.get(COLOR_ATTACHMENT).unwrap().get_texture().unwrap());
+        if color_attachment.resolve_target.is_some() {
+            color_attachment.resolve_target = Some(TextureAttachment::Id(
+                input
+                    .get(COLOR_RESOLVE_TARGET)
+                    .unwrap()
+                    .get_texture()
+                    .unwrap(),
+            ));
+        }
+        if let Some(clear_color) = resources.get::<ClearColor>() {
+            color_attachment.ops.load = LoadOp::Clear(clear_color.0);
+        }
+
+        let size = std::mem::size_of::<SkyUniform>();
+        let render_resource_context = render_context.resources();
+        // looked up every frame, because the pipeline is compiled again when its shaders are modified
+        let pipeline = {
+            let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+            let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+            let mut pipeline_compiler = resources.get_mut::<PipelineCompiler>().unwrap();
+            pipeline_compiler
+                .compile_pipeline(
+                    render_resource_context,
+                    &mut pipelines,
+                    &mut shaders,
+                    &SKY_PIPELINE_HANDLE,
+                    &self.pipeline_specialization,
+                )
+                .ok()
+        };
+        if self.vertex_buffer.is_none() {
+            // a single triangle that covers the whole screen
+            let positions: [[f32; 2]; 3] = [[-1.0, -1.0], [3.0, -1.0], [-1.0, 3.0]];
+            self.vertex_buffer = Some(render_resource_context.create_buffer_with_data(
+                BufferInfo {
+                    buffer_usage: BufferUsage::VERTEX,
+                    ..Default::default()
+                },
+                positions.as_bytes(),
+            ));
+            self.uniform_buffer = Some(render_resource_context.create_buffer(BufferInfo {
+                size,
+                buffer_usage: BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+                ..Default::default()
+            }));
+            self.staging_buffer = Some(render_resource_context.create_buffer(BufferInfo {
+                size,
+                buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
+                ..Default::default()
+            }));
+        }
+
+        let active_cameras = resources.get::<ActiveCameras>().unwrap();
+        let camera = active_cameras
+            .get(base::camera::CAMERA3D)
+            .and_then(|entity| {
+                Some((
+                    world.get::<Camera>(entity).ok()?,
+                    world.get::<GlobalTransform>(entity).ok()?,
+                ))
+            });
+        let sky = resources.get::<Sky>().unwrap();
+        let render_resource_bindings = resources.get::<RenderResourceBindings>().unwrap();
+        let (camera, camera_transform, pipeline) =
+            if let (Some((camera, camera_transform)), Some(pipeline)) = (camera, pipeline.as_ref())
+            {
+                (camera, camera_transform, pipeline)
+            } else {
+                // nothing to draw the sky for, or its pipeline failed to compile, but the main pass still expects the
+                // attachment to be cleared
+                render_context.begin_pass(
+                    &self.descriptor,
+                    &render_resource_bindings,
+                    &mut |_render_pass| {},
+                );
+                return;
+            };
+
+        // the sky is infinitely far away, so only the camera's rotation matters
+        let view = Mat4::from_quat(camera_transform.rotation).inverse();
+        let mut inverse_view_proj = (camera.projection_matrix * view).inverse();
+        // the camera's projection covers the whole window, while the sky is only drawn in the viewport
+        let viewport = camera.viewport.and_then(|viewport| {
+            let windows = resources.get::<Windows>()?;
+            let window = windows.get(camera.window)?;
+            inverse_view_proj = inverse_view_proj
+                * viewport_to_window_clip(
+                    viewport,
+                    window.physical_width(),
+                    window.physical_height(),
+                );
+            Some(viewport)
+        });
+        let exposure = camera.exposure.map_or(1.0, |exposure| exposure.scale());
+        let model = sky.model();
+        let sun_color = sky.sun_color();
+        // a white surface facing the sun reflects 1/pi of its illuminance, the disc spreads it over its solid angle
+        let sun_solid_angle = 2.0 * std::f32::consts::PI * (1.0 - SUN_ANGULAR_RADIUS.cos());
+        let sun_disc = sun_color * (std::f32::consts::PI / sun_solid_angle * exposure);
+        let [a, b, c, d, e] = model.perez;
+        let uniform = SkyUniform {
+            inverse_view_proj: inverse_view_proj.to_cols_array_2d(),
+            sun_direction: model.sun_direction.extend(SUN_ANGULAR_RADIUS.cos()).into(),
+            sun_disc: sun_disc.extend(0.0).into(),
+            zenith: model.zenith.extend(model.fade * exposure).into(),
+            perez: [
+                a.extend(0.0).into(),
+                b.extend(0.0).into(),
+                c.extend(0.0).into(),
+                d.extend(0.0).into(),
+                e.extend(0.0).into(),
+            ],
+        };
+
+        let staging_buffer = self.staging_buffer.unwrap();
+        let uniform_buffer = self.uniform_buffer.unwrap();
+        let render_resource_context = render_context.resources();
+        render_resource_context.map_buffer(staging_buffer);
+        render_resource_context.write_mapped_buffer(
+            staging_buffer,
+            0..size as u64,
+            &mut |data, _renderer| {
+                data.copy_from_slice(uniform.as_bytes());
+            },
+        );
+        render_resource_context.unmap_buffer(staging_buffer);
+        render_context.copy_buffer_to_buffer(staging_buffer, 0, uniform_buffer, 0, size as u64);
+
+        let pipelines = resources.get::<Assets<PipelineDescriptor>>().unwrap();
+        let bind_group_descriptor = pipelines
+            .get(pipeline)
+            .and_then(|descriptor| descriptor.get_layout())
+            .and_then(|layout| layout.get_bind_group(0))
+            .unwrap();
+        let bind_group = BindGroup::build()
+            .add_buffer(0, uniform_buffer, 0..size as u64)
+            .finish();
+        render_context
+            .resources()
+            .create_bind_group(bind_group_descriptor.id, &bind_group);
+
+        let vertex_buffer = self.vertex_buffer.unwrap();
+        render_context.begin_pass(
+            &self.descriptor,
+            &render_resource_bindings,
+            &mut |render_pass| {
+                render_pass.set_pipeline(pipeline);
+                if let Some(viewport) = viewport {
+                    render_pass.set_viewport(
+                        viewport.x as f32,
+                        viewport.y as f32,
+                        viewport.width as f32,
+                        viewport.height as f32,
+                        0.0,
+                        1.0,
+                    );
+                    render_pass.set_scissor_rect(
+                        viewport.x,
+                        viewport.y,
+                        viewport.width,
+                        viewport.height,
+                    );
+                }
+                render_pass.set_vertex_buffer(0, vertex_buffer, 0);
+                render_pass.set_bind_group(0, bind_group_descriptor.id, bind_group.id, None);
+                render_pass.draw(0..3, 0..1);
+            },
+        );
+    }
+}
+
+/// Maps clip space positions of the viewport to clip space positions of its window
+fn viewport_to_window_clip(viewport: Viewport, window_width: u32, window_height: u32) -> Mat4 {
+    let (window_width, window_height) = (window_width as f32, window_height as f32);
+    let scale = Vec3::new(
+        viewport.width as f32 / window_width,
+        viewport.height as f32 / window_height,
+        1.0,
+    );
+    // viewports are measured down from the top of the window, clip space up from the bottom
+    let center = Vec3::new(
+        (2.0 * viewport.x as f32 + viewport.width as f32) / window_width - 1.0,
+        1.0 - (2.0 * viewport.y as f32 + viewport.height as f32) / window_height,
+        0.0,
+    );
+    Mat4::from_translation(center) * Mat4::from_scale(scale)
+}
+
+pub(crate) fn build_sky_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    PipelineDescriptor {
+        rasterization_state: Some(RasterizationStateDescriptor {
+            front_face: FrontFace::Ccw,
+            cull_mode: CullMode::None,
+            depth_bias: 0,
+            depth_bias_slope_scale: 0.0,
+            depth_bias_clamp: 0.0,
+            clamp_depth: false,
+        }),
+        color_states: vec![ColorStateDescriptor {
+            format: TextureFormat::default(),
+            color_blend: BlendDescriptor::REPLACE,
+            alpha_blend: BlendDescriptor::REPLACE,
+            write_mask: ColorWrite::ALL,
+        }],
+        ..PipelineDescriptor::new(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                include_str!("sky.vert"),
+            )),
+            fragment: Some(shaders.add(Shader::from_glsl(
+                ShaderStage::Fragment,
+                include_str!("sky.frag"),
+            ))),
+        })
+    }
+}
+
+/// Adds the [SkyNode] before the main pass, drawing to the same color attachment
+pub(crate) fn add_sky_graph(graph: &mut RenderGraph, resources: &Resources, msaa: &Msaa) {
+    let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+    let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+    pipelines.set_untracked(SKY_PIPELINE_HANDLE, build_sky_pipeline(&mut shaders));
+
+    let main_pass = if let Ok(main_pass) = graph.get_node_state(base::node::MAIN_PASS) {
+        main_pass
+    } else {
+        return;
+    };
+    // the sky takes the color attachments from the nodes that give them to the main pass
+    let color_edges = main_pass
+        .edges
+        .input_edges
+        .iter()
+        .filter_map(|edge| match *edge {
+            Edge::SlotEdge {
+                input_index,
+                output_node,
+                output_index,
+                ..
+            } => {
+                let name = main_pass
+                    .input_slots
+                    .get_slot(input_index)
+                    .ok()?
+                    .info
+                    .name
+                    .to_string();
+                if name == SkyNode::IN_COLOR_ATTACHMENT || name == SkyNode::IN_COLOR_RESOLVE_TARGET
+                {
+                    Some((output_node, output_index, name))
+                } else {
+                    None
+                }
+            }
+            Edge::NodeEdge { .. } => None,
+        })
+        .collect::<Vec<_>>();
+
+    graph.add_node(node::SKY, SkyNode::new(msaa));
+    for (output_node, output_index, name) in color_edges {
+        graph
+            .add_slot_edge(output_node, output_index, node::SKY, name)
+            .unwrap();
+    }
+    graph
+        .add_node_edge(node::SKY, base::node::MAIN_PASS)
+        .unwrap();
+    graph
+        .get_node_mut::<PassNode<&MainPass>>(base::node::MAIN_PASS)
+        .unwrap()
+        .load_color_attachment(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::viewport_to_window_clip;
+    use bevy_math::{Vec2, Vec4};
+    use bevy_render::camera::Viewport;
+
+    #[test]
+    fn viewport_corners_map_to_their_place_in_the_window() {
+        let viewport = Viewport {
+            x: 100,
+            y: 50,
+            width: 400,
+            height: 300,
+        };
+        let to_window = viewport_to_window_clip(viewport, 800, 600);
+        let window_position = |x: f32, y: f32| {
+            let position = to_window * Vec4::new(x, y, 1.0, 1.0);
+            Vec2::new(position.x(), position.y())
+        };
+
+        // the corners are 100 and 500 pixels from the left, and 50 and 350 pixels from the top of the window
+        let top_left = window_position(-1.0, 1.0);
+        let bottom_right = window_position(1.0, -1.0);
+        assert!((top_left - Vec2::new(-0.75, 5.0 / 6.0)).length() < 1e-6);
+        assert!((bottom_right - Vec2::new(0.25, -1.0 / 6.0)).length() < 1e-6);
+    }
+}
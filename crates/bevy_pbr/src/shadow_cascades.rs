@@ -0,0 +1,220 @@
+use bevy_math::{FaceToward, Mat4, Vec3, Vec4};
+use bevy_render::camera::PerspectiveProjection;
+use bevy_transform::components::GlobalTransform;
+
+/// The most cascades a [CascadeShadowConfig] can have. Must match the size of the cascade arrays in forward.frag.
+pub const MAX_CASCADES: usize = 4;
+
+/// How the view distance covered by shadows is divided between cascades
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CascadeSplit {
+    /// Every cascade covers the same distance
+    Uniform,
+    /// Each cascade covers the same ratio of distances, so near cascades are much smaller than far ones
+    Logarithmic,
+    /// A blend of [CascadeSplit::Uniform] (`lambda` = 0) and [CascadeSplit::Logarithmic] (`lambda` = 1)
+    Practical { lambda: f32 },
+}
+
+/// The shadows the sun of the [EnvironmentLight](crate::EnvironmentLight) casts, as a resource. The part of the 3D
+/// camera's view that receives shadows is split into `cascade_count` slices by distance, and each slice gets its own
+/// shadow map of `resolution` texels, so near geometry gets sharp shadows and far away geometry still gets shadows.
+#[derive(Debug, Clone)]
+pub struct CascadeShadowConfig {
+    /// Up to [MAX_CASCADES]. Zero turns shadows off.
+    pub cascade_count: usize,
+    /// The distance from the camera where shadows end
+    pub max_distance: f32,
+    pub split: CascadeSplit,
+    /// The width and height of each cascade's shadow map, in texels. The shadow maps are side by side in one texture,
+    /// so `cascade_count * resolution` must fit in the GPU's texture size limit.
+    pub resolution: u32,
+    /// How far behind each cascade shadow casters are still included
+    pub caster_distance: f32,
+}
+
+impl Default for CascadeShadowConfig {
+    fn default() -> Self {
+        CascadeShadowConfig {
+            cascade_count: 4,
+            max_distance: 100.0,
+            split: CascadeSplit::Practical { lambda: 0.75 },
+            resolution: 2048,
+            caster_distance: 100.0,
+        }
+    }
+}
+
+/// One slice of the camera view and the light projection that covers it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cascade {
+    /// The distance from the camera where the cascade starts
+    pub near: f32,
+    /// The distance from the camera where the cascade ends
+    pub far: f32,
+    /// Maps world positions into the cascade's shadow map, with depth from 0 to 1
+    pub view_projection: Mat4,
+    /// The size of a shadow map texel in world units
+    pub texel_size: f32,
+}
+
+impl CascadeShadowConfig {
+    /// The distances from the camera where each cascade ends, starting from a camera near plane at `near`
+    pub fn split_distances(&self, near: f32) -> Vec<f32> {
+        let far = self.max_distance.max(near);
+        let count = self.cascade_count.min(MAX_CASCADES);
+        let lambda = match self.split {
+            CascadeSplit::Uniform => 0.0,
+            CascadeSplit::Logarithmic => 1.0,
+            CascadeSplit::Practical { lambda } => lambda,
+        };
+        (1..=count)
+            .map(|i| {
+                let ratio = i as f32 / count as f32;
+                let uniform = near + (far - near) * ratio;
+                let logarithmic = near * (far / near).powf(ratio);
+                uniform + (logarithmic - uniform) * lambda
+            })
+            .collect()
+    }
+
+    /// Fits a cascade to each slice of the camera's view, for a directional light shining in `light_direction`
+    pub fn compute_cascades(
+        &self,
+        projection: &PerspectiveProjection,
+        camera_transform: &GlobalTransform,
+        light_direction: Vec3,
+    ) -> Vec<Cascade> {
+        let light_direction = light_direction.normalize();
+        let up = if light_direction.y().abs() > 0.99 {
+            Vec3::unit_z()
+        } else {
+            Vec3::unit_y()
+        };
+        // rotates world space into the light's space, where the light shines down -z
+        let light_rotation = Mat4::face_toward(Vec3::zero(), light_direction, up).inverse();
+
+        let camera_matrix = camera_transform.compute_matrix();
+        let mut near = projection.near;
+        self.split_distances(projection.near)
+            .into_iter()
+            .map(|far| {
+                let corners = frustum_slice_corners(projection, &camera_matrix, near, far);
+                let cascade = self.fit_cascade(&corners, &light_rotation, near, far);
+                near = far;
+                cascade
+            })
+            .collect()
+    }
+
+    fn fit_cascade(&self, corners: &[Vec3], light_rotation: &Mat4, near: f32, far: f32) -> Cascade {
+        // a bounding sphere keeps the cascade the same size when the camera rotates, which keeps shadows stable
+        let center = corners
+            .iter()
+            .fold(Vec3::zero(), |sum, corner| sum + *corner)
+            / corners.len() as f32;
+        let radius = corners
+            .iter()
+            .map(|corner| (*corner - center).length())
+            .fold(0.0, f32::max);
+        // leave a one texel margin, so snapping never moves the slice out of the cascade
+        let resolution = self.resolution.max(4) as f32;
+        let half_size = radius * resolution / (resolution - 2.0);
+        let texel_size = 2.0 * half_size / resolution;
+
+        // move the cascade in whole texels, so shadow edges don't shimmer when the camera moves
+        let light_center = light_rotation.transform_point3(center);
+        let snapped_center = Vec3::new(
+            (light_center.x() / texel_size).floor() * texel_size,
+            (light_center.y() / texel_size).floor() * texel_size,
+            light_center.z(),
+        );
+        let projection = Mat4::orthographic_rh(
+            snapped_center.x() - half_size,
+            snapped_center.x() + half_size,
+            snapped_center.y() - half_size,
+            snapped_center.y() + half_size,
+            -snapped_center.z() - radius - self.caster_distance,
+            -snapped_center.z() + radius,
+        );
+        Cascade {
+            near,
+            far,
+            view_projection: projection * *light_rotation,
+            texel_size,
+        }
+    }
+}
+
+/// The world space corners of the part of a perspective camera's view between the `near` and `far` distances
+fn frustum_slice_corners(
+    projection: &PerspectiveProjection,
+    camera_matrix: &Mat4,
+    near: f32,
+    far: f32,
+) -> Vec<Vec3> {
+    let tan_half_fov = (projection.fov / 2.0).tan();
+    let mut corners = Vec::with_capacity(8);
+    for distance in [near, far].iter() {
+        let half_height = distance * tan_half_fov;
+        let half_width = half_height * projection.aspect_ratio;
+        for (x, y) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].iter() {
+            let corner = Vec4::new(x * half_width, y * half_height, -distance, 1.0);
+            corners.push((*camera_matrix * corner).truncate());
+        }
+    }
+    corners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frustum_slice_corners, CascadeShadowConfig, CascadeSplit, MAX_CASCADES};
+    use bevy_math::{Vec3, Vec4};
+    use bevy_render::camera::PerspectiveProjection;
+    use bevy_transform::components::GlobalTransform;
+
+    #[test]
+    fn cascade_splits_and_fit() {
+        let mut config = CascadeShadowConfig {
+            cascade_count: 2,
+            max_distance: 100.0,
+            split: CascadeSplit::Uniform,
+            ..Default::default()
+        };
+        assert_eq!(config.split_distances(1.0), vec![50.5, 100.0]);
+        config.split = CascadeSplit::Logarithmic;
+        let splits = config.split_distances(1.0);
+        assert!((splits[0] - 10.0).abs() < 1e-4);
+        assert!((splits[1] - 100.0).abs() < 1e-3);
+        config.cascade_count = 0;
+        assert!(config.split_distances(1.0).is_empty());
+        config.cascade_count = MAX_CASCADES + 2;
+        assert_eq!(config.split_distances(1.0).len(), MAX_CASCADES);
+        config.cascade_count = 2;
+
+        let projection = PerspectiveProjection {
+            aspect_ratio: 16.0 / 9.0,
+            ..Default::default()
+        };
+        let camera_transform = GlobalTransform::from_translation(Vec3::new(5.0, 10.0, 20.0))
+            .looking_at(Vec3::zero(), Vec3::unit_y());
+        let cascades =
+            config.compute_cascades(&projection, &camera_transform, Vec3::new(-1.0, -2.0, 0.5));
+        assert_eq!(cascades.len(), 2);
+        assert_eq!(cascades[0].near, projection.near);
+        assert_eq!(cascades[0].far, cascades[1].near);
+
+        // every cascade sees its whole slice of the camera view
+        let camera_matrix = camera_transform.compute_matrix();
+        for cascade in cascades.iter() {
+            for corner in
+                frustum_slice_corners(&projection, &camera_matrix, cascade.near, cascade.far)
+            {
+                let ndc = cascade.view_projection * corner.extend(1.0);
+                let ndc: Vec4 = ndc / ndc.w();
+                assert!(ndc.x().abs() <= 1.0 && ndc.y().abs() <= 1.0, "{:?}", ndc);
+                assert!(ndc.z() >= 0.0 && ndc.z() <= 1.0, "{:?}", ndc);
+            }
+        }
+    }
+}
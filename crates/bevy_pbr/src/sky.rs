@@ -0,0 +1,270 @@
+use crate::{light::EnvironmentLight, render_graph::add_sky_graph};
+use bevy_app::prelude::*;
+use bevy_ecs::{IntoQuerySystem, Res, ResMut};
+use bevy_math::Vec3;
+use bevy_render::render_graph::{base::Msaa, RenderGraph};
+
+/// The angular radius of the sun's disc, in radians
+pub(crate) const SUN_ANGULAR_RADIUS: f32 = 0.0047;
+
+/// A sky computed from the direction of the sun with the analytic model of Preetham et al., "A Practical Analytic
+/// Model for Daylight". [SkyPlugin] draws it behind the main pass, and lights shaded materials with it.
+///
+/// The sky is in physical units: luminance in nits and illuminance in lux. View it through a camera with an
+/// [Exposure](bevy_render::camera::Exposure) like `Exposure::SUNLIGHT`, otherwise the daytime sky is white.
+#[derive(Debug, Clone)]
+pub struct Sky {
+    /// Points from the scene towards the sun. The sky fades to black once the sun is a few degrees below the horizon.
+    pub sun_direction: Vec3,
+    /// The haziness of the air, from 2 for a very clear day to about 10 for a hazy one
+    pub turbidity: f32,
+    /// The illuminance of the sun above the air, which absorbs part of it
+    pub sun_illuminance: f32,
+    /// Whether the sky sets the [EnvironmentLight], so shaded materials get the sky's ambient light and the sun's light
+    pub lights_scene: bool,
+}
+
+impl Default for Sky {
+    fn default() -> Self {
+        Sky {
+            sun_direction: Vec3::new(0.3, 0.8, 0.5).normalize(),
+            turbidity: 3.0,
+            sun_illuminance: 128_000.0,
+            lights_scene: true,
+        }
+    }
+}
+
+impl Sky {
+    /// The linear RGB luminance of the sky in a direction, without the sun's disc. Directions below the horizon get
+    /// the color of the horizon.
+    pub fn luminance(&self, direction: Vec3) -> Vec3 {
+        self.model().luminance(direction.normalize())
+    }
+
+    /// The ambient light the sky casts on a white surface that faces up
+    pub fn ambient(&self) -> Vec3 {
+        // cosine weighted average of the luminance over the upper hemisphere
+        let model = self.model();
+        let mut sum = Vec3::zero();
+        let mut weight_sum = 0.0;
+        for zenith_angle in [15.0f32, 45.0, 75.0].iter() {
+            let (sin, cos) = zenith_angle.to_radians().sin_cos();
+            let weight = sin * cos;
+            for azimuth in 0..8 {
+                let (sin_azimuth, cos_azimuth) =
+                    (azimuth as f32 * std::f32::consts::FRAC_PI_4).sin_cos();
+                let direction = Vec3::new(sin * cos_azimuth, cos, sin * sin_azimuth);
+                sum += model.luminance(direction) * weight;
+                weight_sum += weight;
+            }
+        }
+        sum / weight_sum
+    }
+
+    /// The light of the sun on a white surface that faces it, tinted by the air the sunlight passes through
+    pub fn sun_color(&self) -> Vec3 {
+        let model = self.model();
+        // a white diffuse surface reflects 1/pi of the illuminance towards the viewer
+        self.sun_transmittance() * (self.sun_illuminance * model.fade / std::f32::consts::PI)
+    }
+
+    /// The fraction of the sun's red, green and blue light that passes through the air, which scatters it off its
+    /// molecules (Rayleigh scattering) and its haze (Ångström's formula, with Preetham's fit for the turbidity)
+    fn sun_transmittance(&self) -> Vec3 {
+        let zenith_angle = self.sun_direction.normalize().y().max(0.0).min(1.0).acos();
+        // the length of the sun's path through the air relative to its length at the zenith, from Kasten and Young
+        let air_mass = 1.0
+            / (zenith_angle.cos() + 0.50572 * (96.07995 - zenith_angle.to_degrees()).powf(-1.6364));
+        let haze = 0.04608 * self.turbidity - 0.04586;
+        let transmittance = |wavelength: f32| {
+            let optical_depth = 0.0088 * wavelength.powf(-4.05) + haze * wavelength.powf(-1.3);
+            (-air_mass * optical_depth).exp()
+        };
+        // wavelengths in micrometers
+        Vec3::new(
+            transmittance(0.68),
+            transmittance(0.55),
+            transmittance(0.44),
+        )
+    }
+
+    pub(crate) fn model(&self) -> SkyModel {
+        let sun_direction = self.sun_direction.normalize();
+        let turbidity = self.turbidity;
+        // the model only covers suns above the horizon
+        let sun_zenith_angle = sun_direction.y().max(0.0).min(1.0).acos();
+        let fade = smoothstep(-0.1, 0.0, sun_direction.y());
+
+        let perez = [
+            Vec3::new(
+                0.1787 * turbidity - 1.4630,
+                -0.0193 * turbidity - 0.2592,
+                -0.0167 * turbidity - 0.2608,
+            ),
+            Vec3::new(
+                -0.3554 * turbidity + 0.4275,
+                -0.0665 * turbidity + 0.0008,
+                -0.0950 * turbidity + 0.0092,
+            ),
+            Vec3::new(
+                -0.0227 * turbidity + 5.3251,
+                -0.0004 * turbidity + 0.2125,
+                -0.0079 * turbidity + 0.2102,
+            ),
+            Vec3::new(
+                0.1206 * turbidity - 2.5771,
+                -0.0641 * turbidity - 0.8989,
+                -0.0441 * turbidity - 1.6537,
+            ),
+            Vec3::new(
+                -0.0670 * turbidity + 0.3703,
+                -0.0033 * turbidity + 0.0452,
+                -0.0109 * turbidity + 0.0529,
+            ),
+        ];
+
+        let theta = sun_zenith_angle;
+        let chi = (4.0 / 9.0 - turbidity / 120.0) * (std::f32::consts::PI - 2.0 * theta);
+        // in kcd/m²
+        let zenith_luminance =
+            (4.0453 * turbidity - 4.9710) * chi.tan() - 0.2155 * turbidity + 2.4192;
+        let theta2 = theta * theta;
+        let theta3 = theta2 * theta;
+        let turbidity2 = turbidity * turbidity;
+        let zenith_x = turbidity2 * (0.00166 * theta3 - 0.00375 * theta2 + 0.00209 * theta)
+            + turbidity * (-0.02903 * theta3 + 0.06377 * theta2 - 0.03202 * theta + 0.00394)
+            + (0.11693 * theta3 - 0.21196 * theta2 + 0.06052 * theta + 0.25886);
+        let zenith_y = turbidity2 * (0.00275 * theta3 - 0.00610 * theta2 + 0.00317 * theta)
+            + turbidity * (-0.04214 * theta3 + 0.08970 * theta2 - 0.04153 * theta + 0.00516)
+            + (0.15346 * theta3 - 0.26756 * theta2 + 0.06670 * theta + 0.26688);
+
+        let mut model = SkyModel {
+            perez,
+            zenith: Vec3::new(zenith_luminance * 1000.0, zenith_x, zenith_y),
+            sun_direction,
+            fade,
+        };
+        // the zenith values are divided by the distribution at the zenith, so the distribution scales them directly
+        model.zenith /= model.distribution(1.0, theta);
+        model
+    }
+}
+
+/// The coefficients of the Preetham sky model for a [Sky]. The shader of the [SkyNode](crate::render_graph::SkyNode)
+/// evaluates the same model.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SkyModel {
+    /// The coefficients A to E of the Perez distribution, for the luminance and the x and y chromaticity
+    pub perez: [Vec3; 5],
+    /// The luminance (in nits) and chromaticity at the zenith, divided by the distribution at the zenith
+    pub zenith: Vec3,
+    pub sun_direction: Vec3,
+    /// Scales the luminance, so the sky fades to black after sunset
+    pub fade: f32,
+}
+
+impl SkyModel {
+    /// The Perez distribution for a view direction with the given cosine of its zenith angle, at an angle of `gamma`
+    /// from the sun
+    fn distribution(&self, cos_theta: f32, gamma: f32) -> Vec3 {
+        let [a, b, c, d, e] = self.perez;
+        let cos_gamma = gamma.cos();
+        let exp = |v: Vec3| Vec3::new(v.x().exp(), v.y().exp(), v.z().exp());
+        (Vec3::one() + a * exp(b / cos_theta))
+            * (Vec3::one() + c * exp(d * gamma) + e * cos_gamma * cos_gamma)
+    }
+
+    /// The luminance and chromaticity (Yxy) in a normalized direction
+    fn luminance_xyy(&self, direction: Vec3) -> Vec3 {
+        let cos_theta = direction.y().max(0.01);
+        let gamma = direction.dot(self.sun_direction).max(-1.0).min(1.0).acos();
+        self.zenith * self.distribution(cos_theta, gamma)
+    }
+
+    pub fn luminance(&self, direction: Vec3) -> Vec3 {
+        xyy_to_rgb(self.luminance_xyy(direction)) * self.fade
+    }
+}
+
+/// Converts a luminance and chromaticity to linear sRGB
+fn xyy_to_rgb(xyy: Vec3) -> Vec3 {
+    let (luminance, x, y) = (xyy.x(), xyy.y(), xyy.z());
+    let cie_x = x / y * luminance;
+    let cie_z = (1.0 - x - y) / y * luminance;
+    Vec3::new(
+        3.2406 * cie_x - 1.5372 * luminance - 0.4986 * cie_z,
+        -0.9689 * cie_x + 1.8758 * luminance + 0.0415 * cie_z,
+        0.0557 * cie_x - 0.2040 * luminance + 1.0570 * cie_z,
+    )
+    .max(Vec3::zero())
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).max(0.0).min(1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Sets the [EnvironmentLight] from the [Sky] when [Sky::lights_scene] is enabled
+pub fn sky_environment_light_system(
+    sky: Res<Sky>,
+    mut environment_light: ResMut<EnvironmentLight>,
+) {
+    if !sky.lights_scene {
+        return;
+    }
+    environment_light.ambient = sky.ambient();
+    environment_light.sun_direction = sky.sun_direction.normalize();
+    environment_light.sun_color = sky.sun_color();
+}
+
+/// Draws the [Sky] behind everything in the main pass, and lights the scene with it. The 3D camera's view decides
+/// which part of the sky is visible. Add it after the [PbrPlugin](crate::PbrPlugin).
+#[derive(Default)]
+pub struct SkyPlugin;
+
+impl Plugin for SkyPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        if app.resources().get::<Sky>().is_none() {
+            app.init_resource::<Sky>();
+        }
+        if app.resources().get::<EnvironmentLight>().is_none() {
+            app.init_resource::<EnvironmentLight>();
+        }
+        app.add_system_to_stage(stage::POST_UPDATE, sky_environment_light_system.system());
+
+        let resources = app.resources();
+        let msaa = resources.get::<Msaa>().unwrap();
+        let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
+        add_sky_graph(&mut render_graph, resources, &msaa);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sky;
+    use bevy_math::Vec3;
+
+    #[test]
+    fn sky_is_blue_and_brightest_near_the_sun() {
+        let sky = Sky::default();
+        let zenith = sky.luminance(Vec3::unit_y());
+        assert!(zenith.z() > zenith.x());
+
+        let brightness = |color: Vec3| color.x() + color.y() + color.z();
+        let near_sun = sky.luminance(sky.sun_direction + Vec3::new(0.0, 0.05, 0.0));
+        let away_from_sun = sky.luminance(Vec3::new(-0.3, 0.8, -0.5));
+        assert!(brightness(near_sun) > brightness(away_from_sun));
+        assert!(brightness(sky.sun_color()) > brightness(sky.ambient()));
+    }
+
+    #[test]
+    fn sky_is_dark_at_night() {
+        let sky = Sky {
+            sun_direction: Vec3::new(0.0, -0.5, 1.0),
+            ..Default::default()
+        };
+        assert_eq!(sky.ambient(), Vec3::zero());
+        assert_eq!(sky.sun_color(), Vec3::zero());
+    }
+}
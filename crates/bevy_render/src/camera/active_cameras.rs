@@ -16,6 +16,10 @@ impl ActiveCameras {
         self.cameras.insert(name.to_string(), Some(entity));
     }
 
+    pub fn remove(&mut self, name: &str) {
+        self.cameras.remove(name);
+    }
+
     pub fn get(&self, name: &str) -> Option<Entity> {
         self.cameras.get(name).and_then(|e| *e)
     }
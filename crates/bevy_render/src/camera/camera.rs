@@ -1,9 +1,10 @@
-use super::CameraProjection;
+use super::{CameraProjection, Exposure};
 use bevy_app::prelude::{EventReader, Events};
-use bevy_ecs::{Added, Component, Entity, Local, Query, QuerySet, Res};
-use bevy_math::Mat4;
+use bevy_ecs::{Added, Changed, Component, Entity, Local, Query, QuerySet, Res};
+use bevy_math::{Mat4, Vec2, Vec3};
 use bevy_property::Properties;
-use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{WindowCreated, WindowId, WindowResized, WindowScaleFactorChanged, Windows};
 
 #[derive(Default, Debug, Properties)]
 pub struct Camera {
@@ -13,6 +14,45 @@ pub struct Camera {
     pub window: WindowId,
     #[property(ignore)]
     pub depth_calculation: DepthCalculation,
+    /// Limits drawing to part of the window. The rest of the window keeps the pass's clear color. Set by the camera's
+    /// projection, see [CameraProjection::update_for_window].
+    #[property(ignore)]
+    pub viewport: Option<Viewport>,
+    /// Scales the lighting of shaded materials, so lights can use physical intensities. When this is None, light
+    /// intensities are used as they are.
+    #[property(ignore)]
+    pub exposure: Option<Exposure>,
+}
+
+/// A part of a window, in physical pixels from its top left corner
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Viewport {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Camera {
+    /// Projects a world position onto the camera's window, in logical pixels from the bottom left corner. Returns None
+    /// if the window doesn't exist or the position is behind the camera. Positions outside of the window are returned
+    /// as is.
+    pub fn world_to_screen(
+        &self,
+        windows: &Windows,
+        camera_transform: &GlobalTransform,
+        world_position: Vec3,
+    ) -> Option<Vec2> {
+        let window = windows.get(self.window)?;
+        let world_to_ndc = self.projection_matrix * camera_transform.compute_matrix().inverse();
+        let clip_position = world_to_ndc * world_position.extend(1.0);
+        if clip_position.w() <= 0.0 {
+            return None;
+        }
+        let ndc_position = clip_position.truncate().truncate() / clip_position.w();
+        let window_size = Vec2::new(window.width(), window.height());
+        Some((ndc_position + Vec2::one()) / 2.0 * window_size)
+    }
 }
 
 #[derive(Debug)]
@@ -31,16 +71,19 @@ impl Default for DepthCalculation {
 pub struct CameraSystemState {
     window_resized_event_reader: EventReader<WindowResized>,
     window_created_event_reader: EventReader<WindowCreated>,
+    window_scale_factor_changed_event_reader: EventReader<WindowScaleFactorChanged>,
 }
 
 pub fn camera_system<T: CameraProjection + Component>(
     mut state: Local<CameraSystemState>,
     window_resized_events: Res<Events<WindowResized>>,
     window_created_events: Res<Events<WindowCreated>>,
+    window_scale_factor_changed_events: Res<Events<WindowScaleFactorChanged>>,
     windows: Res<Windows>,
     mut queries: QuerySet<(
         Query<(Entity, &mut Camera, &mut T)>,
         Query<(Entity, Added<Camera>)>,
+        Query<(Entity, Changed<T>)>,
     )>,
 ) {
     let mut changed_window_ids = Vec::new();
@@ -70,14 +113,28 @@ pub fn camera_system<T: CameraProjection + Component>(
         changed_window_ids.push(event.id);
     }
 
+    // the logical size of a window changes with its scale factor
+    for event in state
+        .window_scale_factor_changed_event_reader
+        .iter(&window_scale_factor_changed_events)
+    {
+        if !changed_window_ids.contains(&event.id) {
+            changed_window_ids.push(event.id);
+        }
+    }
+
     let mut added_cameras = vec![];
     for (entity, _camera) in &mut queries.q1().iter() {
         added_cameras.push(entity);
     }
+    // projections that were changed by hand, for example to zoom in
+    for (entity, _camera_projection) in &mut queries.q2().iter() {
+        added_cameras.push(entity);
+    }
     for (entity, mut camera, mut camera_projection) in queries.q0_mut().iter_mut() {
         if let Some(window) = windows.get(camera.window) {
             if changed_window_ids.contains(&window.id()) || added_cameras.contains(&entity) {
-                camera_projection.update(window.width() as usize, window.height() as usize);
+                camera.viewport = camera_projection.update_for_window(window);
                 camera.projection_matrix = camera_projection.get_projection_matrix();
                 camera.depth_calculation = camera_projection.depth_calculation();
             }
@@ -0,0 +1,303 @@
+use super::{ActiveCameras, Camera, VisibleEntities};
+use crate::{
+    render_graph::{base, CameraNode, CubemapCaptureNode, RenderGraph},
+    renderer::RenderResourceContext,
+    texture::Texture,
+};
+use bevy_asset::Handle;
+use bevy_ecs::{Changed, Commands, Entity, Local, Query, Res, ResMut};
+use bevy_math::{Mat4, Vec3};
+use bevy_transform::prelude::*;
+use bevy_utils::HashMap;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// How often a [CubemapCapture] renders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubemapCaptureMode {
+    /// Renders once, for example to bake a skybox
+    Once,
+    /// Renders every frame, for example for a reflection probe in a changing scene
+    EveryFrame,
+}
+
+/// Renders the entities of the main pass into a cubemap texture, from the position of the entity this is added to.
+/// The entity needs a [Transform] and a [GlobalTransform]. It gets six child cameras, one per face, which look along
+/// the world axes unless the entity is rotated. Remove the capture or despawn the entity to stop capturing; the
+/// texture keeps the last capture. Changes to the capture apply to the next frame.
+///
+/// The texture has to be made with [Texture::new_cubemap] in the window's format, `TextureFormat::default()`.
+/// Cubemaps are left-handed, so sample the texture with the z of the world direction negated, for example
+/// `texture(samplerCube(Probe, Probe_sampler), vec3(direction.xy, -direction.z))`.
+#[derive(Debug, Clone)]
+pub struct CubemapCapture {
+    pub texture: Handle<Texture>,
+    pub mode: CubemapCaptureMode,
+    pub near: f32,
+    pub far: f32,
+    captured: Arc<AtomicBool>,
+}
+
+impl CubemapCapture {
+    pub fn new(texture: Handle<Texture>, mode: CubemapCaptureMode) -> Self {
+        CubemapCapture {
+            texture,
+            mode,
+            near: 0.1,
+            far: 1000.0,
+            captured: Default::default(),
+        }
+    }
+
+    /// True once the texture has been rendered to. It takes a frame or two after the capture is added.
+    pub fn is_captured(&self) -> bool {
+        self.captured.load(Ordering::Relaxed)
+    }
+}
+
+/// The direction each face looks in and its up direction, in the order of the cubemap's layers. See
+/// [CubemapCapture] for why +Z and -Z are swapped.
+const FACES: [([f32; 3], [f32; 3]); 6] = [
+    ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+    ([-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+    ([0.0, 1.0, 0.0], [0.0, 0.0, 1.0]),
+    ([0.0, -1.0, 0.0], [0.0, 0.0, -1.0]),
+    ([0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+    ([0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+];
+
+fn face_projection(capture: &CubemapCapture) -> Mat4 {
+    Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, capture.near, capture.far)
+}
+
+/// The face cameras and render graph nodes of the [CubemapCapture]s. Render graph nodes can't be removed, so the nodes
+/// of a removed capture are kept without their textures, and reused by the next new capture.
+#[derive(Debug, Default)]
+pub struct CubemapCaptureState {
+    /// The capture that uses each set of nodes, if any
+    slots: Vec<Option<Entity>>,
+    captures: HashMap<Entity, CaptureSlot>,
+}
+
+#[derive(Debug)]
+struct CaptureSlot {
+    index: usize,
+    cameras: Vec<Entity>,
+}
+
+fn node_name(slot: usize) -> String {
+    format!("cubemap_capture_{}", slot)
+}
+
+fn camera_name(slot: usize, face: usize) -> String {
+    format!("cubemap_capture_{}_face_{}", slot, face)
+}
+
+/// Adds the cameras and render graph nodes of new [CubemapCapture]s, keeps them up to date with changed ones, and
+/// removes the cameras and textures of removed ones
+#[allow(clippy::too_many_arguments)]
+pub fn cubemap_capture_system(
+    mut commands: Commands,
+    mut state: Local<CubemapCaptureState>,
+    mut render_graph: ResMut<RenderGraph>,
+    mut active_cameras: ResMut<ActiveCameras>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+    msaa: Res<base::Msaa>,
+    changed_captures: Query<(Entity, Changed<CubemapCapture>)>,
+    captures: Query<&CubemapCapture>,
+    mut cameras: Query<&mut Camera>,
+) {
+    // checked every frame instead of with Query::removed, which misses captures despawned after this system runs
+    let removed = state
+        .captures
+        .keys()
+        .filter(|entity| captures.get(**entity).is_err())
+        .copied()
+        .collect::<Vec<_>>();
+    for entity in removed {
+        let slot = state.captures.remove(&entity).unwrap();
+        state.slots[slot.index] = None;
+        for (face, camera) in slot.cameras.into_iter().enumerate() {
+            // already gone if the capture was despawned recursively
+            commands.despawn(camera);
+            active_cameras.remove(&camera_name(slot.index, face));
+        }
+        render_graph
+            .get_node_mut::<CubemapCaptureNode>(node_name(slot.index))
+            .unwrap()
+            .release_targets(&**render_resource_context);
+    }
+
+    for (entity, capture) in changed_captures.iter() {
+        if let Some(slot) = state.captures.get(&entity) {
+            for camera in slot.cameras.iter() {
+                if let Ok(mut camera) = cameras.get_mut(*camera) {
+                    camera.projection_matrix = face_projection(&capture);
+                }
+            }
+            render_graph
+                .get_node_mut::<CubemapCaptureNode>(node_name(slot.index))
+                .unwrap()
+                .set_capture(
+                    capture.texture.clone_weak(),
+                    capture.mode,
+                    capture.captured.clone(),
+                );
+            continue;
+        }
+
+        let index = if let Some(index) = state.slots.iter().position(|slot| slot.is_none()) {
+            render_graph
+                .get_node_mut::<CubemapCaptureNode>(node_name(index))
+                .unwrap()
+                .set_capture(
+                    capture.texture.clone_weak(),
+                    capture.mode,
+                    capture.captured.clone(),
+                );
+            index
+        } else {
+            let index = state.slots.len();
+            state.slots.push(None);
+            add_capture_nodes(&mut render_graph, index, &capture, &msaa);
+            index
+        };
+
+        let mut slot = CaptureSlot {
+            index,
+            cameras: Vec::with_capacity(FACES.len()),
+        };
+        for (face, (direction, up)) in FACES.iter().enumerate() {
+            let camera_name = camera_name(index, face);
+            commands.spawn((
+                Camera {
+                    name: Some(camera_name.clone()),
+                    projection_matrix: face_projection(&capture),
+                    ..Default::default()
+                },
+                VisibleEntities::default(),
+                Transform::identity().looking_at(Vec3::from(*direction), Vec3::from(*up)),
+                GlobalTransform::default(),
+            ));
+            slot.cameras.push(commands.current_entity().unwrap());
+            active_cameras.add(&camera_name);
+        }
+        commands.push_children(entity, &slot.cameras);
+        state.slots[index] = Some(entity);
+        state.captures.insert(entity, slot);
+    }
+}
+
+fn add_capture_nodes(
+    render_graph: &mut RenderGraph,
+    slot: usize,
+    capture: &CubemapCapture,
+    msaa: &base::Msaa,
+) {
+    let node_name = node_name(slot);
+    let camera_names = (0..FACES.len())
+        .map(|face| camera_name(slot, face))
+        .collect::<Vec<_>>();
+    for camera_name in camera_names.iter() {
+        render_graph.add_system_node(camera_name.clone(), CameraNode::new(camera_name.clone()));
+    }
+
+    render_graph.add_node(
+        node_name.clone(),
+        CubemapCaptureNode::new(
+            capture.texture.clone_weak(),
+            capture.mode,
+            capture.captured.clone(),
+            camera_names.clone(),
+            msaa,
+        ),
+    );
+    for camera_name in camera_names {
+        render_graph
+            .add_node_edge(camera_name, node_name.clone())
+            .unwrap();
+    }
+    for base_node in [base::node::TEXTURE_COPY, base::node::SHARED_BUFFERS].iter() {
+        if render_graph.get_node_id(*base_node).is_ok() {
+            render_graph
+                .add_node_edge(*base_node, node_name.clone())
+                .unwrap();
+        }
+    }
+    // captures are ready for the main pass of the same frame
+    if render_graph.get_node_id(base::node::MAIN_PASS).is_ok() {
+        render_graph
+            .add_node_edge(node_name, base::node::MAIN_PASS)
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{camera_name, cubemap_capture_system, CubemapCapture, CubemapCaptureMode};
+    use crate::{
+        camera::{ActiveCameras, Camera},
+        render_graph::{base::Msaa, RenderGraph},
+        renderer::{HeadlessRenderResourceContext, RenderResourceContext},
+    };
+    use bevy_asset::Handle;
+    use bevy_ecs::{IntoQuerySystem, Resources, Schedule, World};
+    use bevy_math::Mat4;
+    use bevy_transform::prelude::{GlobalTransform, Transform};
+
+    fn camera_count(world: &World) -> usize {
+        world.query::<&Camera>().count()
+    }
+
+    #[test]
+    fn removed_captures_free_their_cameras_for_the_next_capture() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(RenderGraph::default());
+        resources.insert(ActiveCameras::default());
+        resources.insert(Msaa::default());
+        resources.insert::<Box<dyn RenderResourceContext>>(Box::new(
+            HeadlessRenderResourceContext::default(),
+        ));
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", cubemap_capture_system.system());
+
+        let capture = CubemapCapture::new(Handle::default(), CubemapCaptureMode::EveryFrame);
+        let entity = world.spawn((capture, Transform::default(), GlobalTransform::default()));
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(camera_count(&world), 6);
+        let node_count = resources.get::<RenderGraph>().unwrap().iter_nodes().count();
+        assert!(resources
+            .get::<ActiveCameras>()
+            .unwrap()
+            .cameras
+            .contains_key(&camera_name(0, 0)));
+
+        // the face cameras use the new near and far planes
+        world.get_mut::<CubemapCapture>(entity).unwrap().far = 10.0;
+        schedule.run(&mut world, &mut resources);
+        let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 10.0);
+        for camera in world.query::<&Camera>() {
+            assert_eq!(camera.projection_matrix, projection);
+        }
+
+        world.despawn(entity).unwrap();
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(camera_count(&world), 0);
+        assert!(resources.get::<ActiveCameras>().unwrap().cameras.is_empty());
+
+        // a new capture reuses the nodes of the removed one
+        let capture = CubemapCapture::new(Handle::default(), CubemapCaptureMode::EveryFrame);
+        world.spawn((capture, Transform::default(), GlobalTransform::default()));
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(camera_count(&world), 6);
+        assert_eq!(
+            resources.get::<RenderGraph>().unwrap().iter_nodes().count(),
+            node_count
+        );
+        assert_eq!(resources.get::<ActiveCameras>().unwrap().cameras.len(), 6);
+    }
+}
@@ -0,0 +1,54 @@
+/// How much light a camera lets in, as an exposure value at ISO 100. Higher values make the image darker, so bright
+/// scenes need a higher EV100 than dim ones.
+///
+/// Lighting is multiplied by [Exposure::scale] before it is written to the render target. There is no tonemapping,
+/// so light that is still brighter than 1.0 after the exposure is clipped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exposure {
+    pub ev100: f32,
+}
+
+impl Exposure {
+    /// Direct sunlight
+    pub const SUNLIGHT: Exposure = Exposure { ev100: 15.0 };
+    /// An overcast day
+    pub const OVERCAST: Exposure = Exposure { ev100: 12.0 };
+    /// A brightly lit room
+    pub const INDOOR: Exposure = Exposure { ev100: 7.0 };
+
+    pub fn from_ev100(ev100: f32) -> Self {
+        Exposure { ev100 }
+    }
+
+    /// The exposure of a physical camera with the given aperture (f-number), shutter speed (in seconds) and sensitivity
+    /// (ISO)
+    pub fn from_physical(aperture: f32, shutter_speed: f32, sensitivity: f32) -> Self {
+        Exposure {
+            ev100: (aperture * aperture / shutter_speed * 100.0 / sensitivity).log2(),
+        }
+    }
+
+    /// The factor light intensities are multiplied with. A light intensity (in nits) that is bright enough to saturate
+    /// the sensor becomes 1.0.
+    pub fn scale(&self) -> f32 {
+        // the luminance that saturates a sensor, with the lens and vignetting losses of a typical camera
+        1.0 / (1.2 * 2.0f32.powf(self.ev100))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Exposure;
+
+    #[test]
+    fn physical_exposure() {
+        // f/16 at 1/100s and ISO 100 is the "sunny 16" rule
+        let exposure = Exposure::from_physical(16.0, 0.01, 100.0);
+        assert!((exposure.ev100 - 14.64).abs() < 0.01);
+        // doubling the sensitivity lets in one more stop of light
+        let brighter = Exposure::from_physical(16.0, 0.01, 200.0);
+        assert!((brighter.ev100 - (exposure.ev100 - 1.0)).abs() < 1e-4);
+        assert!((brighter.scale() / exposure.scale() - 2.0).abs() < 1e-4);
+        assert!((Exposure::from_ev100(0.0).scale() - 1.0 / 1.2).abs() < 1e-6);
+    }
+}
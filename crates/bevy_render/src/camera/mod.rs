@@ -1,10 +1,14 @@
 mod active_cameras;
 #[allow(clippy::module_inception)]
 mod camera;
+mod cubemap_capture;
+mod exposure;
 mod projection;
 mod visible_entities;
 
 pub use active_cameras::*;
 pub use camera::*;
+pub use cubemap_capture::*;
+pub use exposure::*;
 pub use projection::*;
 pub use visible_entities::*;
@@ -1,12 +1,21 @@
-use super::DepthCalculation;
+use super::{DepthCalculation, Viewport};
 use bevy_math::Mat4;
 use bevy_property::{Properties, Property};
+use bevy_window::Window;
 use serde::{Deserialize, Serialize};
 
 pub trait CameraProjection {
     fn get_projection_matrix(&self) -> Mat4;
-    fn update(&mut self, width: usize, height: usize);
+    /// Updates the projection for a window with the given logical size
+    fn update(&mut self, width: f32, height: f32);
     fn depth_calculation(&self) -> DepthCalculation;
+
+    /// Updates the projection for the window the camera draws to. Returns the part of the window the camera should be
+    /// limited to, if any. Override it for projections that depend on the window's physical size.
+    fn update_for_window(&mut self, window: &Window) -> Option<Viewport> {
+        self.update(window.width(), window.height());
+        None
+    }
 }
 
 #[derive(Debug, Clone, Properties)]
@@ -22,8 +31,8 @@ impl CameraProjection for PerspectiveProjection {
         Mat4::perspective_rh(self.fov, self.aspect_ratio, self.near, self.far)
     }
 
-    fn update(&mut self, width: usize, height: usize) {
-        self.aspect_ratio = width as f32 / height as f32;
+    fn update(&mut self, width: f32, height: f32) {
+        self.aspect_ratio = width / height;
     }
 
     fn depth_calculation(&self) -> DepthCalculation {
@@ -49,6 +58,29 @@ pub enum WindowOrigin {
     BottomLeft,
 }
 
+/// How an [OrthographicProjection] maps world units to the pixels of a window
+#[derive(Debug, Clone, Property, Serialize, Deserialize)]
+pub enum ScalingMode {
+    /// One world unit per logical pixel
+    WindowSize,
+    /// `zoom` physical pixels per world unit, regardless of the window's scale factor. The world unit grid lines up
+    /// with the window's pixels, so pixel art with one texel per world unit doesn't shimmer.
+    PixelPerfect { zoom: u32 },
+    /// Like [ScalingMode::PixelPerfect], with the largest zoom that still shows `width` by `height` world units. With
+    /// `letterbox`, the camera only draws to the part of the window that shows exactly that area.
+    PixelPerfectFit {
+        width: u32,
+        height: u32,
+        letterbox: bool,
+    },
+}
+
+impl Default for ScalingMode {
+    fn default() -> Self {
+        ScalingMode::WindowSize
+    }
+}
+
 #[derive(Debug, Clone, Properties)]
 pub struct OrthographicProjection {
     pub left: f32,
@@ -58,6 +90,23 @@ pub struct OrthographicProjection {
     pub near: f32,
     pub far: f32,
     pub window_origin: WindowOrigin,
+    pub scaling_mode: ScalingMode,
+}
+
+impl OrthographicProjection {
+    /// The number of physical pixels per world unit for a window of the given physical size, if the projection is
+    /// pixel perfect
+    pub fn pixel_perfect_zoom(&self, physical_width: u32, physical_height: u32) -> Option<u32> {
+        match self.scaling_mode {
+            ScalingMode::WindowSize => None,
+            ScalingMode::PixelPerfect { zoom } => Some(zoom.max(1)),
+            ScalingMode::PixelPerfectFit { width, height, .. } => Some(
+                (physical_width / width.max(1))
+                    .min(physical_height / height.max(1))
+                    .max(1),
+            ),
+        }
+    }
 }
 
 impl CameraProjection for OrthographicProjection {
@@ -72,11 +121,11 @@ impl CameraProjection for OrthographicProjection {
         )
     }
 
-    fn update(&mut self, width: usize, height: usize) {
+    fn update(&mut self, width: f32, height: f32) {
         match self.window_origin {
             WindowOrigin::Center => {
-                let half_width = width as f32 / 2.0;
-                let half_height = height as f32 / 2.0;
+                let half_width = width / 2.0;
+                let half_height = height / 2.0;
                 self.left = -half_width;
                 self.right = half_width;
                 self.top = half_height;
@@ -84,8 +133,8 @@ impl CameraProjection for OrthographicProjection {
             }
             WindowOrigin::BottomLeft => {
                 self.left = 0.0;
-                self.right = width as f32;
-                self.top = height as f32;
+                self.right = width;
+                self.top = height;
                 self.bottom = 0.0;
             }
         }
@@ -94,6 +143,51 @@ impl CameraProjection for OrthographicProjection {
     fn depth_calculation(&self) -> DepthCalculation {
         DepthCalculation::ZDifference
     }
+
+    fn update_for_window(&mut self, window: &Window) -> Option<Viewport> {
+        let (physical_width, physical_height) = (window.physical_width(), window.physical_height());
+        let zoom = if let Some(zoom) = self.pixel_perfect_zoom(physical_width, physical_height) {
+            zoom
+        } else {
+            self.update(window.width(), window.height());
+            return None;
+        };
+
+        // the origin is put on a pixel boundary, so world units line up with pixels
+        let (origin_x, origin_y) = match self.window_origin {
+            WindowOrigin::Center => (physical_width / 2, physical_height / 2),
+            WindowOrigin::BottomLeft => (0, 0),
+        };
+        self.left = -(origin_x as f32) / zoom as f32;
+        self.right = self.left + physical_width as f32 / zoom as f32;
+        self.bottom = -(origin_y as f32) / zoom as f32;
+        self.top = self.bottom + physical_height as f32 / zoom as f32;
+
+        match self.scaling_mode {
+            ScalingMode::PixelPerfectFit {
+                width,
+                height,
+                letterbox: true,
+            } => {
+                let viewport_width = (width * zoom).min(physical_width);
+                let viewport_height = (height * zoom).min(physical_height);
+                let (x, bottom) = match self.window_origin {
+                    WindowOrigin::Center => (
+                        origin_x.saturating_sub(viewport_width / 2),
+                        origin_y.saturating_sub(viewport_height / 2),
+                    ),
+                    WindowOrigin::BottomLeft => (0, 0),
+                };
+                Some(Viewport {
+                    x,
+                    y: physical_height - bottom - viewport_height,
+                    width: viewport_width,
+                    height: viewport_height,
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Default for OrthographicProjection {
@@ -106,6 +200,52 @@ impl Default for OrthographicProjection {
             near: 0.0,
             far: 1000.0,
             window_origin: WindowOrigin::Center,
+            scaling_mode: ScalingMode::WindowSize,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CameraProjection, OrthographicProjection, ScalingMode};
+    use crate::camera::Viewport;
+    use bevy_window::{Window, WindowDescriptor, WindowId};
+
+    #[test]
+    fn pixel_perfect_projection() {
+        let window = Window::new(
+            WindowId::primary(),
+            &WindowDescriptor {
+                width: 1001.0,
+                height: 700.0,
+                ..Default::default()
+            },
+        );
+
+        let mut projection = OrthographicProjection {
+            scaling_mode: ScalingMode::PixelPerfect { zoom: 4 },
+            ..Default::default()
+        };
+        assert_eq!(projection.update_for_window(&window), None);
+        assert_eq!((projection.left, projection.right), (-125.0, 125.25));
+        assert_eq!((projection.bottom, projection.top), (-87.5, 87.5));
+
+        let mut projection = OrthographicProjection {
+            scaling_mode: ScalingMode::PixelPerfectFit {
+                width: 320,
+                height: 180,
+                letterbox: true,
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            projection.update_for_window(&window),
+            Some(Viewport {
+                x: 20,
+                y: 80,
+                width: 960,
+                height: 540,
+            })
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use super::{Camera, DepthCalculation};
-use crate::Draw;
+use crate::{Draw, Visible};
 use bevy_core::FloatOrd;
 use bevy_ecs::{Entity, Query, With};
 use bevy_property::Properties;
@@ -25,7 +25,7 @@ impl VisibleEntities {
 
 pub fn visible_entities_system(
     mut camera_query: Query<(&Camera, &GlobalTransform, &mut VisibleEntities)>,
-    draw_query: Query<(Entity, &Draw)>,
+    draw_query: Query<With<Draw, (Entity, Option<&Visible>)>>,
     draw_transform_query: Query<With<Draw, &GlobalTransform>>,
 ) {
     for (camera, camera_global_transform, mut visible_entities) in camera_query.iter_mut() {
@@ -34,8 +34,8 @@ pub fn visible_entities_system(
 
         let mut no_transform_order = 0.0;
         let mut transparent_entities = Vec::new();
-        for (entity, draw) in draw_query.iter() {
-            if !draw.is_visible {
+        for (entity, visible) in draw_query.iter() {
+            if !visible.map_or(true, |visible| visible.is_drawn()) {
                 continue;
             }
 
@@ -52,7 +52,7 @@ pub fn visible_entities_system(
                 order
             };
 
-            if draw.is_transparent {
+            if visible.map_or(false, |visible| visible.is_transparent) {
                 transparent_entities.push(VisibleEntity { entity, order })
             } else {
                 visible_entities.value.push(VisibleEntity { entity, order })
@@ -69,3 +69,47 @@ pub fn visible_entities_system(
         // TODO: check for big changes in visible entities len() vs capacity() (ex: 2x) and resize to prevent holding unneeded memory
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{visible_entities_system, VisibleEntities};
+    use crate::{camera::Camera, Draw, Visible};
+    use bevy_ecs::{IntoQuerySystem, Resources, Schedule, World};
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::GlobalTransform;
+
+    #[test]
+    fn entities_without_visible_are_drawn() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", visible_entities_system.system());
+
+        let camera = world.spawn((
+            Camera::default(),
+            GlobalTransform::default(),
+            VisibleEntities::default(),
+        ));
+        let plain = world.spawn((
+            Draw::default(),
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
+        ));
+        let hidden = world.spawn((
+            Draw::default(),
+            Visible {
+                is_visible: false,
+                ..Default::default()
+            },
+        ));
+        schedule.run(&mut world, &mut resources);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        let entities = visible_entities
+            .iter()
+            .map(|visible_entity| visible_entity.entity)
+            .collect::<Vec<_>>();
+        assert_eq!(entities, vec![plain]);
+        assert!(!entities.contains(&hidden));
+    }
+}
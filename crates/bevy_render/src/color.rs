@@ -132,6 +132,44 @@ impl Color {
         )
     }
 
+    /// New ``Color`` from hue (in degrees), saturation and lightness in sRGB colorspace.
+    pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+        Color::hsla(hue, saturation, lightness, 1.0)
+    }
+
+    /// New ``Color`` from hue (in degrees), saturation, lightness and alpha in sRGB colorspace.
+    pub fn hsla(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Color {
+        let [red, green, blue] = hsl_to_nonlinear_srgb(hue, saturation, lightness);
+        Color::rgba(red, green, blue, alpha)
+    }
+
+    /// New ``Color`` from hue (in degrees), saturation and value in sRGB colorspace.
+    pub fn hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        Color::hsva(hue, saturation, value, 1.0)
+    }
+
+    /// New ``Color`` from hue (in degrees), saturation, value and alpha in sRGB colorspace.
+    pub fn hsva(hue: f32, saturation: f32, value: f32, alpha: f32) -> Color {
+        let [red, green, blue] = hsv_to_nonlinear_srgb(hue, saturation, value);
+        Color::rgba(red, green, blue, alpha)
+    }
+
+    /// Get hue (in degrees), saturation, lightness and alpha in sRGB colorspace.
+    pub fn to_hsla(&self) -> [f32; 4] {
+        let [hue, saturation, lightness] = nonlinear_srgb_to_hsl([self.r(), self.g(), self.b()]);
+        [hue, saturation, lightness, self.alpha]
+    }
+
+    /// Mixes two colors in linear colorspace, which is how light mixes. `t` is 0 for `self` and 1 for `other`.
+    pub fn lerp(self, other: Color, t: f32) -> Color {
+        Color {
+            red: self.red + (other.red - self.red) * t,
+            green: self.green + (other.green - self.green) * t,
+            blue: self.blue + (other.blue - self.blue) * t,
+            alpha: self.alpha + (other.alpha - self.alpha) * t,
+        }
+    }
+
     fn as_nonlinear_srgb_to_linear_srgb(self) -> Color {
         Color {
             red: self.red.nonlinear_to_linear_srgb(),
@@ -599,3 +637,24 @@ fn test_mul_and_mulassign_vec4() {
 
     assert_eq!(starting_color * transformation, mutated_color,);
 }
+
+#[test]
+fn test_hsl_and_lerp() {
+    const EPS: f32 = 0.001;
+    let color = Color::hsla(120.0, 1.0, 0.25, 0.5);
+    assert!((color.r() - 0.0).abs() < EPS);
+    assert!((color.g() - 0.5).abs() < EPS);
+    assert!((color.b() - 0.0).abs() < EPS);
+    assert_eq!(color.a(), 0.5);
+    let [hue, saturation, lightness, alpha] = color.to_hsla();
+    assert!((hue - 120.0).abs() < EPS);
+    assert!((saturation - 1.0).abs() < EPS);
+    assert!((lightness - 0.25).abs() < EPS);
+    assert_eq!(alpha, 0.5);
+    assert_eq!(Color::hsv(0.0, 1.0, 1.0), Color::RED);
+
+    // mixing black and white in linear space is brighter than halfway in sRGB
+    let gray = Color::BLACK.lerp(Color::WHITE, 0.5);
+    assert!((gray.r_linear() - 0.5).abs() < EPS);
+    assert!((gray.r() - 0.735).abs() < EPS);
+}
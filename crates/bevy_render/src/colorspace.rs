@@ -48,3 +48,87 @@ fn test_srgb_full_roundtrip() {
     }
 }
 //==================================================================================================
+
+// HSL and HSV
+//==================================================================================================
+/// Converts a hue in degrees, a chroma and the amount of white to add into non-linear sRGB components
+fn hue_to_nonlinear_srgb(hue: f32, chroma: f32, white: f32) -> [f32; 3] {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let [red, green, blue] = match hue as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    [red + white, green + white, blue + white]
+}
+
+/// Converts hue (in degrees), saturation and lightness into non-linear sRGB components
+pub fn hsl_to_nonlinear_srgb(hue: f32, saturation: f32, lightness: f32) -> [f32; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    hue_to_nonlinear_srgb(hue, chroma, lightness - chroma / 2.0)
+}
+
+/// Converts hue (in degrees), saturation and value into non-linear sRGB components
+pub fn hsv_to_nonlinear_srgb(hue: f32, saturation: f32, value: f32) -> [f32; 3] {
+    let chroma = value * saturation;
+    hue_to_nonlinear_srgb(hue, chroma, value - chroma)
+}
+
+/// Converts non-linear sRGB components into hue (in degrees), saturation and lightness
+pub fn nonlinear_srgb_to_hsl([red, green, blue]: [f32; 3]) -> [f32; 3] {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let chroma = max - min;
+    let lightness = (max + min) / 2.0;
+    if chroma <= 0.0 {
+        return [0.0, 0.0, lightness];
+    }
+
+    let hue = if max == red {
+        60.0 * ((green - blue) / chroma)
+    } else if max == green {
+        60.0 * ((blue - red) / chroma + 2.0)
+    } else {
+        60.0 * ((red - green) / chroma + 4.0)
+    };
+    let saturation = chroma / (1.0 - (2.0 * lightness - 1.0).abs());
+    [hue.rem_euclid(360.0), saturation, lightness]
+}
+
+#[test]
+fn test_hsl_hsv_conversions() {
+    let close =
+        |a: [f32; 3], b: [f32; 3]| a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() < 1e-4);
+    assert!(close(hsl_to_nonlinear_srgb(0.0, 1.0, 0.5), [1.0, 0.0, 0.0]));
+    assert!(close(
+        hsl_to_nonlinear_srgb(120.0, 1.0, 0.25),
+        [0.0, 0.5, 0.0]
+    ));
+    assert!(close(
+        hsl_to_nonlinear_srgb(240.0, 0.0, 0.75),
+        [0.75, 0.75, 0.75]
+    ));
+    assert!(close(
+        hsl_to_nonlinear_srgb(-60.0, 1.0, 0.5),
+        [1.0, 0.0, 1.0]
+    ));
+    assert!(close(
+        hsv_to_nonlinear_srgb(60.0, 1.0, 1.0),
+        [1.0, 1.0, 0.0]
+    ));
+    assert!(close(
+        hsv_to_nonlinear_srgb(180.0, 0.5, 0.5),
+        [0.25, 0.5, 0.5]
+    ));
+
+    for hsl in [[30.0, 0.6, 0.4], [200.0, 0.25, 0.8], [330.0, 1.0, 0.5]].iter() {
+        let [hue, saturation, lightness] = *hsl;
+        let srgb = hsl_to_nonlinear_srgb(hue, saturation, lightness);
+        assert!(close(nonlinear_srgb_to_hsl(srgb), *hsl));
+    }
+}
+//==================================================================================================
@@ -1,7 +1,7 @@
 use crate::{
     pipeline::{
-        PipelineCompiler, PipelineDescriptor, PipelineLayout, PipelineSpecialization,
-        VERTEX_FALLBACK_LAYOUT_NAME,
+        PipelineCompiler, PipelineDescriptor, PipelineError, PipelineLayout,
+        PipelineSpecialization, VERTEX_FALLBACK_LAYOUT_NAME,
     },
     renderer::{
         BindGroup, BindGroupId, BufferId, BufferUsage, RenderResource, RenderResourceBinding,
@@ -11,11 +11,13 @@ use crate::{
 };
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::{
-    FetchResource, Query, Res, ResMut, ResourceIndex, ResourceQuery, Resources, SystemId,
-    TypeAccess, UnsafeClone,
+    Entity, FetchResource, Query, QuerySet, Res, ResMut, ResourceIndex, ResourceQuery, Resources,
+    SystemId, TypeAccess, UnsafeClone,
 };
 use bevy_property::Properties;
-use std::{any::TypeId, ops::Range, sync::Arc};
+use bevy_transform::components::Parent;
+use downcast_rs::{impl_downcast, Downcast};
+use std::{any::TypeId, fmt, ops::Range, sync::Arc};
 use thiserror::Error;
 
 /// A queued command for the renderer
@@ -47,27 +49,52 @@ pub enum RenderCommand {
         vertices: Range<u32>,
         instances: Range<u32>,
     },
+    /// Limits the following draws to a rectangle, in physical pixels relative to the top left of the pass's
+    /// attachments. It stays in effect for later entities in the same pass until it is set again.
+    SetScissorRect {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
 }
 
 /// A component that indicates how to draw an entity.
-#[derive(Debug, Properties, Clone)]
+#[derive(Debug, Default, Properties, Clone)]
 pub struct Draw {
+    #[property(ignore)]
+    pub render_commands: Vec<RenderCommand>,
+}
+
+/// Whether and how an entity is drawn. Hiding an entity also hides its descendants, so a whole character or UI panel
+/// can be hidden without despawning it. Entities without it are drawn as visible and opaque.
+#[derive(Debug, Properties, Clone)]
+pub struct Visible {
     pub is_visible: bool,
+    /// Transparent entities are drawn after opaque ones, from back to front
     pub is_transparent: bool,
+    /// Set by [visible_hierarchy_system] when an ancestor of the entity isn't visible
     #[property(ignore)]
-    pub render_commands: Vec<RenderCommand>,
+    pub is_hidden_by_parent: bool,
 }
 
-impl Default for Draw {
+impl Default for Visible {
     fn default() -> Self {
-        Self {
+        Visible {
             is_visible: true,
             is_transparent: false,
-            render_commands: Default::default(),
+            is_hidden_by_parent: false,
         }
     }
 }
 
+impl Visible {
+    /// The entity and all of its ancestors are visible
+    pub fn is_drawn(&self) -> bool {
+        self.is_visible && !self.is_hidden_by_parent
+    }
+}
+
 impl Draw {
     pub fn clear_render_commands(&mut self) {
         self.render_commands.clear();
@@ -107,6 +134,22 @@ impl Draw {
         });
     }
 
+    pub fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        self.render_command(RenderCommand::Draw {
+            vertices,
+            instances,
+        });
+    }
+
+    pub fn set_scissor_rect(&mut self, x: u32, y: u32, width: u32, height: u32) {
+        self.render_command(RenderCommand::SetScissorRect {
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
     #[inline]
     pub fn render_command(&mut self, render_command: RenderCommand) {
         self.render_commands.push(render_command);
@@ -123,6 +166,10 @@ pub enum DrawError {
     PipelineHasNoLayout,
     #[error("Failed to get a buffer for the given RenderResource.")]
     BufferAllocationFailure,
+    #[error("Pipeline has no bind group {0}")]
+    NonExistentBindGroup(u32),
+    #[error("Pipeline failed to compile: {0}")]
+    PipelineCompilation(#[from] PipelineError),
 }
 
 //#[derive(Debug)]
@@ -220,14 +267,14 @@ impl<'a> FetchResource<'a> for FetchDrawContext {
 }
 
 impl<'a> DrawContext<'a> {
-    pub fn get_uniform_buffer<T: RenderResource>(
+    pub fn get_uniform_buffer<T: RenderResource + ?Sized>(
         &self,
         render_resource: &T,
     ) -> Result<RenderResourceBinding, DrawError> {
         self.get_buffer(render_resource, BufferUsage::UNIFORM)
     }
 
-    pub fn get_buffer<T: RenderResource>(
+    pub fn get_buffer<T: RenderResource + ?Sized>(
         &self,
         render_resource: &T,
         buffer_usage: BufferUsage,
@@ -243,20 +290,13 @@ impl<'a> DrawContext<'a> {
         pipeline_handle: &Handle<PipelineDescriptor>,
         specialization: &PipelineSpecialization,
     ) -> Result<(), DrawError> {
-        let specialized_pipeline = if let Some(specialized_pipeline) = self
-            .pipeline_compiler
-            .get_specialized_pipeline(pipeline_handle, specialization)
-        {
-            specialized_pipeline
-        } else {
-            self.pipeline_compiler.compile_pipeline(
-                &**self.render_resource_context,
-                &mut self.pipelines,
-                &mut self.shaders,
-                pipeline_handle,
-                specialization,
-            )
-        };
+        let specialized_pipeline = self.pipeline_compiler.compile_pipeline(
+            &**self.render_resource_context,
+            &mut self.pipelines,
+            &mut self.shaders,
+            pipeline_handle,
+            specialization,
+        )?;
 
         draw.set_pipeline(&specialized_pipeline);
         self.current_pipeline = Some(specialized_pipeline.clone_weak());
@@ -333,6 +373,29 @@ impl<'a> DrawContext<'a> {
         Ok(())
     }
 
+    /// Writes each of `uniforms` to a new buffer and binds them as the bindings 0, 1, ... of the current pipeline's
+    /// bind group `index`. The buffers only last for this frame.
+    pub fn set_uniforms(
+        &self,
+        draw: &mut Draw,
+        index: u32,
+        uniforms: &[&dyn RenderResource],
+    ) -> Result<(), DrawError> {
+        let bind_group_descriptor = self
+            .get_pipeline_layout()?
+            .get_bind_group(index)
+            .ok_or(DrawError::NonExistentBindGroup(index))?;
+        let mut bind_group = BindGroup::build();
+        for (binding, uniform) in uniforms.iter().enumerate() {
+            bind_group = bind_group.add_binding(binding as u32, self.get_uniform_buffer(*uniform)?);
+        }
+        let bind_group = bind_group.finish();
+        self.render_resource_context
+            .create_bind_group(bind_group_descriptor.id, &bind_group);
+        draw.set_bind_group(index, &bind_group);
+        Ok(())
+    }
+
     pub fn set_vertex_buffers_from_bindings(
         &self,
         draw: &mut Draw,
@@ -375,8 +438,167 @@ pub trait Drawable {
     fn draw(&mut self, draw: &mut Draw, context: &mut DrawContext) -> Result<(), DrawError>;
 }
 
+/// Binds extra data for each draw of an entity, for custom shaders. See [DrawHooks].
+pub trait DrawHook: Downcast + Send + Sync + 'static {
+    /// Called after the entity's pipeline, bind groups and vertex buffers are set, right before the draw call.
+    /// `context` has the current pipeline, so bind groups can be made for its layout, for example with
+    /// [DrawContext::set_uniforms].
+    fn draw(&mut self, draw: &mut Draw, context: &mut DrawContext) -> Result<(), DrawError>;
+}
+
+impl_downcast!(DrawHook);
+
+/// Runs [DrawHook]s before each draw of an entity's
+/// [RenderPipelines](crate::pipeline::RenderPipelines), so custom shaders can get per-draw data like a highlight
+/// color without replacing [draw_render_pipelines_system](crate::pipeline::draw_render_pipelines_system). Hooks set
+/// the bind groups the entity's own bindings don't provide. They can use dynamic uniform offsets, see
+/// [BindGroupBuilder::add_dynamic_buffer](crate::renderer::BindGroupBuilder::add_dynamic_buffer).
+#[derive(Default)]
+pub struct DrawHooks {
+    hooks: Vec<Box<dyn DrawHook>>,
+}
+
+impl fmt::Debug for DrawHooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DrawHooks")
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+impl DrawHooks {
+    pub fn new(hook: impl DrawHook) -> Self {
+        DrawHooks {
+            hooks: vec![Box::new(hook)],
+        }
+    }
+
+    pub fn add(&mut self, hook: impl DrawHook) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    /// The first hook of type `T`, to change the data it binds
+    pub fn get<T: DrawHook>(&self) -> Option<&T> {
+        self.hooks.iter().find_map(|hook| hook.downcast_ref::<T>())
+    }
+
+    pub fn get_mut<T: DrawHook>(&mut self) -> Option<&mut T> {
+        self.hooks
+            .iter_mut()
+            .find_map(|hook| hook.downcast_mut::<T>())
+    }
+
+    pub fn draw(&mut self, draw: &mut Draw, context: &mut DrawContext) -> Result<(), DrawError> {
+        for hook in self.hooks.iter_mut() {
+            hook.draw(draw, context)?;
+        }
+        Ok(())
+    }
+}
+
 pub fn clear_draw_system(mut query: Query<&mut Draw>) {
     for mut draw in query.iter_mut() {
         draw.clear_render_commands();
     }
 }
+
+/// Hides the descendants of entities that aren't visible. Entities without [Visible] in between don't stop it.
+pub fn visible_hierarchy_system(
+    parent_query: Query<&Parent>,
+    mut queries: QuerySet<(Query<(Entity, &Visible)>, Query<&mut Visible>)>,
+) {
+    let mut changed = Vec::new();
+    for (entity, visible) in queries.q0().iter() {
+        let mut is_hidden_by_parent = false;
+        let mut ancestor = entity;
+        while let Ok(parent) = parent_query.get(ancestor) {
+            ancestor = parent.0;
+            if let Ok((_, ancestor_visible)) = queries.q0().get(ancestor) {
+                if !ancestor_visible.is_visible {
+                    is_hidden_by_parent = true;
+                    break;
+                }
+            }
+        }
+        if visible.is_hidden_by_parent != is_hidden_by_parent {
+            changed.push((entity, is_hidden_by_parent));
+        }
+    }
+
+    for (entity, is_hidden_by_parent) in changed {
+        if let Ok(mut visible) = queries.q1_mut().get_mut(entity) {
+            visible.is_hidden_by_parent = is_hidden_by_parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        visible_hierarchy_system, Draw, DrawContext, DrawError, DrawHook, DrawHooks, Visible,
+    };
+    use bevy_ecs::{IntoQuerySystem, Resources, Schedule, World};
+    use bevy_transform::components::Parent;
+
+    #[test]
+    fn hidden_parents_hide_descendants() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", visible_hierarchy_system.system());
+
+        let root = world.spawn((Visible::default(),));
+        // entities without Visible pass visibility on to their children
+        let group = world.spawn((Parent(root),));
+        let child = world.spawn((Visible::default(), Parent(group)));
+        let grandchild = world.spawn((Visible::default(), Parent(child)));
+        schedule.run(&mut world, &mut resources);
+        assert!(world.get::<Visible>(grandchild).unwrap().is_drawn());
+
+        world.get_mut::<Visible>(root).unwrap().is_visible = false;
+        schedule.run(&mut world, &mut resources);
+        assert!(!world.get::<Visible>(root).unwrap().is_drawn());
+        assert!(world.get::<Visible>(child).unwrap().is_hidden_by_parent);
+        assert!(
+            world
+                .get::<Visible>(grandchild)
+                .unwrap()
+                .is_hidden_by_parent
+        );
+
+        world.get_mut::<Visible>(root).unwrap().is_visible = true;
+        world.get_mut::<Visible>(child).unwrap().is_visible = false;
+        schedule.run(&mut world, &mut resources);
+        assert!(!world.get::<Visible>(child).unwrap().is_hidden_by_parent);
+        assert!(!world.get::<Visible>(child).unwrap().is_drawn());
+        assert!(!world.get::<Visible>(grandchild).unwrap().is_drawn());
+    }
+
+    struct Highlight(f32);
+
+    impl DrawHook for Highlight {
+        fn draw(&mut self, _draw: &mut Draw, _context: &mut DrawContext) -> Result<(), DrawError> {
+            Ok(())
+        }
+    }
+
+    struct Outline;
+
+    impl DrawHook for Outline {
+        fn draw(&mut self, _draw: &mut Draw, _context: &mut DrawContext) -> Result<(), DrawError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn draw_hooks_are_found_by_type() {
+        let mut draw_hooks = DrawHooks::new(Outline);
+        assert!(draw_hooks.get::<Highlight>().is_none());
+
+        draw_hooks.add(Highlight(0.5));
+        draw_hooks.get_mut::<Highlight>().unwrap().0 = 1.0;
+        assert_eq!(draw_hooks.get::<Highlight>().unwrap().0, 1.0);
+        assert!(draw_hooks.get::<Outline>().is_some());
+    }
+}
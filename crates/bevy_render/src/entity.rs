@@ -2,7 +2,7 @@ use crate::{
     camera::{Camera, OrthographicProjection, PerspectiveProjection, VisibleEntities},
     pipeline::RenderPipelines,
     render_graph::base,
-    Draw, Mesh,
+    Draw, Mesh, Visible,
 };
 use base::MainPass;
 use bevy_asset::Handle;
@@ -15,6 +15,7 @@ use bevy_transform::components::{GlobalTransform, Transform};
 pub struct MeshComponents {
     pub mesh: Handle<Mesh>,
     pub draw: Draw,
+    pub visible: Visible,
     pub render_pipelines: RenderPipelines,
     pub main_pass: MainPass,
     pub transform: Transform,
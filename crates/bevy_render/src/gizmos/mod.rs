@@ -0,0 +1,348 @@
+use crate::{
+    camera::Camera,
+    color::Color,
+    draw::{Draw, Visible},
+    mesh::{Indices, Mesh},
+    pipeline::{
+        BlendDescriptor, BlendFactor, BlendOperation, ColorStateDescriptor, ColorWrite,
+        CompareFunction, CullMode, DepthStencilStateDescriptor, FrontFace, PipelineDescriptor,
+        PrimitiveTopology, RasterizationStateDescriptor, RenderPipelines, StencilStateDescriptor,
+        StencilStateFaceDescriptor,
+    },
+    render_graph::base::MainPass,
+    shader::{Shader, ShaderStage, ShaderStages},
+    texture::TextureFormat,
+};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Commands, Query, ResMut, With};
+use bevy_math::{Vec3, Vec4};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use bevy_type_registry::TypeUuid;
+use std::f32::consts::PI;
+
+pub const GIZMO_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 7469127417452950301);
+
+pub const GIZMO_MESH_HANDLE: Handle<Mesh> =
+    Handle::weak_from_u64(Mesh::TYPE_UUID, 3217640538101735186);
+
+/// The vertex attribute gizmo colors are stored in
+pub const GIZMO_ATTRIBUTE_COLOR: &str = "Vertex_Color";
+
+/// The number of lines used for a full circle
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// Debug lines and shapes in world space. Gizmos are immediate: everything added to this resource is drawn once, at the
+/// end of the frame, and then cleared, so a system that wants a shape to stay visible draws it every frame.
+///
+/// ```ignore
+/// fn debug_system(mut gizmos: ResMut<Gizmos>, query: Query<&GlobalTransform>) {
+///     for transform in query.iter() {
+///         gizmos.axes(transform, 1.0);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Gizmos {
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+}
+
+impl Gizmos {
+    /// Draws a line from `start` to `end`
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: Color) {
+        let color: [f32; 4] = color.into();
+        self.positions.push(start.into());
+        self.positions.push(end.into());
+        self.colors.push(color);
+        self.colors.push(color);
+    }
+
+    /// Draws a line from `origin` to `origin + direction`
+    pub fn ray(&mut self, origin: Vec3, direction: Vec3, color: Color) {
+        self.line(origin, origin + direction, color);
+    }
+
+    /// Draws lines that connect each point to the next one
+    pub fn line_strip(&mut self, points: impl IntoIterator<Item = Vec3>, color: Color) {
+        let mut points = points.into_iter();
+        if let Some(mut previous) = points.next() {
+            for point in points {
+                self.line(previous, point, color);
+                previous = point;
+            }
+        }
+    }
+
+    /// Draws a line from `start` to `end` with an arrow head at `end`
+    pub fn arrow(&mut self, start: Vec3, end: Vec3, color: Color) {
+        self.line(start, end, color);
+        let direction = end - start;
+        let head_length = direction.length() * 0.2;
+        if head_length <= 0.0 {
+            return;
+        }
+
+        let back = direction.normalize() * -head_length;
+        let side = perpendicular(direction) * head_length * 0.5;
+        let up = direction.normalize().cross(side);
+        for offset in [side, -side, up, -up].iter() {
+            self.line(end, end + back + *offset, color);
+        }
+    }
+
+    /// Draws a circle around `center`, facing `normal`
+    pub fn circle(&mut self, center: Vec3, normal: Vec3, radius: f32, color: Color) {
+        let x_axis = perpendicular(normal);
+        let y_axis = normal.normalize().cross(x_axis);
+        self.arc(center, x_axis, y_axis, radius, 2.0 * PI, color);
+    }
+
+    /// Draws a wire sphere as three circles, one around each axis
+    pub fn sphere(&mut self, center: Vec3, radius: f32, color: Color) {
+        self.circle(center, Vec3::unit_x(), radius, color);
+        self.circle(center, Vec3::unit_y(), radius, color);
+        self.circle(center, Vec3::unit_z(), radius, color);
+    }
+
+    /// Draws the edges of a cube with sides of length 1, moved, rotated and scaled by `transform`
+    pub fn cuboid(&mut self, transform: &Transform, color: Color) {
+        let corners = [
+            Vec3::new(-0.5, -0.5, -0.5),
+            Vec3::new(0.5, -0.5, -0.5),
+            Vec3::new(0.5, 0.5, -0.5),
+            Vec3::new(-0.5, 0.5, -0.5),
+            Vec3::new(-0.5, -0.5, 0.5),
+            Vec3::new(0.5, -0.5, 0.5),
+            Vec3::new(0.5, 0.5, 0.5),
+            Vec3::new(-0.5, 0.5, 0.5),
+        ];
+        let corners = corners
+            .iter()
+            .map(|corner| transform.mul_vec3(*corner))
+            .collect::<Vec<_>>();
+        self.box_edges(&corners, color);
+    }
+
+    /// Draws a wire capsule: a cylinder of the given radius from `start` to `end`, capped with half spheres
+    pub fn capsule(&mut self, start: Vec3, end: Vec3, radius: f32, color: Color) {
+        let axis = if end == start {
+            Vec3::unit_y()
+        } else {
+            (end - start).normalize()
+        };
+        let x_axis = perpendicular(axis);
+        let y_axis = axis.cross(x_axis);
+        self.circle(start, axis, radius, color);
+        self.circle(end, axis, radius, color);
+        for side in [x_axis, -x_axis, y_axis, -y_axis].iter() {
+            self.line(start + *side * radius, end + *side * radius, color);
+        }
+        for side in [x_axis, y_axis].iter() {
+            self.arc(end, *side, axis, radius, PI, color);
+            self.arc(start, *side, -axis, radius, PI, color);
+        }
+    }
+
+    /// Draws the local axes of `transform`: x in red, y in green and z in blue
+    pub fn axes(&mut self, transform: &GlobalTransform, length: f32) {
+        let origin = transform.translation;
+        self.ray(origin, transform.local_x() * length, Color::RED);
+        self.ray(origin, transform.local_y() * length, Color::GREEN);
+        self.ray(origin, transform.local_z() * length, Color::BLUE);
+    }
+
+    /// Draws the volume a camera sees, from its near plane to its far plane
+    pub fn frustum(&mut self, camera: &Camera, transform: &GlobalTransform, color: Color) {
+        let ndc_to_world = transform.compute_matrix() * camera.projection_matrix.inverse();
+        let mut corners = Vec::with_capacity(8);
+        for z in [0.0, 1.0].iter() {
+            for (x, y) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].iter() {
+                let corner = ndc_to_world * Vec4::new(*x, *y, *z, 1.0);
+                corners.push(corner.truncate() / corner.w());
+            }
+        }
+        self.box_edges(&corners, color);
+    }
+
+    /// The number of lines that will be drawn this frame
+    pub fn len(&self) -> usize {
+        self.positions.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Removes every line, so nothing is drawn this frame
+    pub fn clear(&mut self) {
+        self.positions.clear();
+        self.colors.clear();
+    }
+
+    /// Draws the points `center + radius * (cos(a) * x_axis + sin(a) * y_axis)` for `a` from 0 to `angle`
+    fn arc(
+        &mut self,
+        center: Vec3,
+        x_axis: Vec3,
+        y_axis: Vec3,
+        radius: f32,
+        angle: f32,
+        color: Color,
+    ) {
+        let segments = ((CIRCLE_SEGMENTS as f32 * angle / (2.0 * PI)).ceil() as usize).max(1);
+        let points = (0..=segments).map(|i| {
+            let angle = angle * i as f32 / segments as f32;
+            center + (x_axis * angle.cos() + y_axis * angle.sin()) * radius
+        });
+        self.line_strip(points, color);
+    }
+
+    /// Draws the 12 edges of a box, given its back face corners followed by its front face corners in the same
+    /// winding
+    fn box_edges(&mut self, corners: &[Vec3], color: Color) {
+        for i in 0..4 {
+            let next = (i + 1) % 4;
+            self.line(corners[i], corners[next], color);
+            self.line(corners[i + 4], corners[next + 4], color);
+            self.line(corners[i], corners[i + 4], color);
+        }
+    }
+}
+
+/// A normalized vector perpendicular to `direction`
+fn perpendicular(direction: Vec3) -> Vec3 {
+    let other = if direction.x().abs() < 0.9 {
+        Vec3::unit_x()
+    } else {
+        Vec3::unit_y()
+    };
+    direction.cross(other).normalize()
+}
+
+/// Marks the entity that draws [Gizmos]
+#[derive(Debug, Default)]
+pub struct GizmoLines;
+
+pub(crate) fn build_gizmo_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    PipelineDescriptor {
+        rasterization_state: Some(RasterizationStateDescriptor {
+            front_face: FrontFace::Ccw,
+            cull_mode: CullMode::None,
+            depth_bias: 0,
+            depth_bias_slope_scale: 0.0,
+            depth_bias_clamp: 0.0,
+            clamp_depth: false,
+        }),
+        depth_stencil_state: Some(DepthStencilStateDescriptor {
+            format: TextureFormat::Depth32Float,
+            depth_write_enabled: false,
+            depth_compare: CompareFunction::Less,
+            stencil: StencilStateDescriptor {
+                front: StencilStateFaceDescriptor::IGNORE,
+                back: StencilStateFaceDescriptor::IGNORE,
+                read_mask: 0,
+                write_mask: 0,
+            },
+        }),
+        color_states: vec![ColorStateDescriptor {
+            format: TextureFormat::default(),
+            color_blend: BlendDescriptor {
+                src_factor: BlendFactor::SrcAlpha,
+                dst_factor: BlendFactor::OneMinusSrcAlpha,
+                operation: BlendOperation::Add,
+            },
+            alpha_blend: BlendDescriptor {
+                src_factor: BlendFactor::One,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::Add,
+            },
+            write_mask: ColorWrite::ALL,
+        }],
+        ..PipelineDescriptor::new(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                include_str!("gizmo.vert"),
+            )),
+            fragment: Some(shaders.add(Shader::from_glsl(
+                ShaderStage::Fragment,
+                include_str!("gizmo.frag"),
+            ))),
+        })
+    }
+}
+
+pub(crate) fn setup_gizmos_system(mut commands: Commands) {
+    commands.spawn((
+        GIZMO_MESH_HANDLE,
+        RenderPipelines::from_handles(&[GIZMO_PIPELINE_HANDLE]),
+        MainPass,
+        Draw::default(),
+        Visible {
+            is_visible: false,
+            is_transparent: true,
+            ..Default::default()
+        },
+        GizmoLines,
+    ));
+}
+
+/// Moves this frame's [Gizmos] into the gizmo line mesh, and hides it when there is nothing to draw
+pub fn gizmo_system(
+    mut gizmos: ResMut<Gizmos>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<With<GizmoLines, &mut Visible>>,
+) {
+    let is_visible = !gizmos.is_empty();
+    for mut visible in query.iter_mut() {
+        visible.is_visible = is_visible;
+    }
+    if !is_visible {
+        return;
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.set_indices(Some(Indices::U32(
+        (0..gizmos.positions.len() as u32).collect(),
+    )));
+    mesh.set_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        std::mem::take(&mut gizmos.positions).into(),
+    );
+    mesh.set_attribute(
+        GIZMO_ATTRIBUTE_COLOR,
+        std::mem::take(&mut gizmos.colors).into(),
+    );
+    meshes.set_untracked(GIZMO_MESH_HANDLE, mesh);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gizmos;
+    use crate::color::Color;
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::Transform;
+
+    #[test]
+    fn gizmo_shapes() {
+        let mut gizmos = Gizmos::default();
+        gizmos.line(Vec3::zero(), Vec3::one(), Color::WHITE);
+        assert_eq!(gizmos.len(), 1);
+        gizmos.cuboid(&Transform::from_scale(Vec3::splat(2.0)), Color::WHITE);
+        assert_eq!(gizmos.len(), 13);
+        assert!(gizmos.positions.contains(&[1.0, 1.0, 1.0]));
+        assert!(gizmos.positions.contains(&[-1.0, -1.0, -1.0]));
+        gizmos.arrow(Vec3::zero(), Vec3::unit_z(), Color::WHITE);
+        assert_eq!(gizmos.len(), 18);
+
+        gizmos.clear();
+        gizmos.circle(Vec3::unit_y(), Vec3::unit_y(), 2.0, Color::RED);
+        assert_eq!(gizmos.len(), 32);
+        for position in gizmos.positions.iter() {
+            let position = Vec3::from(*position);
+            assert!((position.y() - 1.0).abs() < 1e-5);
+            assert!(((position - Vec3::unit_y()).length() - 2.0).abs() < 1e-5);
+        }
+        assert_eq!(gizmos.colors.len(), gizmos.positions.len());
+    }
+}
@@ -3,9 +3,11 @@ pub mod color;
 pub mod colorspace;
 pub mod draw;
 pub mod entity;
+pub mod gizmos;
 pub mod mesh;
 pub mod pass;
 pub mod pipeline;
+pub mod raycast;
 pub mod render_graph;
 pub mod renderer;
 pub mod shader;
@@ -18,20 +20,22 @@ pub mod prelude {
     pub use crate::{
         base::Msaa,
         color::Color,
-        draw::Draw,
+        draw::{Draw, Visible},
         entity::*,
+        gizmos::Gizmos,
         mesh::{shape, Mesh},
         pass::ClearColor,
         pipeline::RenderPipelines,
+        raycast::{Hit, IgnoreRaycast, Raycast},
         shader::Shader,
         texture::Texture,
     };
 }
 
 use crate::prelude::*;
-use base::{MainPass, Msaa};
+use base::{MainPass, Msaa, TransparentPass};
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{AddAsset, Assets};
 use bevy_ecs::{IntoQuerySystem, IntoThreadLocalSystem};
 use camera::{
     ActiveCameras, Camera, OrthographicProjection, PerspectiveProjection, VisibleEntities,
@@ -42,9 +46,10 @@ use pipeline::{
 };
 use render_graph::{
     base::{self, BaseRenderGraphBuilder, BaseRenderGraphConfig},
-    RenderGraph,
+    RenderGraph, TransientTexturePool,
 };
-use renderer::{AssetRenderResourceBindings, RenderResourceBindings};
+use renderer::{AssetRenderResourceBindings, RenderResourceBindings, RenderStatistics};
+use shader::ShaderLoader;
 use std::ops::Range;
 #[cfg(feature = "hdr")]
 use texture::HdrTextureLoader;
@@ -89,6 +94,8 @@ impl Plugin for RenderPlugin {
             app.init_asset_loader::<HdrTextureLoader>();
         }
 
+        app.init_asset_loader::<ShaderLoader>();
+
         if app.resources().get::<ClearColor>().is_none() {
             app.resources_mut().insert(ClearColor::default());
         }
@@ -104,10 +111,12 @@ impl Plugin for RenderPlugin {
             .add_asset::<PipelineDescriptor>()
             .register_component::<Camera>()
             .register_component::<Draw>()
+            .register_component::<Visible>()
             .register_component::<RenderPipelines>()
             .register_component::<OrthographicProjection>()
             .register_component::<PerspectiveProjection>()
             .register_component::<MainPass>()
+            .register_component::<TransparentPass>()
             .register_component::<VisibleEntities>()
             .register_property::<Color>()
             .register_property::<Range<f32>>()
@@ -117,11 +126,15 @@ impl Plugin for RenderPlugin {
             .register_property::<IndexFormat>()
             .register_properties::<PipelineSpecialization>()
             .init_resource::<RenderGraph>()
+            .init_resource::<TransientTexturePool>()
             .init_resource::<PipelineCompiler>()
             .init_resource::<RenderResourceBindings>()
             .init_resource::<TextureResourceSystemState>()
             .init_resource::<AssetRenderResourceBindings>()
+            .init_resource::<RenderStatistics>()
             .init_resource::<ActiveCameras>()
+            .init_resource::<Gizmos>()
+            .init_resource::<Raycast>()
             .add_system_to_stage(
                 bevy_app::stage::PRE_UPDATE,
                 draw::clear_draw_system.system(),
@@ -138,11 +151,24 @@ impl Plugin for RenderPlugin {
                 bevy_app::stage::POST_UPDATE,
                 camera::camera_system::<PerspectiveProjection>.system(),
             )
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                camera::cubemap_capture_system.system(),
+            )
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                draw::visible_hierarchy_system.system(),
+            )
             // registration order matters here. this must come after all camera_system::<T> systems
             .add_system_to_stage(
                 bevy_app::stage::POST_UPDATE,
                 camera::visible_entities_system.system(),
             )
+            .add_system_to_stage(bevy_app::stage::POST_UPDATE, gizmos::gizmo_system.system())
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                raycast::raycast_system.system(),
+            )
             // TODO: turn these "resource systems" into graph nodes and remove the RENDER_RESOURCE stage
             .add_system_to_stage(
                 stage::RENDER_RESOURCE,
@@ -152,6 +178,10 @@ impl Plugin for RenderPlugin {
                 stage::RENDER_RESOURCE,
                 Texture::texture_resource_system.system(),
             )
+            .add_system_to_stage(
+                stage::RENDER_RESOURCE,
+                pipeline::shader_update_system.system(),
+            )
             .add_system_to_stage(
                 stage::RENDER_GRAPH_SYSTEMS,
                 render_graph::render_graph_schedule_executor_system.thread_local_system(),
@@ -167,6 +197,10 @@ impl Plugin for RenderPlugin {
         }
 
         if let Some(ref config) = self.base_render_graph_config {
+            if config.add_main_pass {
+                app.add_startup_system(gizmos::setup_gizmos_system.system());
+            }
+
             let resources = app.resources();
             let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
             let msaa = resources.get::<Msaa>().unwrap();
@@ -179,6 +213,33 @@ impl Plugin for RenderPlugin {
             if config.add_2d_camera {
                 active_cameras.add(base::camera::CAMERA2D);
             }
+
+            if config.add_main_pass {
+                let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+                let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+                pipelines.set_untracked(
+                    gizmos::GIZMO_PIPELINE_HANDLE,
+                    gizmos::build_gizmo_pipeline(&mut shaders),
+                );
+            }
+
+            if config.main_pass_scale != 1.0 {
+                let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+                let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+                pipelines.set_untracked(
+                    render_graph::COMPOSITE_PIPELINE_HANDLE,
+                    render_graph::build_composite_pipeline(&mut shaders),
+                );
+            }
+
+            if config.add_transparent_pass && msaa.samples > 1 {
+                let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+                let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+                pipelines.set_untracked(
+                    render_graph::SCENE_DEPTH_RESOLVE_PIPELINE_HANDLE,
+                    render_graph::build_scene_depth_resolve_pipeline(&mut shaders),
+                );
+            }
         }
     }
 }
@@ -38,6 +38,66 @@ impl VertexAttributeValues {
         self.len() == 0
     }
 
+    /// The values, if they are [VertexAttributeValues::Float]
+    pub fn as_float(&self) -> Option<&[f32]> {
+        match self {
+            VertexAttributeValues::Float(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The values, if they are [VertexAttributeValues::Float2]
+    pub fn as_float2(&self) -> Option<&[[f32; 2]]> {
+        match self {
+            VertexAttributeValues::Float2(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The values, if they are [VertexAttributeValues::Float3]
+    pub fn as_float3(&self) -> Option<&[[f32; 3]]> {
+        match self {
+            VertexAttributeValues::Float3(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The values, if they are [VertexAttributeValues::Float4]
+    pub fn as_float4(&self) -> Option<&[[f32; 4]]> {
+        match self {
+            VertexAttributeValues::Float4(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_float_mut(&mut self) -> Option<&mut Vec<f32>> {
+        match self {
+            VertexAttributeValues::Float(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_float2_mut(&mut self) -> Option<&mut Vec<[f32; 2]>> {
+        match self {
+            VertexAttributeValues::Float2(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_float3_mut(&mut self) -> Option<&mut Vec<[f32; 3]>> {
+        match self {
+            VertexAttributeValues::Float3(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_float4_mut(&mut self) -> Option<&mut Vec<[f32; 4]>> {
+        match self {
+            VertexAttributeValues::Float4(values) => Some(values),
+            _ => None,
+        }
+    }
+
     // TODO: add vertex format as parameter here and perform type conversions
     pub fn get_bytes(&self) -> &[u8] {
         match self {
@@ -113,6 +173,8 @@ impl Mesh {
     pub const ATTRIBUTE_NORMAL: &'static str = "Vertex_Normal";
     pub const ATTRIBUTE_POSITION: &'static str = "Vertex_Position";
     pub const ATTRIBUTE_UV_0: &'static str = "Vertex_Uv";
+    /// A second set of texture coordinates, used to sample lightmaps
+    pub const ATTRIBUTE_UV_1: &'static str = "Vertex_Uv2";
 
     pub fn new(primitive_topology: PrimitiveTopology) -> Self {
         Mesh {
@@ -126,6 +188,7 @@ impl Mesh {
         self.primitive_topology
     }
 
+    /// Sets the values of a vertex attribute, replacing any previous values
     pub fn set_attribute(
         &mut self,
         name: impl Into<Cow<'static, str>>,
@@ -134,10 +197,40 @@ impl Mesh {
         self.attributes.insert(name.into(), values);
     }
 
+    /// The values of a vertex attribute. Use the typed accessors to get at them, like
+    /// `mesh.attribute(Mesh::ATTRIBUTE_POSITION).and_then(|values| values.as_float3())`.
     pub fn attribute(&self, name: impl Into<Cow<'static, str>>) -> Option<&VertexAttributeValues> {
         self.attributes.get(&name.into())
     }
 
+    pub fn attribute_mut(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<&mut VertexAttributeValues> {
+        self.attributes.get_mut(&name.into())
+    }
+
+    /// Removes a vertex attribute and returns its values
+    pub fn remove_attribute(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<VertexAttributeValues> {
+        self.attributes.remove(&name.into())
+    }
+
+    /// The names and values of all vertex attributes, in no particular order
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, &VertexAttributeValues)> {
+        self.attributes
+            .iter()
+            .map(|(name, values)| (name.as_ref(), values))
+    }
+
+    /// The bounds of the mesh's positions, or None if it has no Float3 positions
+    pub fn compute_aabb(&self) -> Option<Aabb> {
+        let positions = self.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+        Aabb::from_points(positions.iter().map(|position| Vec3::from(*position)))
+    }
+
     pub fn set_indices(&mut self, indices: Option<Indices>) {
         self.indices = indices;
     }
@@ -649,3 +742,34 @@ pub fn mesh_resource_provider_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Mesh, VertexAttributeValues};
+    use crate::pipeline::PrimitiveTopology;
+
+    #[test]
+    fn typed_attribute_access() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]].into(),
+        );
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 3].into());
+
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION).unwrap();
+        assert_eq!(positions.as_float3().unwrap()[1], [1.0, 0.0, 0.0]);
+        assert!(positions.as_float2().is_none());
+
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3_mut)
+            .unwrap()[1] = [2.0, 0.0, 0.0];
+        assert_eq!(mesh.compute_aabb().unwrap().max.x(), 2.0);
+
+        let mut names = mesh.attributes().map(|(name, _)| name).collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, vec![Mesh::ATTRIBUTE_POSITION, Mesh::ATTRIBUTE_UV_0]);
+        assert!(mesh.remove_attribute(Mesh::ATTRIBUTE_UV_0).is_some());
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_none());
+    }
+}
@@ -12,6 +12,8 @@ pub trait RenderPass {
     fn set_pipeline(&mut self, pipeline_handle: &Handle<PipelineDescriptor>);
     fn set_viewport(&mut self, x: f32, y: f32, w: f32, h: f32, min_depth: f32, max_depth: f32);
     fn set_stencil_reference(&mut self, reference: u32);
+    /// Limits drawing to the given rectangle of the attachments, in physical pixels relative to the top left
+    fn set_scissor_rect(&mut self, x: u32, y: u32, w: u32, h: u32);
     fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>);
     fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>);
     fn set_bind_group(
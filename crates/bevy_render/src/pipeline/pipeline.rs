@@ -110,6 +110,20 @@ impl PipelineDescriptor {
         }
     }
 
+    /// Whether this descriptor compiles to the same pipeline as `other`. Names and layouts are ignored, because
+    /// compiled pipelines reflect their layout from their shaders.
+    pub fn has_same_render_state(&self, other: &PipelineDescriptor) -> bool {
+        self.shader_stages == other.shader_stages
+            && self.rasterization_state == other.rasterization_state
+            && self.primitive_topology == other.primitive_topology
+            && self.color_states == other.color_states
+            && self.depth_stencil_state == other.depth_stencil_state
+            && self.index_format == other.index_format
+            && self.sample_count == other.sample_count
+            && self.sample_mask == other.sample_mask
+            && self.alpha_to_coverage_enabled == other.alpha_to_coverage_enabled
+    }
+
     pub fn get_layout(&self) -> Option<&PipelineLayout> {
         self.layout.as_ref()
     }
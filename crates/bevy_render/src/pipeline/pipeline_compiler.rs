@@ -5,14 +5,17 @@ use crate::{
         VERTEX_FALLBACK_LAYOUT_NAME,
     },
     renderer::RenderResourceContext,
-    shader::{Shader, ShaderSource},
+    shader::{Shader, ShaderError, ShaderSource},
 };
-use bevy_asset::{Assets, Handle};
+use bevy_app::{EventReader, Events};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::{Local, Res, ResMut};
 use bevy_property::{Properties, Property};
 use bevy_utils::{HashMap, HashSet};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use thiserror::Error;
 
 #[derive(Clone, Eq, PartialEq, Debug, Properties)]
 pub struct PipelineSpecialization {
@@ -59,6 +62,21 @@ struct SpecializedShader {
 struct SpecializedPipeline {
     pipeline: Handle<PipelineDescriptor>,
     specialization: PipelineSpecialization,
+    /// The descriptor the pipeline was compiled from, used to share it with identical source pipelines
+    source_descriptor: PipelineDescriptor,
+}
+
+#[derive(Debug)]
+struct FailedPipeline {
+    specialization: PipelineSpecialization,
+    error: PipelineError,
+}
+
+/// The new specialized versions of a modified shader, used instead of the current ones while its pipelines are
+/// compiled again
+struct UpdatedShader<'a> {
+    shader: &'a Handle<Shader>,
+    specialized_shaders: &'a [SpecializedShader],
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize, Property)]
@@ -67,10 +85,47 @@ pub struct DynamicBinding {
     pub binding: u32,
 }
 
+/// An error that occurs while compiling a pipeline
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PipelineError {
+    /// The pipeline is compiled once its shaders are loaded. This error isn't logged or cached.
+    #[error("The shaders of the pipeline aren't loaded yet")]
+    ShadersNotLoaded,
+    #[error(transparent)]
+    Shader(#[from] ShaderError),
+    #[error("Vertex attribute {name} of the mesh has the format {mesh_format:?}, but the vertex shader reads it as {shader_format:?}. Integer formats can only be read as int or uint inputs, and all other formats as float inputs.")]
+    IncompatibleVertexAttribute {
+        name: String,
+        mesh_format: VertexFormat,
+        shader_format: VertexFormat,
+    },
+    #[error("The fragment shader reads {name} from location {location}, but the vertex shader doesn't write to that location")]
+    MissingVertexOutput { name: String, location: u32 },
+    #[error("The fragment shader reads {name} from location {location} as {fragment_type}, but the vertex shader writes {vertex_type} there. Both stages must use the same type.")]
+    MismatchedStageVariable {
+        name: String,
+        location: u32,
+        vertex_type: String,
+        fragment_type: String,
+    },
+}
+
+/// Compiles pipelines for each [PipelineSpecialization] of a source pipeline and caches the results. Source pipelines
+/// with the same shaders and render state share their compiled pipelines, so materials that create their own
+/// [PipelineDescriptor] don't compile a pipeline each. Bind groups are cached by the ids of their resources by the
+/// [RenderResourceContext].
+///
+/// Pipelines that fail to compile are logged once and skipped when drawing. When a shader is modified, the
+/// [shader_update_system] compiles its pipelines again, and keeps the previous pipelines if that fails, so a typo
+/// while editing a shader doesn't stop the app. [PipelineCompiler::iter_errors] lists the current errors.
 #[derive(Debug, Default)]
 pub struct PipelineCompiler {
     specialized_shaders: HashMap<Handle<Shader>, Vec<SpecializedShader>>,
     specialized_pipelines: HashMap<Handle<PipelineDescriptor>, Vec<SpecializedPipeline>>,
+    /// Not compiled again until one of their shaders is modified
+    failed_pipelines: HashMap<Handle<PipelineDescriptor>, Vec<FailedPipeline>>,
+    /// The errors of modified shaders whose pipelines still use the previous version
+    failed_shader_updates: HashMap<Handle<Shader>, PipelineError>,
 }
 
 impl PipelineCompiler {
@@ -79,7 +134,7 @@ impl PipelineCompiler {
         shaders: &mut Assets<Shader>,
         shader_handle: &Handle<Shader>,
         shader_specialization: &ShaderSpecialization,
-    ) -> Handle<Shader> {
+    ) -> Result<Handle<Shader>, ShaderError> {
         let specialized_shaders = self
             .specialized_shaders
             .entry(shader_handle.clone_weak())
@@ -89,7 +144,7 @@ impl PipelineCompiler {
 
         // don't produce new shader if the input source is already spirv
         if let ShaderSource::Spirv(_) = shader.source {
-            return shader_handle.clone_weak();
+            return Ok(shader_handle.clone_weak());
         }
 
         if let Some(specialized_shader) =
@@ -100,25 +155,44 @@ impl PipelineCompiler {
                 })
         {
             // if shader has already been compiled with current configuration, use existing shader
-            specialized_shader.shader.clone_weak()
+            Ok(specialized_shader.shader.clone_weak())
         } else {
             // if no shader exists with the current configuration, create new shader and compile
-            let shader_def_vec = shader_specialization
-                .shader_defs
-                .iter()
-                .cloned()
-                .collect::<Vec<String>>();
-            let compiled_shader = shader.get_spirv_shader(Some(&shader_def_vec));
+            let compiled_shader =
+                shader.get_spirv_shader(Some(&shader_defs(shader_specialization)))?;
             let specialized_handle = shaders.add(compiled_shader);
             let weak_specialized_handle = specialized_handle.clone_weak();
             specialized_shaders.push(SpecializedShader {
                 shader: specialized_handle,
                 specialization: shader_specialization.clone(),
             });
-            weak_specialized_handle
+            Ok(weak_specialized_handle)
         }
     }
 
+    fn specialize_shader(
+        &mut self,
+        shaders: &mut Assets<Shader>,
+        shader_handle: &Handle<Shader>,
+        shader_specialization: &ShaderSpecialization,
+        updated_shader: Option<&UpdatedShader>,
+    ) -> Result<Handle<Shader>, ShaderError> {
+        if let Some(specialized_shader) = updated_shader
+            .filter(|updated_shader| updated_shader.shader == shader_handle)
+            .and_then(|updated_shader| {
+                updated_shader
+                    .specialized_shaders
+                    .iter()
+                    .find(|specialized_shader| {
+                        specialized_shader.specialization == *shader_specialization
+                    })
+            })
+        {
+            return Ok(specialized_shader.shader.clone_weak());
+        }
+        self.compile_shader(shaders, shader_handle, shader_specialization)
+    }
+
     pub fn get_specialized_pipeline(
         &self,
         pipeline: &Handle<PipelineDescriptor>,
@@ -136,6 +210,8 @@ impl PipelineCompiler {
             .map(|specialized_pipeline| specialized_pipeline.pipeline.clone_weak())
     }
 
+    /// Compiles `source_pipeline` for a specialization, or returns the pipeline compiled for it before. Errors are
+    /// logged, and returned again without compiling until one of the pipeline's shaders is modified.
     pub fn compile_pipeline(
         &mut self,
         render_resource_context: &dyn RenderResourceContext,
@@ -143,25 +219,147 @@ impl PipelineCompiler {
         shaders: &mut Assets<Shader>,
         source_pipeline: &Handle<PipelineDescriptor>,
         pipeline_specialization: &PipelineSpecialization,
-    ) -> Handle<PipelineDescriptor> {
+    ) -> Result<Handle<PipelineDescriptor>, PipelineError> {
+        if let Some(specialized_pipeline) =
+            self.get_specialized_pipeline(source_pipeline, pipeline_specialization)
+        {
+            return Ok(specialized_pipeline);
+        }
+        if let Some(failed_pipeline) =
+            self.failed_pipelines
+                .get(source_pipeline)
+                .and_then(|failed_pipelines| {
+                    failed_pipelines.iter().find(|failed_pipeline| {
+                        failed_pipeline.specialization == *pipeline_specialization
+                    })
+                })
+        {
+            return Err(failed_pipeline.error.clone());
+        }
+
         let source_descriptor = pipelines.get(source_pipeline).unwrap();
+        if source_descriptor
+            .shader_stages
+            .iter()
+            .any(|shader| shaders.get(&shader).is_none())
+        {
+            return Err(PipelineError::ShadersNotLoaded);
+        }
+        if let Some(shared_pipeline) =
+            self.find_shared_pipeline(source_descriptor, pipeline_specialization)
+        {
+            let specialized_pipelines = self
+                .specialized_pipelines
+                .entry(source_pipeline.clone_weak())
+                .or_insert_with(Vec::new);
+            let weak_shared_pipeline = shared_pipeline.clone_weak();
+            specialized_pipelines.push(SpecializedPipeline {
+                pipeline: shared_pipeline,
+                specialization: pipeline_specialization.clone(),
+                source_descriptor: source_descriptor.clone(),
+            });
+            return Ok(weak_shared_pipeline);
+        }
+
+        let source_descriptor = source_descriptor.clone();
+        let specialized_descriptor = match self.specialize_pipeline(
+            shaders,
+            &source_descriptor,
+            pipeline_specialization,
+            None,
+        ) {
+            Ok(specialized_descriptor) => specialized_descriptor,
+            Err(error) => {
+                log::error!(
+                    "Failed to compile pipeline {:?}: {}",
+                    source_pipeline,
+                    error
+                );
+                self.failed_pipelines
+                    .entry(source_pipeline.clone_weak())
+                    .or_insert_with(Vec::new)
+                    .push(FailedPipeline {
+                        specialization: pipeline_specialization.clone(),
+                        error: error.clone(),
+                    });
+                return Err(error);
+            }
+        };
+
+        let specialized_pipeline_handle = pipelines.add(specialized_descriptor);
+        render_resource_context.create_render_pipeline(
+            specialized_pipeline_handle.clone_weak(),
+            pipelines.get(&specialized_pipeline_handle).unwrap(),
+            &shaders,
+        );
+
+        let specialized_pipelines = self
+            .specialized_pipelines
+            .entry(source_pipeline.clone_weak())
+            .or_insert_with(Vec::new);
+        let weak_specialized_pipeline_handle = specialized_pipeline_handle.clone_weak();
+        specialized_pipelines.push(SpecializedPipeline {
+            pipeline: specialized_pipeline_handle,
+            specialization: pipeline_specialization.clone(),
+            source_descriptor,
+        });
+
+        Ok(weak_specialized_pipeline_handle)
+    }
+
+    fn specialize_pipeline(
+        &mut self,
+        shaders: &mut Assets<Shader>,
+        source_descriptor: &PipelineDescriptor,
+        pipeline_specialization: &PipelineSpecialization,
+        updated_shader: Option<&UpdatedShader>,
+    ) -> Result<PipelineDescriptor, PipelineError> {
         let mut specialized_descriptor = source_descriptor.clone();
-        specialized_descriptor.shader_stages.vertex = self.compile_shader(
+        specialized_descriptor.shader_stages.vertex = self.specialize_shader(
             shaders,
-            &specialized_descriptor.shader_stages.vertex,
+            &source_descriptor.shader_stages.vertex,
             &pipeline_specialization.shader_specialization,
-        );
-        specialized_descriptor.shader_stages.fragment = specialized_descriptor
+            updated_shader,
+        )?;
+        specialized_descriptor.shader_stages.fragment = source_descriptor
             .shader_stages
             .fragment
             .as_ref()
             .map(|fragment| {
-                self.compile_shader(
+                self.specialize_shader(
                     shaders,
                     fragment,
                     &pipeline_specialization.shader_specialization,
+                    updated_shader,
                 )
-            });
+            })
+            .transpose()?;
+
+        // the render backend can't create pipelines whose fragment inputs don't match the vertex outputs
+        if let Some(fragment) = specialized_descriptor.shader_stages.fragment.as_ref() {
+            let vertex_interface = shaders
+                .get(&specialized_descriptor.shader_stages.vertex)
+                .unwrap()
+                .reflect_interface()
+                .unwrap();
+            let fragment_interface = shaders.get(fragment).unwrap().reflect_interface().unwrap();
+            if let Some((input, output)) =
+                fragment_interface.find_unmatched_input(&vertex_interface)
+            {
+                return Err(match output {
+                    Some(output) => PipelineError::MismatchedStageVariable {
+                        name: input.name.clone(),
+                        location: input.location,
+                        vertex_type: output.type_name.clone(),
+                        fragment_type: input.type_name.clone(),
+                    },
+                    None => PipelineError::MissingVertexOutput {
+                        name: input.name.clone(),
+                        location: input.location,
+                    },
+                });
+            }
+        }
 
         specialized_descriptor.reflect_layout(
             shaders,
@@ -197,6 +395,16 @@ impl PipelineCompiler {
                 .iter()
                 .find(|x| x.name == shader_vertex_attribute.name)
             {
+                if !target_vertex_attribute
+                    .format
+                    .is_compatible_with(shader_vertex_attribute.format)
+                {
+                    return Err(PipelineError::IncompatibleVertexAttribute {
+                        name: shader_vertex_attribute.name.to_string(),
+                        mesh_format: target_vertex_attribute.format,
+                        shader_format: shader_vertex_attribute.format,
+                    });
+                }
                 // copy shader location from reflected layout
                 let mut compiled_vertex_attribute = target_vertex_attribute.clone();
                 compiled_vertex_attribute.shader_location = shader_vertex_attribute.shader_location;
@@ -225,25 +433,172 @@ impl PipelineCompiler {
         specialized_descriptor.sample_count = pipeline_specialization.sample_count;
         specialized_descriptor.primitive_topology = pipeline_specialization.primitive_topology;
         specialized_descriptor.index_format = pipeline_specialization.index_format;
+        Ok(specialized_descriptor)
+    }
 
-        let specialized_pipeline_handle = pipelines.add(specialized_descriptor);
-        render_resource_context.create_render_pipeline(
-            specialized_pipeline_handle.clone_weak(),
-            pipelines.get(&specialized_pipeline_handle).unwrap(),
-            &shaders,
+    /// Compiles the pipelines that use `shader` again, after it was modified. Pipelines that failed to compile get
+    /// another try. If the shader or one of its pipelines fails to compile, the error is logged and all pipelines keep
+    /// using the previous version of the shader.
+    pub fn update_shader(
+        &mut self,
+        shader: &Handle<Shader>,
+        pipelines: &mut Assets<PipelineDescriptor>,
+        shaders: &mut Assets<Shader>,
+        render_resource_context: &dyn RenderResourceContext,
+    ) {
+        let uses_shader = |descriptor: &PipelineDescriptor| {
+            descriptor
+                .shader_stages
+                .iter()
+                .any(|stage_shader| stage_shader == *shader)
+        };
+        self.failed_shader_updates.remove(shader);
+        self.failed_pipelines.retain(|source_pipeline, _| {
+            pipelines
+                .get(source_pipeline)
+                .map_or(false, |descriptor| !uses_shader(descriptor))
+        });
+        let source_shader = if let Some(source_shader) = shaders.get(shader) {
+            source_shader.clone()
+        } else {
+            return;
+        };
+
+        // compile everything before replacing anything, so a failure leaves the previous pipelines in place
+        let shader_specializations = self
+            .specialized_shaders
+            .get(shader)
+            .into_iter()
+            .flatten()
+            .map(|specialized_shader| specialized_shader.specialization.clone())
+            .collect::<Vec<_>>();
+        let mut updated_shaders = Vec::new();
+        for specialization in shader_specializations {
+            match source_shader.get_spirv_shader(Some(&shader_defs(&specialization))) {
+                Ok(compiled_shader) => updated_shaders.push(SpecializedShader {
+                    shader: shaders.add(compiled_shader),
+                    specialization,
+                }),
+                Err(error) => {
+                    self.keep_previous_shader(shader, error.into(), &updated_shaders, shaders);
+                    return;
+                }
+            }
+        }
+
+        let mut outdated_pipelines = Vec::new();
+        for specialized_pipelines in self.specialized_pipelines.values() {
+            for specialized_pipeline in specialized_pipelines.iter() {
+                if uses_shader(&specialized_pipeline.source_descriptor)
+                    && !outdated_pipelines
+                        .iter()
+                        .any(|(pipeline, _, _)| *pipeline == specialized_pipeline.pipeline)
+                {
+                    outdated_pipelines.push((
+                        specialized_pipeline.pipeline.clone_weak(),
+                        specialized_pipeline.source_descriptor.clone(),
+                        specialized_pipeline.specialization.clone(),
+                    ));
+                }
+            }
+        }
+        let mut updated_pipelines = Vec::new();
+        for (pipeline, source_descriptor, specialization) in outdated_pipelines {
+            let updated_shader = UpdatedShader {
+                shader,
+                specialized_shaders: &updated_shaders,
+            };
+            match self.specialize_pipeline(
+                shaders,
+                &source_descriptor,
+                &specialization,
+                Some(&updated_shader),
+            ) {
+                Ok(descriptor) => updated_pipelines.push((pipeline, pipelines.add(descriptor))),
+                Err(error) => {
+                    for (_, updated_pipeline) in updated_pipelines {
+                        pipelines.remove(&updated_pipeline);
+                    }
+                    self.keep_previous_shader(shader, error, &updated_shaders, shaders);
+                    return;
+                }
+            }
+        }
+
+        for (pipeline, updated_pipeline) in updated_pipelines {
+            render_resource_context.create_render_pipeline(
+                updated_pipeline.clone_weak(),
+                pipelines.get(&updated_pipeline).unwrap(),
+                &shaders,
+            );
+            for specialized_pipeline in self.specialized_pipelines.values_mut().flatten() {
+                if specialized_pipeline.pipeline == pipeline {
+                    specialized_pipeline.pipeline = updated_pipeline.clone();
+                }
+            }
+            render_resource_context.remove_render_pipeline(&pipeline);
+            pipelines.remove(&pipeline);
+        }
+        if let Some(specialized_shaders) = self.specialized_shaders.get_mut(shader) {
+            for specialized_shader in specialized_shaders.iter_mut() {
+                if let Some(updated_shader) = updated_shaders.iter().find(|updated_shader| {
+                    updated_shader.specialization == specialized_shader.specialization
+                }) {
+                    let previous_shader = std::mem::replace(
+                        &mut specialized_shader.shader,
+                        updated_shader.shader.clone(),
+                    );
+                    shaders.remove(&previous_shader);
+                }
+            }
+        }
+    }
+
+    fn keep_previous_shader(
+        &mut self,
+        shader: &Handle<Shader>,
+        error: PipelineError,
+        updated_shaders: &[SpecializedShader],
+        shaders: &mut Assets<Shader>,
+    ) {
+        log::error!(
+            "Failed to compile the pipelines of modified shader {:?}, keeping its previous version: {}",
+            shader,
+            error
         );
+        for updated_shader in updated_shaders {
+            shaders.remove(&updated_shader.shader);
+        }
+        self.failed_shader_updates
+            .insert(shader.clone_weak(), error);
+    }
 
-        let specialized_pipelines = self
-            .specialized_pipelines
-            .entry(source_pipeline.clone_weak())
-            .or_insert_with(Vec::new);
-        let weak_specialized_pipeline_handle = specialized_pipeline_handle.clone_weak();
-        specialized_pipelines.push(SpecializedPipeline {
-            pipeline: specialized_pipeline_handle,
-            specialization: pipeline_specialization.clone(),
-        });
+    /// The errors of pipelines that failed to compile, and of modified shaders whose pipelines still use the
+    /// previous version
+    pub fn iter_errors(&self) -> impl Iterator<Item = &PipelineError> {
+        self.failed_pipelines
+            .values()
+            .flatten()
+            .map(|failed_pipeline| &failed_pipeline.error)
+            .chain(self.failed_shader_updates.values())
+    }
 
-        weak_specialized_pipeline_handle
+    /// Finds a pipeline that was compiled from another source pipeline with the same render state and specialization
+    fn find_shared_pipeline(
+        &self,
+        source_descriptor: &PipelineDescriptor,
+        specialization: &PipelineSpecialization,
+    ) -> Option<Handle<PipelineDescriptor>> {
+        self.specialized_pipelines
+            .values()
+            .flatten()
+            .find(|specialized_pipeline| {
+                specialized_pipeline.specialization == *specialization
+                    && specialized_pipeline
+                        .source_descriptor
+                        .has_same_render_state(source_descriptor)
+            })
+            .map(|specialized_pipeline| specialized_pipeline.pipeline.clone())
     }
 
     pub fn iter_compiled_pipelines(
@@ -272,3 +627,235 @@ impl PipelineCompiler {
             .flatten()
     }
 }
+
+fn shader_defs(shader_specialization: &ShaderSpecialization) -> Vec<String> {
+    shader_specialization.shader_defs.iter().cloned().collect()
+}
+
+/// Compiles the pipelines that use a shader again when it is modified, for example when the
+/// [AssetServer](bevy_asset::AssetServer) reloads it
+pub fn shader_update_system(
+    mut shader_event_reader: Local<EventReader<AssetEvent<Shader>>>,
+    shader_events: Res<Events<AssetEvent<Shader>>>,
+    mut pipeline_compiler: ResMut<PipelineCompiler>,
+    mut pipelines: ResMut<Assets<PipelineDescriptor>>,
+    mut shaders: ResMut<Assets<Shader>>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+) {
+    let mut modified_shaders = HashSet::default();
+    for event in shader_event_reader.iter(&shader_events) {
+        if let AssetEvent::Modified { handle } = event {
+            modified_shaders.insert(handle.clone_weak());
+        }
+    }
+    for shader in modified_shaders {
+        pipeline_compiler.update_shader(
+            &shader,
+            &mut pipelines,
+            &mut shaders,
+            &**render_resource_context,
+        );
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::{PipelineCompiler, PipelineError, PipelineSpecialization};
+    use crate::{
+        pipeline::PipelineDescriptor,
+        renderer::HeadlessRenderResourceContext,
+        shader::{Shader, ShaderStage, ShaderStages},
+    };
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_type_registry::TypeRegistry;
+
+    const VERTEX_SHADER: &str = r#"
+        #version 450
+        layout(location = 0) in vec3 Vertex_Position;
+        void main() {
+            gl_Position = vec4(Vertex_Position, 1.0);
+        }
+    "#;
+
+    const FRAGMENT_SHADER: &str = r#"
+        #version 450
+        layout(location = 0) out vec4 o_Target;
+        void main() {
+            o_Target = vec4(1.0);
+        }
+    "#;
+
+    #[test]
+    fn compatible_pipelines_share_a_compiled_pipeline() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Shader>()
+            .add_asset::<PipelineDescriptor>();
+        let mut shaders = app.resources().get_mut::<Assets<Shader>>().unwrap();
+        let mut pipelines = app
+            .resources()
+            .get_mut::<Assets<PipelineDescriptor>>()
+            .unwrap();
+
+        let shader_stages = ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(ShaderStage::Vertex, VERTEX_SHADER)),
+            fragment: Some(shaders.add(Shader::from_glsl(ShaderStage::Fragment, FRAGMENT_SHADER))),
+        };
+        // like two materials that each create their own descriptor
+        let first_pipeline =
+            pipelines.add(PipelineDescriptor::default_config(shader_stages.clone()));
+        let second_pipeline = pipelines.add(PipelineDescriptor::default_config(shader_stages));
+
+        let mut pipeline_compiler = PipelineCompiler::default();
+        let render_resource_context = HeadlessRenderResourceContext::default();
+        let specialization = PipelineSpecialization::default();
+        let compiled_pipeline = pipeline_compiler
+            .compile_pipeline(
+                &render_resource_context,
+                &mut pipelines,
+                &mut shaders,
+                &first_pipeline,
+                &specialization,
+            )
+            .unwrap();
+        let pipeline_count = pipelines.len();
+        let shared_pipeline = pipeline_compiler
+            .compile_pipeline(
+                &render_resource_context,
+                &mut pipelines,
+                &mut shaders,
+                &second_pipeline,
+                &specialization,
+            )
+            .unwrap();
+        assert_eq!(shared_pipeline, compiled_pipeline);
+        assert_eq!(pipelines.len(), pipeline_count);
+        assert_eq!(
+            pipeline_compiler.get_specialized_pipeline(&second_pipeline, &specialization),
+            Some(compiled_pipeline.clone())
+        );
+
+        // other specializations are compiled on their own
+        let multisampled_pipeline = pipeline_compiler
+            .compile_pipeline(
+                &render_resource_context,
+                &mut pipelines,
+                &mut shaders,
+                &second_pipeline,
+                &PipelineSpecialization {
+                    sample_count: 4,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_ne!(multisampled_pipeline, compiled_pipeline);
+        assert_eq!(pipelines.len(), pipeline_count + 1);
+    }
+
+    #[test]
+    fn fragment_inputs_must_match_vertex_outputs() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Shader>()
+            .add_asset::<PipelineDescriptor>();
+        let mut shaders = app.resources().get_mut::<Assets<Shader>>().unwrap();
+        let mut pipelines = app
+            .resources()
+            .get_mut::<Assets<PipelineDescriptor>>()
+            .unwrap();
+
+        let vertex_shader = |outputs: &str| {
+            Shader::from_glsl(
+                ShaderStage::Vertex,
+                &format!(
+                    r#"
+                    #version 450
+                    layout(location = 0) in vec3 Vertex_Position;
+                    {}
+                    void main() {{
+                        gl_Position = vec4(Vertex_Position, 1.0);
+                    }}
+                "#,
+                    outputs
+                ),
+            )
+        };
+        let fragment_shader = |inputs: &str| {
+            Shader::from_glsl(
+                ShaderStage::Fragment,
+                &format!(
+                    r#"
+                    #version 450
+                    {}
+                    layout(location = 0) out vec4 o_Target;
+                    void main() {{
+                        o_Target = vec4(1.0);
+                    }}
+                "#,
+                    inputs
+                ),
+            )
+        };
+        let vertex = shaders.add(vertex_shader("layout(location = 0) out vec2 v_Uv;"));
+        let matching_pipeline = pipelines.add(PipelineDescriptor::default_config(ShaderStages {
+            vertex: vertex.clone(),
+            fragment: Some(shaders.add(fragment_shader("layout(location = 0) in vec2 v_Uv;"))),
+        }));
+        let mismatched_pipeline = pipelines.add(PipelineDescriptor::default_config(ShaderStages {
+            vertex: vertex.clone(),
+            fragment: Some(shaders.add(fragment_shader("layout(location = 0) in vec3 v_Uv;"))),
+        }));
+
+        let mut pipeline_compiler = PipelineCompiler::default();
+        let render_resource_context = HeadlessRenderResourceContext::default();
+        let specialization = PipelineSpecialization::default();
+        let compiled_pipeline = pipeline_compiler
+            .compile_pipeline(
+                &render_resource_context,
+                &mut pipelines,
+                &mut shaders,
+                &matching_pipeline,
+                &specialization,
+            )
+            .unwrap();
+        assert_eq!(
+            pipeline_compiler.compile_pipeline(
+                &render_resource_context,
+                &mut pipelines,
+                &mut shaders,
+                &mismatched_pipeline,
+                &specialization,
+            ),
+            Err(PipelineError::MismatchedStageVariable {
+                name: "v_Uv".into(),
+                location: 0,
+                vertex_type: "vec2".into(),
+                fragment_type: "vec3".into(),
+            })
+        );
+
+        // a modified vertex shader that no longer writes v_Uv keeps the previous pipeline
+        shaders.set(&vertex, vertex_shader(""));
+        pipeline_compiler.update_shader(
+            &vertex,
+            &mut pipelines,
+            &mut shaders,
+            &render_resource_context,
+        );
+        assert_eq!(
+            pipeline_compiler.get_specialized_pipeline(&matching_pipeline, &specialization),
+            Some(compiled_pipeline)
+        );
+        assert!(pipeline_compiler.iter_errors().any(|error| *error
+            == PipelineError::MissingVertexOutput {
+                name: "v_Uv".into(),
+                location: 0,
+            }));
+    }
+}
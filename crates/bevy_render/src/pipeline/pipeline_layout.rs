@@ -1,4 +1,4 @@
-use super::{BindGroupDescriptor, VertexBufferDescriptor};
+use super::{BindGroupDescriptor, BindType, VertexBufferDescriptor};
 use crate::shader::ShaderLayout;
 use bevy_utils::HashMap;
 use std::hash::Hash;
@@ -30,8 +30,10 @@ impl PipelineLayout {
                                 .find(|binding| binding.index == shader_binding.index)
                             {
                                 binding.shader_stage |= shader_binding.shader_stage;
-                                if binding.bind_type != shader_binding.bind_type
-                                    || binding.name != shader_binding.name
+                                if !merge_bind_types(
+                                    &mut binding.bind_type,
+                                    &shader_binding.bind_type,
+                                ) || binding.name != shader_binding.name
                                     || binding.index != shader_binding.index
                                 {
                                     panic!("Binding {} in BindGroup {} does not match across all shader types: {:?} {:?}", binding.index, bind_group.index, binding, shader_binding);
@@ -69,6 +71,24 @@ impl PipelineLayout {
     }
 }
 
+/// Merges the bind type of a binding in another shader stage into `bind_type`. Returns false if they don't match.
+fn merge_bind_types(bind_type: &mut BindType, shader_bind_type: &BindType) -> bool {
+    match (bind_type, shader_bind_type) {
+        (
+            BindType::StorageBuffer { dynamic, readonly },
+            BindType::StorageBuffer {
+                dynamic: shader_dynamic,
+                readonly: shader_readonly,
+            },
+        ) => {
+            // storage buffers are only read only if no shader stage writes to them
+            *readonly &= *shader_readonly;
+            dynamic == shader_dynamic
+        }
+        (bind_type, shader_bind_type) => *bind_type == *shader_bind_type,
+    }
+}
+
 #[derive(Hash, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub enum UniformProperty {
     UInt,
@@ -103,3 +123,51 @@ impl UniformProperty {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PipelineLayout;
+    use crate::{
+        pipeline::{BindGroupDescriptor, BindType, BindingDescriptor, BindingShaderStage},
+        shader::ShaderLayout,
+    };
+
+    fn storage_buffer_layout(readonly: bool, shader_stage: BindingShaderStage) -> ShaderLayout {
+        ShaderLayout {
+            entry_point: "main".into(),
+            vertex_buffer_descriptors: Vec::new(),
+            bind_groups: vec![BindGroupDescriptor::new(
+                0,
+                vec![BindingDescriptor {
+                    index: 0,
+                    name: "Lights".into(),
+                    bind_type: BindType::StorageBuffer {
+                        dynamic: false,
+                        readonly,
+                    },
+                    shader_stage,
+                }],
+            )],
+        }
+    }
+
+    #[test]
+    fn storage_buffers_across_stages() {
+        let layout = PipelineLayout::from_shader_layouts(&mut [
+            storage_buffer_layout(true, BindingShaderStage::VERTEX),
+            storage_buffer_layout(false, BindingShaderStage::FRAGMENT),
+        ]);
+        let binding = &layout.get_bind_group(0).unwrap().bindings[0];
+        assert_eq!(
+            binding.bind_type,
+            BindType::StorageBuffer {
+                dynamic: false,
+                readonly: false,
+            }
+        );
+        assert_eq!(
+            binding.shader_stage,
+            BindingShaderStage::VERTEX | BindingShaderStage::FRAGMENT
+        );
+    }
+}
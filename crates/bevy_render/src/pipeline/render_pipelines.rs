@@ -1,6 +1,6 @@
 use super::{PipelineDescriptor, PipelineSpecialization};
 use crate::{
-    draw::{Draw, DrawContext},
+    draw::{Draw, DrawContext, DrawHooks, Visible},
     mesh::{Indices, Mesh},
     prelude::Msaa,
     renderer::RenderResourceBindings,
@@ -77,10 +77,16 @@ pub fn draw_render_pipelines_system(
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
     msaa: Res<Msaa>,
     meshes: Res<Assets<Mesh>>,
-    mut query: Query<(&mut Draw, &mut RenderPipelines, &Handle<Mesh>)>,
+    mut query: Query<(
+        &mut Draw,
+        Option<&Visible>,
+        &mut RenderPipelines,
+        &Handle<Mesh>,
+        Option<&mut DrawHooks>,
+    )>,
 ) {
-    for (mut draw, mut render_pipelines, mesh_handle) in query.iter_mut() {
-        if !draw.is_visible {
+    for (mut draw, visible, mut render_pipelines, mesh_handle, mut draw_hooks) in query.iter_mut() {
+        if !visible.map_or(true, |visible| visible.is_drawn()) {
             continue;
         }
 
@@ -104,13 +110,17 @@ pub fn draw_render_pipelines_system(
         }
 
         for render_pipeline in render_pipelines.pipelines.iter() {
-            draw_context
+            // pipelines that fail to compile are logged by the PipelineCompiler and skipped
+            if draw_context
                 .set_pipeline(
                     &mut draw,
                     &render_pipeline.pipeline,
                     &render_pipeline.specialization,
                 )
-                .unwrap();
+                .is_err()
+            {
+                continue;
+            }
             draw_context
                 .set_bind_groups_from_bindings(
                     &mut draw,
@@ -123,6 +133,12 @@ pub fn draw_render_pipelines_system(
             draw_context
                 .set_vertex_buffers_from_bindings(&mut draw, &[&render_pipelines.bindings])
                 .unwrap();
+            if let Some(draw_hooks) = draw_hooks.as_mut() {
+                if let Err(err) = draw_hooks.draw(&mut draw, &mut draw_context) {
+                    log::warn!("A draw hook failed, skipping the draw: {}", err);
+                    continue;
+                }
+            }
 
             if let Some(indices) = index_range.clone() {
                 draw.draw_indexed(indices, 0, 0..1);
@@ -130,3 +146,149 @@ pub fn draw_render_pipelines_system(
         }
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::{draw_render_pipelines_system, RenderPipeline, RenderPipelines};
+    use crate::{
+        draw::{Draw, DrawContext, DrawError, DrawHook, DrawHooks, RenderCommand, Visible},
+        mesh::{Indices, Mesh},
+        pipeline::{PipelineCompiler, PipelineDescriptor, PrimitiveTopology},
+        prelude::Msaa,
+        renderer::{
+            HeadlessRenderResourceContext, RenderResourceBindings, RenderResourceContext,
+            SharedBuffers,
+        },
+        shader::{Shader, ShaderStage, ShaderStages},
+    };
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_ecs::{IntoQuerySystem, Schedule};
+    use bevy_type_registry::TypeRegistry;
+
+    const VERTEX_SHADER: &str = r#"
+        #version 450
+        layout(location = 0) in vec3 Vertex_Position;
+        void main() {
+            gl_Position = vec4(Vertex_Position, 1.0);
+        }
+    "#;
+
+    const FRAGMENT_SHADER: &str = r#"
+        #version 450
+        layout(location = 0) out vec4 o_Target;
+        void main() {
+            o_Target = vec4(1.0);
+        }
+    "#;
+
+    struct Scissor;
+
+    impl DrawHook for Scissor {
+        fn draw(&mut self, draw: &mut Draw, _context: &mut DrawContext) -> Result<(), DrawError> {
+            draw.set_scissor_rect(0, 0, 16, 16);
+            Ok(())
+        }
+    }
+
+    struct Failing;
+
+    impl DrawHook for Failing {
+        fn draw(&mut self, _draw: &mut Draw, _context: &mut DrawContext) -> Result<(), DrawError> {
+            Err(DrawError::NoPipelineSet)
+        }
+    }
+
+    #[test]
+    fn draw_hooks_run_before_each_mesh_draw() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Shader>()
+            .add_asset::<PipelineDescriptor>()
+            .add_asset::<Mesh>()
+            .init_resource::<PipelineCompiler>()
+            .init_resource::<RenderResourceBindings>()
+            .init_resource::<Msaa>()
+            .add_resource::<Box<dyn RenderResourceContext>>(Box::new(
+                HeadlessRenderResourceContext::default(),
+            ))
+            .add_resource(SharedBuffers::new(Box::new(
+                HeadlessRenderResourceContext::default(),
+            )));
+
+        let pipeline = {
+            let mut shaders = app.resources().get_mut::<Assets<Shader>>().unwrap();
+            let shader_stages = ShaderStages {
+                vertex: shaders.add(Shader::from_glsl(ShaderStage::Vertex, VERTEX_SHADER)),
+                fragment: Some(
+                    shaders.add(Shader::from_glsl(ShaderStage::Fragment, FRAGMENT_SHADER)),
+                ),
+            };
+            let mut pipelines = app
+                .resources()
+                .get_mut::<Assets<PipelineDescriptor>>()
+                .unwrap();
+            pipelines.add(PipelineDescriptor::default_config(shader_stages))
+        };
+        let mesh = {
+            let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+            mesh.set_indices(Some(Indices::U32(vec![0, 1, 2])));
+            app.resources().get_mut::<Assets<Mesh>>().unwrap().add(mesh)
+        };
+
+        let render_pipelines = RenderPipelines::from_pipelines(vec![
+            RenderPipeline::new(pipeline.clone()),
+            RenderPipeline::new(pipeline),
+        ]);
+        let world = &mut app.app.world;
+        let hooked = world.spawn((
+            Draw::default(),
+            Visible::default(),
+            render_pipelines.clone(),
+            mesh.clone(),
+            DrawHooks::new(Scissor),
+        ));
+        let failing = world.spawn((
+            Draw::default(),
+            Visible::default(),
+            render_pipelines,
+            mesh,
+            DrawHooks::new(Failing),
+        ));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("draw");
+        schedule.add_system_to_stage("draw", draw_render_pipelines_system.system());
+        schedule.run(&mut app.app.world, &mut app.app.resources);
+        let world = &app.app.world;
+
+        let scissor = RenderCommand::SetScissorRect {
+            x: 0,
+            y: 0,
+            width: 16,
+            height: 16,
+        };
+        let render_commands = &world.get::<Draw>(hooked).unwrap().render_commands;
+        let draws = render_commands
+            .iter()
+            .enumerate()
+            .filter(|(_, command)| matches!(command, RenderCommand::DrawIndexed { .. }))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        assert_eq!(draws.len(), 2);
+        for draw in draws {
+            assert_eq!(render_commands[draw - 1], scissor);
+        }
+
+        // a failing hook skips the draw
+        assert!(!world
+            .get::<Draw>(failing)
+            .unwrap()
+            .render_commands
+            .iter()
+            .any(|command| matches!(command, RenderCommand::DrawIndexed { .. })));
+    }
+}
@@ -2,7 +2,7 @@ use crate::texture::TextureFormat;
 use bevy_property::Property;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DepthStencilStateDescriptor {
     pub format: TextureFormat,
     pub depth_write_enabled: bool,
@@ -10,7 +10,7 @@ pub struct DepthStencilStateDescriptor {
     pub stencil: StencilStateDescriptor,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct StencilStateDescriptor {
     pub front: StencilStateFaceDescriptor,
     pub back: StencilStateFaceDescriptor,
@@ -99,7 +99,7 @@ impl Default for CullMode {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct RasterizationStateDescriptor {
     pub front_face: FrontFace,
     pub cull_mode: CullMode,
@@ -109,7 +109,7 @@ pub struct RasterizationStateDescriptor {
     pub clamp_depth: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ColorStateDescriptor {
     pub format: TextureFormat,
     pub alpha_blend: BlendDescriptor,
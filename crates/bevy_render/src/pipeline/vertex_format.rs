@@ -70,6 +70,42 @@ impl VertexFormat {
             VertexFormat::Int4 => 4 * 4,
         }
     }
+
+    /// Whether a shader input that was reflected as `shader_format` can read attributes in this format. Normalized
+    /// formats are read as floats. The component counts don't have to match, because the GPU ignores extra components
+    /// and fills in missing ones.
+    pub fn is_compatible_with(&self, shader_format: VertexFormat) -> bool {
+        self.shader_component_type() == shader_format.shader_component_type()
+    }
+
+    fn shader_component_type(&self) -> ShaderComponentType {
+        match *self {
+            VertexFormat::Uchar2
+            | VertexFormat::Uchar4
+            | VertexFormat::Ushort2
+            | VertexFormat::Ushort4
+            | VertexFormat::Uint
+            | VertexFormat::Uint2
+            | VertexFormat::Uint3
+            | VertexFormat::Uint4 => ShaderComponentType::Uint,
+            VertexFormat::Char2
+            | VertexFormat::Char4
+            | VertexFormat::Short2
+            | VertexFormat::Short4
+            | VertexFormat::Int
+            | VertexFormat::Int2
+            | VertexFormat::Int3
+            | VertexFormat::Int4 => ShaderComponentType::Int,
+            _ => ShaderComponentType::Float,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ShaderComponentType {
+    Float,
+    Int,
+    Uint,
 }
 
 pub trait AsVertexFormats {
@@ -134,3 +170,17 @@ impl AsVertexFormats for [f32; 4] {
         &[VertexFormat::Float4]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::VertexFormat;
+
+    #[test]
+    fn shader_compatible_formats() {
+        assert!(VertexFormat::Float3.is_compatible_with(VertexFormat::Float4));
+        assert!(VertexFormat::Uchar4Norm.is_compatible_with(VertexFormat::Float4));
+        assert!(VertexFormat::Ushort4.is_compatible_with(VertexFormat::Uint4));
+        assert!(!VertexFormat::Float4.is_compatible_with(VertexFormat::Uint4));
+        assert!(!VertexFormat::Int2.is_compatible_with(VertexFormat::Uint2));
+    }
+}
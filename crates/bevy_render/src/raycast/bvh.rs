@@ -0,0 +1,268 @@
+use bevy_ecs::Entity;
+use bevy_math::{Aabb, Frustum, Ray, Sphere};
+use bevy_utils::HashMap;
+
+/// The most items a leaf holds before it is split
+const MAX_LEAF_ITEMS: usize = 4;
+
+#[derive(Debug, Clone)]
+enum BvhNodeKind {
+    /// The items `start..start + count` of [Bvh::items]
+    Leaf { start: usize, count: usize },
+    /// The indices of the child nodes, which always come after their parent
+    Branch { left: usize, right: usize },
+}
+
+#[derive(Debug, Clone)]
+struct BvhNode {
+    aabb: Aabb,
+    kind: BvhNodeKind,
+}
+
+/// A bounding volume hierarchy over entity bounding boxes. Moving items only refits the boxes of the nodes above them,
+/// so the tree stays valid but gets less efficient when items move far. Rebuild it when items are added or removed.
+#[derive(Debug, Default, Clone)]
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    items: Vec<(Entity, Aabb)>,
+    item_indices: HashMap<Entity, usize>,
+}
+
+impl Bvh {
+    pub fn build(items: Vec<(Entity, Aabb)>) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            items,
+            item_indices: HashMap::default(),
+        };
+        if !bvh.items.is_empty() {
+            bvh.build_node(0, bvh.items.len());
+        }
+        bvh.item_indices = bvh
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, (entity, _))| (*entity, index))
+            .collect();
+        bvh
+    }
+
+    /// Builds the node for the items `start..end` and returns its index
+    fn build_node(&mut self, start: usize, end: usize) -> usize {
+        let aabb = union(&self.items[start..end]);
+        let index = self.nodes.len();
+        self.nodes.push(BvhNode {
+            aabb,
+            kind: BvhNodeKind::Leaf {
+                start,
+                count: end - start,
+            },
+        });
+        if end - start <= MAX_LEAF_ITEMS {
+            return index;
+        }
+
+        // split at the median along the axis the item centers are spread out the most on
+        let centers =
+            Aabb::from_points(self.items[start..end].iter().map(|(_, aabb)| aabb.center()))
+                .unwrap();
+        let spread = centers.max - centers.min;
+        let axis = if spread.x() >= spread.y() && spread.x() >= spread.z() {
+            0
+        } else if spread.y() >= spread.z() {
+            1
+        } else {
+            2
+        };
+        self.items[start..end].sort_by(|(_, a), (_, b)| {
+            let a = <[f32; 3]>::from(a.center())[axis];
+            let b = <[f32; 3]>::from(b.center())[axis];
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let middle = start + (end - start) / 2;
+        let left = self.build_node(start, middle);
+        let right = self.build_node(middle, end);
+        self.nodes[index].kind = BvhNodeKind::Branch { left, right };
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.item_indices.contains_key(&entity)
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&Aabb> {
+        self.item_indices
+            .get(&entity)
+            .map(|index| &self.items[*index].1)
+    }
+
+    /// Changes the box of an item. Call [Bvh::refit] after changing boxes to update the tree. Returns false if the
+    /// entity isn't in the tree.
+    pub fn set(&mut self, entity: Entity, aabb: Aabb) -> bool {
+        if let Some(index) = self.item_indices.get(&entity) {
+            self.items[*index].1 = aabb;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Updates the boxes of every node to contain the current boxes of their items
+    pub fn refit(&mut self) {
+        // children come after their parents, so walking backwards visits children first
+        for index in (0..self.nodes.len()).rev() {
+            let aabb = match self.nodes[index].kind {
+                BvhNodeKind::Leaf { start, count } => union(&self.items[start..start + count]),
+                BvhNodeKind::Branch { left, right } => {
+                    self.nodes[left].aabb.union(&self.nodes[right].aabb)
+                }
+            };
+            self.nodes[index].aabb = aabb;
+        }
+    }
+
+    /// Calls `visit` with every item whose box might pass `test`. Nodes whose boxes fail `test` are skipped.
+    fn visit(&self, test: impl Fn(&Aabb) -> bool, mut visit: impl FnMut(Entity, &Aabb)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !test(&node.aabb) {
+                continue;
+            }
+            match node.kind {
+                BvhNodeKind::Leaf { start, count } => {
+                    for (entity, aabb) in self.items[start..start + count].iter() {
+                        if test(aabb) {
+                            visit(*entity, aabb);
+                        }
+                    }
+                }
+                BvhNodeKind::Branch { left, right } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+    }
+
+    /// The items whose boxes the ray hits, with the distance to where the ray enters them, in no particular order
+    pub fn cast_ray(&self, ray: &Ray) -> Vec<(Entity, f32)> {
+        let mut hits = Vec::new();
+        self.visit(
+            |aabb| ray.intersect_aabb(aabb).is_some(),
+            |entity, aabb| {
+                if let Some(distance) = ray.intersect_aabb(aabb) {
+                    hits.push((entity, distance));
+                }
+            },
+        );
+        hits
+    }
+
+    pub fn query_aabb(&self, aabb: &Aabb) -> Vec<Entity> {
+        self.query(|item| item.intersects(aabb))
+    }
+
+    pub fn query_sphere(&self, sphere: &Sphere) -> Vec<Entity> {
+        self.query(|item| sphere.intersects_aabb(item))
+    }
+
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<Entity> {
+        self.query(|item| frustum.intersects_aabb(item))
+    }
+
+    fn query(&self, test: impl Fn(&Aabb) -> bool) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        self.visit(test, |entity, _| entities.push(entity));
+        entities
+    }
+}
+
+fn union(items: &[(Entity, Aabb)]) -> Aabb {
+    items
+        .iter()
+        .skip(1)
+        .fold(items[0].1, |union, (_, aabb)| union.union(aabb))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bvh;
+    use bevy_ecs::Entity;
+    use bevy_math::{Aabb, Frustum, Mat4, Ray, Sphere, Vec3};
+
+    fn unit_box(center: Vec3) -> Aabb {
+        Aabb::new(center - Vec3::splat(0.5), center + Vec3::splat(0.5))
+    }
+
+    #[test]
+    fn bvh_queries() {
+        let items = (0..20)
+            .map(|i| {
+                (
+                    Entity::new(i),
+                    unit_box(Vec3::new(i as f32 * 2.0, 0.0, 0.0)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut bvh = Bvh::build(items);
+        assert_eq!(bvh.len(), 20);
+
+        let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::unit_x());
+        let mut hits = bvh.cast_ray(&ray);
+        hits.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        assert_eq!(hits.len(), 20);
+        assert_eq!(hits[0].0, Entity::new(0));
+        assert!((hits[0].1 - 4.5).abs() < 1e-5);
+
+        let ray = Ray::new(Vec3::new(10.0, 5.0, 0.0), -Vec3::unit_y());
+        let hits = bvh.cast_ray(&ray);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, Entity::new(5));
+        assert!((hits[0].1 - 4.5).abs() < 1e-5);
+
+        let mut found = bvh.query_sphere(&Sphere::new(Vec3::new(4.0, 1.0, 0.0), 1.0));
+        found.sort();
+        assert_eq!(found, vec![Entity::new(2)]);
+        let found = bvh.query_aabb(&Aabb::new(
+            Vec3::new(-1.0, -1.0, -1.0),
+            Vec3::new(3.0, 1.0, 1.0),
+        ));
+        assert_eq!(found.len(), 2);
+
+        // moved items are found at their new position after a refit
+        assert!(bvh.set(Entity::new(19), unit_box(Vec3::new(10.0, 10.0, 0.0))));
+        assert!(!bvh.set(Entity::new(20), unit_box(Vec3::zero())));
+        bvh.refit();
+        let mut hits = bvh
+            .cast_ray(&Ray::new(Vec3::new(10.0, 20.0, 0.0), -Vec3::unit_y()))
+            .into_iter()
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        hits.sort();
+        assert_eq!(hits, vec![Entity::new(5), Entity::new(19)]);
+
+        // an orthographic camera at the origin looking down -z, that sees x and y from -3 to 3
+        let frustum = Frustum::from_view_projection(&Mat4::orthographic_rh(
+            -3.0, 3.0, -3.0, 3.0, -10.0, 10.0,
+        ));
+        let mut found = bvh.query_frustum(&frustum);
+        found.sort();
+        assert_eq!(found, vec![Entity::new(0), Entity::new(1)]);
+
+        let aabb = unit_box(Vec3::zero()).transformed(&Mat4::from_scale(Vec3::splat(2.0)));
+        assert_eq!(aabb, Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0)));
+    }
+}
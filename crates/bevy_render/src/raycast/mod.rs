@@ -0,0 +1,246 @@
+mod bvh;
+
+pub use bvh::*;
+
+use crate::{draw::Visible, gizmos::GizmoLines, mesh::Mesh};
+use bevy_app::prelude::{EventReader, Events};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::{Changed, Entity, Local, Query, Res, ResMut, Without};
+use bevy_math::{Aabb, Frustum, Ray, Sphere, Vec3};
+use bevy_transform::prelude::GlobalTransform;
+use bevy_utils::HashMap;
+
+/// An entity a ray hit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    pub entity: Entity,
+    /// The distance along the ray to where it enters the entity's bounding box
+    pub distance: f32,
+    pub point: Vec3,
+}
+
+/// Leaves a mesh entity out of [Raycast], because its mesh doesn't match what is drawn. Sprites and UI nodes have it:
+/// they scale a unit quad in their shaders, and UI nodes are placed in screen space rather than in the world.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct IgnoreRaycast;
+
+/// Finds visible mesh entities along rays and inside volumes. Entities are tested with their world space bounding
+/// boxes, which are kept in a [Bvh] that is refit when entities move and rebuilt when entities are added or removed.
+/// Queries see the entities as of the last [raycast_system] run, in `POST_UPDATE`.
+#[derive(Debug, Default)]
+pub struct Raycast {
+    bvh: Bvh,
+    mesh_aabbs: HashMap<Handle<Mesh>, Option<Aabb>>,
+}
+
+impl Raycast {
+    /// The entities whose bounding boxes the ray hits, nearest first
+    pub fn cast(&self, ray: &Ray) -> Vec<Hit> {
+        let mut hits = self
+            .bvh
+            .cast_ray(ray)
+            .into_iter()
+            .map(|(entity, distance)| Hit {
+                entity,
+                distance,
+                point: ray.at(distance),
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| {
+            a.distance
+                .partial_cmp(&b.distance)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        hits
+    }
+
+    /// The nearest entity the ray hits
+    pub fn cast_first(&self, ray: &Ray) -> Option<Hit> {
+        self.cast(ray).into_iter().next()
+    }
+
+    /// The entities whose bounding boxes are at least partly inside the frustum
+    pub fn frustum(&self, frustum: &Frustum) -> Vec<Entity> {
+        self.bvh.query_frustum(frustum)
+    }
+
+    /// The entities whose bounding boxes are at least partly inside the sphere
+    pub fn sphere(&self, sphere: &Sphere) -> Vec<Entity> {
+        self.bvh.query_sphere(sphere)
+    }
+
+    /// The entities whose bounding boxes intersect `aabb`
+    pub fn aabb(&self, aabb: &Aabb) -> Vec<Entity> {
+        self.bvh.query_aabb(aabb)
+    }
+
+    /// The world space bounding box of an entity
+    pub fn get(&self, entity: Entity) -> Option<&Aabb> {
+        self.bvh.get(entity)
+    }
+
+    pub fn bvh(&self) -> &Bvh {
+        &self.bvh
+    }
+
+    fn mesh_aabb(&mut self, meshes: &Assets<Mesh>, handle: &Handle<Mesh>) -> Option<Aabb> {
+        if let Some(aabb) = self.mesh_aabbs.get(handle) {
+            return *aabb;
+        }
+
+        // meshes that aren't loaded yet are retried every update
+        let mesh = meshes.get(handle)?;
+        let aabb = mesh.compute_aabb();
+        self.mesh_aabbs.insert(handle.clone_weak(), aabb);
+        aabb
+    }
+}
+
+#[derive(Default)]
+pub struct RaycastSystemState {
+    mesh_event_reader: EventReader<AssetEvent<Mesh>>,
+}
+
+/// Keeps the [Raycast] bounding volume hierarchy in sync with visible mesh entities
+pub fn raycast_system(
+    mut state: Local<RaycastSystemState>,
+    mut raycast: ResMut<Raycast>,
+    meshes: Res<Assets<Mesh>>,
+    mesh_events: Res<Events<AssetEvent<Mesh>>>,
+    query: Query<
+        Without<
+            GizmoLines,
+            Without<IgnoreRaycast, (Entity, &Handle<Mesh>, &GlobalTransform, Option<&Visible>)>,
+        >,
+    >,
+    moved_query: Query<
+        Without<
+            GizmoLines,
+            Without<IgnoreRaycast, (Entity, &Handle<Mesh>, Changed<GlobalTransform>)>,
+        >,
+    >,
+    mesh_changed_query: Query<
+        Without<
+            GizmoLines,
+            Without<IgnoreRaycast, (Entity, Changed<Handle<Mesh>>, &GlobalTransform)>,
+        >,
+    >,
+) {
+    let mut meshes_changed = false;
+    for event in state.mesh_event_reader.iter(&mesh_events) {
+        match event {
+            AssetEvent::Created { handle }
+            | AssetEvent::Modified { handle }
+            | AssetEvent::Removed { handle } => {
+                meshes_changed |= raycast.mesh_aabbs.remove(handle).is_some();
+            }
+        }
+    }
+
+    let mut count = 0;
+    let mut same_entities = !meshes_changed;
+    for (entity, mesh, _, visible) in query.iter() {
+        if !visible.map_or(true, |visible| visible.is_drawn())
+            || raycast.mesh_aabb(&meshes, mesh).is_none()
+        {
+            continue;
+        }
+        count += 1;
+        same_entities &= raycast.bvh.contains(entity);
+    }
+
+    if same_entities && count == raycast.bvh.len() {
+        let mut changed = Vec::new();
+        for (entity, mesh, global_transform) in moved_query.iter() {
+            changed.push((entity, mesh.clone_weak(), global_transform.compute_matrix()));
+        }
+        for (entity, mesh, global_transform) in mesh_changed_query.iter() {
+            changed.push((entity, mesh.clone_weak(), global_transform.compute_matrix()));
+        }
+
+        let mut moved = false;
+        for (entity, mesh, matrix) in changed {
+            if !raycast.bvh.contains(entity) {
+                continue;
+            }
+            if let Some(aabb) = raycast.mesh_aabb(&meshes, &mesh) {
+                moved |= raycast.bvh.set(entity, aabb.transformed(&matrix));
+            }
+        }
+        if moved {
+            raycast.bvh.refit();
+        }
+    } else {
+        let mut items = Vec::with_capacity(count);
+        for (entity, mesh, global_transform, visible) in query.iter() {
+            if !visible.map_or(true, |visible| visible.is_drawn()) {
+                continue;
+            }
+            if let Some(aabb) = raycast.mesh_aabb(&meshes, mesh) {
+                items.push((entity, aabb.transformed(&global_transform.compute_matrix())));
+            }
+        }
+        raycast.bvh = Bvh::build(items);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{raycast_system, IgnoreRaycast, Raycast};
+    use crate::{
+        draw::Visible,
+        mesh::{shape, Mesh},
+    };
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_ecs::{IntoQuerySystem, Schedule};
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::GlobalTransform;
+    use bevy_type_registry::TypeRegistry;
+
+    #[test]
+    fn bounds_follow_mesh_changes_and_skip_ignored_entities() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .init_resource::<Raycast>();
+        let (small, large) = {
+            let mut meshes = app.resources().get_mut::<Assets<Mesh>>().unwrap();
+            (
+                meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+                meshes.add(Mesh::from(shape::Cube { size: 4.0 })),
+            )
+        };
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", raycast_system.system());
+
+        let world = &mut app.app.world;
+        let entity = world.spawn((
+            small.clone(),
+            GlobalTransform::identity(),
+            Visible::default(),
+        ));
+        // like a sprite, whose quad mesh is scaled in its shader
+        let ignored = world.spawn((
+            small,
+            GlobalTransform::from_translation(Vec3::new(4.0, 0.0, 0.0)),
+            Visible::default(),
+            IgnoreRaycast,
+        ));
+        schedule.run(&mut app.app.world, &mut app.app.resources);
+        {
+            let raycast = app.app.resources.get::<Raycast>().unwrap();
+            assert_eq!(raycast.get(entity).unwrap().max, Vec3::splat(1.0));
+            assert!(raycast.get(ignored).is_none());
+        }
+
+        *app.app.world.get_mut(entity).unwrap() = large;
+        schedule.run(&mut app.app.world, &mut app.app.resources);
+        let raycast = app.app.resources.get::<Raycast>().unwrap();
+        assert_eq!(raycast.get(entity).unwrap().max, Vec3::splat(4.0));
+    }
+}
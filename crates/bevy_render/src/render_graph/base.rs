@@ -1,13 +1,16 @@
 use super::{
-    CameraNode, PassNode, RenderGraph, SharedBuffersNode, TextureCopyNode, WindowSwapChainNode,
-    WindowTextureNode,
+    CameraNode, CompositeNode, PassNode, RenderGraph, SceneDepthNode, SharedBuffersNode,
+    TextureCopyNode, TransientTextureNode, WindowSwapChainNode, WindowTextureNode,
 };
 use crate::{
     pass::{
         LoadOp, Operations, PassDescriptor, RenderPassColorAttachmentDescriptor,
         RenderPassDepthStencilAttachmentDescriptor, TextureAttachment,
     },
-    texture::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage},
+    texture::{
+        Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage,
+        TextureViewDimension,
+    },
     Color,
 };
 use bevy_property::Properties;
@@ -17,6 +20,11 @@ use bevy_window::WindowId;
 #[derive(Default, Properties)]
 pub struct MainPass;
 
+/// A component that indicates that an entity should be drawn in the "transparent pass". It runs after the main pass,
+/// on top of its color and depth, and can sample the main pass's depth through the "SceneDepth" texture.
+#[derive(Default, Properties)]
+pub struct TransparentPass;
+
 #[derive(Debug)]
 pub struct Msaa {
     pub samples: u32,
@@ -59,6 +67,15 @@ pub struct BaseRenderGraphConfig {
     pub add_main_pass: bool,
     pub connect_main_pass_to_swapchain: bool,
     pub connect_main_pass_to_main_depth_texture: bool,
+    /// Adds the [node::TRANSPARENT_PASS] after the main pass, and copies the main pass's depth to the
+    /// [node::SCENE_DEPTH_TEXTURE] between them. It is only added when the main pass is connected to the swap chain and
+    /// the main depth texture.
+    pub add_transparent_pass: bool,
+    /// Renders the main pass at this fraction of the window's resolution, and scales it to the window in the
+    /// [node::COMPOSITE] node. Passes that run after the composite still render at the window's resolution. That
+    /// includes the UI pass, and the 2D camera, which moves to its own [node::MAIN_PASS_2D] when this isn't 1.0.
+    /// Camera viewports are in window pixels, so they are wrong unless this is 1.0.
+    pub main_pass_scale: f32,
 }
 
 pub mod node {
@@ -69,6 +86,15 @@ pub mod node {
     pub const MAIN_DEPTH_TEXTURE: &str = "main_pass_depth_texture";
     pub const MAIN_SAMPLED_COLOR_ATTACHMENT: &str = "main_pass_sampled_color_attachment";
     pub const MAIN_PASS: &str = "main_pass";
+    pub const MAIN_SCALED_COLOR_TEXTURE: &str = "main_pass_scaled_color_texture";
+    pub const MAIN_SCALED_DEPTH_TEXTURE: &str = "main_pass_scaled_depth_texture";
+    pub const MAIN_SCALED_SAMPLED_COLOR_ATTACHMENT: &str =
+        "main_pass_scaled_sampled_color_attachment";
+    pub const COMPOSITE: &str = "composite";
+    pub const MAIN_PASS_2D: &str = "main_pass_2d";
+    pub const SCENE_DEPTH_TEXTURE: &str = "scene_depth_texture";
+    pub const SCENE_DEPTH: &str = "scene_depth";
+    pub const TRANSPARENT_PASS: &str = "transparent_pass";
     pub const SHARED_BUFFERS: &str = "shared_buffers";
 }
 
@@ -86,6 +112,8 @@ impl Default for BaseRenderGraphConfig {
             add_main_depth_texture: true,
             connect_main_pass_to_swapchain: true,
             connect_main_pass_to_main_depth_texture: true,
+            add_transparent_pass: true,
+            main_pass_scale: 1.0,
         }
     }
 }
@@ -108,25 +136,51 @@ impl BaseRenderGraphBuilder for RenderGraph {
         }
 
         self.add_node(node::SHARED_BUFFERS, SharedBuffersNode::default());
+        let scaled = config.main_pass_scale != 1.0;
+        let transparent = config.add_transparent_pass
+            && config.add_main_pass
+            && config.add_main_depth_texture
+            && config.connect_main_pass_to_main_depth_texture
+            && config.connect_main_pass_to_swapchain;
+        // the 2d camera renders at the window's resolution, over the composited 3d scene
+        let separate_2d_pass = scaled
+            && config.add_2d_camera
+            && config.add_main_pass
+            && config.connect_main_pass_to_swapchain;
         if config.add_main_depth_texture {
+            // the scene depth node copies or resolves the depth the main pass wrote
+            let main_pass_depth_texture_descriptor = if transparent {
+                TextureDescriptor {
+                    usage: TextureUsage::OUTPUT_ATTACHMENT
+                        | TextureUsage::COPY_SRC
+                        | TextureUsage::SAMPLED,
+                    ..depth_texture_descriptor(msaa)
+                }
+            } else {
+                depth_texture_descriptor(msaa)
+            };
+            // the depth and multisampled color attachments only hold data while the passes that draw to them run
             self.add_node(
                 node::MAIN_DEPTH_TEXTURE,
-                WindowTextureNode::new(
+                TransientTextureNode::new(
                     WindowId::primary(),
-                    TextureDescriptor {
-                        size: Extent3d {
-                            depth: 1,
-                            width: 1,
-                            height: 1,
-                        },
-                        mip_level_count: 1,
-                        sample_count: msaa.samples,
-                        dimension: TextureDimension::D2,
-                        format: TextureFormat::Depth32Float, // PERF: vulkan docs recommend using 24 bit depth for better performance
-                        usage: TextureUsage::OUTPUT_ATTACHMENT,
+                    if scaled {
+                        depth_texture_descriptor(msaa)
+                    } else {
+                        main_pass_depth_texture_descriptor
                     },
                 ),
             );
+            if scaled {
+                self.add_node(
+                    node::MAIN_SCALED_DEPTH_TEXTURE,
+                    TransientTextureNode::new(
+                        WindowId::primary(),
+                        main_pass_depth_texture_descriptor,
+                    )
+                    .with_scale(config.main_pass_scale),
+                );
+            }
         }
 
         if config.add_main_pass {
@@ -156,7 +210,7 @@ impl BaseRenderGraphBuilder for RenderGraph {
                 main_pass_node.add_camera(camera::CAMERA3D);
             }
 
-            if config.add_2d_camera {
+            if config.add_2d_camera && !separate_2d_pass {
                 main_pass_node.add_camera(camera::CAMERA2D);
             }
 
@@ -171,7 +225,7 @@ impl BaseRenderGraphBuilder for RenderGraph {
                 self.add_node_edge(node::CAMERA3D, node::MAIN_PASS).unwrap();
             }
 
-            if config.add_2d_camera {
+            if config.add_2d_camera && !separate_2d_pass {
                 self.add_node_edge(node::CAMERA2D, node::MAIN_PASS).unwrap();
             }
         }
@@ -181,59 +235,504 @@ impl BaseRenderGraphBuilder for RenderGraph {
             WindowSwapChainNode::new(WindowId::primary()),
         );
 
+        let color_attachment_slot = if msaa.samples > 1 {
+            "color_resolve_target"
+        } else {
+            "color_attachment"
+        };
         if config.connect_main_pass_to_swapchain {
+            if scaled {
+                // render the main pass to a texture, and draw that texture to the swap chain
+                self.add_node(
+                    node::MAIN_SCALED_COLOR_TEXTURE,
+                    WindowTextureNode::new(
+                        WindowId::primary(),
+                        TextureDescriptor {
+                            sample_count: 1,
+                            usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
+                            ..color_texture_descriptor(msaa)
+                        },
+                    )
+                    .with_scale(config.main_pass_scale),
+                );
+                self.add_slot_edge(
+                    node::MAIN_SCALED_COLOR_TEXTURE,
+                    WindowTextureNode::OUT_TEXTURE,
+                    node::MAIN_PASS,
+                    color_attachment_slot,
+                )
+                .unwrap();
+
+                self.add_node(node::COMPOSITE, CompositeNode::new(msaa));
+                self.add_slot_edge(
+                    node::MAIN_SCALED_COLOR_TEXTURE,
+                    WindowTextureNode::OUT_TEXTURE,
+                    node::COMPOSITE,
+                    CompositeNode::IN_SOURCE,
+                )
+                .unwrap();
+                self.add_slot_edge(
+                    node::PRIMARY_SWAP_CHAIN,
+                    WindowSwapChainNode::OUT_TEXTURE,
+                    node::COMPOSITE,
+                    color_attachment_slot,
+                )
+                .unwrap();
+                self.add_node_edge(node::MAIN_PASS, node::COMPOSITE)
+                    .unwrap();
+            } else {
+                self.add_slot_edge(
+                    node::PRIMARY_SWAP_CHAIN,
+                    WindowSwapChainNode::OUT_TEXTURE,
+                    node::MAIN_PASS,
+                    color_attachment_slot,
+                )
+                .unwrap();
+            }
+        }
+
+        if msaa.samples > 1 {
+            self.add_node(
+                node::MAIN_SAMPLED_COLOR_ATTACHMENT,
+                TransientTextureNode::new(WindowId::primary(), color_texture_descriptor(msaa)),
+            );
+
+            if scaled {
+                self.add_node(
+                    node::MAIN_SCALED_SAMPLED_COLOR_ATTACHMENT,
+                    TransientTextureNode::new(WindowId::primary(), color_texture_descriptor(msaa))
+                        .with_scale(config.main_pass_scale),
+                );
+                self.add_slot_edge(
+                    node::MAIN_SCALED_SAMPLED_COLOR_ATTACHMENT,
+                    TransientTextureNode::OUT_TEXTURE,
+                    node::MAIN_PASS,
+                    "color_attachment",
+                )
+                .unwrap();
+                if config.connect_main_pass_to_swapchain {
+                    self.add_slot_edge(
+                        node::MAIN_SAMPLED_COLOR_ATTACHMENT,
+                        TransientTextureNode::OUT_TEXTURE,
+                        node::COMPOSITE,
+                        CompositeNode::IN_COLOR_ATTACHMENT,
+                    )
+                    .unwrap();
+                }
+            } else {
+                self.add_slot_edge(
+                    node::MAIN_SAMPLED_COLOR_ATTACHMENT,
+                    TransientTextureNode::OUT_TEXTURE,
+                    node::MAIN_PASS,
+                    "color_attachment",
+                )
+                .unwrap();
+            }
+        }
+
+        if config.connect_main_pass_to_main_depth_texture {
             self.add_slot_edge(
-                node::PRIMARY_SWAP_CHAIN,
-                WindowSwapChainNode::OUT_TEXTURE,
-                node::MAIN_PASS,
-                if msaa.samples > 1 {
-                    "color_resolve_target"
+                if scaled {
+                    node::MAIN_SCALED_DEPTH_TEXTURE
                 } else {
-                    "color_attachment"
+                    node::MAIN_DEPTH_TEXTURE
                 },
+                TransientTextureNode::OUT_TEXTURE,
+                node::MAIN_PASS,
+                "depth",
             )
             .unwrap();
         }
 
-        if msaa.samples > 1 {
+        if transparent {
+            let main_pass_depth_texture = if scaled {
+                node::MAIN_SCALED_DEPTH_TEXTURE
+            } else {
+                node::MAIN_DEPTH_TEXTURE
+            };
             self.add_node(
-                node::MAIN_SAMPLED_COLOR_ATTACHMENT,
+                node::SCENE_DEPTH_TEXTURE,
                 WindowTextureNode::new(
                     WindowId::primary(),
                     TextureDescriptor {
-                        size: Extent3d {
-                            depth: 1,
-                            width: 1,
-                            height: 1,
-                        },
-                        mip_level_count: 1,
-                        sample_count: msaa.samples,
-                        dimension: TextureDimension::D2,
-                        format: TextureFormat::default(),
-                        usage: TextureUsage::OUTPUT_ATTACHMENT,
+                        sample_count: 1,
+                        usage: TextureUsage::OUTPUT_ATTACHMENT
+                            | TextureUsage::COPY_DST
+                            | TextureUsage::SAMPLED,
+                        ..depth_texture_descriptor(msaa)
                     },
-                ),
+                )
+                .with_scale(config.main_pass_scale),
             );
+            self.add_node(
+                node::SCENE_DEPTH,
+                SceneDepthNode::new(WindowId::primary(), msaa).with_scale(config.main_pass_scale),
+            );
+            self.add_slot_edge(
+                main_pass_depth_texture,
+                TransientTextureNode::OUT_TEXTURE,
+                node::SCENE_DEPTH,
+                SceneDepthNode::IN_DEPTH,
+            )
+            .unwrap();
+            self.add_slot_edge(
+                node::SCENE_DEPTH_TEXTURE,
+                WindowTextureNode::OUT_TEXTURE,
+                node::SCENE_DEPTH,
+                SceneDepthNode::IN_SCENE_DEPTH,
+            )
+            .unwrap();
+            self.add_node_edge(node::MAIN_PASS, node::SCENE_DEPTH)
+                .unwrap();
 
+            // draws over the main pass's color and depth, without clearing them
+            let mut transparent_pass_node = PassNode::<&TransparentPass>::new(PassDescriptor {
+                color_attachments: vec![msaa.color_attachment_descriptor(
+                    TextureAttachment::Input("color_attachment".to_string()),
+                    TextureAttachment::Input("color_resolve_target".to_string()),
+                    Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    },
+                )],
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: TextureAttachment::Input("depth".to_string()),
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+                sample_count: msaa.samples,
+            });
+            if config.add_3d_camera {
+                transparent_pass_node.add_camera(camera::CAMERA3D);
+            }
+
+            self.add_node(node::TRANSPARENT_PASS, transparent_pass_node);
+            self.add_node_edge(node::TEXTURE_COPY, node::TRANSPARENT_PASS)
+                .unwrap();
+            self.add_node_edge(node::SHARED_BUFFERS, node::TRANSPARENT_PASS)
+                .unwrap();
+            self.add_node_edge(node::SCENE_DEPTH, node::TRANSPARENT_PASS)
+                .unwrap();
+            if config.add_3d_camera {
+                self.add_node_edge(node::CAMERA3D, node::TRANSPARENT_PASS)
+                    .unwrap();
+            }
+
+            // the same attachments as the main pass
             self.add_slot_edge(
-                node::MAIN_SAMPLED_COLOR_ATTACHMENT,
-                WindowSwapChainNode::OUT_TEXTURE,
-                node::MAIN_PASS,
-                "color_attachment",
+                main_pass_depth_texture,
+                TransientTextureNode::OUT_TEXTURE,
+                node::TRANSPARENT_PASS,
+                "depth",
             )
             .unwrap();
+            if scaled {
+                self.add_slot_edge(
+                    node::MAIN_SCALED_COLOR_TEXTURE,
+                    WindowTextureNode::OUT_TEXTURE,
+                    node::TRANSPARENT_PASS,
+                    color_attachment_slot,
+                )
+                .unwrap();
+                self.add_node_edge(node::TRANSPARENT_PASS, node::COMPOSITE)
+                    .unwrap();
+            } else {
+                self.add_slot_edge(
+                    node::PRIMARY_SWAP_CHAIN,
+                    WindowSwapChainNode::OUT_TEXTURE,
+                    node::TRANSPARENT_PASS,
+                    color_attachment_slot,
+                )
+                .unwrap();
+            }
+            if msaa.samples > 1 {
+                self.add_slot_edge(
+                    if scaled {
+                        node::MAIN_SCALED_SAMPLED_COLOR_ATTACHMENT
+                    } else {
+                        node::MAIN_SAMPLED_COLOR_ATTACHMENT
+                    },
+                    TransientTextureNode::OUT_TEXTURE,
+                    node::TRANSPARENT_PASS,
+                    "color_attachment",
+                )
+                .unwrap();
+            }
         }
 
-        if config.connect_main_pass_to_main_depth_texture {
+        if separate_2d_pass {
+            let mut main_pass_2d_node = PassNode::<&MainPass>::new(PassDescriptor {
+                color_attachments: vec![msaa.color_attachment_descriptor(
+                    TextureAttachment::Input("color_attachment".to_string()),
+                    TextureAttachment::Input("color_resolve_target".to_string()),
+                    Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    },
+                )],
+                depth_stencil_attachment: if config.add_main_depth_texture {
+                    Some(RenderPassDepthStencilAttachmentDescriptor {
+                        attachment: TextureAttachment::Input("depth".to_string()),
+                        depth_ops: Some(Operations {
+                            load: LoadOp::Clear(1.0),
+                            store: true,
+                        }),
+                        stencil_ops: None,
+                    })
+                } else {
+                    None
+                },
+                sample_count: msaa.samples,
+            });
+            main_pass_2d_node.add_camera(camera::CAMERA2D);
+
+            self.add_node(node::MAIN_PASS_2D, main_pass_2d_node);
+            self.add_node_edge(node::TEXTURE_COPY, node::MAIN_PASS_2D)
+                .unwrap();
+            self.add_node_edge(node::SHARED_BUFFERS, node::MAIN_PASS_2D)
+                .unwrap();
+            self.add_node_edge(node::CAMERA2D, node::MAIN_PASS_2D)
+                .unwrap();
+            self.add_node_edge(node::COMPOSITE, node::MAIN_PASS_2D)
+                .unwrap();
+
+            // the window's attachments, which the composite drew the 3d scene to
             self.add_slot_edge(
-                node::MAIN_DEPTH_TEXTURE,
-                WindowTextureNode::OUT_TEXTURE,
-                node::MAIN_PASS,
-                "depth",
+                node::PRIMARY_SWAP_CHAIN,
+                WindowSwapChainNode::OUT_TEXTURE,
+                node::MAIN_PASS_2D,
+                color_attachment_slot,
             )
             .unwrap();
+            if msaa.samples > 1 {
+                self.add_slot_edge(
+                    node::MAIN_SAMPLED_COLOR_ATTACHMENT,
+                    TransientTextureNode::OUT_TEXTURE,
+                    node::MAIN_PASS_2D,
+                    "color_attachment",
+                )
+                .unwrap();
+            }
+            if config.add_main_depth_texture {
+                self.add_slot_edge(
+                    node::MAIN_DEPTH_TEXTURE,
+                    TransientTextureNode::OUT_TEXTURE,
+                    node::MAIN_PASS_2D,
+                    "depth",
+                )
+                .unwrap();
+            }
         }
 
         self
     }
 }
+
+fn depth_texture_descriptor(msaa: &Msaa) -> TextureDescriptor {
+    TextureDescriptor {
+        size: Extent3d {
+            depth: 1,
+            width: 1,
+            height: 1,
+        },
+        mip_level_count: 1,
+        sample_count: msaa.samples,
+        dimension: TextureDimension::D2,
+        format: TextureFormat::Depth32Float, // PERF: vulkan docs recommend using 24 bit depth for better performance
+        usage: TextureUsage::OUTPUT_ATTACHMENT,
+        view_dimension: TextureViewDimension::D2,
+    }
+}
+
+fn color_texture_descriptor(msaa: &Msaa) -> TextureDescriptor {
+    TextureDescriptor {
+        size: Extent3d {
+            depth: 1,
+            width: 1,
+            height: 1,
+        },
+        mip_level_count: 1,
+        sample_count: msaa.samples,
+        dimension: TextureDimension::D2,
+        format: TextureFormat::default(),
+        usage: TextureUsage::OUTPUT_ATTACHMENT,
+        view_dimension: TextureViewDimension::D2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{node, BaseRenderGraphBuilder, BaseRenderGraphConfig, Msaa};
+    use crate::{
+        render_graph::{
+            DependentNodeStager, Edge, RenderGraph, RenderGraphStager, TransientTextureNode,
+            TransientTexturePool,
+        },
+        renderer::HeadlessRenderResourceContext,
+    };
+    use bevy_window::{Window, WindowDescriptor, WindowId, Windows};
+
+    fn input_node(graph: &RenderGraph, node_name: &'static str, slot: &'static str) -> String {
+        let node_state = graph.get_node_state(node_name).unwrap();
+        let index = node_state.input_slots.get_slot_index(slot).unwrap();
+        let edge = node_state.edges.get_input_slot_edge(index).unwrap();
+        let output_node = graph.get_node_state(edge.get_output_node()).unwrap();
+        output_node.name.as_ref().unwrap().to_string()
+    }
+
+    #[test]
+    fn transparent_pass_draws_over_the_main_pass() {
+        for &samples in [1, 4].iter() {
+            for &main_pass_scale in [1.0, 0.5].iter() {
+                let mut graph = RenderGraph::default();
+                graph.add_base_graph(
+                    &BaseRenderGraphConfig {
+                        main_pass_scale,
+                        ..Default::default()
+                    },
+                    &Msaa { samples },
+                );
+
+                for node_state in graph.iter_nodes() {
+                    node_state.validate_input_slots().unwrap();
+                }
+                let mut slots = vec!["color_attachment", "depth"];
+                if samples > 1 {
+                    slots.push("color_resolve_target");
+                }
+                for slot in slots {
+                    assert_eq!(
+                        input_node(&graph, node::TRANSPARENT_PASS, slot),
+                        input_node(&graph, node::MAIN_PASS, slot),
+                    );
+                }
+
+                let main_pass = graph.get_node_id(node::MAIN_PASS).unwrap();
+                let scene_depth = graph.get_node_id(node::SCENE_DEPTH).unwrap();
+                let transparent_pass = graph.get_node_id(node::TRANSPARENT_PASS).unwrap();
+                assert!(graph.has_edge(&Edge::NodeEdge {
+                    output_node: main_pass,
+                    input_node: scene_depth,
+                }));
+                assert!(graph.has_edge(&Edge::NodeEdge {
+                    output_node: scene_depth,
+                    input_node: transparent_pass,
+                }));
+                if main_pass_scale != 1.0 {
+                    assert!(graph.has_edge(&Edge::NodeEdge {
+                        output_node: transparent_pass,
+                        input_node: graph.get_node_id(node::COMPOSITE).unwrap(),
+                    }));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scaled_2d_cameras_render_at_the_window_resolution() {
+        for &samples in [1, 4].iter() {
+            let mut graph = RenderGraph::default();
+            graph.add_base_graph(
+                &BaseRenderGraphConfig {
+                    main_pass_scale: 0.5,
+                    ..Default::default()
+                },
+                &Msaa { samples },
+            );
+
+            for node_state in graph.iter_nodes() {
+                node_state.validate_input_slots().unwrap();
+            }
+            let (color_attachment, color_resolve_target) = if samples > 1 {
+                (
+                    node::MAIN_SAMPLED_COLOR_ATTACHMENT,
+                    Some(node::PRIMARY_SWAP_CHAIN),
+                )
+            } else {
+                (node::PRIMARY_SWAP_CHAIN, None)
+            };
+            assert_eq!(
+                input_node(&graph, node::MAIN_PASS_2D, "color_attachment"),
+                color_attachment
+            );
+            if let Some(color_resolve_target) = color_resolve_target {
+                assert_eq!(
+                    input_node(&graph, node::MAIN_PASS_2D, "color_resolve_target"),
+                    color_resolve_target
+                );
+            }
+            assert_eq!(
+                input_node(&graph, node::MAIN_PASS_2D, "depth"),
+                node::MAIN_DEPTH_TEXTURE
+            );
+            assert_eq!(
+                input_node(&graph, node::MAIN_PASS, "depth"),
+                node::MAIN_SCALED_DEPTH_TEXTURE
+            );
+
+            // the 2d camera draws after the scaled 3d scene is composited, and only in its own pass
+            let camera_2d = graph.get_node_id(node::CAMERA2D).unwrap();
+            let main_pass_2d = graph.get_node_id(node::MAIN_PASS_2D).unwrap();
+            assert!(graph.has_edge(&Edge::NodeEdge {
+                output_node: graph.get_node_id(node::COMPOSITE).unwrap(),
+                input_node: main_pass_2d,
+            }));
+            assert!(graph.has_edge(&Edge::NodeEdge {
+                output_node: camera_2d,
+                input_node: main_pass_2d,
+            }));
+            assert!(!graph.has_edge(&Edge::NodeEdge {
+                output_node: camera_2d,
+                input_node: graph.get_node_id(node::MAIN_PASS).unwrap(),
+            }));
+        }
+
+        // unscaled, 2d and 3d share the main pass
+        let mut graph = RenderGraph::default();
+        graph.add_base_graph(&BaseRenderGraphConfig::default(), &Msaa::default());
+        assert!(graph.get_node_id(node::MAIN_PASS_2D).is_err());
+    }
+
+    #[test]
+    fn main_pass_attachments_come_from_the_transient_texture_pool() {
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+        ));
+        for &main_pass_scale in [1.0, 0.5].iter() {
+            let mut graph = RenderGraph::default();
+            graph.add_base_graph(
+                &BaseRenderGraphConfig {
+                    main_pass_scale,
+                    ..Default::default()
+                },
+                &Msaa { samples: 4 },
+            );
+            let stages = DependentNodeStager::loose_grouping()
+                .get_stages(&graph)
+                .unwrap();
+            let mut pool = TransientTexturePool::default();
+            pool.update(
+                &mut graph,
+                &stages,
+                &windows,
+                &HeadlessRenderResourceContext::default(),
+            );
+
+            for &slot in ["color_attachment", "depth"].iter() {
+                let texture_node = input_node(&graph, node::MAIN_PASS, slot);
+                let node_state = graph.get_node_state(texture_node).unwrap();
+                assert!(node_state
+                    .node
+                    .downcast_ref::<TransientTextureNode>()
+                    .is_some());
+                assert!(node_state.output_slots.get(0).is_some());
+            }
+            assert!(!pool.is_empty());
+        }
+    }
+}
@@ -78,8 +78,12 @@ pub fn camera_node_system(
 ) {
     let render_resource_context = &**render_resource_context;
 
-    let (camera, global_transform) = if let Some(entity) = active_cameras.get(&state.camera_name) {
-        query.get(entity).unwrap()
+    // the camera may have been despawned
+    let (camera, global_transform) = if let Some(Ok(camera)) = active_cameras
+        .get(&state.camera_name)
+        .map(|entity| query.get(entity))
+    {
+        camera
     } else {
         return;
     };
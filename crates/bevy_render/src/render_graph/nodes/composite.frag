@@ -0,0 +1,12 @@
+#version 450
+
+layout(location = 0) in vec2 v_Uv;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 0, binding = 0) uniform texture2D CompositeSource;
+layout(set = 0, binding = 1) uniform sampler CompositeSource_sampler;
+
+void main() {
+    o_Target = texture(sampler2D(CompositeSource, CompositeSource_sampler), v_Uv);
+}
@@ -0,0 +1,11 @@
+#version 450
+
+layout(location = 0) in vec2 Vertex_Position;
+
+layout(location = 0) out vec2 v_Uv;
+
+void main() {
+    // texture coordinates start at the top left
+    v_Uv = vec2(Vertex_Position.x * 0.5 + 0.5, 0.5 - Vertex_Position.y * 0.5);
+    gl_Position = vec4(Vertex_Position, 0.0, 1.0);
+}
@@ -0,0 +1,214 @@
+use crate::{
+    pass::{LoadOp, Operations, PassDescriptor, TextureAttachment},
+    pipeline::{
+        BlendDescriptor, ColorStateDescriptor, ColorWrite, CullMode, FrontFace, InputStepMode,
+        PipelineCompiler, PipelineDescriptor, PipelineSpecialization, RasterizationStateDescriptor,
+        VertexAttributeDescriptor, VertexBufferDescriptor, VertexFormat,
+    },
+    render_graph::{base::Msaa, Node, ResourceSlotInfo, ResourceSlots},
+    renderer::{
+        BindGroup, BufferId, BufferInfo, BufferUsage, RenderContext, RenderResourceBindings,
+        RenderResourceType, SamplerId,
+    },
+    shader::{Shader, ShaderStage, ShaderStages},
+    texture::{FilterMode, SamplerDescriptor, TextureFormat},
+};
+use bevy_asset::{Assets, Handle};
+use bevy_core::AsBytes;
+use bevy_ecs::{Resources, World};
+use bevy_type_registry::TypeUuid;
+use std::borrow::Cow;
+
+pub const COMPOSITE_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 11730285532094377542);
+
+/// Draws the `source` texture over the whole color attachment, scaling it to the attachment's size. This is used to
+/// show a pass that rendered at a different resolution than the window.
+#[derive(Debug)]
+pub struct CompositeNode {
+    descriptor: PassDescriptor,
+    inputs: Vec<ResourceSlotInfo>,
+    pipeline_specialization: PipelineSpecialization,
+    sampler: Option<SamplerId>,
+    vertex_buffer: Option<BufferId>,
+}
+
+impl CompositeNode {
+    pub const IN_SOURCE: &'static str = "source";
+    pub const IN_COLOR_ATTACHMENT: &'static str = "color_attachment";
+    pub const IN_COLOR_RESOLVE_TARGET: &'static str = "color_resolve_target";
+
+    pub fn new(msaa: &Msaa) -> Self {
+        let mut inputs = vec![
+            ResourceSlotInfo::new(CompositeNode::IN_SOURCE, RenderResourceType::Texture),
+            ResourceSlotInfo::new(
+                CompositeNode::IN_COLOR_ATTACHMENT,
+                RenderResourceType::Texture,
+            ),
+        ];
+        if msaa.samples > 1 {
+            inputs.push(ResourceSlotInfo::new(
+                CompositeNode::IN_COLOR_RESOLVE_TARGET,
+                RenderResourceType::Texture,
+            ));
+        }
+
+        CompositeNode {
+            descriptor: PassDescriptor {
+                color_attachments: vec![msaa.color_attachment_descriptor(
+                    TextureAttachment::Input(CompositeNode::IN_COLOR_ATTACHMENT.to_string()),
+                    TextureAttachment::Input(CompositeNode::IN_COLOR_RESOLVE_TARGET.to_string()),
+                    Operations {
+                        load: LoadOp::Clear(Default::default()),
+                        store: true,
+                    },
+                )],
+                depth_stencil_attachment: None,
+                sample_count: msaa.samples,
+            },
+            inputs,
+            pipeline_specialization: PipelineSpecialization {
+                sample_count: msaa.samples,
+                vertex_buffer_descriptor: VertexBufferDescriptor {
+                    name: Cow::Borrowed("Vertex_Position"),
+                    stride: VertexFormat::Float2.get_size(),
+                    step_mode: InputStepMode::Vertex,
+                    attributes: vec![VertexAttributeDescriptor {
+                        name: Cow::Borrowed("Vertex_Position"),
+                        offset: 0,
+                        format: VertexFormat::Float2,
+                        shader_location: 0,
+                    }],
+                },
+                ..Default::default()
+            },
+            sampler: None,
+            vertex_buffer: None,
+        }
+    }
+}
+
+impl Node for CompositeNode {
+    fn input(&self) -> &[ResourceSlotInfo] {
+        &self.inputs
+    }
+
+    fn update(
+        &mut self,
+        _world: &World,
+        resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        const SOURCE: usize = 0;
+        const COLOR_ATTACHMENT: usize = 1;
+        const COLOR_RESOLVE_TARGET: usize = 2;
+        let source = input.get(SOURCE).unwrap().get_texture().unwrap();
+        let color_attachment = &mut self.descriptor.color_attachments[0];
+        color_attachment.attachment =
+            TextureAttachment::Id(input.get(COLOR_ATTACHMENT).unwrap().get_texture().unwrap());
+        if color_attachment.resolve_target.is_some() {
+            color_attachment.resolve_target = Some(TextureAttachment::Id(
+                input
+                    .get(COLOR_RESOLVE_TARGET)
+                    .unwrap()
+                    .get_texture()
+                    .unwrap(),
+            ));
+        }
+
+        let render_resource_context = render_context.resources();
+        // looked up every frame, because the pipeline is compiled again when its shaders are modified
+        let pipeline = {
+            let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+            let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+            let mut pipeline_compiler = resources.get_mut::<PipelineCompiler>().unwrap();
+            pipeline_compiler
+                .compile_pipeline(
+                    render_resource_context,
+                    &mut pipelines,
+                    &mut shaders,
+                    &COMPOSITE_PIPELINE_HANDLE,
+                    &self.pipeline_specialization,
+                )
+                .ok()
+        };
+        if self.vertex_buffer.is_none() {
+            // linear filtering smooths the source when it is upscaled
+            self.sampler = Some(render_resource_context.create_sampler(&SamplerDescriptor {
+                mag_filter: FilterMode::Linear,
+                ..Default::default()
+            }));
+            // a single triangle that covers the whole screen
+            let positions: [[f32; 2]; 3] = [[-1.0, -1.0], [3.0, -1.0], [-1.0, 3.0]];
+            self.vertex_buffer = Some(render_resource_context.create_buffer_with_data(
+                BufferInfo {
+                    buffer_usage: BufferUsage::VERTEX,
+                    ..Default::default()
+                },
+                positions.as_bytes(),
+            ));
+        }
+
+        // the compile error was logged by the PipelineCompiler
+        let pipeline = if let Some(pipeline) = pipeline.as_ref() {
+            pipeline
+        } else {
+            return;
+        };
+        let pipelines = resources.get::<Assets<PipelineDescriptor>>().unwrap();
+        let bind_group_descriptor = pipelines
+            .get(pipeline)
+            .and_then(|descriptor| descriptor.get_layout())
+            .and_then(|layout| layout.get_bind_group(0))
+            .unwrap();
+        let bind_group = BindGroup::build()
+            .add_texture(0, source)
+            .add_sampler(1, self.sampler.unwrap())
+            .finish();
+        render_resource_context.create_bind_group(bind_group_descriptor.id, &bind_group);
+
+        let vertex_buffer = self.vertex_buffer.unwrap();
+        let render_resource_bindings = resources.get::<RenderResourceBindings>().unwrap();
+        render_context.begin_pass(
+            &self.descriptor,
+            &render_resource_bindings,
+            &mut |render_pass| {
+                render_pass.set_pipeline(pipeline);
+                render_pass.set_vertex_buffer(0, vertex_buffer, 0);
+                render_pass.set_bind_group(0, bind_group_descriptor.id, bind_group.id, None);
+                render_pass.draw(0..3, 0..1);
+            },
+        );
+    }
+}
+
+pub(crate) fn build_composite_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    PipelineDescriptor {
+        rasterization_state: Some(RasterizationStateDescriptor {
+            front_face: FrontFace::Ccw,
+            cull_mode: CullMode::None,
+            depth_bias: 0,
+            depth_bias_slope_scale: 0.0,
+            depth_bias_clamp: 0.0,
+            clamp_depth: false,
+        }),
+        color_states: vec![ColorStateDescriptor {
+            format: TextureFormat::default(),
+            color_blend: BlendDescriptor::REPLACE,
+            alpha_blend: BlendDescriptor::REPLACE,
+            write_mask: ColorWrite::ALL,
+        }],
+        ..PipelineDescriptor::new(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                include_str!("composite.vert"),
+            )),
+            fragment: Some(shaders.add(Shader::from_glsl(
+                ShaderStage::Fragment,
+                include_str!("composite.frag"),
+            ))),
+        })
+    }
+}
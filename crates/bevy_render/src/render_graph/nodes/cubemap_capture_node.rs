@@ -0,0 +1,243 @@
+use super::PassNode;
+use crate::{
+    camera::{ActiveCameras, CubemapCaptureMode, VisibleEntities},
+    pass::{
+        LoadOp, Operations, PassDescriptor, RenderPassDepthStencilAttachmentDescriptor,
+        TextureAttachment,
+    },
+    render_graph::{
+        base::{MainPass, Msaa},
+        Node, ResourceSlots,
+    },
+    renderer::{RenderContext, RenderResourceContext, TextureId},
+    texture::{
+        Extent3d, Texture, TextureDescriptor, TextureFormat, TextureUsage, TEXTURE_ASSET_INDEX,
+    },
+    Color,
+};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Resources, World};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Renders the entities of the main pass into the six faces of a cubemap texture, one camera per face. Each face is
+/// rendered to a texture of its own and then copied to its layer of the cubemap. Added to the render graph by
+/// [cubemap_capture_system](crate::camera::cubemap_capture_system).
+pub struct CubemapCaptureNode {
+    texture: Handle<Texture>,
+    mode: CubemapCaptureMode,
+    captured: Arc<AtomicBool>,
+    camera_names: Vec<String>,
+    msaa: Msaa,
+    targets: Option<FaceTargets>,
+}
+
+/// The textures a face is rendered to, and the passes that render each face
+struct FaceTargets {
+    size: u32,
+    textures: Vec<TextureId>,
+    passes: Vec<PassNode<&'static MainPass>>,
+}
+
+impl CubemapCaptureNode {
+    pub(crate) fn new(
+        texture: Handle<Texture>,
+        mode: CubemapCaptureMode,
+        captured: Arc<AtomicBool>,
+        camera_names: Vec<String>,
+        msaa: &Msaa,
+    ) -> Self {
+        CubemapCaptureNode {
+            texture,
+            mode,
+            captured,
+            camera_names,
+            msaa: Msaa {
+                samples: msaa.samples,
+            },
+            targets: None,
+        }
+    }
+
+    /// Renders the given capture from now on. A new texture is rendered even if the mode is
+    /// [CubemapCaptureMode::Once] and the old one was captured.
+    pub(crate) fn set_capture(
+        &mut self,
+        texture: Handle<Texture>,
+        mode: CubemapCaptureMode,
+        captured: Arc<AtomicBool>,
+    ) {
+        if texture != self.texture {
+            captured.store(false, Ordering::Relaxed);
+        }
+        self.texture = texture;
+        self.mode = mode;
+        self.captured = captured;
+    }
+
+    /// Removes the textures the faces are rendered to. They are created again when the node renders.
+    pub(crate) fn release_targets(&mut self, render_resource_context: &dyn RenderResourceContext) {
+        if let Some(targets) = self.targets.take() {
+            for texture in targets.textures {
+                render_resource_context.remove_texture(texture);
+            }
+        }
+    }
+
+    fn create_targets(
+        &self,
+        render_resource_context: &dyn RenderResourceContext,
+        size: u32,
+    ) -> FaceTargets {
+        let descriptor = TextureDescriptor {
+            size: Extent3d {
+                width: size,
+                height: size,
+                depth: 1,
+            },
+            // pipelines are specialized for the window's format and sample count, so faces use the same
+            format: TextureFormat::default(),
+            usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::COPY_SRC,
+            ..Default::default()
+        };
+        let face = render_resource_context.create_texture(descriptor);
+        let depth = render_resource_context.create_texture(TextureDescriptor {
+            format: TextureFormat::Depth32Float,
+            usage: TextureUsage::OUTPUT_ATTACHMENT,
+            sample_count: self.msaa.samples,
+            ..descriptor
+        });
+        let mut textures = vec![face, depth];
+        let color_attachment = if self.msaa.samples > 1 {
+            let sampled = render_resource_context.create_texture(TextureDescriptor {
+                usage: TextureUsage::OUTPUT_ATTACHMENT,
+                sample_count: self.msaa.samples,
+                ..descriptor
+            });
+            textures.push(sampled);
+            sampled
+        } else {
+            face
+        };
+
+        let passes = self
+            .camera_names
+            .iter()
+            .map(|camera_name| {
+                let mut pass = PassNode::<&MainPass>::new(PassDescriptor {
+                    color_attachments: vec![self.msaa.color_attachment_descriptor(
+                        TextureAttachment::Id(color_attachment),
+                        TextureAttachment::Id(face),
+                        Operations {
+                            load: LoadOp::Clear(Color::rgb(0.1, 0.1, 0.1)),
+                            store: true,
+                        },
+                    )],
+                    depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                        attachment: TextureAttachment::Id(depth),
+                        depth_ops: Some(Operations {
+                            load: LoadOp::Clear(1.0),
+                            store: true,
+                        }),
+                        stencil_ops: None,
+                    }),
+                    sample_count: self.msaa.samples,
+                });
+                pass.use_default_clear_color(0);
+                pass.add_camera(camera_name);
+                pass
+            })
+            .collect();
+
+        FaceTargets {
+            size,
+            textures,
+            passes,
+        }
+    }
+}
+
+impl Node for CubemapCaptureNode {
+    fn update(
+        &mut self,
+        world: &World,
+        resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        if self.mode == CubemapCaptureMode::Once && self.captured.load(Ordering::Relaxed) {
+            return;
+        }
+
+        // the cameras are set up a frame after the capture is added, and are gone once it is despawned
+        let active_cameras = resources.get::<ActiveCameras>().unwrap();
+        let cameras_ready = self.camera_names.iter().all(|camera_name| {
+            active_cameras
+                .get(camera_name)
+                .map_or(false, |camera| world.get::<VisibleEntities>(camera).is_ok())
+        });
+        if !cameras_ready {
+            return;
+        }
+
+        let textures = resources.get::<Assets<Texture>>().unwrap();
+        let texture = if let Some(texture) = textures.get(&self.texture) {
+            texture
+        } else {
+            return;
+        };
+        if !texture.cubemap || texture.format != TextureFormat::default() {
+            log::warn!(
+                "Cubemap captures need a cubemap texture with the format {:?}",
+                TextureFormat::default()
+            );
+            return;
+        }
+        let cubemap = if let Some(cubemap) = render_context
+            .resources()
+            .get_asset_resource(&self.texture, TEXTURE_ASSET_INDEX)
+            .and_then(|resource| resource.get_texture())
+        {
+            cubemap
+        } else {
+            return;
+        };
+
+        let size = texture.size.x() as u32;
+        if self.targets.as_ref().map(|targets| targets.size) != Some(size) {
+            let render_resource_context = render_context.resources();
+            self.release_targets(render_resource_context);
+            self.targets = Some(self.create_targets(render_resource_context, size));
+        }
+
+        let targets = self.targets.as_mut().unwrap();
+        let face = targets.textures[0];
+        let no_slots = ResourceSlots::default();
+        for (layer, pass) in targets.passes.iter_mut().enumerate() {
+            pass.update(
+                world,
+                resources,
+                render_context,
+                &no_slots,
+                &mut ResourceSlots::default(),
+            );
+            render_context.copy_texture_to_texture(
+                face,
+                [0, 0, 0],
+                0,
+                cubemap,
+                [0, 0, layer as u32],
+                0,
+                Extent3d {
+                    width: size,
+                    height: size,
+                    depth: 1,
+                },
+            );
+        }
+        self.captured.store(true, Ordering::Relaxed);
+    }
+}
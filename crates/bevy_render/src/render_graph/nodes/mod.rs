@@ -1,15 +1,23 @@
 mod camera_node;
+mod composite_node;
+mod cubemap_capture_node;
 mod pass_node;
 mod render_resources_node;
+mod scene_depth_node;
 mod shared_buffers_node;
 mod texture_copy_node;
+mod transient_texture_node;
 mod window_swapchain_node;
 mod window_texture_node;
 
 pub use camera_node::*;
+pub use composite_node::*;
+pub use cubemap_capture_node::*;
 pub use pass_node::*;
 pub use render_resources_node::*;
+pub use scene_depth_node::*;
 pub use shared_buffers_node::*;
 pub use texture_copy_node::*;
+pub use transient_texture_node::*;
 pub use window_swapchain_node::*;
 pub use window_texture_node::*;
@@ -1,6 +1,6 @@
 use crate::{
-    camera::{ActiveCameras, VisibleEntities},
-    draw::{Draw, RenderCommand},
+    camera::{ActiveCameras, Camera, VisibleEntities},
+    draw::{Draw, RenderCommand, Visible},
     pass::{ClearColor, LoadOp, PassDescriptor, TextureAttachment},
     pipeline::{
         BindGroupDescriptor, BindType, BindingDescriptor, BindingShaderStage, PipelineDescriptor,
@@ -13,6 +13,7 @@ use crate::{
 };
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::{HecsQuery, ReadOnlyFetch, Resources, World};
+use bevy_window::Windows;
 use std::{fmt, marker::PhantomData, ops::Deref};
 
 #[derive(Debug)]
@@ -137,6 +138,15 @@ impl<Q: HecsQuery> PassNode<Q> {
     pub fn use_default_clear_color(&mut self, color_attachment_index: usize) {
         self.default_clear_color_inputs.push(color_attachment_index);
     }
+
+    /// Keeps what an earlier node drew to the color attachment instead of clearing it, for example a sky
+    pub fn load_color_attachment(&mut self, color_attachment_index: usize) {
+        self.default_clear_color_inputs
+            .retain(|index| *index != color_attachment_index);
+        self.descriptor.color_attachments[color_attachment_index]
+            .ops
+            .load = LoadOp::Load;
+    }
 }
 
 impl<Q: HecsQuery + Send + Sync + 'static> Node for PassNode<Q>
@@ -158,6 +168,7 @@ where
         let render_resource_bindings = resources.get::<RenderResourceBindings>().unwrap();
         let pipelines = resources.get::<Assets<PipelineDescriptor>>().unwrap();
         let active_cameras = resources.get::<ActiveCameras>().unwrap();
+        let windows = resources.get::<Windows>();
 
         for (i, color_attachment) in self.descriptor.color_attachments.iter_mut().enumerate() {
             if self.default_clear_color_inputs.contains(&i) {
@@ -207,6 +218,7 @@ where
             &self.descriptor,
             &render_resource_bindings,
             &mut |render_pass| {
+                let mut has_scissor_rect = false;
                 for camera_info in self.cameras.iter() {
                     let camera_bind_group_id= if let Some(bind_group_id) = camera_info.bind_group_id {
                         bind_group_id
@@ -214,13 +226,28 @@ where
                         continue;
                     };
 
-                    // get an ordered list of entities visible to the camera
-                    let visible_entities = if let Some(camera_entity) = active_cameras.get(&camera_info.name) {
-                        world.get::<VisibleEntities>(camera_entity).unwrap()
+                    let camera_entity = if let Some(camera_entity) = active_cameras.get(&camera_info.name) {
+                        camera_entity
                     } else {
                         continue;
                     };
 
+                    // limit drawing to the camera's viewport, or undo the limit of the previous camera
+                    if let Ok(camera) = world.get::<Camera>(camera_entity) {
+                        if let Some(viewport) = camera.viewport {
+                            render_pass.set_scissor_rect(viewport.x, viewport.y, viewport.width, viewport.height);
+                            has_scissor_rect = true;
+                        } else if has_scissor_rect {
+                            if let Some(window) = windows.as_ref().and_then(|windows| windows.get(camera.window)) {
+                                render_pass.set_scissor_rect(0, 0, window.physical_width(), window.physical_height());
+                                has_scissor_rect = false;
+                            }
+                        }
+                    }
+
+                    // get an ordered list of entities visible to the camera
+                    let visible_entities = world.get::<VisibleEntities>(camera_entity).unwrap();
+
                     // attempt to draw each visible entity
                     let mut draw_state = DrawState::default();
                     for visible_entity in visible_entities.iter() {
@@ -235,7 +262,10 @@ where
                             continue;
                         };
 
-                        if !draw.is_visible {
+                        let is_drawn = world
+                            .get::<Visible>(visible_entity.entity)
+                            .map_or(true, |visible| visible.is_drawn());
+                        if !is_drawn {
                             continue;
                         }
 
@@ -292,6 +322,14 @@ where
                                     render_pass.set_vertex_buffer(*slot, *buffer, *offset);
                                     draw_state.set_vertex_buffer(*slot, *buffer);
                                 }
+                                RenderCommand::SetScissorRect {
+                                    x,
+                                    y,
+                                    width,
+                                    height,
+                                } => {
+                                    render_pass.set_scissor_rect(*x, *y, *width, *height);
+                                }
                                 RenderCommand::SetIndexBuffer { buffer, offset } => {
                                     render_pass.set_index_buffer(*buffer, *offset);
                                     draw_state.set_index_buffer(*buffer)
@@ -1,5 +1,5 @@
 use crate::{
-    draw::Draw,
+    draw::Visible,
     pipeline::RenderPipelines,
     render_graph::{CommandQueue, Node, ResourceSlots, SystemNode},
     renderer::{
@@ -19,19 +19,29 @@ use std::{hash::Hash, marker::PhantomData, ops::DerefMut};
 
 pub const BIND_BUFFER_ALIGNMENT: usize = 256;
 
+/// Buffers can't be empty, but storage buffers can hold empty arrays
+const MIN_BUFFER_SIZE: usize = 4;
+
 fn get_aligned_dynamic_uniform_size(data_size: usize) -> usize {
     BIND_BUFFER_ALIGNMENT * ((data_size as f32 / BIND_BUFFER_ALIGNMENT as f32).ceil() as usize)
 }
 
+/// Whether the render resource at `index` is bound as a storage buffer instead of a uniform buffer
+fn is_storage_buffer<T: RenderResources>(render_resources: &T, index: usize) -> bool {
+    render_resources
+        .get_render_resource_hints(index)
+        .map_or(false, |hints| hints.contains(RenderResourceHints::BUFFER))
+}
+
 #[derive(Debug)]
 struct QueuedBufferWrite {
     buffer: BufferId,
-    target_offset: usize,
     source_offset: usize,
     size: usize,
 }
 
-/// Used to track items in a gpu buffer in an "array" style
+/// Used to track items in a gpu buffer in an "array" style. Items are bound with dynamic offsets into the buffer, so
+/// all items share one buffer and bind group, and the whole array is copied from the staging buffer at once.
 #[derive(Debug)]
 struct BufferArray<I> {
     item_size: usize,
@@ -41,6 +51,8 @@ struct BufferArray<I> {
     buffer: Option<BufferId>,
     free_indices: Vec<usize>,
     indices: HashMap<I, usize>,
+    /// Where the array starts in the staging buffer. Items are staged at their offset in the array.
+    staging_offset: usize,
 }
 
 impl<I: Hash + Eq> BufferArray<I> {
@@ -57,9 +69,18 @@ impl<I: Hash + Eq> BufferArray<I> {
             buffer: None,
             free_indices: Vec::new(),
             indices: HashMap::default(),
+            staging_offset: 0,
         }
     }
 
+    /// The size of the array up to its last used item
+    fn staging_size(&self) -> usize {
+        self.indices
+            .values()
+            .max()
+            .map_or(0, |index| (index + 1) * self.item_size)
+    }
+
     fn get_or_assign_index(&mut self, id: I) -> usize {
         if let Some(index) = self.indices.get(&id) {
             *index
@@ -159,12 +180,18 @@ where
     I: Hash + Eq + Copy,
     T: renderer::RenderResources,
 {
-    /// Initialize this UniformBufferArrays using information from a RenderResources value.
-    fn initialize(&mut self, render_resources: &T) {
+    /// Initialize this UniformBufferArrays using information from a RenderResources value. Buffers are only packed
+    /// into arrays with `dynamic_uniforms`, otherwise every item gets its own buffer.
+    fn initialize(&mut self, render_resources: &T, dynamic_uniforms: bool) {
         if self.buffer_arrays.len() != render_resources.render_resources_len() {
             let mut buffer_arrays = Vec::with_capacity(render_resources.render_resources_len());
-            for render_resource in render_resources.iter() {
-                if let Some(RenderResourceType::Buffer) = render_resource.resource_type() {
+            for (i, render_resource) in render_resources.iter().enumerate() {
+                // storage buffers can change size, so they get their own buffer instead of a slot in an array
+                if let (Some(RenderResourceType::Buffer), true, false) = (
+                    render_resource.resource_type(),
+                    dynamic_uniforms,
+                    is_storage_buffer(render_resources, i),
+                ) {
                     let size = render_resource.buffer_byte_len().unwrap();
                     buffer_arrays.push(Some(BufferArray::new(size, 10, true)));
                 } else {
@@ -189,17 +216,20 @@ where
                 let size = render_resource.buffer_byte_len().unwrap();
                 if let Some(buffer_array) = &mut self.buffer_arrays[i] {
                     buffer_array.get_or_assign_index(id);
+                } else {
                     self.required_staging_buffer_size += size;
                 }
             }
         }
     }
 
-    /// Resize BufferArray buffers if they aren't large enough
+    /// Resize BufferArray buffers if they aren't large enough and prepare space for them in the staging buffer
     fn resize_buffer_arrays(&mut self, render_resource_context: &dyn RenderResourceContext) {
         for buffer_array in self.buffer_arrays.iter_mut() {
             if let Some(buffer_array) = buffer_array {
                 buffer_array.resize(render_resource_context);
+                buffer_array.staging_offset = self.required_staging_buffer_size;
+                self.required_staging_buffer_size += buffer_array.staging_size();
             }
         }
     }
@@ -212,10 +242,14 @@ where
                 render_resource_context.remove_buffer(staging_buffer);
             }
 
-            if self.required_staging_buffer_size > 0 {
+            // grow geometrically, so spawning entities one by one doesn't reallocate every frame
+            let size = self
+                .required_staging_buffer_size
+                .max(self.staging_buffer_size * 2);
+            if size > 0 {
                 let staging_buffer = render_resource_context.create_buffer(BufferInfo {
                     buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
-                    size: self.required_staging_buffer_size,
+                    size,
                     ..Default::default()
                 });
                 self.staging_buffer = Some(staging_buffer);
@@ -223,7 +257,7 @@ where
                 self.staging_buffer = None;
             }
 
-            self.staging_buffer_size = self.required_staging_buffer_size;
+            self.staging_buffer_size = size;
         }
     }
 
@@ -239,7 +273,6 @@ where
         &mut self,
         id: I,
         uniforms: &T,
-        dynamic_uniforms: bool,
         render_resource_context: &dyn RenderResourceContext,
         render_resource_bindings: &mut RenderResourceBindings,
         staging_buffer: &mut [u8],
@@ -249,69 +282,70 @@ where
                 Some(RenderResourceType::Buffer) => {
                     let size = render_resource.buffer_byte_len().unwrap();
                     let render_resource_name = uniforms.get_render_resource_name(i).unwrap();
-                    let buffer_array = self.buffer_arrays[i].as_mut().unwrap();
-                    let range = 0..size as u64;
-                    let (target_buffer, target_offset) = if dynamic_uniforms {
+                    if let Some(buffer_array) = &self.buffer_arrays[i] {
                         let binding = buffer_array.get_binding(id).unwrap();
                         let dynamic_index = if let RenderResourceBinding::Buffer {
                             dynamic_index: Some(dynamic_index),
                             ..
                         } = binding
                         {
-                            dynamic_index
+                            dynamic_index as usize
                         } else {
                             panic!("dynamic index should always be set");
                         };
                         render_resource_bindings.set(render_resource_name, binding);
-                        (buffer_array.buffer.unwrap(), dynamic_index)
-                    } else {
-                        let mut matching_buffer = None;
-                        if let Some(binding) = render_resource_bindings.get(render_resource_name) {
-                            let buffer_id = binding.get_buffer().unwrap();
-                            if let Some(BufferInfo {
-                                size: current_size, ..
-                            }) = render_resource_context.get_buffer_info(buffer_id)
-                            {
-                                if size == current_size {
-                                    matching_buffer = Some(buffer_id);
-                                } else {
-                                    render_resource_context.remove_buffer(buffer_id);
-                                }
+                        let offset = buffer_array.staging_offset + dynamic_index;
+                        render_resource
+                            .write_buffer_bytes(&mut staging_buffer[offset..(offset + size)]);
+                        continue;
+                    }
+
+                    let buffer_size = size.max(MIN_BUFFER_SIZE);
+                    let mut matching_buffer = None;
+                    if let Some(binding) = render_resource_bindings.get(render_resource_name) {
+                        let buffer_id = binding.get_buffer().unwrap();
+                        if let Some(BufferInfo {
+                            size: current_size, ..
+                        }) = render_resource_context.get_buffer_info(buffer_id)
+                        {
+                            if buffer_size == current_size {
+                                matching_buffer = Some(buffer_id);
+                            } else {
+                                render_resource_context.remove_buffer(buffer_id);
                             }
                         }
+                    }
 
-                        let resource = if let Some(matching_buffer) = matching_buffer {
-                            matching_buffer
+                    let target_buffer = if let Some(matching_buffer) = matching_buffer {
+                        matching_buffer
+                    } else {
+                        let usage = if is_storage_buffer(uniforms, i) {
+                            BufferUsage::STORAGE
                         } else {
-                            let mut usage = BufferUsage::UNIFORM;
-                            if let Some(render_resource_hints) =
-                                uniforms.get_render_resource_hints(i)
-                            {
-                                if render_resource_hints.contains(RenderResourceHints::BUFFER) {
-                                    usage = BufferUsage::STORAGE
-                                }
-                            }
-
-                            let buffer = render_resource_context.create_buffer(BufferInfo {
-                                size,
-                                buffer_usage: BufferUsage::COPY_DST | usage,
-                                ..Default::default()
-                            });
-
-                            render_resource_bindings.set(
-                                render_resource_name,
-                                RenderResourceBinding::Buffer {
-                                    buffer,
-                                    range,
-                                    dynamic_index: None,
-                                },
-                            );
-                            buffer
+                            BufferUsage::UNIFORM
                         };
 
-                        (resource, 0)
+                        let buffer = render_resource_context.create_buffer(BufferInfo {
+                            size: buffer_size,
+                            buffer_usage: BufferUsage::COPY_DST | usage,
+                            ..Default::default()
+                        });
+
+                        render_resource_bindings.set(
+                            render_resource_name,
+                            RenderResourceBinding::Buffer {
+                                buffer,
+                                range: 0..buffer_size as u64,
+                                dynamic_index: None,
+                            },
+                        );
+                        buffer
                     };
 
+                    if size == 0 {
+                        continue;
+                    }
+
                     render_resource.write_buffer_bytes(
                         &mut staging_buffer[self.current_staging_buffer_offset
                             ..(self.current_staging_buffer_offset + size)],
@@ -319,7 +353,6 @@ where
 
                     self.queued_buffer_writes.push(QueuedBufferWrite {
                         buffer: target_buffer,
-                        target_offset: target_offset as usize,
                         source_offset: self.current_staging_buffer_offset,
                         size,
                     });
@@ -342,10 +375,25 @@ where
                 staging_buffer,
                 queued_buffer_write.source_offset as u64,
                 queued_buffer_write.buffer,
-                queued_buffer_write.target_offset as u64,
+                0,
                 queued_buffer_write.size as u64,
             )
         }
+
+        for buffer_array in self.buffer_arrays.iter() {
+            if let Some(buffer_array) = buffer_array {
+                let size = buffer_array.staging_size();
+                if size > 0 {
+                    command_queue.copy_buffer_to_buffer(
+                        staging_buffer,
+                        buffer_array.staging_offset as u64,
+                        buffer_array.buffer.unwrap(),
+                        0,
+                        size as u64,
+                    );
+                }
+            }
+        }
     }
 }
 
@@ -426,7 +474,7 @@ impl<I, T: RenderResources> Default for RenderResourcesNodeState<I, T> {
 fn render_resources_node_system<T: RenderResources>(
     mut state: Local<RenderResourcesNodeState<Entity, T>>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
-    mut query: Query<(Entity, &T, &Draw, &mut RenderPipelines)>,
+    mut query: Query<(Entity, &T, Option<&Visible>, &mut RenderPipelines)>,
 ) {
     let state = state.deref_mut();
     let uniform_buffer_arrays = &mut state.uniform_buffer_arrays;
@@ -434,15 +482,15 @@ fn render_resources_node_system<T: RenderResources>(
     uniform_buffer_arrays.begin_update();
     // initialize uniform buffer arrays using the first RenderResources
     if let Some((_, first, _, _)) = query.iter_mut().next() {
-        uniform_buffer_arrays.initialize(first);
+        uniform_buffer_arrays.initialize(first, state.dynamic_uniforms);
     }
 
     for entity in query.removed::<T>() {
         uniform_buffer_arrays.remove_bindings(*entity);
     }
 
-    for (entity, uniforms, draw, mut render_pipelines) in query.iter_mut() {
-        if !draw.is_visible {
+    for (entity, uniforms, visible, mut render_pipelines) in query.iter_mut() {
+        if !visible.map_or(true, |visible| visible.is_drawn()) {
             continue;
         }
 
@@ -463,15 +511,14 @@ fn render_resources_node_system<T: RenderResources>(
             staging_buffer,
             0..state.uniform_buffer_arrays.staging_buffer_size as u64,
             &mut |mut staging_buffer, _render_resource_context| {
-                for (entity, uniforms, draw, mut render_pipelines) in query.iter_mut() {
-                    if !draw.is_visible {
+                for (entity, uniforms, visible, mut render_pipelines) in query.iter_mut() {
+                    if !visible.map_or(true, |visible| visible.is_drawn()) {
                         continue;
                     }
 
                     state.uniform_buffer_arrays.write_uniform_buffers(
                         entity,
                         &uniforms,
-                        state.dynamic_uniforms,
                         render_resource_context,
                         &mut render_pipelines.bindings,
                         &mut staging_buffer,
@@ -487,15 +534,14 @@ fn render_resources_node_system<T: RenderResources>(
     } else {
         // TODO: can we just remove this?
         let mut staging_buffer: [u8; 0] = [];
-        for (entity, uniforms, draw, mut render_pipelines) in query.iter_mut() {
-            if !draw.is_visible {
+        for (entity, uniforms, visible, mut render_pipelines) in query.iter_mut() {
+            if !visible.map_or(true, |visible| visible.is_drawn()) {
                 continue;
             }
 
             state.uniform_buffer_arrays.write_uniform_buffers(
                 entity,
                 &uniforms,
-                state.dynamic_uniforms,
                 render_resource_context,
                 &mut render_pipelines.bindings,
                 &mut staging_buffer,
@@ -569,7 +615,7 @@ fn asset_render_resources_node_system<T: RenderResources + Asset>(
     assets: Res<Assets<T>>,
     mut asset_render_resource_bindings: ResMut<AssetRenderResourceBindings>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
-    mut query: Query<(&Handle<T>, &Draw, &mut RenderPipelines)>,
+    mut query: Query<(&Handle<T>, Option<&Visible>, &mut RenderPipelines)>,
 ) {
     let state = state.deref_mut();
     let uniform_buffer_arrays = &mut state.uniform_buffer_arrays;
@@ -581,7 +627,7 @@ fn asset_render_resources_node_system<T: RenderResources + Asset>(
     // initialize uniform buffer arrays using the first RenderResources
     if let Some(first_handle) = modified_assets.get(0) {
         let asset = assets.get(*first_handle).expect(EXPECT_ASSET_MESSAGE);
-        uniform_buffer_arrays.initialize(asset);
+        uniform_buffer_arrays.initialize(asset, state.dynamic_uniforms);
     }
 
     for asset_handle in modified_assets.iter() {
@@ -609,7 +655,6 @@ fn asset_render_resources_node_system<T: RenderResources + Asset>(
                     state.uniform_buffer_arrays.write_uniform_buffers(
                         *asset_handle,
                         &asset,
-                        state.dynamic_uniforms,
                         render_resource_context,
                         &mut render_resource_bindings,
                         &mut staging_buffer,
@@ -632,7 +677,6 @@ fn asset_render_resources_node_system<T: RenderResources + Asset>(
             state.uniform_buffer_arrays.write_uniform_buffers(
                 *asset_handle,
                 &asset,
-                state.dynamic_uniforms,
                 render_resource_context,
                 &mut render_resource_bindings,
                 &mut staging_buffer,
@@ -640,8 +684,8 @@ fn asset_render_resources_node_system<T: RenderResources + Asset>(
         }
     }
 
-    for (asset_handle, draw, mut render_pipelines) in query.iter_mut() {
-        if !draw.is_visible {
+    for (asset_handle, visible, mut render_pipelines) in query.iter_mut() {
+        if !visible.map_or(true, |visible| visible.is_drawn()) {
             continue;
         }
         if let Some(asset_bindings) = asset_render_resource_bindings.get(asset_handle) {
@@ -683,3 +727,71 @@ fn setup_uniform_texture_resources<T>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{UniformBufferArrays, BIND_BUFFER_ALIGNMENT};
+    use crate::renderer::{
+        HeadlessRenderResourceContext, RenderResourceBinding, RenderResourceBindings,
+    };
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::GlobalTransform;
+
+    #[test]
+    fn dynamic_uniforms_share_one_buffer() {
+        let render_resource_context = HeadlessRenderResourceContext::default();
+        let transforms = [
+            GlobalTransform::from_translation(Vec3::new(1.0, 0.0, 0.0)),
+            GlobalTransform::from_translation(Vec3::new(2.0, 0.0, 0.0)),
+            GlobalTransform::from_translation(Vec3::new(3.0, 0.0, 0.0)),
+        ];
+        let mut arrays = UniformBufferArrays::<usize, GlobalTransform>::default();
+        arrays.begin_update();
+        arrays.initialize(&transforms[0], true);
+        for (id, transform) in transforms.iter().enumerate() {
+            arrays.prepare_uniform_buffers(id, transform);
+        }
+        arrays.resize_buffer_arrays(&render_resource_context);
+        arrays.resize_staging_buffer(&render_resource_context);
+        assert_eq!(
+            arrays.required_staging_buffer_size,
+            3 * BIND_BUFFER_ALIGNMENT
+        );
+
+        let mut staging_buffer = vec![0; arrays.staging_buffer_size];
+        let mut bindings = Vec::new();
+        for (id, transform) in transforms.iter().enumerate() {
+            let mut render_resource_bindings = RenderResourceBindings::default();
+            arrays.write_uniform_buffers(
+                id,
+                transform,
+                &render_resource_context,
+                &mut render_resource_bindings,
+                &mut staging_buffer,
+            );
+            bindings.push(render_resource_bindings.get("Transform").unwrap().clone());
+        }
+
+        // every transform is a slot in the same buffer, written in place in the staging buffer
+        assert!(arrays.queued_buffer_writes.is_empty());
+        let buffer = bindings[0].get_buffer().unwrap();
+        for (index, binding) in bindings.iter().enumerate() {
+            if let RenderResourceBinding::Buffer {
+                buffer: binding_buffer,
+                dynamic_index: Some(dynamic_index),
+                ..
+            } = binding
+            {
+                assert_eq!(*binding_buffer, buffer);
+                let offset = *dynamic_index as usize;
+                assert_eq!(offset, index * BIND_BUFFER_ALIGNMENT);
+                // the translation is the 13th float of the matrix
+                let mut x = [0; 4];
+                x.copy_from_slice(&staging_buffer[offset + 48..offset + 52]);
+                assert_eq!(f32::from_ne_bytes(x), index as f32 + 1.0);
+            } else {
+                panic!("expected a dynamic buffer binding");
+            }
+        }
+    }
+}
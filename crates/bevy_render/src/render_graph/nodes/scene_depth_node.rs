@@ -0,0 +1,260 @@
+use super::scaled_size;
+use crate::{
+    pass::{
+        LoadOp, Operations, PassDescriptor, RenderPassDepthStencilAttachmentDescriptor,
+        TextureAttachment,
+    },
+    pipeline::{
+        CompareFunction, CullMode, DepthStencilStateDescriptor, FrontFace, InputStepMode,
+        PipelineCompiler, PipelineDescriptor, PipelineSpecialization, RasterizationStateDescriptor,
+        StencilStateDescriptor, StencilStateFaceDescriptor, VertexAttributeDescriptor,
+        VertexBufferDescriptor, VertexFormat,
+    },
+    render_graph::{base::Msaa, Node, ResourceSlotInfo, ResourceSlots},
+    renderer::{
+        BindGroup, BufferId, BufferInfo, BufferUsage, RenderContext, RenderResourceBinding,
+        RenderResourceBindings, RenderResourceType, SamplerId,
+    },
+    shader::{Shader, ShaderStage, ShaderStages},
+    texture::{Extent3d, SamplerDescriptor, TextureFormat},
+};
+use bevy_asset::{Assets, Handle};
+use bevy_core::AsBytes;
+use bevy_ecs::{Resources, World};
+use bevy_type_registry::TypeUuid;
+use bevy_window::{WindowId, Windows};
+use std::borrow::Cow;
+
+pub const SCENE_DEPTH_RESOLVE_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 6216539203117349817);
+
+/// Copies the `depth` texture of a pass to the single sampled `scene_depth` texture, and binds the copy to the
+/// "SceneDepth" texture and "SceneDepth_sampler" sampler of the global [RenderResourceBindings]. Passes that run after
+/// it can sample the depth of everything the first pass drew, for example to fade transparent materials near opaque
+/// geometry. Multisampled depth is resolved by drawing its first sample into the copy.
+#[derive(Debug)]
+pub struct SceneDepthNode {
+    window_id: WindowId,
+    scale: f32,
+    descriptor: PassDescriptor,
+    pipeline_specialization: Option<PipelineSpecialization>,
+    sampler: Option<SamplerId>,
+    vertex_buffer: Option<BufferId>,
+}
+
+impl SceneDepthNode {
+    pub const IN_DEPTH: &'static str = "depth";
+    pub const IN_SCENE_DEPTH: &'static str = "scene_depth";
+
+    pub fn new(window_id: WindowId, msaa: &Msaa) -> Self {
+        SceneDepthNode {
+            window_id,
+            scale: 1.0,
+            descriptor: PassDescriptor {
+                color_attachments: Vec::new(),
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: TextureAttachment::Input(
+                        SceneDepthNode::IN_SCENE_DEPTH.to_string(),
+                    ),
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(1.0),
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+                sample_count: 1,
+            },
+            pipeline_specialization: if msaa.samples > 1 {
+                Some(PipelineSpecialization {
+                    vertex_buffer_descriptor: VertexBufferDescriptor {
+                        name: Cow::Borrowed("Vertex_Position"),
+                        stride: VertexFormat::Float2.get_size(),
+                        step_mode: InputStepMode::Vertex,
+                        attributes: vec![VertexAttributeDescriptor {
+                            name: Cow::Borrowed("Vertex_Position"),
+                            offset: 0,
+                            format: VertexFormat::Float2,
+                            shader_location: 0,
+                        }],
+                    },
+                    ..Default::default()
+                })
+            } else {
+                None
+            },
+            sampler: None,
+            vertex_buffer: None,
+        }
+    }
+
+    /// The scale of the window's physical size that the `depth` and `scene_depth` textures were created with, see
+    /// [WindowTextureNode::with_scale](super::WindowTextureNode::with_scale)
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+impl Node for SceneDepthNode {
+    fn input(&self) -> &[ResourceSlotInfo] {
+        static INPUT: &[ResourceSlotInfo] = &[
+            ResourceSlotInfo {
+                name: Cow::Borrowed(SceneDepthNode::IN_DEPTH),
+                resource_type: RenderResourceType::Texture,
+            },
+            ResourceSlotInfo {
+                name: Cow::Borrowed(SceneDepthNode::IN_SCENE_DEPTH),
+                resource_type: RenderResourceType::Texture,
+            },
+        ];
+        INPUT
+    }
+
+    fn update(
+        &mut self,
+        _world: &World,
+        resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        const DEPTH: usize = 0;
+        const SCENE_DEPTH: usize = 1;
+        let depth = input.get(DEPTH).unwrap().get_texture().unwrap();
+        let scene_depth = input.get(SCENE_DEPTH).unwrap().get_texture().unwrap();
+
+        let render_resource_context = render_context.resources();
+        let sampler = *self.sampler.get_or_insert_with(|| {
+            render_resource_context.create_sampler(&SamplerDescriptor::default())
+        });
+        {
+            let mut render_resource_bindings =
+                resources.get_mut::<RenderResourceBindings>().unwrap();
+            render_resource_bindings.set("SceneDepth", RenderResourceBinding::Texture(scene_depth));
+            render_resource_bindings.set(
+                "SceneDepth_sampler",
+                RenderResourceBinding::Sampler(sampler),
+            );
+        }
+
+        if self.pipeline_specialization.is_none() {
+            let windows = resources.get::<Windows>().unwrap();
+            let window = if let Some(window) = windows.get(self.window_id) {
+                window
+            } else {
+                return;
+            };
+            render_context.copy_texture_to_texture(
+                depth,
+                [0, 0, 0],
+                0,
+                scene_depth,
+                [0, 0, 0],
+                0,
+                Extent3d {
+                    width: scaled_size(window.physical_width(), self.scale),
+                    height: scaled_size(window.physical_height(), self.scale),
+                    depth: 1,
+                },
+            );
+            return;
+        }
+
+        // looked up every frame, because the pipeline is compiled again when its shaders are modified
+        let pipeline = {
+            let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+            let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+            let mut pipeline_compiler = resources.get_mut::<PipelineCompiler>().unwrap();
+            pipeline_compiler
+                .compile_pipeline(
+                    render_resource_context,
+                    &mut pipelines,
+                    &mut shaders,
+                    &SCENE_DEPTH_RESOLVE_PIPELINE_HANDLE,
+                    self.pipeline_specialization.as_ref().unwrap(),
+                )
+                .ok()
+        };
+        if self.vertex_buffer.is_none() {
+            // a single triangle that covers the whole screen
+            let positions: [[f32; 2]; 3] = [[-1.0, -1.0], [3.0, -1.0], [-1.0, 3.0]];
+            self.vertex_buffer = Some(render_resource_context.create_buffer_with_data(
+                BufferInfo {
+                    buffer_usage: BufferUsage::VERTEX,
+                    ..Default::default()
+                },
+                positions.as_bytes(),
+            ));
+        }
+
+        // the compile error was logged by the PipelineCompiler
+        let pipeline = if let Some(pipeline) = pipeline.as_ref() {
+            pipeline
+        } else {
+            return;
+        };
+        let pipelines = resources.get::<Assets<PipelineDescriptor>>().unwrap();
+        let bind_group_descriptor = pipelines
+            .get(pipeline)
+            .and_then(|descriptor| descriptor.get_layout())
+            .and_then(|layout| layout.get_bind_group(0))
+            .unwrap();
+        let bind_group = BindGroup::build()
+            .add_texture(0, depth)
+            .add_sampler(1, sampler)
+            .finish();
+        render_resource_context.create_bind_group(bind_group_descriptor.id, &bind_group);
+
+        if let Some(depth_stencil_attachment) = self.descriptor.depth_stencil_attachment.as_mut() {
+            depth_stencil_attachment.attachment = TextureAttachment::Id(scene_depth);
+        }
+        let vertex_buffer = self.vertex_buffer.unwrap();
+        let render_resource_bindings = resources.get::<RenderResourceBindings>().unwrap();
+        render_context.begin_pass(
+            &self.descriptor,
+            &render_resource_bindings,
+            &mut |render_pass| {
+                render_pass.set_pipeline(pipeline);
+                render_pass.set_vertex_buffer(0, vertex_buffer, 0);
+                render_pass.set_bind_group(0, bind_group_descriptor.id, bind_group.id, None);
+                render_pass.draw(0..3, 0..1);
+            },
+        );
+    }
+}
+
+pub(crate) fn build_scene_depth_resolve_pipeline(
+    shaders: &mut Assets<Shader>,
+) -> PipelineDescriptor {
+    PipelineDescriptor {
+        rasterization_state: Some(RasterizationStateDescriptor {
+            front_face: FrontFace::Ccw,
+            cull_mode: CullMode::None,
+            depth_bias: 0,
+            depth_bias_slope_scale: 0.0,
+            depth_bias_clamp: 0.0,
+            clamp_depth: false,
+        }),
+        depth_stencil_state: Some(DepthStencilStateDescriptor {
+            format: TextureFormat::Depth32Float,
+            depth_write_enabled: true,
+            depth_compare: CompareFunction::Always,
+            stencil: StencilStateDescriptor {
+                front: StencilStateFaceDescriptor::IGNORE,
+                back: StencilStateFaceDescriptor::IGNORE,
+                read_mask: 0,
+                write_mask: 0,
+            },
+        }),
+        ..PipelineDescriptor::new(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                include_str!("composite.vert"),
+            )),
+            fragment: Some(shaders.add(Shader::from_glsl(
+                ShaderStage::Fragment,
+                include_str!("scene_depth_resolve.frag"),
+            ))),
+        })
+    }
+}
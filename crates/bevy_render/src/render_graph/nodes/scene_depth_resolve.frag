@@ -0,0 +1,12 @@
+#version 450
+
+layout(set = 0, binding = 0) uniform texture2DMS SceneDepthSource;
+layout(set = 0, binding = 1) uniform sampler SceneDepthSource_sampler;
+
+void main() {
+    // the first sample is close enough for fading, and avoids averaging depths of different surfaces
+    gl_FragDepth = texelFetch(
+        sampler2DMS(SceneDepthSource, SceneDepthSource_sampler),
+        ivec2(gl_FragCoord.xy),
+        0).r;
+}
@@ -0,0 +1,74 @@
+use crate::{
+    render_graph::{Node, ResourceSlotInfo, ResourceSlots},
+    renderer::{RenderContext, RenderResourceType},
+    texture::TextureDescriptor,
+};
+use bevy_ecs::{Resources, World};
+use bevy_window::{WindowId, Windows};
+use std::borrow::Cow;
+
+/// Outputs a window sized texture that only holds data while the graph runs, like the intermediate targets of a
+/// post-processing chain. Unlike [WindowTextureNode](super::WindowTextureNode), it doesn't own its texture: the
+/// [TransientTexturePool](crate::render_graph::TransientTexturePool) gives transient textures with the same
+/// descriptor the same texture when the nodes using them never run at the same time.
+///
+/// The contents are lost between frames and after the last node that uses the texture, so nodes have to write the
+/// texture before they read it.
+#[derive(Debug)]
+pub struct TransientTextureNode {
+    window_id: WindowId,
+    descriptor: TextureDescriptor,
+    scale: f32,
+}
+
+impl TransientTextureNode {
+    pub const OUT_TEXTURE: &'static str = "texture";
+
+    pub fn new(window_id: WindowId, descriptor: TextureDescriptor) -> Self {
+        TransientTextureNode {
+            window_id,
+            descriptor,
+            scale: 1.0,
+        }
+    }
+
+    /// Sizes the texture to the window's physical size multiplied by `scale`
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// The descriptor of the texture at the window's current size, or None if the window doesn't exist
+    pub fn get_descriptor(&self, windows: &Windows) -> Option<TextureDescriptor> {
+        let window = windows.get(self.window_id)?;
+        let mut descriptor = self.descriptor;
+        descriptor.size.width = scaled_size(window.physical_width(), self.scale);
+        descriptor.size.height = scaled_size(window.physical_height(), self.scale);
+        Some(descriptor)
+    }
+}
+
+impl Node for TransientTextureNode {
+    fn output(&self) -> &[ResourceSlotInfo] {
+        static OUTPUT: &[ResourceSlotInfo] = &[ResourceSlotInfo {
+            name: Cow::Borrowed(TransientTextureNode::OUT_TEXTURE),
+            resource_type: RenderResourceType::Texture,
+        }];
+        OUTPUT
+    }
+
+    fn update(
+        &mut self,
+        _world: &World,
+        _resources: &Resources,
+        _render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        // the output is set by the TransientTexturePool before the graph runs
+    }
+}
+
+fn scaled_size(size: u32, scale: f32) -> u32 {
+    ((size as f32 * scale).round() as u32).max(1)
+}
@@ -11,6 +11,7 @@ use std::borrow::Cow;
 pub struct WindowTextureNode {
     window_id: WindowId,
     descriptor: TextureDescriptor,
+    scale: f32,
     window_created_event_reader: EventReader<WindowCreated>,
     window_resized_event_reader: EventReader<WindowResized>,
 }
@@ -22,10 +23,18 @@ impl WindowTextureNode {
         WindowTextureNode {
             window_id,
             descriptor,
+            scale: 1.0,
             window_created_event_reader: Default::default(),
             window_resized_event_reader: Default::default(),
         }
     }
+
+    /// Sizes the texture to the window's physical size multiplied by `scale`, to render at a lower or higher
+    /// resolution than the window
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
 }
 
 impl Node for WindowTextureNode {
@@ -68,10 +77,14 @@ impl Node for WindowTextureNode {
                 render_resource_context.remove_texture(old_texture);
             }
 
-            self.descriptor.size.width = window.width();
-            self.descriptor.size.height = window.height();
+            self.descriptor.size.width = scaled_size(window.physical_width(), self.scale);
+            self.descriptor.size.height = scaled_size(window.physical_height(), self.scale);
             let texture_resource = render_resource_context.create_texture(self.descriptor);
             output.set(WINDOW_TEXTURE, RenderResourceId::Texture(texture_resource));
         }
     }
 }
+
+pub(crate) fn scaled_size(size: u32, scale: f32) -> u32 {
+    ((size as f32 * scale).round() as u32).max(1)
+}
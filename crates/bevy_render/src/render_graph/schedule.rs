@@ -65,6 +65,16 @@ impl Stages {
         }
     }
 
+    /// Whether node `a` is always done before node `b` starts. Nodes in different jobs of the same stage have no order.
+    pub fn runs_before(&self, a: NodeId, b: NodeId) -> bool {
+        match (self.node_indices.get(&a), self.node_indices.get(&b)) {
+            (Some(a), Some(b)) => {
+                a.stage < b.stage || (a.stage == b.stage && a.job == b.job && a.node < b.node)
+            }
+            _ => false,
+        }
+    }
+
     pub fn borrow<'a>(&self, render_graph: &'a mut RenderGraph) -> Vec<StageBorrow<'a>> {
         // unfortunately borrowing render graph nodes in a specific order takes a little bit of gymnastics
         let mut stage_borrows = Vec::with_capacity(self.stages.len());
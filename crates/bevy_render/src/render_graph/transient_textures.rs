@@ -0,0 +1,228 @@
+use super::{NodeId, RenderGraph, Stages, TransientTextureNode};
+use crate::{
+    renderer::{RenderResourceContext, RenderResourceId, TextureId},
+    texture::TextureDescriptor,
+};
+use bevy_window::Windows;
+
+/// A texture of the pool, and the nodes that use it this frame
+#[derive(Debug)]
+struct PooledTexture {
+    descriptor: TextureDescriptor,
+    texture: TextureId,
+    /// The nodes that use each of the transient textures that share this texture
+    users: Vec<Vec<NodeId>>,
+}
+
+/// Allocates the textures of [TransientTextureNode]s. Before the render graph runs, it looks up the nodes that use
+/// each transient texture, and gives transient textures with the same descriptor the same texture when all nodes that
+/// use one are done before any node that uses the other starts. A chain of post-processing passes that each read the
+/// output of the pass before then needs two textures instead of one per pass.
+#[derive(Debug, Default)]
+pub struct TransientTexturePool {
+    textures: Vec<PooledTexture>,
+}
+
+impl TransientTexturePool {
+    /// Sets the outputs of the transient texture nodes of `render_graph`, which runs in the order of `stages`.
+    /// Textures that aren't used anymore, for example because the window was resized, are removed.
+    pub fn update(
+        &mut self,
+        render_graph: &mut RenderGraph,
+        stages: &Stages,
+        windows: &Windows,
+        render_resource_context: &dyn RenderResourceContext,
+    ) {
+        for pooled_texture in self.textures.iter_mut() {
+            pooled_texture.users.clear();
+        }
+
+        let mut assignments = Vec::new();
+        for node_state in render_graph.iter_nodes() {
+            let descriptor = if let Some(descriptor) = node_state
+                .node
+                .downcast_ref::<TransientTextureNode>()
+                .and_then(|node| node.get_descriptor(windows))
+            {
+                descriptor
+            } else {
+                continue;
+            };
+            let users = node_state
+                .edges
+                .output_edges
+                .iter()
+                .map(|edge| edge.get_input_node())
+                .collect::<Vec<_>>();
+            if users.is_empty() {
+                continue;
+            }
+
+            let runs_apart = |other_users: &Vec<NodeId>| {
+                let all_before = |first: &[NodeId], second: &[NodeId]| {
+                    first
+                        .iter()
+                        .all(|a| second.iter().all(|b| stages.runs_before(*a, *b)))
+                };
+                all_before(&users, other_users) || all_before(other_users, &users)
+            };
+            let pooled_texture = if let Some(pooled_texture) =
+                self.textures.iter_mut().find(|pooled_texture| {
+                    pooled_texture.descriptor == descriptor
+                        && pooled_texture.users.iter().all(runs_apart)
+                }) {
+                pooled_texture
+            } else {
+                self.textures.push(PooledTexture {
+                    descriptor,
+                    texture: render_resource_context.create_texture(descriptor),
+                    users: Vec::new(),
+                });
+                self.textures.last_mut().unwrap()
+            };
+            pooled_texture.users.push(users);
+            assignments.push((node_state.id, pooled_texture.texture));
+        }
+
+        for (node, texture) in assignments {
+            render_graph
+                .get_node_state_mut(node)
+                .unwrap()
+                .output_slots
+                .set(0, RenderResourceId::Texture(texture));
+        }
+
+        self.textures.retain(|pooled_texture| {
+            if pooled_texture.users.is_empty() {
+                render_resource_context.remove_texture(pooled_texture.texture);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// The number of textures the transient textures share
+    pub fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransientTexturePool;
+    use crate::{
+        render_graph::{
+            DependentNodeStager, Node, RenderGraph, RenderGraphStager, ResourceSlotInfo,
+            ResourceSlots, TransientTextureNode,
+        },
+        renderer::{HeadlessRenderResourceContext, RenderContext, RenderResourceType},
+        texture::{TextureDescriptor, TextureFormat},
+    };
+    use bevy_ecs::{Resources, World};
+    use bevy_window::{Window, WindowDescriptor, WindowId, Windows};
+
+    struct TestPass {
+        inputs: Vec<ResourceSlotInfo>,
+    }
+
+    impl TestPass {
+        fn new(inputs: usize) -> Self {
+            TestPass {
+                inputs: (0..inputs)
+                    .map(|i| {
+                        ResourceSlotInfo::new(format!("in_{}", i), RenderResourceType::Texture)
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    impl Node for TestPass {
+        fn input(&self) -> &[ResourceSlotInfo] {
+            &self.inputs
+        }
+
+        fn update(
+            &mut self,
+            _: &World,
+            _: &Resources,
+            _: &mut dyn RenderContext,
+            _: &ResourceSlots,
+            _: &mut ResourceSlots,
+        ) {
+        }
+    }
+
+    #[test]
+    fn transient_textures_used_at_different_times_are_shared() {
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+        ));
+        let descriptor = TextureDescriptor {
+            format: TextureFormat::default(),
+            ..Default::default()
+        };
+
+        // a chain of passes where each reads the texture the previous one wrote
+        let mut graph = RenderGraph::default();
+        for texture in ["a", "b", "c"].iter() {
+            graph.add_node(
+                *texture,
+                TransientTextureNode::new(WindowId::primary(), descriptor),
+            );
+        }
+        graph.add_node("write_a", TestPass::new(1));
+        graph.add_node("a_to_b", TestPass::new(2));
+        graph.add_node("b_to_c", TestPass::new(2));
+        graph.add_node("read_c", TestPass::new(1));
+        for (texture, pass, slot) in [
+            ("a", "write_a", 0),
+            ("a", "a_to_b", 0),
+            ("b", "a_to_b", 1),
+            ("b", "b_to_c", 0),
+            ("c", "b_to_c", 1),
+            ("c", "read_c", 0),
+        ]
+        .iter()
+        {
+            graph
+                .add_slot_edge(*texture, TransientTextureNode::OUT_TEXTURE, *pass, *slot)
+                .unwrap();
+        }
+        graph.add_node_edge("write_a", "a_to_b").unwrap();
+        graph.add_node_edge("a_to_b", "b_to_c").unwrap();
+        graph.add_node_edge("b_to_c", "read_c").unwrap();
+
+        let stages = DependentNodeStager::loose_grouping()
+            .get_stages(&graph)
+            .unwrap();
+        let mut pool = TransientTexturePool::default();
+        let render_resource_context = HeadlessRenderResourceContext::default();
+        pool.update(&mut graph, &stages, &windows, &render_resource_context);
+
+        let texture = |graph: &RenderGraph, name: &'static str| {
+            graph
+                .get_node_state(name)
+                .unwrap()
+                .output_slots
+                .get(0)
+                .unwrap()
+        };
+        assert_eq!(pool.len(), 2);
+        assert_eq!(texture(&graph, "a"), texture(&graph, "c"));
+        assert_ne!(texture(&graph, "a"), texture(&graph, "b"));
+
+        // the same textures are used again in the next frame
+        let a = texture(&graph, "a");
+        pool.update(&mut graph, &stages, &windows, &render_resource_context);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(texture(&graph, "a"), a);
+    }
+}
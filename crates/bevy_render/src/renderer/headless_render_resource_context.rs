@@ -115,6 +115,8 @@ impl RenderResourceContext for HeadlessRenderResourceContext {
     ) {
     }
 
+    fn remove_render_pipeline(&self, _pipeline_handle: &Handle<PipelineDescriptor>) {}
+
     fn create_bind_group(
         &self,
         _bind_group_descriptor_id: BindGroupDescriptorId,
@@ -2,8 +2,10 @@ mod headless_render_resource_context;
 mod render_context;
 mod render_resource;
 mod render_resource_context;
+mod render_statistics;
 
 pub use headless_render_resource_context::*;
 pub use render_context::*;
 pub use render_resource::*;
 pub use render_resource_context::*;
+pub use render_statistics::*;
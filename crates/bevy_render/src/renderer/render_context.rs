@@ -27,6 +27,19 @@ pub trait RenderContext {
         destination_mip_level: u32,
         size: Extent3d,
     );
+    /// Copies a region of one texture to another. The z of the origins and the depth of the size are array layers,
+    /// for example the faces of a cubemap.
+    #[allow(clippy::too_many_arguments)]
+    fn copy_texture_to_texture(
+        &mut self,
+        source_texture: TextureId,
+        source_origin: [u32; 3],
+        source_mip_level: u32,
+        destination_texture: TextureId,
+        destination_origin: [u32; 3],
+        destination_mip_level: u32,
+        size: Extent3d,
+    );
     fn begin_pass(
         &mut self,
         pass_descriptor: &PassDescriptor,
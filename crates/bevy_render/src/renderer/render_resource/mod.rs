@@ -4,6 +4,7 @@ mod buffer;
 mod render_resource;
 mod render_resource_bindings;
 mod shared_buffers;
+mod std140;
 mod texture;
 
 pub use bind_group::*;
@@ -11,4 +12,5 @@ pub use buffer::*;
 pub use render_resource::*;
 pub use render_resource_bindings::*;
 pub use shared_buffers::*;
+pub use std140::*;
 pub use texture::*;
@@ -148,6 +148,7 @@ impl_render_resource_bytes!(Vec2);
 impl_render_resource_bytes!(Vec3);
 impl_render_resource_bytes!(Vec4);
 impl_render_resource_bytes!(Mat4);
+impl_render_resource_bytes!(bool);
 impl_render_resource_bytes!(u8);
 impl_render_resource_bytes!(u16);
 impl_render_resource_bytes!(u32);
@@ -185,13 +186,22 @@ impl RenderResource for GlobalTransform {
         Some(RenderResourceType::Buffer)
     }
 
+    /// Writes the `Transform { mat4 Model; mat3 NormalMatrix; }` uniform block. The normal matrix columns are
+    /// padded to vec4s (std140)
     fn write_buffer_bytes(&self, buffer: &mut [u8]) {
-        let mat4 = self.compute_matrix();
-        mat4.write_bytes(buffer);
+        let (model, normal) = buffer.split_at_mut(std::mem::size_of::<[f32; 16]>());
+        self.compute_matrix().write_bytes(model);
+        let normal_matrix = self.compute_normal_matrix();
+        let columns = [
+            normal_matrix.x_axis().extend(0.0),
+            normal_matrix.y_axis().extend(0.0),
+            normal_matrix.z_axis().extend(0.0),
+        ];
+        columns.write_bytes(normal);
     }
 
     fn buffer_byte_len(&self) -> Option<usize> {
-        Some(std::mem::size_of::<[f32; 16]>())
+        Some(std::mem::size_of::<[f32; 16]>() + std::mem::size_of::<[[f32; 4]; 3]>())
     }
 
     fn texture(&self) -> Option<&Handle<Texture>> {
@@ -25,7 +25,7 @@ impl SharedBuffers {
         }
     }
 
-    pub fn get_buffer<T: RenderResource>(
+    pub fn get_buffer<T: RenderResource + ?Sized>(
         &self,
         render_resource: &T,
         buffer_usage: BufferUsage,
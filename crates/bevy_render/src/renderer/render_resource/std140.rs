@@ -0,0 +1,218 @@
+use super::{RenderResource, RenderResourceType};
+use crate::{color::Color, texture::Texture};
+use bevy_asset::Handle;
+use bevy_core::{AsBytes, Bytes};
+pub use bevy_derive::Std140;
+use bevy_math::{Mat3, Mat4, Vec2, Vec3, Vec4};
+
+/// A type that can be written to a uniform block with the GLSL std140 layout. Use `#[derive(Std140)]` for structs,
+/// which lays out the fields like a GLSL struct. Each field must also implement Std140 or this will fail.
+///
+/// Scalars, vectors and matrices follow the std140 rules. Arrays and structs are aligned to 16 bytes, and every
+/// array element takes up a multiple of 16 bytes.
+pub trait Std140 {
+    /// The alignment of the type in a uniform block, in bytes
+    const ALIGNMENT: usize;
+    /// The size of the type in a uniform block, in bytes. This doesn't include the padding after a vec3, because the
+    /// member that follows it can use those 4 bytes.
+    const SIZE: usize;
+
+    /// Writes the type to the start of `buffer`, which is at least [Std140::SIZE] bytes long. Padding bytes are left
+    /// untouched.
+    fn write_std140(&self, buffer: &mut [u8]);
+}
+
+/// Rounds `offset` up to a multiple of `alignment`
+pub const fn std140_align(offset: usize, alignment: usize) -> usize {
+    (offset + alignment - 1) / alignment * alignment
+}
+
+/// The larger of two alignments, for use in const contexts
+pub const fn std140_max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// The size of a std140 uniform block that contains a `T`
+pub const fn std140_block_size<T: Std140>() -> usize {
+    std140_align(T::SIZE, std140_max(T::ALIGNMENT, 16))
+}
+
+macro_rules! impl_std140_bytes {
+    ($ty:ident, $alignment:expr, $size:expr) => {
+        impl Std140 for $ty {
+            const ALIGNMENT: usize = $alignment;
+            const SIZE: usize = $size;
+
+            fn write_std140(&self, buffer: &mut [u8]) {
+                self.write_bytes(&mut buffer[..Self::SIZE]);
+            }
+        }
+    };
+}
+
+impl_std140_bytes!(f32, 4, 4);
+impl_std140_bytes!(i32, 4, 4);
+impl_std140_bytes!(u32, 4, 4);
+impl_std140_bytes!(bool, 4, 4);
+impl_std140_bytes!(Vec2, 8, 8);
+impl_std140_bytes!(Vec4, 16, 16);
+impl_std140_bytes!(Mat4, 16, 64);
+impl_std140_bytes!(Color, 16, 16);
+
+impl Std140 for Vec3 {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 12;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        // glam pads Vec3 to 16 bytes, so it can't be written with Bytes
+        [self.x(), self.y(), self.z()].write_bytes(&mut buffer[..Self::SIZE]);
+    }
+}
+
+/// Matrices are laid out like arrays of their columns, so each column of a Mat3 is padded to 16 bytes
+impl Std140 for Mat3 {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 48;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        let columns = self.to_cols_array();
+        for (column, buffer) in columns.chunks(3).zip(buffer.chunks_mut(16)) {
+            buffer[..12].copy_from_slice(column.as_bytes());
+        }
+    }
+}
+
+macro_rules! impl_std140_array {
+    ($($len:expr),*) => {
+        $(
+            impl<T: Std140> Std140 for [T; $len] {
+                const ALIGNMENT: usize = std140_max(T::ALIGNMENT, 16);
+                const SIZE: usize = $len * std140_align(T::SIZE, Self::ALIGNMENT);
+
+                fn write_std140(&self, buffer: &mut [u8]) {
+                    let stride = std140_align(T::SIZE, Self::ALIGNMENT);
+                    for (item, buffer) in self.iter().zip(buffer.chunks_mut(stride)) {
+                        item.write_std140(buffer);
+                    }
+                }
+            }
+
+            /// Arrays are uniform blocks that contain a single array, laid out with std140
+            impl<T: Std140> RenderResource for [T; $len] {
+                fn resource_type(&self) -> Option<RenderResourceType> {
+                    Some(RenderResourceType::Buffer)
+                }
+
+                fn write_buffer_bytes(&self, buffer: &mut [u8]) {
+                    self.write_std140(buffer);
+                }
+
+                fn buffer_byte_len(&self) -> Option<usize> {
+                    Some(std140_block_size::<Self>())
+                }
+
+                fn texture(&self) -> Option<&Handle<Texture>> {
+                    None
+                }
+            }
+        )*
+    };
+}
+
+impl_std140_array!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32, 64, 128, 256
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{std140_block_size, Std140};
+    use crate::{color::Color, renderer::RenderResource};
+    use bevy_math::{Mat3, Vec2, Vec3};
+
+    #[derive(Std140)]
+    #[as_crate(bevy_render)]
+    struct Light {
+        position: Vec3,
+        intensity: f32,
+        color: Color,
+    }
+
+    #[derive(RenderResource, Std140)]
+    #[as_crate(bevy_render)]
+    #[render_resource(std140)]
+    struct Material {
+        tint: Vec2,
+        lights: [Light; 2],
+        scale: f32,
+        normal: Mat3,
+        weights: [f32; 3],
+    }
+
+    fn read_f32(buffer: &[u8], offset: usize) -> f32 {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&buffer[offset..offset + 4]);
+        f32::from_ne_bytes(bytes)
+    }
+
+    #[test]
+    fn std140_layout() {
+        assert_eq!(Vec3::ALIGNMENT, 16);
+        assert_eq!(<[f32; 3]>::SIZE, 48);
+        assert_eq!(<[Vec3; 2]>::SIZE, 32);
+        assert_eq!(Light::ALIGNMENT, 16);
+        assert_eq!(Light::SIZE, 32);
+        // tint: 0, lights: 16, scale: 80, normal: 96, weights: 144
+        assert_eq!(Material::SIZE, 192);
+        assert_eq!(std140_block_size::<Material>(), 192);
+        assert_eq!(std140_block_size::<f32>(), 16);
+
+        let material = Material {
+            tint: Vec2::new(1.0, 2.0),
+            lights: [
+                Light {
+                    position: Vec3::new(3.0, 4.0, 5.0),
+                    intensity: 6.0,
+                    color: Color::rgba_linear(0.1, 0.2, 0.3, 0.4),
+                },
+                Light {
+                    position: Vec3::new(7.0, 8.0, 9.0),
+                    intensity: 10.0,
+                    color: Color::WHITE,
+                },
+            ],
+            scale: 11.0,
+            normal: Mat3::identity(),
+            weights: [12.0, 13.0, 14.0],
+        };
+        assert_eq!(material.buffer_byte_len(), Some(192));
+        let mut buffer = vec![0; 192];
+        material.write_buffer_bytes(&mut buffer);
+
+        let expected = [
+            (0, 1.0),
+            (4, 2.0),
+            (16, 3.0),
+            (24, 5.0),
+            (28, 6.0),
+            (44, 0.4),
+            (48, 7.0),
+            (60, 10.0),
+            (80, 11.0),
+            (96, 1.0),
+            (116, 1.0),
+            (136, 1.0),
+            (140, 0.0),
+            (144, 12.0),
+            (160, 13.0),
+            (176, 14.0),
+        ];
+        for (offset, value) in expected.iter() {
+            assert_eq!(read_f32(&buffer, *offset), *value, "offset {}", offset);
+        }
+    }
+}
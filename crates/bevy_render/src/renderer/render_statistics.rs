@@ -0,0 +1,114 @@
+use crate::{pipeline::PrimitiveTopology, texture::TextureDescriptor};
+use std::ops::Range;
+
+/// Counters the render backend collects while it renders a frame. The per-frame counters describe the last rendered
+/// frame, while the memory totals cover all buffers and textures that currently exist (swap chain textures excluded).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RenderStatistics {
+    pub draw_calls: u64,
+    /// The number of instances drawn, summed over all draw calls
+    pub instances: u64,
+    /// The number of triangles drawn, including every instance. Draws with line or point topologies don't add any.
+    pub triangles: u64,
+    pub pipeline_switches: u64,
+    /// The number of copies from staging buffers and buffers created with data
+    pub buffer_uploads: u64,
+    pub buffer_upload_bytes: u64,
+    pub buffer_memory: u64,
+    pub texture_memory: u64,
+}
+
+impl RenderStatistics {
+    /// Records a draw of `elements` vertices or indices
+    pub fn record_draw(
+        &mut self,
+        topology: PrimitiveTopology,
+        elements: Range<u32>,
+        instances: Range<u32>,
+    ) {
+        let element_count = (elements.end.saturating_sub(elements.start)) as u64;
+        let instance_count = (instances.end.saturating_sub(instances.start)) as u64;
+        let triangles = match topology {
+            PrimitiveTopology::TriangleList => element_count / 3,
+            PrimitiveTopology::TriangleStrip => element_count.saturating_sub(2),
+            PrimitiveTopology::PointList
+            | PrimitiveTopology::LineList
+            | PrimitiveTopology::LineStrip => 0,
+        };
+        self.draw_calls += 1;
+        self.instances += instance_count;
+        self.triangles += triangles * instance_count;
+    }
+
+    pub fn record_upload(&mut self, bytes: u64) {
+        self.buffer_uploads += 1;
+        self.buffer_upload_bytes += bytes;
+    }
+
+    /// Resets the per-frame counters, keeping the memory totals
+    pub fn reset_frame(&mut self) {
+        *self = RenderStatistics {
+            buffer_memory: self.buffer_memory,
+            texture_memory: self.texture_memory,
+            ..Default::default()
+        };
+    }
+}
+
+/// Estimates the memory a texture uses, including its mip levels and samples
+pub fn texture_memory(descriptor: &TextureDescriptor) -> u64 {
+    let pixel_size = descriptor.format.pixel_size() as u64;
+    let mut memory = 0;
+    for mip_level in 0..descriptor.mip_level_count {
+        let width = (descriptor.size.width >> mip_level).max(1) as u64;
+        let height = (descriptor.size.height >> mip_level).max(1) as u64;
+        memory += width * height * descriptor.size.depth as u64 * pixel_size;
+    }
+    memory * descriptor.sample_count as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{texture_memory, RenderStatistics};
+    use crate::{
+        pipeline::PrimitiveTopology,
+        texture::{Extent3d, TextureDescriptor, TextureFormat},
+    };
+
+    #[test]
+    fn render_statistics() {
+        let mut statistics = RenderStatistics {
+            texture_memory: 64,
+            ..Default::default()
+        };
+        statistics.record_draw(PrimitiveTopology::TriangleList, 0..6, 0..10);
+        statistics.record_draw(PrimitiveTopology::TriangleStrip, 0..4, 0..1);
+        statistics.record_draw(PrimitiveTopology::LineList, 0..4, 0..1);
+        statistics.record_upload(256);
+        assert_eq!(statistics.draw_calls, 3);
+        assert_eq!(statistics.instances, 12);
+        assert_eq!(statistics.triangles, 22);
+        assert_eq!(statistics.buffer_upload_bytes, 256);
+
+        statistics.reset_frame();
+        assert_eq!(
+            statistics,
+            RenderStatistics {
+                texture_memory: 64,
+                ..Default::default()
+            }
+        );
+
+        let descriptor = TextureDescriptor {
+            size: Extent3d {
+                width: 4,
+                height: 4,
+                depth: 1,
+            },
+            mip_level_count: 3,
+            format: TextureFormat::Rgba8UnormSrgb,
+            ..Default::default()
+        };
+        assert_eq!(texture_memory(&descriptor), (16 + 4 + 1) * 4);
+    }
+}
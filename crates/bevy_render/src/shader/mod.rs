@@ -23,4 +23,43 @@ pub struct ShaderLayout {
     pub entry_point: String,
 }
 
+/// An input or output variable of a shader, other than builtins like `gl_Position`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderInterfaceVariable {
+    pub name: String,
+    pub location: u32,
+    /// The GLSL name of the variable's type, like `vec3`
+    pub type_name: String,
+}
+
+/// The input and output variables of a shader, which pass values from one stage to the next
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShaderInterface {
+    pub inputs: Vec<ShaderInterfaceVariable>,
+    pub outputs: Vec<ShaderInterfaceVariable>,
+}
+
+impl ShaderInterface {
+    /// Finds an input that the previous stage doesn't write a value of the same type to, and the output of the
+    /// previous stage at its location, if there is one
+    pub fn find_unmatched_input<'a>(
+        &'a self,
+        previous_stage: &'a ShaderInterface,
+    ) -> Option<(
+        &'a ShaderInterfaceVariable,
+        Option<&'a ShaderInterfaceVariable>,
+    )> {
+        self.inputs.iter().find_map(|input| {
+            let output = previous_stage
+                .outputs
+                .iter()
+                .find(|output| output.location == input.location);
+            match output {
+                Some(output) if output.type_name == input.type_name => None,
+                output => Some((input, output)),
+            }
+        })
+    }
+}
+
 pub const GL_VERTEX_INDEX: &str = "gl_VertexIndex";
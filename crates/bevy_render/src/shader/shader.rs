@@ -1,7 +1,10 @@
-use super::ShaderLayout;
-use bevy_asset::Handle;
+use super::{ShaderInterface, ShaderLayout};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, Handle, LoadContext, LoadedAsset};
 use bevy_type_registry::TypeUuid;
+use bevy_utils::BoxedFuture;
 use std::marker::Copy;
+use thiserror::Error;
 
 /// The stage of a shader
 #[derive(Hash, Eq, PartialEq, Copy, Clone, Debug)]
@@ -11,6 +14,14 @@ pub enum ShaderStage {
     Compute,
 }
 
+/// An error that occurs while compiling a shader
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ShaderError {
+    /// The compiler's log, with the source of each line it reports an error on
+    #[error("Failed to compile {stage:?} shader:\n{log}")]
+    Compilation { stage: ShaderStage, log: String },
+}
+
 #[cfg(all(not(target_os = "ios"), not(target_arch = "wasm32")))]
 impl Into<bevy_glsl_to_spirv::ShaderType> for ShaderStage {
     fn into(self) -> bevy_glsl_to_spirv::ShaderType {
@@ -27,8 +38,13 @@ fn glsl_to_spirv(
     glsl_source: &str,
     stage: ShaderStage,
     shader_defs: Option<&[String]>,
-) -> Vec<u32> {
-    bevy_glsl_to_spirv::compile(glsl_source, stage.into(), shader_defs).unwrap()
+) -> Result<Vec<u32>, ShaderError> {
+    bevy_glsl_to_spirv::compile(glsl_source, stage.into(), shader_defs).map_err(|log| {
+        ShaderError::Compilation {
+            stage,
+            log: annotate_compile_log(glsl_source, &log),
+        }
+    })
 }
 
 #[cfg(target_os = "ios")]
@@ -47,7 +63,7 @@ fn glsl_to_spirv(
     glsl_source: &str,
     stage: ShaderStage,
     shader_defs: Option<&[String]>,
-) -> Vec<u32> {
+) -> Result<Vec<u32>, ShaderError> {
     let mut compiler = shaderc::Compiler::new().unwrap();
     let mut options = shaderc::CompileOptions::new().unwrap();
     if let Some(shader_defs) = shader_defs {
@@ -64,9 +80,39 @@ fn glsl_to_spirv(
             "main",
             Some(&options),
         )
-        .unwrap();
+        .map_err(|err| ShaderError::Compilation {
+            stage,
+            log: annotate_compile_log(glsl_source, &err.to_string()),
+        })?;
+
+    Ok(binary_result.as_binary().to_vec())
+}
 
-    binary_result.as_binary().to_vec()
+/// Appends the source line to each line of a compiler log that reports an error or a warning on it. The compilers
+/// report lines like `ERROR: 0:12: 'foo' : undeclared identifier`, where 0 is the source string and 12 the line.
+#[cfg(not(target_arch = "wasm32"))]
+fn annotate_compile_log(source: &str, log: &str) -> String {
+    let source_lines = source.lines().collect::<Vec<_>>();
+    let mut annotated = String::new();
+    for log_line in log.lines() {
+        annotated.push_str(log_line);
+        annotated.push('\n');
+        let line_number = log_line
+            .splitn(4, ':')
+            .nth(2)
+            .and_then(|line_number| line_number.trim().parse::<usize>().ok());
+        if let Some(source_line) = line_number
+            .and_then(|line_number| line_number.checked_sub(1))
+            .and_then(|index| source_lines.get(index))
+        {
+            annotated.push_str(&format!(
+                "{:>5} | {}\n",
+                line_number.unwrap(),
+                source_line.trim_end()
+            ));
+        }
+    }
+    annotated
 }
 
 fn bytes_to_words(bytes: &[u8]) -> Vec<u32> {
@@ -114,22 +160,22 @@ impl Shader {
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn get_spirv(&self, macros: Option<&[String]>) -> Vec<u32> {
+    pub fn get_spirv(&self, macros: Option<&[String]>) -> Result<Vec<u32>, ShaderError> {
         match self.source {
-            ShaderSource::Spirv(ref bytes) => bytes.clone(),
+            ShaderSource::Spirv(ref bytes) => Ok(bytes.clone()),
             ShaderSource::Glsl(ref source) => glsl_to_spirv(&source, self.stage, macros),
         }
     }
 
     #[allow(unused_variables)]
-    pub fn get_spirv_shader(&self, macros: Option<&[String]>) -> Shader {
-        Shader {
+    pub fn get_spirv_shader(&self, macros: Option<&[String]>) -> Result<Shader, ShaderError> {
+        Ok(Shader {
             #[cfg(not(target_arch = "wasm32"))]
-            source: ShaderSource::Spirv(self.get_spirv(macros)),
+            source: ShaderSource::Spirv(self.get_spirv(macros)?),
             #[cfg(target_arch = "wasm32")]
             source: self.source.clone(),
             stage: self.stage,
-        }
+        })
     }
 
     pub fn reflect_layout(&self, enforce_bevy_conventions: bool) -> Option<ShaderLayout> {
@@ -142,6 +188,43 @@ impl Shader {
             panic!("Cannot reflect layout of non-SpirV shader. Try compiling this shader to SpirV first using self.get_spirv_shader()");
         }
     }
+
+    pub fn reflect_interface(&self) -> Option<ShaderInterface> {
+        if let ShaderSource::Spirv(ref spirv) = self.source {
+            Some(ShaderInterface::from_spirv(spirv.as_slice()))
+        } else {
+            panic!("Cannot reflect interface of non-SpirV shader. Try compiling this shader to SpirV first using self.get_spirv_shader()");
+        }
+    }
+}
+
+/// Loads GLSL shaders, taking the stage from the extension: `.vert` for vertex shaders and `.frag` for fragment
+/// shaders. With [AssetServer::watch_for_changes](bevy_asset::AssetServer::watch_for_changes), edited shaders are
+/// recompiled by the [PipelineCompiler](crate::pipeline::PipelineCompiler).
+#[derive(Clone, Default)]
+pub struct ShaderLoader;
+
+impl AssetLoader for ShaderLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let stage = match load_context.path().extension().and_then(|ext| ext.to_str()) {
+                Some("vert") => ShaderStage::Vertex,
+                Some("frag") => ShaderStage::Fragment,
+                _ => anyhow::bail!("Unknown shader extension of {:?}", load_context.path()),
+            };
+            let source = std::str::from_utf8(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(Shader::from_glsl(stage, source)));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["vert", "frag"]
+    }
 }
 
 /// All stages in a shader program
@@ -185,3 +268,25 @@ impl ShaderStages {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::{Shader, ShaderError, ShaderStage};
+
+    #[test]
+    fn compile_errors_show_the_source_line() {
+        let shader = Shader::from_glsl(
+            ShaderStage::Fragment,
+            "#version 450\nlayout(location = 0) out vec4 o_Target;\nvoid main() {\n    o_Target = undefined_color;\n}\n",
+        );
+        match shader.get_spirv(None) {
+            Err(ShaderError::Compilation { stage, log }) => {
+                assert_eq!(stage, ShaderStage::Fragment);
+                assert!(log.contains("undefined_color"));
+                assert!(log.contains("    4 |     o_Target = undefined_color;"));
+            }
+            Ok(_) => panic!("the shader should fail to compile"),
+        }
+    }
+}
@@ -3,14 +3,15 @@ use crate::{
         BindGroupDescriptor, BindType, BindingDescriptor, BindingShaderStage, InputStepMode,
         UniformProperty, VertexAttributeDescriptor, VertexBufferDescriptor, VertexFormat,
     },
-    shader::{ShaderLayout, GL_VERTEX_INDEX},
+    shader::{ShaderInterface, ShaderInterfaceVariable, ShaderLayout, GL_VERTEX_INDEX},
     texture::{TextureComponentType, TextureViewDimension},
 };
 use bevy_core::AsBytes;
 use spirv_reflect::{
     types::{
-        ReflectDescriptorBinding, ReflectDescriptorSet, ReflectDescriptorType, ReflectDimension,
-        ReflectShaderStageFlags, ReflectTypeDescription, ReflectTypeFlags,
+        ReflectDecorationFlags, ReflectDescriptorBinding, ReflectDescriptorSet,
+        ReflectDescriptorType, ReflectDimension, ReflectInterfaceVariable, ReflectShaderStageFlags,
+        ReflectTypeDescription, ReflectTypeFlags,
     },
     ShaderModule,
 };
@@ -38,6 +39,7 @@ impl ShaderLayout {
                     vertex_attribute_descriptors.push(VertexAttributeDescriptor {
                         name: input_variable.name.clone().into(),
                         format: reflect_vertex_format(
+                            &input_variable.name,
                             input_variable.type_description.as_ref().unwrap(),
                         ),
                         offset: 0,
@@ -89,6 +91,84 @@ impl ShaderLayout {
     }
 }
 
+impl ShaderInterface {
+    pub fn from_spirv(spirv_data: &[u32]) -> ShaderInterface {
+        match ShaderModule::load_u8_data(spirv_data.as_bytes()) {
+            Ok(ref module) => ShaderInterface {
+                inputs: reflect_interface_variables(
+                    module.enumerate_input_variables(None).unwrap(),
+                ),
+                outputs: reflect_interface_variables(
+                    module.enumerate_output_variables(None).unwrap(),
+                ),
+            },
+            Err(err) => panic!("Failed to reflect shader interface: {:?}", err),
+        }
+    }
+}
+
+fn reflect_interface_variables(
+    variables: Vec<ReflectInterfaceVariable>,
+) -> Vec<ShaderInterfaceVariable> {
+    let mut interface_variables = variables
+        .into_iter()
+        .filter(|variable| {
+            !variable
+                .decoration_flags
+                .contains(ReflectDecorationFlags::BUILT_IN)
+                && variable.location != u32::MAX
+        })
+        .map(|variable| ShaderInterfaceVariable {
+            type_name: variable
+                .type_description
+                .as_ref()
+                .map_or_else(String::new, reflect_glsl_type_name),
+            name: variable.name,
+            location: variable.location,
+        })
+        .collect::<Vec<_>>();
+    interface_variables.sort_by_key(|variable| variable.location);
+    interface_variables
+}
+
+fn reflect_glsl_type_name(type_description: &ReflectTypeDescription) -> String {
+    let flags = type_description.type_flags;
+    let numeric = &type_description.traits.numeric;
+    let (prefix, scalar) = if flags.contains(ReflectTypeFlags::STRUCT) {
+        ("", type_description.type_name.as_str())
+    } else if flags.contains(ReflectTypeFlags::BOOL) {
+        ("b", "bool")
+    } else if flags.contains(ReflectTypeFlags::INT) {
+        if numeric.scalar.signedness == 0 {
+            ("u", "uint")
+        } else {
+            ("i", "int")
+        }
+    } else if numeric.scalar.width == 64 {
+        ("d", "double")
+    } else {
+        ("", "float")
+    };
+    let mut type_name = if flags.contains(ReflectTypeFlags::STRUCT) {
+        scalar.to_string()
+    } else if flags.contains(ReflectTypeFlags::MATRIX) {
+        format!(
+            "{}mat{}x{}",
+            prefix, numeric.matrix.column_count, numeric.matrix.row_count
+        )
+    } else if flags.contains(ReflectTypeFlags::VECTOR) {
+        format!("{}vec{}", prefix, numeric.vector.component_count)
+    } else {
+        scalar.to_string()
+    };
+    if flags.contains(ReflectTypeFlags::ARRAY) {
+        for dim in type_description.traits.array.dims.iter() {
+            type_name.push_str(&format!("[{}]", dim));
+        }
+    }
+    type_name
+}
+
 fn reflect_bind_group(
     descriptor_set: &ReflectDescriptorSet,
     shader_stage: ReflectShaderStageFlags,
@@ -130,19 +210,22 @@ fn reflect_binding(
             BindType::SampledTexture {
                 dimension: reflect_dimension(type_description),
                 component_type: TextureComponentType::Float,
-                multisampled: false,
+                multisampled: type_description.traits.image.ms != 0,
             },
         ),
         ReflectDescriptorType::StorageBuffer => (
             &type_description.type_name,
             BindType::StorageBuffer {
                 dynamic: false,
-                readonly: true,
+                readonly: reflect_readonly(binding),
             },
         ),
         // TODO: detect comparison "true" case: https://github.com/gpuweb/gpuweb/issues/552
         ReflectDescriptorType::Sampler => (&binding.name, BindType::Sampler { comparison: false }),
-        _ => panic!("unsupported bind type {:?}", binding.descriptor_type),
+        _ => panic!(
+            "binding {} has an unsupported bind type {:?}",
+            binding.name, binding.descriptor_type
+        ),
     };
 
     let mut shader_stage = match shader_stage {
@@ -166,6 +249,20 @@ fn reflect_binding(
     }
 }
 
+/// Storage buffers declared `readonly` mark the buffer or each of its members as non-writable
+fn reflect_readonly(binding: &ReflectDescriptorBinding) -> bool {
+    let block = &binding.block;
+    block
+        .decoration_flags
+        .contains(ReflectDecorationFlags::NON_WRITABLE)
+        || (!block.members.is_empty()
+            && block.members.iter().all(|member| {
+                member
+                    .decoration_flags
+                    .contains(ReflectDecorationFlags::NON_WRITABLE)
+            }))
+}
+
 #[derive(Debug)]
 enum NumberType {
     Int,
@@ -246,7 +343,7 @@ fn reflect_uniform_numeric(type_description: &ReflectTypeDescription) -> Uniform
     }
 }
 
-fn reflect_vertex_format(type_description: &ReflectTypeDescription) -> VertexFormat {
+fn reflect_vertex_format(name: &str, type_description: &ReflectTypeDescription) -> VertexFormat {
     let traits = &type_description.traits;
     let number_type = if type_description.type_flags.contains(ReflectTypeFlags::INT) {
         match traits.numeric.scalar.signedness {
@@ -273,7 +370,7 @@ fn reflect_vertex_format(type_description: &ReflectTypeDescription) -> VertexFor
         (NumberType::UInt, 2, 16) => VertexFormat::Ushort2,
         (NumberType::UInt, 4, 16) => VertexFormat::Ushort4,
         (NumberType::Int, 2, 16) => VertexFormat::Short2,
-        (NumberType::Int, 8, 16) => VertexFormat::Short4,
+        (NumberType::Int, 4, 16) => VertexFormat::Short4,
         (NumberType::Float, 2, 16) => VertexFormat::Half2,
         (NumberType::Float, 4, 16) => VertexFormat::Half4,
         (NumberType::Float, 0, 32) => VertexFormat::Float,
@@ -289,8 +386,8 @@ fn reflect_vertex_format(type_description: &ReflectTypeDescription) -> VertexFor
         (NumberType::Int, 3, 32) => VertexFormat::Int3,
         (NumberType::Int, 4, 32) => VertexFormat::Int4,
         (number_type, component_count, width) => panic!(
-            "unexpected uniform property format {:?} {} {}",
-            number_type, component_count, width
+            "vertex attribute {} has an unsupported format: {} components of {:?} with {} bits",
+            name, component_count, number_type, width
         ),
     }
 }
@@ -328,7 +425,8 @@ mod tests {
             }
         "#,
         )
-        .get_spirv_shader(None);
+        .get_spirv_shader(None)
+        .unwrap();
 
         let layout = vertex_shader.reflect_layout(true).unwrap();
         assert_eq!(
@@ -397,4 +495,77 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn multisampled_textures() {
+        let fragment_shader = Shader::from_glsl(
+            ShaderStage::Fragment,
+            r#"
+            #version 450
+            layout(set = 0, binding = 0) uniform texture2DMS Depth;
+            layout(set = 0, binding = 1) uniform sampler Depth_sampler;
+
+            void main() {
+                gl_FragDepth = texelFetch(sampler2DMS(Depth, Depth_sampler), ivec2(gl_FragCoord.xy), 0).r;
+            }
+        "#,
+        )
+        .get_spirv_shader(None)
+        .unwrap();
+
+        let layout = fragment_shader.reflect_layout(true).unwrap();
+        assert_eq!(
+            layout.bind_groups[0].bindings[0].bind_type,
+            BindType::SampledTexture {
+                multisampled: true,
+                dimension: TextureViewDimension::D2,
+                component_type: TextureComponentType::Float,
+            }
+        );
+    }
+
+    #[test]
+    fn reflect_interface() {
+        let vertex_shader = Shader::from_glsl(
+            ShaderStage::Vertex,
+            r#"
+            #version 450
+            layout(location = 0) in vec3 Vertex_Position;
+            layout(location = 1) in vec2 Vertex_Uv;
+            layout(location = 0) out vec2 v_Uv;
+            layout(location = 1) flat out ivec2 v_Cell;
+            layout(location = 2) out mat3 v_Basis;
+
+            void main() {
+                v_Uv = Vertex_Uv;
+                v_Cell = ivec2(0);
+                v_Basis = mat3(1.0);
+                gl_Position = vec4(Vertex_Position, 1.0);
+            }
+        "#,
+        )
+        .get_spirv_shader(None)
+        .unwrap();
+
+        let variable = |name: &str, location: u32, type_name: &str| ShaderInterfaceVariable {
+            name: name.into(),
+            location,
+            type_name: type_name.into(),
+        };
+        // gl_Position isn't part of the interface
+        assert_eq!(
+            vertex_shader.reflect_interface().unwrap(),
+            ShaderInterface {
+                inputs: vec![
+                    variable("Vertex_Position", 0, "vec3"),
+                    variable("Vertex_Uv", 1, "vec2"),
+                ],
+                outputs: vec![
+                    variable("v_Uv", 0, "vec2"),
+                    variable("v_Cell", 1, "ivec2"),
+                    variable("v_Basis", 2, "mat3x3"),
+                ],
+            }
+        );
+    }
 }
@@ -1,7 +1,13 @@
-use crate::shader::ShaderLayout;
+use crate::shader::{ShaderInterface, ShaderLayout};
 
 impl ShaderLayout {
     pub fn from_spirv(_spirv_data: &[u32], _bevy_conventions: bool) -> ShaderLayout {
         panic!("reflecting shader layout from spirv data is not available");
     }
 }
+
+impl ShaderInterface {
+    pub fn from_spirv(_spirv_data: &[u32]) -> ShaderInterface {
+        panic!("reflecting shader interface from spirv data is not available");
+    }
+}
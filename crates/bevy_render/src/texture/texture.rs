@@ -8,6 +8,7 @@ use bevy_ecs::{Res, ResMut};
 use bevy_math::Vec2;
 use bevy_type_registry::TypeUuid;
 use bevy_utils::HashSet;
+use bevy_window::WindowIcon;
 
 pub const TEXTURE_ASSET_INDEX: u64 = 0;
 pub const SAMPLER_ASSET_INDEX: u64 = 1;
@@ -19,6 +20,9 @@ pub struct Texture {
     pub size: Vec2,
     pub format: TextureFormat,
     pub sampler: SamplerDescriptor,
+    /// The texture is a cubemap. Its six faces are stacked vertically in the order +X, -X, +Y, -Y, +Z, -Z, so it is
+    /// six times as high as it is wide.
+    pub cubemap: bool,
 }
 
 impl Default for Texture {
@@ -28,6 +32,7 @@ impl Default for Texture {
             size: Default::default(),
             format: TextureFormat::Rgba8UnormSrgb,
             sampler: Default::default(),
+            cubemap: false,
         }
     }
 }
@@ -68,16 +73,162 @@ impl Texture {
         value
     }
 
+    /// A cubemap texture with square faces of the given size, filled with zeros. See [Texture::cubemap].
+    pub fn new_cubemap(face_size: u32, format: TextureFormat) -> Self {
+        let size = Vec2::new(face_size as f32, face_size as f32 * 6.0);
+        Self {
+            cubemap: true,
+            ..Texture::new(
+                size,
+                vec![0; face_size as usize * face_size as usize * 6 * format.pixel_size()],
+                format,
+            )
+        }
+    }
+
     pub fn aspect(&self) -> f32 {
         self.size.y() / self.size.x()
     }
 
+    /// Changes the size of the texture. Pixels keep their position, pixels outside the new size are dropped and new
+    /// pixels are zeroed.
     pub fn resize(&mut self, size: Vec2) {
-        self.size = size;
+        let old_width = self.width();
+        let old_height = self.height();
         let width = size.x() as usize;
         let height = size.y() as usize;
-        self.data
-            .resize(width * height * self.format.pixel_size(), 0);
+        let pixel_size = self.format.pixel_size();
+        if width == old_width || old_height == 0 {
+            self.data.resize(width * height * pixel_size, 0);
+        } else {
+            let mut data = vec![0; width * height * pixel_size];
+            let row_size = old_width.min(width) * pixel_size;
+            for y in 0..old_height.min(height) {
+                let source = y * old_width * pixel_size;
+                let destination = y * width * pixel_size;
+                data[destination..destination + row_size]
+                    .copy_from_slice(&self.data[source..source + row_size]);
+            }
+            self.data = data;
+        }
+        self.size = size;
+    }
+
+    fn width(&self) -> usize {
+        self.size.x() as usize
+    }
+
+    fn height(&self) -> usize {
+        self.size.y() as usize
+    }
+
+    fn pixel_range(&self, x: u32, y: u32) -> Option<std::ops::Range<usize>> {
+        let (x, y) = (x as usize, y as usize);
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+        let pixel_size = self.format.pixel_size();
+        let start = (y * self.width() + x) * pixel_size;
+        Some(start..start + pixel_size)
+    }
+
+    /// The bytes of the pixel at `x`, `y`, or None if it is outside the texture
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<&[u8]> {
+        self.pixel_range(x, y).map(|range| &self.data[range])
+    }
+
+    pub fn get_pixel_mut(&mut self, x: u32, y: u32) -> Option<&mut [u8]> {
+        let range = self.pixel_range(x, y)?;
+        Some(&mut self.data[range])
+    }
+
+    /// Sets the pixel at `x`, `y`. Returns false if it is outside the texture.
+    ///
+    /// Changing a texture through [Assets::get_mut] uploads it to the GPU again, so batch changes into one `get_mut`
+    /// per frame.
+    pub fn set_pixel(&mut self, x: u32, y: u32, pixel: &[u8]) -> bool {
+        debug_assert_eq!(
+            pixel.len(),
+            self.format.pixel_size(),
+            "Pixel data and format have to match"
+        );
+        if let Some(current_pixel) = self.get_pixel_mut(x, y) {
+            current_pixel.copy_from_slice(pixel);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sets every pixel to `pixel`
+    pub fn fill(&mut self, pixel: &[u8]) {
+        debug_assert_eq!(
+            pixel.len(),
+            self.format.pixel_size(),
+            "Pixel data and format have to match"
+        );
+        for current_pixel in self.data.chunks_exact_mut(pixel.len()) {
+            current_pixel.copy_from_slice(pixel);
+        }
+    }
+
+    /// Copies the `size` pixels at `source_origin` in `source` to `destination_origin` in this texture. The parts of
+    /// the region that are outside either texture are skipped. Both textures must have the same format.
+    pub fn copy_region(
+        &mut self,
+        source: &Texture,
+        source_origin: [u32; 2],
+        size: [u32; 2],
+        destination_origin: [u32; 2],
+    ) {
+        debug_assert_eq!(
+            self.format, source.format,
+            "Source and destination formats have to match"
+        );
+        let pixel_size = self.format.pixel_size();
+        let [source_x, source_y] = [source_origin[0] as usize, source_origin[1] as usize];
+        let [destination_x, destination_y] = [
+            destination_origin[0] as usize,
+            destination_origin[1] as usize,
+        ];
+        let width = (size[0] as usize)
+            .min(source.width().saturating_sub(source_x))
+            .min(self.width().saturating_sub(destination_x));
+        let height = (size[1] as usize)
+            .min(source.height().saturating_sub(source_y))
+            .min(self.height().saturating_sub(destination_y));
+        let row_size = width * pixel_size;
+        for row in 0..height {
+            let source_start = ((source_y + row) * source.width() + source_x) * pixel_size;
+            let destination_start =
+                ((destination_y + row) * self.width() + destination_x) * pixel_size;
+            self.data[destination_start..destination_start + row_size]
+                .copy_from_slice(&source.data[source_start..source_start + row_size]);
+        }
+    }
+
+    /// Converts this texture to a [WindowIcon]. Returns `None` if the texture's format can't be represented as RGBA8.
+    pub fn to_window_icon(&self) -> Option<WindowIcon> {
+        let rgba = match self.format {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => self.data.clone(),
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => self
+                .data
+                .chunks_exact(4)
+                .flat_map(|pixel| vec![pixel[2], pixel[1], pixel[0], pixel[3]])
+                .collect(),
+            TextureFormat::R8Unorm => self
+                .data
+                .iter()
+                .flat_map(|value| vec![*value, *value, *value, 255])
+                .collect(),
+            _ => return None,
+        };
+
+        Some(WindowIcon {
+            rgba,
+            width: self.size.x() as u32,
+            height: self.size.y() as u32,
+        })
     }
 
     pub fn texture_resource_system(
@@ -182,3 +333,51 @@ impl RenderResource for Handle<Texture> {
         Some(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Texture;
+    use crate::texture::{Extent3d, TextureDescriptor, TextureFormat, TextureViewDimension};
+    use bevy_math::Vec2;
+
+    #[test]
+    fn pixels_and_regions() {
+        let mut texture = Texture::new_fill(Vec2::new(4.0, 3.0), &[1], TextureFormat::R8Unorm);
+        assert_eq!(texture.get_pixel(3, 2), Some(&[1][..]));
+        assert_eq!(texture.get_pixel(4, 0), None);
+        assert!(texture.set_pixel(1, 2, &[7]));
+        assert!(!texture.set_pixel(0, 3, &[7]));
+        assert_eq!(texture.data[2 * 4 + 1], 7);
+
+        // pixels keep their position when the texture is resized
+        texture.resize(Vec2::new(2.0, 4.0));
+        assert_eq!(texture.data, vec![1, 1, 1, 1, 1, 7, 0, 0]);
+
+        let mut decal = Texture::new_fill(Vec2::new(3.0, 3.0), &[9], TextureFormat::R8Unorm);
+        decal.set_pixel(0, 0, &[5]);
+        // the region is clipped to the destination
+        texture.copy_region(&decal, [0, 0], [3, 3], [1, 2]);
+        assert_eq!(texture.data, vec![1, 1, 1, 1, 1, 5, 0, 9]);
+
+        texture.fill(&[3]);
+        assert!(texture.data.iter().all(|value| *value == 3));
+    }
+
+    #[test]
+    fn cubemap_layers() {
+        let cubemap = Texture::new_cubemap(8, TextureFormat::Rgba8Unorm);
+        assert_eq!(cubemap.size, Vec2::new(8.0, 48.0));
+        assert_eq!(cubemap.data.len(), 8 * 48 * 4);
+
+        let descriptor = TextureDescriptor::from(&cubemap);
+        assert_eq!(
+            descriptor.size,
+            Extent3d {
+                width: 8,
+                height: 8,
+                depth: 6
+            }
+        );
+        assert_eq!(descriptor.view_dimension, TextureViewDimension::Cube);
+    }
+}
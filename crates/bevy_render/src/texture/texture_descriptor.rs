@@ -1,4 +1,6 @@
-use super::{Extent3d, Texture, TextureDimension, TextureFormat, TextureUsage};
+use super::{
+    Extent3d, Texture, TextureDimension, TextureFormat, TextureUsage, TextureViewDimension,
+};
 
 /// Describes a texture
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -9,21 +11,31 @@ pub struct TextureDescriptor {
     pub dimension: TextureDimension,
     pub format: TextureFormat,
     pub usage: TextureUsage,
+    /// How shaders see the texture. [TextureViewDimension::Cube] needs a [TextureDimension::D2] texture with a depth
+    /// of 6.
+    pub view_dimension: TextureViewDimension,
 }
 
 impl From<&Texture> for TextureDescriptor {
     fn from(texture: &Texture) -> Self {
+        // the faces of a cubemap are stacked vertically, and each becomes a layer
+        let (layers, view_dimension) = if texture.cubemap {
+            (6, TextureViewDimension::Cube)
+        } else {
+            (1, TextureViewDimension::D2)
+        };
         TextureDescriptor {
             size: Extent3d {
                 width: texture.size.x() as u32,
-                height: texture.size.y() as u32,
-                depth: 1,
+                height: texture.size.y() as u32 / layers,
+                depth: layers,
             },
             mip_level_count: 1,
             sample_count: 1,
             dimension: TextureDimension::D2,
             format: texture.format,
             usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
+            view_dimension,
         }
     }
 }
@@ -41,6 +53,7 @@ impl Default for TextureDescriptor {
             dimension: TextureDimension::D2,
             format: TextureFormat::Rgba8UnormSrgb,
             usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
+            view_dimension: TextureViewDimension::D2,
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::{serde::SceneSerializer, Scene};
 use anyhow::Result;
+use bevy_asset::Handle;
 use bevy_ecs::{EntityMap, Resources, World};
 use bevy_property::{DynamicProperties, PropertyTypeRegistry};
 use bevy_type_registry::{ComponentRegistry, TypeRegistry, TypeUuid};
@@ -16,6 +17,8 @@ pub enum DynamicSceneToWorldError {
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
     pub entities: Vec<Entity>,
+    /// Instances of other scenes that are spawned along with the entities of this scene
+    pub instances: Vec<SceneInstance>,
 }
 
 pub struct Entity {
@@ -23,6 +26,18 @@ pub struct Entity {
     pub components: Vec<DynamicProperties>,
 }
 
+/// An instance of another scene in a [DynamicScene], with overrides for the components of its entities. This is how a
+/// scene file places a prefab with per-instance changes, like an enemy with more health or a different material.
+pub struct SceneInstance {
+    /// The asset path of the instanced scene. This is what gets serialized.
+    pub path: String,
+    /// The instanced scene. The scene loader sets this from `path`.
+    pub scene: Handle<DynamicScene>,
+    /// Entity ids refer to entities of the instanced scene, and components only need to contain the overridden
+    /// properties. Entities and components that don't exist in the instanced scene are added.
+    pub overrides: Vec<Entity>,
+}
+
 impl DynamicScene {
     pub fn from_scene(scene: &Scene, component_registry: &ComponentRegistry) -> Self {
         Self::from_world(&scene.world, component_registry)
@@ -55,11 +70,13 @@ impl DynamicScene {
         scene
     }
 
+    /// Spawns the entities of this scene into the given world, returning the mapping from scene entities to
+    /// the newly spawned entities. [SceneInstance]s are only spawned by the [SceneSpawner](crate::SceneSpawner).
     pub fn write_to_world(
         &self,
         world: &mut World,
         resources: &Resources,
-    ) -> Result<(), DynamicSceneToWorldError> {
+    ) -> Result<EntityMap, DynamicSceneToWorldError> {
         let type_registry = resources.get::<TypeRegistry>().unwrap();
         let component_registry = type_registry.component.read();
         let mut entity_map = EntityMap::default();
@@ -87,7 +104,7 @@ impl DynamicScene {
                 .unwrap();
         }
 
-        Ok(())
+        Ok(entity_map)
     }
 
     // TODO: move to AssetSaver when it is implemented
@@ -115,3 +132,123 @@ where
     serialize.serialize(&mut ron_serializer)?;
     Ok(String::from_utf8(buf).unwrap())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DynamicScene;
+    use crate::serde::SceneDeserializer;
+    use bevy_ecs::World;
+    use bevy_property::{Properties, PropertyTypeRegistry};
+    use bevy_type_registry::ComponentRegistry;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Properties, Default)]
+    struct ComponentA {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Properties, Default)]
+    struct ComponentB {
+        value: String,
+    }
+
+    #[test]
+    fn serialize_ron_round_trip() {
+        let mut component_registry = ComponentRegistry::default();
+        component_registry.register::<ComponentA>();
+        component_registry.register::<ComponentB>();
+        let mut property_registry = PropertyTypeRegistry::default();
+        property_registry.register::<ComponentA>();
+        property_registry.register::<ComponentB>();
+
+        let mut world = World::new();
+        world.spawn((
+            ComponentA { x: 1.0, y: 2.0 },
+            ComponentB {
+                value: "hello".to_string(),
+            },
+        ));
+        world.spawn((ComponentA { x: 3.0, y: 4.0 }, 5u32));
+
+        let scene = DynamicScene::from_world(&world, &component_registry);
+        let ron = scene.serialize_ron(&property_registry).unwrap();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            property_type_registry: &property_registry,
+        };
+        let deserialized = scene_deserializer.deserialize(&mut deserializer).unwrap();
+
+        assert_eq!(deserialized.entities.len(), 2);
+        for (entity, deserialized_entity) in scene.entities.iter().zip(deserialized.entities.iter())
+        {
+            assert_eq!(entity.entity, deserialized_entity.entity);
+            assert_eq!(
+                entity.components.len(),
+                deserialized_entity.components.len()
+            );
+        }
+
+        // unregistered components (u32) are not serialized
+        let component_count = deserialized
+            .entities
+            .iter()
+            .map(|entity| entity.components.len())
+            .sum::<usize>();
+        assert_eq!(component_count, 3);
+        assert_eq!(deserialized.serialize_ron(&property_registry).unwrap(), ron);
+    }
+
+    #[test]
+    fn deserialize_scene_instances_with_overrides() {
+        let mut property_registry = PropertyTypeRegistry::default();
+        property_registry.register::<ComponentA>();
+        let deserialize = |ron: &str| {
+            let mut deserializer = ron::de::Deserializer::from_str(ron).unwrap();
+            SceneDeserializer {
+                property_type_registry: &property_registry,
+            }
+            .deserialize(&mut deserializer)
+            .unwrap()
+        };
+
+        let scene = deserialize(
+            r#"(
+                entities: [
+                    (entity: 0, components: []),
+                ],
+                instances: [
+                    (
+                        scene: "scenes/enemy.scn",
+                        overrides: [
+                            (
+                                entity: 1,
+                                components: [
+                                    {
+                                        "type": "ComponentA",
+                                        "map": { "x": 10.0 },
+                                    },
+                                ],
+                            ),
+                        ],
+                    ),
+                ],
+            )"#,
+        );
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.instances.len(), 1);
+        let instance = &scene.instances[0];
+        assert_eq!(instance.path, "scenes/enemy.scn");
+        assert_eq!(instance.overrides[0].entity, 1);
+        // overrides only contain the overridden properties
+        let component = &instance.overrides[0].components[0];
+        assert_eq!(component.prop_len(), 1);
+        assert_eq!(component.prop_name(0), Some("x"));
+
+        let ron = scene.serialize_ron(&property_registry).unwrap();
+        let deserialized = deserialize(&ron);
+        assert_eq!(deserialized.instances[0].path, "scenes/enemy.scn");
+        assert_eq!(deserialized.serialize_ron(&property_registry).unwrap(), ron);
+    }
+}
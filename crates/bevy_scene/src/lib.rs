@@ -4,15 +4,17 @@ mod scene;
 mod scene_loader;
 mod scene_spawner;
 pub mod serde;
+mod world_snapshot;
 
 pub use command::*;
 pub use dynamic_scene::*;
 pub use scene::*;
 pub use scene_loader::*;
 pub use scene_spawner::*;
+pub use world_snapshot::*;
 
 pub mod prelude {
-    pub use crate::{DynamicScene, Scene, SceneSpawner, SpawnSceneCommands};
+    pub use crate::{DynamicScene, Scene, SceneSpawner, SnapshotWorld, SpawnSceneCommands};
 }
 
 use bevy_app::prelude::*;
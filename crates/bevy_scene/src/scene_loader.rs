@@ -1,6 +1,6 @@
 use crate::serde::SceneDeserializer;
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
 use bevy_ecs::{FromResources, Resources};
 use bevy_property::PropertyTypeRegistry;
 use bevy_type_registry::TypeRegistry;
@@ -35,8 +35,14 @@ impl AssetLoader for SceneLoader {
             let scene_deserializer = SceneDeserializer {
                 property_type_registry: &registry,
             };
-            let scene = scene_deserializer.deserialize(&mut deserializer)?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            let mut scene = scene_deserializer.deserialize(&mut deserializer)?;
+            let mut dependencies = Vec::new();
+            for instance in scene.instances.iter_mut() {
+                let path = AssetPath::from(instance.path.as_str());
+                instance.scene = load_context.get_handle(path.clone());
+                dependencies.push(path.to_owned());
+            }
+            load_context.set_default_asset(LoadedAsset::new(scene).with_dependencies(dependencies));
             Ok(())
         })
     }
@@ -1,19 +1,41 @@
-use crate::{DynamicScene, Scene};
+use crate::{dynamic_scene, DynamicScene, Scene};
 use bevy_app::prelude::*;
 use bevy_asset::{AssetEvent, Assets, Handle};
-use bevy_ecs::{EntityMap, Resources, World};
-use bevy_type_registry::TypeRegistry;
-use bevy_utils::HashMap;
+use bevy_ecs::{Entity, EntityMap, Resources, World};
+use bevy_type_registry::{ComponentRegistry, TypeRegistry};
+use bevy_utils::{HashMap, HashSet};
+use std::any::TypeId;
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct InstanceInfo {
     entity_map: EntityMap,
+    /// The registered components each entity of a dynamic scene instance got from the scene and its overrides
+    scene_components: HashMap<Entity, HashSet<TypeId>>,
+    /// The instances of other scenes that the scene of a dynamic scene instance contains
+    instances: Vec<InstanceInfo>,
 }
 
+impl InstanceInfo {
+    fn entities(&self) -> Vec<Entity> {
+        let mut entities = self.entity_map.values().collect::<Vec<_>>();
+        for instance in self.instances.iter() {
+            entities.extend(instance.entities());
+        }
+        entities
+    }
+
+    fn despawn(&self, world: &mut World) {
+        for entity in self.entities() {
+            let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
+        }
+    }
+}
+
+/// Identifies a single spawned instance of a scene
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct InstanceId(Uuid);
+pub struct InstanceId(Uuid);
 
 impl InstanceId {
     pub fn new() -> Self {
@@ -21,15 +43,24 @@ impl InstanceId {
     }
 }
 
+impl Default for InstanceId {
+    fn default() -> Self {
+        InstanceId::new()
+    }
+}
+
 #[derive(Default)]
 pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: EventReader<AssetEvent<DynamicScene>>,
-    dynamic_scenes_to_spawn: Vec<Handle<DynamicScene>>,
-    scenes_to_spawn: Vec<Handle<Scene>>,
+    dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
+    scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
+    instances_to_despawn: Vec<InstanceId>,
+    instance_overrides: HashMap<InstanceId, Handle<DynamicScene>>,
+    scenes_to_update: Vec<Handle<DynamicScene>>,
 }
 
 #[derive(Error, Debug)]
@@ -40,51 +71,123 @@ pub enum SceneSpawnError {
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("Scene does not exist. Perhaps it is still loading?")]
     NonExistentRealScene { handle: Handle<Scene> },
+    #[error("Scene contains an instance of itself.")]
+    RecursiveScene { handle: Handle<DynamicScene> },
 }
 
 impl SceneSpawner {
-    pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) {
-        self.dynamic_scenes_to_spawn.push(scene_handle);
+    /// Queues a new instance of the given scene to be spawned. The returned [InstanceId] can be used to refer
+    /// to the instance once it has been spawned.
+    pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.dynamic_scenes_to_spawn
+            .push((scene_handle, instance_id));
+        instance_id
+    }
+
+    /// Queues a new instance of the given scene to be spawned, with the components in `overrides` applied on top
+    /// of it. Entity ids in the overrides scene refer to entities in the base scene and their components only need
+    /// to contain the patched properties. Entities and components that don't exist in the base scene are added.
+    /// Hot reloading either scene re-applies the overrides to the instance.
+    pub fn spawn_dynamic_with_overrides(
+        &mut self,
+        scene_handle: Handle<DynamicScene>,
+        overrides: Handle<DynamicScene>,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.instance_overrides.insert(instance_id, overrides);
+        self.dynamic_scenes_to_spawn
+            .push((scene_handle, instance_id));
+        instance_id
     }
 
-    pub fn spawn(&mut self, scene_handle: Handle<Scene>) {
-        self.scenes_to_spawn.push(scene_handle);
+    /// Queues a new instance of the given scene to be spawned. The returned [InstanceId] can be used to refer
+    /// to the instance once it has been spawned.
+    pub fn spawn(&mut self, scene_handle: Handle<Scene>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.scenes_to_spawn.push((scene_handle, instance_id));
+        instance_id
     }
 
+    /// Queues every spawned instance of the given scene to be despawned
     pub fn despawn(&mut self, scene_handle: Handle<DynamicScene>) {
         self.scenes_to_despawn.push(scene_handle);
     }
 
+    /// Queues a single spawned scene instance to be despawned
+    pub fn despawn_instance(&mut self, instance_id: InstanceId) {
+        self.instances_to_despawn.push(instance_id);
+    }
+
+    /// Returns true if the given instance has been spawned into the world
+    pub fn instance_is_ready(&self, instance_id: InstanceId) -> bool {
+        self.spawned_instances.contains_key(&instance_id)
+    }
+
+    /// Iterates over the entities that were spawned for the given instance
+    pub fn iter_instance_entities(
+        &self,
+        instance_id: InstanceId,
+    ) -> Option<impl Iterator<Item = Entity> + '_> {
+        self.spawned_instances
+            .get(&instance_id)
+            .map(|instance| instance.entities().into_iter())
+    }
+
     pub fn despawn_sync(
         &mut self,
         world: &mut World,
         scene_handle: Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_dynamic_scenes.get(&scene_handle) {
+        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&scene_handle) {
             for instance_id in instance_ids {
-                if let Some(instance) = self.spawned_instances.get(&instance_id) {
-                    for entity in instance.entity_map.values() {
-                        let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
-                    }
-                }
+                self.despawn_instance_sync(world, &instance_id);
             }
-
-            self.spawned_dynamic_scenes.remove(&scene_handle);
         }
         Ok(())
     }
 
+    pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: &InstanceId) {
+        if let Some(instance) = self.spawned_instances.remove(instance_id) {
+            instance.despawn(world);
+        }
+        self.instance_overrides.remove(instance_id);
+
+        for instance_ids in self
+            .spawned_dynamic_scenes
+            .values_mut()
+            .chain(self.spawned_scenes.values_mut())
+        {
+            instance_ids.retain(|id| id != instance_id);
+        }
+    }
+
     pub fn spawn_dynamic_sync(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handle: &Handle<DynamicScene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
         let instance_id = InstanceId::new();
-        let mut instance_info = InstanceInfo {
-            entity_map: EntityMap::default(),
-        };
-        Self::spawn_dynamic_internal(world, resources, scene_handle, &mut instance_info)?;
+        self.spawn_dynamic_instance_sync(world, resources, scene_handle, instance_id)?;
+        Ok(instance_id)
+    }
+
+    fn spawn_dynamic_instance_sync(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        scene_handle: &Handle<DynamicScene>,
+        instance_id: InstanceId,
+    ) -> Result<(), SceneSpawnError> {
+        let mut instance_info = InstanceInfo::default();
+        Self::write_dynamic_instance(
+            world,
+            resources,
+            scene_handle,
+            self.instance_overrides.get(&instance_id),
+            &mut instance_info,
+        )?;
         self.spawned_instances.insert(instance_id, instance_info);
         let spawned = self
             .spawned_dynamic_scenes
@@ -94,27 +197,135 @@ impl SceneSpawner {
         Ok(())
     }
 
-    fn spawn_dynamic_internal(
+    /// Spawns a dynamic scene instance, or updates it to the current version of its scene. Nothing is written unless
+    /// the scene, its overrides and every scene it instances are loaded, so an instance is never partially spawned.
+    fn write_dynamic_instance(
         world: &mut World,
         resources: &Resources,
         scene_handle: &Handle<DynamicScene>,
+        overrides: Option<&Handle<DynamicScene>>,
         instance_info: &mut InstanceInfo,
     ) -> Result<(), SceneSpawnError> {
         let type_registry = resources.get::<TypeRegistry>().unwrap();
         let component_registry = type_registry.component.read();
         let scenes = resources.get::<Assets<DynamicScene>>().unwrap();
+        for handle in std::iter::once(scene_handle).chain(overrides) {
+            Self::check_scene_is_loaded(&scenes, handle, &mut Vec::new())?;
+        }
+        let overrides = overrides.map_or(&[][..], |overrides| {
+            &scenes.get(overrides).unwrap().entities[..]
+        });
+        Self::write_scene_instance(
+            world,
+            resources,
+            &component_registry,
+            &scenes,
+            scene_handle,
+            overrides,
+            instance_info,
+        )
+    }
+
+    /// Checks that a scene and the scenes it instances are loaded, and that no scene instances itself
+    fn check_scene_is_loaded(
+        scenes: &Assets<DynamicScene>,
+        scene_handle: &Handle<DynamicScene>,
+        instancing_scenes: &mut Vec<Handle<DynamicScene>>,
+    ) -> Result<(), SceneSpawnError> {
+        if instancing_scenes.contains(scene_handle) {
+            return Err(SceneSpawnError::RecursiveScene {
+                handle: scene_handle.clone_weak(),
+            });
+        }
         let scene = scenes
             .get(scene_handle)
             .ok_or_else(|| SceneSpawnError::NonExistentScene {
                 handle: scene_handle.clone_weak(),
             })?;
+        instancing_scenes.push(scene_handle.clone_weak());
+        for instance in scene.instances.iter() {
+            Self::check_scene_is_loaded(scenes, &instance.scene, instancing_scenes)?;
+        }
+        instancing_scenes.pop();
+        Ok(())
+    }
+
+    fn write_scene_instance(
+        world: &mut World,
+        resources: &Resources,
+        component_registry: &ComponentRegistry,
+        scenes: &Assets<DynamicScene>,
+        scene_handle: &Handle<DynamicScene>,
+        overrides: &[dynamic_scene::Entity],
+        instance_info: &mut InstanceInfo,
+    ) -> Result<(), SceneSpawnError> {
+        let scene = scenes.get(scene_handle).unwrap();
+        Self::despawn_removed_entities(world, scene, overrides, instance_info);
 
-        for scene_entity in scene.entities.iter() {
+        let previous_components = std::mem::take(&mut instance_info.scene_components);
+        Self::write_scene_entities(
+            world,
+            resources,
+            component_registry,
+            &scene.entities,
+            instance_info,
+        )?;
+        Self::write_scene_entities(
+            world,
+            resources,
+            component_registry,
+            overrides,
+            instance_info,
+        )?;
+
+        if instance_info.instances.len() > scene.instances.len() {
+            for removed_instance in instance_info.instances.drain(scene.instances.len()..) {
+                removed_instance.despawn(world);
+            }
+        }
+        for (index, instance) in scene.instances.iter().enumerate() {
+            if index == instance_info.instances.len() {
+                instance_info.instances.push(InstanceInfo::default());
+            }
+            Self::write_scene_instance(
+                world,
+                resources,
+                component_registry,
+                scenes,
+                &instance.scene,
+                &instance.overrides,
+                &mut instance_info.instances[index],
+            )?;
+        }
+
+        Self::remove_stale_components(
+            world,
+            component_registry,
+            previous_components,
+            instance_info,
+        );
+        for component_registration in component_registry.iter() {
+            component_registration
+                .map_entities(world, &instance_info.entity_map)
+                .unwrap();
+        }
+        Ok(())
+    }
+
+    fn write_scene_entities(
+        world: &mut World,
+        resources: &Resources,
+        component_registry: &ComponentRegistry,
+        scene_entities: &[dynamic_scene::Entity],
+        instance_info: &mut InstanceInfo,
+    ) -> Result<(), SceneSpawnError> {
+        for scene_entity in scene_entities.iter() {
             let entity = *instance_info
                 .entity_map
                 // TODO: use Entity type directly in scenes to properly encode generation / avoid the need to patch things up?
-                .entry(bevy_ecs::Entity::new(scene_entity.entity))
+                .entry(Entity::new(scene_entity.entity))
                 .or_insert_with(|| world.reserve_entity());
+            let scene_components = instance_info.scene_components.entry(entity).or_default();
             for component in scene_entity.components.iter() {
                 let component_registration = component_registry
                     .get_with_name(&component.type_name)
@@ -129,21 +340,55 @@ impl SceneSpawner {
                     component_registration
                         .add_property_to_entity(world, resources, entity, component);
                 }
+                scene_components.insert(component_registration.ty);
             }
         }
         Ok(())
     }
 
+    /// Removes the components an instance got from a previous version of its scene that the current version doesn't
+    /// have anymore. Components that were added to the instance's entities by other means are kept.
+    fn remove_stale_components(
+        world: &mut World,
+        component_registry: &ComponentRegistry,
+        previous_components: HashMap<Entity, HashSet<TypeId>>,
+        instance_info: &InstanceInfo,
+    ) {
+        for (entity, previous_components) in previous_components {
+            let scene_components =
+                if let Some(scene_components) = instance_info.scene_components.get(&entity) {
+                    scene_components
+                } else {
+                    // the entity was removed from the scene and has been despawned
+                    continue;
+                };
+            for ty in previous_components.difference(scene_components) {
+                if let Some(component_registration) = component_registry.get(ty) {
+                    component_registration.remove_from_entity(world, entity);
+                }
+            }
+        }
+    }
+
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handle: Handle<Scene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
         let instance_id = InstanceId::new();
-        let mut instance_info = InstanceInfo {
-            entity_map: EntityMap::default(),
-        };
+        self.spawn_instance_sync(world, resources, scene_handle, instance_id)?;
+        Ok(instance_id)
+    }
+
+    fn spawn_instance_sync(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        scene_handle: Handle<Scene>,
+        instance_id: InstanceId,
+    ) -> Result<(), SceneSpawnError> {
+        let mut instance_info = InstanceInfo::default();
         let type_registry = resources.get::<TypeRegistry>().unwrap();
         let component_registry = type_registry.component.read();
         let scenes = resources.get::<Assets<Scene>>().unwrap();
@@ -193,29 +438,96 @@ impl SceneSpawner {
         resources: &Resources,
         scene_handles: &[Handle<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
-        for scene_handle in scene_handles {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
-                for instance_id in spawned_instances.iter() {
-                    if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
-                            world,
-                            resources,
-                            scene_handle,
-                            instance_info,
-                        )?;
+        let mut retry = false;
+        for (scene_handle, spawned_instances) in self.spawned_dynamic_scenes.iter() {
+            for instance_id in spawned_instances.iter() {
+                let overrides = self.instance_overrides.get(instance_id);
+                let updated = {
+                    let scenes = resources.get::<Assets<DynamicScene>>().unwrap();
+                    std::iter::once(scene_handle)
+                        .chain(overrides)
+                        .any(|handle| Self::scene_uses(&scenes, handle, scene_handles))
+                };
+                if !updated {
+                    continue;
+                }
+                if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+                    match Self::write_dynamic_instance(
+                        world,
+                        resources,
+                        scene_handle,
+                        overrides,
+                        instance_info,
+                    ) {
+                        Ok(()) => {}
+                        // a newly instanced scene is still loading
+                        Err(SceneSpawnError::NonExistentScene { .. }) => retry = true,
+                        Err(err) => return Err(err),
                     }
                 }
             }
         }
+        if retry {
+            // update again once the instanced scenes are loaded
+            self.scenes_to_update.extend(scene_handles.iter().cloned());
+        }
         Ok(())
     }
 
+    /// Returns true if the scene is one of `scene_handles`, or instances one of them
+    fn scene_uses(
+        scenes: &Assets<DynamicScene>,
+        scene_handle: &Handle<DynamicScene>,
+        scene_handles: &[Handle<DynamicScene>],
+    ) -> bool {
+        scene_handles.contains(scene_handle)
+            || scenes.get(scene_handle).map_or(false, |scene| {
+                scene
+                    .instances
+                    .iter()
+                    .any(|instance| Self::scene_uses(scenes, &instance.scene, scene_handles))
+            })
+    }
+
+    /// Despawns the entities of a spawned instance that no longer exist in its (modified) scene or its overrides
+    fn despawn_removed_entities(
+        world: &mut World,
+        scene: &DynamicScene,
+        overrides: &[dynamic_scene::Entity],
+        instance_info: &mut InstanceInfo,
+    ) {
+        let scene_entities = scene
+            .entities
+            .iter()
+            .chain(overrides)
+            .map(|scene_entity| Entity::new(scene_entity.entity))
+            .collect::<HashSet<_>>();
+        let removed_entities = instance_info
+            .entity_map
+            .keys()
+            .filter(|scene_entity| !scene_entities.contains(scene_entity))
+            .collect::<Vec<_>>();
+        for scene_entity in removed_entities {
+            if let Ok(entity) = instance_info.entity_map.get(scene_entity) {
+                let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
+                instance_info.scene_components.remove(&entity);
+            }
+            instance_info.entity_map.remove(scene_entity);
+        }
+    }
+
     pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
         let scenes_to_despawn = std::mem::take(&mut self.scenes_to_despawn);
 
         for scene_handle in scenes_to_despawn {
             self.despawn_sync(world, scene_handle)?;
         }
+
+        let instances_to_despawn = std::mem::take(&mut self.instances_to_despawn);
+
+        for instance_id in instances_to_despawn {
+            self.despawn_instance_sync(world, &instance_id);
+        }
         Ok(())
     }
 
@@ -226,23 +538,23 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_dynamic_sync(world, resources, &scene_handle) {
+        for (scene_handle, instance_id) in scenes_to_spawn {
+            match self.spawn_dynamic_instance_sync(world, resources, &scene_handle, instance_id) {
                 Ok(_) => {}
-                Err(SceneSpawnError::NonExistentScene { .. }) => {
-                    self.dynamic_scenes_to_spawn.push(scene_handle)
-                }
+                Err(SceneSpawnError::NonExistentScene { .. }) => self
+                    .dynamic_scenes_to_spawn
+                    .push((scene_handle, instance_id)),
                 Err(err) => return Err(err),
             }
         }
 
         let scenes_to_spawn = std::mem::take(&mut self.scenes_to_spawn);
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_sync(world, resources, scene_handle) {
+        for (scene_handle, instance_id) in scenes_to_spawn {
+            match self.spawn_instance_sync(world, resources, scene_handle, instance_id) {
                 Ok(_) => {}
                 Err(SceneSpawnError::NonExistentRealScene { handle }) => {
-                    self.scenes_to_spawn.push(handle)
+                    self.scenes_to_spawn.push((handle, instance_id))
                 }
                 Err(err) => return Err(err),
             }
@@ -256,15 +568,15 @@ pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
     let mut scene_spawner = resources.get_mut::<SceneSpawner>().unwrap();
     let scene_asset_events = resources.get::<Events<AssetEvent<DynamicScene>>>().unwrap();
 
-    let mut updated_spawned_scenes = Vec::new();
+    let mut updated_spawned_scenes = std::mem::take(&mut scene_spawner.scenes_to_update);
     for event in scene_spawner
         .scene_asset_event_reader
         .iter(&scene_asset_events)
     {
+        // scenes can be used by spawned instances as overrides or as instances in other scenes, so every modified
+        // scene is passed on to update_spawned_scenes to sort out
         if let AssetEvent::Modified { handle } = event {
-            if scene_spawner.spawned_dynamic_scenes.contains_key(handle) {
-                updated_spawned_scenes.push(handle.clone_weak());
-            }
+            updated_spawned_scenes.push(handle.clone_weak());
         }
     }
 
@@ -274,3 +586,81 @@ pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
         .update_spawned_scenes(world, resources, &updated_spawned_scenes)
         .unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{InstanceInfo, SceneSpawner};
+    use crate::{dynamic_scene, DynamicScene};
+    use bevy_ecs::{Resources, World};
+    use bevy_property::Properties;
+    use bevy_type_registry::ComponentRegistry;
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct ComponentA {
+        value: f32,
+    }
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct ComponentB {
+        value: f32,
+    }
+
+    fn scene(with_b: bool) -> DynamicScene {
+        let mut components = vec![ComponentA { value: 1.0 }.to_dynamic()];
+        if with_b {
+            components.push(ComponentB { value: 2.0 }.to_dynamic());
+        }
+        DynamicScene {
+            entities: vec![dynamic_scene::Entity {
+                entity: 0,
+                components,
+            }],
+            instances: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn reloading_removes_components_removed_from_the_scene() {
+        let mut component_registry = ComponentRegistry::default();
+        component_registry.register::<ComponentA>();
+        component_registry.register::<ComponentB>();
+        let mut world = World::new();
+        let resources = Resources::default();
+
+        let mut instance_info = InstanceInfo::default();
+        SceneSpawner::write_scene_entities(
+            &mut world,
+            &resources,
+            &component_registry,
+            &scene(true).entities,
+            &mut instance_info,
+        )
+        .unwrap();
+        let entity = instance_info.entity_map.values().next().unwrap();
+        // components that don't come from the scene are left alone
+        world.insert_one(entity, 5u32).unwrap();
+
+        let previous_components = std::mem::take(&mut instance_info.scene_components);
+        SceneSpawner::write_scene_entities(
+            &mut world,
+            &resources,
+            &component_registry,
+            &scene(false).entities,
+            &mut instance_info,
+        )
+        .unwrap();
+        SceneSpawner::remove_stale_components(
+            &mut world,
+            &component_registry,
+            previous_components,
+            &instance_info,
+        );
+
+        assert_eq!(
+            *world.get::<ComponentA>(entity).unwrap(),
+            ComponentA { value: 1.0 }
+        );
+        assert!(world.get::<ComponentB>(entity).is_err());
+        assert_eq!(*world.get::<u32>(entity).unwrap(), 5);
+    }
+}
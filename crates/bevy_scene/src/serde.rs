@@ -1,4 +1,4 @@
-use crate::{DynamicScene, Entity};
+use crate::{DynamicScene, Entity, SceneInstance, WorldSnapshot};
 use anyhow::Result;
 use bevy_property::{
     property_serde::{DynamicPropertiesDeserializer, DynamicPropertiesSerializer},
@@ -26,8 +26,40 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in self.scene.entities.iter() {
+        let entities = EntitiesSerializer {
+            entities: &self.scene.entities,
+            registry: self.registry,
+        };
+        // scenes without instances are written as a plain list of entities
+        if self.scene.instances.is_empty() {
+            return entities.serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        state.serialize_field(SCENE_FIELD_ENTITIES, &entities)?;
+        state.serialize_field(
+            SCENE_FIELD_INSTANCES,
+            &SceneInstancesSerializer {
+                instances: &self.scene.instances,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [Entity],
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities.iter() {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -37,6 +69,50 @@ impl<'a> Serialize for SceneSerializer<'a> {
     }
 }
 
+pub struct SceneInstancesSerializer<'a> {
+    pub instances: &'a [SceneInstance],
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for SceneInstancesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.instances.len()))?;
+        for instance in self.instances.iter() {
+            state.serialize_element(&SceneInstanceSerializer {
+                instance,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+pub struct SceneInstanceSerializer<'a> {
+    pub instance: &'a SceneInstance,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for SceneInstanceSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(INSTANCE_STRUCT, 2)?;
+        state.serialize_field(INSTANCE_FIELD_SCENE, &self.instance.path)?;
+        state.serialize_field(
+            INSTANCE_FIELD_OVERRIDES,
+            &EntitiesSerializer {
+                entities: &self.instance.overrides,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
 pub struct EntitySerializer<'a> {
     pub entity: &'a Entity,
     pub registry: &'a PropertyTypeRegistry,
@@ -88,16 +164,221 @@ pub struct SceneDeserializer<'a> {
 impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     type Value = DynamicScene;
 
+    /// Reads either a list of entities, or a struct with a list of `entities` and a list of `instances` of other
+    /// scenes. The handles of the instanced scenes are left at their default, the scene loader sets them.
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SceneVisiter {
+            registry: self.property_type_registry,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Entities,
+    Instances,
+}
+
+pub const SCENE_STRUCT: &str = "DynamicScene";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+pub const SCENE_FIELD_INSTANCES: &str = "instances";
+
+struct SceneVisiter<'a> {
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisiter<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of entities or scene")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Ok(DynamicScene {
+            entities: SceneEntitySeqVisiter {
+                property_type_registry: self.registry,
+            }
+            .visit_seq(seq)?,
+            instances: Vec::new(),
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = None;
+        let mut instances = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
+                        property_type_registry: self.registry,
+                    })?);
+                }
+                SceneField::Instances => {
+                    if instances.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_INSTANCES));
+                    }
+                    instances = Some(map.next_value_seed(SceneInstancesDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        Ok(DynamicScene {
+            entities: entities.unwrap_or_default(),
+            instances: instances.unwrap_or_default(),
+        })
+    }
+}
+
+pub struct SceneEntitiesDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+    type Value = Vec<Entity>;
+
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let mut scene = DynamicScene::default();
-        scene.entities = deserializer.deserialize_seq(SceneEntitySeqVisiter {
+        deserializer.deserialize_seq(SceneEntitySeqVisiter {
             property_type_registry: self.property_type_registry,
-        })?;
+        })
+    }
+}
+
+struct SceneInstancesDeserializer<'a> {
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneInstancesDeserializer<'a> {
+    type Value = Vec<SceneInstance>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneInstanceSeqVisiter {
+            registry: self.registry,
+        })
+    }
+}
+
+struct SceneInstanceSeqVisiter<'a> {
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneInstanceSeqVisiter<'a> {
+    type Value = Vec<SceneInstance>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of scene instances")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut instances = Vec::new();
+        while let Some(instance) = seq.next_element_seed(SceneInstanceDeserializer {
+            registry: self.registry,
+        })? {
+            instances.push(instance);
+        }
 
-        Ok(scene)
+        Ok(instances)
+    }
+}
+
+struct SceneInstanceDeserializer<'a> {
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneInstanceDeserializer<'a> {
+    type Value = SceneInstance;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            INSTANCE_STRUCT,
+            &[INSTANCE_FIELD_SCENE, INSTANCE_FIELD_OVERRIDES],
+            SceneInstanceVisiter {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum InstanceField {
+    Scene,
+    Overrides,
+}
+
+pub const INSTANCE_STRUCT: &str = "SceneInstance";
+pub const INSTANCE_FIELD_SCENE: &str = "scene";
+pub const INSTANCE_FIELD_OVERRIDES: &str = "overrides";
+
+struct SceneInstanceVisiter<'a> {
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneInstanceVisiter<'a> {
+    type Value = SceneInstance;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene instance")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut path = None;
+        let mut overrides = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                InstanceField::Scene => {
+                    if path.is_some() {
+                        return Err(Error::duplicate_field(INSTANCE_FIELD_SCENE));
+                    }
+                    path = Some(map.next_value::<String>()?);
+                }
+                InstanceField::Overrides => {
+                    if overrides.is_some() {
+                        return Err(Error::duplicate_field(INSTANCE_FIELD_OVERRIDES));
+                    }
+                    overrides = Some(map.next_value_seed(SceneEntitiesDeserializer {
+                        property_type_registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        let path = path.ok_or_else(|| Error::missing_field(INSTANCE_FIELD_SCENE))?;
+        Ok(SceneInstance {
+            path,
+            scene: Default::default(),
+            overrides: overrides.unwrap_or_default(),
+        })
     }
 }
 
@@ -253,3 +534,115 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisiter<'a> {
         Ok(dynamic_properties)
     }
 }
+
+pub struct WorldSnapshotSerializer<'a> {
+    pub snapshot: &'a WorldSnapshot,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> WorldSnapshotSerializer<'a> {
+    pub fn new(snapshot: &'a WorldSnapshot, registry: &'a PropertyTypeRegistry) -> Self {
+        WorldSnapshotSerializer { snapshot, registry }
+    }
+}
+
+impl<'a> Serialize for WorldSnapshotSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(SNAPSHOT_STRUCT, 2)?;
+        state.serialize_field(
+            SNAPSHOT_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.snapshot.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SNAPSHOT_FIELD_ENTITIES,
+            &SceneSerializer::new(&self.snapshot.scene, self.registry),
+        )?;
+        state.end()
+    }
+}
+
+pub struct WorldSnapshotDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for WorldSnapshotDeserializer<'a> {
+    type Value = WorldSnapshot;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SNAPSHOT_STRUCT,
+            &[SNAPSHOT_FIELD_RESOURCES, SNAPSHOT_FIELD_ENTITIES],
+            WorldSnapshotVisiter {
+                registry: self.property_type_registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SnapshotField {
+    Resources,
+    Entities,
+}
+
+pub const SNAPSHOT_STRUCT: &str = "WorldSnapshot";
+pub const SNAPSHOT_FIELD_RESOURCES: &str = "resources";
+pub const SNAPSHOT_FIELD_ENTITIES: &str = "entities";
+
+struct WorldSnapshotVisiter<'a> {
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for WorldSnapshotVisiter<'a> {
+    type Value = WorldSnapshot;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("world snapshot")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut scene = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SnapshotField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SNAPSHOT_FIELD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                SnapshotField::Entities => {
+                    if scene.is_some() {
+                        return Err(Error::duplicate_field(SNAPSHOT_FIELD_ENTITIES));
+                    }
+                    scene = Some(map.next_value_seed(SceneDeserializer {
+                        property_type_registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        let resources = resources.ok_or_else(|| Error::missing_field(SNAPSHOT_FIELD_RESOURCES))?;
+        let scene = scene.ok_or_else(|| Error::missing_field(SNAPSHOT_FIELD_ENTITIES))?;
+        Ok(WorldSnapshot {
+            scene,
+            resources,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    dynamic_scene::serialize_ron, serde::WorldSnapshotSerializer, DynamicScene,
+    DynamicSceneToWorldError,
+};
+use bevy_ecs::{Entity, EntityMap, Resources, World};
+use bevy_property::{DynamicProperties, PropertyTypeRegistry};
+use bevy_type_registry::{ResourceRegistryError, TypeRegistry};
+use bevy_utils::HashSet;
+use thiserror::Error;
+
+/// A copy of the registered components and resources in a [World]. Snapshots can be restored later (ex: to roll
+/// back game state) or serialized to implement save games.
+#[derive(Default)]
+pub struct WorldSnapshot {
+    pub scene: DynamicScene,
+    pub resources: Vec<DynamicProperties>,
+    /// The world entities that currently hold this snapshot's entities: the captured entities, or the ones spawned
+    /// by the last restore. Restoring only despawns these. Not serialized.
+    pub entities: Vec<Entity>,
+}
+
+#[derive(Error, Debug)]
+pub enum WorldSnapshotError {
+    #[error("Snapshot contains an unregistered component.")]
+    UnregisteredComponent { type_name: String },
+    #[error("Snapshot contains an unregistered resource.")]
+    UnregisteredResource { type_name: String },
+    #[error("Snapshot contains a resource name shared by several resources.")]
+    AmbiguousResource { type_name: String },
+}
+
+impl From<ResourceRegistryError> for WorldSnapshotError {
+    fn from(error: ResourceRegistryError) -> Self {
+        match error {
+            ResourceRegistryError::Unregistered { type_name } => {
+                WorldSnapshotError::UnregisteredResource { type_name }
+            }
+            ResourceRegistryError::AmbiguousName { type_name } => {
+                WorldSnapshotError::AmbiguousResource { type_name }
+            }
+        }
+    }
+}
+
+impl From<DynamicSceneToWorldError> for WorldSnapshotError {
+    fn from(error: DynamicSceneToWorldError) -> Self {
+        match error {
+            DynamicSceneToWorldError::UnregisteredComponent { type_name } => {
+                WorldSnapshotError::UnregisteredComponent { type_name }
+            }
+        }
+    }
+}
+
+impl WorldSnapshot {
+    /// Captures every registered component in the world and every registered resource
+    pub fn from_world(world: &World, resources: &Resources) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let mut scene = DynamicScene::from_world(world, &type_registry.component.read());
+        scene
+            .entities
+            .retain(|entity| !entity.components.is_empty());
+        let captured = scene
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .collect::<HashSet<_>>();
+        let entities = world
+            .archetypes()
+            .flat_map(|archetype| archetype.iter_entities())
+            .filter(|entity| captured.contains(&entity.id()))
+            .copied()
+            .collect();
+        let resource_registry = type_registry.resource.read();
+        let resources = resource_registry
+            .iter()
+            .filter_map(|registration| registration.get_dynamic(resources))
+            .collect();
+        WorldSnapshot {
+            scene,
+            resources,
+            entities,
+        }
+    }
+
+    /// Replaces the state of the world with this snapshot. The entities this snapshot captured (or spawned on its
+    /// last restore) are despawned and the snapshot's entities are spawned in their place. Other entities are left
+    /// untouched. Restored entities get new ids, so the returned [EntityMap] maps snapshot entities to their
+    /// restored counterparts.
+    pub fn restore(
+        &mut self,
+        world: &mut World,
+        resources: &mut Resources,
+    ) -> Result<EntityMap, WorldSnapshotError> {
+        let type_registry = TypeRegistry::clone(&resources.get::<TypeRegistry>().unwrap());
+
+        // validate the whole snapshot first so a failed restore doesn't leave the world half-restored
+        {
+            let component_registry = type_registry.component.read();
+            for component in self
+                .scene
+                .entities
+                .iter()
+                .flat_map(|entity| entity.components.iter())
+            {
+                if component_registry
+                    .get_with_name(&component.type_name)
+                    .is_none()
+                {
+                    return Err(WorldSnapshotError::UnregisteredComponent {
+                        type_name: component.type_name.to_string(),
+                    });
+                }
+            }
+
+            let resource_registry = type_registry.resource.read();
+            for resource in self.resources.iter() {
+                resource_registry.get_with_name(&resource.type_name)?;
+            }
+        }
+
+        for entity in self.entities.drain(..) {
+            // the entity might have been despawned since
+            let _ = world.despawn(entity);
+        }
+
+        let entity_map = self.scene.write_to_world(world, resources)?;
+        self.entities = entity_map.values().collect();
+
+        let resource_registry = type_registry.resource.read();
+        for resource in self.resources.iter() {
+            let registration = resource_registry.get_with_name(&resource.type_name)?;
+            registration.apply_property(resources, resource);
+        }
+
+        Ok(entity_map)
+    }
+
+    pub fn serialize_ron(&self, registry: &PropertyTypeRegistry) -> Result<String, ron::Error> {
+        serialize_ron(WorldSnapshotSerializer::new(self, registry))
+    }
+}
+
+/// Adds [WorldSnapshot] support to [World]
+pub trait SnapshotWorld {
+    fn snapshot(&self, resources: &Resources) -> WorldSnapshot;
+    fn restore(
+        &mut self,
+        resources: &mut Resources,
+        snapshot: &mut WorldSnapshot,
+    ) -> Result<EntityMap, WorldSnapshotError>;
+}
+
+impl SnapshotWorld for World {
+    fn snapshot(&self, resources: &Resources) -> WorldSnapshot {
+        WorldSnapshot::from_world(self, resources)
+    }
+
+    fn restore(
+        &mut self,
+        resources: &mut Resources,
+        snapshot: &mut WorldSnapshot,
+    ) -> Result<EntityMap, WorldSnapshotError> {
+        snapshot.restore(self, resources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SnapshotWorld, WorldSnapshot};
+    use crate::serde::WorldSnapshotDeserializer;
+    use bevy_ecs::{Resources, World};
+    use bevy_property::Properties;
+    use bevy_type_registry::TypeRegistry;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Properties, Default, Debug, PartialEq)]
+    struct Score {
+        value: u32,
+    }
+
+    fn setup() -> (World, Resources) {
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register::<Position>();
+        type_registry.property.write().register::<Position>();
+        type_registry.resource.write().register::<Score>();
+        type_registry.property.write().register::<Score>();
+        let mut resources = Resources::default();
+        resources.insert(type_registry);
+        (World::new(), resources)
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let (mut world, mut resources) = setup();
+        resources.insert(Score { value: 10 });
+        world.spawn((Position { x: 1.0, y: 2.0 },));
+        let unregistered = world.spawn((5u32,));
+
+        let mut snapshot = world.snapshot(&resources);
+        assert_eq!(snapshot.scene.entities.len(), 1);
+        assert_eq!(snapshot.resources.len(), 1);
+
+        for mut position in world.query_mut::<&mut Position>() {
+            position.x = 100.0;
+        }
+        resources.get_mut::<Score>().unwrap().value = 20;
+
+        let entity_map = world.restore(&mut resources, &mut snapshot).unwrap();
+        let positions = world.query::<&Position>().collect::<Vec<_>>();
+        assert_eq!(positions, vec![&Position { x: 1.0, y: 2.0 }]);
+        assert_eq!(entity_map.keys().count(), 1);
+        assert_eq!(*resources.get::<Score>().unwrap(), Score { value: 10 });
+        assert_eq!(*world.get::<u32>(unregistered).unwrap(), 5);
+    }
+
+    #[test]
+    fn restore_only_despawns_snapshot_entities() {
+        let (mut world, mut resources) = setup();
+        world.spawn((Position { x: 1.0, y: 2.0 },));
+        let mut snapshot = world.snapshot(&resources);
+
+        let spawned_later = world.spawn((Position { x: 3.0, y: 4.0 },));
+        world.restore(&mut resources, &mut snapshot).unwrap();
+        // restoring twice replaces the entities spawned by the first restore instead of duplicating them
+        let entity_map = world.restore(&mut resources, &mut snapshot).unwrap();
+
+        let restored = entity_map.values().next().unwrap();
+        assert_eq!(
+            *world.get::<Position>(restored).unwrap(),
+            Position { x: 1.0, y: 2.0 }
+        );
+        assert_eq!(
+            *world.get::<Position>(spawned_later).unwrap(),
+            Position { x: 3.0, y: 4.0 }
+        );
+        assert_eq!(world.query::<&Position>().count(), 2);
+    }
+
+    #[test]
+    fn serialize_ron_round_trip() {
+        let (mut world, mut resources) = setup();
+        resources.insert(Score { value: 3 });
+        world.spawn((Position { x: 1.0, y: 2.0 },));
+        let ron = world
+            .snapshot(&resources)
+            .serialize_ron(&resources.get::<TypeRegistry>().unwrap().property.read());
+        let ron = ron.unwrap();
+
+        let (mut loaded_world, mut loaded_resources) = setup();
+        let mut snapshot: WorldSnapshot = {
+            let type_registry = loaded_resources.get::<TypeRegistry>().unwrap();
+            let property_type_registry = type_registry.property.read();
+            let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+            WorldSnapshotDeserializer {
+                property_type_registry: &property_type_registry,
+            }
+            .deserialize(&mut deserializer)
+            .unwrap()
+        };
+        loaded_world
+            .restore(&mut loaded_resources, &mut snapshot)
+            .unwrap();
+
+        let positions = loaded_world.query::<&Position>().collect::<Vec<_>>();
+        assert_eq!(positions, vec![&Position { x: 1.0, y: 2.0 }]);
+        assert_eq!(
+            *loaded_resources.get::<Score>().unwrap(),
+            Score { value: 3 }
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use crate::TextureAtlasSprite;
+use bevy_app::Events;
+use bevy_core::Time;
+use bevy_ecs::{Entity, Query, Res, ResMut};
+use bevy_utils::HashMap;
+use std::ops::Range;
+
+/// One frame of a [SpriteAnimationClip]: an index into the sprite's [TextureAtlas](crate::TextureAtlas) and how long
+/// it is shown, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationFrame {
+    pub index: u32,
+    pub duration: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationMode {
+    /// Plays the clip once and stops on the last frame
+    Once,
+    /// Starts over from the first frame after the last one
+    Loop,
+    /// Plays the clip forwards, then backwards, then forwards again
+    PingPong,
+}
+
+impl Default for AnimationMode {
+    fn default() -> Self {
+        AnimationMode::Loop
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpriteAnimationClip {
+    pub frames: Vec<AnimationFrame>,
+    pub mode: AnimationMode,
+}
+
+impl SpriteAnimationClip {
+    /// Creates a clip that shows a range of atlas indices for the same duration each
+    pub fn from_indices(indices: Range<u32>, frame_duration: f32, mode: AnimationMode) -> Self {
+        SpriteAnimationClip {
+            frames: indices
+                .map(|index| AnimationFrame {
+                    index,
+                    duration: frame_duration,
+                })
+                .collect(),
+            mode,
+        }
+    }
+}
+
+/// Sent when an [AnimatedSprite] finishes playing a clip. Looping clips send it each time they complete a cycle.
+#[derive(Debug, Clone)]
+pub struct SpriteAnimationFinished {
+    pub entity: Entity,
+    pub clip: String,
+}
+
+/// Plays named [SpriteAnimationClip]s by changing the index of the entity's [TextureAtlasSprite].
+#[derive(Debug, Clone)]
+pub struct AnimatedSprite {
+    pub clips: HashMap<String, SpriteAnimationClip>,
+    /// Multiplies the speed clips are played at
+    pub speed: f32,
+    current_clip: Option<String>,
+    frame: usize,
+    elapsed: f32,
+    reverse: bool,
+    finished: bool,
+}
+
+impl Default for AnimatedSprite {
+    fn default() -> Self {
+        AnimatedSprite {
+            clips: Default::default(),
+            speed: 1.0,
+            current_clip: None,
+            frame: 0,
+            elapsed: 0.0,
+            reverse: false,
+            finished: false,
+        }
+    }
+}
+
+impl AnimatedSprite {
+    pub fn with_clip<S: Into<String>>(mut self, name: S, clip: SpriteAnimationClip) -> Self {
+        self.clips.insert(name.into(), clip);
+        self
+    }
+
+    /// Plays the clip with the given name from its first frame, unless it's already playing
+    pub fn play(&mut self, name: &str) {
+        if self.current_clip.as_deref() == Some(name) && !self.finished {
+            return;
+        }
+        self.restart(name);
+    }
+
+    /// Plays the clip with the given name from its first frame
+    pub fn restart(&mut self, name: &str) {
+        self.current_clip = Some(name.to_string());
+        self.frame = 0;
+        self.elapsed = 0.0;
+        self.reverse = false;
+        self.finished = false;
+    }
+
+    pub fn stop(&mut self) {
+        self.current_clip = None;
+    }
+
+    pub fn current_clip(&self) -> Option<&str> {
+        self.current_clip.as_deref()
+    }
+
+    /// The index of the current frame in the current clip
+    pub fn current_frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Whether a clip played with [AnimationMode::Once] reached its end
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances the current clip by `delta` seconds. Returns the atlas index to show, and whether the clip finished
+    /// or completed a cycle.
+    pub fn tick(&mut self, delta: f32) -> Option<(u32, bool)> {
+        let clip = self.clips.get(self.current_clip.as_ref()?)?;
+        if clip.frames.is_empty() {
+            return None;
+        }
+        // the clip's frames might have been shortened while it was playing
+        self.frame = self.frame.min(clip.frames.len() - 1);
+
+        let mut completed = false;
+        if !self.finished {
+            self.elapsed += delta * self.speed;
+            // frames with no duration would never advance the animation
+            while self.elapsed >= clip.frames[self.frame].duration
+                && clip.frames[self.frame].duration > 0.0
+            {
+                self.elapsed -= clip.frames[self.frame].duration;
+                let last = clip.frames.len() - 1;
+                match clip.mode {
+                    AnimationMode::Once if self.frame == last => {
+                        self.finished = true;
+                        self.elapsed = 0.0;
+                        completed = true;
+                        break;
+                    }
+                    AnimationMode::Loop if self.frame == last => {
+                        self.frame = 0;
+                        completed = true;
+                    }
+                    AnimationMode::PingPong if last == 0 => {
+                        completed = true;
+                    }
+                    AnimationMode::PingPong if self.reverse => {
+                        self.frame -= 1;
+                        if self.frame == 0 {
+                            self.reverse = false;
+                            completed = true;
+                        }
+                    }
+                    AnimationMode::PingPong if self.frame == last => {
+                        self.reverse = true;
+                        self.frame -= 1;
+                    }
+                    _ => self.frame += 1,
+                }
+            }
+        }
+
+        Some((clip.frames[self.frame].index, completed))
+    }
+}
+
+pub fn sprite_animation_system(
+    time: Res<Time>,
+    mut finished_events: ResMut<Events<SpriteAnimationFinished>>,
+    mut query: Query<(Entity, &mut AnimatedSprite, &mut TextureAtlasSprite)>,
+) {
+    for (entity, mut animated_sprite, mut sprite) in query.iter_mut() {
+        if let Some((index, completed)) = animated_sprite.tick(time.delta_seconds) {
+            if sprite.index != index {
+                sprite.index = index;
+            }
+            if completed {
+                finished_events.send(SpriteAnimationFinished {
+                    entity,
+                    clip: animated_sprite.current_clip().unwrap().to_string(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnimatedSprite, AnimationMode, SpriteAnimationClip};
+
+    fn play(mode: AnimationMode, ticks: usize) -> Vec<(u32, bool)> {
+        let mut animated_sprite = AnimatedSprite::default()
+            .with_clip("clip", SpriteAnimationClip::from_indices(4..7, 0.1, mode));
+        animated_sprite.play("clip");
+        (0..ticks)
+            .map(|_| animated_sprite.tick(0.1).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn animation_modes() {
+        assert_eq!(
+            play(AnimationMode::Once, 4),
+            vec![(5, false), (6, false), (6, true), (6, false)]
+        );
+        assert_eq!(
+            play(AnimationMode::Loop, 4),
+            vec![(5, false), (6, false), (4, true), (5, false)]
+        );
+        assert_eq!(
+            play(AnimationMode::PingPong, 5),
+            vec![(5, false), (6, false), (5, false), (4, true), (5, false)]
+        );
+    }
+
+    #[test]
+    fn shortened_clip_clamps_frame() {
+        let mut animated_sprite = AnimatedSprite::default().with_clip(
+            "clip",
+            SpriteAnimationClip::from_indices(4..8, 0.1, AnimationMode::Loop),
+        );
+        animated_sprite.play("clip");
+        animated_sprite.tick(0.25);
+        assert_eq!(animated_sprite.current_frame(), 2);
+
+        animated_sprite
+            .clips
+            .get_mut("clip")
+            .unwrap()
+            .frames
+            .truncate(2);
+        assert_eq!(animated_sprite.tick(0.0), Some((5, false)));
+        assert_eq!(animated_sprite.tick(0.1), Some((4, true)));
+    }
+}
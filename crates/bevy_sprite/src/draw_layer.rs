@@ -0,0 +1,82 @@
+use bevy_ecs::Query;
+use bevy_transform::prelude::{GlobalTransform, Transform};
+
+/// The range of z values each [DrawLayer] covers
+pub const DRAW_LAYER_Z_STEP: f32 = 10.0;
+
+/// The distance in y over which y-sorting uses half of a layer's z range. Entities much further away from the origin
+/// than this can end up with the same z.
+const Y_SORT_SCALE: f32 = 1000.0;
+
+/// Controls the order 2D entities are drawn in by setting the z of their [GlobalTransform]. Entities on higher layers
+/// are drawn over entities on lower layers.
+///
+/// Within a layer, entities are ordered by the z of their [Transform], which should be between 0 and
+/// [DRAW_LAYER_Z_STEP]. With `y_sort` they are ordered by their y position instead, so entities lower on the screen
+/// are drawn over the ones above them, which is the usual overlap for characters and props in top-down games.
+///
+/// The default 2D camera sees the first 99 layers. Children aren't moved along with their parent's layer, so give them
+/// a [DrawLayer] of their own.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct DrawLayer {
+    pub layer: u16,
+    pub y_sort: bool,
+}
+
+impl DrawLayer {
+    pub fn new(layer: u16) -> Self {
+        DrawLayer {
+            layer,
+            y_sort: false,
+        }
+    }
+
+    pub fn y_sorted(layer: u16) -> Self {
+        DrawLayer {
+            layer,
+            y_sort: true,
+        }
+    }
+
+    /// Returns the z to draw an entity at, given its local z and its y position in the world
+    pub fn z(&self, local_z: f32, y: f32) -> f32 {
+        let offset = if self.y_sort {
+            // maps y to 0..1, decreasing as y increases. the small margin keeps entities from reaching the next layer
+            (0.5 - (y / Y_SORT_SCALE).atan() / std::f32::consts::PI) * 0.99 * DRAW_LAYER_Z_STEP
+        } else {
+            local_z
+        };
+        self.layer as f32 * DRAW_LAYER_Z_STEP + offset
+    }
+}
+
+/// Runs after transform propagation and overrides the z it computed for entities with a [DrawLayer]
+pub fn draw_layer_system(mut query: Query<(&DrawLayer, &Transform, &mut GlobalTransform)>) {
+    for (draw_layer, transform, mut global_transform) in query.iter_mut() {
+        let z = draw_layer.z(transform.translation.z(), global_transform.translation.y());
+        if global_transform.translation.z() != z {
+            global_transform.translation.set_z(z);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DrawLayer;
+
+    #[test]
+    fn draw_layer_order() {
+        let ground = DrawLayer::new(0);
+        let characters = DrawLayer::y_sorted(1);
+        let sky = DrawLayer::new(2);
+
+        // lower entities are in front of higher ones on a y-sorted layer
+        assert!(characters.z(0.0, -10.0) > characters.z(0.0, 10.0));
+        assert!(characters.z(0.0, -10.5) > characters.z(0.0, -10.0));
+
+        // layers always win over y position and local z
+        assert!(characters.z(0.0, 1.0e6) > ground.z(9.0, 0.0));
+        assert!(characters.z(0.0, -1.0e6) < sky.z(0.0, 0.0));
+        assert!(ground.z(1.0, 0.0) > ground.z(0.0, 0.0));
+    }
+}
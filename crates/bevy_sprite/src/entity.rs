@@ -1,13 +1,15 @@
 use crate::{
-    render::SPRITE_PIPELINE_HANDLE, sprite::Sprite, ColorMaterial, TextureAtlas,
-    TextureAtlasSprite, QUAD_HANDLE, SPRITE_SHEET_PIPELINE_HANDLE,
+    render::SPRITE_PIPELINE_HANDLE, sprite::Sprite, BatchedSprite, ColorMaterial, LitColorMaterial,
+    TextureAtlas, TextureAtlasSprite, LIT_SPRITE_PIPELINE_HANDLE, QUAD_HANDLE,
+    SPRITE_SHEET_PIPELINE_HANDLE,
 };
 use bevy_asset::Handle;
 use bevy_ecs::Bundle;
 use bevy_render::{
     mesh::Mesh,
     pipeline::{DynamicBinding, PipelineSpecialization, RenderPipeline, RenderPipelines},
-    prelude::Draw,
+    prelude::{Draw, Visible},
+    raycast::IgnoreRaycast,
     render_graph::base::MainPass,
 };
 use bevy_transform::prelude::{GlobalTransform, Transform};
@@ -19,9 +21,11 @@ pub struct SpriteComponents {
     pub material: Handle<ColorMaterial>,
     pub main_pass: MainPass,
     pub draw: Draw,
+    pub visible: Visible,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
+    pub ignore_raycast: IgnoreRaycast,
 }
 
 impl Default for SpriteComponents {
@@ -37,16 +41,99 @@ impl Default for SpriteComponents {
                             bind_group: 2,
                             binding: 0,
                         },
-                        // Sprite
+                        // Sprite_size
                         DynamicBinding {
                             bind_group: 2,
                             binding: 1,
                         },
+                        // Sprite_color
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 2,
+                        },
+                        // Sprite_flip_x
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 3,
+                        },
+                        // Sprite_flip_y
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 4,
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            draw: Default::default(),
+            visible: Visible {
+                is_transparent: true,
+                ..Default::default()
+            },
+            sprite: Default::default(),
+            main_pass: MainPass,
+            material: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            ignore_raycast: IgnoreRaycast,
+        }
+    }
+}
+
+/// A Bundle of components for drawing a sprite that is lit by 2D lights, see [LitColorMaterial]
+#[derive(Bundle)]
+pub struct LitSpriteComponents {
+    pub sprite: Sprite,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<LitColorMaterial>,
+    pub main_pass: MainPass,
+    pub draw: Draw,
+    pub visible: Visible,
+    pub render_pipelines: RenderPipelines,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub ignore_raycast: IgnoreRaycast,
+}
+
+impl Default for LitSpriteComponents {
+    fn default() -> Self {
+        Self {
+            mesh: QUAD_HANDLE,
+            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::specialized(
+                LIT_SPRITE_PIPELINE_HANDLE,
+                PipelineSpecialization {
+                    dynamic_bindings: vec![
+                        // Transform
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 0,
+                        },
+                        // Sprite_size
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 1,
+                        },
+                        // Sprite_color
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 2,
+                        },
+                        // Sprite_flip_x
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 3,
+                        },
+                        // Sprite_flip_y
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 4,
+                        },
                     ],
                     ..Default::default()
                 },
             )]),
-            draw: Draw {
+            draw: Default::default(),
+            visible: Visible {
                 is_transparent: true,
                 ..Default::default()
             },
@@ -55,6 +142,7 @@ impl Default for SpriteComponents {
             material: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
+            ignore_raycast: IgnoreRaycast,
         }
     }
 }
@@ -69,11 +157,13 @@ pub struct SpriteSheetComponents {
     pub texture_atlas: Handle<TextureAtlas>,
     /// Data pertaining to how the sprite is drawn on the screen
     pub draw: Draw,
+    pub visible: Visible,
     pub render_pipelines: RenderPipelines,
     pub main_pass: MainPass,
     pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
     pub transform: Transform,
     pub global_transform: GlobalTransform,
+    pub ignore_raycast: IgnoreRaycast,
 }
 
 impl Default for SpriteSheetComponents {
@@ -97,7 +187,8 @@ impl Default for SpriteSheetComponents {
                     ..Default::default()
                 },
             )]),
-            draw: Draw {
+            draw: Default::default(),
+            visible: Visible {
                 is_transparent: true,
                 ..Default::default()
             },
@@ -107,6 +198,31 @@ impl Default for SpriteSheetComponents {
             texture_atlas: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
+            ignore_raycast: IgnoreRaycast,
         }
     }
 }
+
+/// A Bundle of components for a sprite that is drawn in a batch with other sprites that share its material, see
+/// [BatchedSprite]
+#[derive(Bundle, Default)]
+pub struct BatchedSpriteComponents {
+    pub sprite: Sprite,
+    pub material: Handle<ColorMaterial>,
+    pub batched_sprite: BatchedSprite,
+    pub visible: Visible,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+/// A Bundle of components for a sprite from a sprite sheet that is drawn in a batch with other sprites from the same
+/// sheet, see [BatchedSprite]
+#[derive(Bundle, Default)]
+pub struct BatchedSpriteSheetComponents {
+    pub sprite: TextureAtlasSprite,
+    pub texture_atlas: Handle<TextureAtlas>,
+    pub batched_sprite: BatchedSprite,
+    pub visible: Visible,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
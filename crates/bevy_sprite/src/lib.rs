@@ -1,26 +1,49 @@
 pub mod collide_aabb;
 pub mod entity;
 
+mod animation;
 mod color_material;
+mod draw_layer;
 mod dynamic_texture_atlas_builder;
+mod light;
+mod lit_color_material;
+mod pixel_snap;
 mod rect;
 mod render;
 mod sprite;
+mod sprite_batch;
 mod texture_atlas;
 mod texture_atlas_builder;
+mod texture_atlas_loader;
 
+pub use animation::*;
 pub use color_material::*;
+pub use draw_layer::*;
 pub use dynamic_texture_atlas_builder::*;
+pub use light::{
+    AmbientLight2d, LightOccluder2d, OccluderShape, PointLight2d, MAX_LIGHT_OCCLUDERS_2D,
+    MAX_POINT_LIGHTS_2D,
+};
+pub use lit_color_material::*;
+pub use pixel_snap::*;
 pub use rect::*;
 pub use render::*;
 pub use sprite::*;
+pub use sprite_batch::*;
 pub use texture_atlas::*;
 pub use texture_atlas_builder::*;
+pub use texture_atlas_loader::*;
 
 pub mod prelude {
     pub use crate::{
-        entity::{SpriteComponents, SpriteSheetComponents},
-        ColorMaterial, Sprite, SpriteResizeMode, TextureAtlas, TextureAtlasSprite,
+        entity::{
+            BatchedSpriteComponents, BatchedSpriteSheetComponents, LitSpriteComponents,
+            SpriteComponents, SpriteSheetComponents,
+        },
+        AmbientLight2d, AnimatedSprite, AnimationMode, BatchedSprite, ColorMaterial, DrawLayer,
+        LightOccluder2d, LitColorMaterial, OccluderShape, PixelSnap, PointLight2d, Sprite,
+        SpriteAnimationClip, SpriteAnimationFinished, SpriteResizeMode, TextureAtlas,
+        TextureAtlasSprite,
     };
 }
 
@@ -34,7 +57,7 @@ use bevy_render::{
     shader::asset_shader_defs_system,
 };
 use bevy_type_registry::TypeUuid;
-use sprite::sprite_system;
+use sprite::{lit_sprite_system, sprite_system};
 
 #[derive(Default)]
 pub struct SpritePlugin;
@@ -44,12 +67,26 @@ pub const QUAD_HANDLE: Handle<Mesh> = Handle::weak_from_u64(Mesh::TYPE_UUID, 142
 impl Plugin for SpritePlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_asset::<ColorMaterial>()
+            .add_asset::<LitColorMaterial>()
             .add_asset::<TextureAtlas>()
+            .init_asset_loader::<TextureAtlasLoader>()
+            .add_event::<SpriteAnimationFinished>()
+            .add_system_to_stage(stage::POST_UPDATE, sprite_animation_system.system())
+            .add_startup_system(sprite_batch_setup_system.system())
             .add_system_to_stage(stage::POST_UPDATE, sprite_system.system())
+            .add_system_to_stage(stage::POST_UPDATE, lit_sprite_system.system())
+            .add_system_to_stage(stage::POST_UPDATE, normal_map_format_system.system())
+            .add_system_to_stage(stage::POST_UPDATE, draw_layer_system.system())
+            .add_system_to_stage(stage::POST_UPDATE, pixel_snap_system.system())
             .add_system_to_stage(
                 stage::POST_UPDATE,
                 asset_shader_defs_system::<ColorMaterial>.system(),
-            );
+            )
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                asset_shader_defs_system::<LitColorMaterial>.system(),
+            )
+            .add_system_to_stage(bevy_render::stage::DRAW, sprite_batch_system.system());
 
         let resources = app.resources_mut();
         let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
@@ -59,6 +96,11 @@ impl Plugin for SpritePlugin {
 
         let mut color_materials = resources.get_mut::<Assets<ColorMaterial>>().unwrap();
         color_materials.set_untracked(Handle::<ColorMaterial>::default(), ColorMaterial::default());
+        let mut lit_color_materials = resources.get_mut::<Assets<LitColorMaterial>>().unwrap();
+        lit_color_materials.set_untracked(
+            Handle::<LitColorMaterial>::default(),
+            LitColorMaterial::default(),
+        );
         meshes.set_untracked(
             QUAD_HANDLE,
             // Use a flipped quad because the camera is facing "forward" but quads should face backward
@@ -0,0 +1,202 @@
+use bevy_core::Byteable;
+use bevy_math::{Vec2, Vec3};
+use bevy_render::color::Color;
+use bevy_transform::prelude::GlobalTransform;
+
+/// The most [PointLight2d]s that light sprites at once. Must match the lit sprite shader.
+pub const MAX_POINT_LIGHTS_2D: usize = 16;
+
+/// The most [LightOccluder2d]s that cast shadows at once. Must match the lit sprite shader.
+pub const MAX_LIGHT_OCCLUDERS_2D: usize = 32;
+
+/// A light that shines on lit sprites from its position, see [LitColorMaterial](crate::LitColorMaterial).
+#[derive(Debug, Clone)]
+pub struct PointLight2d {
+    pub color: Color,
+    pub intensity: f32,
+    /// The distance at which the light fades out completely
+    pub radius: f32,
+    /// How far above the sprites the light is. Low lights graze normal mapped sprites and bring out their bumps.
+    pub height: f32,
+    /// Whether [LightOccluder2d]s block this light
+    pub casts_shadows: bool,
+}
+
+impl Default for PointLight2d {
+    fn default() -> Self {
+        PointLight2d {
+            color: Color::WHITE,
+            intensity: 1.0,
+            radius: 300.0,
+            height: 50.0,
+            casts_shadows: true,
+        }
+    }
+}
+
+/// Light that reaches all lit sprites equally. The colors of all ambient lights are added up.
+#[derive(Debug, Clone)]
+pub struct AmbientLight2d {
+    pub color: Color,
+}
+
+impl Default for AmbientLight2d {
+    fn default() -> Self {
+        AmbientLight2d {
+            color: Color::rgb_linear(0.1, 0.1, 0.1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OccluderShape {
+    /// A rectangle centered on the occluder, turned with its rotation around z
+    Rectangle {
+        size: Vec2,
+    },
+    Circle {
+        radius: f32,
+    },
+}
+
+/// Blocks the light of [PointLight2d]s that cast shadows, so lit sprites behind it from the light's point of view are
+/// in shadow. Sprites are never shadowed by an occluder that covers them, so an occluder can share an entity with the
+/// sprite that casts the shadow.
+#[derive(Debug, Clone)]
+pub struct LightOccluder2d {
+    pub shape: OccluderShape,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct PointLight2dRaw {
+    /// x, y, height, radius
+    pub position: [f32; 4],
+    /// the color times the intensity, and 1.0 in alpha if the light casts shadows
+    pub color: [f32; 4],
+}
+
+unsafe impl Byteable for PointLight2dRaw {}
+
+impl PointLight2dRaw {
+    pub fn from(light: &PointLight2d, global_transform: &GlobalTransform) -> PointLight2dRaw {
+        let color = light.color * light.intensity;
+        PointLight2dRaw {
+            position: [
+                global_transform.translation.x(),
+                global_transform.translation.y(),
+                light.height,
+                light.radius,
+            ],
+            color: [
+                color.r_linear(),
+                color.g_linear(),
+                color.b_linear(),
+                if light.casts_shadows { 1.0 } else { 0.0 },
+            ],
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct LightOccluder2dRaw {
+    /// x, y, then the half size of rectangles or the radius of circles
+    pub shape: [f32; 4],
+    /// the cosine and sine of the rotation, then 0.0 for rectangles and 1.0 for circles
+    pub rotation: [f32; 4],
+}
+
+unsafe impl Byteable for LightOccluder2dRaw {}
+
+impl LightOccluder2dRaw {
+    pub fn from(occluder: &LightOccluder2d, global_transform: &GlobalTransform) -> Self {
+        let scale = global_transform.scale.truncate();
+        let (x, y) = (
+            global_transform.translation.x(),
+            global_transform.translation.y(),
+        );
+        // the angle of the occluder's x axis
+        let x_axis = global_transform.rotation * Vec3::unit_x();
+        let angle = x_axis.y().atan2(x_axis.x());
+        match occluder.shape {
+            OccluderShape::Rectangle { size } => {
+                let extents = size * scale / 2.0;
+                LightOccluder2dRaw {
+                    shape: [x, y, extents.x(), extents.y()],
+                    rotation: [angle.cos(), angle.sin(), 0.0, 0.0],
+                }
+            }
+            OccluderShape::Circle { radius } => LightOccluder2dRaw {
+                shape: [x, y, radius * scale.x().max(scale.y()), 0.0],
+                rotation: [1.0, 0.0, 1.0, 0.0],
+            },
+        }
+    }
+}
+
+/// The data of the "Lights2d" uniform
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Lights2dRaw {
+    pub ambient_color: [f32; 4],
+    /// the number of point lights and occluders
+    pub counts: [u32; 4],
+    pub point_lights: [PointLight2dRaw; MAX_POINT_LIGHTS_2D],
+    pub occluders: [LightOccluder2dRaw; MAX_LIGHT_OCCLUDERS_2D],
+}
+
+unsafe impl Byteable for Lights2dRaw {}
+
+impl Default for Lights2dRaw {
+    fn default() -> Self {
+        Lights2dRaw {
+            ambient_color: [0.0; 4],
+            counts: [0; 4],
+            point_lights: [PointLight2dRaw::default(); MAX_POINT_LIGHTS_2D],
+            occluders: [LightOccluder2dRaw::default(); MAX_LIGHT_OCCLUDERS_2D],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Quat;
+
+    #[test]
+    fn occluder_raw() {
+        // the shader reads the lights as an std140 block of vec4s
+        assert_eq!(
+            std::mem::size_of::<Lights2dRaw>(),
+            32 + 32 * MAX_POINT_LIGHTS_2D + 32 * MAX_LIGHT_OCCLUDERS_2D
+        );
+
+        let global_transform = GlobalTransform {
+            translation: Vec3::new(10.0, 20.0, 1.0),
+            rotation: Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::new(2.0, 1.0, 1.0),
+        };
+        let rectangle = LightOccluder2dRaw::from(
+            &LightOccluder2d {
+                shape: OccluderShape::Rectangle {
+                    size: Vec2::new(4.0, 6.0),
+                },
+            },
+            &global_transform,
+        );
+        assert_eq!(rectangle.shape, [10.0, 20.0, 4.0, 3.0]);
+        assert!(rectangle.rotation[0].abs() < 1e-6);
+        assert!((rectangle.rotation[1] - 1.0).abs() < 1e-6);
+        assert_eq!(rectangle.rotation[2], 0.0);
+
+        let circle = LightOccluder2dRaw::from(
+            &LightOccluder2d {
+                shape: OccluderShape::Circle { radius: 5.0 },
+            },
+            &global_transform,
+        );
+        assert_eq!(circle.shape, [10.0, 20.0, 10.0, 0.0]);
+        assert_eq!(circle.rotation[2], 1.0);
+    }
+}
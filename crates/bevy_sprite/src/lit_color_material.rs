@@ -0,0 +1,87 @@
+use bevy_asset::{self, Assets, Handle};
+use bevy_ecs::{Res, ResMut};
+use bevy_render::{
+    color::Color,
+    renderer::RenderResources,
+    shader::ShaderDefs,
+    texture::{Texture, TextureFormat},
+};
+use bevy_type_registry::TypeUuid;
+
+/// A sprite material that is lit by [PointLight2d](crate::PointLight2d)s and [AmbientLight2d](crate::AmbientLight2d)s.
+/// Use it with [LitSpriteComponents](crate::entity::LitSpriteComponents).
+#[derive(Debug, RenderResources, ShaderDefs, TypeUuid)]
+#[uuid = "0b02d405-2604-4b77-8329-fdf55be4773d"]
+pub struct LitColorMaterial {
+    pub color: Color,
+    #[shader_def]
+    pub texture: Option<Handle<Texture>>,
+    /// A tangent space normal map, with red pointing right and green pointing up. It is loaded as linear data instead
+    /// of sRGB, so it shouldn't be shared with materials that use it as a color texture.
+    #[shader_def]
+    pub normal_map: Option<Handle<Texture>>,
+}
+
+impl LitColorMaterial {
+    pub fn color(color: Color) -> Self {
+        LitColorMaterial {
+            color,
+            ..Default::default()
+        }
+    }
+
+    pub fn texture(texture: Handle<Texture>) -> Self {
+        LitColorMaterial {
+            texture: Some(texture),
+            ..Default::default()
+        }
+    }
+
+    pub fn normal_mapped(texture: Handle<Texture>, normal_map: Handle<Texture>) -> Self {
+        LitColorMaterial {
+            texture: Some(texture),
+            normal_map: Some(normal_map),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for LitColorMaterial {
+    fn default() -> Self {
+        LitColorMaterial {
+            color: Color::WHITE,
+            texture: None,
+            normal_map: None,
+        }
+    }
+}
+
+impl From<Color> for LitColorMaterial {
+    fn from(color: Color) -> Self {
+        LitColorMaterial::color(color)
+    }
+}
+
+impl From<Handle<Texture>> for LitColorMaterial {
+    fn from(texture: Handle<Texture>) -> Self {
+        LitColorMaterial::texture(texture)
+    }
+}
+
+/// Switches the normal maps of [LitColorMaterial]s to a linear format. Images are loaded as sRGB, which would bend
+/// the normals when the GPU converts them back to linear.
+pub fn normal_map_format_system(
+    materials: Res<Assets<LitColorMaterial>>,
+    mut textures: ResMut<Assets<Texture>>,
+) {
+    for (_, material) in materials.iter() {
+        if let Some(ref normal_map) = material.normal_map {
+            let is_srgb = textures.get(normal_map).map_or(false, |texture| {
+                texture.format == TextureFormat::Rgba8UnormSrgb
+            });
+            if is_srgb {
+                textures.get_mut(normal_map).unwrap().format = TextureFormat::Rgba8Unorm;
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{Sprite, TextureAtlas, TextureAtlasSprite};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Query, QuerySet, Res, With};
+use bevy_math::{Vec2, Vec3};
+use bevy_transform::prelude::GlobalTransform;
+
+/// Keeps the entity's sprite on the grid of world units by rounding the position of its bottom left corner. Pixel art
+/// with one texel per world unit then stays sharp while it moves, when it's drawn by a camera with a pixel perfect
+/// [ScalingMode](bevy_render::camera::ScalingMode).
+#[derive(Debug, Default, Copy, Clone)]
+pub struct PixelSnap;
+
+fn snap(translation: Vec3, size: Vec2) -> Vec3 {
+    let extents = size / 2.0;
+    let corner = translation.truncate() - extents;
+    (Vec2::new(corner.x().round(), corner.y().round()) + extents).extend(translation.z())
+}
+
+fn snap_global_transform(global_transform: &mut GlobalTransform, size: Vec2) {
+    let translation = snap(
+        global_transform.translation,
+        size * global_transform.scale.truncate(),
+    );
+    if global_transform.translation != translation {
+        global_transform.translation = translation;
+    }
+}
+
+/// Runs after transform propagation, so the snapped position is only used for drawing
+pub fn pixel_snap_system(
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    mut queries: QuerySet<(
+        Query<With<PixelSnap, (&Sprite, &mut GlobalTransform)>>,
+        Query<
+            With<
+                PixelSnap,
+                (
+                    &TextureAtlasSprite,
+                    &Handle<TextureAtlas>,
+                    &mut GlobalTransform,
+                ),
+            >,
+        >,
+    )>,
+) {
+    for (sprite, mut global_transform) in queries.q0_mut().iter_mut() {
+        snap_global_transform(&mut global_transform, sprite.size);
+    }
+    for (sprite, texture_atlas_handle, mut global_transform) in queries.q1_mut().iter_mut() {
+        if let Some(rect) = texture_atlases
+            .get(texture_atlas_handle)
+            .and_then(|texture_atlas| texture_atlas.textures.get(sprite.index as usize))
+        {
+            snap_global_transform(
+                &mut global_transform,
+                Vec2::new(rect.width(), rect.height()),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::snap;
+    use bevy_math::{Vec2, Vec3};
+
+    #[test]
+    fn pixel_snapping() {
+        // even sizes are centered on whole units, odd sizes between them
+        assert_eq!(
+            snap(Vec3::new(10.3, -4.6, 2.0), Vec2::new(16.0, 8.0)),
+            Vec3::new(10.0, -5.0, 2.0)
+        );
+        assert_eq!(
+            snap(Vec3::new(10.3, -4.6, 2.0), Vec2::new(15.0, 7.0)),
+            Vec3::new(10.5, -4.5, 2.0)
+        );
+    }
+}
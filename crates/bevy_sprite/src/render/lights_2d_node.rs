@@ -0,0 +1,142 @@
+use crate::{
+    light::{LightOccluder2dRaw, Lights2dRaw, PointLight2dRaw},
+    render::uniform,
+    AmbientLight2d, LightOccluder2d, PointLight2d, MAX_LIGHT_OCCLUDERS_2D, MAX_POINT_LIGHTS_2D,
+};
+use bevy_core::AsBytes;
+use bevy_ecs::{Commands, IntoQuerySystem, Local, Query, Res, ResMut, Resources, System, World};
+use bevy_render::{
+    color::Color,
+    render_graph::{CommandQueue, Node, ResourceSlots, SystemNode},
+    renderer::{
+        BufferId, BufferInfo, BufferUsage, RenderContext, RenderResourceBinding,
+        RenderResourceBindings, RenderResourceContext,
+    },
+};
+use bevy_transform::prelude::*;
+
+/// A Render Graph [Node] that writes the 2D lights and light occluders from the ECS to a GPU buffer
+#[derive(Debug, Default)]
+pub struct Lights2dNode {
+    command_queue: CommandQueue,
+}
+
+impl Node for Lights2dNode {
+    fn update(
+        &mut self,
+        _world: &World,
+        _resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        self.command_queue.execute(render_context);
+    }
+}
+
+impl SystemNode for Lights2dNode {
+    fn get_system(&self, commands: &mut Commands) -> Box<dyn System> {
+        let system = lights_2d_node_system.system();
+        commands.insert_local_resource(
+            system.id(),
+            Lights2dNodeSystemState {
+                command_queue: self.command_queue.clone(),
+                lights_buffer: None,
+                staging_buffer: None,
+            },
+        );
+        system
+    }
+}
+
+/// Local "lights 2d node system" state
+#[derive(Debug, Default)]
+pub struct Lights2dNodeSystemState {
+    lights_buffer: Option<BufferId>,
+    staging_buffer: Option<BufferId>,
+    command_queue: CommandQueue,
+}
+
+pub fn lights_2d_node_system(
+    mut state: Local<Lights2dNodeSystemState>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+    mut render_resource_bindings: ResMut<RenderResourceBindings>,
+    ambient_light_query: Query<&AmbientLight2d>,
+    point_light_query: Query<(&PointLight2d, &GlobalTransform)>,
+    occluder_query: Query<(&LightOccluder2d, &GlobalTransform)>,
+) {
+    let state = &mut state;
+    let render_resource_context = &**render_resource_context;
+    let size = std::mem::size_of::<Lights2dRaw>();
+
+    if let Some(staging_buffer) = state.staging_buffer {
+        render_resource_context.map_buffer(staging_buffer);
+    } else {
+        let buffer = render_resource_context.create_buffer(BufferInfo {
+            size,
+            buffer_usage: BufferUsage::UNIFORM | BufferUsage::COPY_SRC | BufferUsage::COPY_DST,
+            ..Default::default()
+        });
+        render_resource_bindings.set(
+            uniform::LIGHTS_2D,
+            RenderResourceBinding::Buffer {
+                buffer,
+                range: 0..size as u64,
+                dynamic_index: None,
+            },
+        );
+        state.lights_buffer = Some(buffer);
+
+        let staging_buffer = render_resource_context.create_buffer(BufferInfo {
+            size,
+            buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
+            mapped_at_creation: true,
+        });
+        state.staging_buffer = Some(staging_buffer);
+    }
+
+    let mut lights = Lights2dRaw::default();
+    let ambient_color = ambient_light_query
+        .iter()
+        .fold(Color::BLACK, |color, ambient_light| {
+            color + ambient_light.color
+        });
+    lights.ambient_color = [
+        ambient_color.r_linear(),
+        ambient_color.g_linear(),
+        ambient_color.b_linear(),
+        1.0,
+    ];
+
+    // lights and occluders past the maximum are ignored
+    let mut light_count = 0;
+    for ((light, global_transform), slot) in
+        point_light_query.iter().zip(lights.point_lights.iter_mut())
+    {
+        *slot = PointLight2dRaw::from(&light, &global_transform);
+        light_count += 1;
+    }
+    let mut occluder_count = 0;
+    for ((occluder, global_transform), slot) in
+        occluder_query.iter().zip(lights.occluders.iter_mut())
+    {
+        *slot = LightOccluder2dRaw::from(&occluder, &global_transform);
+        occluder_count += 1;
+    }
+    debug_assert!(light_count <= MAX_POINT_LIGHTS_2D && occluder_count <= MAX_LIGHT_OCCLUDERS_2D);
+    lights.counts = [light_count as u32, occluder_count as u32, 0, 0];
+
+    let staging_buffer = state.staging_buffer.unwrap();
+    render_resource_context.write_mapped_buffer(
+        staging_buffer,
+        0..size as u64,
+        &mut |data, _renderer| {
+            data.copy_from_slice(lights.as_bytes());
+        },
+    );
+    render_resource_context.unmap_buffer(staging_buffer);
+    let lights_buffer = state.lights_buffer.unwrap();
+    state
+        .command_queue
+        .copy_buffer_to_buffer(staging_buffer, 0, lights_buffer, 0, size as u64);
+}
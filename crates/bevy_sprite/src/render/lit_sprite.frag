@@ -0,0 +1,132 @@
+#version 450
+
+const int MAX_POINT_LIGHTS_2D = 16;
+const int MAX_LIGHT_OCCLUDERS_2D = 32;
+
+struct PointLight2d {
+    vec4 Position;
+    vec4 Color;
+};
+
+struct Occluder2d {
+    vec4 Shape;
+    vec4 Rotation;
+};
+
+layout(location = 0) in vec2 v_Uv;
+layout(location = 1) in vec4 v_Color;
+layout(location = 2) in vec2 v_WorldPosition;
+layout(location = 3) in vec2 v_NormalFlip;
+layout(location = 4) in vec2 v_XAxis;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 1, binding = 0) uniform LitColorMaterial_color {
+    vec4 Color;
+};
+
+# ifdef LITCOLORMATERIAL_TEXTURE
+layout(set = 1, binding = 1) uniform texture2D LitColorMaterial_texture;
+layout(set = 1, binding = 2) uniform sampler LitColorMaterial_texture_sampler;
+# endif
+
+# ifdef LITCOLORMATERIAL_NORMAL_MAP
+layout(set = 1, binding = 3) uniform texture2D LitColorMaterial_normal_map;
+layout(set = 1, binding = 4) uniform sampler LitColorMaterial_normal_map_sampler;
+# endif
+
+layout(set = 3, binding = 0) uniform Lights2d {
+    vec4 AmbientColor;
+    uvec4 Counts;
+    PointLight2d PointLights[MAX_POINT_LIGHTS_2D];
+    Occluder2d Occluders[MAX_LIGHT_OCCLUDERS_2D];
+};
+
+// whether the segment from the fragment to the light crosses the occluder
+bool occludes(Occluder2d occluder, vec2 from, vec2 to) {
+    vec2 center = occluder.Shape.xy;
+    if (occluder.Rotation.z > 0.5) {
+        float radius = occluder.Shape.z;
+        vec2 offset = from - center;
+        if (dot(offset, offset) < radius * radius) {
+            return false;
+        }
+        vec2 segment = to - from;
+        float t = clamp(dot(center - from, segment) / max(dot(segment, segment), 0.0001), 0.0, 1.0);
+        vec2 closest = from + segment * t - center;
+        return dot(closest, closest) < radius * radius;
+    }
+
+    // rectangles are tested in their own space, where they are axis aligned
+    vec2 extents = occluder.Shape.zw;
+    mat2 to_local = mat2(
+        occluder.Rotation.x, -occluder.Rotation.y,
+        occluder.Rotation.y, occluder.Rotation.x);
+    vec2 local_from = to_local * (from - center);
+    vec2 local_to = to_local * (to - center);
+    if (all(lessThan(abs(local_from), extents))) {
+        return false;
+    }
+    vec2 direction = local_to - local_from;
+    vec2 inverse_direction = 1.0 / (direction + vec2(equal(direction, vec2(0.0))) * 0.0001);
+    vec2 t0 = (-extents - local_from) * inverse_direction;
+    vec2 t1 = (extents - local_from) * inverse_direction;
+    vec2 t_min = min(t0, t1);
+    vec2 t_max = max(t0, t1);
+    float enter = max(max(t_min.x, t_min.y), 0.0);
+    float exit = min(min(t_max.x, t_max.y), 1.0);
+    return enter <= exit;
+}
+
+void main() {
+    vec4 color = Color * v_Color;
+# ifdef LITCOLORMATERIAL_TEXTURE
+    color *= texture(
+        sampler2D(LitColorMaterial_texture, LitColorMaterial_texture_sampler),
+        v_Uv);
+# endif
+
+    vec3 normal = vec3(0.0, 0.0, 1.0);
+# ifdef LITCOLORMATERIAL_NORMAL_MAP
+    vec3 tangent_normal = texture(
+        sampler2D(LitColorMaterial_normal_map, LitColorMaterial_normal_map_sampler),
+        v_Uv).xyz * 2.0 - 1.0;
+    tangent_normal.xy *= v_NormalFlip;
+    vec2 y_axis = vec2(-v_XAxis.y, v_XAxis.x);
+    normal = normalize(vec3(
+        tangent_normal.x * v_XAxis + tangent_normal.y * y_axis,
+        tangent_normal.z));
+# endif
+
+    vec3 light = AmbientColor.rgb;
+    for (int i = 0; i < int(Counts.x) && i < MAX_POINT_LIGHTS_2D; ++i) {
+        PointLight2d point_light = PointLights[i];
+        vec2 to_light = point_light.Position.xy - v_WorldPosition;
+        float distance = length(to_light);
+        float radius = point_light.Position.w;
+        if (distance >= radius) {
+            continue;
+        }
+
+        bool shadowed = false;
+        if (point_light.Color.a > 0.5) {
+            for (int j = 0; j < int(Counts.y) && j < MAX_LIGHT_OCCLUDERS_2D; ++j) {
+                if (occludes(Occluders[j], v_WorldPosition, point_light.Position.xy)) {
+                    shadowed = true;
+                    break;
+                }
+            }
+        }
+        if (shadowed) {
+            continue;
+        }
+
+        float attenuation = 1.0 - distance / radius;
+        attenuation *= attenuation;
+        vec3 light_direction = normalize(vec3(to_light, point_light.Position.z));
+        float diffuse = max(dot(normal, light_direction), 0.0);
+        light += point_light.Color.rgb * attenuation * diffuse;
+    }
+
+    o_Target = vec4(color.rgb * light, color.a);
+}
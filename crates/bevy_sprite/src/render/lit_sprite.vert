@@ -0,0 +1,50 @@
+#version 450
+
+layout(location = 0) in vec3 Vertex_Position;
+layout(location = 1) in vec3 Vertex_Normal;
+layout(location = 2) in vec2 Vertex_Uv;
+
+layout(location = 0) out vec2 v_Uv;
+layout(location = 1) out vec4 v_Color;
+layout(location = 2) out vec2 v_WorldPosition;
+layout(location = 3) out vec2 v_NormalFlip;
+layout(location = 4) out vec2 v_XAxis;
+
+layout(set = 0, binding = 0) uniform Camera {
+    mat4 ViewProj;
+};
+
+layout(set = 2, binding = 0) uniform Transform {
+    mat4 Model;
+};
+layout(set = 2, binding = 1) uniform Sprite_size {
+    vec2 size;
+};
+layout(set = 2, binding = 2) uniform Sprite_color {
+    vec4 color;
+};
+layout(set = 2, binding = 3) uniform Sprite_flip_x {
+    uint flip_x;
+};
+layout(set = 2, binding = 4) uniform Sprite_flip_y {
+    uint flip_y;
+};
+
+void main() {
+    v_Uv = Vertex_Uv;
+    v_NormalFlip = vec2(1.0, 1.0);
+    if (flip_x != 0) {
+        v_Uv.x = 1.0 - v_Uv.x;
+        v_NormalFlip.x = -1.0;
+    }
+    if (flip_y != 0) {
+        v_Uv.y = 1.0 - v_Uv.y;
+        v_NormalFlip.y = -1.0;
+    }
+    v_Color = color;
+    v_XAxis = normalize(Model[0].xy);
+    vec3 position = Vertex_Position * vec3(size, 1.0);
+    vec4 world_position = Model * vec4(position, 1.0);
+    v_WorldPosition = world_position.xy;
+    gl_Position = ViewProj * world_position;
+}
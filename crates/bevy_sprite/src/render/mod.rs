@@ -1,4 +1,8 @@
-use crate::{ColorMaterial, Sprite, TextureAtlas, TextureAtlasSprite};
+mod lights_2d_node;
+
+pub use lights_2d_node::*;
+
+use crate::{ColorMaterial, LitColorMaterial, Sprite, TextureAtlas, TextureAtlasSprite};
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::Resources;
 use bevy_render::{
@@ -19,7 +23,16 @@ pub const SPRITE_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
 pub const SPRITE_SHEET_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
     Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 9016885805180281612);
 
-pub fn build_sprite_sheet_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+pub const SPRITE_BATCH_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 4731869013553904178);
+
+pub const SPRITE_SHEET_BATCH_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 12436512470186640291);
+
+pub const LIT_SPRITE_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 7135724759862663948);
+
+fn sprite_pipeline_descriptor(shader_stages: ShaderStages) -> PipelineDescriptor {
     PipelineDescriptor {
         rasterization_state: Some(RasterizationStateDescriptor {
             front_face: FrontFace::Ccw,
@@ -54,74 +67,92 @@ pub fn build_sprite_sheet_pipeline(shaders: &mut Assets<Shader>) -> PipelineDesc
             },
             write_mask: ColorWrite::ALL,
         }],
-        ..PipelineDescriptor::new(ShaderStages {
-            vertex: shaders.add(Shader::from_glsl(
-                ShaderStage::Vertex,
-                include_str!("sprite_sheet.vert"),
-            )),
-            fragment: Some(shaders.add(Shader::from_glsl(
-                ShaderStage::Fragment,
-                include_str!("sprite_sheet.frag"),
-            ))),
-        })
+        ..PipelineDescriptor::new(shader_stages)
     }
 }
 
+pub fn build_sprite_sheet_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    sprite_pipeline_descriptor(ShaderStages {
+        vertex: shaders.add(Shader::from_glsl(
+            ShaderStage::Vertex,
+            include_str!("sprite_sheet.vert"),
+        )),
+        fragment: Some(shaders.add(Shader::from_glsl(
+            ShaderStage::Fragment,
+            include_str!("sprite_sheet.frag"),
+        ))),
+    })
+}
+
 pub fn build_sprite_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
-    PipelineDescriptor {
-        rasterization_state: Some(RasterizationStateDescriptor {
-            front_face: FrontFace::Ccw,
-            cull_mode: CullMode::None,
-            depth_bias: 0,
-            depth_bias_slope_scale: 0.0,
-            depth_bias_clamp: 0.0,
-            clamp_depth: false,
-        }),
-        depth_stencil_state: Some(DepthStencilStateDescriptor {
-            format: TextureFormat::Depth32Float,
-            depth_write_enabled: true,
-            depth_compare: CompareFunction::LessEqual,
-            stencil: StencilStateDescriptor {
-                front: StencilStateFaceDescriptor::IGNORE,
-                back: StencilStateFaceDescriptor::IGNORE,
-                read_mask: 0,
-                write_mask: 0,
-            },
-        }),
-        color_states: vec![ColorStateDescriptor {
-            format: TextureFormat::default(),
-            color_blend: BlendDescriptor {
-                src_factor: BlendFactor::SrcAlpha,
-                dst_factor: BlendFactor::OneMinusSrcAlpha,
-                operation: BlendOperation::Add,
-            },
-            alpha_blend: BlendDescriptor {
-                src_factor: BlendFactor::One,
-                dst_factor: BlendFactor::One,
-                operation: BlendOperation::Add,
-            },
-            write_mask: ColorWrite::ALL,
-        }],
-        ..PipelineDescriptor::new(ShaderStages {
-            vertex: shaders.add(Shader::from_glsl(
-                ShaderStage::Vertex,
-                include_str!("sprite.vert"),
-            )),
-            fragment: Some(shaders.add(Shader::from_glsl(
-                ShaderStage::Fragment,
-                include_str!("sprite.frag"),
-            ))),
-        })
-    }
+    sprite_pipeline_descriptor(ShaderStages {
+        vertex: shaders.add(Shader::from_glsl(
+            ShaderStage::Vertex,
+            include_str!("sprite.vert"),
+        )),
+        fragment: Some(shaders.add(Shader::from_glsl(
+            ShaderStage::Fragment,
+            include_str!("sprite.frag"),
+        ))),
+    })
+}
+
+/// Draws batched sprites from a vertex buffer that holds their positions in world space, see [BatchedSprite](crate::BatchedSprite)
+pub fn build_sprite_batch_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    sprite_pipeline_descriptor(ShaderStages {
+        vertex: shaders.add(Shader::from_glsl(
+            ShaderStage::Vertex,
+            include_str!("sprite_batch.vert"),
+        )),
+        fragment: Some(shaders.add(Shader::from_glsl(
+            ShaderStage::Fragment,
+            include_str!("sprite.frag"),
+        ))),
+    })
+}
+
+/// Draws batched sprites from a texture atlas, see [BatchedSprite](crate::BatchedSprite)
+pub fn build_sprite_sheet_batch_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    sprite_pipeline_descriptor(ShaderStages {
+        vertex: shaders.add(Shader::from_glsl(
+            ShaderStage::Vertex,
+            include_str!("sprite_batch.vert"),
+        )),
+        fragment: Some(shaders.add(Shader::from_glsl(
+            ShaderStage::Fragment,
+            include_str!("sprite_sheet.frag"),
+        ))),
+    })
+}
+
+/// Draws sprites with a [LitColorMaterial], lit by the 2D lights in the "Lights2d" uniform
+pub fn build_lit_sprite_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    sprite_pipeline_descriptor(ShaderStages {
+        vertex: shaders.add(Shader::from_glsl(
+            ShaderStage::Vertex,
+            include_str!("lit_sprite.vert"),
+        )),
+        fragment: Some(shaders.add(Shader::from_glsl(
+            ShaderStage::Fragment,
+            include_str!("lit_sprite.frag"),
+        ))),
+    })
 }
 
 pub mod node {
     pub const COLOR_MATERIAL: &str = "color_material";
+    pub const LIT_COLOR_MATERIAL: &str = "lit_color_material";
+    pub const LIGHTS_2D: &str = "lights_2d";
     pub const SPRITE: &str = "sprite";
     pub const SPRITE_SHEET: &str = "sprite_sheet";
     pub const SPRITE_SHEET_SPRITE: &str = "sprite_sheet_sprite";
 }
 
+/// the names of sprite uniforms
+pub mod uniform {
+    pub const LIGHTS_2D: &str = "Lights2d";
+}
+
 pub trait SpriteRenderGraphBuilder {
     fn add_sprite_graph(&mut self, resources: &Resources) -> &mut Self;
 }
@@ -135,6 +166,17 @@ impl SpriteRenderGraphBuilder for RenderGraph {
         self.add_node_edge(node::COLOR_MATERIAL, base::node::MAIN_PASS)
             .unwrap();
 
+        self.add_system_node(
+            node::LIT_COLOR_MATERIAL,
+            AssetRenderResourcesNode::<LitColorMaterial>::new(false),
+        );
+        self.add_node_edge(node::LIT_COLOR_MATERIAL, base::node::MAIN_PASS)
+            .unwrap();
+
+        self.add_system_node(node::LIGHTS_2D, Lights2dNode::default());
+        self.add_node_edge(node::LIGHTS_2D, base::node::MAIN_PASS)
+            .unwrap();
+
         self.add_system_node(node::SPRITE, RenderResourcesNode::<Sprite>::new(true));
         self.add_node_edge(node::SPRITE, base::node::MAIN_PASS)
             .unwrap();
@@ -156,6 +198,18 @@ impl SpriteRenderGraphBuilder for RenderGraph {
             SPRITE_SHEET_PIPELINE_HANDLE,
             build_sprite_sheet_pipeline(&mut shaders),
         );
+        pipelines.set_untracked(
+            SPRITE_BATCH_PIPELINE_HANDLE,
+            build_sprite_batch_pipeline(&mut shaders),
+        );
+        pipelines.set_untracked(
+            SPRITE_SHEET_BATCH_PIPELINE_HANDLE,
+            build_sprite_sheet_batch_pipeline(&mut shaders),
+        );
+        pipelines.set_untracked(
+            LIT_SPRITE_PIPELINE_HANDLE,
+            build_lit_sprite_pipeline(&mut shaders),
+        );
         self
     }
 }
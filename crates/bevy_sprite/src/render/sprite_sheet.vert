@@ -33,17 +33,29 @@ layout(set = 2, binding = 0) uniform Transform {
 layout(set = 2, binding = 1) uniform TextureAtlasSprite {
     vec4 TextureAtlasSprite_color;
     uint TextureAtlasSprite_index;
+    uint TextureAtlasSprite_flip_x;
+    uint TextureAtlasSprite_flip_y;
 };
 
 void main() {
     Rect sprite_rect = Textures[TextureAtlasSprite_index];
     vec2 sprite_dimensions = sprite_rect.end - sprite_rect.begin;
     vec3 vertex_position = vec3(Vertex_Position.xy * sprite_dimensions, 0.0);
+    vec2 uv_begin = sprite_rect.begin;
+    vec2 uv_end = sprite_rect.end;
+    if (TextureAtlasSprite_flip_x != 0) {
+        uv_begin.x = sprite_rect.end.x;
+        uv_end.x = sprite_rect.begin.x;
+    }
+    if (TextureAtlasSprite_flip_y != 0) {
+        uv_begin.y = sprite_rect.end.y;
+        uv_end.y = sprite_rect.begin.y;
+    }
     vec2 atlas_positions[4] = vec2[](
-        vec2(sprite_rect.begin.x, sprite_rect.end.y),
-        sprite_rect.begin,
-        vec2(sprite_rect.end.x, sprite_rect.begin.y), 
-        sprite_rect.end
+        vec2(uv_begin.x, uv_end.y),
+        uv_begin,
+        vec2(uv_end.x, uv_begin.y),
+        uv_end
     );
     v_Uv = (atlas_positions[gl_VertexIndex] + vec2(0.01, 0.01)) / AtlasSize;
     v_Color = TextureAtlasSprite_color;
@@ -1,18 +1,36 @@
-use crate::ColorMaterial;
+use crate::{ColorMaterial, LitColorMaterial};
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::{Query, Res};
 use bevy_math::Vec2;
-use bevy_render::{renderer::RenderResources, texture::Texture};
+use bevy_render::{color::Color, renderer::RenderResources, texture::Texture};
 use bevy_type_registry::TypeUuid;
 
-#[derive(Debug, Default, RenderResources, TypeUuid)]
+#[derive(Debug, RenderResources, TypeUuid)]
 #[uuid = "7233c597-ccfa-411f-bd59-9af349432ada"]
 pub struct Sprite {
     pub size: Vec2,
+    /// Multiplied with the color of the sprite's material, so sprites sharing a material can be tinted separately
+    pub color: Color,
+    /// Mirrors the texture horizontally
+    pub flip_x: bool,
+    /// Mirrors the texture vertically
+    pub flip_y: bool,
     #[render_resources(ignore)]
     pub resize_mode: SpriteResizeMode,
 }
 
+impl Default for Sprite {
+    fn default() -> Self {
+        Self {
+            size: Default::default(),
+            color: Color::WHITE,
+            flip_x: false,
+            flip_y: false,
+            resize_mode: Default::default(),
+        }
+    }
+}
+
 /// Determines how `Sprite` resize should be handled
 #[derive(Debug)]
 pub enum SpriteResizeMode {
@@ -32,6 +50,7 @@ impl Sprite {
         Self {
             size,
             resize_mode: SpriteResizeMode::Manual,
+            ..Default::default()
         }
     }
 }
@@ -42,16 +61,41 @@ pub fn sprite_system(
     mut query: Query<(&mut Sprite, &Handle<ColorMaterial>)>,
 ) {
     for (mut sprite, handle) in query.iter_mut() {
-        match sprite.resize_mode {
-            SpriteResizeMode::Manual => continue,
-            SpriteResizeMode::Automatic => {
-                let material = materials.get(handle).unwrap();
-                if let Some(ref texture_handle) = material.texture {
-                    if let Some(texture) = textures.get(texture_handle) {
-                        sprite.size = texture.size;
-                    }
-                }
+        if let SpriteResizeMode::Manual = sprite.resize_mode {
+            continue;
+        }
+        if let Some(material) = materials.get(handle) {
+            if let Some(size) = texture_size(material.texture.as_ref(), &textures) {
+                sprite.size = size;
+            }
+        }
+    }
+}
+
+/// Resizes sprites with a [LitColorMaterial] like [sprite_system] does for sprites with a [ColorMaterial]
+pub fn lit_sprite_system(
+    materials: Res<Assets<LitColorMaterial>>,
+    textures: Res<Assets<Texture>>,
+    mut query: Query<(&mut Sprite, &Handle<LitColorMaterial>)>,
+) {
+    for (mut sprite, handle) in query.iter_mut() {
+        if let SpriteResizeMode::Manual = sprite.resize_mode {
+            continue;
+        }
+        if let Some(material) = materials.get(handle) {
+            if let Some(size) = texture_size(material.texture.as_ref(), &textures) {
+                sprite.size = size;
             }
         }
     }
 }
+
+/// The size of the material's texture, if it has one and it is loaded
+fn texture_size(
+    texture_handle: Option<&Handle<Texture>>,
+    textures: &Assets<Texture>,
+) -> Option<Vec2> {
+    texture_handle
+        .and_then(|handle| textures.get(handle))
+        .map(|texture| texture.size)
+}
@@ -0,0 +1,384 @@
+use crate::{
+    ColorMaterial, Sprite, TextureAtlas, TextureAtlasSprite, SPRITE_BATCH_PIPELINE_HANDLE,
+    SPRITE_SHEET_BATCH_PIPELINE_HANDLE,
+};
+use bevy_asset::{Assets, Handle, HandleId};
+use bevy_core::{Byteable, FloatOrd};
+use bevy_ecs::{Bundle, Commands, Query, Res, ResMut, With};
+use bevy_math::{Mat4, Vec2};
+use bevy_render::{
+    color::Color,
+    draw::{Draw, DrawContext, DrawError, Drawable, Visible},
+    pipeline::{
+        InputStepMode, PipelineSpecialization, ShaderSpecialization, VertexAttributeDescriptor,
+        VertexBufferDescriptor, VertexFormat,
+    },
+    prelude::Msaa,
+    render_graph::base::MainPass,
+    renderer::{
+        AssetRenderResourceBindings, BufferUsage, RenderResourceBinding, RenderResourceBindings,
+    },
+    shader::ShaderDefs,
+};
+use bevy_transform::prelude::GlobalTransform;
+use std::{borrow::Cow, ops::Range};
+
+/// Draws the entity's [Sprite] or [TextureAtlasSprite] in a batch with other sprites that share its material or
+/// texture atlas, instead of with a draw call of its own. Batched sprites don't have a [Draw] component, see
+/// [BatchedSpriteComponents](crate::entity::BatchedSpriteComponents) and
+/// [BatchedSpriteSheetComponents](crate::entity::BatchedSpriteSheetComponents).
+#[derive(Debug, Default, Copy, Clone)]
+pub struct BatchedSprite;
+
+/// Marks the entity that batched sprites are drawn with. [SpritePlugin](crate::SpritePlugin) spawns one at startup.
+///
+/// Batched sprites are drawn back to front. A new batch starts whenever the next sprite uses a different material or
+/// texture atlas, so sprites that share a texture are cheapest when they also share a z value. All batches are drawn
+/// after the sprites that have a [Draw] component of their own.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SpriteBatch;
+
+#[derive(Bundle)]
+pub struct SpriteBatchComponents {
+    pub sprite_batch: SpriteBatch,
+    pub draw: Draw,
+    pub visible: Visible,
+    pub main_pass: MainPass,
+}
+
+impl Default for SpriteBatchComponents {
+    fn default() -> Self {
+        Self {
+            sprite_batch: SpriteBatch,
+            draw: Default::default(),
+            visible: Visible {
+                is_transparent: true,
+                ..Default::default()
+            },
+            main_pass: MainPass,
+        }
+    }
+}
+
+/// A vertex of a batched sprite, positioned in world space
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct SpriteVertex {
+    pub position: [f32; 3],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+}
+
+unsafe impl Byteable for SpriteVertex {}
+
+impl SpriteVertex {
+    pub fn vertex_buffer_descriptor() -> VertexBufferDescriptor {
+        VertexBufferDescriptor {
+            name: Cow::Borrowed("SpriteVertex"),
+            stride: std::mem::size_of::<SpriteVertex>() as u64,
+            step_mode: InputStepMode::Vertex,
+            attributes: vec![
+                VertexAttributeDescriptor {
+                    name: Cow::Borrowed("Vertex_Position"),
+                    offset: 0,
+                    format: VertexFormat::Float3,
+                    shader_location: 0,
+                },
+                VertexAttributeDescriptor {
+                    name: Cow::Borrowed("Vertex_Uv"),
+                    offset: VertexFormat::Float3.get_size(),
+                    format: VertexFormat::Float2,
+                    shader_location: 1,
+                },
+                VertexAttributeDescriptor {
+                    name: Cow::Borrowed("Vertex_Color"),
+                    offset: VertexFormat::Float3.get_size() + VertexFormat::Float2.get_size(),
+                    format: VertexFormat::Float4,
+                    shader_location: 2,
+                },
+            ],
+        }
+    }
+}
+
+/// The texture a batch is drawn with
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum BatchTexture {
+    Material(HandleId),
+    Atlas(HandleId),
+}
+
+struct BatchedQuad {
+    z: f32,
+    texture: BatchTexture,
+    vertices: [SpriteVertex; 6],
+}
+
+/// Returns the two triangles of a quad of the given size, centered on the transform's origin. `uv_min` is the top
+/// left of the quad's region of the texture.
+fn quad_vertices(
+    transform: &Mat4,
+    size: Vec2,
+    mut uv_min: Vec2,
+    mut uv_max: Vec2,
+    flip_x: bool,
+    flip_y: bool,
+    color: Color,
+) -> [SpriteVertex; 6] {
+    if flip_x {
+        std::mem::swap(uv_min.x_mut(), uv_max.x_mut());
+    }
+    if flip_y {
+        std::mem::swap(uv_min.y_mut(), uv_max.y_mut());
+    }
+
+    let extents = size / 2.0;
+    let vertex = |x: f32, y: f32, u: f32, v: f32| SpriteVertex {
+        position: transform
+            .transform_point3(Vec2::new(x * extents.x(), y * extents.y()).extend(0.0))
+            .into(),
+        uv: [u, v],
+        color: color.into(),
+    };
+    let south_west = vertex(-1.0, -1.0, uv_min.x(), uv_max.y());
+    let south_east = vertex(1.0, -1.0, uv_max.x(), uv_max.y());
+    let north_east = vertex(1.0, 1.0, uv_max.x(), uv_min.y());
+    let north_west = vertex(-1.0, 1.0, uv_min.x(), uv_min.y());
+    [
+        south_west, south_east, north_east, south_west, north_east, north_west,
+    ]
+}
+
+/// Sorts quads back to front and merges neighbours with the same texture into batches. Returns the vertices of all
+/// quads and the range of vertices each batch covers.
+fn build_batches(
+    mut quads: Vec<BatchedQuad>,
+) -> (Vec<SpriteVertex>, Vec<(BatchTexture, Range<u32>)>) {
+    quads.sort_by_key(|quad| (FloatOrd(quad.z), quad.texture));
+
+    let mut vertices = Vec::with_capacity(quads.len() * 6);
+    let mut batches: Vec<(BatchTexture, Range<u32>)> = Vec::new();
+    for quad in quads {
+        let start = vertices.len() as u32;
+        vertices.extend_from_slice(&quad.vertices);
+        let end = vertices.len() as u32;
+        match batches.last_mut() {
+            Some((texture, range)) if *texture == quad.texture => range.end = end,
+            _ => batches.push((quad.texture, start..end)),
+        }
+    }
+    (vertices, batches)
+}
+
+struct DrawableSpriteBatches<'a> {
+    materials: &'a Assets<ColorMaterial>,
+    render_resource_bindings: &'a mut RenderResourceBindings,
+    asset_render_resource_bindings: &'a mut AssetRenderResourceBindings,
+    msaa: &'a Msaa,
+    vertices: &'a Vec<SpriteVertex>,
+    batches: &'a [(BatchTexture, Range<u32>)],
+}
+
+impl<'a> Drawable for DrawableSpriteBatches<'a> {
+    fn draw(&mut self, draw: &mut Draw, context: &mut DrawContext) -> Result<(), DrawError> {
+        let vertex_buffer = if let RenderResourceBinding::Buffer { buffer, .. } =
+            context.get_buffer(self.vertices, BufferUsage::VERTEX)?
+        {
+            buffer
+        } else {
+            return Err(DrawError::BufferAllocationFailure);
+        };
+        let vertex_buffer_descriptor = SpriteVertex::vertex_buffer_descriptor();
+
+        for (texture, vertices) in self.batches.iter() {
+            let (pipeline, shader_defs, asset_bindings) = match *texture {
+                BatchTexture::Material(id) => {
+                    let handle = Handle::<ColorMaterial>::weak(id);
+                    let material = if let Some(material) = self.materials.get(&handle) {
+                        material
+                    } else {
+                        continue;
+                    };
+                    (
+                        &SPRITE_BATCH_PIPELINE_HANDLE,
+                        material
+                            .iter_shader_defs()
+                            .map(|shader_def| shader_def.to_string())
+                            .collect(),
+                        self.asset_render_resource_bindings.get_mut(&handle),
+                    )
+                }
+                BatchTexture::Atlas(id) => (
+                    &SPRITE_SHEET_BATCH_PIPELINE_HANDLE,
+                    Default::default(),
+                    self.asset_render_resource_bindings
+                        .get_mut(&Handle::<TextureAtlas>::weak(id)),
+                ),
+            };
+            // the bindings of new assets are created when the render graph runs later this frame
+            let asset_bindings = if let Some(asset_bindings) = asset_bindings {
+                asset_bindings
+            } else {
+                continue;
+            };
+
+            context.set_pipeline(
+                draw,
+                pipeline,
+                &PipelineSpecialization {
+                    sample_count: self.msaa.samples,
+                    vertex_buffer_descriptor: vertex_buffer_descriptor.clone(),
+                    shader_specialization: ShaderSpecialization { shader_defs },
+                    ..Default::default()
+                },
+            )?;
+            draw.set_vertex_buffer(0, vertex_buffer, 0);
+            context.set_bind_groups_from_bindings(
+                draw,
+                &mut [self.render_resource_bindings, asset_bindings],
+            )?;
+            draw.draw(vertices.clone(), 0..1);
+        }
+        Ok(())
+    }
+}
+
+pub fn sprite_batch_setup_system(mut commands: Commands) {
+    commands.spawn(SpriteBatchComponents::default());
+}
+
+/// Rebuilds the vertices of all [BatchedSprite]s every frame and draws them with the [SpriteBatch] entity.
+pub fn sprite_batch_system(
+    mut draw_context: DrawContext,
+    msaa: Res<Msaa>,
+    materials: Res<Assets<ColorMaterial>>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    mut render_resource_bindings: ResMut<RenderResourceBindings>,
+    mut asset_render_resource_bindings: ResMut<AssetRenderResourceBindings>,
+    mut batch_query: Query<With<SpriteBatch, &mut Draw>>,
+    sprite_query: Query<
+        With<BatchedSprite, (&Sprite, &Handle<ColorMaterial>, &GlobalTransform, &Visible)>,
+    >,
+    sprite_sheet_query: Query<
+        With<
+            BatchedSprite,
+            (
+                &TextureAtlasSprite,
+                &Handle<TextureAtlas>,
+                &GlobalTransform,
+                &Visible,
+            ),
+        >,
+    >,
+) {
+    let mut quads = Vec::new();
+    for (sprite, material, global_transform, visible) in sprite_query.iter() {
+        if !visible.is_drawn() {
+            continue;
+        }
+        quads.push(BatchedQuad {
+            z: global_transform.translation.z(),
+            texture: BatchTexture::Material(material.id),
+            vertices: quad_vertices(
+                &global_transform.compute_matrix(),
+                sprite.size,
+                Vec2::zero(),
+                Vec2::one(),
+                sprite.flip_x,
+                sprite.flip_y,
+                sprite.color,
+            ),
+        });
+    }
+    for (sprite, texture_atlas_handle, global_transform, visible) in sprite_sheet_query.iter() {
+        if !visible.is_drawn() {
+            continue;
+        }
+        let texture_atlas = if let Some(texture_atlas) = texture_atlases.get(texture_atlas_handle) {
+            texture_atlas
+        } else {
+            continue;
+        };
+        let rect = if let Some(rect) = texture_atlas.textures.get(sprite.index as usize) {
+            rect
+        } else {
+            continue;
+        };
+        quads.push(BatchedQuad {
+            z: global_transform.translation.z(),
+            texture: BatchTexture::Atlas(texture_atlas_handle.id),
+            vertices: quad_vertices(
+                &global_transform.compute_matrix(),
+                Vec2::new(rect.width(), rect.height()),
+                rect.min / texture_atlas.size,
+                rect.max / texture_atlas.size,
+                sprite.flip_x,
+                sprite.flip_y,
+                sprite.color,
+            ),
+        });
+    }
+
+    if quads.is_empty() {
+        return;
+    }
+
+    let (vertices, batches) = build_batches(quads);
+    for mut draw in batch_query.iter_mut() {
+        let mut drawable_batches = DrawableSpriteBatches {
+            materials: &materials,
+            render_resource_bindings: &mut render_resource_bindings,
+            asset_render_resource_bindings: &mut asset_render_resource_bindings,
+            msaa: &msaa,
+            vertices: &vertices,
+            batches: &batches,
+        };
+        drawable_batches.draw(&mut draw, &mut draw_context).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_batches, quad_vertices, BatchTexture, BatchedQuad};
+    use crate::ColorMaterial;
+    use bevy_asset::HandleId;
+    use bevy_math::{Mat4, Vec2, Vec3};
+    use bevy_render::color::Color;
+    use bevy_type_registry::TypeUuid;
+
+    #[test]
+    fn sprite_batching() {
+        let vertices = quad_vertices(
+            &Mat4::from_translation(Vec3::new(10.0, 20.0, 0.0)),
+            Vec2::new(4.0, 2.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.5, 1.0),
+            true,
+            false,
+            Color::WHITE,
+        );
+        // south west corner, with the texture mirrored horizontally
+        assert_eq!(vertices[0].position, [8.0, 19.0, 0.0]);
+        assert_eq!(vertices[0].uv, [0.5, 1.0]);
+        // north east corner
+        assert_eq!(vertices[2].position, [12.0, 21.0, 0.0]);
+        assert_eq!(vertices[2].uv, [0.0, 0.0]);
+
+        let a = BatchTexture::Material(HandleId::new(ColorMaterial::TYPE_UUID, 0));
+        let b = BatchTexture::Material(HandleId::new(ColorMaterial::TYPE_UUID, 1));
+        let quad = |z: f32, texture: BatchTexture| BatchedQuad {
+            z,
+            texture,
+            vertices: vertices.clone(),
+        };
+        let (vertices, batches) = build_batches(vec![
+            quad(2.0, a),
+            quad(0.0, b),
+            quad(1.0, a),
+            quad(0.0, a),
+            quad(1.0, a),
+        ]);
+        assert_eq!(vertices.len(), 30);
+        assert_eq!(batches, vec![(a, 0..6), (b, 6..12), (a, 12..30)]);
+    }
+}
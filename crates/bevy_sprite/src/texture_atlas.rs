@@ -23,6 +23,9 @@ pub struct TextureAtlas {
     pub textures: Vec<Rect>,
     #[render_resources(ignore)]
     pub texture_handles: Option<HashMap<Handle<Texture>, usize>>,
+    /// The names of textures, for atlases loaded from files
+    #[render_resources(ignore)]
+    pub texture_names: Option<HashMap<String, usize>>,
 }
 
 // NOTE: cannot do `unsafe impl Byteable` here because Vec3 takes up the space of a Vec4. If/when glam changes this we can swap out
@@ -30,8 +33,13 @@ pub struct TextureAtlas {
 #[derive(Bytes, Debug, RenderResources, RenderResource)]
 #[render_resources(from_self)]
 pub struct TextureAtlasSprite {
+    /// Multiplied with the color of the texture
     pub color: Color,
     pub index: u32,
+    /// Mirrors the texture horizontally
+    pub flip_x: bool,
+    /// Mirrors the texture vertically
+    pub flip_y: bool,
 }
 
 impl Default for TextureAtlasSprite {
@@ -39,6 +47,8 @@ impl Default for TextureAtlasSprite {
         Self {
             index: 0,
             color: Color::WHITE,
+            flip_x: false,
+            flip_y: false,
         }
     }
 }
@@ -60,10 +70,23 @@ impl TextureAtlas {
             texture,
             size: dimensions,
             texture_handles: None,
+            texture_names: None,
             textures: Vec::new(),
         }
     }
 
+    /// Create a new `TextureAtlas` from textures that were already packed into a texture, for example by an external
+    /// tool. Each rect is the area of one texture, in pixels from the top-left corner of the atlas.
+    pub fn from_rects(texture: Handle<Texture>, dimensions: Vec2, rects: Vec<Rect>) -> Self {
+        Self {
+            texture,
+            size: dimensions,
+            texture_handles: None,
+            texture_names: None,
+            textures: rects,
+        }
+    }
+
     /// Generate a `TextureAtlas` by splitting a texture into a grid where each
     /// cell of the grid  of `tile_size` is one of the textures in the atlas
     pub fn from_grid(
@@ -118,6 +141,7 @@ impl TextureAtlas {
             textures: sprites,
             texture,
             texture_handles: None,
+            texture_names: None,
         }
     }
 
@@ -145,4 +169,10 @@ impl TextureAtlas {
             .as_ref()
             .and_then(|texture_handles| texture_handles.get(texture).cloned())
     }
+
+    pub fn get_texture_index_by_name(&self, name: &str) -> Option<usize> {
+        self.texture_names
+            .as_ref()
+            .and_then(|texture_names| texture_names.get(name).cloned())
+    }
 }
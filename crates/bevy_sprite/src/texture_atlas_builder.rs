@@ -9,12 +9,18 @@ use rectangle_pack::{
 };
 use thiserror::Error;
 
+/// Packs textures into a single [TextureAtlas] at runtime. The atlas starts at `initial_size` and doubles in size
+/// until the textures fit, up to `max_size`. Use [TextureAtlas::get_texture_index] to look up where a texture went.
 #[derive(Debug)]
 pub struct TextureAtlasBuilder {
     pub textures: Vec<Handle<Texture>>,
     pub rects_to_place: GroupedRectsToPlace<Handle<Texture>>,
     pub initial_size: Vec2,
     pub max_size: Vec2,
+    /// The number of empty pixels after each texture, so neighbouring textures don't bleed into each other when
+    /// they are sampled with linear filtering. It's applied when the atlas is packed, so it also applies to the
+    /// textures that were added before it was set.
+    pub padding: u32,
 }
 
 impl Default for TextureAtlasBuilder {
@@ -27,6 +33,8 @@ impl Default for TextureAtlasBuilder {
 pub enum RectanglePackError {
     #[error("Could not pack textures into an atlas within the given bounds")]
     NotEnoughSpace,
+    #[error("Texture has the format {0:?}, but atlases are Rgba8UnormSrgb")]
+    WrongFormat(TextureFormat),
 }
 
 impl TextureAtlasBuilder {
@@ -36,10 +44,17 @@ impl TextureAtlasBuilder {
             rects_to_place: GroupedRectsToPlace::new(),
             initial_size,
             max_size,
+            padding: 0,
         }
     }
 
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
     pub fn add_texture(&mut self, texture_handle: Handle<Texture>, texture: &Texture) {
+        self.textures.push(texture_handle.clone_weak());
         self.rects_to_place.push_rect(
             texture_handle,
             None,
@@ -47,14 +62,35 @@ impl TextureAtlasBuilder {
         )
     }
 
+    /// The rects of the added textures, grown by the padding
+    fn padded_rects_to_place(
+        &self,
+        textures: &Assets<Texture>,
+    ) -> GroupedRectsToPlace<Handle<Texture>> {
+        let mut rects_to_place = GroupedRectsToPlace::new();
+        for texture_handle in self.textures.iter() {
+            let texture = textures.get(texture_handle).unwrap();
+            rects_to_place.push_rect(
+                texture_handle.clone_weak(),
+                None,
+                RectToInsert::new(
+                    texture.size.x() as u32 + self.padding,
+                    texture.size.y() as u32 + self.padding,
+                    1,
+                ),
+            );
+        }
+        rects_to_place
+    }
+
     fn place_texture(
         &mut self,
         atlas_texture: &mut Texture,
         texture: &Texture,
         packed_location: &PackedLocation,
     ) {
-        let rect_width = packed_location.width() as usize;
-        let rect_height = packed_location.height() as usize;
+        let rect_width = texture.size.x() as usize;
+        let rect_height = texture.size.y() as usize;
         let rect_x = packed_location.x() as usize;
         let rect_y = packed_location.y() as usize;
         let atlas_width = atlas_texture.size.x() as usize;
@@ -83,6 +119,14 @@ impl TextureAtlasBuilder {
         let mut current_height = initial_height;
         let mut rect_placements = None;
         let mut atlas_texture = Texture::default();
+        let padded_rects_to_place = if self.padding > 0 {
+            Some(self.padded_rects_to_place(textures))
+        } else {
+            None
+        };
+        let rects_to_place = padded_rects_to_place
+            .as_ref()
+            .unwrap_or(&self.rects_to_place);
 
         while rect_placements.is_none() {
             if current_width > max_width || current_height > max_height {
@@ -97,7 +141,7 @@ impl TextureAtlasBuilder {
                 TextureFormat::Rgba8UnormSrgb,
             );
             rect_placements = match pack_rects(
-                &self.rects_to_place,
+                rects_to_place,
                 target_bins,
                 &volume_heuristic,
                 &contains_smallest_box,
@@ -117,12 +161,11 @@ impl TextureAtlasBuilder {
         let mut texture_handles = HashMap::default();
         for (texture_handle, (_, packed_location)) in rect_placements.packed_locations().iter() {
             let texture = textures.get(texture_handle).unwrap();
+            if texture.format != atlas_texture.format {
+                return Err(RectanglePackError::WrongFormat(texture.format));
+            }
             let min = Vec2::new(packed_location.x() as f32, packed_location.y() as f32);
-            let max = min
-                + Vec2::new(
-                    packed_location.width() as f32,
-                    packed_location.height() as f32,
-                );
+            let max = min + texture.size;
             texture_handles.insert(texture_handle.clone_weak(), texture_rects.len());
             texture_rects.push(Rect { min, max });
             self.place_texture(&mut atlas_texture, texture, packed_location);
@@ -132,6 +175,61 @@ impl TextureAtlasBuilder {
             texture: textures.add(atlas_texture),
             textures: texture_rects,
             texture_handles: Some(texture_handles),
+            texture_names: None,
         })
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::{RectanglePackError, TextureAtlasBuilder};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_math::Vec2;
+    use bevy_render::texture::{Texture, TextureFormat};
+    use bevy_type_registry::TypeRegistry;
+
+    #[test]
+    fn padding_applies_to_textures_added_before_it() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Texture>();
+        let mut textures = app.resources().get_mut::<Assets<Texture>>().unwrap();
+        let texture = Texture::new_fill(
+            Vec2::new(8.0, 8.0),
+            &[255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        let builder = |max_size: f32, textures: &mut Assets<Texture>| {
+            let mut builder =
+                TextureAtlasBuilder::new(Vec2::new(16.0, 16.0), Vec2::new(max_size, max_size));
+            for _ in 0..4 {
+                builder.add_texture(textures.add(texture.clone()), &texture);
+            }
+            builder.with_padding(1)
+        };
+
+        // four 8x8 textures fill a 16x16 atlas, but don't fit once they are padded
+        assert!(matches!(
+            builder(16.0, &mut textures).finish(&mut textures),
+            Err(RectanglePackError::NotEnoughSpace)
+        ));
+
+        let atlas = builder(32.0, &mut textures).finish(&mut textures).unwrap();
+        assert_eq!(atlas.size, Vec2::new(32.0, 32.0));
+        for (i, a) in atlas.textures.iter().enumerate() {
+            assert_eq!(a.max - a.min, Vec2::new(8.0, 8.0));
+            for b in atlas.textures.iter().skip(i + 1) {
+                assert!(
+                    a.max.x() + 1.0 <= b.min.x()
+                        || b.max.x() + 1.0 <= a.min.x()
+                        || a.max.y() + 1.0 <= b.min.y()
+                        || b.max.y() + 1.0 <= a.min.y()
+                );
+            }
+        }
+    }
+}
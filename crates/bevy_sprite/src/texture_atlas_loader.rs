@@ -0,0 +1,196 @@
+use crate::{Rect, TextureAtlas};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
+use bevy_math::Vec2;
+use bevy_utils::{BoxedFuture, HashMap};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Loads [TextureAtlas]es from `.atlas.json` files in the JSON format written by TexturePacker and similar tools,
+/// either with a "frames" array or a "frames" object keyed by name. Textures in an object are indexed in name
+/// order. The atlas texture is loaded from the "image" path in "meta", relative to the atlas file.
+#[derive(Default)]
+pub struct TextureAtlasLoader;
+
+#[derive(Error, Debug)]
+pub enum TextureAtlasLoaderError {
+    #[error("Rotated frames are not supported: {0}")]
+    RotatedFrame(String),
+}
+
+#[derive(Deserialize)]
+struct AtlasFile {
+    frames: AtlasFrames,
+    meta: AtlasMeta,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AtlasFrames {
+    Array(Vec<NamedAtlasFrame>),
+    Hash(BTreeMap<String, AtlasFrame>),
+}
+
+#[derive(Deserialize)]
+struct NamedAtlasFrame {
+    filename: String,
+    #[serde(flatten)]
+    frame: AtlasFrame,
+}
+
+#[derive(Deserialize)]
+struct AtlasFrame {
+    frame: AtlasRect,
+    #[serde(default)]
+    rotated: bool,
+}
+
+#[derive(Deserialize)]
+struct AtlasRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct AtlasMeta {
+    image: String,
+    size: AtlasSize,
+}
+
+#[derive(Deserialize)]
+struct AtlasSize {
+    w: f32,
+    h: f32,
+}
+
+/// The contents of an atlas file, with its frames in atlas index order
+struct ParsedAtlas {
+    image: String,
+    size: Vec2,
+    textures: Vec<Rect>,
+    texture_names: HashMap<String, usize>,
+}
+
+fn parse_atlas(bytes: &[u8]) -> Result<ParsedAtlas> {
+    let atlas_file: AtlasFile = serde_json::from_slice(bytes)?;
+    let frames = match atlas_file.frames {
+        AtlasFrames::Array(frames) => frames
+            .into_iter()
+            .map(|frame| (frame.filename, frame.frame))
+            .collect::<Vec<_>>(),
+        AtlasFrames::Hash(frames) => frames.into_iter().collect::<Vec<_>>(),
+    };
+
+    let mut textures = Vec::with_capacity(frames.len());
+    let mut texture_names = HashMap::default();
+    for (name, frame) in frames {
+        if frame.rotated {
+            return Err(TextureAtlasLoaderError::RotatedFrame(name).into());
+        }
+        let min = Vec2::new(frame.frame.x, frame.frame.y);
+        let max = min + Vec2::new(frame.frame.w, frame.frame.h);
+        texture_names.insert(name, textures.len());
+        textures.push(Rect { min, max });
+    }
+
+    Ok(ParsedAtlas {
+        image: atlas_file.meta.image,
+        size: Vec2::new(atlas_file.meta.size.w, atlas_file.meta.size.h),
+        textures,
+        texture_names,
+    })
+}
+
+impl AssetLoader for TextureAtlasLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let atlas = parse_atlas(bytes)?;
+            let image_path = load_context.path().parent().unwrap().join(&atlas.image);
+            let image_asset_path = AssetPath::new(image_path, None);
+            let mut texture_atlas = TextureAtlas::from_rects(
+                load_context.get_handle(image_asset_path.clone()),
+                atlas.size,
+                atlas.textures,
+            );
+            texture_atlas.texture_names = Some(atlas.texture_names);
+            load_context.set_default_asset(
+                LoadedAsset::new(texture_atlas).with_dependency(image_asset_path),
+            );
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["atlas.json"];
+        EXTENSIONS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_frames_array() {
+        let atlas = parse_atlas(
+            br#"{
+                "frames": [
+                    { "filename": "run_1.png", "frame": { "x": 24, "y": 0, "w": 24, "h": 24 } },
+                    { "filename": "idle.png", "frame": { "x": 0, "y": 0, "w": 24, "h": 24 }, "rotated": false }
+                ],
+                "meta": { "image": "chars.png", "size": { "w": 48, "h": 24 } }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(atlas.image, "chars.png");
+        assert_eq!(atlas.size, Vec2::new(48.0, 24.0));
+        // array frames keep their order
+        assert_eq!(atlas.texture_names["run_1.png"], 0);
+        assert_eq!(atlas.texture_names["idle.png"], 1);
+        assert_eq!(atlas.textures[0].min, Vec2::new(24.0, 0.0));
+        assert_eq!(atlas.textures[0].max, Vec2::new(48.0, 24.0));
+    }
+
+    #[test]
+    fn parse_frames_hash() {
+        let atlas = parse_atlas(
+            br#"{
+                "frames": {
+                    "run_1.png": { "frame": { "x": 24, "y": 0, "w": 24, "h": 24 } },
+                    "idle.png": { "frame": { "x": 0, "y": 0, "w": 24, "h": 24 } }
+                },
+                "meta": { "image": "chars.png", "size": { "w": 48, "h": 24 } }
+            }"#,
+        )
+        .unwrap();
+        // hash frames are indexed in name order
+        assert_eq!(atlas.texture_names["idle.png"], 0);
+        assert_eq!(atlas.texture_names["run_1.png"], 1);
+        assert_eq!(atlas.textures[1].min, Vec2::new(24.0, 0.0));
+    }
+
+    #[test]
+    fn rotated_frames_are_rejected() {
+        let error = parse_atlas(
+            br#"{
+                "frames": [
+                    { "filename": "idle.png", "frame": { "x": 0, "y": 0, "w": 24, "h": 24 }, "rotated": true }
+                ],
+                "meta": { "image": "chars.png", "size": { "w": 24, "h": 24 } }
+            }"#,
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(
+            error.downcast_ref::<TextureAtlasLoaderError>(),
+            Some(TextureAtlasLoaderError::RotatedFrame(name)) if name == "idle.png"
+        ));
+    }
+}
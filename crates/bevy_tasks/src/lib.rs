@@ -27,6 +27,7 @@ pub mod prelude {
     pub use crate::{
         iter::ParallelIterator,
         slice::{ParallelSlice, ParallelSliceMut},
+        task::Task,
         usages::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool},
     };
 }
@@ -83,32 +83,38 @@ impl TaskPool {
             .collect()
     }
 
-    // Spawns a static future onto the JS event loop. For now it is returning FakeTask
-    // instance with no-op detach method. Returning real Task is possible here, but tricky:
-    // future is running on JS event loop, Task is running on async_executor::LocalExecutor
-    // so some proxy future is needed. Moreover currently we don't have long-living
-    // LocalExecutor here (above `spawn` implementation creates temporary one)
-    // But for typical use cases it seems that current implementation should be sufficient:
-    // caller can spawn long-running future writing results to some channel / event queue
-    // and simply call detach on returned Task (like AssetServer does) - spawned future
-    // can write results to some channel / event queue.
-
-    pub fn spawn<T>(&self, future: impl Future<Output = T> + 'static) -> FakeTask
+    // Spawns a static future onto the JS event loop. Returning a real Task is possible here, but
+    // tricky: the future is running on the JS event loop, while a Task runs on an
+    // async_executor::LocalExecutor, and we don't have a long-living LocalExecutor here (the
+    // `scope` implementation above creates a temporary one). Instead the future writes its output
+    // to a slot that the returned FakeTask can be polled for.
+    pub fn spawn<T>(&self, future: impl Future<Output = T> + 'static) -> FakeTask<T>
     where
         T: 'static,
     {
+        let output = Arc::new(Mutex::new(None));
+        let task_output = output.clone();
         wasm_bindgen_futures::spawn_local(async move {
-            future.await;
+            let value = future.await;
+            task_output.lock().unwrap().replace(value);
         });
-        FakeTask
+        FakeTask { output }
     }
 }
 
+/// The output of a future spawned on the JS event loop, with the polling API of [Task](crate::Task)
 #[derive(Debug)]
-pub struct FakeTask;
+pub struct FakeTask<T> {
+    output: Arc<Mutex<Option<T>>>,
+}
 
-impl FakeTask {
+impl<T> FakeTask<T> {
     pub fn detach(self) {}
+
+    /// Returns the future's output if it completed, without waiting for it
+    pub fn poll_once(&mut self) -> Option<T> {
+        self.output.lock().unwrap().take()
+    }
 }
 
 #[derive(Debug)]
@@ -1,3 +1,4 @@
+use futures_lite::future;
 use std::{
     future::Future,
     pin::Pin,
@@ -39,6 +40,15 @@ impl<T> Task<T> {
     pub async fn cancel(self) -> Option<T> {
         self.0.cancel().await
     }
+
+    /// Returns the task's output if it completed, without waiting for it. This lets a system check on a task once
+    /// per frame, for example when the task is stored in a component.
+    ///
+    /// The output can only be taken once: polling the task again after it returned its output panics, so drop the
+    /// task or remove its component instead.
+    pub fn poll_once(&mut self) -> Option<T> {
+        future::block_on(future::poll_once(&mut self.0))
+    }
 }
 
 impl<T> Future for Task<T> {
@@ -262,4 +262,21 @@ mod tests {
         assert_eq!(outputs.len(), 100);
         assert_eq!(count.load(Ordering::Relaxed), 100);
     }
+
+    #[test]
+    pub fn test_poll_once() {
+        let pool = TaskPool::new();
+        let (sender, receiver) = async_channel::bounded(1);
+        let mut task = pool.spawn(async move { receiver.recv().await.unwrap() * 2 });
+
+        assert_eq!(task.poll_once(), None);
+        future::block_on(sender.send(21)).unwrap();
+        let output = loop {
+            if let Some(output) = task.poll_once() {
+                break output;
+            }
+            std::thread::yield_now();
+        };
+        assert_eq!(output, 42);
+    }
 }
@@ -1,12 +1,15 @@
-use crate::{Font, FontAtlasSet};
-use ab_glyph::{Glyph, PxScale, ScaleFont};
-use bevy_asset::Assets;
+use crate::{
+    Font, FontAtlasSet, GlyphRendering, PositionedGlyph, TextSection,
+    DISTANCE_FIELD_TEXT_PIPELINE_HANDLE,
+};
+use ab_glyph::{PxScale, ScaleFont};
+use bevy_asset::{Assets, Handle};
 use bevy_math::{Mat4, Vec2, Vec3};
 use bevy_render::{
     color::Color,
     draw::{Draw, DrawContext, DrawError, Drawable},
     mesh,
-    pipeline::{PipelineSpecialization, VertexBufferDescriptor},
+    pipeline::{PipelineDescriptor, PipelineSpecialization, VertexBufferDescriptor},
     prelude::Msaa,
     renderer::{
         AssetRenderResourceBindings, BindGroup, BufferUsage, RenderResourceBindings,
@@ -30,32 +33,55 @@ impl Default for TextStyle {
     }
 }
 
+/// Draws laid out glyphs, see [TextLayout](crate::TextLayout). `position` is the origin the glyph positions are
+/// relative to.
 pub struct DrawableText<'a> {
-    pub font: &'a Font,
-    pub font_atlas_set: &'a FontAtlasSet,
+    pub fonts: &'a Assets<Font>,
+    pub font_atlas_sets: &'a Assets<FontAtlasSet>,
     pub texture_atlases: &'a Assets<TextureAtlas>,
     pub render_resource_bindings: &'a mut RenderResourceBindings,
     pub asset_render_resource_bindings: &'a mut AssetRenderResourceBindings,
     pub position: Vec3,
-    pub container_size: Vec2,
-    pub style: &'a TextStyle,
-    pub text: &'a str,
+    pub sections: &'a [TextSection],
+    pub glyphs: &'a [PositionedGlyph],
     pub msaa: &'a Msaa,
     pub font_quad_vertex_descriptor: &'a VertexBufferDescriptor,
 }
 
-impl<'a> Drawable for DrawableText<'a> {
-    fn draw(&mut self, draw: &mut Draw, context: &mut DrawContext) -> Result<(), DrawError> {
+impl<'a> DrawableText<'a> {
+    /// Switches to the pipeline for the font's glyphs, unless it is the current one
+    fn set_pipeline(
+        &mut self,
+        draw: &mut Draw,
+        context: &mut DrawContext,
+        current_pipeline: &mut Option<Handle<PipelineDescriptor>>,
+        font: &Font,
+    ) -> Result<(), DrawError> {
+        let pipeline = match font.rendering {
+            GlyphRendering::Bitmap => bevy_sprite::SPRITE_SHEET_PIPELINE_HANDLE,
+            GlyphRendering::DistanceField { .. } => DISTANCE_FIELD_TEXT_PIPELINE_HANDLE,
+        };
+        if current_pipeline.as_ref() == Some(&pipeline) {
+            return Ok(());
+        }
         context.set_pipeline(
             draw,
-            &bevy_sprite::SPRITE_SHEET_PIPELINE_HANDLE,
+            &pipeline,
             &PipelineSpecialization {
                 sample_count: self.msaa.samples,
                 vertex_buffer_descriptor: self.font_quad_vertex_descriptor.clone(),
                 ..Default::default()
             },
         )?;
+        // set global bindings
+        context.set_bind_groups_from_bindings(draw, &mut [self.render_resource_bindings])?;
+        *current_pipeline = Some(pipeline);
+        Ok(())
+    }
+}
 
+impl<'a> Drawable for DrawableText<'a> {
+    fn draw(&mut self, draw: &mut Draw, context: &mut DrawContext) -> Result<(), DrawError> {
         let render_resource_context = &**context.render_resource_context;
 
         if let Some(RenderResourceId::Buffer(vertex_attribute_buffer_id)) = render_resource_context
@@ -78,36 +104,32 @@ impl<'a> Drawable for DrawableText<'a> {
             }
         }
 
-        // set global bindings
-        context.set_bind_groups_from_bindings(draw, &mut [self.render_resource_bindings])?;
-
+        let mut current_pipeline = None;
         // NOTE: this uses ab_glyph apis directly. it _might_ be a good idea to add our own layer on top
-        let font = &self.font.font;
-        let scale = PxScale::from(self.style.font_size);
-        let scaled_font = ab_glyph::Font::as_scaled(&font, scale);
-        let mut caret = self.position;
-        let mut last_glyph: Option<Glyph> = None;
-
-        // set local per-character bindings
-        for character in self.text.chars() {
-            if character.is_control() {
-                if character == '\n' {
-                    caret.set_x(self.position.x());
-                    // TODO: Necessary to also calculate scaled_font.line_gap() in here?
-                    caret.set_y(caret.y() - scaled_font.height());
-                }
+        for positioned_glyph in self.glyphs.iter() {
+            let section = &self.sections[positioned_glyph.section_index];
+            let font = if let Some(font) = self.fonts.get(&section.font) {
+                font
+            } else {
                 continue;
-            }
-
-            let glyph = scaled_font.scaled_glyph(character);
-            if let Some(last_glyph) = last_glyph.take() {
-                caret.set_x(caret.x() + scaled_font.kern(last_glyph.id, glyph.id));
-            }
-            if let Some(glyph_atlas_info) = self
-                .font_atlas_set
-                .get_glyph_atlas_info(self.style.font_size, character)
-            {
-                if let Some(outlined) = scaled_font.outline_glyph(glyph.clone()) {
+            };
+            let font_atlas_set =
+                if let Some(font_atlas_set) = self.font_atlas_sets.get(section.font.id) {
+                    font_atlas_set
+                } else {
+                    continue;
+                };
+            // glyphs are drawn from outlines at the size they were rasterized at, and scaled to the font size
+            let raster_size = font.rendering.raster_size(section.style.font_size);
+            let scaled_font = ab_glyph::Font::as_scaled(&font.font, PxScale::from(raster_size));
+            let glyph = scaled_font.scaled_glyph(positioned_glyph.character);
+            if let Some(glyph_atlas_info) = font_atlas_set.get_glyph_atlas_info(
+                section.style.font_size,
+                font.rendering,
+                positioned_glyph.character,
+            ) {
+                if let Some(outlined) = scaled_font.outline_glyph(glyph) {
+                    self.set_pipeline(draw, context, &mut current_pipeline, font)?;
                     let texture_atlas = self
                         .texture_atlases
                         .get(&glyph_atlas_info.texture_atlas)
@@ -125,13 +147,28 @@ impl<'a> Drawable for DrawableText<'a> {
                     )?;
 
                     let bounds = outlined.px_bounds();
-                    let x = bounds.min.x + glyph_width / 2.0;
-                    // the 0.5 accounts for odd-numbered heights (bump up by 1 pixel)
-                    let y = -bounds.max.y + glyph_height / 2.0 - scaled_font.descent() + 0.5;
-                    let transform = Mat4::from_translation(caret + Vec3::new(x, y, 0.0));
+                    let (offset, scale) = match font.rendering {
+                        GlyphRendering::Bitmap => {
+                            let x = bounds.min.x + glyph_width / 2.0;
+                            // the 0.5 accounts for odd-numbered heights (bump up by 1 pixel)
+                            let y = -bounds.max.y + glyph_height / 2.0 + 0.5;
+                            (Vec2::new(x, y), 1.0)
+                        }
+                        GlyphRendering::DistanceField { .. } => {
+                            // the distance field is padded evenly, so it is centered on the glyph's bounds
+                            let scale = section.style.font_size / raster_size;
+                            let x = (bounds.min.x + bounds.max.x) / 2.0 * scale;
+                            let y = -(bounds.min.y + bounds.max.y) / 2.0 * scale;
+                            (Vec2::new(x, y), scale)
+                        }
+                    };
+                    let transform = Mat4::from_translation(
+                        self.position + positioned_glyph.position.extend(0.0) + offset.extend(0.0),
+                    ) * Mat4::from_scale(Vec3::new(scale, scale, 1.0));
                     let sprite = TextureAtlasSprite {
                         index: glyph_atlas_info.char_index,
-                        color: self.style.color,
+                        color: section.style.color,
+                        ..Default::default()
                     };
 
                     let transform_buffer = context
@@ -152,8 +189,6 @@ impl<'a> Drawable for DrawableText<'a> {
                     draw.draw_indexed(indices.clone(), 0, 0..1);
                 }
             }
-            caret.set_x(caret.x() + scaled_font.h_advance(glyph.id));
-            last_glyph = Some(glyph);
         }
         Ok(())
     }
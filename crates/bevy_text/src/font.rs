@@ -6,10 +6,49 @@ use bevy_render::{
 };
 use bevy_type_registry::TypeUuid;
 
+/// How the glyphs of a [Font] are rasterized into font atlases
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GlyphRendering {
+    /// Glyphs are rasterized at every font size they are drawn at. They are sharpest at that size, but blurry when
+    /// scaled.
+    Bitmap,
+    /// Glyphs are rasterized once, at `size`, as signed distance fields that reach `spread` pixels from their edges.
+    /// They stay sharp at any font size and scale, and all sizes share one atlas, but sharp corners are slightly
+    /// rounded.
+    DistanceField { size: f32, spread: f32 },
+}
+
+impl Default for GlyphRendering {
+    fn default() -> Self {
+        GlyphRendering::Bitmap
+    }
+}
+
+impl GlyphRendering {
+    /// Distance fields rasterized at a size that suits most fonts
+    pub fn distance_field() -> Self {
+        GlyphRendering::DistanceField {
+            size: 48.0,
+            spread: 6.0,
+        }
+    }
+
+    /// The font size glyphs drawn at `font_size` are rasterized at
+    pub fn raster_size(&self, font_size: f32) -> f32 {
+        match *self {
+            GlyphRendering::Bitmap => font_size,
+            GlyphRendering::DistanceField { size, .. } => size,
+        }
+    }
+}
+
 #[derive(Debug, TypeUuid)]
 #[uuid = "97059ac6-c9ba-4da9-95b6-bed82c3ce198"]
 pub struct Font {
     pub font: FontVec,
+    /// Fonts are loaded with the rendering of the [FontLoader](crate::FontLoader). Texts are rasterized again when it
+    /// changes.
+    pub rendering: GlyphRendering,
 }
 
 unsafe impl Send for Font {}
@@ -18,7 +57,68 @@ unsafe impl Sync for Font {}
 impl Font {
     pub fn try_from_bytes(font_data: Vec<u8>) -> Result<Self, InvalidFont> {
         let font = FontVec::try_from_vec(font_data)?;
-        Ok(Font { font })
+        Ok(Font {
+            font,
+            rendering: GlyphRendering::default(),
+        })
+    }
+
+    /// The glyph's texture for this font's [GlyphRendering]
+    pub fn get_glyph_texture(&self, outlined_glyph: OutlinedGlyph) -> Texture {
+        match self.rendering {
+            GlyphRendering::Bitmap => Font::get_outlined_glyph_texture(outlined_glyph),
+            GlyphRendering::DistanceField { spread, .. } => {
+                Font::get_distance_field_glyph_texture(outlined_glyph, spread)
+            }
+        }
+    }
+
+    /// A texture whose alpha is the signed distance to the glyph's edge: 0.5 on the edge, rising to 1.0 at `spread`
+    /// pixels inside and falling to 0.0 at `spread` pixels outside. The texture has `spread` pixels of padding on each
+    /// side, so the field isn't cut off.
+    pub fn get_distance_field_glyph_texture(outlined_glyph: OutlinedGlyph, spread: f32) -> Texture {
+        let bounds = outlined_glyph.px_bounds();
+        let padding = spread.ceil().max(1.0) as usize;
+        let width = bounds.width() as usize + 2 * padding;
+        let height = bounds.height() as usize + 2 * padding;
+        let mut coverage = vec![0.0; width * height];
+        outlined_glyph.draw(|x, y, v| {
+            coverage[(y as usize + padding) * width + x as usize + padding] = v;
+        });
+
+        let radius = padding as isize;
+        let mut data = Vec::with_capacity(width * height * 4);
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                let value = coverage[y as usize * width + x as usize];
+                let inside = value >= 0.5;
+                // the edge crosses partly covered pixels, at a distance that depends on their coverage
+                let mut distance = if value > 0.0 && value < 1.0 {
+                    (value - 0.5).abs()
+                } else {
+                    spread
+                };
+                // otherwise the edge is about halfway to the nearest pixel on its other side
+                for other_y in (y - radius).max(0)..(y + radius + 1).min(height as isize) {
+                    for other_x in (x - radius).max(0)..(x + radius + 1).min(width as isize) {
+                        if (coverage[other_y as usize * width + other_x as usize] >= 0.5) != inside
+                        {
+                            let offset = Vec2::new((other_x - x) as f32, (other_y - y) as f32);
+                            distance = distance.min(offset.length() - 0.5);
+                        }
+                    }
+                }
+                let signed_distance = if inside { distance } else { -distance };
+                let alpha = (0.5 + signed_distance / (2.0 * spread)).max(0.0).min(1.0);
+                data.extend_from_slice(&[255, 255, 255, (alpha * 255.0).round() as u8]);
+            }
+        }
+
+        Texture::new(
+            Vec2::new(width as f32, height as f32),
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
     }
 
     pub fn get_outlined_glyph_texture(outlined_glyph: OutlinedGlyph) -> Texture {
@@ -160,3 +260,41 @@ fn layout_paragraph<F, SF>(
         target.push(glyph);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Font, GlyphRendering};
+    use ab_glyph::ScaleFont;
+
+    #[test]
+    fn distance_field_glyph_texture() {
+        let mut font = Font::try_from_bytes(
+            include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf").to_vec(),
+        )
+        .unwrap();
+        font.rendering = GlyphRendering::DistanceField {
+            size: 32.0,
+            spread: 4.0,
+        };
+        assert_eq!(font.rendering.raster_size(12.0), 32.0);
+        assert_eq!(GlyphRendering::Bitmap.raster_size(12.0), 12.0);
+
+        let scaled_font = ab_glyph::Font::as_scaled(&font.font, 32.0);
+        let outlined = scaled_font
+            .outline_glyph(scaled_font.scaled_glyph('I'))
+            .unwrap();
+        let bounds = outlined.px_bounds();
+        let texture = font.get_glyph_texture(outlined);
+        let width = texture.size.x() as usize;
+        let height = texture.size.y() as usize;
+        assert_eq!(width, bounds.width() as usize + 8);
+        assert_eq!(height, bounds.height() as usize + 8);
+
+        let alpha = |x: usize, y: usize| texture.data[(y * width + x) * 4 + 3];
+        // far outside the glyph, inside its stem, and rising towards the stem
+        assert_eq!(alpha(0, 0), 0);
+        assert!(alpha(width / 2, height / 2) > 160);
+        assert!(alpha(1, height / 2) < alpha(3, height / 2));
+        assert!(alpha(3, height / 2) < 128);
+    }
+}
@@ -1,6 +1,6 @@
 use bevy_asset::{Assets, Handle};
 use bevy_math::Vec2;
-use bevy_render::texture::{Texture, TextureFormat};
+use bevy_render::texture::{SamplerDescriptor, Texture, TextureFormat};
 use bevy_sprite::{DynamicTextureAtlasBuilder, TextureAtlas};
 use bevy_utils::HashMap;
 
@@ -16,11 +16,24 @@ impl FontAtlas {
         texture_atlases: &mut Assets<TextureAtlas>,
         size: Vec2,
     ) -> FontAtlas {
-        let atlas_texture = textures.add(Texture::new_fill(
+        FontAtlas::with_sampler(
+            textures,
+            texture_atlases,
             size,
-            &[0, 0, 0, 0],
-            TextureFormat::Rgba8UnormSrgb,
-        ));
+            SamplerDescriptor::default(),
+        )
+    }
+
+    /// Creates an atlas whose texture is sampled with the given sampler
+    pub fn with_sampler(
+        textures: &mut Assets<Texture>,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        size: Vec2,
+        sampler: SamplerDescriptor,
+    ) -> FontAtlas {
+        let mut texture = Texture::new_fill(size, &[0, 0, 0, 0], TextureFormat::Rgba8UnormSrgb);
+        texture.sampler = sampler;
+        let atlas_texture = textures.add(texture);
         let texture_atlas = TextureAtlas::new_empty(atlas_texture, size);
         Self {
             texture_atlas: texture_atlases.add(texture_atlas),
@@ -1,21 +1,30 @@
-use crate::{Font, FontAtlas};
+use crate::{Font, FontAtlas, GlyphRendering};
 use ab_glyph::{Glyph, ScaleFont};
 use bevy_asset::{Assets, Handle};
 use bevy_core::FloatOrd;
 use bevy_math::Vec2;
-use bevy_render::texture::Texture;
+use bevy_render::texture::{FilterMode, SamplerDescriptor, Texture};
 use bevy_sprite::TextureAtlas;
 use bevy_type_registry::TypeUuid;
 use bevy_utils::HashMap;
 
-// work around rust's f32 order/hash limitations
-type FontSizeKey = FloatOrd;
+// work around rust's f32 order/hash limitations. Atlases are kept apart by raster size and by the distance field
+// spread of the glyph rendering, so bitmap and distance field glyphs never share an atlas
+type FontAtlasKey = (FloatOrd, Option<FloatOrd>);
+
+fn font_atlas_key(rendering: GlyphRendering, font_size: f32) -> FontAtlasKey {
+    let spread = match rendering {
+        GlyphRendering::Bitmap => None,
+        GlyphRendering::DistanceField { spread, .. } => Some(FloatOrd(spread)),
+    };
+    (FloatOrd(rendering.raster_size(font_size)), spread)
+}
 
 #[derive(Default, TypeUuid)]
 #[uuid = "73ba778b-b6b5-4f45-982d-d21b6b86ace2"]
 pub struct FontAtlasSet {
     font: Handle<Font>,
-    font_atlases: HashMap<FontSizeKey, Vec<FontAtlas>>,
+    font_atlases: HashMap<FontAtlasKey, Vec<FontAtlas>>,
 }
 
 #[derive(Debug)]
@@ -32,13 +41,14 @@ impl FontAtlasSet {
         }
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (&FontSizeKey, &Vec<FontAtlas>)> {
+    pub fn iter(&self) -> impl Iterator<Item = (&FontAtlasKey, &Vec<FontAtlas>)> {
         self.font_atlases.iter()
     }
 
-    pub fn has_char(&self, character: char, font_size: f32) -> bool {
+    /// Whether the character was added to the atlases of the given font size and glyph rendering
+    pub fn has_char(&self, character: char, font_size: f32, rendering: GlyphRendering) -> bool {
         self.font_atlases
-            .get(&FloatOrd(font_size))
+            .get(&font_atlas_key(rendering, font_size))
             .map_or(false, |font_atlas| {
                 font_atlas
                     .iter()
@@ -57,16 +67,27 @@ impl FontAtlasSet {
         let mut width = 0.0;
         let font = fonts.get(&self.font)?;
         let scaled_font = ab_glyph::Font::as_scaled(&font.font, font_size);
+        let raster_size = font.rendering.raster_size(font_size);
+        let key = font_atlas_key(font.rendering, font_size);
+        // the font's glyph rendering changed, so the atlases of the previous one won't be drawn again
+        self.font_atlases.retain(|(_, spread), _| *spread == key.1);
+        let raster_font = ab_glyph::Font::as_scaled(&font.font, raster_size);
+        let new_atlas = |textures: &mut Assets<Texture>,
+                         texture_atlases: &mut Assets<TextureAtlas>| {
+            let sampler = match font.rendering {
+                GlyphRendering::Bitmap => SamplerDescriptor::default(),
+                // distance fields are magnified, and must be interpolated to stay smooth
+                GlyphRendering::DistanceField { .. } => SamplerDescriptor {
+                    mag_filter: FilterMode::Linear,
+                    ..Default::default()
+                },
+            };
+            FontAtlas::with_sampler(textures, texture_atlases, Vec2::new(512.0, 512.0), sampler)
+        };
         let font_atlases = self
             .font_atlases
-            .entry(FloatOrd(font_size))
-            .or_insert_with(|| {
-                vec![FontAtlas::new(
-                    textures,
-                    texture_atlases,
-                    Vec2::new(512.0, 512.0),
-                )]
-            });
+            .entry(key)
+            .or_insert_with(|| vec![new_atlas(textures, texture_atlases)]);
 
         let mut last_glyph: Option<Glyph> = None;
         for character in text.chars() {
@@ -81,17 +102,14 @@ impl FontAtlasSet {
                 .iter()
                 .any(|atlas| atlas.get_char_index(character).is_some())
             {
-                if let Some(outlined_glyph) = scaled_font.outline_glyph(glyph.clone()) {
-                    let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
+                let raster_glyph = raster_font.scaled_glyph(character);
+                if let Some(outlined_glyph) = raster_font.outline_glyph(raster_glyph) {
+                    let glyph_texture = font.get_glyph_texture(outlined_glyph);
                     let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
                         atlas.add_char(textures, texture_atlases, character, &glyph_texture)
                     };
                     if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
-                        font_atlases.push(FontAtlas::new(
-                            textures,
-                            texture_atlases,
-                            Vec2::new(512.0, 512.0),
-                        ));
+                        font_atlases.push(new_atlas(textures, texture_atlases));
                         if !font_atlases.last_mut().unwrap().add_char(
                             textures,
                             texture_atlases,
@@ -110,9 +128,15 @@ impl FontAtlasSet {
         Some(width)
     }
 
-    pub fn get_glyph_atlas_info(&self, font_size: f32, character: char) -> Option<GlyphAtlasInfo> {
+    /// Where the character is in the atlases of the given font size and glyph rendering
+    pub fn get_glyph_atlas_info(
+        &self,
+        font_size: f32,
+        rendering: GlyphRendering,
+        character: char,
+    ) -> Option<GlyphAtlasInfo> {
         self.font_atlases
-            .get(&FloatOrd(font_size))
+            .get(&font_atlas_key(rendering, font_size))
             .and_then(|font_atlas| {
                 font_atlas
                     .iter()
@@ -128,3 +152,63 @@ impl FontAtlasSet {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FontAtlasSet;
+    use crate::{Font, GlyphRendering};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_render::texture::Texture;
+    use bevy_sprite::TextureAtlas;
+    use bevy_type_registry::TypeRegistry;
+
+    #[test]
+    fn atlases_are_kept_apart_by_glyph_rendering() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Font>()
+            .add_asset::<Texture>()
+            .add_asset::<TextureAtlas>();
+        let resources = app.resources();
+        let mut fonts = resources.get_mut::<Assets<Font>>().unwrap();
+        let mut textures = resources.get_mut::<Assets<Texture>>().unwrap();
+        let mut texture_atlases = resources.get_mut::<Assets<TextureAtlas>>().unwrap();
+        let font = fonts.add(
+            Font::try_from_bytes(
+                include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf").to_vec(),
+            )
+            .unwrap(),
+        );
+        // rasterized at the same size as the bitmap glyphs
+        let distance_field = GlyphRendering::DistanceField {
+            size: 20.0,
+            spread: 4.0,
+        };
+
+        let mut font_atlas_set = FontAtlasSet::new(font.clone_weak());
+        font_atlas_set
+            .add_glyphs_to_atlas(&fonts, &mut texture_atlases, &mut textures, 20.0, "a")
+            .unwrap();
+        assert!(font_atlas_set.has_char('a', 20.0, GlyphRendering::Bitmap));
+        assert!(!font_atlas_set.has_char('a', 20.0, distance_field));
+        assert!(font_atlas_set
+            .get_glyph_atlas_info(20.0, distance_field, 'a')
+            .is_none());
+
+        fonts.get_mut(&font).unwrap().rendering = distance_field;
+        font_atlas_set
+            .add_glyphs_to_atlas(&fonts, &mut texture_atlases, &mut textures, 12.0, "a")
+            .unwrap();
+        assert!(font_atlas_set.has_char('a', 12.0, distance_field));
+        assert!(font_atlas_set
+            .get_glyph_atlas_info(20.0, distance_field, 'a')
+            .is_some());
+        // the bitmap atlases were dropped with the old rendering
+        assert!(!font_atlas_set.has_char('a', 20.0, GlyphRendering::Bitmap));
+        assert_eq!(font_atlas_set.iter().count(), 1);
+    }
+}
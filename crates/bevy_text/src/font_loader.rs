@@ -1,10 +1,21 @@
-use crate::Font;
+use crate::{Font, GlyphRendering};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_utils::BoxedFuture;
 
-#[derive(Default)]
-pub struct FontLoader;
+/// Loads ttf and otf fonts. To load fonts with another [GlyphRendering], add a loader with that rendering after the
+/// [TextPlugin](crate::TextPlugin):
+///
+/// ```ignore
+/// app.add_asset_loader(FontLoader {
+///     rendering: GlyphRendering::distance_field(),
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct FontLoader {
+    /// The glyph rendering of loaded fonts
+    pub rendering: GlyphRendering,
+}
 
 impl AssetLoader for FontLoader {
     fn load<'a>(
@@ -13,14 +24,15 @@ impl AssetLoader for FontLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            let font = Font::try_from_bytes(bytes.into())?;
+            let mut font = Font::try_from_bytes(bytes.into())?;
+            font.rendering = self.rendering;
             load_context.set_default_asset(LoadedAsset::new(font));
             Ok(())
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        static EXTENSIONS: &[&str] = &["ttf"];
+        static EXTENSIONS: &[&str] = &["ttf", "otf"];
         EXTENSIONS
     }
 }
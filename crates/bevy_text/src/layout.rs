@@ -0,0 +1,400 @@
+use crate::{Font, TextStyle};
+use ab_glyph::{Glyph, ScaleFont};
+use bevy_asset::{Assets, Handle};
+use bevy_math::Vec2;
+use std::ops::Range;
+
+/// A run of text that shares a font and style. A text can be made of several sections.
+#[derive(Debug, Default, Clone)]
+pub struct TextSection {
+    pub value: String,
+    pub font: Handle<Font>,
+    pub style: TextStyle,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HorizontalAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl Default for HorizontalAlign {
+    fn default() -> Self {
+        HorizontalAlign::Left
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VerticalAlign {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl Default for VerticalAlign {
+    fn default() -> Self {
+        VerticalAlign::Top
+    }
+}
+
+/// Aligns each line horizontally, and the whole block of lines vertically, within the area the text is drawn in.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TextAlignment {
+    pub horizontal: HorizontalAlign,
+    pub vertical: VerticalAlign,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionedGlyph {
+    pub section_index: usize,
+    pub character: char,
+    /// The glyph's origin on the baseline. [TextLayout::new] positions glyphs relative to the top left of the text
+    /// with y pointing down, while [TextLayout::aligned_glyphs] positions them relative to the bottom left of the
+    /// container with y pointing up.
+    pub position: Vec2,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    pub glyphs: Range<usize>,
+    /// The width of the line, not counting trailing whitespace
+    pub width: f32,
+}
+
+/// The glyphs of a text broken into lines.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextLayout {
+    pub glyphs: Vec<PositionedGlyph>,
+    pub lines: Vec<TextLine>,
+    pub size: Vec2,
+}
+
+struct LineGlyph {
+    section_index: usize,
+    character: char,
+    x: f32,
+    advance: f32,
+    ascent: f32,
+    height: f32,
+}
+
+#[derive(Default)]
+struct LineBuilder {
+    layout: TextLayout,
+    glyphs: Vec<LineGlyph>,
+    top: f32,
+}
+
+impl LineBuilder {
+    fn width(&self) -> f32 {
+        self.glyphs
+            .last()
+            .map_or(0.0, |glyph| glyph.x + glyph.advance)
+    }
+
+    /// Finishes the current line. `metrics` is the (ascent, height) used for lines without glyphs.
+    fn finish_line(&mut self, metrics: (f32, f32)) {
+        while self
+            .glyphs
+            .last()
+            .map_or(false, |glyph| glyph.character.is_whitespace())
+        {
+            self.glyphs.pop();
+        }
+
+        let (mut ascent, mut height) = metrics;
+        if !self.glyphs.is_empty() {
+            ascent = self
+                .glyphs
+                .iter()
+                .map(|glyph| glyph.ascent)
+                .fold(0.0, f32::max);
+            height = self
+                .glyphs
+                .iter()
+                .map(|glyph| glyph.height)
+                .fold(0.0, f32::max);
+        }
+        let width = self.width();
+        let baseline = self.top + ascent;
+        let start = self.layout.glyphs.len();
+        for glyph in self.glyphs.drain(..) {
+            self.layout.glyphs.push(PositionedGlyph {
+                section_index: glyph.section_index,
+                character: glyph.character,
+                position: Vec2::new(glyph.x, baseline),
+            });
+        }
+        self.layout.lines.push(TextLine {
+            glyphs: start..self.layout.glyphs.len(),
+            width,
+        });
+        self.top += height;
+        self.layout.size = Vec2::new(self.layout.size.x().max(width), self.top);
+    }
+
+    /// Moves the word at the end of the current line to a new line. Returns false if the line has no break
+    /// opportunity.
+    fn wrap_word(&mut self, metrics: (f32, f32)) -> bool {
+        let word_start = match self
+            .glyphs
+            .iter()
+            .rposition(|glyph| glyph.character.is_whitespace())
+        {
+            Some(index) => index + 1,
+            None => return false,
+        };
+        let mut word = self.glyphs.split_off(word_start);
+        self.finish_line(metrics);
+        if let Some(offset) = word.first().map(|glyph| glyph.x) {
+            for glyph in word.iter_mut() {
+                glyph.x -= offset;
+            }
+        }
+        self.glyphs = word;
+        true
+    }
+}
+
+impl TextLayout {
+    /// Lays out the sections, breaking lines at newlines and, if `max_width` is set, wrapping words that don't fit.
+    /// Words wider than `max_width` are broken between characters. Returns None if a font isn't loaded yet.
+    pub fn new(
+        fonts: &Assets<Font>,
+        sections: &[TextSection],
+        max_width: Option<f32>,
+    ) -> Option<TextLayout> {
+        let mut builder = LineBuilder::default();
+        let mut metrics = (0.0, 0.0);
+        for (section_index, section) in sections.iter().enumerate() {
+            let font = fonts.get(&section.font)?;
+            let scaled_font = ab_glyph::Font::as_scaled(&font.font, section.style.font_size);
+            metrics = (scaled_font.ascent(), scaled_font.height());
+            let mut last_glyph: Option<Glyph> = None;
+            for character in section.value.chars() {
+                if character.is_control() {
+                    if character == '\n' {
+                        builder.finish_line(metrics);
+                        last_glyph = None;
+                    }
+                    continue;
+                }
+
+                let glyph = scaled_font.scaled_glyph(character);
+                let advance = scaled_font.h_advance(glyph.id);
+                let mut x = builder.width();
+                if let Some(last_glyph) = last_glyph.take() {
+                    x += scaled_font.kern(last_glyph.id, glyph.id);
+                }
+                if let Some(max_width) = max_width {
+                    if !character.is_whitespace()
+                        && x + advance > max_width
+                        && !builder.glyphs.is_empty()
+                    {
+                        if !builder.wrap_word(metrics) {
+                            builder.finish_line(metrics);
+                        }
+                        x = builder.width();
+                    }
+                }
+
+                builder.glyphs.push(LineGlyph {
+                    section_index,
+                    character,
+                    x,
+                    advance,
+                    ascent: metrics.0,
+                    height: metrics.1,
+                });
+                last_glyph = Some(glyph);
+            }
+        }
+
+        if !sections.is_empty() {
+            builder.finish_line(metrics);
+        }
+        Some(builder.layout)
+    }
+
+    /// Returns the glyphs aligned within a container of the given size, relative to its bottom left corner with y
+    /// pointing up.
+    pub fn aligned_glyphs(
+        &self,
+        container_size: Vec2,
+        alignment: TextAlignment,
+    ) -> Vec<PositionedGlyph> {
+        let y_offset = match alignment.vertical {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Center => (container_size.y() - self.size.y()) / 2.0,
+            VerticalAlign::Bottom => container_size.y() - self.size.y(),
+        };
+
+        let mut glyphs = Vec::with_capacity(self.glyphs.len());
+        for line in self.lines.iter() {
+            let x_offset = match alignment.horizontal {
+                HorizontalAlign::Left => 0.0,
+                HorizontalAlign::Center => (container_size.x() - line.width) / 2.0,
+                HorizontalAlign::Right => container_size.x() - line.width,
+            };
+            for glyph in self.glyphs[line.glyphs.clone()].iter() {
+                glyphs.push(PositionedGlyph {
+                    position: Vec2::new(
+                        glyph.position.x() + x_offset,
+                        container_size.y() - glyph.position.y() - y_offset,
+                    ),
+                    ..glyph.clone()
+                });
+            }
+        }
+        glyphs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextStyle;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_type_registry::TypeRegistry;
+
+    fn with_font(test: impl FnOnce(&Assets<Font>, Handle<Font>)) {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Font>();
+        let mut fonts = app.resources().get_mut::<Assets<Font>>().unwrap();
+        let font = fonts.add(
+            Font::try_from_bytes(
+                include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf").to_vec(),
+            )
+            .unwrap(),
+        );
+        test(&fonts, font);
+    }
+
+    fn section(value: &str, font: &Handle<Font>) -> TextSection {
+        TextSection {
+            value: value.to_string(),
+            font: font.clone(),
+            style: TextStyle {
+                font_size: 20.0,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn line_text(layout: &TextLayout, line: &TextLine) -> String {
+        layout.glyphs[line.glyphs.clone()]
+            .iter()
+            .map(|glyph| glyph.character)
+            .collect()
+    }
+
+    fn assert_near(position: Vec2, expected: Vec2) {
+        assert!(
+            (position - expected).length() < 1e-3,
+            "{:?} != {:?}",
+            position,
+            expected
+        );
+    }
+
+    #[test]
+    fn breaks_lines_at_newlines() {
+        with_font(|fonts, font| {
+            let one_line = TextLayout::new(fonts, &[section("ab", &font)], None).unwrap();
+            let layout = TextLayout::new(fonts, &[section("ab\ncd", &font)], None).unwrap();
+            assert_eq!(layout.lines.len(), 2);
+            assert_eq!(line_text(&layout, &layout.lines[0]), "ab");
+            assert_eq!(line_text(&layout, &layout.lines[1]), "cd");
+            assert_eq!(layout.glyphs[2].position.x(), 0.0);
+            assert!(layout.glyphs[2].position.y() > layout.glyphs[0].position.y());
+            assert_eq!(layout.size.y(), one_line.size.y() * 2.0);
+        });
+    }
+
+    #[test]
+    fn wraps_words_that_do_not_fit() {
+        with_font(|fonts, font| {
+            let first_word = TextLayout::new(fonts, &[section("aaa", &font)], None).unwrap();
+            let both_words = TextLayout::new(fonts, &[section("aaa bbb", &font)], None).unwrap();
+            let max_width = (first_word.size.x() + both_words.size.x()) / 2.0;
+            let layout =
+                TextLayout::new(fonts, &[section("aaa bbb", &font)], Some(max_width)).unwrap();
+            assert_eq!(layout.lines.len(), 2);
+            assert_eq!(line_text(&layout, &layout.lines[0]), "aaa");
+            assert_eq!(line_text(&layout, &layout.lines[1]), "bbb");
+            // trailing whitespace doesn't count towards the width
+            assert_eq!(layout.lines[0].width, first_word.size.x());
+            assert_eq!(layout.glyphs[3].position.x(), 0.0);
+
+            // words wider than the text are broken between characters
+            let layout =
+                TextLayout::new(fonts, &[section("aaaaaa", &font)], Some(max_width / 2.0)).unwrap();
+            assert!(layout.lines.len() > 1);
+            assert_eq!(layout.glyphs.len(), 6);
+            assert!(layout
+                .lines
+                .iter()
+                .all(|line| line.width <= max_width / 2.0));
+        });
+    }
+
+    #[test]
+    fn sections_share_lines() {
+        with_font(|fonts, font| {
+            let mut large = section("b", &font);
+            large.style.font_size = 40.0;
+            let layout = TextLayout::new(fonts, &[section("a", &font), large], None).unwrap();
+            assert_eq!(layout.lines.len(), 1);
+            assert_eq!(layout.glyphs[0].section_index, 0);
+            assert_eq!(layout.glyphs[1].section_index, 1);
+            // glyphs of a line share the baseline of its largest font
+            assert_eq!(layout.glyphs[0].position.y(), layout.glyphs[1].position.y());
+            assert!(layout.glyphs[1].position.x() > 0.0);
+        });
+    }
+
+    #[test]
+    fn aligns_lines_within_the_container() {
+        with_font(|fonts, font| {
+            let layout = TextLayout::new(fonts, &[section("ab", &font)], None).unwrap();
+            let container_size = Vec2::new(200.0, 100.0);
+            let baseline = layout.glyphs[0].position.y();
+
+            let top_left = layout.aligned_glyphs(container_size, TextAlignment::default());
+            assert_near(top_left[0].position, Vec2::new(0.0, 100.0 - baseline));
+
+            let bottom_right = layout.aligned_glyphs(
+                container_size,
+                TextAlignment {
+                    horizontal: HorizontalAlign::Right,
+                    vertical: VerticalAlign::Bottom,
+                },
+            );
+            assert_near(
+                bottom_right[0].position,
+                Vec2::new(200.0 - layout.lines[0].width, layout.size.y() - baseline),
+            );
+
+            let centered = layout.aligned_glyphs(
+                container_size,
+                TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    vertical: VerticalAlign::Center,
+                },
+            );
+            assert_near(
+                centered[0].position,
+                Vec2::new(
+                    (200.0 - layout.lines[0].width) / 2.0,
+                    100.0 - baseline - (100.0 - layout.size.y()) / 2.0,
+                ),
+            );
+        });
+    }
+}
@@ -3,19 +3,26 @@ mod font;
 mod font_atlas;
 mod font_atlas_set;
 mod font_loader;
+mod layout;
+mod render;
 
 pub use draw::*;
 pub use font::*;
 pub use font_atlas::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
+pub use layout::*;
+pub use render::*;
 
 pub mod prelude {
-    pub use crate::{Font, TextStyle};
+    pub use crate::{
+        Font, GlyphRendering, HorizontalAlign, TextAlignment, TextSection, TextStyle, VerticalAlign,
+    };
 }
 
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{AddAsset, Assets};
+use bevy_render::{pipeline::PipelineDescriptor, shader::Shader};
 
 #[derive(Default)]
 pub struct TextPlugin;
@@ -25,5 +32,13 @@ impl Plugin for TextPlugin {
         app.add_asset::<Font>()
             .add_asset::<FontAtlasSet>()
             .init_asset_loader::<FontLoader>();
+
+        let resources = app.resources();
+        let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+        let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+        pipelines.set_untracked(
+            DISTANCE_FIELD_TEXT_PIPELINE_HANDLE,
+            build_distance_field_text_pipeline(&mut shaders),
+        );
     }
 }
@@ -0,0 +1,19 @@
+#version 450
+
+layout(location = 0) in vec2 v_Uv;
+layout(location = 1) in vec4 v_Color;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 1, binding = 2) uniform texture2D TextureAtlas_texture;
+layout(set = 1, binding = 3) uniform sampler TextureAtlas_texture_sampler;
+
+void main() {
+    float distance = texture(
+        sampler2D(TextureAtlas_texture, TextureAtlas_texture_sampler),
+        v_Uv).a;
+    // antialias over about one screen pixel, whatever size the glyph is drawn at
+    float width = max(fwidth(distance) * 0.7, 0.0001);
+    float alpha = smoothstep(0.5 - width, 0.5 + width, distance);
+    o_Target = vec4(v_Color.rgb, v_Color.a * alpha);
+}
@@ -0,0 +1,20 @@
+use bevy_asset::{Assets, Handle};
+use bevy_render::{
+    pipeline::PipelineDescriptor,
+    shader::{Shader, ShaderStage},
+};
+use bevy_type_registry::TypeUuid;
+
+/// Draws glyphs of fonts with [GlyphRendering::DistanceField](crate::GlyphRendering::DistanceField)
+pub const DISTANCE_FIELD_TEXT_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 3385916426436291907);
+
+/// The sprite sheet pipeline, with a fragment shader that turns distance fields into sharp edges
+pub fn build_distance_field_text_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    let mut descriptor = bevy_sprite::build_sprite_sheet_pipeline(shaders);
+    descriptor.shader_stages.fragment = Some(shaders.add(Shader::from_glsl(
+        ShaderStage::Fragment,
+        include_str!("distance_field_text.frag"),
+    )));
+    descriptor
+}
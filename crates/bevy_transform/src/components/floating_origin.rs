@@ -0,0 +1,67 @@
+use bevy_math::Vec3;
+
+/// Marks the entity, usually the camera, that the world is kept centered on. There should be at most one, and it
+/// should be a root entity. See [WorldOrigin].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FloatingOrigin;
+
+/// Keeps a root entity in place when the world is moved back to the origin, because it is placed on the screen rather
+/// than in the world. The UI camera and the UI node bundles have it.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct IgnoreFloatingOrigin;
+
+/// Keeps translations precise in large worlds. f32 translations get less precise the further they are from the
+/// origin, which makes distant objects jitter. Whenever the [FloatingOrigin] entity moves further than `threshold`
+/// from the origin on any axis, every root entity is moved back by its translation, so the part of the world around
+/// it stays near the origin and the renderer only sees small translations. Root entities with
+/// [IgnoreFloatingOrigin] stay where they are.
+///
+/// The moves add up to `offset`, which is kept in f64. Use [WorldOrigin::to_world] and [WorldOrigin::to_local] for
+/// positions that have to stay put across moves, like spawn points or save data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldOrigin {
+    /// How far the [FloatingOrigin] entity may move from the origin before the world is moved back
+    pub threshold: f32,
+    offset: [f64; 3],
+}
+
+impl Default for WorldOrigin {
+    fn default() -> Self {
+        WorldOrigin {
+            threshold: 1000.0,
+            offset: [0.0; 3],
+        }
+    }
+}
+
+impl WorldOrigin {
+    /// The world position of the origin of translations
+    pub fn offset(&self) -> [f64; 3] {
+        self.offset
+    }
+
+    /// The world position of a root entity's translation
+    pub fn to_world(&self, translation: Vec3) -> [f64; 3] {
+        [
+            self.offset[0] + translation.x() as f64,
+            self.offset[1] + translation.y() as f64,
+            self.offset[2] + translation.z() as f64,
+        ]
+    }
+
+    /// The root entity translation of a world position. It is only precise for positions near the origin.
+    pub fn to_local(&self, position: [f64; 3]) -> Vec3 {
+        Vec3::new(
+            (position[0] - self.offset[0]) as f32,
+            (position[1] - self.offset[1]) as f32,
+            (position[2] - self.offset[2]) as f32,
+        )
+    }
+
+    /// Moves the origin of translations to the given translation. Root entity translations must be moved by
+    /// `-translation` to stay in place, which [floating_origin_system](crate::floating_origin_system::floating_origin_system)
+    /// does.
+    pub(crate) fn shift(&mut self, translation: Vec3) {
+        self.offset = self.to_world(translation);
+    }
+}
@@ -68,17 +68,65 @@ impl GlobalTransform {
         Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
     }
 
+    /// Computes the matrix that transforms normals: the inverse transpose of the rotation and scale, which keeps
+    /// normals perpendicular to surfaces under non-uniform scale
     #[inline]
-    pub fn forward(&self) -> Vec3 {
+    pub fn compute_normal_matrix(&self) -> Mat3 {
+        Mat3::from_quat(self.rotation) * Mat3::from_scale(Vec3::one() / self.scale)
+    }
+
+    /// Get the unit vector in the local x direction
+    #[inline]
+    pub fn local_x(&self) -> Vec3 {
+        self.rotation * Vec3::unit_x()
+    }
+
+    /// Get the unit vector in the local y direction
+    #[inline]
+    pub fn local_y(&self) -> Vec3 {
+        self.rotation * Vec3::unit_y()
+    }
+
+    /// Get the unit vector in the local z direction
+    #[inline]
+    pub fn local_z(&self) -> Vec3 {
         self.rotation * Vec3::unit_z()
     }
 
+    /// Get the unit vector this transform is facing. Like cameras, transforms face their local -z direction,
+    /// which is the direction [look_at](Self::look_at) points them in.
+    #[inline]
+    pub fn forward(&self) -> Vec3 {
+        -self.local_z()
+    }
+
+    /// Get the unit vector pointing to the right of this transform (local x)
+    #[inline]
+    pub fn right(&self) -> Vec3 {
+        self.local_x()
+    }
+
+    /// Get the unit vector pointing up from this transform (local y)
+    #[inline]
+    pub fn up(&self) -> Vec3 {
+        self.local_y()
+    }
+
     #[inline]
     /// Rotate the transform by the given rotation
     pub fn rotate(&mut self, rotation: Quat) {
         self.rotation *= rotation;
     }
 
+    /// Rotate the transform around the given point, changing both its translation and its rotation
+    #[inline]
+    pub fn rotate_around(&mut self, point: Vec3, rotation: Quat) {
+        self.translation = point + rotation * (self.translation - point);
+        self.rotation = rotation * self.rotation;
+    }
+
+    /// Composes this transform with a child transform. Scale is combined per axis, which is exact unless the child
+    /// is rotated relative to a non-uniformly scaled parent (that would require shear)
     #[inline]
     pub fn mul_transform(&self, transform: Transform) -> GlobalTransform {
         let translation = self.mul_vec3(transform.translation);
@@ -93,8 +141,8 @@ impl GlobalTransform {
 
     #[inline]
     pub fn mul_vec3(&self, mut value: Vec3) -> Vec3 {
-        value = self.rotation * value;
         value = self.scale * value;
+        value = self.rotation * value;
         value += self.translation;
         value
     }
@@ -155,3 +203,29 @@ impl Mul<Vec3> for GlobalTransform {
         self.mul_vec3(value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normal_matrix_is_inverse_transpose() {
+        let transform = GlobalTransform {
+            translation: Vec3::new(1.0, 2.0, 3.0),
+            rotation: Quat::from_rotation_y(0.5),
+            scale: Vec3::new(2.0, 0.5, 3.0),
+        };
+        let matrix = transform.compute_matrix();
+        let expected = matrix.inverse().transpose();
+        let normal_matrix = transform.compute_normal_matrix();
+        for (actual, expected) in [
+            (normal_matrix.x_axis(), expected.x_axis()),
+            (normal_matrix.y_axis(), expected.y_axis()),
+            (normal_matrix.z_axis(), expected.z_axis()),
+        ]
+        .iter()
+        {
+            assert!((*actual - expected.truncate()).length() < 1e-5);
+        }
+    }
+}
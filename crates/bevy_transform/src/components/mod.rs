@@ -1,9 +1,13 @@
 mod children;
+mod floating_origin;
 mod global_transform;
 mod parent;
 mod transform;
+mod transform_interpolation;
 
 pub use children::Children;
+pub use floating_origin::*;
 pub use global_transform::*;
 pub use parent::{Parent, PreviousParent};
 pub use transform::*;
+pub use transform_interpolation::*;
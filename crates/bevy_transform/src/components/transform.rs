@@ -68,17 +68,58 @@ impl Transform {
         Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
     }
 
+    /// Get the unit vector in the local x direction
     #[inline]
-    pub fn forward(&self) -> Vec3 {
+    pub fn local_x(&self) -> Vec3 {
+        self.rotation * Vec3::unit_x()
+    }
+
+    /// Get the unit vector in the local y direction
+    #[inline]
+    pub fn local_y(&self) -> Vec3 {
+        self.rotation * Vec3::unit_y()
+    }
+
+    /// Get the unit vector in the local z direction
+    #[inline]
+    pub fn local_z(&self) -> Vec3 {
         self.rotation * Vec3::unit_z()
     }
 
+    /// Get the unit vector this transform is facing. Like cameras, transforms face their local -z direction,
+    /// which is the direction [look_at](Self::look_at) points them in.
+    #[inline]
+    pub fn forward(&self) -> Vec3 {
+        -self.local_z()
+    }
+
+    /// Get the unit vector pointing to the right of this transform (local x)
+    #[inline]
+    pub fn right(&self) -> Vec3 {
+        self.local_x()
+    }
+
+    /// Get the unit vector pointing up from this transform (local y)
+    #[inline]
+    pub fn up(&self) -> Vec3 {
+        self.local_y()
+    }
+
     #[inline]
     /// Rotate the transform by the given rotation
     pub fn rotate(&mut self, rotation: Quat) {
         self.rotation *= rotation;
     }
 
+    /// Rotate the transform around the given point, changing both its translation and its rotation
+    #[inline]
+    pub fn rotate_around(&mut self, point: Vec3, rotation: Quat) {
+        self.translation = point + rotation * (self.translation - point);
+        self.rotation = rotation * self.rotation;
+    }
+
+    /// Composes this transform with a child transform. Scale is combined per axis, which is exact unless the child
+    /// is rotated relative to a non-uniformly scaled parent (that would require shear)
     #[inline]
     pub fn mul_transform(&self, transform: Transform) -> Self {
         let translation = self.mul_vec3(transform.translation);
@@ -93,8 +134,8 @@ impl Transform {
 
     #[inline]
     pub fn mul_vec3(&self, mut value: Vec3) -> Vec3 {
-        value = self.rotation * value;
         value = self.scale * value;
+        value = self.rotation * value;
         value += self.translation;
         value
     }
@@ -144,3 +185,56 @@ impl Mul<Vec3> for Transform {
         self.mul_vec3(value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_approx_eq(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn direction_accessors() {
+        let transform = Transform::from_translation(Vec3::new(0.0, 0.0, 5.0))
+            .looking_at(Vec3::zero(), Vec3::unit_y());
+        assert_approx_eq(transform.forward(), -Vec3::unit_z());
+        assert_approx_eq(transform.right(), Vec3::unit_x());
+        assert_approx_eq(transform.up(), Vec3::unit_y());
+
+        let transform = Transform::from_translation(Vec3::new(1.0, 2.0, 3.0))
+            .looking_at(Vec3::new(4.0, -1.0, 0.0), Vec3::unit_y());
+        assert_approx_eq(
+            transform.forward(),
+            (Vec3::new(4.0, -1.0, 0.0) - transform.translation).normalize(),
+        );
+    }
+
+    #[test]
+    fn non_uniform_scale() {
+        let transform = Transform {
+            translation: Vec3::new(1.0, 2.0, 3.0),
+            rotation: Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::new(2.0, 1.0, 1.0),
+        };
+        let point = Vec3::new(1.0, 1.0, 0.0);
+        assert_approx_eq(
+            transform.mul_vec3(point),
+            transform.compute_matrix().transform_point3(point),
+        );
+
+        let child = transform.mul_transform(Transform::from_translation(point));
+        assert_approx_eq(child.translation, Vec3::new(0.0, 4.0, 3.0));
+    }
+
+    #[test]
+    fn rotate_around() {
+        let mut transform = Transform::from_translation(Vec3::new(2.0, 0.0, 0.0));
+        transform.rotate_around(
+            Vec3::new(1.0, 0.0, 0.0),
+            Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+        );
+        assert_approx_eq(transform.translation, Vec3::new(1.0, 0.0, -1.0));
+        assert_approx_eq(transform.right(), Vec3::new(0.0, 0.0, -1.0));
+    }
+}
@@ -0,0 +1,39 @@
+use super::Transform;
+use bevy_property::Properties;
+
+/// Smooths the rendered [GlobalTransform](super::GlobalTransform) of an entity that is moved by a
+/// [FixedTimestep](bevy_core::FixedTimestep) schedule, by interpolating between its transforms at the last two steps.
+///
+/// [TransformPlugin](crate::TransformPlugin) records the previous transform before every step. Without a running
+/// fixed timestep the current [Transform] is propagated.
+#[derive(Debug, PartialEq, Clone, Copy, Properties)]
+pub struct TransformInterpolation {
+    /// The transform before the last fixed step
+    pub previous: Transform,
+    /// The transform used for propagation this frame
+    pub interpolated: Transform,
+}
+
+impl TransformInterpolation {
+    pub fn new(transform: Transform) -> Self {
+        TransformInterpolation {
+            previous: transform,
+            interpolated: transform,
+        }
+    }
+
+    /// Returns the transform `alpha` of the way from the previous transform to `current`
+    pub fn interpolate(&self, current: &Transform, alpha: f32) -> Transform {
+        Transform {
+            translation: self.previous.translation.lerp(current.translation, alpha),
+            rotation: self.previous.rotation.lerp(current.rotation, alpha),
+            scale: self.previous.scale.lerp(current.scale, alpha),
+        }
+    }
+}
+
+impl Default for TransformInterpolation {
+    fn default() -> Self {
+        Self::new(Transform::identity())
+    }
+}
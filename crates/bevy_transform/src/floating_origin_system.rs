@@ -0,0 +1,109 @@
+use crate::components::*;
+use bevy_ecs::prelude::*;
+
+/// Moves every root entity without [IgnoreFloatingOrigin] back towards the origin when the [FloatingOrigin] entity
+/// moves too far from it, see [WorldOrigin]
+pub fn floating_origin_system(
+    mut world_origin: ResMut<WorldOrigin>,
+    mut queries: QuerySet<(
+        Query<With<FloatingOrigin, &Transform>>,
+        Query<
+            Without<
+                Parent,
+                Without<
+                    IgnoreFloatingOrigin,
+                    (&mut Transform, Option<&mut TransformInterpolation>),
+                >,
+            >,
+        >,
+    )>,
+) {
+    let shift = match queries.q0().iter().next() {
+        Some(transform) => transform.translation,
+        None => return,
+    };
+    let threshold = world_origin.threshold;
+    if shift.x().abs() <= threshold && shift.y().abs() <= threshold && shift.z().abs() <= threshold
+    {
+        return;
+    }
+
+    world_origin.shift(shift);
+    for (mut transform, interpolation) in queries.q1_mut().iter_mut() {
+        transform.translation -= shift;
+        if let Some(mut interpolation) = interpolation {
+            interpolation.previous.translation -= shift;
+            interpolation.interpolated.translation -= shift;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_ecs::{Resources, Schedule, World};
+    use bevy_math::Vec3;
+
+    #[test]
+    fn world_moves_back_to_origin() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut world_origin = WorldOrigin::default();
+        world_origin.threshold = 100.0;
+        resources.insert(world_origin);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", floating_origin_system.system());
+
+        let camera = world.spawn((
+            Transform::from_translation(Vec3::new(50.0, 0.0, 0.0)),
+            FloatingOrigin,
+        ));
+        let other = world.spawn((Transform::from_translation(Vec3::new(200.0, 10.0, 0.0)),));
+        let child = world.spawn((
+            Transform::from_translation(Vec3::new(1.0, 0.0, 0.0)),
+            Parent(other),
+        ));
+        let screen = world.spawn((
+            Transform::from_translation(Vec3::new(0.0, 0.0, 999.9)),
+            IgnoreFloatingOrigin,
+        ));
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            world.get::<Transform>(camera).unwrap().translation,
+            Vec3::new(50.0, 0.0, 0.0)
+        );
+
+        world.get_mut::<Transform>(camera).unwrap().translation = Vec3::new(150.0, 0.0, 0.0);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            world.get::<Transform>(camera).unwrap().translation,
+            Vec3::zero()
+        );
+        assert_eq!(
+            world.get::<Transform>(other).unwrap().translation,
+            Vec3::new(50.0, 10.0, 0.0)
+        );
+        // children move with their parents
+        assert_eq!(
+            world.get::<Transform>(child).unwrap().translation,
+            Vec3::new(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            world.get::<Transform>(screen).unwrap().translation,
+            Vec3::new(0.0, 0.0, 999.9)
+        );
+
+        let world_origin = resources.get::<WorldOrigin>().unwrap();
+        assert_eq!(world_origin.offset(), [150.0, 0.0, 0.0]);
+        assert_eq!(
+            world_origin.to_world(Vec3::new(50.0, 10.0, 0.0)),
+            [200.0, 10.0, 0.0]
+        );
+        assert_eq!(
+            world_origin.to_local([200.0, 10.0, 0.0]),
+            Vec3::new(50.0, 10.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::components::{Children, Parent};
+use bevy_ecs::{Entity, Query};
+use smallvec::SmallVec;
+
+/// Iterates over the descendants of an entity, depth first. Created by [DescendantsQueryExt::iter_descendants].
+pub struct DescendantIter<'q, 'w, 'c> {
+    children_query: &'q Query<'w, &'c Children>,
+    stack: SmallVec<[Entity; 8]>,
+}
+
+impl<'q, 'w, 'c> Iterator for DescendantIter<'q, 'w, 'c> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.stack.pop()?;
+        if let Ok(children) = self.children_query.get(entity) {
+            // push in reverse so children are visited in order
+            self.stack.extend(children.iter().rev().cloned());
+        }
+        Some(entity)
+    }
+}
+
+/// Iterates over the ancestors of an entity, starting with its parent. Created by
+/// [AncestorsQueryExt::iter_ancestors].
+pub struct AncestorIter<'q, 'w, 'p> {
+    parent_query: &'q Query<'w, &'p Parent>,
+    current: Entity,
+}
+
+impl<'q, 'w, 'p> Iterator for AncestorIter<'q, 'w, 'p> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parent = self.parent_query.get(self.current).ok()?.0;
+        self.current = parent;
+        Some(parent)
+    }
+}
+
+pub trait DescendantsQueryExt<'w, 'c> {
+    /// Iterates over the children of the given entity, their children, and so on. The entity itself is not included.
+    fn iter_descendants(&self, entity: Entity) -> DescendantIter<'_, 'w, 'c>;
+}
+
+impl<'w, 'c> DescendantsQueryExt<'w, 'c> for Query<'w, &'c Children> {
+    fn iter_descendants(&self, entity: Entity) -> DescendantIter<'_, 'w, 'c> {
+        let mut stack = SmallVec::new();
+        if let Ok(children) = self.get(entity) {
+            stack.extend(children.iter().rev().cloned());
+        }
+        DescendantIter {
+            children_query: self,
+            stack,
+        }
+    }
+}
+
+pub trait AncestorsQueryExt<'w, 'p> {
+    /// Iterates over the parent of the given entity, its parent, and so on up to the root
+    fn iter_ancestors(&self, entity: Entity) -> AncestorIter<'_, 'w, 'p>;
+}
+
+impl<'w, 'p> AncestorsQueryExt<'w, 'p> for Query<'w, &'p Parent> {
+    fn iter_ancestors(&self, entity: Entity) -> AncestorIter<'_, 'w, 'p> {
+        AncestorIter {
+            parent_query: self,
+            current: entity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hierarchy::BuildChildren;
+    use bevy_ecs::{Commands, IntoQuerySystem, Res, ResMut, Resources, Schedule, World};
+
+    struct Root(Entity);
+    struct Leaf(Entity);
+    #[derive(Default)]
+    struct Visited {
+        descendants: Vec<Entity>,
+        ancestors: Vec<Entity>,
+    }
+
+    fn walk_hierarchy(
+        root: Res<Root>,
+        leaf: Res<Leaf>,
+        mut visited: ResMut<Visited>,
+        children_query: Query<&Children>,
+        parent_query: Query<&Parent>,
+    ) {
+        visited.descendants = children_query.iter_descendants(root.0).collect();
+        visited.ancestors = parent_query.iter_ancestors(leaf.0).collect();
+    }
+
+    #[test]
+    fn iter_descendants_and_ancestors() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut commands = Commands::default();
+        commands.set_entity_reserver(world.get_entity_reserver());
+
+        let mut entities = Vec::new();
+        commands
+            .spawn((0u32,))
+            .for_current_entity(|entity| entities.push(entity))
+            .with_children(|parent| {
+                parent
+                    .spawn((1u32,))
+                    .for_current_entity(|entity| entities.push(entity))
+                    .with_children(|parent| {
+                        parent
+                            .spawn((2u32,))
+                            .for_current_entity(|entity| entities.push(entity));
+                    })
+                    .spawn((3u32,))
+                    .for_current_entity(|entity| entities.push(entity));
+            });
+        commands.apply(&mut world, &mut resources);
+
+        resources.insert(Root(entities[0]));
+        resources.insert(Leaf(entities[2]));
+        resources.insert(Visited::default());
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", walk_hierarchy.system());
+        schedule.run(&mut world, &mut resources);
+
+        let visited = resources.get::<Visited>().unwrap();
+        assert_eq!(
+            visited.descendants,
+            vec![entities[1], entities[2], entities[3]]
+        );
+        assert_eq!(visited.ancestors, vec![entities[1], entities[0]]);
+    }
+}
@@ -2,9 +2,11 @@ mod child_builder;
 #[allow(clippy::module_inception)]
 mod hierarchy;
 mod hierarchy_maintenance_system;
+mod hierarchy_query;
 mod world_child_builder;
 
 pub use child_builder::*;
 pub use hierarchy::*;
 pub use hierarchy_maintenance_system::*;
+pub use hierarchy_query::*;
 pub use world_child_builder::*;
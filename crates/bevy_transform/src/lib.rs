@@ -1,5 +1,7 @@
 pub mod components;
+pub mod floating_origin_system;
 pub mod hierarchy;
+pub mod transform_interpolation_system;
 pub mod transform_propagate_system;
 
 pub mod prelude {
@@ -7,13 +9,15 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
+use bevy_core::FixedStepSystems;
 use bevy_ecs::prelude::*;
 use bevy_type_registry::RegisterType;
-use prelude::{Children, GlobalTransform, Parent, Transform};
+use prelude::{Children, GlobalTransform, Parent, Transform, TransformInterpolation, WorldOrigin};
 
 pub(crate) fn transform_systems() -> Vec<Box<dyn System>> {
     let mut systems = Vec::with_capacity(5);
 
+    systems.push(transform_propagate_system::missing_global_transform_system.system());
     systems.append(&mut hierarchy::hierarchy_maintenance_systems());
     systems.push(transform_propagate_system::transform_propagate_system.system());
 
@@ -25,12 +29,30 @@ pub struct TransformPlugin;
 
 impl Plugin for TransformPlugin {
     fn build(&self, app: &mut AppBuilder) {
+        if app.resources().get::<WorldOrigin>().is_none() {
+            app.init_resource::<WorldOrigin>();
+        }
+        app.init_resource::<FixedStepSystems>()
+            .resources()
+            .get_mut::<FixedStepSystems>()
+            .unwrap()
+            .add(|| transform_interpolation_system::store_previous_transforms_system.system());
         app.register_component_with::<Children>(|reg| reg.map_entities())
             .register_component_with::<Parent>(|reg| reg.map_entities())
             .register_component::<Transform>()
             .register_component::<GlobalTransform>()
+            .register_component::<TransformInterpolation>()
             // add transform systems to startup so the first update is "correct"
             .add_startup_systems(transform_systems())
+            // move the world back before anything reads translations for this frame
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                floating_origin_system::floating_origin_system.system(),
+            )
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                transform_interpolation_system::transform_interpolation_system.system(),
+            )
             .add_systems_to_stage(stage::POST_UPDATE, transform_systems());
     }
 }
@@ -0,0 +1,152 @@
+use crate::components::*;
+use bevy_core::FixedTimestepState;
+use bevy_ecs::prelude::*;
+
+/// Records the current transform of interpolated entities. [TransformPlugin](crate::TransformPlugin) runs this at the
+/// start of every fixed step, see [FixedStepSystems](bevy_core::FixedStepSystems).
+pub fn store_previous_transforms_system(
+    mut query: Query<(&Transform, &mut TransformInterpolation)>,
+) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.previous = *transform;
+    }
+}
+
+/// Computes the transform that is propagated for interpolated entities this frame. Without a running
+/// [FixedTimestep](bevy_core::FixedTimestep) this is the current transform.
+pub fn transform_interpolation_system(
+    fixed_timestep: Res<FixedTimestepState>,
+    mut query: Query<(&Transform, &mut TransformInterpolation)>,
+) {
+    if !fixed_timestep.running {
+        for (transform, mut interpolation) in query.iter_mut() {
+            *interpolation = TransformInterpolation::new(*transform);
+        }
+        return;
+    }
+
+    let alpha = fixed_timestep.overstep_percentage().min(1.0) as f32;
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.interpolated = interpolation.interpolate(transform, alpha);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transform_propagate_system::transform_propagate_system;
+    use crate::TransformPlugin;
+    use bevy_app::App;
+    use bevy_core::{FixedTimestep, Time};
+    use bevy_ecs::{IntoThreadLocalSystem, Resources, Schedule, World};
+    use bevy_math::Vec3;
+    use bevy_type_registry::TypeRegistry;
+
+    #[test]
+    fn did_interpolate() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(FixedTimestepState {
+            step: 0.1,
+            accumulator: 0.025,
+            running: true,
+        });
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", transform_interpolation_system.system());
+        schedule.add_system_to_stage("update", transform_propagate_system.system());
+
+        let entity = world.spawn((
+            Transform::from_translation(Vec3::new(4.0, 0.0, 0.0)),
+            TransformInterpolation::new(Transform::identity()),
+            GlobalTransform::identity(),
+        ));
+        schedule.run(&mut world, &mut resources);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(entity).unwrap(),
+            GlobalTransform::from_translation(Vec3::new(1.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn uses_the_current_transform_without_a_fixed_timestep() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(FixedTimestepState::default());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", transform_interpolation_system.system());
+        schedule.add_system_to_stage("update", transform_propagate_system.system());
+
+        let entity = world.spawn((
+            Transform::from_translation(Vec3::new(4.0, 0.0, 0.0)),
+            TransformInterpolation::new(Transform::identity()),
+            GlobalTransform::identity(),
+        ));
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            *world.get::<GlobalTransform>(entity).unwrap(),
+            GlobalTransform::from_translation(Vec3::new(4.0, 0.0, 0.0))
+        );
+
+        world.get_mut::<Transform>(entity).unwrap().translation = Vec3::new(8.0, 0.0, 0.0);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            *world.get::<GlobalTransform>(entity).unwrap(),
+            GlobalTransform::from_translation(Vec3::new(8.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn interpolates_between_fixed_steps() {
+        fn move_system(mut query: Query<&mut Transform>) {
+            for mut transform in query.iter_mut() {
+                transform.translation += Vec3::new(4.0, 0.0, 0.0);
+            }
+        }
+
+        // the plugin registers the system that stores the previous transforms
+        let mut app = App::build();
+        app.init_resource::<TypeRegistry>()
+            .init_resource::<FixedTimestepState>()
+            .add_plugin(TransformPlugin);
+        let mut fixed_schedule = Schedule::default();
+        fixed_schedule.add_stage("update");
+        fixed_schedule.add_system_to_stage("update", move_system.system());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage(
+            "update",
+            FixedTimestep::new(0.1, fixed_schedule).thread_local_system(),
+        );
+        schedule.add_system_to_stage("update", transform_interpolation_system.system());
+        schedule.add_system_to_stage("update", transform_propagate_system.system());
+
+        let mut world = World::default();
+        let mut resources = std::mem::take(app.resources_mut());
+        resources.insert(Time::default());
+        let entity = world.spawn((
+            Transform::identity(),
+            TransformInterpolation::default(),
+            GlobalTransform::identity(),
+        ));
+
+        let mut advance = |delta: f64| {
+            resources.get_mut::<Time>().unwrap().delta_seconds_f64 = delta;
+            schedule.run(&mut world, &mut resources);
+            world
+                .get::<GlobalTransform>(entity)
+                .unwrap()
+                .translation
+                .x()
+        };
+        // one step, and none of the way into the next one
+        assert!(advance(0.1).abs() < 1e-4);
+        // one more step, and a quarter of the way into the next one
+        assert!((advance(0.125) - 5.0).abs() < 1e-4);
+    }
+}
@@ -1,18 +1,45 @@
 use crate::components::*;
 use bevy_ecs::prelude::*;
 
+/// Adds a [GlobalTransform] to entities that only have a [Transform], so they take part in propagation
+pub fn missing_global_transform_system(
+    mut commands: Commands,
+    query: Query<Without<GlobalTransform, (Entity, &Transform)>>,
+) {
+    for (entity, transform) in query.iter() {
+        commands.insert_one(entity, GlobalTransform::from(*transform));
+    }
+}
+
 pub fn transform_propagate_system(
     mut root_query: Query<
         Without<
             Parent,
-            With<GlobalTransform, (Option<&Children>, &Transform, &mut GlobalTransform)>,
+            With<
+                GlobalTransform,
+                (
+                    Option<&Children>,
+                    &Transform,
+                    Option<&TransformInterpolation>,
+                    &mut GlobalTransform,
+                ),
+            >,
+        >,
+    >,
+    mut transform_query: Query<
+        With<
+            Parent,
+            (
+                &Transform,
+                Option<&TransformInterpolation>,
+                &mut GlobalTransform,
+            ),
         >,
     >,
-    mut transform_query: Query<With<Parent, (&Transform, &mut GlobalTransform)>>,
     children_query: Query<With<Parent, With<GlobalTransform, Option<&Children>>>>,
 ) {
-    for (children, transform, mut global_transform) in root_query.iter_mut() {
-        *global_transform = GlobalTransform::from(*transform);
+    for (children, transform, interpolation, mut global_transform) in root_query.iter_mut() {
+        *global_transform = GlobalTransform::from(local_transform(transform, interpolation));
 
         if let Some(children) = children {
             for child in children.0.iter() {
@@ -29,15 +56,26 @@ pub fn transform_propagate_system(
 
 fn propagate_recursive(
     parent: &GlobalTransform,
-    transform_query: &mut Query<With<Parent, (&Transform, &mut GlobalTransform)>>,
+    transform_query: &mut Query<
+        With<
+            Parent,
+            (
+                &Transform,
+                Option<&TransformInterpolation>,
+                &mut GlobalTransform,
+            ),
+        >,
+    >,
     children_query: &Query<With<Parent, With<GlobalTransform, Option<&Children>>>>,
     entity: Entity,
 ) {
     log::trace!("Updating Transform for {:?}", entity);
 
     let global_matrix = {
-        if let Ok((transform, mut global_transform)) = transform_query.get_mut(entity) {
-            *global_transform = parent.mul_transform(*transform);
+        if let Ok((transform, interpolation, mut global_transform)) =
+            transform_query.get_mut(entity)
+        {
+            *global_transform = parent.mul_transform(local_transform(transform, interpolation));
             *global_transform
         } else {
             return;
@@ -51,6 +89,14 @@ fn propagate_recursive(
     }
 }
 
+/// The transform to propagate for an entity, which is interpolated if the entity has a [TransformInterpolation]
+fn local_transform(
+    transform: &Transform,
+    interpolation: Option<&TransformInterpolation>,
+) -> Transform {
+    interpolation.map_or(*transform, |interpolation| interpolation.interpolated)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -108,6 +154,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn did_add_missing_global_transform() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        for system in transform_systems() {
+            schedule.add_system_to_stage("update", system);
+        }
+
+        let mut commands = Commands::default();
+        commands.set_entity_reserver(world.get_entity_reserver());
+        let mut child = None;
+        commands
+            .spawn((Transform::from_translation(Vec3::new(1.0, 0.0, 0.0)),))
+            .with_children(|parent| {
+                parent
+                    .spawn((Transform::from_translation(Vec3::new(0.0, 2.0, 0.0)),))
+                    .for_current_entity(|entity| child = Some(entity));
+            });
+        commands.apply(&mut world, &mut resources);
+        // the first run adds the missing GlobalTransforms, the second propagates them
+        schedule.run(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(child.unwrap()).unwrap(),
+            GlobalTransform::from_translation(Vec3::new(1.0, 0.0, 0.0))
+                * Transform::from_translation(Vec3::new(0.0, 2.0, 0.0))
+        );
+    }
+
     #[test]
     fn did_propagate_command_buffer() {
         let mut world = World::default();
@@ -6,5 +6,3 @@ use crate::{
 use bevy_ecs::{IntoQuerySystem, System};
 use hierarchy_maintenance_system::hierarchy_maintenance_systems;
 use transform_systems::transform_systems;
-
-
@@ -1,6 +1,6 @@
 use crate::{ComponentRegistration, ComponentRegistrationBuilder, TypeRegistry};
 use bevy_app::AppBuilder;
-use bevy_ecs::{Component, FromResources};
+use bevy_ecs::{Component, FromResources, Resource};
 use bevy_property::{DeserializeProperty, Properties, Property};
 
 pub trait RegisterType {
@@ -16,6 +16,9 @@ pub trait RegisterType {
     fn register_properties<T>(&mut self) -> &mut Self
     where
         T: Properties + DeserializeProperty + FromResources;
+    fn register_resource<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Resource + FromResources;
     fn register_property<T>(&mut self) -> &mut Self
     where
         T: Property + DeserializeProperty;
@@ -45,6 +48,18 @@ impl RegisterType for AppBuilder {
         self
     }
 
+    fn register_resource<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Resource + FromResources,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.resource.write().register::<T>();
+            type_registry.property.write().register::<T>();
+        }
+        self
+    }
+
     fn register_property<T>(&mut self) -> &mut Self
     where
         T: Property + DeserializeProperty,
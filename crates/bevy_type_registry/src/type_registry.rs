@@ -1,18 +1,21 @@
 use bevy_ecs::{
     Archetype, Component, Entity, EntityMap, FromResources, MapEntities, MapEntitiesError,
-    Resources, World,
+    Resource, Resources, World,
 };
 use bevy_property::{
-    DeserializeProperty, Properties, Property, PropertyTypeRegistration, PropertyTypeRegistry,
+    DeserializeProperty, DynamicProperties, Properties, Property, PropertyTypeRegistration,
+    PropertyTypeRegistry,
 };
 use bevy_utils::{HashMap, HashSet};
 use parking_lot::RwLock;
 use std::{any::TypeId, marker::PhantomData, sync::Arc};
+use thiserror::Error;
 
 #[derive(Clone, Default)]
 pub struct TypeRegistry {
     pub property: Arc<RwLock<PropertyTypeRegistry>>,
     pub component: Arc<RwLock<ComponentRegistry>>,
+    pub resource: Arc<RwLock<ResourceRegistry>>,
 }
 
 #[derive(Default)]
@@ -83,6 +86,8 @@ pub struct ComponentRegistration {
     pub short_name: String,
     pub long_name: &'static str,
     component_add_fn: fn(&mut World, resources: &Resources, Entity, &dyn Property),
+    component_add_default_fn: fn(&mut World, resources: &Resources, Entity),
+    component_remove_fn: fn(&mut World, Entity),
     component_apply_fn: fn(&mut World, Entity, &dyn Property),
     component_properties_fn: fn(&Archetype, usize) -> &dyn Properties,
     component_copy_fn: fn(&World, &mut World, &Resources, Entity, Entity),
@@ -105,6 +110,21 @@ impl ComponentRegistrationDefaults {
         world.insert_one(entity, component).unwrap();
     }
 
+    fn component_add_default<T: Component + FromResources>(
+        world: &mut World,
+        resources: &Resources,
+        entity: Entity,
+    ) {
+        world
+            .insert_one(entity, T::from_resources(resources))
+            .unwrap();
+    }
+
+    fn component_remove<T: Component>(world: &mut World, entity: Entity) {
+        // Ignore the result, remove only cares if the component exists
+        let _ = world.remove_one::<T>(entity);
+    }
+
     fn component_apply<T: Component + Properties>(
         world: &mut World,
         entity: Entity,
@@ -161,6 +181,8 @@ impl ComponentRegistration {
         Self {
             ty,
             component_add_fn: ComponentRegistrationDefaults::component_add::<T>,
+            component_add_default_fn: ComponentRegistrationDefaults::component_add_default::<T>,
+            component_remove_fn: ComponentRegistrationDefaults::component_remove::<T>,
             component_apply_fn: ComponentRegistrationDefaults::component_apply::<T>,
             component_copy_fn: ComponentRegistrationDefaults::component_copy::<T>,
             component_properties_fn: ComponentRegistrationDefaults::component_properties::<T>,
@@ -182,6 +204,16 @@ impl ComponentRegistration {
         (self.component_add_fn)(world, resources, entity, property);
     }
 
+    /// Adds a new instance of this component to the given entity, created using its `FromResources` impl
+    pub fn add_default_to_entity(&self, world: &mut World, resources: &Resources, entity: Entity) {
+        (self.component_add_default_fn)(world, resources, entity);
+    }
+
+    /// Removes this component from the given entity, if it exists
+    pub fn remove_from_entity(&self, world: &mut World, entity: Entity) {
+        (self.component_remove_fn)(world, entity);
+    }
+
     pub fn apply_property_to_entity(
         &self,
         world: &mut World,
@@ -224,7 +256,7 @@ impl ComponentRegistration {
         source_entity: Entity,
         destination_entity: Entity,
     ) {
-        (self.component_copy_fn)(
+        (self.copy_from_scene_fn)(
             scene_world,
             destination_world,
             resources,
@@ -273,9 +305,12 @@ where
         T: MapEntities,
     {
         self.registration.map_entities_fn = |world: &mut World, entity_map: &EntityMap| {
-            // TODO: add UntrackedMut<T> pointer that returns &mut T. This will avoid setting the "mutated" state
-            for mut component in &mut world.query_mut::<&mut T>() {
-                component.map_entities(entity_map)?;
+            // only entities that were mapped (ex: spawned from a scene) should have their references updated
+            for entity in entity_map.values() {
+                // TODO: add UntrackedMut<T> pointer that returns &mut T. This will avoid setting the "mutated" state
+                if let Ok(mut component) = world.get_mut::<T>(entity) {
+                    component.map_entities(entity_map)?;
+                }
             }
 
             Ok(())
@@ -331,3 +366,202 @@ where
 pub trait IntoComponent<ToComponent: Component> {
     fn into_component(&self, resources: &Resources) -> ToComponent;
 }
+
+/// Resources that can be captured and restored by name, ex: when snapshotting a [World]
+#[derive(Default)]
+pub struct ResourceRegistry {
+    pub registrations: HashMap<TypeId, ResourceRegistration>,
+    pub short_names: HashMap<String, TypeId>,
+    pub full_names: HashMap<String, TypeId>,
+    pub ambiguous_names: HashSet<String>,
+}
+
+impl ResourceRegistry {
+    pub fn register<T>(&mut self)
+    where
+        T: Properties + Resource + FromResources,
+    {
+        self.add_registration(ResourceRegistration::of::<T>());
+    }
+
+    pub fn add_registration(&mut self, registration: ResourceRegistration) {
+        let short_name = registration.short_name.to_string();
+        self.full_names
+            .insert(registration.long_name.to_string(), registration.ty);
+        if self.short_names.contains_key(&short_name) || self.ambiguous_names.contains(&short_name)
+        {
+            // name is ambiguous. fall back to long names for all ambiguous types
+            self.short_names.remove(&short_name);
+            self.ambiguous_names.insert(short_name);
+        } else {
+            self.short_names.insert(short_name, registration.ty);
+        }
+        self.registrations.insert(registration.ty, registration);
+    }
+
+    pub fn get(&self, type_id: &TypeId) -> Option<&ResourceRegistration> {
+        self.registrations.get(type_id)
+    }
+
+    pub fn get_with_full_name(&self, full_name: &str) -> Option<&ResourceRegistration> {
+        self.full_names
+            .get(full_name)
+            .and_then(|id| self.registrations.get(id))
+    }
+
+    pub fn get_with_short_name(&self, short_name: &str) -> Option<&ResourceRegistration> {
+        self.short_names
+            .get(short_name)
+            .and_then(|id| self.registrations.get(id))
+    }
+
+    /// Looks up a resource by its short name, falling back to its full name. Short names shared by several
+    /// resources must be written in full.
+    pub fn get_with_name(
+        &self,
+        type_name: &str,
+    ) -> Result<&ResourceRegistration, ResourceRegistryError> {
+        self.get_with_short_name(type_name)
+            .or_else(|| self.get_with_full_name(type_name))
+            .ok_or_else(|| {
+                if self.ambiguous_names.contains(type_name) {
+                    ResourceRegistryError::AmbiguousName {
+                        type_name: type_name.to_string(),
+                    }
+                } else {
+                    ResourceRegistryError::Unregistered {
+                        type_name: type_name.to_string(),
+                    }
+                }
+            })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ResourceRegistration> {
+        self.registrations.values()
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ResourceRegistryError {
+    #[error("Resource is not registered.")]
+    Unregistered { type_name: String },
+    #[error("Resource name is shared by several resources. Use its full name instead.")]
+    AmbiguousName { type_name: String },
+}
+
+#[derive(Clone)]
+pub struct ResourceRegistration {
+    pub ty: TypeId,
+    pub short_name: String,
+    pub long_name: &'static str,
+    resource_to_dynamic_fn: fn(&Resources) -> Option<DynamicProperties>,
+    resource_apply_fn: fn(&mut Resources, &dyn Property),
+}
+
+impl ResourceRegistration {
+    pub fn of<T: Properties + Resource + FromResources>() -> Self {
+        Self {
+            ty: TypeId::of::<T>(),
+            short_name: PropertyTypeRegistration::get_short_name(std::any::type_name::<T>()),
+            long_name: std::any::type_name::<T>(),
+            resource_to_dynamic_fn: |resources: &Resources| {
+                resources.get::<T>().map(|resource| resource.to_dynamic())
+            },
+            resource_apply_fn: |resources: &mut Resources, property: &dyn Property| {
+                if let Some(mut resource) = resources.get_mut::<T>() {
+                    resource.apply(property);
+                    return;
+                }
+                let mut resource = T::from_resources(resources);
+                resource.apply(property);
+                resources.insert(resource);
+            },
+        }
+    }
+
+    /// Returns a dynamic copy of this resource, or `None` if it has not been inserted
+    pub fn get_dynamic(&self, resources: &Resources) -> Option<DynamicProperties> {
+        (self.resource_to_dynamic_fn)(resources)
+    }
+
+    /// Applies the given property to this resource, inserting it first (using `FromResources`) if it doesn't exist
+    pub fn apply_property(&self, resources: &mut Resources, property: &dyn Property) {
+        (self.resource_apply_fn)(resources, property);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentRegistry, ResourceRegistry, ResourceRegistryError};
+    use bevy_ecs::{Resources, World};
+    use bevy_property::Properties;
+
+    #[derive(Properties, Debug, Default, PartialEq)]
+    struct Health {
+        value: f32,
+    }
+
+    #[test]
+    fn add_and_remove_component_by_name() {
+        let mut registry = ComponentRegistry::default();
+        registry.register::<Health>();
+
+        let mut world = World::new();
+        let resources = Resources::default();
+        let entity = world.spawn((1u32,));
+
+        let registration = registry.get_with_name("Health").unwrap();
+        registration.add_default_to_entity(&mut world, &resources, entity);
+        assert_eq!(*world.get::<Health>(entity).unwrap(), Health::default());
+
+        registration.apply_property_to_entity(&mut world, entity, &Health { value: 10.0 });
+        assert_eq!(world.get::<Health>(entity).unwrap().value, 10.0);
+
+        registration.remove_from_entity(&mut world, entity);
+        assert!(world.get::<Health>(entity).is_err());
+        assert_eq!(*world.get::<u32>(entity).unwrap(), 1);
+    }
+
+    mod a {
+        use bevy_property::Properties;
+
+        #[derive(Properties, Default)]
+        pub struct Settings {
+            pub value: u32,
+        }
+    }
+
+    mod b {
+        use bevy_property::Properties;
+
+        #[derive(Properties, Default)]
+        pub struct Settings {
+            pub value: u32,
+        }
+    }
+
+    #[test]
+    fn ambiguous_resource_names_return_an_error() {
+        let mut registry = ResourceRegistry::default();
+        registry.register::<a::Settings>();
+        registry.register::<b::Settings>();
+
+        assert_eq!(
+            registry.get_with_name("Settings").err(),
+            Some(ResourceRegistryError::AmbiguousName {
+                type_name: "Settings".to_string()
+            })
+        );
+        assert_eq!(
+            registry.get_with_name("Missing").err(),
+            Some(ResourceRegistryError::Unregistered {
+                type_name: "Missing".to_string()
+            })
+        );
+        let full_name = std::any::type_name::<a::Settings>();
+        assert_eq!(
+            registry.get_with_name(full_name).unwrap().long_name,
+            full_name
+        );
+    }
+}
@@ -1,3 +1,9 @@
+use crate::{Margins, PositionType, Style, Val};
+use bevy_math::{Rect, Vec2};
+
+/// Points on a parent node, as fractions of its size from its bottom left corner, that a node's edges are attached to.
+/// Anchored nodes are absolutely positioned, so they stay attached to their parent's edges or center as it's resized.
+/// A node whose left and right anchors differ stretches between them horizontally, and likewise vertically.
 #[derive(Debug, Clone)]
 pub struct Anchors {
     pub left: f32,
@@ -32,6 +38,53 @@ impl Anchors {
             top,
         }
     }
+
+    /// Returns a style that places each edge of a node at its anchor, moved by the margin in pixels. For example, a
+    /// node anchored to [Anchors::FULL] with margins of `(10.0, -10.0, 10.0, -10.0)` fills its parent except for a 10
+    /// pixel border.
+    pub fn style(&self, margins: &Margins) -> Style {
+        Style {
+            position_type: PositionType::Absolute,
+            position: Rect {
+                left: Val::Percent(self.left * 100.0),
+                right: Val::Percent((1.0 - self.right) * 100.0),
+                bottom: Val::Percent(self.bottom * 100.0),
+                top: Val::Percent((1.0 - self.top) * 100.0),
+            },
+            margin: Rect {
+                left: Val::Px(margins.left),
+                right: Val::Px(-margins.right),
+                bottom: Val::Px(margins.bottom),
+                top: Val::Px(-margins.top),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Returns a style for a node of the given size in pixels. Along axes where both anchors are the same, the node's
+    /// matching point is placed on the anchor, so a node anchored to [Anchors::TOP_RIGHT] has its top right corner in
+    /// the parent's top right corner and one anchored to [Anchors::CENTER] is centered. Along axes where they differ,
+    /// the node stretches between the anchors and its size is ignored. The node is then moved by `offset`.
+    pub fn sized_style(&self, size: Vec2, offset: Vec2) -> Style {
+        let (left, right) = if self.left == self.right {
+            let left = -self.left * size.x();
+            (left, left + size.x())
+        } else {
+            (0.0, 0.0)
+        };
+        let (bottom, top) = if self.bottom == self.top {
+            let bottom = -self.bottom * size.y();
+            (bottom, bottom + size.y())
+        } else {
+            (0.0, 0.0)
+        };
+        self.style(&Margins::new(
+            left + offset.x(),
+            right + offset.x(),
+            bottom + offset.y(),
+            top + offset.y(),
+        ))
+    }
 }
 
 impl Default for Anchors {
@@ -44,3 +97,59 @@ impl Default for Anchors {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Anchors;
+    use crate::{Margins, Style};
+    use bevy_math::Vec2;
+    use stretch::{geometry::Size, style::Dimension, Stretch};
+
+    fn layout(style: Style) -> (Vec2, Vec2) {
+        let mut stretch = Stretch::new();
+        let child = stretch.new_node((&style).into(), Vec::new()).unwrap();
+        let parent = stretch
+            .new_node(
+                stretch::style::Style {
+                    size: Size {
+                        width: Dimension::Points(800.0),
+                        height: Dimension::Points(600.0),
+                    },
+                    ..Default::default()
+                },
+                vec![child],
+            )
+            .unwrap();
+        stretch.compute_layout(parent, Size::undefined()).unwrap();
+        let layout = stretch.layout(child).unwrap();
+        (
+            Vec2::new(layout.location.x, layout.location.y),
+            Vec2::new(layout.size.width, layout.size.height),
+        )
+    }
+
+    #[test]
+    fn anchored_layout() {
+        // stretched with a 10 pixel border
+        let full = Anchors::FULL.style(&Margins::new(10.0, -10.0, 10.0, -10.0));
+        assert_eq!(
+            layout(full),
+            (Vec2::new(10.0, 10.0), Vec2::new(780.0, 580.0))
+        );
+
+        // centered
+        let center = Anchors::CENTER.sized_style(Vec2::new(200.0, 100.0), Vec2::zero());
+        assert_eq!(
+            layout(center),
+            (Vec2::new(300.0, 250.0), Vec2::new(200.0, 100.0))
+        );
+
+        // in the top right corner, moved down by 5 pixels
+        let top_right =
+            Anchors::TOP_RIGHT.sized_style(Vec2::new(200.0, 100.0), Vec2::new(0.0, -5.0));
+        assert_eq!(
+            layout(top_right),
+            (Vec2::new(600.0, 495.0), Vec2::new(200.0, 100.0))
+        );
+    }
+}
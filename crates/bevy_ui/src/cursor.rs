@@ -0,0 +1,152 @@
+use crate::Node;
+use bevy_app::{EventReader, Events};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_render::draw::Visible;
+use bevy_transform::prelude::Transform;
+use bevy_window::{CursorMoved, Windows};
+
+/// Draws a root UI node in place of the system cursor, for custom cursor images that the window can't show itself. Add
+/// it to an image node, like [ImageComponents](crate::entity::ImageComponents), with a [ZIndex::Global](crate::ZIndex)
+/// above the rest of the UI. While the node is visible the system cursor is hidden over the primary window, and the
+/// node follows the cursor so that its `hotspot` is on the cursor position. The node never takes focus from the nodes
+/// below it.
+///
+/// The image is drawn by the app, so it trails the system cursor by a frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CursorImage {
+    /// The point of the image that the cursor position is on, in logical pixels from the image's top left corner
+    pub hotspot: Vec2,
+}
+
+impl CursorImage {
+    pub fn new(hotspot: Vec2) -> Self {
+        CursorImage { hotspot }
+    }
+}
+
+#[derive(Default)]
+pub struct CursorImageState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor_position: Option<Vec2>,
+    hides_system_cursor: bool,
+}
+
+/// Runs after the flex layout, so it overrides the position the layout gave cursor images.
+pub fn cursor_image_system(
+    mut state: Local<CursorImageState>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    mut windows: ResMut<Windows>,
+    mut node_query: Query<With<Node, (&CursorImage, &Node, &mut Transform, Option<&Visible>)>>,
+) {
+    let window = match windows.get_primary_mut() {
+        Some(window) => window,
+        None => return,
+    };
+    for event in state.cursor_moved_event_reader.iter(&cursor_moved_events) {
+        if event.id == window.id() {
+            state.cursor_position = Some(event.position);
+        }
+    }
+
+    let mut shown = false;
+    for (cursor_image, node, mut transform, visible) in node_query.iter_mut() {
+        if !visible.map_or(true, |visible| visible.is_drawn()) {
+            continue;
+        }
+        shown = true;
+
+        let position = match state.cursor_position {
+            // the hotspot is measured down from the top, and UI positions up from the bottom of the window
+            Some(cursor_position) => Vec2::new(
+                cursor_position.x() - cursor_image.hotspot.x() + node.size.x() / 2.0,
+                cursor_position.y() + cursor_image.hotspot.y() - node.size.y() / 2.0,
+            ),
+            // keep the image out of the window until the cursor has moved over it
+            None => -node.size,
+        };
+        transform.translation.set_x(position.x());
+        transform.translation.set_y(position.y());
+    }
+
+    if shown != state.hides_system_cursor {
+        window.set_cursor_visibility(!shown);
+        state.hides_system_cursor = shown;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::Schedule;
+    use bevy_window::{Window, WindowDescriptor, WindowId};
+    use std::time::Instant;
+
+    #[test]
+    fn cursor_images_follow_the_cursor_and_hide_the_system_cursor() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+        ));
+        resources.insert(windows);
+        resources.insert(Events::<CursorMoved>::default());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", cursor_image_system.system());
+
+        let cursor = world.spawn((
+            CursorImage::new(Vec2::new(2.0, 4.0)),
+            Node {
+                size: Vec2::new(32.0, 32.0),
+            },
+            Transform::default(),
+            Visible::default(),
+        ));
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            world
+                .get::<Transform>(cursor)
+                .unwrap()
+                .translation
+                .truncate(),
+            Vec2::new(-32.0, -32.0)
+        );
+        assert!(!resources
+            .get::<Windows>()
+            .unwrap()
+            .get_primary()
+            .unwrap()
+            .cursor_visible());
+
+        resources
+            .get_mut::<Events<CursorMoved>>()
+            .unwrap()
+            .send(CursorMoved {
+                id: WindowId::primary(),
+                position: Vec2::new(100.0, 200.0),
+                timestamp: Instant::now(),
+            });
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            world
+                .get::<Transform>(cursor)
+                .unwrap()
+                .translation
+                .truncate(),
+            Vec2::new(114.0, 188.0)
+        );
+
+        world.get_mut::<Visible>(cursor).unwrap().is_visible = false;
+        schedule.run(&mut world, &mut resources);
+        assert!(resources
+            .get::<Windows>()
+            .unwrap()
+            .get_primary()
+            .unwrap()
+            .cursor_visible());
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{
+    entity::{NodeComponents, TextComponents},
+    overlay_font::add_overlay_font,
+    widget::Text,
+    AlignItems, FlexDirection, PositionType, Style, Val, OVERLAY_FONT_HANDLE,
+};
+use bevy_app::prelude::*;
+use bevy_asset::{Assets, Handle};
+use bevy_diagnostic::{
+    Diagnostic, DiagnosticId, Diagnostics, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+};
+use bevy_ecs::{Commands, IntoQuerySystem, Query, Res, ResMut, With};
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_math::{Rect, Size};
+use bevy_render::{color::Color, draw::Visible, renderer::RenderStatistics};
+use bevy_sprite::ColorMaterial;
+use bevy_text::{Font, TextAlignment, TextStyle};
+use bevy_transform::prelude::BuildChildren;
+use std::collections::VecDeque;
+
+/// Shows the FPS, a graph of recent frame times, the entity count and the number of draw calls and triangles in the
+/// top left corner of the window, so performance regressions are visible without attaching a profiler. The overlay is
+/// drawn by the UI camera, so the app needs to spawn one.
+///
+/// Rows whose diagnostics aren't registered show a dash. Add the [FrameTimeDiagnosticsPlugin] and the
+/// [EntityCountDiagnosticsPlugin] to fill them in. The draw calls and triangles are measured by this plugin, from the
+/// [RenderStatistics] of the render backend.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsOverlayPlugin {
+    /// The font of the overlay text. Defaults to the embedded [OVERLAY_FONT_HANDLE].
+    pub font: Handle<Font>,
+    /// Shows or hides the overlay when pressed
+    pub toggle_key: KeyCode,
+    /// Whether the overlay is shown at startup
+    pub visible: bool,
+}
+
+impl Default for DiagnosticsOverlayPlugin {
+    fn default() -> Self {
+        DiagnosticsOverlayPlugin {
+            font: OVERLAY_FONT_HANDLE,
+            toggle_key: KeyCode::F3,
+            visible: true,
+        }
+    }
+}
+
+/// State used by the [DiagnosticsOverlayPlugin]
+#[derive(Debug)]
+pub struct DiagnosticsOverlayState {
+    pub visible: bool,
+    pub toggle_key: KeyCode,
+    font: Handle<Font>,
+    bar_materials: Vec<Handle<ColorMaterial>>,
+    frame_times: VecDeque<f64>,
+}
+
+/// Marks the root node of the diagnostics overlay
+#[derive(Debug, Default, Copy, Clone)]
+pub struct DiagnosticsOverlay;
+
+#[derive(Debug, Default, Copy, Clone)]
+struct DiagnosticsOverlayText;
+
+/// A bar of the frame time graph. Bar 0 is the oldest frame.
+#[derive(Debug, Default, Copy, Clone)]
+struct FrameTimeBar(usize);
+
+impl Plugin for DiagnosticsOverlayPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        add_overlay_font(app);
+        app.add_resource(DiagnosticsOverlayState {
+            visible: self.visible,
+            toggle_key: self.toggle_key,
+            font: self.font.clone(),
+            bar_materials: Vec::new(),
+            frame_times: VecDeque::with_capacity(Self::GRAPH_FRAMES),
+        })
+        .add_startup_system(Self::setup_system.system())
+        .add_system_to_stage(stage::PRE_UPDATE, Self::toggle_system.system())
+        .add_system_to_stage(stage::UPDATE, Self::update_system.system())
+        .add_system_to_stage(
+            stage::LAST,
+            Self::render_statistics_diagnostic_system.system(),
+        );
+    }
+}
+
+impl DiagnosticsOverlayPlugin {
+    pub const DRAW_CALLS: DiagnosticId =
+        DiagnosticId::from_u128(119861823487012389513419758162049373551);
+    pub const TRIANGLES: DiagnosticId =
+        DiagnosticId::from_u128(262514793170433478105316593904175231877);
+
+    /// The number of frames the graph shows
+    pub const GRAPH_FRAMES: usize = 60;
+    /// The frame time that fills the height of the graph, in seconds
+    pub const GRAPH_MAX_FRAME_TIME: f64 = 1.0 / 20.0;
+    const GRAPH_HEIGHT: f32 = 40.0;
+    const BAR_WIDTH: f32 = 3.0;
+
+    pub fn setup_system(
+        mut commands: Commands,
+        mut diagnostics: ResMut<Diagnostics>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+        mut state: ResMut<DiagnosticsOverlayState>,
+    ) {
+        diagnostics.add(Diagnostic::new(Self::DRAW_CALLS, "draw_calls", 20));
+        diagnostics.add(Diagnostic::new(Self::TRIANGLES, "triangles", 20));
+
+        // bars for frames under 60 fps, under 30 fps and slower
+        state.bar_materials = vec![
+            materials.add(Color::rgb(0.2, 0.8, 0.2).into()),
+            materials.add(Color::rgb(0.9, 0.8, 0.1).into()),
+            materials.add(Color::rgb(0.9, 0.2, 0.2).into()),
+        ];
+        let transparent = Visible {
+            is_transparent: true,
+            ..Default::default()
+        };
+        let font = state.font.clone();
+
+        commands
+            .spawn(NodeComponents {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        left: Val::Px(5.0),
+                        top: Val::Px(5.0),
+                        ..Default::default()
+                    },
+                    padding: Rect::all(Val::Px(5.0)),
+                    flex_direction: FlexDirection::ColumnReverse,
+                    ..Default::default()
+                },
+                material: materials.add(Color::rgba(0.0, 0.0, 0.0, 0.7).into()),
+                // hiding the overlay also hides its children
+                visible: Visible {
+                    is_visible: state.visible,
+                    ..transparent.clone()
+                },
+                ..Default::default()
+            })
+            .with(DiagnosticsOverlay)
+            .with_children(|parent| {
+                parent
+                    .spawn(TextComponents {
+                        text: Text::with_section(
+                            "",
+                            font,
+                            TextStyle {
+                                font_size: 16.0,
+                                color: Color::WHITE,
+                            },
+                            TextAlignment::default(),
+                        ),
+                        visible: transparent.clone(),
+                        ..Default::default()
+                    })
+                    .with(DiagnosticsOverlayText)
+                    .spawn(NodeComponents {
+                        style: Style {
+                            size: Size::new(Val::Auto, Val::Px(Self::GRAPH_HEIGHT)),
+                            margin: Rect {
+                                top: Val::Px(5.0),
+                                ..Default::default()
+                            },
+                            // stretch's y axis is flipped, so this keeps the bars on the bottom of the graph
+                            align_items: AlignItems::FlexStart,
+                            ..Default::default()
+                        },
+                        material: materials.add(Color::NONE.into()),
+                        visible: transparent.clone(),
+                        ..Default::default()
+                    })
+                    .with_children(|graph| {
+                        for index in 0..Self::GRAPH_FRAMES {
+                            graph
+                                .spawn(NodeComponents {
+                                    style: Style {
+                                        size: Size::new(Val::Px(Self::BAR_WIDTH), Val::Px(0.0)),
+                                        ..Default::default()
+                                    },
+                                    material: state.bar_materials[0].clone(),
+                                    visible: transparent.clone(),
+                                    ..Default::default()
+                                })
+                                .with(FrameTimeBar(index));
+                        }
+                    });
+            });
+    }
+
+    pub fn toggle_system(
+        keyboard_input: Res<Input<KeyCode>>,
+        mut state: ResMut<DiagnosticsOverlayState>,
+        mut overlay_query: Query<With<DiagnosticsOverlay, &mut Visible>>,
+    ) {
+        if !keyboard_input.just_pressed(state.toggle_key) {
+            return;
+        }
+        state.visible = !state.visible;
+        for mut visible in overlay_query.iter_mut() {
+            visible.is_visible = state.visible;
+        }
+    }
+
+    pub fn update_system(
+        diagnostics: Res<Diagnostics>,
+        mut state: ResMut<DiagnosticsOverlayState>,
+        mut text_query: Query<With<DiagnosticsOverlayText, &mut Text>>,
+        mut bar_query: Query<(&FrameTimeBar, &mut Style, &mut Handle<ColorMaterial>)>,
+    ) {
+        if let Some(frame_time) = diagnostics
+            .get(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+            .and_then(|diagnostic| diagnostic.value())
+        {
+            if state.frame_times.len() == Self::GRAPH_FRAMES {
+                state.frame_times.pop_front();
+            }
+            state.frame_times.push_back(frame_time);
+        }
+        if !state.visible {
+            return;
+        }
+
+        let value = |id: DiagnosticId, precision: usize| {
+            diagnostics
+                .get(id)
+                .and_then(|diagnostic| diagnostic.average())
+                .map(|value| format!("{:.*}", precision, value))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        let frame_time = diagnostics
+            .get(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+            .and_then(|diagnostic| diagnostic.average())
+            .map(|frame_time| format!("{:.2}", frame_time * 1000.0))
+            .unwrap_or_else(|| "-".to_string());
+        let overlay_text = format!(
+            "fps: {}\nframe time: {} ms\nentities: {}\ndraw calls: {}\ntriangles: {}",
+            value(FrameTimeDiagnosticsPlugin::FPS, 1),
+            frame_time,
+            value(EntityCountDiagnosticsPlugin::ENTITY_COUNT, 0),
+            value(Self::DRAW_CALLS, 0),
+            value(Self::TRIANGLES, 0),
+        );
+        for mut text in text_query.iter_mut() {
+            if text.sections[0].value != overlay_text {
+                text.sections[0].value = overlay_text.clone();
+            }
+        }
+
+        // the newest frame is drawn by the last bar
+        let first_bar = Self::GRAPH_FRAMES - state.frame_times.len();
+        for (bar, mut style, mut material) in bar_query.iter_mut() {
+            let frame_time = if bar.0 >= first_bar {
+                state.frame_times[bar.0 - first_bar]
+            } else {
+                0.0
+            };
+            let height = Val::Px(bar_height(frame_time, Self::GRAPH_HEIGHT));
+            if style.size.height != height {
+                style.size.height = height;
+            }
+            let bar_material = &state.bar_materials[bar_color_index(frame_time)];
+            if *material != *bar_material {
+                *material = bar_material.clone();
+            }
+        }
+    }
+
+    pub fn render_statistics_diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        render_statistics: Res<RenderStatistics>,
+    ) {
+        diagnostics.add_measurement(Self::DRAW_CALLS, render_statistics.draw_calls as f64);
+        diagnostics.add_measurement(Self::TRIANGLES, render_statistics.triangles as f64);
+    }
+}
+
+fn bar_height(frame_time: f64, graph_height: f32) -> f32 {
+    (frame_time / DiagnosticsOverlayPlugin::GRAPH_MAX_FRAME_TIME).min(1.0) as f32 * graph_height
+}
+
+fn bar_color_index(frame_time: f64) -> usize {
+    if frame_time <= 1.0 / 59.0 {
+        0
+    } else if frame_time <= 1.0 / 29.0 {
+        1
+    } else {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bar_color_index, bar_height};
+
+    #[test]
+    fn frame_time_bars() {
+        assert_eq!(bar_height(0.0, 40.0), 0.0);
+        assert_eq!(bar_height(1.0 / 40.0, 40.0), 20.0);
+        assert_eq!(bar_height(1.0, 40.0), 40.0);
+        assert_eq!(bar_color_index(1.0 / 60.0), 0);
+        assert_eq!(bar_color_index(1.0 / 30.0), 1);
+        assert_eq!(bar_color_index(1.0 / 10.0), 2);
+    }
+}
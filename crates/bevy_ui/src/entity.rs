@@ -9,12 +9,13 @@ use bevy_ecs::Bundle;
 use bevy_math::Vec3;
 use bevy_render::{
     camera::{Camera, OrthographicProjection, VisibleEntities, WindowOrigin},
-    draw::Draw,
+    draw::{Draw, Visible},
     mesh::Mesh,
     pipeline::{DynamicBinding, PipelineSpecialization, RenderPipeline, RenderPipelines},
+    raycast::IgnoreRaycast,
 };
 use bevy_sprite::{ColorMaterial, QUAD_HANDLE};
-use bevy_transform::prelude::{GlobalTransform, Transform};
+use bevy_transform::prelude::{GlobalTransform, IgnoreFloatingOrigin, Transform};
 
 #[derive(Bundle, Clone, Debug)]
 pub struct NodeComponents {
@@ -23,9 +24,12 @@ pub struct NodeComponents {
     pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
     pub material: Handle<ColorMaterial>,
     pub draw: Draw,
+    pub visible: Visible,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
+    pub ignore_floating_origin: IgnoreFloatingOrigin,
+    pub ignore_raycast: IgnoreRaycast,
 }
 
 impl Default for NodeComponents {
@@ -54,8 +58,11 @@ impl Default for NodeComponents {
             style: Default::default(),
             material: Default::default(),
             draw: Default::default(),
+            visible: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
+            ignore_floating_origin: Default::default(),
+            ignore_raycast: IgnoreRaycast,
         }
     }
 }
@@ -69,9 +76,12 @@ pub struct ImageComponents {
     pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
     pub material: Handle<ColorMaterial>,
     pub draw: Draw,
+    pub visible: Visible,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
+    pub ignore_floating_origin: IgnoreFloatingOrigin,
+    pub ignore_raycast: IgnoreRaycast,
 }
 
 impl Default for ImageComponents {
@@ -92,6 +102,11 @@ impl Default for ImageComponents {
                             bind_group: 1,
                             binding: 1,
                         },
+                        // NinePatch
+                        DynamicBinding {
+                            bind_group: 1,
+                            binding: 2,
+                        },
                     ],
                     ..Default::default()
                 },
@@ -102,8 +117,11 @@ impl Default for ImageComponents {
             style: Default::default(),
             material: Default::default(),
             draw: Default::default(),
+            visible: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
+            ignore_floating_origin: Default::default(),
+            ignore_raycast: IgnoreRaycast,
         }
     }
 }
@@ -113,18 +131,21 @@ pub struct TextComponents {
     pub node: Node,
     pub style: Style,
     pub draw: Draw,
+    pub visible: Visible,
     pub text: Text,
     pub calculated_size: CalculatedSize,
     pub focus_policy: FocusPolicy,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
+    pub ignore_floating_origin: IgnoreFloatingOrigin,
 }
 
 impl Default for TextComponents {
     fn default() -> Self {
         TextComponents {
             focus_policy: FocusPolicy::Pass,
-            draw: Draw {
+            draw: Default::default(),
+            visible: Visible {
                 is_transparent: true,
                 ..Default::default()
             },
@@ -134,6 +155,7 @@ impl Default for TextComponents {
             style: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
+            ignore_floating_origin: Default::default(),
         }
     }
 }
@@ -148,9 +170,12 @@ pub struct ButtonComponents {
     pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
     pub material: Handle<ColorMaterial>,
     pub draw: Draw,
+    pub visible: Visible,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
+    pub ignore_floating_origin: IgnoreFloatingOrigin,
+    pub ignore_raycast: IgnoreRaycast,
 }
 
 impl Default for ButtonComponents {
@@ -182,8 +207,11 @@ impl Default for ButtonComponents {
             style: Default::default(),
             material: Default::default(),
             draw: Default::default(),
+            visible: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
+            ignore_floating_origin: Default::default(),
+            ignore_raycast: IgnoreRaycast,
         }
     }
 }
@@ -195,6 +223,7 @@ pub struct UiCameraComponents {
     pub visible_entities: VisibleEntities,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
+    pub ignore_floating_origin: IgnoreFloatingOrigin,
 }
 
 impl Default for UiCameraComponents {
@@ -215,6 +244,52 @@ impl Default for UiCameraComponents {
             visible_entities: Default::default(),
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, far - 0.1)),
             global_transform: Default::default(),
+            ignore_floating_origin: Default::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{NodeComponents, UiCameraComponents};
+    use bevy_ecs::{IntoQuerySystem, Resources, Schedule, World};
+    use bevy_math::Vec3;
+    use bevy_transform::{
+        floating_origin_system::floating_origin_system,
+        prelude::{FloatingOrigin, Transform, WorldOrigin},
+    };
+
+    #[test]
+    fn ui_stays_on_screen_when_the_world_moves_to_the_origin() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(WorldOrigin::default());
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", floating_origin_system.system());
+
+        let ui_camera = world.spawn(UiCameraComponents::default());
+        let node = world.spawn(NodeComponents {
+            transform: Transform::from_translation(Vec3::new(100.0, 50.0, 0.0)),
+            ..Default::default()
+        });
+        let camera = world.spawn((
+            Transform::from_translation(Vec3::new(5000.0, 0.0, 0.0)),
+            FloatingOrigin,
+        ));
+        schedule.run(&mut world, &mut resources);
+
+        assert_eq!(
+            world.get::<Transform>(camera).unwrap().translation,
+            Vec3::zero()
+        );
+        assert_eq!(
+            world.get::<Transform>(ui_camera).unwrap().translation,
+            UiCameraComponents::default().transform.translation
+        );
+        assert_eq!(
+            world.get::<Transform>(node).unwrap().translation,
+            Vec3::new(100.0, 50.0, 0.0)
+        );
+    }
+}
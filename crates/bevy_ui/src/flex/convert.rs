@@ -1,6 +1,6 @@
 use crate::{
     AlignContent, AlignItems, AlignSelf, Direction, Display, FlexDirection, FlexWrap,
-    JustifyContent, PositionType, Style, Val,
+    JustifyContent, Overflow, PositionType, Style, Val,
 };
 use bevy_math::{Rect, Size};
 
@@ -30,7 +30,7 @@ where
 impl From<&Style> for stretch::style::Style {
     fn from(value: &Style) -> Self {
         Self {
-            overflow: stretch::style::Overflow::Visible,
+            overflow: value.overflow.into(),
             display: value.display.into(),
             position_type: value.position_type.into(),
             direction: value.direction.into(),
@@ -150,6 +150,16 @@ impl From<JustifyContent> for stretch::style::JustifyContent {
     }
 }
 
+impl From<Overflow> for stretch::style::Overflow {
+    fn from(value: Overflow) -> Self {
+        match value {
+            Overflow::Visible => stretch::style::Overflow::Visible,
+            Overflow::Hidden => stretch::style::Overflow::Hidden,
+            Overflow::Scroll => stretch::style::Overflow::Scroll,
+        }
+    }
+}
+
 impl From<PositionType> for stretch::style::PositionType {
     fn from(value: PositionType) -> Self {
         match value {
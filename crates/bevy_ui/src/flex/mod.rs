@@ -1,6 +1,6 @@
 mod convert;
 
-use crate::{CalculatedSize, Node, Style};
+use crate::{CalculatedSize, Node, ScrollPosition, Style};
 use bevy_ecs::{Changed, Entity, Query, Res, ResMut, With, Without};
 use bevy_math::Vec2;
 use bevy_transform::prelude::{Children, Parent, Transform};
@@ -116,8 +116,8 @@ impl FlexSurface {
                 *node,
                 stretch::style::Style {
                     size: stretch::geometry::Size {
-                        width: stretch::style::Dimension::Points(window.width() as f32),
-                        height: stretch::style::Dimension::Points(window.height() as f32),
+                        width: stretch::style::Dimension::Points(window.width()),
+                        height: stretch::style::Dimension::Points(window.height()),
                     },
                     ..Default::default()
                 },
@@ -139,6 +139,15 @@ impl FlexSurface {
             .unwrap();
     }
 
+    /// Removes the layout nodes of entities that are no longer UI nodes (ex: because they were despawned)
+    pub fn remove_entities(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        for entity in entities {
+            if let Some(stretch_node) = self.entity_to_stretch.remove(&entity) {
+                self.stretch.remove(stretch_node);
+            }
+        }
+    }
+
     pub fn compute_window_layouts(&mut self) {
         for window_node in self.window_nodes.values() {
             self.stretch
@@ -165,6 +174,7 @@ pub fn flex_node_system(
     changed_size_query: Query<With<Node, (Entity, &Style, Changed<CalculatedSize>)>>,
     children_query: Query<With<Node, (Entity, Changed<Children>)>>,
     mut node_transform_query: Query<(Entity, &mut Node, &mut Transform, Option<&Parent>)>,
+    scroll_query: Query<&ScrollPosition>,
 ) {
     // update window root nodes
     for window in windows.iter() {
@@ -185,7 +195,8 @@ pub fn flex_node_system(
         flex_surface.upsert_leaf(entity, &style, *calculated_size);
     }
 
-    // TODO: handle removed nodes
+    // clean up nodes whose entities were despawned or had their Node removed
+    flex_surface.remove_entities(root_node_query.removed::<Node>().iter().cloned());
 
     // update window children (for now assuming all Nodes live in the primary window)
     if let Some(primary_window) = windows.get_primary() {
@@ -211,6 +222,10 @@ pub fn flex_node_system(
                 *position.x_mut() -= parent_layout.size.width / 2.0;
                 *position.y_mut() -= parent_layout.size.height / 2.0;
             }
+            if let Ok(scroll_position) = scroll_query.get(parent.0) {
+                *position.x_mut() -= scroll_position.offset.x();
+                *position.y_mut() -= scroll_position.offset.y();
+            }
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::Node;
+use crate::{CalculatedClip, CursorImage, Node};
 use bevy_app::{EventReader, Events};
 use bevy_core::FloatOrd;
 use bevy_ecs::prelude::*;
@@ -7,6 +7,8 @@ use bevy_math::Vec2;
 use bevy_transform::components::GlobalTransform;
 use bevy_window::CursorMoved;
 
+/// Describes what the cursor (or the first touch) is doing with a UI node. Only the topmost node under the cursor,
+/// plus any nodes above it with [FocusPolicy::Pass], are hovered or clicked.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Interaction {
     Clicked,
@@ -44,13 +46,20 @@ pub fn ui_focus_system(
     mouse_button_input: Res<Input<MouseButton>>,
     cursor_moved_events: Res<Events<CursorMoved>>,
     touches_input: Res<Touches>,
-    mut node_query: Query<(
-        Entity,
-        &Node,
-        &GlobalTransform,
-        Option<&mut Interaction>,
-        Option<&FocusPolicy>,
-    )>,
+    // cursor images are drawn under the cursor, so they would cover everything else
+    mut node_query: Query<
+        Without<
+            CursorImage,
+            (
+                Entity,
+                &Node,
+                &GlobalTransform,
+                Option<&mut Interaction>,
+                Option<&FocusPolicy>,
+                Option<&CalculatedClip>,
+            ),
+        >,
+    >,
 ) {
     if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
         state.cursor_position = cursor_moved.position;
@@ -60,7 +69,8 @@ pub fn ui_focus_system(
     }
 
     if mouse_button_input.just_released(MouseButton::Left) || touches_input.just_released(0) {
-        for (_entity, _node, _global_transform, interaction, _focus_policy) in node_query.iter_mut()
+        for (_entity, _node, _global_transform, interaction, _focus_policy, _clip) in
+            node_query.iter_mut()
         {
             if let Some(mut interaction) = interaction {
                 if *interaction == Interaction::Clicked {
@@ -71,19 +81,24 @@ pub fn ui_focus_system(
     }
 
     let mouse_clicked =
-        mouse_button_input.just_pressed(MouseButton::Left) || touches_input.just_released(0);
+        mouse_button_input.just_pressed(MouseButton::Left) || touches_input.just_pressed(0);
     let mut hovered_entity = None;
 
     {
         let mut moused_over_z_sorted_nodes = node_query
             .iter_mut()
             .filter_map(
-                |(entity, node, global_transform, interaction, focus_policy)| {
+                |(entity, node, global_transform, interaction, focus_policy, clip)| {
                     let position = global_transform.translation;
                     let ui_position = position.truncate();
                     let extents = node.size / 2.0;
-                    let min = ui_position - extents;
-                    let max = ui_position + extents;
+                    let mut min = ui_position - extents;
+                    let mut max = ui_position + extents;
+                    // only the visible part of a clipped node can be interacted with
+                    if let Some(clip) = clip {
+                        min = min.max(clip.clip.min);
+                        max = max.min(clip.clip.max);
+                    }
                     // if the current cursor position is within the bounds of the node, consider it for clicking
                     if (min.x()..max.x()).contains(&state.cursor_position.x())
                         && (min.y()..max.y()).contains(&state.cursor_position.y())
@@ -102,7 +117,18 @@ pub fn ui_focus_system(
             .collect::<Vec<_>>();
 
         moused_over_z_sorted_nodes.sort_by_key(|(_, _, _, z)| -*z);
+        let mut blocked = false;
         for (entity, focus_policy, interaction, _) in moused_over_z_sorted_nodes {
+            // nodes below a blocking node are covered, so they can't be hovered or clicked
+            if blocked {
+                if let Some(mut interaction) = interaction {
+                    if *interaction == Interaction::Hovered {
+                        *interaction = Interaction::None;
+                    }
+                }
+                continue;
+            }
+
             if let Some(mut interaction) = interaction {
                 if mouse_clicked {
                     // only consider nodes with ClickState "clickable"
@@ -118,7 +144,7 @@ pub fn ui_focus_system(
 
             match focus_policy.cloned().unwrap_or(FocusPolicy::Block) {
                 FocusPolicy::Block => {
-                    break;
+                    blocked = true;
                 }
                 FocusPolicy::Pass => { /* allow the next node to be hovered/clicked */ }
             }
@@ -1,33 +1,48 @@
 mod anchors;
+mod cursor;
+mod diagnostics_overlay;
 pub mod entity;
 mod flex;
 mod focus;
 mod margins;
+mod navigation;
 mod node;
+mod overlay_font;
 mod render;
+mod scroll;
+mod shader_error_overlay;
 pub mod update;
 pub mod widget;
+mod world_space;
 
 pub use anchors::*;
+pub use cursor::*;
+pub use diagnostics_overlay::*;
 pub use flex::*;
 pub use focus::*;
 pub use margins::*;
+pub use navigation::*;
 pub use node::*;
+pub use overlay_font::*;
 pub use render::*;
+pub use scroll::*;
+pub use shader_error_overlay::*;
+pub use world_space::*;
 
 pub mod prelude {
     pub use crate::{
         entity::*,
         node::*,
-        widget::{Button, Text},
-        Anchors, Interaction, Margins,
+        widget::{Button, NinePatch, Text},
+        Anchors, CursorImage, Focusable, Interaction, Margins, NodeActivated, OffScreen,
+        ScrollPosition, UiFocus, WorldSpaceNode,
     };
 }
 
 use bevy_app::prelude::*;
 use bevy_ecs::IntoQuerySystem;
-use bevy_render::render_graph::RenderGraph;
-use update::ui_z_system;
+use bevy_render::{render_graph::RenderGraph, shader};
+use update::{ui_clip_system, ui_z_system};
 
 #[derive(Default)]
 pub struct UiPlugin;
@@ -39,14 +54,25 @@ pub mod stage {
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<FlexSurface>()
+            .init_resource::<UiFocus>()
+            .add_event::<NodeActivated>()
             .add_stage_before(bevy_app::stage::POST_UPDATE, stage::UI)
             .add_system_to_stage(bevy_app::stage::PRE_UPDATE, ui_focus_system.system())
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, ui_navigation_system.system())
             // add these stages to front because these must run before transform update systems
             .add_system_to_stage(stage::UI, widget::text_system.system())
             .add_system_to_stage(stage::UI, widget::image_node_system.system())
             .add_system_to_stage(stage::UI, ui_z_system.system())
+            .add_system_to_stage(stage::UI, ui_scroll_system.system())
             .add_system_to_stage(stage::UI, flex_node_system.system())
-            .add_system_to_stage(bevy_render::stage::DRAW, widget::draw_text_system.system());
+            .add_system_to_stage(stage::UI, world_space_node_system.system())
+            .add_system_to_stage(stage::UI, cursor_image_system.system())
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                shader::shader_defs_system::<widget::Image>.system(),
+            )
+            .add_system_to_stage(bevy_render::stage::DRAW, widget::draw_text_system.system())
+            .add_system_to_stage(bevy_render::stage::DRAW, ui_clip_system.system());
 
         let resources = app.resources();
         let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
@@ -0,0 +1,241 @@
+use crate::Node;
+use bevy_app::Events;
+use bevy_core::FloatOrd;
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    gamepad::{GamepadButton, GamepadButtonType},
+    keyboard::KeyCode,
+    Input,
+};
+use bevy_math::Vec2;
+use bevy_transform::components::GlobalTransform;
+
+/// Marks a UI node that can receive focus from the keyboard or a gamepad.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Focusable;
+
+/// The [Focusable] node that currently has keyboard and gamepad focus. Focus moves with the arrow keys or the d-pad to
+/// the nearest node in that direction, and with tab / shift+tab through the nodes in reading order.
+#[derive(Debug, Default)]
+pub struct UiFocus {
+    focused: Option<Entity>,
+}
+
+impl UiFocus {
+    pub fn focused(&self) -> Option<Entity> {
+        self.focused
+    }
+
+    pub fn set_focused(&mut self, focused: Option<Entity>) {
+        self.focused = focused;
+    }
+}
+
+/// Sent when the focused node is activated with enter, space or the south gamepad button.
+#[derive(Debug, Clone)]
+pub struct NodeActivated {
+    pub entity: Entity,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NavigationDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl NavigationDirection {
+    fn as_vec2(self) -> Vec2 {
+        match self {
+            NavigationDirection::Up => Vec2::unit_y(),
+            NavigationDirection::Down => -Vec2::unit_y(),
+            NavigationDirection::Left => -Vec2::unit_x(),
+            NavigationDirection::Right => Vec2::unit_x(),
+        }
+    }
+}
+
+enum NavigationRequest {
+    Direction(NavigationDirection),
+    Next,
+    Previous,
+}
+
+/// Finds the node nearest to `from` in the given direction. Nodes that are off to the side count as further away than
+/// nodes straight ahead.
+pub fn find_in_direction(
+    from: Vec2,
+    direction: NavigationDirection,
+    candidates: impl IntoIterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    let direction = direction.as_vec2();
+    candidates
+        .into_iter()
+        .filter_map(|(entity, position)| {
+            let offset = position - from;
+            let along = offset.dot(direction);
+            if along <= 0.0 {
+                return None;
+            }
+            let across = (offset - direction * along).length();
+            Some((entity, FloatOrd(along + 2.0 * across)))
+        })
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(entity, _)| entity)
+}
+
+/// Sorts nodes in reading order: top to bottom, then left to right.
+fn sort_reading_order(nodes: &mut Vec<(Entity, Vec2)>) {
+    nodes.sort_by_key(|(_, position)| (FloatOrd(-position.y()), FloatOrd(position.x())));
+}
+
+fn navigation_request(
+    keyboard_input: &Input<KeyCode>,
+    gamepad_buttons: &Input<GamepadButton>,
+) -> Option<NavigationRequest> {
+    let gamepad_pressed = |button_type: GamepadButtonType| {
+        gamepad_buttons
+            .get_just_pressed()
+            .any(|button| button.1 == button_type)
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Tab) {
+        if keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift) {
+            Some(NavigationRequest::Previous)
+        } else {
+            Some(NavigationRequest::Next)
+        }
+    } else if keyboard_input.just_pressed(KeyCode::Up) || gamepad_pressed(GamepadButtonType::DPadUp)
+    {
+        Some(NavigationRequest::Direction(NavigationDirection::Up))
+    } else if keyboard_input.just_pressed(KeyCode::Down)
+        || gamepad_pressed(GamepadButtonType::DPadDown)
+    {
+        Some(NavigationRequest::Direction(NavigationDirection::Down))
+    } else if keyboard_input.just_pressed(KeyCode::Left)
+        || gamepad_pressed(GamepadButtonType::DPadLeft)
+    {
+        Some(NavigationRequest::Direction(NavigationDirection::Left))
+    } else if keyboard_input.just_pressed(KeyCode::Right)
+        || gamepad_pressed(GamepadButtonType::DPadRight)
+    {
+        Some(NavigationRequest::Direction(NavigationDirection::Right))
+    } else {
+        None
+    }
+}
+
+pub fn ui_navigation_system(
+    mut focus: ResMut<UiFocus>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut activated_events: ResMut<Events<NodeActivated>>,
+    node_query: Query<With<Focusable, (Entity, &Node, &GlobalTransform)>>,
+) {
+    let mut nodes = node_query
+        .iter()
+        .map(|(entity, _node, global_transform)| (entity, global_transform.translation.truncate()))
+        .collect::<Vec<_>>();
+
+    // drop focus from nodes that were despawned or are no longer focusable
+    let focused = focus
+        .focused
+        .and_then(|focused| nodes.iter().find(|(entity, _)| *entity == focused).cloned());
+    if focused.is_none() && focus.focused.is_some() {
+        focus.focused = None;
+    }
+
+    if let Some((entity, _)) = focused {
+        if keyboard_input.just_pressed(KeyCode::Return)
+            || keyboard_input.just_pressed(KeyCode::NumpadEnter)
+            || keyboard_input.just_pressed(KeyCode::Space)
+            || gamepad_buttons
+                .get_just_pressed()
+                .any(|button| button.1 == GamepadButtonType::South)
+        {
+            activated_events.send(NodeActivated { entity });
+        }
+    }
+
+    let request = if let Some(request) = navigation_request(&keyboard_input, &gamepad_buttons) {
+        request
+    } else {
+        return;
+    };
+
+    sort_reading_order(&mut nodes);
+    let new_focused = match (focused, request) {
+        // without focus, any navigation focuses the first node
+        (None, _) => nodes.first().map(|(entity, _)| *entity),
+        (Some((_, position)), NavigationRequest::Direction(direction)) => {
+            let candidates = nodes
+                .iter()
+                .filter(|(entity, _)| Some(*entity) != focus.focused)
+                .cloned();
+            find_in_direction(position, direction, candidates)
+        }
+        (Some((focused, _)), NavigationRequest::Next) => {
+            let index = nodes
+                .iter()
+                .position(|(entity, _)| *entity == focused)
+                .unwrap();
+            Some(nodes[(index + 1) % nodes.len()].0)
+        }
+        (Some((focused, _)), NavigationRequest::Previous) => {
+            let index = nodes
+                .iter()
+                .position(|(entity, _)| *entity == focused)
+                .unwrap();
+            Some(nodes[(index + nodes.len() - 1) % nodes.len()].0)
+        }
+    };
+
+    if let Some(new_focused) = new_focused {
+        focus.focused = Some(new_focused);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_in_direction, sort_reading_order, NavigationDirection};
+    use bevy_ecs::Entity;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn navigation_geometry() {
+        let a = Entity::new(0);
+        let b = Entity::new(1);
+        let c = Entity::new(2);
+        let d = Entity::new(3);
+        // a b
+        // c   d
+        let nodes = vec![
+            (c, Vec2::new(0.0, 0.0)),
+            (d, Vec2::new(250.0, 0.0)),
+            (a, Vec2::new(0.0, 100.0)),
+            (b, Vec2::new(100.0, 100.0)),
+        ];
+
+        let from_a = nodes.iter().filter(|(entity, _)| *entity != a).cloned();
+        assert_eq!(
+            find_in_direction(Vec2::new(0.0, 100.0), NavigationDirection::Right, from_a),
+            Some(b)
+        );
+        let from_b = nodes.iter().filter(|(entity, _)| *entity != b).cloned();
+        assert_eq!(
+            find_in_direction(Vec2::new(100.0, 100.0), NavigationDirection::Up, from_b),
+            None
+        );
+        let from_b = nodes.iter().filter(|(entity, _)| *entity != b).cloned();
+        assert_eq!(
+            find_in_direction(Vec2::new(100.0, 100.0), NavigationDirection::Down, from_b),
+            Some(c)
+        );
+
+        let mut sorted = nodes.clone();
+        sort_reading_order(&mut sorted);
+        let order = sorted.iter().map(|(entity, _)| *entity).collect::<Vec<_>>();
+        assert_eq!(order, vec![a, b, c, d]);
+    }
+}
@@ -49,6 +49,31 @@ pub struct CalculatedSize {
     pub size: Size,
 }
 
+/// The area a node is clipped to by its ancestors with [Overflow::Hidden] or [Overflow::Scroll], in logical pixels
+/// relative to the bottom left of the window. This is managed by the UI systems: nodes that aren't clipped don't have
+/// it.
+#[derive(Copy, Clone, Debug)]
+pub struct CalculatedClip {
+    pub clip: bevy_sprite::Rect,
+}
+
+/// Overrides the draw order of a node. Nodes are otherwise drawn (and hit tested) in hierarchy order: parents below
+/// their children, and later siblings above earlier ones.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ZIndex {
+    /// Orders the node relative to its siblings. Siblings with the same index keep their hierarchy order.
+    Local(i32),
+    /// Orders the node and its descendants relative to the root nodes and every other node with a global index, as if
+    /// it were a root node.
+    Global(i32),
+}
+
+impl Default for ZIndex {
+    fn default() -> Self {
+        ZIndex::Local(0)
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Style {
     pub display: Display,
@@ -60,6 +85,7 @@ pub struct Style {
     pub align_self: AlignSelf,
     pub align_content: AlignContent,
     pub justify_content: JustifyContent,
+    pub overflow: Overflow,
     pub position: Rect<Val>,
     pub margin: Rect<Val>,
     pub padding: Rect<Val>,
@@ -85,6 +111,7 @@ impl Default for Style {
             align_self: Default::default(),
             align_content: Default::default(),
             justify_content: Default::default(),
+            overflow: Default::default(),
             position: Default::default(),
             margin: Default::default(),
             padding: Default::default(),
@@ -202,19 +229,22 @@ impl Default for JustifyContent {
     }
 }
 
-// TODO: add support for overflow settings
-// #[derive(Copy, Clone, PartialEq, Debug)]
-// pub enum Overflow {
-//     Visible,
-//     Hidden,
-//     Scroll,
-// }
+/// Whether a node's children are drawn outside of its bounds
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Overflow {
+    Visible,
+    /// Children are clipped to the node's bounds. They can still be scrolled by setting a
+    /// [ScrollPosition](crate::ScrollPosition) on the node.
+    Hidden,
+    /// Children are clipped to the node's bounds and can be scrolled with the mouse wheel or by dragging
+    Scroll,
+}
 
-// impl Default for Overflow {
-//     fn default() -> Overflow {
-//         Overflow::Visible
-//     }
-// }
+impl Default for Overflow {
+    fn default() -> Overflow {
+        Overflow::Visible
+    }
+}
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum PositionType {
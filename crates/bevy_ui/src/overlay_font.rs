@@ -0,0 +1,53 @@
+use bevy_app::AppBuilder;
+use bevy_asset::{Assets, Handle};
+use bevy_text::Font;
+use bevy_type_registry::TypeUuid;
+
+/// Fira Mono Medium, the default font of the [DiagnosticsOverlayPlugin](crate::DiagnosticsOverlayPlugin) and the
+/// [ShaderErrorOverlayPlugin](crate::ShaderErrorOverlayPlugin). It's embedded in the binary, so the overlays work in
+/// apps that don't ship any fonts.
+pub const OVERLAY_FONT_HANDLE: Handle<Font> =
+    Handle::weak_from_u64(Font::TYPE_UUID, 10390870532063823941);
+
+/// Adds the embedded overlay font to the font assets, unless another overlay already did
+pub(crate) fn add_overlay_font(app: &mut AppBuilder) {
+    let mut fonts = app.resources().get_mut::<Assets<Font>>().unwrap();
+    if fonts.get(&OVERLAY_FONT_HANDLE).is_none() {
+        let font = Font::try_from_bytes(include_bytes!("fonts/FiraMono-Medium.ttf").to_vec())
+            .expect("the embedded overlay font is valid");
+        fonts.set_untracked(OVERLAY_FONT_HANDLE, font);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OVERLAY_FONT_HANDLE;
+    use crate::{DiagnosticsOverlayPlugin, ShaderErrorOverlayPlugin};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_text::Font;
+    use bevy_type_registry::TypeRegistry;
+
+    #[test]
+    fn overlays_use_the_embedded_font_by_default() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Font>()
+            .add_plugin(DiagnosticsOverlayPlugin::default())
+            .add_plugin(ShaderErrorOverlayPlugin::default());
+
+        assert_eq!(
+            DiagnosticsOverlayPlugin::default().font,
+            OVERLAY_FONT_HANDLE
+        );
+        assert_eq!(
+            ShaderErrorOverlayPlugin::default().font,
+            OVERLAY_FONT_HANDLE
+        );
+        let fonts = app.resources().get::<Assets<Font>>().unwrap();
+        assert!(fonts.get(&OVERLAY_FONT_HANDLE).is_some());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Node;
+use crate::{widget::Image, Node};
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::Resources;
 use bevy_render::{
@@ -10,8 +10,8 @@ use bevy_render::{
     pipeline::*,
     prelude::Msaa,
     render_graph::{
-        base, CameraNode, PassNode, RenderGraph, RenderResourcesNode, WindowSwapChainNode,
-        WindowTextureNode,
+        base, CameraNode, PassNode, RenderGraph, RenderResourcesNode, TransientTextureNode,
+        WindowSwapChainNode,
     },
     shader::{Shader, ShaderStage, ShaderStages},
     texture::TextureFormat,
@@ -72,6 +72,7 @@ pub fn build_ui_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
 pub mod node {
     pub const UI_CAMERA: &str = "ui_camera";
     pub const NODE: &str = "node";
+    pub const IMAGE: &str = "image";
     pub const UI_PASS: &str = "ui_pass";
 }
 
@@ -127,7 +128,7 @@ impl UiRenderGraphBuilder for RenderGraph {
 
         self.add_slot_edge(
             base::node::MAIN_DEPTH_TEXTURE,
-            WindowTextureNode::OUT_TEXTURE,
+            TransientTextureNode::OUT_TEXTURE,
             node::UI_PASS,
             "depth",
         )
@@ -136,7 +137,7 @@ impl UiRenderGraphBuilder for RenderGraph {
         if msaa.samples > 1 {
             self.add_slot_edge(
                 base::node::MAIN_SAMPLED_COLOR_ATTACHMENT,
-                WindowSwapChainNode::OUT_TEXTURE,
+                TransientTextureNode::OUT_TEXTURE,
                 node::UI_PASS,
                 "color_attachment",
             )
@@ -146,12 +147,29 @@ impl UiRenderGraphBuilder for RenderGraph {
         // ensure ui pass runs after main pass
         self.add_node_edge(base::node::MAIN_PASS, node::UI_PASS)
             .unwrap();
+        // and after the 2d pass, when it renders separately from the scaled 3d scene
+        if self.get_node_id(base::node::MAIN_PASS_2D).is_ok() {
+            self.add_node_edge(base::node::MAIN_PASS_2D, node::UI_PASS)
+                .unwrap();
+        }
+        // and after the transparent pass draws over it
+        if self.get_node_id(base::node::TRANSPARENT_PASS).is_ok() {
+            self.add_node_edge(base::node::TRANSPARENT_PASS, node::UI_PASS)
+                .unwrap();
+        }
+        // and after the main pass is drawn to the window, when it renders at a different resolution
+        if self.get_node_id(base::node::COMPOSITE).is_ok() {
+            self.add_node_edge(base::node::COMPOSITE, node::UI_PASS)
+                .unwrap();
+        }
 
         // setup ui camera
         self.add_system_node(node::UI_CAMERA, CameraNode::new(camera::UI_CAMERA));
         self.add_node_edge(node::UI_CAMERA, node::UI_PASS).unwrap();
         self.add_system_node(node::NODE, RenderResourcesNode::<Node>::new(true));
         self.add_node_edge(node::NODE, node::UI_PASS).unwrap();
+        self.add_system_node(node::IMAGE, RenderResourcesNode::<Image>::new(true));
+        self.add_node_edge(node::IMAGE, node::UI_PASS).unwrap();
         let mut active_cameras = resources.get_mut::<ActiveCameras>().unwrap();
         active_cameras.add(camera::UI_CAMERA);
         self
@@ -13,12 +13,45 @@ layout(set = 2, binding = 1) uniform texture2D ColorMaterial_texture;
 layout(set = 2, binding = 2) uniform sampler ColorMaterial_texture_sampler;
 # endif
 
+# if defined(IMAGE_NINE_PATCH) && defined(COLORMATERIAL_TEXTURE)
+layout(set = 1, binding = 1) uniform Node_size {
+    vec2 NodeSize;
+};
+layout(set = 1, binding = 2) uniform NinePatch {
+    float Left;
+    float Right;
+    float Top;
+    float Bottom;
+};
+
+// maps a position along one axis of the node to a position along the same axis of the texture
+float slice(float position, float node_size, float texture_size, float start, float end) {
+    if (position < start) {
+        return position;
+    }
+    if (position > node_size - end) {
+        return texture_size - (node_size - position);
+    }
+    float node_middle = max(node_size - start - end, 0.0001);
+    return start + (position - start) / node_middle * (texture_size - start - end);
+}
+# endif
+
 void main() {
     vec4 color = Color;
 # ifdef COLORMATERIAL_TEXTURE
+    vec2 uv = v_Uv;
+# ifdef IMAGE_NINE_PATCH
+    vec2 texture_size = vec2(textureSize(sampler2D(ColorMaterial_texture, ColorMaterial_texture_sampler), 0));
+    vec2 position = v_Uv * NodeSize;
+    uv = vec2(
+        slice(position.x, NodeSize.x, texture_size.x, Left, Right),
+        slice(position.y, NodeSize.y, texture_size.y, Top, Bottom)
+    ) / texture_size;
+# endif
     color *= texture(
         sampler2D(ColorMaterial_texture, ColorMaterial_texture_sampler),
-        v_Uv);
+        uv);
 # endif
     o_Target = color;
 }
@@ -0,0 +1,182 @@
+use crate::{CalculatedClip, FlexSurface, Node, Overflow, Style};
+use bevy_app::{EventReader, Events};
+use bevy_core::FloatOrd;
+use bevy_ecs::prelude::*;
+use bevy_input::{mouse::MouseWheel, touch::Touches};
+use bevy_math::Vec2;
+use bevy_transform::components::{Children, GlobalTransform};
+use bevy_window::CursorMoved;
+
+/// How far the children of a node are scrolled, in logical pixels. Nodes with [Overflow::Scroll] get one and are
+/// scrolled by the mouse wheel and by dragging the first touch, clamped so the children can't be scrolled past their
+/// edges. It can also be set on other nodes to scroll them from code.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ScrollPosition {
+    pub offset: Vec2,
+}
+
+#[derive(Default)]
+pub struct ScrollState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    mouse_wheel_event_reader: EventReader<MouseWheel>,
+    cursor_position: Vec2,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_scroll_system(
+    mut commands: Commands,
+    mut state: Local<ScrollState>,
+    flex_surface: Res<FlexSurface>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    mouse_wheel_events: Res<Events<MouseWheel>>,
+    touches_input: Res<Touches>,
+    node_query: Query<(&Node, &GlobalTransform, Option<&CalculatedClip>)>,
+    children_query: Query<&Children>,
+    new_scroll_query: Query<With<Node, Without<ScrollPosition, (Entity, &Style)>>>,
+    mut scroll_query: Query<With<Node, (Entity, &Style, &mut ScrollPosition)>>,
+) {
+    for (entity, style) in new_scroll_query.iter() {
+        if style.overflow == Overflow::Scroll {
+            commands.insert_one(entity, ScrollPosition::default());
+        }
+    }
+
+    if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        state.cursor_position = cursor_moved.position;
+    }
+
+    let mut delta = Vec2::zero();
+    for event in state.mouse_wheel_event_reader.iter(&mouse_wheel_events) {
+        delta += event.pixels(MouseWheel::DEFAULT_PIXELS_PER_LINE);
+    }
+    if let Some(touch) = touches_input.get_pressed(0) {
+        state.cursor_position = touch.position;
+        // dragging moves the children along with the touch
+        delta -= touch.delta();
+    }
+
+    if delta == Vec2::zero() {
+        return;
+    }
+
+    // only the topmost scrollable node under the cursor is scrolled
+    let cursor_position = state.cursor_position;
+    let target = scroll_query
+        .iter_mut()
+        .filter(|(_, style, _)| style.overflow == Overflow::Scroll)
+        .filter_map(|(entity, _, _)| {
+            let (node, global_transform, clip) = node_query.get(entity).ok()?;
+            let position = global_transform.translation;
+            let extents = node.size / 2.0;
+            let mut min = position.truncate() - extents;
+            let mut max = position.truncate() + extents;
+            if let Some(clip) = clip {
+                min = min.max(clip.clip.min);
+                max = max.min(clip.clip.max);
+            }
+            if (min.x()..max.x()).contains(&cursor_position.x())
+                && (min.y()..max.y()).contains(&cursor_position.y())
+            {
+                Some((entity, FloatOrd(position.z())))
+            } else {
+                None
+            }
+        })
+        .max_by_key(|(_, z)| *z)
+        .map(|(entity, _)| entity);
+
+    let entity = if let Some(entity) = target {
+        entity
+    } else {
+        return;
+    };
+
+    let (node, _, _) = node_query.get(entity).unwrap();
+    let (min, max) = scroll_bounds(
+        &flex_surface,
+        &node_query,
+        &children_query,
+        entity,
+        node.size,
+    );
+    let mut scroll_position = scroll_query
+        .get_component_mut::<ScrollPosition>(entity)
+        .unwrap();
+    let offset = (scroll_position.offset + delta).max(min).min(max);
+    if scroll_position.offset != offset {
+        scroll_position.offset = offset;
+    }
+}
+
+/// Returns the range the scroll offset of a node can move in, so that its children always cover as much of the node
+/// as they can.
+fn scroll_bounds(
+    flex_surface: &FlexSurface,
+    node_query: &Query<(&Node, &GlobalTransform, Option<&CalculatedClip>)>,
+    children_query: &Query<&Children>,
+    entity: Entity,
+    size: Vec2,
+) -> (Vec2, Vec2) {
+    let mut content_min = Vec2::zero();
+    let mut content_max = size;
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter() {
+            if node_query.get(*child).is_err() {
+                continue;
+            }
+            if let Ok(layout) = flex_surface.get_layout(*child) {
+                let min = Vec2::new(layout.location.x, layout.location.y);
+                let max = min + Vec2::new(layout.size.width, layout.size.height);
+                content_min = content_min.min(min);
+                content_max = content_max.max(max);
+            }
+        }
+    }
+
+    (content_min, content_max - size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ui_scroll_system, ScrollPosition};
+    use crate::{FlexSurface, Node, Overflow, Style};
+    use bevy_app::Events;
+    use bevy_ecs::{IntoQuerySystem, Resources, Schedule, World};
+    use bevy_input::{mouse::MouseWheel, touch::Touches};
+    use bevy_transform::prelude::GlobalTransform;
+    use bevy_window::CursorMoved;
+
+    #[test]
+    fn scroll_overflow_adds_scroll_position() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(FlexSurface::default());
+        resources.insert(Events::<CursorMoved>::default());
+        resources.insert(Events::<MouseWheel>::default());
+        resources.insert(Touches::default());
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", ui_scroll_system.system());
+
+        let mut spawn_node = |overflow| {
+            world.spawn((
+                Node::default(),
+                GlobalTransform::default(),
+                Style {
+                    overflow,
+                    ..Default::default()
+                },
+            ))
+        };
+        let scroll = spawn_node(Overflow::Scroll);
+        let hidden = spawn_node(Overflow::Hidden);
+
+        schedule.run(&mut world, &mut resources);
+
+        assert_eq!(
+            *world.get::<ScrollPosition>(scroll).unwrap(),
+            ScrollPosition::default()
+        );
+        assert!(world.get::<ScrollPosition>(hidden).is_err());
+    }
+}
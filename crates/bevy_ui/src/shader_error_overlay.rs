@@ -0,0 +1,128 @@
+use crate::{
+    entity::{NodeComponents, TextComponents},
+    overlay_font::add_overlay_font,
+    widget::Text,
+    PositionType, Style, Val, OVERLAY_FONT_HANDLE,
+};
+use bevy_app::prelude::*;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Commands, IntoQuerySystem, Query, Res, ResMut, With};
+use bevy_math::Rect;
+use bevy_render::{color::Color, draw::Visible, pipeline::PipelineCompiler};
+use bevy_sprite::ColorMaterial;
+use bevy_text::{Font, TextAlignment, TextStyle};
+use bevy_transform::prelude::BuildChildren;
+
+/// Shows the errors of shaders and pipelines that failed to compile in the top left corner of the window, while there
+/// are any. Combined with [AssetServer::watch_for_changes](bevy_asset::AssetServer::watch_for_changes), this shows
+/// mistakes in an edited shader without looking at the log, and hides them once the shader is fixed. The overlay is
+/// drawn by the UI camera, so the app needs to spawn one.
+#[derive(Debug, Clone)]
+pub struct ShaderErrorOverlayPlugin {
+    /// The font of the overlay text. Defaults to the embedded [OVERLAY_FONT_HANDLE].
+    pub font: Handle<Font>,
+}
+
+impl Default for ShaderErrorOverlayPlugin {
+    fn default() -> Self {
+        ShaderErrorOverlayPlugin {
+            font: OVERLAY_FONT_HANDLE,
+        }
+    }
+}
+
+/// Marks the root node of the shader error overlay
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ShaderErrorOverlay;
+
+#[derive(Debug, Default, Copy, Clone)]
+struct ShaderErrorOverlayText;
+
+#[derive(Debug)]
+struct ShaderErrorOverlayFont(Handle<Font>);
+
+impl Plugin for ShaderErrorOverlayPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        add_overlay_font(app);
+        app.add_resource(ShaderErrorOverlayFont(self.font.clone()))
+            .add_startup_system(Self::setup_system.system())
+            .add_system_to_stage(stage::UPDATE, Self::update_system.system());
+    }
+}
+
+impl ShaderErrorOverlayPlugin {
+    fn setup_system(
+        mut commands: Commands,
+        font: Res<ShaderErrorOverlayFont>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+    ) {
+        let transparent = Visible {
+            is_transparent: true,
+            ..Default::default()
+        };
+        commands
+            .spawn(NodeComponents {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        left: Val::Px(5.0),
+                        top: Val::Px(5.0),
+                        ..Default::default()
+                    },
+                    padding: Rect::all(Val::Px(5.0)),
+                    ..Default::default()
+                },
+                material: materials.add(Color::rgba(0.3, 0.0, 0.0, 0.85).into()),
+                // hidden until there is an error, which also hides the text
+                visible: Visible {
+                    is_visible: false,
+                    ..transparent.clone()
+                },
+                ..Default::default()
+            })
+            .with(ShaderErrorOverlay)
+            .with_children(|parent| {
+                parent
+                    .spawn(TextComponents {
+                        text: Text::with_section(
+                            "",
+                            font.0.clone(),
+                            TextStyle {
+                                font_size: 14.0,
+                                color: Color::WHITE,
+                            },
+                            TextAlignment::default(),
+                        ),
+                        visible: transparent,
+                        ..Default::default()
+                    })
+                    .with(ShaderErrorOverlayText);
+            });
+    }
+
+    fn update_system(
+        pipeline_compiler: Res<PipelineCompiler>,
+        mut overlay_query: Query<With<ShaderErrorOverlay, &mut Visible>>,
+        mut text_query: Query<With<ShaderErrorOverlayText, &mut Text>>,
+    ) {
+        let errors = pipeline_compiler
+            .iter_errors()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        for mut visible in overlay_query.iter_mut() {
+            if visible.is_visible == errors.is_empty() {
+                visible.is_visible = !errors.is_empty();
+            }
+        }
+        if errors.is_empty() {
+            return;
+        }
+
+        let overlay_text = errors.join("\n");
+        for mut text in text_query.iter_mut() {
+            if text.sections[0].value != overlay_text {
+                text.sections[0].value = overlay_text.clone();
+            }
+        }
+    }
+}
@@ -1,49 +1,315 @@
 use super::Node;
-use bevy_ecs::{Entity, Query, With, Without};
-use bevy_transform::{
-    hierarchy,
-    prelude::{Children, Parent, Transform},
-};
+use crate::{CalculatedClip, Overflow, Style, ZIndex};
+use bevy_ecs::{Commands, Entity, Query, Res, With, Without};
+use bevy_render::draw::{Draw, RenderCommand};
+use bevy_sprite::Rect;
+use bevy_transform::prelude::{Children, GlobalTransform, Parent, Transform};
+use bevy_utils::HashMap;
+use bevy_window::{Window, Windows};
 
 pub const UI_Z_STEP: f32 = 0.001;
 
+/// Sets the z of every node so nodes are drawn and hit tested in hierarchy order, adjusted by their [ZIndex].
 pub fn ui_z_system(
     root_node_query: Query<With<Node, Without<Parent, Entity>>>,
-    mut node_query: Query<(Entity, &Node, &mut Transform)>,
+    mut node_query: Query<With<Node, (Option<&ZIndex>, Option<&Parent>, &mut Transform)>>,
     children_query: Query<&Children>,
 ) {
-    let mut current_global_z = 0.0;
+    // sort the root nodes so their order doesn't depend on the archetypes they're stored in
+    let mut root_nodes = root_node_query.iter().collect::<Vec<_>>();
+    root_nodes.sort();
 
-    for entity in root_node_query.iter() {
-        if let Some(result) = hierarchy::run_on_hierarchy(
+    let mut global_context = Vec::new();
+    for entity in root_nodes {
+        let z_index = match node_query.get_component::<ZIndex>(entity) {
+            Ok(ZIndex::Local(z_index)) | Ok(ZIndex::Global(z_index)) => *z_index,
+            Err(_) => 0,
+        };
+        push_global_entry(
+            &node_query,
             &children_query,
-            &mut node_query,
             entity,
-            Some(current_global_z),
-            Some(current_global_z),
-            &mut update_node_entity,
-        ) {
-            current_global_z = result;
+            z_index,
+            &mut global_context,
+        );
+    }
+    global_context.sort_by_key(|entry| entry.z_index);
+
+    let mut draw_order = Vec::new();
+    flatten_stacking_context(global_context, &mut draw_order);
+
+    let global_z = draw_order
+        .iter()
+        .enumerate()
+        .map(|(i, entity)| (*entity, (i + 1) as f32 * UI_Z_STEP))
+        .collect::<HashMap<_, _>>();
+    for entity in draw_order {
+        let parent_global_z = node_query
+            .get_component::<Parent>(entity)
+            .ok()
+            .and_then(|parent| global_z.get(&parent.0))
+            .cloned()
+            .unwrap_or(0.0);
+        let z = global_z[&entity] - parent_global_z;
+        let mut transform = node_query.get_component_mut::<Transform>(entity).unwrap();
+        if transform.translation.z() != z {
+            transform.translation.set_z(z);
+        }
+    }
+}
+
+struct StackingEntry {
+    entity: Entity,
+    z_index: i32,
+    context: Vec<StackingEntry>,
+}
+
+/// Returns the children of the given node sorted by their [ZIndex::Local], moving descendants with a
+/// [ZIndex::Global] to the global context.
+fn build_stacking_context(
+    node_query: &Query<With<Node, (Option<&ZIndex>, Option<&Parent>, &mut Transform)>>,
+    children_query: &Query<&Children>,
+    entity: Entity,
+    global_context: &mut Vec<StackingEntry>,
+) -> Vec<StackingEntry> {
+    let mut context = Vec::new();
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter().cloned() {
+            if node_query.get_component::<Transform>(child).is_err() {
+                continue;
+            }
+            let z_index = match node_query.get_component::<ZIndex>(child).ok().cloned() {
+                Some(ZIndex::Global(z_index)) => {
+                    push_global_entry(node_query, children_query, child, z_index, global_context);
+                    continue;
+                }
+                Some(ZIndex::Local(z_index)) => z_index,
+                None => 0,
+            };
+            context.push(StackingEntry {
+                entity: child,
+                z_index,
+                context: build_stacking_context(node_query, children_query, child, global_context),
+            });
         }
     }
+    // the sort is stable, so siblings with the same index keep their hierarchy order
+    context.sort_by_key(|entry| entry.z_index);
+    context
 }
 
-fn update_node_entity(
-    node_query: &mut Query<(Entity, &Node, &mut Transform)>,
+/// Adds a node to the global context before its descendants are visited, so global entries with the same index keep
+/// their hierarchy order.
+fn push_global_entry(
+    node_query: &Query<With<Node, (Option<&ZIndex>, Option<&Parent>, &mut Transform)>>,
+    children_query: &Query<&Children>,
     entity: Entity,
-    parent_result: Option<f32>,
-    previous_result: Option<f32>,
-) -> Option<f32> {
-    let mut z = UI_Z_STEP;
-    let parent_global_z = parent_result.unwrap();
-    if let Some(previous_global_z) = previous_result {
-        z += previous_global_z - parent_global_z;
+    z_index: i32,
+    global_context: &mut Vec<StackingEntry>,
+) {
+    let index = global_context.len();
+    global_context.push(StackingEntry {
+        entity,
+        z_index,
+        context: Vec::new(),
+    });
+    let context = build_stacking_context(node_query, children_query, entity, global_context);
+    global_context[index].context = context;
+}
+
+fn flatten_stacking_context(context: Vec<StackingEntry>, draw_order: &mut Vec<Entity>) {
+    for entry in context {
+        draw_order.push(entry.entity);
+        flatten_stacking_context(entry.context, draw_order);
+    }
+}
+
+/// Computes the [CalculatedClip] of nodes below nodes with [Overflow::Hidden] or [Overflow::Scroll] and limits their
+/// drawing to it with a scissor rect. This runs after the other draw systems so the scissor rect comes before the
+/// node's draw commands.
+pub fn ui_clip_system(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    root_node_query: Query<With<Node, Without<Parent, Entity>>>,
+    node_query: Query<(&Node, &GlobalTransform, &Style, Option<&CalculatedClip>)>,
+    children_query: Query<&Children>,
+    mut draw_query: Query<With<Node, &mut Draw>>,
+) {
+    // for now assuming all Nodes live in the primary window
+    let window = if let Some(window) = windows.get_primary() {
+        window
+    } else {
+        return;
     };
-    let global_z = z + parent_global_z;
 
-    if let Ok(mut transform) = node_query.get_component_mut::<Transform>(entity) {
-        transform.translation.set_z(z);
+    for entity in root_node_query.iter() {
+        update_clipping(
+            &mut commands,
+            window,
+            &node_query,
+            &children_query,
+            &mut draw_query,
+            entity,
+            None,
+        );
     }
+}
+
+fn update_clipping(
+    commands: &mut Commands,
+    window: &Window,
+    node_query: &Query<(&Node, &GlobalTransform, &Style, Option<&CalculatedClip>)>,
+    children_query: &Query<&Children>,
+    draw_query: &mut Query<With<Node, &mut Draw>>,
+    entity: Entity,
+    clip: Option<Rect>,
+) {
+    let (node, global_transform, style, calculated_clip) =
+        if let Ok(result) = node_query.get(entity) {
+            result
+        } else {
+            return;
+        };
 
-    Some(global_z)
+    match (clip, calculated_clip) {
+        (Some(clip), Some(calculated_clip))
+            if clip.min == calculated_clip.clip.min && clip.max == calculated_clip.clip.max => {}
+        (Some(clip), _) => {
+            commands.insert_one(entity, CalculatedClip { clip });
+        }
+        (None, Some(_)) => {
+            commands.remove_one::<CalculatedClip>(entity);
+        }
+        (None, None) => {}
+    }
+
+    if let Ok(mut draw) = draw_query.get_mut(entity) {
+        set_scissor_rect(&mut draw, window, clip);
+    }
+
+    let children_clip = match style.overflow {
+        Overflow::Visible => clip,
+        Overflow::Hidden | Overflow::Scroll => {
+            let position = global_transform.translation.truncate();
+            let extents = node.size / 2.0;
+            let node_rect = Rect {
+                min: position - extents,
+                max: position + extents,
+            };
+            Some(match clip {
+                Some(clip) => Rect {
+                    min: clip.min.max(node_rect.min),
+                    max: clip.max.min(node_rect.max),
+                },
+                None => node_rect,
+            })
+        }
+    };
+
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter() {
+            update_clipping(
+                commands,
+                window,
+                node_query,
+                children_query,
+                draw_query,
+                *child,
+                children_clip,
+            );
+        }
+    }
+}
+
+fn set_scissor_rect(draw: &mut Draw, window: &Window, clip: Option<Rect>) {
+    if draw.render_commands.is_empty() {
+        return;
+    }
+
+    let width = window.physical_width() as f32;
+    let height = window.physical_height() as f32;
+    // the scissor rect is in physical pixels with the origin at the top left
+    let (min_x, min_y, max_x, max_y) = match clip {
+        Some(clip) => {
+            let scale_factor = window.scale_factor() as f32;
+            (
+                (clip.min.x() * scale_factor).floor().max(0.0),
+                (height - clip.max.y() * scale_factor).floor().max(0.0),
+                (clip.max.x() * scale_factor).ceil().min(width),
+                (height - clip.min.y() * scale_factor).ceil().min(height),
+            )
+        }
+        None => (0.0, 0.0, width, height),
+    };
+
+    if max_x <= min_x || max_y <= min_y {
+        // the node is entirely clipped
+        draw.clear_render_commands();
+        return;
+    }
+
+    // every ui node sets a scissor rect because it stays in effect for the rest of the pass
+    draw.render_commands.insert(
+        0,
+        RenderCommand::SetScissorRect {
+            x: min_x as u32,
+            y: min_y as u32,
+            width: (max_x - min_x) as u32,
+            height: (max_y - min_y) as u32,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ui_z_system, UI_Z_STEP};
+    use crate::{Node, ZIndex};
+    use bevy_ecs::{Entity, IntoQuerySystem, Resources, Schedule, World};
+    use bevy_transform::prelude::{Children, Parent, Transform};
+
+    fn spawn_node(world: &mut World, parent: Option<Entity>, z_index: Option<ZIndex>) -> Entity {
+        let entity = world.spawn((Node::default(), Transform::default()));
+        if let Some(z_index) = z_index {
+            world.insert_one(entity, z_index).unwrap();
+        }
+        if let Some(parent) = parent {
+            world.insert_one(entity, Parent(parent)).unwrap();
+            let mut children = world
+                .get::<Children>(parent)
+                .map(|children| children.to_vec())
+                .unwrap_or_default();
+            children.push(entity);
+            world.insert_one(parent, Children::with(&children)).unwrap();
+        }
+        entity
+    }
+
+    fn local_z(world: &World, entity: Entity) -> f32 {
+        world.get::<Transform>(entity).unwrap().translation.z()
+    }
+
+    #[test]
+    fn z_index_orders_nodes() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", ui_z_system.system());
+
+        let root = spawn_node(&mut world, None, None);
+        let raised = spawn_node(&mut world, Some(root), Some(ZIndex::Local(1)));
+        let child = spawn_node(&mut world, Some(root), None);
+        let global = spawn_node(&mut world, Some(child), Some(ZIndex::Global(1)));
+        let other_root = spawn_node(&mut world, None, None);
+
+        schedule.run(&mut world, &mut resources);
+
+        // draw order: root, child, raised, other_root, global
+        let approx_eq = |a: f32, b: f32| (a - b).abs() < UI_Z_STEP / 10.0;
+        assert!(approx_eq(local_z(&world, root), UI_Z_STEP));
+        assert!(approx_eq(local_z(&world, child), UI_Z_STEP));
+        assert!(approx_eq(local_z(&world, raised), 2.0 * UI_Z_STEP));
+        assert!(approx_eq(local_z(&world, other_root), 4.0 * UI_Z_STEP));
+        assert!(approx_eq(local_z(&world, global), 3.0 * UI_Z_STEP));
+    }
 }
@@ -1,13 +1,23 @@
+use super::NinePatch;
 use crate::CalculatedSize;
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::{Query, Res};
 use bevy_math::Size;
-use bevy_render::texture::Texture;
+use bevy_render::{
+    renderer::{RenderResource, RenderResourceIterator, RenderResources},
+    shader::{ShaderDefIterator, ShaderDefs},
+    texture::Texture,
+};
 use bevy_sprite::ColorMaterial;
 
+/// How a UI image node draws its material's texture
 #[derive(Debug, Clone)]
 pub enum Image {
+    /// The node is sized to its texture
     KeepAspect,
+    /// The texture is sliced by a [NinePatch] to fit the node. The node isn't sized to its texture, so give it a size
+    /// in its [Style](crate::Style).
+    NinePatch(NinePatch),
 }
 
 impl Default for Image {
@@ -16,12 +26,68 @@ impl Default for Image {
     }
 }
 
+/// Used as the `NinePatch` uniform of images that aren't sliced. Its borders are empty, which stretches the whole
+/// texture across the node.
+const NO_NINE_PATCH: NinePatch = NinePatch {
+    left: 0.0,
+    right: 0.0,
+    top: 0.0,
+    bottom: 0.0,
+};
+
+impl RenderResources for Image {
+    fn render_resources_len(&self) -> usize {
+        1
+    }
+
+    fn get_render_resource(&self, index: usize) -> Option<&dyn RenderResource> {
+        match (index, self) {
+            (0, Image::NinePatch(nine_patch)) => Some(nine_patch),
+            (0, Image::KeepAspect) => Some(&NO_NINE_PATCH),
+            _ => None,
+        }
+    }
+
+    fn get_render_resource_name(&self, index: usize) -> Option<&str> {
+        if index == 0 {
+            Some("NinePatch")
+        } else {
+            None
+        }
+    }
+
+    fn iter(&self) -> RenderResourceIterator {
+        RenderResourceIterator::new(self)
+    }
+}
+
+impl ShaderDefs for Image {
+    fn shader_defs_len(&self) -> usize {
+        1
+    }
+
+    fn get_shader_def(&self, index: usize) -> Option<&str> {
+        match (index, self) {
+            (0, Image::NinePatch(_)) => Some("IMAGE_NINE_PATCH"),
+            _ => None,
+        }
+    }
+
+    fn iter_shader_defs(&self) -> ShaderDefIterator {
+        ShaderDefIterator::new(self)
+    }
+}
+
 pub fn image_node_system(
     materials: Res<Assets<ColorMaterial>>,
     textures: Res<Assets<Texture>>,
     mut query: Query<(&Image, &mut CalculatedSize, &Handle<ColorMaterial>)>,
 ) {
-    for (_image, mut calculated_size, material_handle) in query.iter_mut() {
+    for (image, mut calculated_size, material_handle) in query.iter_mut() {
+        if let Image::NinePatch(_) = image {
+            continue;
+        }
+
         if let Some(texture) = materials
             .get(material_handle)
             .and_then(|material| material.texture.as_ref())
@@ -34,3 +100,32 @@ pub fn image_node_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nine_patch_images_define_their_uniform_and_shader_def() {
+        let image = Image::NinePatch(NinePatch::uniform(8.0));
+        assert_eq!(
+            image.iter_shader_defs().collect::<Vec<_>>(),
+            vec!["IMAGE_NINE_PATCH"]
+        );
+        assert_eq!(image.get_render_resource_name(0), Some("NinePatch"));
+        let mut bytes = [0; 16];
+        image
+            .get_render_resource(0)
+            .unwrap()
+            .write_buffer_bytes(&mut bytes);
+        assert_eq!(bytes[0..4], 8.0f32.to_ne_bytes());
+
+        let image = Image::KeepAspect;
+        assert_eq!(image.iter_shader_defs().count(), 0);
+        image
+            .get_render_resource(0)
+            .unwrap()
+            .write_buffer_bytes(&mut bytes);
+        assert_eq!(bytes, [0; 16]);
+    }
+}
@@ -1,7 +1,9 @@
 mod button;
 mod image;
+mod nine_patch;
 mod text;
 
 pub use button::*;
 pub use image::*;
+pub use nine_patch::*;
 pub use text::*;
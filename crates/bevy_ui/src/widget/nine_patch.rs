@@ -0,0 +1,29 @@
+use bevy_core::Bytes;
+use bevy_render::renderer::{RenderResource, RenderResources};
+
+/// Slices a UI node's texture into nine parts so it can be scaled without stretching its corners. The borders are
+/// in texture pixels. Corners keep their size, edges stretch along one axis and the center stretches along both.
+#[derive(Bytes, Debug, Default, Clone, Copy, PartialEq, RenderResources, RenderResource)]
+#[render_resources(from_self)]
+pub struct NinePatch {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl NinePatch {
+    pub fn new(left: f32, right: f32, top: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    /// Uses the same border size on all four sides
+    pub fn uniform(border: f32) -> Self {
+        Self::new(border, border, border, border)
+    }
+}
@@ -1,16 +1,19 @@
-use crate::{CalculatedSize, Node};
-use bevy_asset::{Assets, Handle};
-use bevy_ecs::{Changed, Entity, Local, Query, QuerySet, Res, ResMut};
+use crate::{CalculatedSize, Node, Style, Val};
+use bevy_app::{EventReader, Events};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::{Changed, Entity, Local, Or, Query, QuerySet, Res, ResMut};
 use bevy_math::Size;
 use bevy_render::{
-    draw::{Draw, DrawContext, Drawable},
+    draw::{Draw, DrawContext, Drawable, Visible},
     mesh::Mesh,
     prelude::Msaa,
     renderer::{AssetRenderResourceBindings, RenderResourceBindings},
     texture::Texture,
 };
 use bevy_sprite::{TextureAtlas, QUAD_HANDLE};
-use bevy_text::{DrawableText, Font, FontAtlasSet, TextStyle};
+use bevy_text::{
+    DrawableText, Font, FontAtlasSet, TextAlignment, TextLayout, TextSection, TextStyle,
+};
 use bevy_transform::prelude::GlobalTransform;
 
 #[derive(Debug, Default)]
@@ -18,44 +21,131 @@ pub struct QueuedText {
     entities: Vec<Entity>,
 }
 
+/// A UI text made of one or more [TextSection]s, each with its own font and style. Set a width or max width in
+/// pixels on the node's [Style] to wrap the text at that width.
 #[derive(Debug, Default, Clone)]
 pub struct Text {
-    pub value: String,
-    pub font: Handle<Font>,
-    pub style: TextStyle,
+    pub sections: Vec<TextSection>,
+    pub alignment: TextAlignment,
+}
+
+impl Text {
+    /// Creates a text with a single section
+    pub fn with_section<S: Into<String>>(
+        value: S,
+        font: Handle<Font>,
+        style: TextStyle,
+        alignment: TextAlignment,
+    ) -> Self {
+        Self {
+            sections: vec![TextSection {
+                value: value.into(),
+                font,
+                style,
+            }],
+            alignment,
+        }
+    }
 }
 
+/// The width text is wrapped at, taken from the node's width or max width if they are set in pixels
+fn text_bounds(style: &Style) -> Option<f32> {
+    match (style.size.width, style.max_size.width) {
+        (Val::Px(width), _) | (_, Val::Px(width)) => Some(width),
+        _ => None,
+    }
+}
+
+/// Adds the glyphs of the text to the font atlases and measures it. Returns None if a font isn't loaded yet.
+fn measure_text(
+    fonts: &Assets<Font>,
+    font_atlas_sets: &mut Assets<FontAtlasSet>,
+    texture_atlases: &mut Assets<TextureAtlas>,
+    textures: &mut Assets<Texture>,
+    text: &Text,
+    style: &Style,
+) -> Option<Size> {
+    for section in text.sections.iter() {
+        let font_atlases = font_atlas_sets.get_or_insert_with(section.font.id, || {
+            FontAtlasSet::new(section.font.clone_weak())
+        });
+        // TODO: this call results in one or more TextureAtlases, whose render resources are created in the RENDER_GRAPH_SYSTEMS
+        // stage. That logic runs _before_ the DRAW stage, which means we cant call add_glyphs_to_atlas in the draw stage
+        // without our render resources being a frame behind. Therefore glyph atlasing either needs its own system or the TextureAtlas
+        // resource generation needs to happen AFTER the render graph systems. maybe draw systems should execute within the
+        // render graph so ordering like this can be taken into account? Maybe the RENDER_GRAPH_SYSTEMS stage should be removed entirely
+        // in favor of node.update()? Regardless, in the immediate short term the current approach is fine.
+        font_atlases.add_glyphs_to_atlas(
+            fonts,
+            texture_atlases,
+            textures,
+            section.style.font_size,
+            &section.value,
+        )?;
+    }
+
+    let layout = TextLayout::new(fonts, &text.sections, text_bounds(style))?;
+    let width = match style.size.width {
+        // the node's width is fixed, so keep the measured size in proportion to it
+        Val::Px(width) => width,
+        _ => layout.size.x(),
+    };
+    Some(Size::new(width, layout.size.y()))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn text_system(
     mut queued_text: Local<QueuedText>,
+    mut font_event_reader: Local<EventReader<AssetEvent<Font>>>,
     mut textures: ResMut<Assets<Texture>>,
     fonts: Res<Assets<Font>>,
+    font_events: Res<Events<AssetEvent<Font>>>,
     mut font_atlas_sets: ResMut<Assets<FontAtlasSet>>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     mut queries: QuerySet<(
-        Query<(Entity, Changed<Text>, &mut CalculatedSize)>,
-        Query<(&Text, &mut CalculatedSize)>,
+        // the style sets the width the text is wrapped at
+        Query<(
+            Entity,
+            Or<(Changed<Text>, Changed<Style>)>,
+            &mut CalculatedSize,
+        )>,
+        Query<(&Text, &Style, &mut CalculatedSize)>,
+        Query<(Entity, &Text)>,
     )>,
 ) {
+    // texts of modified fonts are rasterized again, the font's glyph rendering may have changed
+    let mut modified_fonts = Vec::new();
+    for event in font_event_reader.iter(&font_events) {
+        if let AssetEvent::Modified { handle } = event {
+            modified_fonts.push(handle.id);
+        }
+    }
+    if !modified_fonts.is_empty() {
+        for (entity, text) in queries.q2().iter() {
+            if text
+                .sections
+                .iter()
+                .any(|section| modified_fonts.contains(&section.font.id))
+                && !queued_text.entities.contains(&entity)
+            {
+                queued_text.entities.push(entity);
+            }
+        }
+    }
+
     // add queued text to atlases
     let mut new_queued_text = Vec::new();
     for entity in queued_text.entities.drain(..) {
-        if let Ok((text, mut calculated_size)) = queries.q1_mut().get_mut(entity) {
-            let font_atlases = font_atlas_sets
-                .get_or_insert_with(text.font.id, || FontAtlasSet::new(text.font.clone_weak()));
-            // TODO: this call results in one or more TextureAtlases, whose render resources are created in the RENDER_GRAPH_SYSTEMS
-            // stage. That logic runs _before_ the DRAW stage, which means we cant call add_glyphs_to_atlas in the draw stage
-            // without our render resources being a frame behind. Therefore glyph atlasing either needs its own system or the TextureAtlas
-            // resource generation needs to happen AFTER the render graph systems. maybe draw systems should execute within the
-            // render graph so ordering like this can be taken into account? Maybe the RENDER_GRAPH_SYSTEMS stage should be removed entirely
-            // in favor of node.update()? Regardless, in the immediate short term the current approach is fine.
-            if let Some(width) = font_atlases.add_glyphs_to_atlas(
+        if let Ok((text, style, mut calculated_size)) = queries.q1_mut().get_mut(entity) {
+            if let Some(size) = measure_text(
                 &fonts,
+                &mut font_atlas_sets,
                 &mut texture_atlases,
                 &mut textures,
-                text.style.font_size,
-                &text.value,
+                &text,
+                &style,
             ) {
-                calculated_size.size = Size::new(width, text.style.font_size);
+                calculated_size.size = size;
             } else {
                 new_queued_text.push(entity);
             }
@@ -64,24 +154,17 @@ pub fn text_system(
 
     queued_text.entities = new_queued_text;
 
-    // add changed text to atlases
-    for (entity, text, mut calculated_size) in queries.q0_mut().iter_mut() {
-        let font_atlases = font_atlas_sets
-            .get_or_insert_with(text.font.id, || FontAtlasSet::new(text.font.clone_weak()));
-        // TODO: this call results in one or more TextureAtlases, whose render resources are created in the RENDER_GRAPH_SYSTEMS
-        // stage. That logic runs _before_ the DRAW stage, which means we cant call add_glyphs_to_atlas in the draw stage
-        // without our render resources being a frame behind. Therefore glyph atlasing either needs its own system or the TextureAtlas
-        // resource generation needs to happen AFTER the render graph systems. maybe draw systems should execute within the
-        // render graph so ordering like this can be taken into account? Maybe the RENDER_GRAPH_SYSTEMS stage should be removed entirely
-        // in favor of node.update()? Regardless, in the immediate short term the current approach is fine.
-        if let Some(width) = font_atlases.add_glyphs_to_atlas(
+    // add changed text to atlases, and measure it again when its style changes
+    for (entity, (text, style), mut calculated_size) in queries.q0_mut().iter_mut() {
+        if let Some(size) = measure_text(
             &fonts,
+            &mut font_atlas_sets,
             &mut texture_atlases,
             &mut textures,
-            text.style.font_size,
-            &text.value,
+            &text,
+            &style,
         ) {
-            calculated_size.size = Size::new(width, text.style.font_size);
+            calculated_size.size = size;
         } else {
             queued_text.entities.push(entity);
         }
@@ -98,28 +181,95 @@ pub fn draw_text_system(
     meshes: Res<Assets<Mesh>>,
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
     mut asset_render_resource_bindings: ResMut<AssetRenderResourceBindings>,
-    mut query: Query<(&mut Draw, &Text, &Node, &GlobalTransform)>,
+    mut query: Query<(&mut Draw, &Visible, &Text, &Style, &Node, &GlobalTransform)>,
 ) {
     let font_quad = meshes.get(&QUAD_HANDLE).unwrap();
     let vertex_buffer_descriptor = font_quad.get_vertex_buffer_descriptor();
 
-    for (mut draw, text, node, global_transform) in query.iter_mut() {
-        if let Some(font) = fonts.get(&text.font) {
+    for (mut draw, visible, text, style, node, global_transform) in query.iter_mut() {
+        if !visible.is_drawn() {
+            continue;
+        }
+        if let Some(layout) = TextLayout::new(&fonts, &text.sections, text_bounds(&style)) {
             let position = global_transform.translation - (node.size / 2.0).extend(0.0);
+            let glyphs = layout.aligned_glyphs(node.size, text.alignment);
             let mut drawable_text = DrawableText {
-                font,
-                font_atlas_set: font_atlas_sets.get(text.font.id).unwrap(),
+                fonts: &fonts,
+                font_atlas_sets: &font_atlas_sets,
                 texture_atlases: &texture_atlases,
                 render_resource_bindings: &mut render_resource_bindings,
                 asset_render_resource_bindings: &mut asset_render_resource_bindings,
                 position,
                 msaa: &msaa,
-                style: &text.style,
-                text: &text.value,
-                container_size: node.size,
+                sections: &text.sections,
+                glyphs: &glyphs,
                 font_quad_vertex_descriptor: &vertex_buffer_descriptor,
             };
             drawable_text.draw(&mut draw, &mut draw_context).unwrap();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{text_system, Text};
+    use crate::{CalculatedSize, Style, Val};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::DefaultTaskPoolOptions;
+    use bevy_ecs::{IntoQuerySystem, Schedule};
+    use bevy_render::texture::Texture;
+    use bevy_sprite::TextureAtlas;
+    use bevy_text::{Font, FontAtlasSet, TextStyle};
+    use bevy_type_registry::TypeRegistry;
+
+    #[test]
+    fn text_is_measured_again_when_its_style_changes() {
+        let mut app = App::build();
+        DefaultTaskPoolOptions::default().create_default_pools(app.resources_mut());
+        app.init_resource::<TypeRegistry>()
+            .add_plugin(AssetPlugin)
+            .add_asset::<Font>()
+            .add_asset::<FontAtlasSet>()
+            .add_asset::<Texture>()
+            .add_asset::<TextureAtlas>();
+        let font = app.resources().get_mut::<Assets<Font>>().unwrap().add(
+            Font::try_from_bytes(
+                include_bytes!("../../../../assets/fonts/FiraSans-Bold.ttf").to_vec(),
+            )
+            .unwrap(),
+        );
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", text_system.system());
+
+        let mut style = Style::default();
+        style.max_size.width = Val::Px(40.0);
+        let entity = app.app.world.spawn((
+            Text::with_section(
+                "aaa bbb",
+                font,
+                TextStyle {
+                    font_size: 20.0,
+                    ..Default::default()
+                },
+                Default::default(),
+            ),
+            style,
+            CalculatedSize::default(),
+        ));
+        schedule.run(&mut app.app.world, &mut app.app.resources);
+        let wrapped_size = app.app.world.get::<CalculatedSize>(entity).unwrap().size;
+
+        app.app
+            .world
+            .get_mut::<Style>(entity)
+            .unwrap()
+            .max_size
+            .width = Val::Auto;
+        schedule.run(&mut app.app.world, &mut app.app.resources);
+        let size = app.app.world.get::<CalculatedSize>(entity).unwrap().size;
+        assert!(size.width > wrapped_size.width);
+        assert_eq!(size.height * 2.0, wrapped_size.height);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::Node;
+use bevy_ecs::prelude::*;
+use bevy_math::{Vec2, Vec3};
+use bevy_render::{
+    camera::{ActiveCameras, Camera},
+    draw::Visible,
+    render_graph::base,
+};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use bevy_window::Windows;
+use std::borrow::Cow;
+
+/// What to do with a [WorldSpaceNode] whose target is outside of the camera's view.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OffScreen {
+    /// Hides the node
+    Hide,
+    /// Keeps the node at the nearest edge of the window, which is useful for markers pointing at objectives
+    Clamp,
+}
+
+impl Default for OffScreen {
+    fn default() -> Self {
+        OffScreen::Hide
+    }
+}
+
+/// Centers a root UI node on the screen position of a target entity, for health bars, name labels and other markers
+/// that follow things in the world. The node and its descendants are hidden while the target is further away than
+/// `max_distance`, or off screen (or behind the camera) with [OffScreen::Hide]. The node's own [Visible] setting is
+/// restored when it's shown again.
+///
+/// Nodes aren't occluded by geometry in front of their target.
+#[derive(Debug, Clone)]
+pub struct WorldSpaceNode {
+    pub target: Entity,
+    /// Added to the target's position, in world space. Use it to place a health bar above a character's head.
+    pub world_offset: Vec3,
+    /// Added to the projected position, in logical pixels
+    pub screen_offset: Vec2,
+    /// The name of the camera to project with
+    pub camera: Cow<'static, str>,
+    pub off_screen: OffScreen,
+    pub max_distance: Option<f32>,
+    /// The node's `is_visible` from before [world_space_node_system] hid it
+    hidden_visibility: Option<bool>,
+}
+
+impl WorldSpaceNode {
+    pub fn new(target: Entity) -> Self {
+        WorldSpaceNode {
+            target,
+            world_offset: Vec3::zero(),
+            screen_offset: Vec2::zero(),
+            camera: Cow::Borrowed(base::camera::CAMERA3D),
+            off_screen: OffScreen::default(),
+            max_distance: None,
+            hidden_visibility: None,
+        }
+    }
+}
+
+/// Runs after the flex layout, so it overrides the position the layout gave world space nodes.
+pub fn world_space_node_system(
+    windows: Res<Windows>,
+    active_cameras: Res<ActiveCameras>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    target_query: Query<&GlobalTransform>,
+    mut node_query: Query<With<Node, (&mut WorldSpaceNode, &Node, &mut Transform, &mut Visible)>>,
+) {
+    for (mut world_space_node, node, mut transform, mut visible) in node_query.iter_mut() {
+        let position = active_cameras
+            .get(&world_space_node.camera)
+            .and_then(|camera_entity| camera_query.get(camera_entity).ok())
+            .and_then(|(camera, camera_transform)| {
+                let target_transform = target_query.get(world_space_node.target).ok()?;
+                let world_position = target_transform.translation + world_space_node.world_offset;
+                if let Some(max_distance) = world_space_node.max_distance {
+                    if (world_position - camera_transform.translation).length() > max_distance {
+                        return None;
+                    }
+                }
+                project_node(
+                    camera,
+                    camera_transform,
+                    &windows,
+                    world_position,
+                    &world_space_node,
+                    node.size,
+                )
+            });
+
+        if let Some(position) = position {
+            transform.translation.set_x(position.x());
+            transform.translation.set_y(position.y());
+        }
+
+        // hiding the node also hides its children
+        match (position, world_space_node.hidden_visibility) {
+            (Some(_), Some(is_visible)) => {
+                visible.is_visible = is_visible;
+                world_space_node.hidden_visibility = None;
+            }
+            (None, None) => {
+                world_space_node.hidden_visibility = Some(visible.is_visible);
+                visible.is_visible = false;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns where the node should be drawn, in logical pixels from the bottom left of the window, or None if it should
+/// be hidden
+fn project_node(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    windows: &Windows,
+    world_position: Vec3,
+    world_space_node: &WorldSpaceNode,
+    node_size: Vec2,
+) -> Option<Vec2> {
+    let window = windows.get(camera.window)?;
+    let window_size = Vec2::new(window.width(), window.height());
+    let extents = node_size / 2.0;
+    let clamp = |position: Vec2| {
+        position
+            .max(extents)
+            .min((window_size - extents).max(extents))
+    };
+
+    let screen_position = match camera.world_to_screen(windows, camera_transform, world_position) {
+        Some(screen_position) => screen_position + world_space_node.screen_offset,
+        None => {
+            return match world_space_node.off_screen {
+                OffScreen::Hide => None,
+                OffScreen::Clamp => {
+                    // the target is behind the camera, so push the node to the edge on the side of the target
+                    let view_position =
+                        camera_transform.compute_matrix().inverse() * world_position.extend(1.0);
+                    let mut direction = view_position.truncate().truncate();
+                    if direction == Vec2::zero() {
+                        direction = Vec2::new(0.0, -1.0);
+                    }
+                    let center = window_size / 2.0;
+                    Some(clamp(center + direction.normalize() * window_size.length()))
+                }
+            };
+        }
+    };
+    let on_screen =
+        screen_position.cmpge(-extents).all() && screen_position.cmple(window_size + extents).all();
+    match world_space_node.off_screen {
+        OffScreen::Hide if !on_screen => None,
+        OffScreen::Hide => Some(screen_position),
+        OffScreen::Clamp => Some(clamp(screen_position)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::Schedule;
+    use bevy_render::camera::{CameraProjection, PerspectiveProjection};
+
+    fn windows() -> Windows {
+        let mut windows = Windows::default();
+        windows.add(bevy_window::Window::new(
+            bevy_window::WindowId::primary(),
+            &bevy_window::WindowDescriptor {
+                width: 800.0,
+                height: 600.0,
+                ..Default::default()
+            },
+        ));
+        windows
+    }
+
+    fn camera() -> (Camera, GlobalTransform) {
+        let mut projection = PerspectiveProjection::default();
+        projection.update(800.0, 600.0);
+        let camera = Camera {
+            projection_matrix: projection.get_projection_matrix(),
+            ..Default::default()
+        };
+        // looks down -z from the origin
+        (camera, GlobalTransform::identity())
+    }
+
+    #[test]
+    fn clamp_keeps_targets_behind_the_camera_on_screen() {
+        let (camera, camera_transform) = camera();
+        let windows = windows();
+        let node_size = Vec2::new(20.0, 20.0);
+        let mut world_space_node = WorldSpaceNode::new(Entity::new(0));
+        let behind_right = Vec3::new(5.0, 0.0, 5.0);
+
+        assert_eq!(
+            project_node(
+                &camera,
+                &camera_transform,
+                &windows,
+                behind_right,
+                &world_space_node,
+                node_size
+            ),
+            None
+        );
+
+        world_space_node.off_screen = OffScreen::Clamp;
+        let position = project_node(
+            &camera,
+            &camera_transform,
+            &windows,
+            behind_right,
+            &world_space_node,
+            node_size,
+        )
+        .unwrap();
+        // clamped to the right edge, on the side of the target
+        assert_eq!(position.x(), 790.0);
+
+        let in_front = project_node(
+            &camera,
+            &camera_transform,
+            &windows,
+            Vec3::new(0.0, 0.0, -5.0),
+            &world_space_node,
+            node_size,
+        )
+        .unwrap();
+        assert!((in_front - Vec2::new(400.0, 300.0)).length() < 1e-3);
+    }
+
+    #[test]
+    fn restores_the_nodes_visibility() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(Windows::default());
+        resources.insert(ActiveCameras::default());
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", world_space_node_system.system());
+
+        let target = world.spawn((GlobalTransform::identity(),));
+        let hidden_node = world.spawn((
+            Node::default(),
+            WorldSpaceNode::new(target),
+            Transform::default(),
+            Visible {
+                is_visible: false,
+                ..Default::default()
+            },
+        ));
+
+        // there's no camera, so the node is hidden by the system
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            world
+                .get::<WorldSpaceNode>(hidden_node)
+                .unwrap()
+                .hidden_visibility,
+            Some(false)
+        );
+
+        // once the target can be projected, the node gets its own visibility back
+        resources.insert(windows());
+        let (camera, _) = camera();
+        let camera_entity = world.spawn((
+            camera,
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 5.0)),
+        ));
+        resources
+            .get_mut::<ActiveCameras>()
+            .unwrap()
+            .add(base::camera::CAMERA3D);
+        resources
+            .get_mut::<ActiveCameras>()
+            .unwrap()
+            .set(base::camera::CAMERA3D, camera_entity);
+        schedule.run(&mut world, &mut resources);
+
+        assert!(!world.get::<Visible>(hidden_node).unwrap().is_visible);
+        assert_eq!(
+            world
+                .get::<WorldSpaceNode>(hidden_node)
+                .unwrap()
+                .hidden_visibility,
+            None
+        );
+    }
+}
@@ -114,6 +114,28 @@ impl RenderContext for WgpuRenderContext {
         )
     }
 
+    fn copy_texture_to_texture(
+        &mut self,
+        source_texture: TextureId,
+        source_origin: [u32; 3],
+        source_mip_level: u32,
+        destination_texture: TextureId,
+        destination_origin: [u32; 3],
+        destination_mip_level: u32,
+        size: Extent3d,
+    ) {
+        self.render_resource_context.copy_texture_to_texture(
+            self.command_encoder.get_or_create(&self.device),
+            source_texture,
+            source_origin,
+            source_mip_level,
+            destination_texture,
+            destination_origin,
+            destination_mip_level,
+            size,
+        )
+    }
+
     fn resources(&self) -> &dyn RenderResourceContext {
         &self.render_resource_context
     }
@@ -146,6 +168,7 @@ impl RenderContext for WgpuRenderContext {
                 render_context: self,
                 wgpu_resources: refs,
                 pipeline_descriptor: None,
+                primitive_topology: Default::default(),
             };
 
             run_pass(&mut wgpu_render_pass);
@@ -70,6 +70,13 @@ impl WgpuRenderGraphExecutor {
                                 panic!("no edge connected to input")
                             }
                         }
+                        #[cfg(feature = "trace_spans")]
+                        let node_span = tracing::info_span!(
+                            "render_graph_node",
+                            name = node_state.name.as_deref().unwrap_or("unnamed")
+                        );
+                        #[cfg(feature = "trace_spans")]
+                        let _node_guard = node_span.enter();
                         node_state.node.update(
                             world,
                             resources,
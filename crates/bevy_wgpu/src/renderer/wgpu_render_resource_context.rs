@@ -59,6 +59,45 @@ impl WgpuRenderResourceContext {
             destination_offset,
             size,
         );
+        self.resources.render_statistics.write().record_upload(size);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn copy_texture_to_texture(
+        &self,
+        command_encoder: &mut wgpu::CommandEncoder,
+        source_texture: TextureId,
+        source_origin: [u32; 3],
+        source_mip_level: u32,
+        destination_texture: TextureId,
+        destination_origin: [u32; 3],
+        destination_mip_level: u32,
+        size: Extent3d,
+    ) {
+        let textures = self.resources.textures.read();
+        let source = textures.get(&source_texture).unwrap();
+        let destination = textures.get(&destination_texture).unwrap();
+        command_encoder.copy_texture_to_texture(
+            wgpu::TextureCopyView {
+                texture: source,
+                mip_level: source_mip_level,
+                origin: wgpu::Origin3d {
+                    x: source_origin[0],
+                    y: source_origin[1],
+                    z: source_origin[2],
+                },
+            },
+            wgpu::TextureCopyView {
+                texture: destination,
+                mip_level: destination_mip_level,
+                origin: wgpu::Origin3d {
+                    x: destination_origin[0],
+                    y: destination_origin[1],
+                    z: destination_origin[2],
+                },
+            },
+            size.wgpu_into(),
+        );
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -84,7 +123,8 @@ impl WgpuRenderResourceContext {
                 layout: wgpu::TextureDataLayout {
                     offset: source_offset,
                     bytes_per_row: source_bytes_per_row,
-                    rows_per_image: 0, // NOTE: Example sets this to 0, but should it be height?
+                    // layers are packed one after the other
+                    rows_per_image: size.height,
                 },
             },
             wgpu::TextureCopyView {
@@ -98,6 +138,10 @@ impl WgpuRenderResourceContext {
             },
             size.wgpu_into(),
         );
+        self.resources
+            .render_statistics
+            .write()
+            .record_upload(source_bytes_per_row as u64 * size.height as u64 * size.depth as u64);
     }
 
     pub fn create_bind_group_layout(&self, descriptor: &BindGroupDescriptor) {
@@ -175,7 +219,10 @@ impl RenderResourceContext for WgpuRenderResourceContext {
 
         let descriptor: wgpu::TextureDescriptor = (&texture_descriptor).wgpu_into();
         let texture = self.device.create_texture(&descriptor);
-        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(texture_descriptor.view_dimension.wgpu_into()),
+            ..Default::default()
+        });
 
         let id = TextureId::new();
         texture_descriptors.insert(id, texture_descriptor);
@@ -219,6 +266,10 @@ impl RenderResourceContext for WgpuRenderResourceContext {
         let id = BufferId::new();
         buffer_infos.insert(id, buffer_info);
         buffers.insert(id, Arc::new(buffer));
+        self.resources
+            .render_statistics
+            .write()
+            .record_upload(data.len() as u64);
         id
     }
 
@@ -247,7 +298,7 @@ impl RenderResourceContext for WgpuRenderResourceContext {
 
     fn create_shader_module_from_source(&self, shader_handle: &Handle<Shader>, shader: &Shader) {
         let mut shader_modules = self.resources.shader_modules.write();
-        let spirv: Cow<[u32]> = shader.get_spirv(None).into();
+        let spirv: Cow<[u32]> = shader.get_spirv(None).unwrap().into();
         let shader_module = self
             .device
             .create_shader_module(wgpu::ShaderModuleSource::SpirV(spirv));
@@ -436,9 +487,22 @@ impl RenderResourceContext for WgpuRenderResourceContext {
             .device
             .create_render_pipeline(&render_pipeline_descriptor);
         let mut render_pipelines = self.resources.render_pipelines.write();
+        self.resources.render_pipeline_topologies.write().insert(
+            pipeline_handle.clone_weak(),
+            pipeline_descriptor.primitive_topology,
+        );
         render_pipelines.insert(pipeline_handle, render_pipeline);
     }
 
+    fn remove_render_pipeline(&self, pipeline_handle: &Handle<PipelineDescriptor>) {
+        let mut render_pipelines = self.resources.render_pipelines.write();
+        render_pipelines.remove(pipeline_handle);
+        self.resources
+            .render_pipeline_topologies
+            .write()
+            .remove(pipeline_handle);
+    }
+
     fn bind_group_descriptor_exists(
         &self,
         bind_group_descriptor_id: BindGroupDescriptorId,
@@ -2,7 +2,7 @@ use crate::{renderer::WgpuRenderContext, WgpuResourceRefs};
 use bevy_asset::Handle;
 use bevy_render::{
     pass::RenderPass,
-    pipeline::{BindGroupDescriptorId, PipelineDescriptor},
+    pipeline::{BindGroupDescriptorId, PipelineDescriptor, PrimitiveTopology},
     renderer::{BindGroupId, BufferId, RenderContext},
 };
 use std::ops::Range;
@@ -13,6 +13,8 @@ pub struct WgpuRenderPass<'a> {
     pub render_context: &'a WgpuRenderContext,
     pub wgpu_resources: WgpuResourceRefs<'a>,
     pub pipeline_descriptor: Option<&'a PipelineDescriptor>,
+    /// The topology of the current pipeline, used to count the triangles that are drawn
+    pub primitive_topology: PrimitiveTopology,
 }
 
 impl<'a> RenderPass for WgpuRenderPass<'a> {
@@ -35,17 +37,23 @@ impl<'a> RenderPass for WgpuRenderPass<'a> {
         self.render_pass.set_stencil_reference(reference);
     }
 
+    fn set_scissor_rect(&mut self, x: u32, y: u32, w: u32, h: u32) {
+        self.render_pass.set_scissor_rect(x, y, w, h);
+    }
+
     fn set_index_buffer(&mut self, buffer_id: BufferId, offset: u64) {
         let buffer = self.wgpu_resources.buffers.get(&buffer_id).unwrap();
         self.render_pass.set_index_buffer(buffer.slice(offset..));
     }
 
     fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
+        self.record_draw(indices.clone(), instances.clone());
         self.render_pass
             .draw_indexed(indices, base_vertex, instances);
     }
 
     fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        self.record_draw(vertices.clone(), instances.clone());
         self.render_pass.draw(vertices, instances);
     }
 
@@ -91,5 +99,26 @@ impl<'a> RenderPass for WgpuRenderPass<'a> {
             "Attempted to use a pipeline that does not exist in this RenderPass's RenderContext",
         );
         self.render_pass.set_pipeline(pipeline);
+
+        let resources = &self.render_context.render_resource_context.resources;
+        if let Some(primitive_topology) = resources
+            .render_pipeline_topologies
+            .read()
+            .get(pipeline_handle)
+        {
+            self.primitive_topology = *primitive_topology;
+        }
+        resources.render_statistics.write().pipeline_switches += 1;
+    }
+}
+
+impl<'a> WgpuRenderPass<'a> {
+    fn record_draw(&self, elements: Range<u32>, instances: Range<u32>) {
+        self.render_context
+            .render_resource_context
+            .resources
+            .render_statistics
+            .write()
+            .record_draw(self.primitive_topology, elements, instances);
     }
 }
@@ -5,8 +5,8 @@ use crate::{
 use bevy_app::prelude::*;
 use bevy_ecs::{Resources, World};
 use bevy_render::{
-    render_graph::{DependentNodeStager, RenderGraph, RenderGraphStager},
-    renderer::RenderResourceContext,
+    render_graph::{DependentNodeStager, RenderGraph, RenderGraphStager, TransientTexturePool},
+    renderer::{texture_memory, RenderResourceContext, RenderStatistics},
 };
 use bevy_window::{WindowCreated, WindowResized, Windows};
 use std::{ops::Deref, sync::Arc};
@@ -94,6 +94,19 @@ impl WgpuRenderer {
         // stage nodes
         let mut stager = DependentNodeStager::loose_grouping();
         let stages = stager.get_stages(&render_graph).unwrap();
+        // the executor needs the render resource context mutably, so release it once the transient textures are set
+        {
+            let mut transient_texture_pool = resources.get_mut::<TransientTexturePool>().unwrap();
+            let windows = resources.get::<Windows>().unwrap();
+            let render_resource_context =
+                resources.get::<Box<dyn RenderResourceContext>>().unwrap();
+            transient_texture_pool.update(
+                &mut render_graph,
+                &stages,
+                &windows,
+                &**render_resource_context,
+            );
+        }
         let mut borrowed = stages.borrow(&mut render_graph);
 
         // execute stages
@@ -116,5 +129,36 @@ impl WgpuRenderer {
         let render_resource_context = resources.get::<Box<dyn RenderResourceContext>>().unwrap();
         render_resource_context.drop_all_swap_chain_textures();
         render_resource_context.clear_bind_groups();
+        if let Some(render_resource_context) =
+            render_resource_context.downcast_ref::<WgpuRenderResourceContext>()
+        {
+            Self::update_render_statistics(render_resource_context, resources);
+        }
+    }
+
+    /// Publishes the counters of the frame that was just rendered to the [RenderStatistics] resource, and starts
+    /// counting the next frame
+    fn update_render_statistics(
+        render_resource_context: &WgpuRenderResourceContext,
+        resources: &Resources,
+    ) {
+        let wgpu_resources = &render_resource_context.resources;
+        let mut frame_statistics = wgpu_resources.render_statistics.write();
+        frame_statistics.buffer_memory = wgpu_resources
+            .buffer_infos
+            .read()
+            .values()
+            .map(|buffer_info| buffer_info.size as u64)
+            .sum();
+        frame_statistics.texture_memory = wgpu_resources
+            .texture_descriptors
+            .read()
+            .values()
+            .map(texture_memory)
+            .sum();
+        if let Some(mut render_statistics) = resources.get_mut::<RenderStatistics>() {
+            *render_statistics = *frame_statistics;
+        }
+        frame_statistics.reset_frame();
     }
 }
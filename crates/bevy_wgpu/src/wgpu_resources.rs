@@ -1,7 +1,9 @@
 use bevy_asset::{Handle, HandleUntyped};
 use bevy_render::{
-    pipeline::{BindGroupDescriptorId, PipelineDescriptor},
-    renderer::{BindGroupId, BufferId, BufferInfo, RenderResourceId, SamplerId, TextureId},
+    pipeline::{BindGroupDescriptorId, PipelineDescriptor, PrimitiveTopology},
+    renderer::{
+        BindGroupId, BufferId, BufferInfo, RenderResourceId, RenderStatistics, SamplerId, TextureId,
+    },
     shader::Shader,
     texture::TextureDescriptor,
 };
@@ -82,9 +84,13 @@ pub struct WgpuResources {
     pub samplers: Arc<RwLock<HashMap<SamplerId, wgpu::Sampler>>>,
     pub shader_modules: Arc<RwLock<HashMap<Handle<Shader>, wgpu::ShaderModule>>>,
     pub render_pipelines: Arc<RwLock<HashMap<Handle<PipelineDescriptor>, wgpu::RenderPipeline>>>,
+    pub render_pipeline_topologies:
+        Arc<RwLock<HashMap<Handle<PipelineDescriptor>, PrimitiveTopology>>>,
     pub bind_groups: Arc<RwLock<HashMap<BindGroupDescriptorId, WgpuBindGroupInfo>>>,
     pub bind_group_layouts: Arc<RwLock<HashMap<BindGroupDescriptorId, wgpu::BindGroupLayout>>>,
     pub asset_resources: Arc<RwLock<HashMap<(HandleUntyped, u64), RenderResourceId>>>,
+    /// The counters of the frame that is being rendered, see [RenderStatistics]
+    pub render_statistics: Arc<RwLock<RenderStatistics>>,
 }
 
 impl WgpuResources {
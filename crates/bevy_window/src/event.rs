@@ -1,12 +1,36 @@
 use super::{WindowDescriptor, WindowId};
 use bevy_math::Vec2;
+use std::path::PathBuf;
 
-/// A window event that is sent whenever a window has been resized.
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// A window event that is sent whenever a window has been resized. The size is in logical pixels.
 #[derive(Debug, Clone)]
 pub struct WindowResized {
     pub id: WindowId,
-    pub width: usize,
-    pub height: usize,
+    pub width: f32,
+    pub height: f32,
+    /// When winit delivered the event to the app, not when the OS generated it
+    pub timestamp: Instant,
+}
+
+/// An event that is sent whenever the scale factor used by a window changes, either because the platform's scale
+/// factor changed or because the override was changed.
+#[derive(Debug, Clone)]
+pub struct WindowScaleFactorChanged {
+    pub id: WindowId,
+    pub scale_factor: f64,
+}
+
+/// An event that is sent whenever the platform's scale factor for a window changes (ex: the window was moved to a
+/// monitor with a different DPI). It is sent even if the window has a scale factor override.
+#[derive(Debug, Clone)]
+pub struct WindowBackendScaleFactorChanged {
+    pub id: WindowId,
+    pub scale_factor: f64,
 }
 
 /// An event that indicates that a new window should be created.
@@ -22,6 +46,11 @@ pub struct CloseWindow {
     pub id: WindowId,
 }
 
+/// An event that asks for another update when the App only updates in response to input, like with winit's
+/// reactive update mode. Send it while something is animating.
+#[derive(Debug, Clone)]
+pub struct RequestRedraw;
+
 /// An event that is sent whenever a new window is created.
 #[derive(Debug, Clone)]
 pub struct WindowCreated {
@@ -29,14 +58,65 @@ pub struct WindowCreated {
 }
 
 /// An event that is sent whenever a close was requested for a window. For example: when the "close" button
-/// is pressed on a window.
+/// is pressed on a window. The window is not closed automatically: by default [WindowPlugin](crate::WindowPlugin) exits
+/// the app, but apps that set `exit_on_close` to `false` can handle this event themselves (ex: to ask the user to save
+/// before quitting) and send [AppExit](bevy_app::AppExit) when appropriate.
 #[derive(Debug, Clone)]
 pub struct WindowCloseRequested {
     pub id: WindowId,
+    /// When winit delivered the event to the app, not when the OS generated it
+    pub timestamp: Instant,
 }
 
+/// An event that is sent whenever a window gains or loses focus
+#[derive(Debug, Clone)]
+pub struct WindowFocused {
+    pub id: WindowId,
+    pub focused: bool,
+    /// When winit delivered the event to the app, not when the OS generated it
+    pub timestamp: Instant,
+}
+
+/// An event that is sent whenever the cursor moves within a window. The position is in logical pixels, relative to the
+/// bottom left of the window.
 #[derive(Debug, Clone)]
 pub struct CursorMoved {
     pub id: WindowId,
     pub position: Vec2,
+    /// When winit delivered the event to the app, not when the OS generated it
+    pub timestamp: Instant,
+}
+
+/// An event that is sent whenever a window receives a character from the OS, after keyboard layout, dead keys and
+/// input methods have been applied. Use this for text input instead of translating key codes. Control characters
+/// (ex: backspace) are included.
+#[derive(Debug, Clone)]
+pub struct ReceivedCharacter {
+    pub id: WindowId,
+    pub char: char,
+    /// When winit delivered the event to the app, not when the OS generated it
+    pub timestamp: Instant,
+}
+
+/// Events related to files being dragged and dropped on a window. Their `timestamp` is when winit delivered the event
+/// to the app, not when the OS generated it.
+///
+/// Note: on Windows, drag and drop is disabled when creating windows because it conflicts with the COM threading
+/// model used by audio, so these events are not sent there.
+#[derive(Debug, Clone)]
+pub enum FileDragAndDrop {
+    /// A file has been dropped on the window
+    DroppedFile {
+        id: WindowId,
+        path_buf: PathBuf,
+        timestamp: Instant,
+    },
+    /// A file is being dragged over the window. Sent once per file when the drag enters the window.
+    HoveredFile {
+        id: WindowId,
+        path_buf: PathBuf,
+        timestamp: Instant,
+    },
+    /// The hovered files have left the window or the drag has been cancelled
+    HoveredFileCancelled { id: WindowId, timestamp: Instant },
 }
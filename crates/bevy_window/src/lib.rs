@@ -1,8 +1,10 @@
+mod cursor;
 mod event;
 mod system;
 mod window;
 mod windows;
 
+pub use cursor::*;
 pub use event::*;
 pub use system::*;
 pub use window::*;
@@ -17,6 +19,8 @@ use bevy_ecs::IntoQuerySystem;
 
 pub struct WindowPlugin {
     pub add_primary_window: bool,
+    /// Sends [AppExit](bevy_app::AppExit) as soon as a window close is requested. Disable this to handle
+    /// [WindowCloseRequested] events yourself.
     pub exit_on_close: bool,
 }
 
@@ -32,11 +36,17 @@ impl Default for WindowPlugin {
 impl Plugin for WindowPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_event::<WindowResized>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<CreateWindow>()
             .add_event::<WindowCreated>()
             .add_event::<WindowCloseRequested>()
+            .add_event::<WindowFocused>()
             .add_event::<CloseWindow>()
             .add_event::<CursorMoved>()
+            .add_event::<ReceivedCharacter>()
+            .add_event::<FileDragAndDrop>()
+            .add_event::<RequestRedraw>()
             .init_resource::<Windows>();
 
         if self.add_primary_window {
@@ -1,3 +1,5 @@
+use crate::CursorIcon;
+use bevy_math::Vec2;
 use uuid::Uuid;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -34,15 +36,24 @@ impl Default for WindowId {
 #[derive(Debug)]
 pub struct Window {
     id: WindowId,
-    width: u32,
-    height: u32,
+    requested_width: f32,
+    requested_height: f32,
+    physical_width: u32,
+    physical_height: u32,
+    scale_factor_override: Option<f64>,
+    backend_scale_factor: f64,
     title: String,
     vsync: bool,
     resizable: bool,
     decorations: bool,
     cursor_visible: bool,
     cursor_locked: bool,
+    cursor_icon: CursorIcon,
+    position: Option<(i32, i32)>,
     mode: WindowMode,
+    monitor: MonitorSelection,
+    resize_constraints: WindowResizeConstraints,
+    aspect_ratio: Option<f32>,
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
     command_queue: Vec<WindowCommand>,
@@ -52,14 +63,18 @@ pub struct Window {
 pub enum WindowCommand {
     SetWindowMode {
         mode: WindowMode,
+        monitor: MonitorSelection,
         resolution: (u32, u32),
     },
     SetTitle {
         title: String,
     },
     SetResolution {
-        width: u32,
-        height: u32,
+        logical_resolution: (f32, f32),
+        scale_factor: f64,
+    },
+    SetScaleFactor {
+        scale_factor: f64,
     },
     SetVsync {
         vsync: bool,
@@ -76,6 +91,23 @@ pub enum WindowCommand {
     SetCursorVisibility {
         visible: bool,
     },
+    /// The position is in physical pixels, relative to the bottom left of the window
+    SetCursorPosition {
+        position: Vec2,
+    },
+    SetCursorIcon {
+        icon: CursorIcon,
+    },
+    SetIcon {
+        icon: Option<WindowIcon>,
+    },
+    SetPosition {
+        x: i32,
+        y: i32,
+    },
+    SetResizeConstraints {
+        resize_constraints: WindowResizeConstraints,
+    },
 }
 
 /// Defines the way a window is displayed
@@ -90,19 +122,92 @@ pub enum WindowMode {
     Fullscreen { use_size: bool },
 }
 
+/// The minimum and maximum logical size a window can be resized to. Use `f32::INFINITY` for no maximum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowResizeConstraints {
+    pub min_width: f32,
+    pub min_height: f32,
+    pub max_width: f32,
+    pub max_height: f32,
+}
+
+impl Default for WindowResizeConstraints {
+    fn default() -> Self {
+        Self {
+            min_width: 180.,
+            min_height: 120.,
+            max_width: f32::INFINITY,
+            max_height: f32::INFINITY,
+        }
+    }
+}
+
+impl WindowResizeConstraints {
+    /// Returns constraints where the minimum is at least 1 and the maximum is never smaller than the minimum
+    pub fn check_constraints(&self) -> Self {
+        let min_width = self.min_width.max(1.);
+        let min_height = self.min_height.max(1.);
+        Self {
+            min_width,
+            min_height,
+            max_width: self.max_width.max(min_width),
+            max_height: self.max_height.max(min_height),
+        }
+    }
+
+    /// Clamps the given logical size to these constraints
+    pub fn clamp(&self, width: f32, height: f32) -> (f32, f32) {
+        (
+            width.max(self.min_width).min(self.max_width),
+            height.max(self.min_height).min(self.max_height),
+        )
+    }
+}
+
+/// An RGBA8 image used as a window's icon
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowIcon {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Selects the monitor a window is made fullscreen on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorSelection {
+    /// The monitor the window is currently on (or the primary monitor if the window hasn't been created yet)
+    Current,
+    Primary,
+    /// The monitor at the given index in the platform's list of available monitors
+    Number(usize),
+}
+
 impl Window {
     pub fn new(id: WindowId, window_descriptor: &WindowDescriptor) -> Self {
         Window {
             id,
-            height: window_descriptor.height,
-            width: window_descriptor.width,
+            requested_width: window_descriptor.width,
+            requested_height: window_descriptor.height,
+            physical_width: (window_descriptor.width as f64
+                * window_descriptor.scale_factor_override.unwrap_or(1.0))
+                as u32,
+            physical_height: (window_descriptor.height as f64
+                * window_descriptor.scale_factor_override.unwrap_or(1.0))
+                as u32,
+            scale_factor_override: window_descriptor.scale_factor_override,
+            backend_scale_factor: 1.0,
             title: window_descriptor.title.clone(),
             vsync: window_descriptor.vsync,
             resizable: window_descriptor.resizable,
             decorations: window_descriptor.decorations,
             cursor_visible: window_descriptor.cursor_visible,
             cursor_locked: window_descriptor.cursor_locked,
+            cursor_icon: window_descriptor.cursor_icon,
+            position: window_descriptor.position,
             mode: window_descriptor.mode,
+            monitor: window_descriptor.monitor,
+            resize_constraints: window_descriptor.resize_constraints.check_constraints(),
+            aspect_ratio: window_descriptor.aspect_ratio,
             #[cfg(target_arch = "wasm32")]
             canvas: window_descriptor.canvas.clone(),
             command_queue: Vec::new(),
@@ -114,27 +219,115 @@ impl Window {
         self.id
     }
 
+    /// The current logical width of the window's client area
     #[inline]
-    pub fn width(&self) -> u32 {
-        self.width
+    pub fn width(&self) -> f32 {
+        (self.physical_width as f64 / self.scale_factor()) as f32
     }
 
+    /// The current logical height of the window's client area
     #[inline]
-    pub fn height(&self) -> u32 {
-        self.height
+    pub fn height(&self) -> f32 {
+        (self.physical_height as f64 / self.scale_factor()) as f32
     }
 
-    pub fn set_resolution(&mut self, width: u32, height: u32) {
-        self.width = width;
-        self.height = height;
-        self.command_queue
-            .push(WindowCommand::SetResolution { width, height });
+    /// The logical width requested by the user. It may differ from [Window::width] if the platform doesn't honor the
+    /// request or the window was resized since.
+    #[inline]
+    pub fn requested_width(&self) -> f32 {
+        self.requested_width
+    }
+
+    /// The logical height requested by the user
+    #[inline]
+    pub fn requested_height(&self) -> f32 {
+        self.requested_height
+    }
+
+    /// The window's client area width in physical pixels
+    #[inline]
+    pub fn physical_width(&self) -> u32 {
+        self.physical_width
+    }
+
+    /// The window's client area height in physical pixels
+    #[inline]
+    pub fn physical_height(&self) -> u32 {
+        self.physical_height
+    }
+
+    /// Requests a new logical size for the window's client area
+    pub fn set_resolution(&mut self, width: f32, height: f32) {
+        if self.requested_width == width && self.requested_height == height {
+            return;
+        }
+        self.requested_width = width;
+        self.requested_height = height;
+        self.command_queue.push(WindowCommand::SetResolution {
+            logical_resolution: (width, height),
+            scale_factor: self.scale_factor(),
+        });
+    }
+
+    #[doc(hidden)]
+    pub fn update_resolution_from_backend(&mut self, physical_width: u32, physical_height: u32) {
+        self.physical_width = physical_width;
+        self.physical_height = physical_height;
+    }
+
+    /// The ratio of physical pixels to logical pixels used by the window. This is the
+    /// [override](Window::scale_factor_override) if one is set, and the platform's
+    /// [scale factor](Window::backend_scale_factor) otherwise.
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor_override
+            .unwrap_or(self.backend_scale_factor)
+    }
+
+    /// The scale factor the platform reports for the window, ignoring any override
+    #[inline]
+    pub fn backend_scale_factor(&self) -> f64 {
+        self.backend_scale_factor
+    }
+
+    #[inline]
+    pub fn scale_factor_override(&self) -> Option<f64> {
+        self.scale_factor_override
+    }
+
+    /// Forces the window to use the given scale factor instead of the platform's. `None` goes back to the platform's
+    /// scale factor. The window is resized so its logical size stays the same.
+    pub fn set_scale_factor_override(&mut self, scale_factor: Option<f64>) {
+        self.scale_factor_override = scale_factor;
+        self.command_queue.push(WindowCommand::SetScaleFactor {
+            scale_factor: self.scale_factor(),
+        });
+        self.command_queue.push(WindowCommand::SetResolution {
+            logical_resolution: (self.requested_width, self.requested_height),
+            scale_factor: self.scale_factor(),
+        });
     }
 
     #[doc(hidden)]
-    pub fn update_resolution_from_backend(&mut self, width: u32, height: u32) {
-        self.width = width;
-        self.height = height;
+    pub fn update_scale_factor_from_backend(&mut self, scale_factor: f64) {
+        self.backend_scale_factor = scale_factor;
+    }
+
+    /// The position of the window's top left corner on the desktop in physical pixels, if it is known
+    #[inline]
+    pub fn position(&self) -> Option<(i32, i32)> {
+        self.position
+    }
+
+    /// Moves the window's top left corner to the given position on the desktop in physical pixels
+    pub fn set_position(&mut self, x: i32, y: i32) {
+        self.position = Some((x, y));
+        self.command_queue.push(WindowCommand::SetPosition { x, y });
+    }
+
+    #[doc(hidden)]
+    pub fn update_position_from_backend(&mut self, x: i32, y: i32) {
+        self.position = Some((x, y));
     }
 
     pub fn title(&self) -> &str {
@@ -196,6 +389,30 @@ impl Window {
         });
     }
 
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.cursor_icon
+    }
+
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.cursor_icon = icon;
+        self.command_queue
+            .push(WindowCommand::SetCursorIcon { icon });
+    }
+
+    /// Sets the icon shown in the window's title bar and the taskbar. `None` restores the platform's default icon.
+    pub fn set_icon(&mut self, icon: Option<WindowIcon>) {
+        self.command_queue.push(WindowCommand::SetIcon { icon });
+    }
+
+    /// Moves the cursor to the given position in logical pixels, relative to the bottom left of the window (the same
+    /// coordinates as [CursorMoved](crate::CursorMoved)). Useful for keeping the cursor centered on platforms that can
+    /// only confine it to the window instead of locking it.
+    pub fn set_cursor_position(&mut self, position: Vec2) {
+        self.command_queue.push(WindowCommand::SetCursorPosition {
+            position: position * self.scale_factor() as f32,
+        });
+    }
+
     pub fn mode(&self) -> WindowMode {
         self.mode
     }
@@ -204,10 +421,73 @@ impl Window {
         self.mode = mode;
         self.command_queue.push(WindowCommand::SetWindowMode {
             mode,
-            resolution: (self.width, self.height),
+            monitor: self.monitor,
+            resolution: (self.physical_width, self.physical_height),
         });
     }
 
+    /// Switches between windowed and borderless fullscreen mode
+    pub fn toggle_fullscreen(&mut self) {
+        match self.mode {
+            WindowMode::Windowed => self.set_mode(WindowMode::BorderlessFullscreen),
+            _ => self.set_mode(WindowMode::Windowed),
+        }
+    }
+
+    pub fn monitor(&self) -> MonitorSelection {
+        self.monitor
+    }
+
+    /// Sets the monitor used by fullscreen modes. A fullscreen window moves to the new monitor immediately.
+    pub fn set_monitor(&mut self, monitor: MonitorSelection) {
+        self.monitor = monitor;
+        match self.mode {
+            WindowMode::Windowed => {}
+            mode => self.set_mode(mode),
+        }
+    }
+
+    pub fn resize_constraints(&self) -> WindowResizeConstraints {
+        self.resize_constraints
+    }
+
+    /// Limits the logical size the window can be resized to
+    pub fn set_resize_constraints(&mut self, resize_constraints: WindowResizeConstraints) {
+        let resize_constraints = resize_constraints.check_constraints();
+        self.resize_constraints = resize_constraints;
+        self.command_queue
+            .push(WindowCommand::SetResizeConstraints { resize_constraints });
+    }
+
+    /// The width / height ratio the window is locked to, if any
+    pub fn aspect_ratio(&self) -> Option<f32> {
+        self.aspect_ratio
+    }
+
+    /// Locks the window to the given width / height ratio. The window is resized immediately to match the ratio while
+    /// keeping its width, and any later resizes are corrected to the ratio.
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: Option<f32>) {
+        self.aspect_ratio = aspect_ratio;
+        if let Some((width, height)) = self.aspect_corrected_size(self.width(), self.height()) {
+            self.set_resolution(width, height);
+        }
+    }
+
+    /// Returns the logical size closest to `width` x `height` that matches the window's aspect ratio and resize
+    /// constraints, or `None` if the size already matches or the window has no aspect ratio.
+    pub fn aspect_corrected_size(&self, width: f32, height: f32) -> Option<(f32, f32)> {
+        let aspect_ratio = self.aspect_ratio?;
+        let (_, corrected_height) = self.resize_constraints.clamp(width, width / aspect_ratio);
+        // clamping the height may have broken the ratio, so derive the width from it again
+        let corrected_width = corrected_height * aspect_ratio;
+        // allow a pixel of rounding error so corrections don't keep triggering resizes
+        if (corrected_width - width).abs() < 1. && (corrected_height - height).abs() < 1. {
+            None
+        } else {
+            Some((corrected_width, corrected_height))
+        }
+    }
+
     pub fn drain_commands(&mut self) -> impl Iterator<Item = WindowCommand> + '_ {
         self.command_queue.drain(..)
     }
@@ -215,15 +495,27 @@ impl Window {
 
 #[derive(Debug, Clone)]
 pub struct WindowDescriptor {
-    pub width: u32,
-    pub height: u32,
+    /// The logical width of the window's client area
+    pub width: f32,
+    /// The logical height of the window's client area
+    pub height: f32,
+    /// Overrides the platform's scale factor (ex: `Some(1.0)` to always use physical pixels)
+    pub scale_factor_override: Option<f64>,
     pub title: String,
     pub vsync: bool,
     pub resizable: bool,
     pub decorations: bool,
     pub cursor_visible: bool,
     pub cursor_locked: bool,
+    pub cursor_icon: CursorIcon,
+    /// The initial position of the window's top left corner in physical pixels. The platform picks a position if this
+    /// is `None`.
+    pub position: Option<(i32, i32)>,
     pub mode: WindowMode,
+    pub monitor: MonitorSelection,
+    pub resize_constraints: WindowResizeConstraints,
+    /// Locks the window to the given width / height ratio
+    pub aspect_ratio: Option<f32>,
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
 }
@@ -232,16 +524,51 @@ impl Default for WindowDescriptor {
     fn default() -> Self {
         WindowDescriptor {
             title: "bevy".to_string(),
-            width: 1280,
-            height: 720,
+            width: 1280.,
+            height: 720.,
+            scale_factor_override: None,
             vsync: true,
             resizable: true,
             decorations: true,
             cursor_locked: false,
             cursor_visible: true,
+            cursor_icon: CursorIcon::Default,
+            position: None,
             mode: WindowMode::Windowed,
+            monitor: MonitorSelection::Current,
+            resize_constraints: WindowResizeConstraints::default(),
+            aspect_ratio: None,
             #[cfg(target_arch = "wasm32")]
             canvas: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aspect_corrected_size() {
+        let mut window = Window::new(
+            WindowId::new(),
+            &WindowDescriptor {
+                width: 400.,
+                height: 300.,
+                aspect_ratio: Some(2.),
+                resize_constraints: WindowResizeConstraints {
+                    max_height: 250.,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        assert_eq!(window.aspect_corrected_size(400., 300.), Some((400., 200.)));
+        assert_eq!(window.aspect_corrected_size(400., 200.5), None);
+        // the height is limited by the constraints, so the width shrinks to keep the ratio
+        assert_eq!(window.aspect_corrected_size(800., 400.), Some((500., 250.)));
+
+        window.set_aspect_ratio(None);
+        assert_eq!(window.aspect_corrected_size(400., 300.), None);
+    }
+}
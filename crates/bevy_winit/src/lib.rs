@@ -1,4 +1,5 @@
 mod converters;
+mod update_pacer;
 mod winit_config;
 mod winit_windows;
 use bevy_input::{
@@ -9,11 +10,19 @@ use bevy_input::{
 pub use winit_config::*;
 pub use winit_windows::*;
 
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use update_pacer::UpdatePacer;
+
 use bevy_app::{prelude::*, AppExit};
 use bevy_ecs::{IntoThreadLocalSystem, Resources, World};
 use bevy_math::Vec2;
 use bevy_window::{
-    CreateWindow, CursorMoved, Window, WindowCloseRequested, WindowCreated, WindowResized, Windows,
+    CreateWindow, CursorMoved, FileDragAndDrop, ReceivedCharacter, RequestRedraw, Window,
+    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowFocused,
+    WindowResized, WindowScaleFactorChanged, Windows,
 };
 use winit::{
     event::{self, DeviceEvent, Event, WindowEvent},
@@ -44,37 +53,43 @@ fn change_window(_: &mut World, resources: &mut Resources) {
 
     for bevy_window in windows.iter_mut() {
         let id = bevy_window.id();
+        let scale_factor = bevy_window.scale_factor();
         for command in bevy_window.drain_commands() {
             match command {
                 bevy_window::WindowCommand::SetWindowMode {
                     mode,
+                    monitor,
                     resolution: (width, height),
                 } => {
                     let window = winit_windows.get_window(id).unwrap();
-                    match mode {
-                        bevy_window::WindowMode::BorderlessFullscreen => {
-                            window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)))
-                        }
-                        bevy_window::WindowMode::Fullscreen { use_size } => window.set_fullscreen(
-                            Some(winit::window::Fullscreen::Exclusive(match use_size {
-                                true => get_fitting_videomode(
-                                    &window.current_monitor().unwrap(),
-                                    width,
-                                    height,
-                                ),
-                                false => get_best_videomode(&window.current_monitor().unwrap()),
-                            })),
-                        ),
-                        bevy_window::WindowMode::Windowed => window.set_fullscreen(None),
-                    }
+                    let monitor = select_monitor(
+                        monitor,
+                        window.current_monitor(),
+                        window.primary_monitor(),
+                        window.available_monitors(),
+                    );
+                    window.set_fullscreen(get_fullscreen(mode, monitor, width, height));
                 }
                 bevy_window::WindowCommand::SetTitle { title } => {
                     let window = winit_windows.get_window(id).unwrap();
                     window.set_title(&title);
                 }
-                bevy_window::WindowCommand::SetResolution { width, height } => {
+                bevy_window::WindowCommand::SetResolution {
+                    logical_resolution: (width, height),
+                    scale_factor,
+                } => {
                     let window = winit_windows.get_window(id).unwrap();
-                    window.set_inner_size(winit::dpi::PhysicalSize::new(width, height));
+                    window.set_inner_size(
+                        winit::dpi::LogicalSize::new(width, height)
+                            .to_physical::<f64>(scale_factor),
+                    );
+                }
+                bevy_window::WindowCommand::SetScaleFactor { scale_factor } => {
+                    let mut window_scale_factor_changed_events = resources
+                        .get_mut::<Events<WindowScaleFactorChanged>>()
+                        .unwrap();
+                    window_scale_factor_changed_events
+                        .send(WindowScaleFactorChanged { id, scale_factor });
                 }
                 bevy_window::WindowCommand::SetVsync { .. } => (),
                 bevy_window::WindowCommand::SetResizable { resizable } => {
@@ -87,12 +102,45 @@ fn change_window(_: &mut World, resources: &mut Resources) {
                 }
                 bevy_window::WindowCommand::SetCursorLockMode { locked } => {
                     let window = winit_windows.get_window(id).unwrap();
-                    window.set_cursor_grab(locked).unwrap();
+                    if let Err(err) = window.set_cursor_grab(locked) {
+                        log::warn!("Unable to change cursor lock mode: {}", err);
+                    }
                 }
                 bevy_window::WindowCommand::SetCursorVisibility { visible } => {
                     let window = winit_windows.get_window(id).unwrap();
                     window.set_cursor_visible(visible);
                 }
+                bevy_window::WindowCommand::SetPosition { x, y } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
+                }
+                bevy_window::WindowCommand::SetResizeConstraints { resize_constraints } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    set_resize_constraints(window, resize_constraints, scale_factor);
+                }
+                bevy_window::WindowCommand::SetCursorPosition { position } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    let inner_size = window.inner_size();
+                    // move origin to top left
+                    let y_position = inner_size.height as f32 - position.y();
+                    if let Err(err) = window.set_cursor_position(winit::dpi::PhysicalPosition::new(
+                        position.x(),
+                        y_position,
+                    )) {
+                        log::warn!("Unable to set cursor position: {}", err);
+                    }
+                }
+                bevy_window::WindowCommand::SetCursorIcon { icon } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    window.set_cursor_icon(converters::convert_cursor_icon(icon));
+                }
+                bevy_window::WindowCommand::SetIcon { icon } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    match icon.map(converters::convert_window_icon).transpose() {
+                        Ok(icon) => window.set_window_icon(icon),
+                        Err(err) => log::warn!("Unable to set window icon: {}", err),
+                    }
+                }
             }
         }
     }
@@ -145,6 +193,8 @@ pub fn winit_runner(mut app: App) {
     let mut event_loop = EventLoop::new();
     let mut create_window_event_reader = EventReader::<CreateWindow>::default();
     let mut app_exit_event_reader = EventReader::<AppExit>::default();
+    let mut request_redraw_event_reader = EventReader::<RequestRedraw>::default();
+    let mut update_pacer = UpdatePacer::new(Instant::now());
 
     app.resources
         .insert_thread_local(EventLoopProxyPtr(
@@ -171,6 +221,8 @@ pub fn winit_runner(mut app: App) {
                               control_flow: &mut ControlFlow| {
         *control_flow = if cfg!(feature = "metal-auto-capture") {
             ControlFlow::Exit
+        } else if let Some(next_update) = update_pacer.wait_until() {
+            ControlFlow::WaitUntil(next_update)
         } else {
             ControlFlow::Poll
         };
@@ -181,6 +233,13 @@ pub fn winit_runner(mut app: App) {
             }
         }
 
+        if matches!(
+            event,
+            event::Event::WindowEvent { .. } | event::Event::DeviceEvent { .. }
+        ) {
+            update_pacer.received_event();
+        }
+
         match event {
             event::Event::WindowEvent {
                 event: WindowEvent::Resized(size),
@@ -193,11 +252,22 @@ pub fn winit_runner(mut app: App) {
                 let window = windows.get_mut(window_id).unwrap();
                 window.update_resolution_from_backend(size.width, size.height);
 
+                if let Some((width, height)) =
+                    window.aspect_corrected_size(window.width(), window.height())
+                {
+                    let winit_window = winit_windows.get_window(window_id).unwrap();
+                    winit_window.set_inner_size(
+                        winit::dpi::LogicalSize::new(width, height)
+                            .to_physical::<f64>(window.scale_factor()),
+                    );
+                }
+
                 let mut resize_events = app.resources.get_mut::<Events<WindowResized>>().unwrap();
                 resize_events.send(WindowResized {
                     id: window_id,
-                    height: window.height() as usize,
-                    width: window.width() as usize,
+                    width: window.width(),
+                    height: window.height(),
+                    timestamp: Instant::now(),
                 });
             }
             event::Event::WindowEvent {
@@ -212,25 +282,141 @@ pub fn winit_runner(mut app: App) {
                         .unwrap();
                     let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
                     let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
-                    window_close_requested_events.send(WindowCloseRequested { id: window_id });
+                    window_close_requested_events.send(WindowCloseRequested {
+                        id: window_id,
+                        timestamp: Instant::now(),
+                    });
+                }
+                WindowEvent::Focused(focused) => {
+                    let mut focused_events =
+                        app.resources.get_mut::<Events<WindowFocused>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    focused_events.send(WindowFocused {
+                        id: window_id,
+                        focused,
+                        timestamp: Instant::now(),
+                    });
                 }
                 WindowEvent::KeyboardInput { ref input, .. } => {
                     let mut keyboard_input_events =
                         app.resources.get_mut::<Events<KeyboardInput>>().unwrap();
-                    keyboard_input_events.send(converters::convert_keyboard_input(input));
+                    keyboard_input_events
+                        .send(converters::convert_keyboard_input(input, Instant::now()));
                 }
                 WindowEvent::CursorMoved { position, .. } => {
                     let mut cursor_moved_events =
                         app.resources.get_mut::<Events<CursorMoved>>().unwrap();
                     let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let windows = app.resources.get_mut::<Windows>().unwrap();
                     let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
-                    let window = winit_windows.get_window(window_id).unwrap();
-                    let inner_size = window.inner_size();
+                    let window = windows.get(window_id).unwrap();
+                    let position = position.to_logical::<f32>(window.scale_factor());
                     // move origin to bottom left
-                    let y_position = inner_size.height as f32 - position.y as f32;
+                    let y_position = window.height() - position.y;
                     cursor_moved_events.send(CursorMoved {
                         id: window_id,
-                        position: Vec2::new(position.x as f32, y_position as f32),
+                        position: Vec2::new(position.x, y_position),
+                        timestamp: Instant::now(),
+                    });
+                }
+                WindowEvent::ScaleFactorChanged {
+                    scale_factor,
+                    new_inner_size,
+                } => {
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let mut windows = app.resources.get_mut::<Windows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    let window = windows.get_mut(window_id).unwrap();
+
+                    let mut backend_scale_factor_changed_events = app
+                        .resources
+                        .get_mut::<Events<WindowBackendScaleFactorChanged>>()
+                        .unwrap();
+                    backend_scale_factor_changed_events.send(WindowBackendScaleFactorChanged {
+                        id: window_id,
+                        scale_factor,
+                    });
+
+                    let previous_scale_factor = window.scale_factor();
+                    window.update_scale_factor_from_backend(scale_factor);
+
+                    if let Some(scale_factor_override) = window.scale_factor_override() {
+                        // keep the window at the size implied by the override instead of the size suggested by the
+                        // platform
+                        *new_inner_size = winit::dpi::LogicalSize::new(
+                            window.requested_width(),
+                            window.requested_height(),
+                        )
+                        .to_physical::<u32>(scale_factor_override);
+                    } else if (previous_scale_factor - scale_factor).abs() > f64::EPSILON {
+                        let mut scale_factor_changed_events = app
+                            .resources
+                            .get_mut::<Events<WindowScaleFactorChanged>>()
+                            .unwrap();
+                        scale_factor_changed_events.send(WindowScaleFactorChanged {
+                            id: window_id,
+                            scale_factor,
+                        });
+                    }
+
+                    window.update_resolution_from_backend(
+                        new_inner_size.width,
+                        new_inner_size.height,
+                    );
+                    set_resize_constraints(
+                        winit_windows.get_window(window_id).unwrap(),
+                        window.resize_constraints(),
+                        window.scale_factor(),
+                    );
+                }
+                WindowEvent::Moved(position) => {
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let mut windows = app.resources.get_mut::<Windows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    let window = windows.get_mut(window_id).unwrap();
+                    window.update_position_from_backend(position.x, position.y);
+                }
+                WindowEvent::ReceivedCharacter(c) => {
+                    let mut char_input_events = app
+                        .resources
+                        .get_mut::<Events<ReceivedCharacter>>()
+                        .unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    char_input_events.send(ReceivedCharacter {
+                        id: window_id,
+                        char: c,
+                        timestamp: Instant::now(),
+                    });
+                }
+                WindowEvent::DroppedFile(path_buf) => {
+                    let mut events = app.resources.get_mut::<Events<FileDragAndDrop>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    events.send(FileDragAndDrop::DroppedFile {
+                        id: window_id,
+                        path_buf,
+                        timestamp: Instant::now(),
+                    });
+                }
+                WindowEvent::HoveredFile(path_buf) => {
+                    let mut events = app.resources.get_mut::<Events<FileDragAndDrop>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    events.send(FileDragAndDrop::HoveredFile {
+                        id: window_id,
+                        path_buf,
+                        timestamp: Instant::now(),
+                    });
+                }
+                WindowEvent::HoveredFileCancelled => {
+                    let mut events = app.resources.get_mut::<Events<FileDragAndDrop>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    events.send(FileDragAndDrop::HoveredFileCancelled {
+                        id: window_id,
+                        timestamp: Instant::now(),
                     });
                 }
                 WindowEvent::MouseInput { state, button, .. } => {
@@ -239,6 +425,7 @@ pub fn winit_runner(mut app: App) {
                     mouse_button_input_events.send(MouseButtonInput {
                         button: converters::convert_mouse_button(button),
                         state: converters::convert_element_state(state),
+                        timestamp: Instant::now(),
                     });
                 }
                 WindowEvent::MouseWheel { delta, .. } => match delta {
@@ -249,6 +436,7 @@ pub fn winit_runner(mut app: App) {
                             unit: MouseScrollUnit::Line,
                             x,
                             y,
+                            timestamp: Instant::now(),
                         });
                     }
                     event::MouseScrollDelta::PixelDelta(p) => {
@@ -258,19 +446,27 @@ pub fn winit_runner(mut app: App) {
                             unit: MouseScrollUnit::Pixel,
                             x: p.x as f32,
                             y: p.y as f32,
+                            timestamp: Instant::now(),
                         });
                     }
                 },
-                WindowEvent::Touch(mut touch) => {
+                WindowEvent::Touch(touch) => {
                     let mut touch_input_events =
                         app.resources.get_mut::<Events<TouchInput>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
                     let windows = app.resources.get_mut::<Windows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    let window = windows.get(window_id).unwrap();
+                    let mut location = touch.location.to_logical::<f32>(window.scale_factor());
                     // FIXME?: On Android window start is top while on PC/Linux/OSX on bottom
                     if cfg!(target_os = "android") {
-                        let window_height = windows.get_primary().unwrap().height();
-                        touch.location.y = window_height as f64 - touch.location.y;
+                        location.y = window.height() - location.y;
                     }
-                    touch_input_events.send(converters::convert_touch_input(touch));
+                    touch_input_events.send(converters::convert_touch_input(
+                        touch,
+                        Vec2::new(location.x, location.y),
+                        Instant::now(),
+                    ));
                 }
                 _ => {}
             },
@@ -280,16 +476,40 @@ pub fn winit_runner(mut app: App) {
                         app.resources.get_mut::<Events<MouseMotion>>().unwrap();
                     mouse_motion_events.send(MouseMotion {
                         delta: Vec2::new(delta.0 as f32, delta.1 as f32),
+                        timestamp: Instant::now(),
                     });
                 }
             }
+            event::Event::LoopDestroyed => {
+                // release windows, the renderer and audio output while the event loop still exists
+                app.teardown();
+            }
             event::Event::MainEventsCleared => {
+                let update_mode = app
+                    .resources
+                    .get::<WinitConfig>()
+                    .map_or(UpdateMode::Continuous, |config| config.update_mode);
+                let now = Instant::now();
+                if !update_pacer.should_update(update_mode, now) {
+                    return;
+                }
+
                 handle_create_window_events(
                     &mut app.resources,
                     event_loop,
                     &mut create_window_event_reader,
                 );
                 app.update();
+
+                let redraw_requested = app.resources.get::<Events<RequestRedraw>>().map_or(
+                    false,
+                    |request_redraw_events| {
+                        request_redraw_event_reader
+                            .latest(&request_redraw_events)
+                            .is_some()
+                    },
+                );
+                update_pacer.updated(update_mode, now, redraw_requested);
             }
             _ => (),
         }
@@ -311,8 +531,8 @@ fn handle_create_window_events(
     let create_window_events = resources.get::<Events<CreateWindow>>().unwrap();
     let mut window_created_events = resources.get_mut::<Events<WindowCreated>>().unwrap();
     for create_window_event in create_window_event_reader.iter(&create_window_events) {
-        let window = Window::new(create_window_event.id, &create_window_event.descriptor);
-        winit_windows.create_window(event_loop, &window);
+        let mut window = Window::new(create_window_event.id, &create_window_event.descriptor);
+        winit_windows.create_window(event_loop, &mut window);
         let window_id = window.id();
         windows.add(window);
         window_created_events.send(WindowCreated { id: window_id });
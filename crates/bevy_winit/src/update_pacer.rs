@@ -0,0 +1,130 @@
+use crate::UpdateMode;
+use std::time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// Decides when the winit runner updates the App, following the [UpdateMode]
+#[derive(Debug)]
+pub(crate) struct UpdatePacer {
+    last_update: Instant,
+    /// Whether a window or input event arrived since the last update
+    received_event: bool,
+    redraw_requested: bool,
+    /// The time the next update is due, or None to update as soon as possible
+    next_update: Option<Instant>,
+}
+
+impl UpdatePacer {
+    pub fn new(now: Instant) -> Self {
+        UpdatePacer {
+            last_update: now,
+            received_event: false,
+            // the first update always runs
+            redraw_requested: true,
+            next_update: None,
+        }
+    }
+
+    /// The time the event loop can sleep until, or None if it shouldn't sleep
+    pub fn wait_until(&self) -> Option<Instant> {
+        self.next_update
+    }
+
+    pub fn received_event(&mut self) {
+        self.received_event = true;
+    }
+
+    /// Whether the App should update now. Otherwise the event loop waits until [UpdatePacer::wait_until].
+    pub fn should_update(&mut self, update_mode: UpdateMode, now: Instant) -> bool {
+        let next_update = match update_mode {
+            UpdateMode::Continuous => None,
+            UpdateMode::Limited { max_fps } => {
+                update_interval(max_fps).map(|interval| self.last_update + interval)
+            }
+            UpdateMode::Reactive { .. } if self.received_event || self.redraw_requested => None,
+            UpdateMode::Reactive { max_wait } => Some(self.last_update + max_wait),
+        };
+        self.next_update = next_update.filter(|next_update| *next_update > now);
+        self.next_update.is_none()
+    }
+
+    /// Records an update that started at `now`
+    pub fn updated(&mut self, update_mode: UpdateMode, now: Instant, redraw_requested: bool) {
+        self.last_update = now;
+        self.received_event = false;
+        self.redraw_requested = redraw_requested;
+        self.next_update = match update_mode {
+            UpdateMode::Continuous => None,
+            UpdateMode::Limited { max_fps } => {
+                update_interval(max_fps).map(|interval| now + interval)
+            }
+            UpdateMode::Reactive { .. } if redraw_requested => None,
+            UpdateMode::Reactive { max_wait } => Some(now + max_wait),
+        };
+    }
+}
+
+/// The time between updates limited to `max_fps`, or None if `max_fps` isn't positive and updates aren't limited
+fn update_interval(max_fps: f64) -> Option<Duration> {
+    if max_fps > 0.0 {
+        Some(Duration::from_secs_f64(1.0 / max_fps))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UpdatePacer;
+    use crate::UpdateMode;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn limited_updates() {
+        let start = Instant::now();
+        let mode = UpdateMode::Limited { max_fps: 10.0 };
+        let mut pacer = UpdatePacer::new(start);
+        pacer.updated(mode, start, false);
+
+        let early = start + Duration::from_millis(40);
+        assert!(!pacer.should_update(mode, early));
+        assert_eq!(pacer.wait_until(), Some(start + Duration::from_millis(100)));
+        assert!(pacer.should_update(mode, start + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn limits_that_are_not_positive_are_ignored() {
+        let start = Instant::now();
+        for &max_fps in [0.0, -30.0, f64::NAN].iter() {
+            let mode = UpdateMode::Limited { max_fps };
+            let mut pacer = UpdatePacer::new(start);
+            pacer.updated(mode, start, false);
+            assert_eq!(pacer.wait_until(), None);
+            assert!(pacer.should_update(mode, start));
+        }
+    }
+
+    #[test]
+    fn reactive_updates() {
+        let start = Instant::now();
+        let mode = UpdateMode::Reactive {
+            max_wait: Duration::from_secs(5),
+        };
+        let mut pacer = UpdatePacer::new(start);
+        assert!(pacer.should_update(mode, start));
+        pacer.updated(mode, start, false);
+
+        let later = start + Duration::from_secs(1);
+        assert!(!pacer.should_update(mode, later));
+        pacer.received_event();
+        assert!(pacer.should_update(mode, later));
+        pacer.updated(mode, later, true);
+        assert_eq!(pacer.wait_until(), None);
+        assert!(pacer.should_update(mode, later));
+        pacer.updated(mode, later, false);
+        assert!(pacer.should_update(mode, later + Duration::from_secs(5)));
+    }
+}
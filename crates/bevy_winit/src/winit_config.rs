@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// A resource for configuring usage of the `rust_winit` library.
 #[derive(Debug, Default)]
 pub struct WinitConfig {
@@ -12,4 +14,26 @@ pub struct WinitConfig {
     /// `openbsd`. If set to true on an unsupported platform
     /// [run](bevy_app::App::run) will panic.
     pub return_from_run: bool,
+    /// How often the App is updated. It can be changed at any time, for example to save power while a menu is open.
+    pub update_mode: UpdateMode,
+}
+
+/// How often the winit event loop updates the App
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateMode {
+    /// Updates as often as possible. With vsync, that's the refresh rate of the display.
+    Continuous,
+    /// Updates at most `max_fps` times per second and sleeps in between. Values that aren't positive don't limit
+    /// updates, like [UpdateMode::Continuous].
+    Limited { max_fps: f64 },
+    /// Only updates when a window or input event arrives, a [RequestRedraw](bevy_window::RequestRedraw) event was
+    /// sent during the last update, or `max_wait` passed since the last update. Saves power in tools and menus that
+    /// don't animate.
+    Reactive { max_wait: Duration },
+}
+
+impl Default for UpdateMode {
+    fn default() -> Self {
+        UpdateMode::Continuous
+    }
 }
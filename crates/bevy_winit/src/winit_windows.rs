@@ -1,5 +1,5 @@
 use bevy_utils::HashMap;
-use bevy_window::{Window, WindowId, WindowMode};
+use bevy_window::{MonitorSelection, Window, WindowId, WindowMode, WindowResizeConstraints};
 
 #[derive(Debug, Default)]
 pub struct WinitWindows {
@@ -12,7 +12,7 @@ impl WinitWindows {
     pub fn create_window(
         &mut self,
         event_loop: &winit::event_loop::EventLoopWindowTarget<()>,
-        window: &Window,
+        window: &mut Window,
     ) {
         #[cfg(target_os = "windows")]
         let mut winit_window_builder = {
@@ -24,26 +24,34 @@ impl WinitWindows {
         let mut winit_window_builder = winit::window::WindowBuilder::new();
 
         winit_window_builder = match window.mode() {
-            WindowMode::BorderlessFullscreen => winit_window_builder.with_fullscreen(Some(
-                winit::window::Fullscreen::Borderless(event_loop.primary_monitor()),
-            )),
-            WindowMode::Fullscreen { use_size } => winit_window_builder.with_fullscreen(Some(
-                winit::window::Fullscreen::Exclusive(match use_size {
-                    true => get_fitting_videomode(
-                        &event_loop.primary_monitor().unwrap(),
-                        window.width(),
-                        window.height(),
-                    ),
-                    false => get_best_videomode(&event_loop.primary_monitor().unwrap()),
-                }),
-            )),
-            _ => winit_window_builder
-                .with_inner_size(winit::dpi::PhysicalSize::new(
-                    window.width(),
-                    window.height(),
+            WindowMode::Windowed => {
+                let logical_size = winit::dpi::LogicalSize::new(
+                    window.requested_width(),
+                    window.requested_height(),
+                );
+                let winit_window_builder = match window.scale_factor_override() {
+                    Some(scale_factor) => winit_window_builder
+                        .with_inner_size(logical_size.to_physical::<f64>(scale_factor)),
+                    None => winit_window_builder.with_inner_size(logical_size),
+                };
+                winit_window_builder
+                    .with_resizable(window.resizable())
+                    .with_decorations(window.decorations())
+            }
+            mode => {
+                let monitor = select_monitor(
+                    window.monitor(),
+                    None,
+                    event_loop.primary_monitor(),
+                    event_loop.available_monitors(),
+                );
+                winit_window_builder.with_fullscreen(get_fullscreen(
+                    mode,
+                    monitor,
+                    window.physical_width(),
+                    window.physical_height(),
                 ))
-                .with_resizable(window.resizable())
-                .with_decorations(window.decorations()),
+            }
         };
 
         #[allow(unused_mut)]
@@ -78,6 +86,20 @@ impl WinitWindows {
         }
 
         winit_window.set_cursor_visible(window.cursor_visible());
+        winit_window.set_cursor_icon(crate::converters::convert_cursor_icon(window.cursor_icon()));
+
+        window.update_scale_factor_from_backend(winit_window.scale_factor());
+        let inner_size = winit_window.inner_size();
+        window.update_resolution_from_backend(inner_size.width, inner_size.height);
+        set_resize_constraints(
+            &winit_window,
+            window.resize_constraints(),
+            window.scale_factor(),
+        );
+
+        if let Some((x, y)) = window.position() {
+            winit_window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
+        }
 
         self.window_id_to_winit
             .insert(window.id(), winit_window.id());
@@ -113,6 +135,72 @@ impl WinitWindows {
         self.winit_to_window_id.get(&id).cloned()
     }
 }
+
+/// Applies logical resize constraints to a winit window. The constraints are converted with bevy's scale factor
+/// (which may be overridden) rather than winit's.
+pub fn set_resize_constraints(
+    winit_window: &winit::window::Window,
+    resize_constraints: WindowResizeConstraints,
+    scale_factor: f64,
+) {
+    winit_window.set_min_inner_size(Some(
+        winit::dpi::LogicalSize::new(resize_constraints.min_width, resize_constraints.min_height)
+            .to_physical::<f64>(scale_factor),
+    ));
+    let max_size =
+        if resize_constraints.max_width.is_finite() && resize_constraints.max_height.is_finite() {
+            Some(
+                winit::dpi::LogicalSize::new(
+                    resize_constraints.max_width,
+                    resize_constraints.max_height,
+                )
+                .to_physical::<f64>(scale_factor),
+            )
+        } else {
+            None
+        };
+    winit_window.set_max_inner_size(max_size);
+}
+
+/// Resolves a [MonitorSelection] against the monitors known to winit. `current` falls back to
+/// `primary` when the window's monitor is unknown (ex: before the window is created).
+pub fn select_monitor(
+    selection: MonitorSelection,
+    current: Option<winit::monitor::MonitorHandle>,
+    primary: Option<winit::monitor::MonitorHandle>,
+    mut available: impl Iterator<Item = winit::monitor::MonitorHandle>,
+) -> Option<winit::monitor::MonitorHandle> {
+    match selection {
+        MonitorSelection::Current => current.or(primary),
+        MonitorSelection::Primary => primary,
+        MonitorSelection::Number(number) => available.nth(number),
+    }
+}
+
+/// Returns the winit fullscreen setting for the given [WindowMode] on `monitor`. Exclusive
+/// fullscreen requires a monitor, so it falls back to windowed mode if none was found.
+pub fn get_fullscreen(
+    mode: WindowMode,
+    monitor: Option<winit::monitor::MonitorHandle>,
+    width: u32,
+    height: u32,
+) -> Option<winit::window::Fullscreen> {
+    match mode {
+        WindowMode::Windowed => None,
+        WindowMode::BorderlessFullscreen => Some(winit::window::Fullscreen::Borderless(monitor)),
+        WindowMode::Fullscreen { use_size } => match monitor {
+            Some(monitor) => Some(winit::window::Fullscreen::Exclusive(match use_size {
+                true => get_fitting_videomode(&monitor, width, height),
+                false => get_best_videomode(&monitor),
+            })),
+            None => {
+                log::warn!("No monitor found for exclusive fullscreen, using windowed mode");
+                None
+            }
+        },
+    }
+}
+
 pub fn get_fitting_videomode(
     monitor: &winit::monitor::MonitorHandle,
     width: u32,
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+/// This example shows how to light sprites with 2D lights, and how occluders cast shadows
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(orbit_system.system())
+        .run();
+}
+
+struct Orbit {
+    radius: f32,
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<LitColorMaterial>>,
+) {
+    let texture_handle = asset_server.load("branding/icon.png");
+    commands
+        .spawn(Camera2dComponents::default())
+        .spawn((AmbientLight2d {
+            color: Color::rgb_linear(0.05, 0.05, 0.08),
+        },))
+        // a floor for the shadows to fall on
+        .spawn(LitSpriteComponents {
+            material: materials.add(Color::rgb(0.6, 0.6, 0.6).into()),
+            sprite: Sprite::new(Vec2::new(1200.0, 800.0)),
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
+            ..Default::default()
+        })
+        .spawn(LitSpriteComponents {
+            material: materials.add(texture_handle.into()),
+            ..Default::default()
+        })
+        // a wall that casts a shadow, with an occluder of the same size as its sprite
+        .spawn(LitSpriteComponents {
+            material: materials.add(Color::rgb(0.3, 0.2, 0.2).into()),
+            sprite: Sprite::new(Vec2::new(40.0, 200.0)),
+            transform: Transform::from_translation(Vec3::new(-250.0, 0.0, 0.0)),
+            ..Default::default()
+        })
+        .with(LightOccluder2d {
+            shape: OccluderShape::Rectangle {
+                size: Vec2::new(40.0, 200.0),
+            },
+        })
+        .spawn((
+            PointLight2d {
+                color: Color::rgb(1.0, 0.8, 0.6),
+                intensity: 2.0,
+                radius: 600.0,
+                ..Default::default()
+            },
+            Orbit { radius: 350.0 },
+            Transform::default(),
+            GlobalTransform::default(),
+        ));
+}
+
+fn orbit_system(time: Res<Time>, mut query: Query<(&Orbit, &mut Transform)>) {
+    let angle = time.seconds_since_startup as f32 * 0.5;
+    for (orbit, mut transform) in query.iter_mut() {
+        transform.translation = Vec3::new(angle.cos(), angle.sin(), 0.0) * orbit.radius;
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::{
+    prelude::*,
+    render::camera::{OrthographicProjection, ScalingMode},
+};
+
+/// This example draws a pixel art character with a pixel perfect camera. Each texel of the sprite covers a whole
+/// number of pixels of the window, and the character's position is snapped to texels so it doesn't shimmer while it
+/// moves. Press up and down to change the zoom.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(move_character.system())
+        .add_system(zoom.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let texture_handle = asset_server.load("textures/rpg/chars/gabe/gabe-idle-run.png");
+    let texture_atlas = TextureAtlas::from_grid(texture_handle, Vec2::new(24.0, 24.0), 7, 1);
+    let mut animated_sprite = AnimatedSprite::default().with_clip(
+        "run",
+        SpriteAnimationClip::from_indices(1..7, 0.1, AnimationMode::Loop),
+    );
+    animated_sprite.play("run");
+
+    let mut camera = Camera2dComponents::default();
+    camera.orthographic_projection.scaling_mode = ScalingMode::PixelPerfect { zoom: 4 };
+    commands
+        .spawn(camera)
+        .spawn(SpriteSheetComponents {
+            texture_atlas: texture_atlases.add(texture_atlas),
+            ..Default::default()
+        })
+        .with(animated_sprite)
+        .with(PixelSnap);
+}
+
+fn move_character(time: Res<Time>, mut query: Query<With<PixelSnap, &mut Transform>>) {
+    for mut transform in query.iter_mut() {
+        let x = (time.seconds_since_startup as f32 * 0.5).sin() * 100.0;
+        transform.translation.set_x(x);
+    }
+}
+
+fn zoom(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut OrthographicProjection>) {
+    for mut projection in query.iter_mut() {
+        if let ScalingMode::PixelPerfect { zoom } = projection.scaling_mode {
+            let zoom = if keyboard_input.just_pressed(KeyCode::Up) {
+                zoom + 1
+            } else if keyboard_input.just_pressed(KeyCode::Down) && zoom > 1 {
+                zoom - 1
+            } else {
+                continue;
+            };
+            projection.scaling_mode = ScalingMode::PixelPerfect { zoom };
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, PrintDiagnosticsPlugin},
+    prelude::*,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// This example draws ten thousand sprites that share a material. They are drawn in a single batch instead of with a
+/// draw call each. For the best results, run it in release mode: ```cargo run --example sprite_batch --release
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(PrintDiagnosticsPlugin::default())
+        .add_startup_system(setup.system())
+        .add_system(rotate_sprites.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let material = materials.add(asset_server.load("branding/icon.png").into());
+    commands.spawn(Camera2dComponents::default());
+
+    let mut rng = StdRng::from_entropy();
+    for _ in 0..10_000 {
+        commands.spawn(BatchedSpriteComponents {
+            sprite: Sprite {
+                size: Vec2::new(16.0, 16.0),
+                color: Color::rgb(rng.gen(), rng.gen(), rng.gen()),
+                flip_x: rng.gen(),
+                ..Default::default()
+            },
+            material: material.clone(),
+            transform: Transform::from_translation(Vec3::new(
+                rng.gen_range(-600.0, 600.0),
+                rng.gen_range(-350.0, 350.0),
+                0.0,
+            )),
+            ..Default::default()
+        });
+    }
+}
+
+fn rotate_sprites(time: Res<Time>, mut query: Query<With<BatchedSprite, &mut Transform>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate(Quat::from_rotation_z(time.delta_seconds));
+    }
+}
@@ -4,22 +4,9 @@ fn main() {
     App::build()
         .add_plugins(DefaultPlugins)
         .add_startup_system(setup.system())
-        .add_system(animate_sprite_system.system())
         .run();
 }
 
-fn animate_sprite_system(
-    texture_atlases: Res<Assets<TextureAtlas>>,
-    mut query: Query<(&mut Timer, &mut TextureAtlasSprite, &Handle<TextureAtlas>)>,
-) {
-    for (timer, mut sprite, texture_atlas_handle) in query.iter_mut() {
-        if timer.finished {
-            let texture_atlas = texture_atlases.get(texture_atlas_handle).unwrap();
-            sprite.index = ((sprite.index as usize + 1) % texture_atlas.textures.len()) as u32;
-        }
-    }
-}
-
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -28,6 +15,11 @@ fn setup(
     let texture_handle = asset_server.load("textures/rpg/chars/gabe/gabe-idle-run.png");
     let texture_atlas = TextureAtlas::from_grid(texture_handle, Vec2::new(24.0, 24.0), 7, 1);
     let texture_atlas_handle = texture_atlases.add(texture_atlas);
+    let mut animated_sprite = AnimatedSprite::default().with_clip(
+        "run",
+        SpriteAnimationClip::from_indices(0..7, 0.1, AnimationMode::Loop),
+    );
+    animated_sprite.play("run");
     commands
         .spawn(Camera2dComponents::default())
         .spawn(SpriteSheetComponents {
@@ -35,5 +27,5 @@ fn setup(
             transform: Transform::from_scale(Vec3::splat(6.0)),
             ..Default::default()
         })
-        .with(Timer::from_seconds(0.1, true));
+        .with(animated_sprite);
 }
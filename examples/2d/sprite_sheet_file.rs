@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+/// This example loads a sprite sheet from a TexturePacker style JSON file and plays the "run" frames by name.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(animate_sprite_system.system())
+        .run();
+}
+
+fn animate_sprite_system(
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    mut query: Query<(&mut Timer, &mut TextureAtlasSprite, &Handle<TextureAtlas>)>,
+) {
+    for (timer, mut sprite, texture_atlas_handle) in query.iter_mut() {
+        if timer.finished {
+            // the atlas is loaded in the background, so it might not be available yet
+            if let Some(texture_atlas) = texture_atlases.get(texture_atlas_handle) {
+                let first = texture_atlas.get_texture_index_by_name("run_1").unwrap();
+                let last = texture_atlas.get_texture_index_by_name("run_6").unwrap();
+                let index = sprite.index as usize;
+                sprite.index = if index < first || index >= last {
+                    first
+                } else {
+                    index + 1
+                } as u32;
+            }
+        }
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let texture_atlas_handle =
+        asset_server.load("textures/rpg/chars/gabe/gabe-idle-run.atlas.json");
+    commands
+        .spawn(Camera2dComponents::default())
+        .spawn(SpriteSheetComponents {
+            texture_atlas: texture_atlas_handle,
+            transform: Transform::from_scale(Vec3::splat(6.0)),
+            ..Default::default()
+        })
+        .with(Timer::from_seconds(0.1, true));
+}
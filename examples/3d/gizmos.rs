@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+/// This example shows how to draw debug shapes with Gizmos. Gizmos only last one frame, so they are drawn by a system
+/// that runs every frame.
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(draw_gizmos_system.system())
+        .run();
+}
+
+struct Rotating;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn(PbrComponents {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_translation(Vec3::new(0.0, 0.5, 0.0)),
+            ..Default::default()
+        })
+        .with(Rotating)
+        .spawn(LightComponents {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dComponents {
+            transform: Transform::from_translation(Vec3::new(-3.0, 5.0, 8.0))
+                .looking_at(Vec3::default(), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
+
+fn draw_gizmos_system(
+    time: Res<Time>,
+    mut gizmos: ResMut<Gizmos>,
+    mut query: Query<With<Rotating, (&mut Transform, &GlobalTransform)>>,
+) {
+    for (mut transform, global_transform) in query.iter_mut() {
+        transform.rotate(Quat::from_rotation_y(time.delta_seconds));
+        gizmos.axes(global_transform, 1.5);
+        gizmos.cuboid(
+            &transform.mul_transform(Transform::from_scale(Vec3::splat(1.2))),
+            Color::rgb(1.0, 1.0, 0.0),
+        );
+    }
+
+    let height = time.seconds_since_startup.sin() as f32;
+    gizmos.sphere(
+        Vec3::new(2.0, 1.0 + height, 0.0),
+        0.5,
+        Color::hsl(200.0, 0.8, 0.6),
+    );
+    gizmos.capsule(
+        Vec3::new(-2.0, 0.5, 0.0),
+        Vec3::new(-2.0, 1.5, 0.0),
+        0.3,
+        Color::GREEN,
+    );
+    gizmos.arrow(
+        Vec3::new(0.0, 0.0, 2.0),
+        Vec3::new(0.0, 0.0, 3.0),
+        Color::RED,
+    );
+    gizmos.circle(Vec3::zero(), Vec3::unit_y(), 3.0, Color::WHITE);
+}
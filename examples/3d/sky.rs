@@ -0,0 +1,45 @@
+use bevy::{prelude::*, render::camera::Exposure};
+
+/// Draws a procedural sky that lights the scene, with the sun going round once every 20 seconds
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(SkyPlugin)
+        .add_startup_system(setup.system())
+        .add_system(sun_system.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // the sky is as bright as a real one, so the camera needs a daylight exposure
+    let mut camera = Camera3dComponents {
+        transform: Transform::from_translation(Vec3::new(-3.0, 2.0, 8.0))
+            .looking_at(Vec3::new(0.0, 2.0, 0.0), Vec3::unit_y()),
+        ..Default::default()
+    };
+    camera.camera.exposure = Some(Exposure::SUNLIGHT);
+
+    commands
+        .spawn(PbrComponents {
+            mesh: meshes.add(Mesh::from(shape::Plane { size: 20.0 })),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        .spawn(PbrComponents {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_translation(Vec3::new(0.0, 0.5, 0.0)),
+            ..Default::default()
+        })
+        .spawn(camera);
+}
+
+fn sun_system(time: Res<Time>, mut sky: ResMut<Sky>) {
+    let angle = time.seconds_since_startup as f32 * std::f32::consts::PI / 10.0;
+    sky.sun_direction = Vec3::new(angle.cos(), angle.sin(), 0.3).normalize();
+}
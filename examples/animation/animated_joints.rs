@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+/// This example illustrates how to animate the joints of a hierarchy with AnimationClips. The arm's segments are
+/// children of each other, so each joint's rotation carries the segments after it along. Press 1 and 2 to cross-fade
+/// between a slow swing and a fast wave, space to pause the animation, and the left and right arrow keys to change its
+/// speed.
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .init_resource::<ArmClips>()
+        .add_startup_system(setup.system())
+        .add_system(control_system.system())
+        .run();
+}
+
+#[derive(Default)]
+struct ArmClips {
+    swing: Handle<AnimationClip>,
+    wave: Handle<AnimationClip>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut clips: ResMut<Assets<AnimationClip>>,
+    mut arm_clips: ResMut<ArmClips>,
+) {
+    let segment_mesh = meshes.add(Mesh::from(shape::Cube { size: 0.5 }));
+    let segment_material = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+    let segment = |translation: Vec3| PbrComponents {
+        mesh: segment_mesh.clone(),
+        material: segment_material.clone(),
+        transform: Transform::from_translation(translation),
+        ..Default::default()
+    };
+
+    // each joint swings back and forth, the later ones a bit out of phase
+    let swing = |angle: f32, period: f32, offset: f32| {
+        JointCurves::default().with_rotation(Curve::new(vec![
+            (0.0, Quat::from_rotation_z(-angle)),
+            ((0.5 + offset) * period, Quat::from_rotation_z(angle)),
+            (period, Quat::from_rotation_z(-angle)),
+        ]))
+    };
+    arm_clips.swing = clips.add(AnimationClip::new(vec![
+        swing(0.3, 2.0, 0.0),
+        swing(0.6, 2.0, 0.1),
+        swing(0.9, 2.0, 0.2),
+    ]));
+    arm_clips.wave = clips.add(AnimationClip::new(vec![
+        swing(0.1, 0.6, 0.0),
+        swing(0.2, 0.6, 0.0),
+        swing(1.2, 0.6, 0.2),
+    ]));
+
+    let mut joints = Vec::new();
+    commands.spawn(segment(Vec3::zero()));
+    joints.push(commands.current_entity().unwrap());
+    commands.with_children(|parent| {
+        parent.spawn(segment(Vec3::new(0.0, 0.75, 0.0)));
+        joints.push(parent.current_entity().unwrap());
+        parent.with_children(|parent| {
+            parent.spawn(segment(Vec3::new(0.0, 0.75, 0.0)));
+            joints.push(parent.current_entity().unwrap());
+        });
+    });
+
+    let mut player = AnimationPlayer::new(joints);
+    player.play(arm_clips.swing.clone());
+    commands
+        .spawn((player,))
+        // light
+        .spawn(LightComponents {
+            transform: Transform::from_translation(Vec3::new(4.0, 5.0, 4.0)),
+            ..Default::default()
+        })
+        // camera
+        .spawn(Camera3dComponents {
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, 5.0))
+                .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
+
+fn control_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    arm_clips: Res<ArmClips>,
+    mut query: Query<&mut AnimationPlayer>,
+) {
+    for mut player in query.iter_mut() {
+        if keyboard_input.just_pressed(KeyCode::Key1) {
+            player.cross_fade(arm_clips.swing.clone(), 0.5);
+        }
+        if keyboard_input.just_pressed(KeyCode::Key2) {
+            player.cross_fade(arm_clips.wave.clone(), 0.5);
+        }
+        if keyboard_input.just_pressed(KeyCode::Space) {
+            if player.is_paused() {
+                player.resume();
+            } else {
+                player.pause();
+            }
+        }
+        if keyboard_input.just_pressed(KeyCode::Right) {
+            player.speed *= 2.0;
+        }
+        if keyboard_input.just_pressed(KeyCode::Left) {
+            player.speed /= 2.0;
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::{
+    animation::{
+        ColorMaterialColorLens, TransformPositionLens, TransformScaleLens, UiPositionLens,
+    },
+    prelude::*,
+};
+
+/// This example illustrates how to animate transforms, material colors and UI styles with Tweens. A sprite bounces
+/// back and forth while it pulses, a panel slides in and fades out once the slide completes.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(fade_out_system.system())
+        .run();
+}
+
+const PANEL_SLIDE: u64 = 1;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let texture_handle = asset_server.load("branding/icon.png");
+    commands
+        .spawn(Camera2dComponents::default())
+        .spawn(UiCameraComponents::default())
+        .spawn(SpriteComponents {
+            material: materials.add(texture_handle.into()),
+            ..Default::default()
+        })
+        .with(
+            Tween::new(
+                EaseFunction::BounceOut,
+                1.5,
+                TransformPositionLens {
+                    start: Vec3::new(-300.0, 0.0, 0.0),
+                    end: Vec3::new(300.0, 0.0, 0.0),
+                },
+            )
+            .with_repeat(TweenRepeat::Forever)
+            .with_mirrored(true),
+        )
+        .spawn(SpriteComponents {
+            material: materials.add(Color::rgb(0.3, 0.5, 0.9).into()),
+            sprite: Sprite::new(Vec2::new(50.0, 50.0)),
+            transform: Transform::from_translation(Vec3::new(0.0, -200.0, 0.0)),
+            ..Default::default()
+        })
+        .with(
+            Tween::new(
+                EaseFunction::SineInOut,
+                0.5,
+                TransformScaleLens {
+                    start: Vec3::one(),
+                    end: Vec3::splat(1.5),
+                },
+            )
+            .with_repeat(TweenRepeat::Forever)
+            .with_mirrored(true),
+        )
+        .spawn(NodeComponents {
+            style: Style {
+                size: Size::new(Val::Px(200.0), Val::Px(100.0)),
+                position_type: PositionType::Absolute,
+                ..Default::default()
+            },
+            material: materials.add(Color::rgb(0.9, 0.4, 0.2).into()),
+            visible: Visible {
+                is_transparent: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with(
+            Tween::new(
+                EaseFunction::BackOut,
+                1.0,
+                UiPositionLens {
+                    start: Rect {
+                        left: Val::Px(-200.0),
+                        top: Val::Px(20.0),
+                        ..Default::default()
+                    },
+                    end: Rect {
+                        left: Val::Px(20.0),
+                        top: Val::Px(20.0),
+                        ..Default::default()
+                    },
+                },
+            )
+            .with_delay(0.5)
+            .with_user_data(PANEL_SLIDE),
+        );
+}
+
+/// Fades the panel out once it slid in
+fn fade_out_system(
+    mut commands: Commands,
+    mut completed_reader: Local<EventReader<TweenCompleted>>,
+    completed_events: Res<Events<TweenCompleted>>,
+    materials: Res<Assets<ColorMaterial>>,
+    query: Query<&Handle<ColorMaterial>>,
+) {
+    for completed in completed_reader.iter(&completed_events) {
+        if completed.user_data != PANEL_SLIDE {
+            continue;
+        }
+        let color = query
+            .get(completed.entity)
+            .ok()
+            .and_then(|material| materials.get(material))
+            .map(|material| material.color)
+            .unwrap_or(Color::WHITE);
+        let mut transparent = color;
+        transparent.set_a(0.0);
+        commands.insert_one(
+            completed.entity,
+            Tween::new(
+                EaseFunction::QuadraticIn,
+                2.0,
+                ColorMaterialColorLens {
+                    start: color,
+                    end: transparent,
+                },
+            )
+            .with_delay(1.0),
+        );
+    }
+}
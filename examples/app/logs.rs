@@ -0,0 +1,26 @@
+use bevy::{
+    log::{LevelFilter, LogFilter, LogPlugin},
+    prelude::*,
+};
+
+/// This example illustrates how to configure which log records are written. Setting the `RUST_LOG` environment
+/// variable (ex: `RUST_LOG=warn,logs=trace`) overrides the filter at runtime.
+fn main() {
+    App::build()
+        .add_plugins_with(DefaultPlugins, |group| {
+            group.set(LogPlugin {
+                filter: LogFilter::new(LevelFilter::Warn)
+                    .with_module_level("logs", LevelFilter::Trace),
+            })
+        })
+        .add_system(log_system.system())
+        .run();
+}
+
+fn log_system() {
+    bevy::log::trace!("very noisy");
+    bevy::log::debug!("helpful for debugging");
+    bevy::log::info!("helpful information that is worth printing by default");
+    bevy::log::warn!("something bad happened that isn't a failure, but that's worth calling out");
+    bevy::log::error!("something failed");
+}
@@ -0,0 +1,49 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, PrintDiagnosticsPlugin},
+    prelude::*,
+    window::RequestRedraw,
+    winit::{UpdateMode, WinitConfig},
+};
+use std::time::Duration;
+
+/// This example illustrates how to limit how often the App updates, which saves power in tools and menus. Press 1 to
+/// update continuously, 2 to cap the frame rate at 30 fps and 3 to only update on input. Hold space to keep updating
+/// in the reactive mode, like a UI animation would.
+fn main() {
+    App::build()
+        .add_resource(WinitConfig {
+            update_mode: UpdateMode::Limited { max_fps: 30.0 },
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(PrintDiagnosticsPlugin::default())
+        .add_system(update_mode_system.system())
+        .run();
+}
+
+fn update_mode_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut winit_config: ResMut<WinitConfig>,
+    mut request_redraw_events: ResMut<Events<RequestRedraw>>,
+) {
+    let update_mode = if keyboard_input.just_pressed(KeyCode::Key1) {
+        UpdateMode::Continuous
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        UpdateMode::Limited { max_fps: 30.0 }
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        UpdateMode::Reactive {
+            max_wait: Duration::from_secs(5),
+        }
+    } else {
+        winit_config.update_mode
+    };
+    if update_mode != winit_config.update_mode {
+        println!("Update mode: {:?}", update_mode);
+        winit_config.update_mode = update_mode;
+    }
+
+    if keyboard_input.pressed(KeyCode::Space) {
+        request_redraw_events.send(RequestRedraw);
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+/// This example illustrates how to control audio after it started playing: space pauses and resumes the music,
+/// the up and down arrows change its volume, M mutes the music channel and S stops it.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(music_control_system.system())
+        .run();
+}
+
+struct Music(AudioInstance);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, audio: Res<Audio>) {
+    let music = asset_server.load("sounds/Windless Slopes.mp3");
+    let instance = audio.play_with_settings(
+        music,
+        PlaybackSettings::LOOP
+            .with_volume(0.5)
+            .in_channel(AudioChannel::MUSIC),
+    );
+    commands.insert_resource(Music(instance));
+}
+
+fn music_control_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    music: Res<Music>,
+    mut channels: ResMut<AudioChannels>,
+) {
+    let instance = &music.0;
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        if instance.is_paused() {
+            instance.resume();
+        } else {
+            instance.pause();
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        instance.set_volume((instance.volume() + 0.1).min(1.0));
+    }
+    if keyboard_input.just_pressed(KeyCode::Down) {
+        instance.set_volume((instance.volume() - 0.1).max(0.0));
+    }
+    if keyboard_input.just_pressed(KeyCode::M) {
+        let music_channel = channels.get_mut(AudioChannel::MUSIC);
+        music_channel.muted = !music_channel.muted;
+    }
+    if keyboard_input.just_pressed(KeyCode::S) {
+        instance.stop();
+    }
+}
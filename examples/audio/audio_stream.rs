@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+/// This example illustrates how to stream long audio files from the asset folder instead of loading them into memory
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .run();
+}
+
+fn setup(
+    asset_server: Res<AssetServer>,
+    mut audio_streams: ResMut<Assets<AudioStream>>,
+    audio: Res<Audio<AudioStream>>,
+) {
+    let music = audio_streams.add(AudioStream::new(
+        &asset_server,
+        "sounds/Windless Slopes.mp3",
+    ));
+    audio.play_with_settings(music, PlaybackSettings::LOOP);
+}
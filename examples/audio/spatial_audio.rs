@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+/// This example illustrates spatial audio: the music follows the sprite as it moves from one side of the listener to
+/// the other, and gets quieter when it's further away
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(move_emitter_system.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let music = asset_server.load("sounds/Windless Slopes.mp3");
+    let instance = audio.play_with_settings(music, PlaybackSettings::LOOP.spatial());
+    commands
+        .spawn(Camera2dComponents::default())
+        .with(AudioListener)
+        .spawn(SpriteComponents {
+            material: materials.add(Color::rgb(0.2, 0.6, 1.0).into()),
+            sprite: Sprite::new(Vec2::new(40.0, 40.0)),
+            ..Default::default()
+        })
+        .with(SpatialAudioEmitter::new(instance, 800.0));
+}
+
+fn move_emitter_system(
+    time: Res<Time>,
+    mut query: Query<With<SpatialAudioEmitter, &mut Transform>>,
+) {
+    for mut transform in query.iter_mut() {
+        let x = (time.seconds_since_startup as f32 * 0.5).sin() * 600.0;
+        transform.translation.set_x(x);
+    }
+}
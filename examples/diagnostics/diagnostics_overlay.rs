@@ -0,0 +1,39 @@
+use bevy::{
+    diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+    ui::DiagnosticsOverlayPlugin,
+};
+
+/// This example shows the diagnostics overlay on top of a scene with many sprites. Press F3 to show or hide it.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(EntityCountDiagnosticsPlugin::default())
+        .add_plugin(DiagnosticsOverlayPlugin::default())
+        .add_startup_system(setup.system())
+        .run();
+}
+
+fn setup(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    commands
+        .spawn(Camera2dComponents::default())
+        // the overlay is drawn by the ui camera
+        .spawn(UiCameraComponents::default());
+
+    for x in -10..10 {
+        for y in -10..10 {
+            commands.spawn(SpriteComponents {
+                material: materials
+                    .add(Color::rgb((x + 10) as f32 / 20.0, (y + 10) as f32 / 20.0, 0.5).into()),
+                transform: Transform::from_translation(Vec3::new(
+                    x as f32 * 30.0,
+                    y as f32 * 30.0,
+                    0.0,
+                )),
+                sprite: Sprite::new(Vec2::new(25.0, 25.0)),
+                ..Default::default()
+            });
+        }
+    }
+}
@@ -13,5 +13,13 @@ fn main() {
         // Any plugin can register diagnostics
         // Uncomment this to add some render resource diagnostics:
         // .add_plugin(bevy::wgpu::diagnostic::WgpuResourceDiagnosticsPlugin::default())
+        // Uncomment this to count entities, and the entities with each component:
+        // .add_plugin(bevy::diagnostic::EntityCountDiagnosticsPlugin { component_counts: true })
+        // Uncomment this to count the loaded textures and estimate their memory use:
+        // .add_plugin(
+        //     bevy::asset::diagnostic::AssetCountDiagnosticsPlugin::<Texture>::with_memory_estimate(
+        //         |texture| texture.data.len(),
+        //     ),
+        // )
         .run();
 }
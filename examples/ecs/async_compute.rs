@@ -0,0 +1,93 @@
+use bevy::{prelude::*, tasks::AsyncComputeTaskPool};
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// This example illustrates how to run expensive work on the AsyncComputeTaskPool without blocking frames. Each task
+/// is stored in a component and polled once per frame. When it completes, its result is used to spawn a cube.
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup_env.system())
+        .add_startup_system(add_assets.system())
+        .add_startup_system(spawn_tasks.system())
+        .add_system(handle_tasks.system())
+        .run();
+}
+
+const NUM_CUBES: u32 = 6;
+
+struct BoxMeshHandle(Handle<Mesh>);
+struct BoxMaterialHandle(Handle<StandardMaterial>);
+
+fn add_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(BoxMeshHandle(
+        meshes.add(Mesh::from(shape::Cube { size: 0.25 })),
+    ));
+    commands.insert_resource(BoxMaterialHandle(
+        materials.add(Color::rgb(1.0, 0.2, 0.3).into()),
+    ));
+}
+
+/// Spawns a task for every cube, which takes a while to compute its transform
+fn spawn_tasks(mut commands: Commands, thread_pool: Res<AsyncComputeTaskPool>) {
+    for x in 0..NUM_CUBES {
+        for y in 0..NUM_CUBES {
+            for z in 0..NUM_CUBES {
+                let task = thread_pool.spawn(async move {
+                    let mut rng = rand::thread_rng();
+                    let start_time = Instant::now();
+                    let duration = Duration::from_secs_f32(rng.gen_range(0.05, 0.2));
+                    while Instant::now() - start_time < duration {
+                        // spin, simulating work being done
+                    }
+
+                    Transform::from_translation(Vec3::new(x as f32, y as f32, z as f32))
+                });
+                commands.spawn((task,));
+            }
+        }
+    }
+}
+
+/// Polls the tasks once per frame and spawns a cube for each one that completed
+fn handle_tasks(
+    mut commands: Commands,
+    box_mesh_handle: Res<BoxMeshHandle>,
+    box_material_handle: Res<BoxMaterialHandle>,
+    mut transform_tasks: Query<(Entity, &mut Task<Transform>)>,
+) {
+    for (entity, mut task) in transform_tasks.iter_mut() {
+        if let Some(transform) = task.poll_once() {
+            commands
+                .insert(
+                    entity,
+                    PbrComponents {
+                        mesh: box_mesh_handle.0.clone(),
+                        material: box_material_handle.0.clone(),
+                        transform,
+                        ..Default::default()
+                    },
+                )
+                .remove_one::<Task<Transform>>(entity);
+        }
+    }
+}
+
+fn setup_env(mut commands: Commands) {
+    let offset = (NUM_CUBES - 1) as f32 / 2.0;
+    commands
+        .spawn(LightComponents {
+            transform: Transform::from_translation(Vec3::new(4.0, 12.0, 15.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dComponents {
+            transform: Transform::from_translation(Vec3::new(offset, offset, 15.0))
+                .looking_at(Vec3::new(offset, offset, 0.0), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
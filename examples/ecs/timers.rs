@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+/// This example illustrates Timers and Stopwatches. Timer and Stopwatch components are ticked automatically, while the
+/// ones stored in resources are ticked by hand.
+fn main() {
+    App::build()
+        .add_plugins(MinimalPlugins)
+        .add_resource(Countdown {
+            interval: Timer::from_seconds(1.0, true),
+            total: Timer::from_seconds(5.0, false),
+        })
+        .add_startup_system(setup.system())
+        .add_system(countdown_system.system())
+        .add_system(print_when_finished.system())
+        .run();
+}
+
+struct Countdown {
+    interval: Timer,
+    total: Timer,
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn((Timer::from_seconds(2.0, false),))
+        .spawn((Stopwatch::new(),));
+}
+
+fn countdown_system(time: Res<Time>, mut countdown: ResMut<Countdown>) {
+    countdown.total.tick(time.delta_seconds);
+    if countdown.interval.tick(time.delta_seconds).just_finished {
+        if countdown.total.finished {
+            countdown.interval.pause();
+        } else {
+            println!(
+                "{:.0}% of the countdown left",
+                countdown.total.percent_left() * 100.0
+            );
+        }
+    }
+}
+
+fn print_when_finished(timer_query: Query<&Timer>, stopwatch_query: Query<&Stopwatch>) {
+    for timer in timer_query.iter() {
+        if timer.just_finished {
+            for stopwatch in stopwatch_query.iter() {
+                println!("The entity timer finished after {:.2}s", stopwatch.elapsed);
+            }
+        }
+    }
+}
@@ -67,14 +67,15 @@ fn setup(
         })
         // scoreboard
         .spawn(TextComponents {
-            text: Text {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                value: "Score:".to_string(),
-                style: TextStyle {
+            text: Text::with_section(
+                "Score:",
+                asset_server.load("fonts/FiraSans-Bold.ttf"),
+                TextStyle {
                     color: Color::rgb(0.5, 0.5, 1.0),
                     font_size: 40.0,
                 },
-            },
+                Default::default(),
+            ),
             style: Style {
                 position_type: PositionType::Absolute,
                 position: Rect {
@@ -190,7 +191,7 @@ fn ball_movement_system(time: Res<Time>, mut ball_query: Query<(&Ball, &mut Tran
 
 fn scoreboard_system(scoreboard: Res<Scoreboard>, mut query: Query<&mut Text>) {
     for mut text in query.iter_mut() {
-        text.value = format!("Score: {}", scoreboard.score);
+        text.sections[0].value = format!("Score: {}", scoreboard.score);
     }
 }
 
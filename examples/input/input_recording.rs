@@ -0,0 +1,60 @@
+use bevy::{input::recording::InputRecordingPlugin, prelude::*};
+
+/// Records the input while you move the square with the arrow keys, and replays it when run with `playback`:
+///   cargo run --example input_recording --features serialize
+///   cargo run --example input_recording --features serialize -- playback
+fn main() {
+    // written to the temp directory so running the example doesn't add files to the working directory
+    let path = std::env::temp_dir().join("input_recording_example.ron");
+    println!("Input recording: {}", path.display());
+    let recording = if std::env::args().any(|arg| arg == "playback") {
+        InputRecordingPlugin::playback(path)
+    } else {
+        InputRecordingPlugin::record(path)
+    };
+
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(recording)
+        .add_startup_system(setup.system())
+        .add_system(movement_system.system())
+        .run();
+}
+
+struct Player;
+
+fn setup(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    commands
+        .spawn(Camera2dComponents::default())
+        .spawn(SpriteComponents {
+            material: materials.add(Color::rgb(0.5, 0.5, 1.0).into()),
+            sprite: Sprite::new(Vec2::new(50.0, 50.0)),
+            ..Default::default()
+        })
+        .with(Player);
+}
+
+/// Moves the player the same distance every frame, so the playback ends where the recording did even when the frame
+/// times differ
+fn movement_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<With<Player, &mut Transform>>,
+) {
+    let mut direction = Vec3::zero();
+    if keyboard_input.pressed(KeyCode::Left) {
+        *direction.x_mut() -= 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::Right) {
+        *direction.x_mut() += 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::Down) {
+        *direction.y_mut() -= 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::Up) {
+        *direction.y_mut() += 1.0;
+    }
+
+    for mut transform in query.iter_mut() {
+        transform.translation += direction * 5.0;
+    }
+}
@@ -58,6 +58,12 @@ fn load_scene_system(asset_server: Res<AssetServer>, mut scene_spawner: ResMut<S
     // This guarantees that it will not overwrite existing entities.
     scene_spawner.spawn_dynamic(scene_handle);
 
+    // Scenes can contain instances of other scenes, with overrides for some of the properties of their components.
+    // This one spawns `load_scene_example.scn` again, but with a different x for the ComponentA of its second entity.
+    let instance_handle: Handle<DynamicScene> =
+        asset_server.load("scenes/load_scene_example_instance.scn");
+    scene_spawner.spawn_dynamic(instance_handle);
+
     // This tells the AssetServer to watch for changes to assets.
     // It enables our scenes to automatically reload in game when we modify their files
     asset_server.watch_for_changes().unwrap();
@@ -92,12 +98,19 @@ fn save_scene_system(_world: &mut World, resources: &mut Resources) {
     let scene = DynamicScene::from_world(&world, &type_registry.component.read());
 
     // Scenes can be serialized like this:
-    println!(
-        "{}",
-        scene.serialize_ron(&type_registry.property.read()).unwrap()
-    );
+    let serialized_scene = scene.serialize_ron(&type_registry.property.read()).unwrap();
+    println!("{}", serialized_scene);
 
-    // TODO: save scene
+    // Serialized scenes can be written to disk and loaded later using the AssetServer, just like
+    // `scenes/load_scene_example.scn` is loaded in `load_scene_system`. This example writes to the temp directory so
+    // running it doesn't add files to the asset folder.
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let path = std::env::temp_dir().join("load_scene_example-new.scn");
+        std::fs::write(&path, serialized_scene.as_bytes())
+            .expect("Error while writing scene to file");
+        println!("Saved the scene to {}", path.display());
+    }
 }
 
 // This is only necessary for the info message in the UI. See examples/ui/text.rs for a standalone text example.
@@ -109,14 +122,15 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 align_self: AlignSelf::FlexEnd,
                 ..Default::default()
             },
-            text: Text {
-                value: "Nothing to see in this window! Check the console output!".to_string(),
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                style: TextStyle {
+            text: Text::with_section(
+                "Nothing to see in this window! Check the console output!",
+                asset_server.load("fonts/FiraSans-Bold.ttf"),
+                TextStyle {
                     font_size: 50.0,
                     color: Color::WHITE,
                 },
-            },
+                Default::default(),
+            ),
             ..Default::default()
         });
 }
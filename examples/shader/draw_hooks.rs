@@ -0,0 +1,112 @@
+use bevy::{
+    prelude::*,
+    render::{
+        draw::{DrawContext, DrawError, DrawHook, DrawHooks},
+        mesh::shape,
+        pipeline::{DynamicBinding, PipelineDescriptor, PipelineSpecialization, RenderPipeline},
+        shader::{ShaderStage, ShaderStages},
+    },
+};
+
+/// This example illustrates how to bind extra data for each draw of an entity, here a highlight color per cube
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(pulse_system.system())
+        .run();
+}
+
+const VERTEX_SHADER: &str = r#"
+#version 450
+layout(location = 0) in vec3 Vertex_Position;
+layout(set = 0, binding = 0) uniform Camera {
+    mat4 ViewProj;
+};
+layout(set = 1, binding = 0) uniform Transform {
+    mat4 Model;
+};
+void main() {
+    gl_Position = ViewProj * Model * vec4(Vertex_Position, 1.0);
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"
+#version 450
+layout(location = 0) out vec4 o_Target;
+layout(set = 2, binding = 0) uniform Highlight {
+    vec4 color;
+};
+void main() {
+    o_Target = color;
+}
+"#;
+
+/// Binds the highlight color to set 2 right before the cube is drawn
+struct Highlight {
+    color: Color,
+    phase: f32,
+}
+
+impl DrawHook for Highlight {
+    fn draw(&mut self, draw: &mut Draw, context: &mut DrawContext) -> Result<(), DrawError> {
+        context.set_uniforms(draw, 2, &[&self.color])
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut pipelines: ResMut<Assets<PipelineDescriptor>>,
+    mut shaders: ResMut<Assets<Shader>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let pipeline_handle = pipelines.add(PipelineDescriptor::default_config(ShaderStages {
+        vertex: shaders.add(Shader::from_glsl(ShaderStage::Vertex, VERTEX_SHADER)),
+        fragment: Some(shaders.add(Shader::from_glsl(ShaderStage::Fragment, FRAGMENT_SHADER))),
+    }));
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+
+    for i in 0..3 {
+        commands
+            .spawn(MeshComponents {
+                mesh: cube.clone(),
+                render_pipelines: RenderPipelines::from_pipelines(vec![
+                    RenderPipeline::specialized(
+                        pipeline_handle.clone(),
+                        PipelineSpecialization {
+                            dynamic_bindings: vec![
+                                // Transform
+                                DynamicBinding {
+                                    bind_group: 1,
+                                    binding: 0,
+                                },
+                            ],
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+                transform: Transform::from_translation(Vec3::new(i as f32 * 1.5 - 1.5, 0.0, 0.0)),
+                ..Default::default()
+            })
+            .with(DrawHooks::new(Highlight {
+                color: Color::WHITE,
+                phase: i as f32,
+            }));
+    }
+
+    commands.spawn(Camera3dComponents {
+        transform: Transform::from_translation(Vec3::new(0.0, 3.0, -6.0))
+            .looking_at(Vec3::default(), Vec3::unit_y()),
+        ..Default::default()
+    });
+}
+
+/// Changes the data the hooks bind
+fn pulse_system(time: Res<Time>, mut query: Query<&mut DrawHooks>) {
+    for mut draw_hooks in query.iter_mut() {
+        let highlight = draw_hooks.get_mut::<Highlight>().unwrap();
+        let brightness =
+            (time.seconds_since_startup as f32 * 2.0 + highlight.phase).sin() * 0.5 + 0.5;
+        highlight.color = Color::rgb(1.0, brightness, brightness * 0.5);
+    }
+}
@@ -0,0 +1,61 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::shape,
+        pipeline::{DynamicBinding, PipelineDescriptor, PipelineSpecialization, RenderPipeline},
+        shader::ShaderStages,
+    },
+    ui::ShaderErrorOverlayPlugin,
+};
+
+/// This example loads its shaders from `assets/shaders` and compiles them again when they change. Edit `hot.frag`
+/// while it runs: the cube's color updates, and if the shader doesn't compile, the cube keeps its previous shader
+/// and the error is shown on screen until it is fixed.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ShaderErrorOverlayPlugin::default())
+        .add_startup_system(setup.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pipelines: ResMut<Assets<PipelineDescriptor>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    // reload assets, including shaders, when their files change
+    asset_server.watch_for_changes().unwrap();
+
+    let pipeline_handle = pipelines.add(PipelineDescriptor::default_config(ShaderStages {
+        vertex: asset_server.load("shaders/hot.vert"),
+        fragment: Some(asset_server.load("shaders/hot.frag")),
+    }));
+
+    commands
+        .spawn(MeshComponents {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::specialized(
+                pipeline_handle,
+                PipelineSpecialization {
+                    dynamic_bindings: vec![
+                        // Transform
+                        DynamicBinding {
+                            bind_group: 1,
+                            binding: 0,
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        })
+        .spawn(Camera3dComponents {
+            transform: Transform::from_translation(Vec3::new(3.0, 5.0, -8.0))
+                .looking_at(Vec3::default(), Vec3::unit_y()),
+            ..Default::default()
+        })
+        // the error overlay is drawn by the ui camera
+        .spawn(UiCameraComponents::default());
+}
@@ -65,8 +65,8 @@ fn text_update_system(mut state: ResMut<State>, time: Res<Time>, mut query: Quer
     for mut text in query.iter_mut() {
         state.timer.tick(time.delta_seconds);
         let c = rand::random::<u8>() as char;
-        if !text.value.contains(c) && state.timer.finished {
-            text.value = format!("{}{}", text.value, c);
+        if !text.sections[0].value.contains(c) && state.timer.finished {
+            text.sections[0].value = format!("{}{}", text.sections[0].value, c);
             state.timer.reset();
         }
     }
@@ -82,14 +82,15 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut state: ResM
                 size: Size::new(Val::Px(250.0), Val::Px(60.0)),
                 ..Default::default()
             },
-            text: Text {
-                value: "a".to_string(),
-                font: font_handle,
-                style: TextStyle {
+            text: Text::with_section(
+                "a",
+                font_handle,
+                TextStyle {
                     font_size: 60.0,
                     color: Color::WHITE,
                 },
-            },
+                Default::default(),
+            ),
             ..Default::default()
         });
 }
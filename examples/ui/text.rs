@@ -20,7 +20,7 @@ fn text_update_system(diagnostics: Res<Diagnostics>, mut query: Query<(&mut Text
     for (mut text, _tag) in query.iter_mut() {
         if let Some(fps) = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS) {
             if let Some(average) = fps.average() {
-                text.value = format!("FPS: {:.2}", average);
+                text.sections[0].value = format!("FPS: {:.2}", average);
             }
         }
     }
@@ -36,14 +36,15 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 align_self: AlignSelf::FlexEnd,
                 ..Default::default()
             },
-            text: Text {
-                value: "FPS:".to_string(),
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                style: TextStyle {
+            text: Text::with_section(
+                "FPS:",
+                asset_server.load("fonts/FiraSans-Bold.ttf"),
+                TextStyle {
                     font_size: 60.0,
                     color: Color::WHITE,
                 },
-            },
+                Default::default(),
+            ),
             ..Default::default()
         })
         .with(FpsText);
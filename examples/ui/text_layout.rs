@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+/// This example illustrates text wrapping, alignment, and text made of multiple sections with different fonts, sizes
+/// and colors.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let bold = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let mono = asset_server.load("fonts/FiraMono-Medium.ttf");
+    commands
+        .spawn(UiCameraComponents::default())
+        // wrapped and centered text
+        .spawn(TextComponents {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(20.0),
+                    top: Val::Px(20.0),
+                    ..Default::default()
+                },
+                // text is wrapped at the node's width
+                size: Size::new(Val::Px(300.0), Val::Auto),
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "This text is wrapped at the width of its node, and each line is centered.",
+                bold.clone(),
+                TextStyle {
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                },
+                TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+            ),
+            ..Default::default()
+        })
+        // a rich message made of several sections
+        .spawn(TextComponents {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(20.0),
+                    bottom: Val::Px(20.0),
+                    ..Default::default()
+                },
+                max_size: Size::new(Val::Px(400.0), Val::Auto),
+                ..Default::default()
+            },
+            text: Text {
+                sections: vec![
+                    TextSection {
+                        value: "You found ".to_string(),
+                        font: bold.clone(),
+                        style: TextStyle {
+                            font_size: 40.0,
+                            color: Color::WHITE,
+                        },
+                    },
+                    TextSection {
+                        value: "the_sword".to_string(),
+                        font: mono,
+                        style: TextStyle {
+                            font_size: 30.0,
+                            color: Color::rgb(1.0, 0.8, 0.0),
+                        },
+                    },
+                    TextSection {
+                        value: "!\nIt looks sharp.".to_string(),
+                        font: bold,
+                        style: TextStyle {
+                            font_size: 40.0,
+                            color: Color::rgb(0.6, 0.6, 0.6),
+                        },
+                    },
+                ],
+                alignment: TextAlignment::default(),
+            },
+            ..Default::default()
+        });
+}
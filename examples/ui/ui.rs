@@ -57,14 +57,15 @@ fn setup(
                                     margin: Rect::all(Val::Px(5.0)),
                                     ..Default::default()
                                 },
-                                text: Text {
-                                    value: "Text Example".to_string(),
-                                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                    style: TextStyle {
+                                text: Text::with_section(
+                                    "Text Example",
+                                    asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                    TextStyle {
                                         font_size: 30.0,
                                         color: Color::WHITE,
                                     },
-                                },
+                                    Default::default(),
+                                ),
                                 ..Default::default()
                             });
                         });
@@ -114,7 +115,7 @@ fn setup(
                         ..Default::default()
                     },
                     material: materials.add(Color::NONE.into()),
-                    draw: Draw {
+                    visible: Visible {
                         is_transparent: true,
                         ..Default::default()
                     },
@@ -187,7 +188,7 @@ fn setup(
                                         ..Default::default()
                                     },
                                     material: materials.add(Color::rgba(1.0, 0.9, 0.9, 0.4).into()),
-                                    draw: Draw {
+                                    visible: Visible {
                                         is_transparent: true,
                                         ..Default::default()
                                     },
@@ -205,7 +206,7 @@ fn setup(
                         ..Default::default()
                     },
                     material: materials.add(Color::NONE.into()),
-                    draw: Draw {
+                    visible: Visible {
                         is_transparent: true,
                         ..Default::default()
                     },
@@ -220,7 +221,7 @@ fn setup(
                         },
                         material: materials
                             .add(asset_server.load("branding/bevy_logo_dark_big.png").into()),
-                        draw: Draw {
+                        visible: Visible {
                             is_transparent: true,
                             ..Default::default()
                         },
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+/// This example illustrates UI nodes that follow entities in a 3D scene, like health bars above characters.
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(move_system.system())
+        .run();
+}
+
+struct Moving;
+
+fn move_system(time: Res<Time>, mut query: Query<With<Moving, &mut Transform>>) {
+    for mut transform in query.iter_mut() {
+        let t = time.seconds_since_startup as f32;
+        transform.translation = Vec3::new(3.0 * t.cos(), 0.5, 3.0 * t.sin());
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands
+        .spawn(PbrComponents {
+            mesh: meshes.add(Mesh::from(shape::Plane { size: 10.0 })),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        .spawn(LightComponents {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dComponents {
+            transform: Transform::from_translation(Vec3::new(-3.0, 5.0, 8.0))
+                .looking_at(Vec3::default(), Vec3::unit_y()),
+            ..Default::default()
+        })
+        .spawn(UiCameraComponents::default())
+        // a cube that moves in a circle
+        .spawn(PbrComponents {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            ..Default::default()
+        })
+        .with(Moving);
+
+    let cube = commands.current_entity().unwrap();
+    commands
+        // a health bar that follows the cube
+        .spawn(NodeComponents {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Px(60.0), Val::Px(10.0)),
+                ..Default::default()
+            },
+            material: color_materials.add(Color::rgb(0.2, 0.2, 0.2).into()),
+            ..Default::default()
+        })
+        .with(WorldSpaceNode {
+            // place it above the cube
+            world_offset: Vec3::new(0.0, 1.0, 0.0),
+            ..WorldSpaceNode::new(cube)
+        })
+        .with_children(|parent| {
+            parent.spawn(NodeComponents {
+                style: Style {
+                    size: Size::new(Val::Percent(70.0), Val::Percent(100.0)),
+                    ..Default::default()
+                },
+                material: color_materials.add(Color::rgb(0.8, 0.1, 0.1).into()),
+                ..Default::default()
+            });
+        });
+}
@@ -0,0 +1,61 @@
+use bevy::{
+    app::AppExit,
+    prelude::*,
+    window::{WindowCloseRequested, WindowPlugin},
+};
+
+/// This example intercepts window close requests and asks for confirmation before exiting
+fn main() {
+    App::build()
+        .add_plugins_with(DefaultPlugins, |group| {
+            group.set(WindowPlugin {
+                exit_on_close: false,
+                ..Default::default()
+            })
+        })
+        .init_resource::<PromptState>()
+        .add_system(close_requested_system.system())
+        .add_system(confirm_exit_system.system())
+        .run();
+}
+
+#[derive(Default)]
+struct PromptState {
+    close_requested_event_reader: EventReader<WindowCloseRequested>,
+    prompt_open: bool,
+}
+
+/// This system opens the prompt instead of exiting when a window's close button is pressed
+fn close_requested_system(
+    mut state: ResMut<PromptState>,
+    close_requested_events: Res<Events<WindowCloseRequested>>,
+) {
+    if state
+        .close_requested_event_reader
+        .iter(&close_requested_events)
+        .next()
+        .is_some()
+        && !state.prompt_open
+    {
+        state.prompt_open = true;
+        println!("Save before quitting? Press Y to quit or N to keep playing");
+    }
+}
+
+/// This system exits the app once the user confirms
+fn confirm_exit_system(
+    mut state: ResMut<PromptState>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut app_exit_events: ResMut<Events<AppExit>>,
+) {
+    if !state.prompt_open {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Y) {
+        println!("Saved. Goodbye!");
+        app_exit_events.send(AppExit);
+    } else if keyboard_input.just_pressed(KeyCode::N) {
+        state.prompt_open = false;
+    }
+}
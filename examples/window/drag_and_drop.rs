@@ -0,0 +1,20 @@
+use bevy::{prelude::*, window::FileDragAndDrop};
+
+/// This example prints out the paths of files that are dragged onto the window
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_system(file_drag_and_drop_system.system())
+        .run();
+}
+
+#[derive(Default)]
+struct State {
+    event_reader: EventReader<FileDragAndDrop>,
+}
+
+fn file_drag_and_drop_system(mut state: Local<State>, events: Res<Events<FileDragAndDrop>>) {
+    for event in state.event_reader.iter(&events) {
+        println!("{:?}", event);
+    }
+}
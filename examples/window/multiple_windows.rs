@@ -35,8 +35,8 @@ fn setup(
     create_window_events.send(CreateWindow {
         id: window_id,
         descriptor: WindowDescriptor {
-            width: 800,
-            height: 600,
+            width: 800.,
+            height: 600.,
             vsync: false,
             title: "second window".to_string(),
             ..Default::default()
@@ -136,6 +136,7 @@ fn setup(
                     dimension: TextureDimension::D2,
                     format: TextureFormat::default(),
                     usage: TextureUsage::OUTPUT_ATTACHMENT,
+                    ..Default::default()
                 },
             ),
         );
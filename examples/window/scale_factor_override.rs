@@ -0,0 +1,63 @@
+use bevy::{prelude::*, window::WindowScaleFactorChanged};
+
+/// This example shows how the scale factor of a window affects its logical size. Press Enter to toggle between the
+/// platform's scale factor and an override of 1.0, and the up/down arrows to change the override.
+fn main() {
+    App::build()
+        .add_resource(WindowDescriptor {
+            width: 500.,
+            height: 300.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_system(toggle_override.system())
+        .add_system(change_scale_factor.system())
+        .add_system(print_scale_factor_changes.system())
+        .run();
+}
+
+/// This system toggles the scale factor override when Enter is pressed
+fn toggle_override(input: Res<Input<KeyCode>>, mut windows: ResMut<Windows>) {
+    let window = windows.get_primary_mut().unwrap();
+    if input.just_pressed(KeyCode::Return) {
+        window.set_scale_factor_override(match window.scale_factor_override() {
+            Some(_) => None,
+            None => Some(1.0),
+        });
+    }
+}
+
+/// This system changes the scale factor override when up or down is pressed
+fn change_scale_factor(input: Res<Input<KeyCode>>, mut windows: ResMut<Windows>) {
+    let window = windows.get_primary_mut().unwrap();
+    if let Some(scale_factor) = window.scale_factor_override() {
+        if input.just_pressed(KeyCode::Up) {
+            window.set_scale_factor_override(Some(scale_factor + 0.5));
+        } else if input.just_pressed(KeyCode::Down) {
+            window.set_scale_factor_override(Some((scale_factor - 0.5).max(0.5)));
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    event_reader: EventReader<WindowScaleFactorChanged>,
+}
+
+fn print_scale_factor_changes(
+    mut state: Local<State>,
+    events: Res<Events<WindowScaleFactorChanged>>,
+    windows: Res<Windows>,
+) {
+    for event in state.event_reader.iter(&events) {
+        let window = windows.get(event.id).unwrap();
+        println!(
+            "scale factor: {} logical size: {}x{} physical size: {}x{}",
+            event.scale_factor,
+            window.width(),
+            window.height(),
+            window.physical_width(),
+            window.physical_height()
+        );
+    }
+}
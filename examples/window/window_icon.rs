@@ -0,0 +1,105 @@
+use bevy::{prelude::*, window::CursorIcon};
+
+/// This example sets the window icon from a texture and cycles through the system cursor icons when the mouse is clicked.
+/// Right clicking swaps the system cursor for a custom cursor image.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .init_resource::<IconState>()
+        .add_startup_system(setup.system())
+        .add_system(window_icon_system.system())
+        .add_system(cursor_icon_system.system())
+        .add_system(cursor_image_system.system())
+        .run();
+}
+
+#[derive(Default)]
+struct IconState {
+    icon: Handle<Texture>,
+    icon_set: bool,
+    cursor_index: usize,
+    cursor_image: Option<Entity>,
+}
+
+const CURSOR_ICONS: &[CursorIcon] = &[
+    CursorIcon::Default,
+    CursorIcon::Hand,
+    CursorIcon::Crosshair,
+    CursorIcon::Text,
+    CursorIcon::Wait,
+    CursorIcon::Grab,
+    CursorIcon::NotAllowed,
+];
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut state: ResMut<IconState>,
+) {
+    state.icon = asset_server.load("branding/icon.png");
+    commands
+        .spawn(UiCameraComponents::default())
+        // the cursor image is hidden until the right mouse button is clicked
+        .spawn(ImageComponents {
+            style: Style {
+                size: Size::new(Val::Px(32.0), Val::Px(32.0)),
+                position_type: PositionType::Absolute,
+                ..Default::default()
+            },
+            material: materials.add(state.icon.clone().into()),
+            visible: Visible {
+                is_visible: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        // the click point is the top left corner of the icon
+        .with(CursorImage::new(Vec2::zero()))
+        .with(ZIndex::Global(i32::MAX));
+    state.cursor_image = commands.current_entity();
+}
+
+fn window_icon_system(
+    mut state: ResMut<IconState>,
+    textures: Res<Assets<Texture>>,
+    mut windows: ResMut<Windows>,
+) {
+    if state.icon_set {
+        return;
+    }
+
+    // the icon can only be set once the texture has finished loading
+    if let Some(texture) = textures.get(&state.icon) {
+        let window = windows.get_primary_mut().unwrap();
+        window.set_icon(texture.to_window_icon());
+        state.icon_set = true;
+    }
+}
+
+fn cursor_icon_system(
+    mut state: ResMut<IconState>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    mut windows: ResMut<Windows>,
+) {
+    if mouse_button_input.just_pressed(MouseButton::Left) {
+        state.cursor_index = (state.cursor_index + 1) % CURSOR_ICONS.len();
+        let window = windows.get_primary_mut().unwrap();
+        window.set_cursor_icon(CURSOR_ICONS[state.cursor_index]);
+        println!("cursor icon: {:?}", CURSOR_ICONS[state.cursor_index]);
+    }
+}
+
+fn cursor_image_system(
+    state: Res<IconState>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    mut visible_query: Query<&mut Visible>,
+) {
+    if mouse_button_input.just_pressed(MouseButton::Right) {
+        if let Some(cursor_image) = state.cursor_image {
+            let mut visible = visible_query.get_mut(cursor_image).unwrap();
+            visible.is_visible = !visible.is_visible;
+            println!("cursor image shown: {}", visible.is_visible);
+        }
+    }
+}
@@ -45,6 +45,11 @@ pub mod prelude;
 mod default_plugins;
 pub use default_plugins::*;
 
+pub mod animation {
+    //! Keyframed joint animations.
+    pub use bevy_animation::*;
+}
+
 pub mod app {
     //! Build bevy apps, create plugins, and read events.
     pub use bevy_app::*;
@@ -75,6 +80,11 @@ pub mod input {
     pub use bevy_input::*;
 }
 
+pub mod log {
+    //! Logging with per-module level filters.
+    pub use bevy_log::*;
+}
+
 pub mod math {
     //! Math types (Vec3, Mat4, Quat, etc) and helpers.
     pub use bevy_math::*;
@@ -126,6 +136,12 @@ pub mod gltf {
     pub use bevy_gltf::*;
 }
 
+#[cfg(feature = "bevy_net")]
+pub mod net {
+    //! Connections between apps with reliable and unreliable message channels.
+    pub use bevy_net::*;
+}
+
 #[cfg(feature = "bevy_pbr")]
 pub mod pbr {
     //! Physically based rendering.
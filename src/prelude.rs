@@ -1,12 +1,16 @@
 pub use crate::{
-    app::prelude::*, asset::prelude::*, core::prelude::*, ecs::prelude::*, input::prelude::*,
-    math::prelude::*, property::prelude::*, scene::prelude::*, transform::prelude::*,
-    type_registry::RegisterType, window::prelude::*, DefaultPlugins, MinimalPlugins,
+    animation::prelude::*, app::prelude::*, asset::prelude::*, core::prelude::*, ecs::prelude::*,
+    input::prelude::*, math::prelude::*, property::prelude::*, scene::prelude::*,
+    tasks::prelude::*, transform::prelude::*, type_registry::RegisterType, window::prelude::*,
+    DefaultPlugins, MinimalPlugins,
 };
 
 #[cfg(feature = "bevy_audio")]
 pub use crate::audio::prelude::*;
 
+#[cfg(feature = "bevy_net")]
+pub use crate::net::prelude::*;
+
 #[cfg(feature = "bevy_pbr")]
 pub use crate::pbr::prelude::*;
 